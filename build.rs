@@ -0,0 +1,92 @@
+//! Build script for VenvCleaner
+//!
+//! Injects the git commit and build date shown by the about windows and
+//! `venv_cleaner info`, and generates the list of third-party crates with
+//! their licenses from `Cargo.lock` and the manifests in the cargo registry.
+//! Builds outside a git checkout or without the registry sources report the
+//! commit or a crate's license as `unknown` instead of failing.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=Cargo.lock");
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs");
+
+    let commit = git(&["rev-parse", "--short=10", "HEAD"]).unwrap_or_else(|| "unknown".to_string());
+    let dirty = git(&["status", "--porcelain", "--untracked-files=no"]).is_some_and(|status| !status.is_empty());
+    println!("cargo:rustc-env=VENV_CLEANER_COMMIT={}{}", commit, if dirty && commit != "unknown" { "-dirty" } else { "" });
+    // SOURCE_DATE_EPOCH keeps reproducible builds reproducible
+    let date = env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| git(&["log", "-1", "--format=%cs", &format!("--until=@{}", epoch)]))
+        .or_else(|| git(&["log", "-1", "--format=%cs"]))
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=VENV_CLEANER_BUILD_DATE={}", date);
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("cargo sets OUT_DIR"));
+    let lock = fs::read_to_string("Cargo.lock").unwrap_or_default();
+    let package = env::var("CARGO_PKG_NAME").unwrap_or_default();
+    let sources = registry_sources();
+    let mut lines = Vec::new();
+    for (name, version) in locked_packages(&lock) {
+        if name == package {
+            continue;
+        }
+        let license = sources
+            .iter()
+            .find_map(|dir| manifest_license(&dir.join(format!("{}-{}", name, version)).join("Cargo.toml")))
+            .unwrap_or_else(|| "unknown".to_string());
+        lines.push(format!("{}\t{}\t{}", name, version, license));
+    }
+    fs::write(out_dir.join("third_party_licenses.tsv"), lines.join("\n") + "\n").expect("OUT_DIR is writable");
+}
+
+/// Run git in the crate directory, returning its trimmed output on success
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Get the name and version of every package in a lock file
+fn locked_packages(lock: &str) -> Vec<(String, String)> {
+    let mut packages = Vec::new();
+    let mut name = None;
+    for line in lock.lines() {
+        if line == "[[package]]" {
+            name = None;
+        } else if let Some(value) = line.strip_prefix("name = ") {
+            name = Some(value.trim_matches('"').to_string());
+        } else if let (Some(value), Some(package)) = (line.strip_prefix("version = "), name.take()) {
+            packages.push((package, value.trim_matches('"').to_string()));
+        }
+    }
+    packages
+}
+
+/// Get the directories holding the unpacked sources of registry crates
+fn registry_sources() -> Vec<PathBuf> {
+    let cargo_home = env::var_os("CARGO_HOME").map(PathBuf::from).or_else(|| {
+        env::var_os("HOME").or_else(|| env::var_os("USERPROFILE")).map(|home| Path::new(&home).join(".cargo"))
+    });
+    let Some(src) = cargo_home.map(|home| home.join("registry").join("src")) else {
+        return Vec::new();
+    };
+    fs::read_dir(src)
+        .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+        .unwrap_or_default()
+}
+
+/// Read the `license` (or `license-file`) of a crate manifest
+fn manifest_license(manifest: &Path) -> Option<String> {
+    let text = fs::read_to_string(manifest).ok()?;
+    let value = |key: &str| {
+        text.lines()
+            .find_map(|line| line.strip_prefix(key)?.trim_start().strip_prefix('='))
+            .map(|value| value.trim().trim_matches('"').to_string())
+    };
+    value("license ").or_else(|| value("license=")).or_else(|| value("license-file").map(|file| format!("see {}", file)))
+}
@@ -0,0 +1,212 @@
+//! `advise` subcommand for VenvCleaner
+//!
+//! This module walks through the cleanup recommendations for the .venv
+//! directories found one category at a time ("2 old .venv directories ...
+//! (34.00 GB) — clean them? [y/N]") and carries out each bulk action the
+//! user approves, so nobody has to learn the filters to tidy up. Declined
+//! categories print the command that acts on them later.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use clap::ArgMatches;
+use colored::*;
+use tracing::info;
+
+use crate::core::{recommendations, ByteSize, Config, Recommendation, RecommendationKind, VenvCleaner, VenvCleanerError, VenvInfo, Result};
+use super::resolve_base_directory;
+
+/// Number of .venv directories listed before a question
+const LISTED_VENVS: usize = 10;
+
+/// Handler for the `advise` subcommand
+pub struct AdviseCommand {
+    /// The core VenvCleaner instance
+    cleaner: VenvCleaner,
+}
+
+impl AdviseCommand {
+    /// Create a new AdviseCommand from the `advise` subcommand arguments
+    ///
+    /// # Arguments
+    /// * `matches` - Parsed arguments of the `advise` subcommand
+    ///
+    /// # Returns
+    /// A new AdviseCommand instance or an error
+    pub fn new(matches: &ArgMatches) -> Result<Self> {
+        let base_directory = resolve_base_directory(matches)?;
+        let recursive = matches.get_flag("recursive");
+        let dry_run = matches.get_flag("dry-run");
+        let verbosity = matches.get_count("verbose");
+
+        // Every bulk action is confirmed here, so the cleaner itself never prompts
+        // but still honours the protected paths of the config file
+        let config = Config::load()?;
+        let cleaner = VenvCleaner::new(base_directory, recursive, false, dry_run, verbosity)
+            .with_policy(config.policy)
+            .with_delete_hooks(config.hooks);
+
+        Ok(Self { cleaner })
+    }
+
+    /// Execute the advise subcommand, reading the answers from stdin
+    ///
+    /// # Returns
+    /// The bytes reclaimed (or that would be, in dry-run mode)
+    pub fn execute(&self) -> Result<u64> {
+        let stdin = io::stdin();
+        self.run(&mut stdin.lock())
+    }
+
+    /// Walk through the recommendations, reading each answer from `input`
+    ///
+    /// # Arguments
+    /// * `input` - Where the answers come from; end of input declines
+    ///
+    /// # Returns
+    /// The bytes reclaimed (or that would be, in dry-run mode)
+    pub fn run(&self, input: &mut impl BufRead) -> Result<u64> {
+        info!("Executing advise subcommand");
+
+        let venv_dirs = match self.cleaner.find_venv_directories() {
+            Ok(venv_dirs) => venv_dirs,
+            Err(VenvCleanerError::NoVenvFound) => {
+                println!("{}", "No .venv directories found.".yellow());
+                return Ok(0);
+            }
+            Err(e) => return Err(e),
+        };
+
+        let advice = recommendations::recommend(&venv_dirs, &self.cleaner.roots());
+        if advice.is_empty() {
+            println!("✅ Nothing to recommend for the {} .venv directories found", venv_dirs.len());
+            return Ok(0);
+        }
+
+        let by_path: HashMap<&Path, &VenvInfo> = venv_dirs.iter().map(|venv| (venv.path(), venv)).collect();
+        let mut total_reclaimed = 0u64;
+        for recommendation in &advice {
+            println!(
+                "\n{} {} ({})",
+                recommendation.kind().icon(),
+                recommendation.title().bold(),
+                ByteSize::new(recommendation.bytes()).to_string().cyan()
+            );
+            for path in recommendation.venvs().iter().take(LISTED_VENVS) {
+                println!("   • {}", path.display());
+            }
+            if recommendation.venvs().len() > LISTED_VENVS {
+                println!("   … and {} more", recommendation.venvs().len() - LISTED_VENVS);
+            }
+
+            if recommendation.kind().is_advice() {
+                let what = if recommendation.kind() == RecommendationKind::Large { "what takes the space" } else { "the shared packages" };
+                println!("   💡 To see {}: {}", what, recommendation.command().green());
+                if let Some(link) = recommendation.kind().doc_link() {
+                    println!("   📖 {}", link.underline());
+                }
+                continue;
+            }
+
+            let question = if recommendation.kind().deletes() { "Clean them?" } else { "Slim them?" };
+            if !Self::ask(input, question)? {
+                println!("   Skipped. To do it later: {}", recommendation.command().green());
+                continue;
+            }
+            let venvs: Vec<&VenvInfo> = recommendation
+                .venvs()
+                .iter()
+                .filter_map(|path| by_path.get(path.as_path()).copied())
+                .collect();
+            total_reclaimed += self.act(recommendation, &venvs);
+        }
+
+        let verb = if self.cleaner.is_dry_run() { "Would reclaim" } else { "Reclaimed" };
+        println!("\n{} {} {}", "Summary:".bold(), verb, ByteSize::new(total_reclaimed).to_string().cyan());
+        Ok(total_reclaimed)
+    }
+
+    /// Carry out an approved recommendation
+    ///
+    /// # Returns
+    /// The bytes reclaimed
+    fn act(&self, recommendation: &Recommendation, venvs: &[&VenvInfo]) -> u64 {
+        let mut reclaimed = 0u64;
+        for venv in venvs {
+            let result = if recommendation.kind().deletes() {
+                self.cleaner.delete_venv_directory(venv).map(|_| venv.size().bytes())
+            } else {
+                self.cleaner.slim_venv_directory(venv, false).map(|report| report.bytes_saved())
+            };
+            match result {
+                Ok(bytes) => reclaimed += bytes,
+                Err(e) => println!("   ❌ {}: {}", venv.location(), e.to_string().red()),
+            }
+        }
+        println!("   ✅ {} reclaimed", ByteSize::new(reclaimed).to_string().green());
+        reclaimed
+    }
+
+    /// Ask a yes/no question, defaulting to no
+    fn ask(input: &mut impl BufRead, question: &str) -> Result<bool> {
+        print!("   {} [y/N] ", question);
+        io::stdout().flush()?;
+
+        let mut answer = String::new();
+        input.read_line(&mut answer)?;
+        Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Command;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_test_command() -> Command {
+        Command::new("advise")
+            .arg(clap::Arg::new("directory").index(1))
+            .arg(clap::Arg::new("recursive").short('r').action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("dry-run").long("dry-run").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("verbose").short('v').action(clap::ArgAction::Count))
+    }
+
+    /// Create a .venv without a pyvenv.cfg, which is recommended for deletion as broken
+    fn create_broken_venv(root: &Path) -> std::path::PathBuf {
+        let venv = root.join("project").join(".venv");
+        fs::create_dir_all(venv.join("bin")).unwrap();
+        fs::create_dir_all(venv.join("lib")).unwrap();
+        fs::write(venv.join("bin").join("activate"), vec![0u8; 32]).unwrap();
+        venv
+    }
+
+    #[test]
+    fn test_advise_cleans_approved_category() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = create_broken_venv(temp_dir.path());
+
+        let matches = create_test_command()
+            .try_get_matches_from(["advise", "-r", temp_dir.path().to_str().unwrap()])
+            .unwrap();
+        let reclaimed = AdviseCommand::new(&matches).unwrap().run(&mut "y\n".as_bytes()).unwrap();
+        assert!(reclaimed > 0);
+        assert!(!venv.exists());
+    }
+
+    #[test]
+    fn test_advise_keeps_declined_category() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = create_broken_venv(temp_dir.path());
+
+        let matches = create_test_command()
+            .try_get_matches_from(["advise", "-r", temp_dir.path().to_str().unwrap()])
+            .unwrap();
+        let command = AdviseCommand::new(&matches).unwrap();
+        // An empty answer and the end of input both decline
+        assert_eq!(command.run(&mut "\n".as_bytes()).unwrap(), 0);
+        assert_eq!(command.run(&mut "".as_bytes()).unwrap(), 0);
+        assert!(venv.exists());
+    }
+}
@@ -0,0 +1,263 @@
+//! `apply-plan` subcommand for VenvCleaner
+//!
+//! This module carries out a deletion plan made while browsing a saved scan
+//! with `--from-index`. It runs on the scanned machine itself: every planned
+//! .venv is looked at again and deleted after one confirmation, while those
+//! already gone or no longer a virtual environment are skipped. A .venv now
+//! holding user data is confirmed again on its own.
+
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+use chrono::Utc;
+use clap::ArgMatches;
+use colored::*;
+use tracing::info;
+
+use crate::core::{BatchOptions, ByteSize, Config, ConfirmationRequest, DeletionPlan, FileUtils, OwnerFilter, VenvCleaner, VenvCleanerError, VenvInfo, Result};
+use super::resolve_delete_mode;
+
+/// Handler for the `apply-plan` subcommand
+pub struct ApplyPlanCommand {
+    /// The plan file
+    plan: PathBuf,
+    /// The core VenvCleaner instance
+    cleaner: VenvCleaner,
+    /// Whether .venv directories holding user data are deleted without their own confirmation
+    include_user_data: bool,
+}
+
+impl ApplyPlanCommand {
+    /// Create a new ApplyPlanCommand from the `apply-plan` subcommand arguments
+    ///
+    /// # Arguments
+    /// * `matches` - Parsed arguments of the `apply-plan` subcommand
+    ///
+    /// # Returns
+    /// A new ApplyPlanCommand instance or an error
+    pub fn new(matches: &ArgMatches) -> Result<Self> {
+        let plan = matches
+            .get_one::<String>("plan")
+            .map(PathBuf::from)
+            .ok_or_else(|| VenvCleanerError::InvalidArgument("A plan file is required".to_string()))?;
+
+        // The plan is confirmed as a whole here, but the policy and hooks still apply
+        let config = Config::load()?;
+        config.policy.check_force(matches.get_flag("force"))?;
+        let cleaner = VenvCleaner::new(
+            std::env::current_dir()?,
+            false,
+            matches.get_flag("force"),
+            matches.get_flag("dry-run"),
+            matches.get_count("verbose"),
+        )
+        .with_policy(config.policy)
+        .with_delete_mode(resolve_delete_mode(matches, config.delete_mode.as_deref())?)
+        .with_owner_filter(if matches.get_flag("all-users") { OwnerFilter::AllUsers } else { OwnerFilter::Mine })
+        .with_delete_hooks(config.hooks);
+
+        Ok(Self { plan, cleaner, include_user_data: matches.get_flag("include-user-data") })
+    }
+
+    /// Execute the apply-plan subcommand, reading the confirmation from stdin
+    ///
+    /// # Returns
+    /// The bytes freed (or that would be, in dry-run mode)
+    pub fn execute(&self) -> Result<u64> {
+        let stdin = io::stdin();
+        self.run(&mut stdin.lock())
+    }
+
+    /// Carry out the plan, reading the confirmation from `input` unless forced
+    ///
+    /// # Arguments
+    /// * `input` - Where the answer comes from; end of input declines
+    ///
+    /// # Returns
+    /// The bytes freed (or that would be, in dry-run mode)
+    pub fn run(&self, input: &mut impl BufRead) -> Result<u64> {
+        info!("Executing apply-plan subcommand");
+
+        let planned = DeletionPlan::load(&self.plan)?;
+        let mut venvs = Vec::new();
+        for entry in &planned {
+            if !FileUtils::is_valid_venv_directory(&entry.path) {
+                println!("⏭️  {} {}", entry.path.display(), "(gone or no longer a virtual environment)".dimmed());
+                continue;
+            }
+            // Sizes may have changed since the index was captured
+            let now = Utc::now();
+            let placeholder = VenvInfo::new(entry.path.clone(), ByteSize::new(entry.size_bytes), now, now);
+            match self.cleaner.recalculate_size(&placeholder) {
+                Ok(venv) => venvs.push(venv),
+                Err(e) => println!("❌ {}: {}", entry.path.display(), e.to_string().red()),
+            }
+        }
+
+        if venvs.is_empty() {
+            println!("{}", "Nothing in the plan is left to delete.".yellow());
+            return Ok(0);
+        }
+
+        let total: u64 = venvs.iter().map(|venv| venv.size().bytes()).sum();
+        println!("\n{}", format!("Planned in {}", self.plan.display()).bold());
+        for venv in &venvs {
+            println!("  🗑️  {}  {}", venv.path().display(), venv.size_formatted().dimmed());
+        }
+
+        let question = format!("Delete these {} .venv directories ({})?", venvs.len(), ByteSize::new(total));
+        if !self.cleaner.is_force_mode() && !self.cleaner.is_dry_run() && !Self::ask(input, &question)? {
+            println!("{}", "Nothing deleted.".dimmed());
+            return Ok(0);
+        }
+        let venvs = self.confirm_user_data(venvs, input)?;
+
+        let batch = self.cleaner.delete_many(&venvs, &BatchOptions::new(), |item| {
+            if let Err(e) = &item.result {
+                println!("❌ {}: {}", item.venv.path().display(), e.to_string().red());
+            }
+        });
+        let freed = batch.freed_bytes();
+
+        let verb = if self.cleaner.is_dry_run() { "would be freed" } else { "freed" };
+        println!("\n{} {} {}", "Summary:".bold(), ByteSize::new(freed).to_string().green(), verb);
+        Ok(freed)
+    }
+
+    /// Confirm each planned .venv holding user data on its own, as a cleanup does
+    ///
+    /// Force mode keeps them unless `--include-user-data` was given; dry runs
+    /// keep them all in the plan, since nothing is lost.
+    ///
+    /// # Returns
+    /// The .venv directories still to delete
+    fn confirm_user_data(&self, venvs: Vec<VenvInfo>, input: &mut impl BufRead) -> Result<Vec<VenvInfo>> {
+        let mut confirmed = Vec::with_capacity(venvs.len());
+        for venv in venvs {
+            if !venv.has_user_data() || self.include_user_data || self.cleaner.is_dry_run() {
+                confirmed.push(venv);
+                continue;
+            }
+            println!(
+                "\n📄 {} {}",
+                venv.path().display(),
+                format!("holds user data ({})", ByteSize::new(venv.user_data_bytes())).yellow()
+            );
+            if self.cleaner.is_force_mode() {
+                println!("⏭️  {}", "Skipped: contains user data (pass --include-user-data to delete it)".yellow());
+            } else if Self::ask(input, &ConfirmationRequest::DeleteUserData(&venv).question())? {
+                confirmed.push(venv);
+            } else {
+                println!("⏭️  {}", "Skipped (user data kept)".dimmed());
+            }
+        }
+        Ok(confirmed)
+    }
+
+    /// Ask a yes/no question, defaulting to no
+    fn ask(input: &mut impl BufRead, question: &str) -> Result<bool> {
+        print!("\n{} (y/N): ", question.bold());
+        io::stdout().flush()?;
+
+        let mut answer = String::new();
+        input.read_line(&mut answer)?;
+        Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_test_command() -> clap::Command {
+        clap::Command::new("apply-plan")
+            .arg(clap::Arg::new("plan").index(1))
+            .arg(clap::Arg::new("force").short('f').action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("dry-run").long("dry-run").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("trash").long("trash").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("permanent").long("permanent").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("all-users").long("all-users").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("include-user-data").long("include-user-data").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("verbose").short('v').action(clap::ArgAction::Count))
+    }
+
+    /// Write a plan for a real .venv and one that is already gone
+    fn write_plan(temp_dir: &TempDir) -> (PathBuf, PathBuf) {
+        let venv = temp_dir.path().join("app").join(".venv");
+        fs::create_dir_all(venv.join("bin")).unwrap();
+        fs::write(venv.join("pyvenv.cfg"), "home = /usr/bin\n").unwrap();
+        fs::write(venv.join("bin").join("python"), "").unwrap();
+
+        let plan = temp_dir.path().join("plan.json");
+        let entries = serde_json::json!({
+            "venvs": [
+                { "path": venv, "size_bytes": 1 },
+                { "path": temp_dir.path().join("gone").join(".venv"), "size_bytes": 1 },
+            ]
+        });
+        fs::write(&plan, entries.to_string()).unwrap();
+        (plan, venv)
+    }
+
+    #[test]
+    fn test_apply_plan_asks_before_deleting() {
+        let temp_dir = TempDir::new().unwrap();
+        let (plan, venv) = write_plan(&temp_dir);
+
+        let matches = create_test_command()
+            .try_get_matches_from(["apply-plan", plan.to_str().unwrap(), "--permanent"])
+            .unwrap();
+        let command = ApplyPlanCommand::new(&matches).unwrap();
+        assert_eq!(command.run(&mut "n\n".as_bytes()).unwrap(), 0);
+        assert!(venv.exists());
+
+        assert!(command.run(&mut "y\n".as_bytes()).unwrap() > 0);
+        assert!(!venv.exists());
+    }
+
+    #[test]
+    fn test_apply_plan_confirms_user_data_on_its_own() {
+        let temp_dir = TempDir::new().unwrap();
+        let (plan, venv) = write_plan(&temp_dir);
+        fs::write(venv.join("results.csv"), vec![0u8; 2 * 1024 * 1024]).unwrap();
+
+        let matches = create_test_command()
+            .try_get_matches_from(["apply-plan", plan.to_str().unwrap(), "--permanent"])
+            .unwrap();
+        let command = ApplyPlanCommand::new(&matches).unwrap();
+        assert_eq!(command.run(&mut "y\nn\n".as_bytes()).unwrap(), 0);
+        assert!(venv.exists());
+
+        let matches = create_test_command()
+            .try_get_matches_from(["apply-plan", plan.to_str().unwrap(), "--permanent", "-f"])
+            .unwrap();
+        assert_eq!(ApplyPlanCommand::new(&matches).unwrap().run(&mut "".as_bytes()).unwrap(), 0);
+        assert!(venv.exists());
+
+        assert!(command.run(&mut "y\ny\n".as_bytes()).unwrap() > 0);
+        assert!(!venv.exists());
+    }
+
+    #[test]
+    fn test_apply_plan_delete_mode_follows_the_flags() {
+        use crate::core::DeleteMode;
+
+        for (flag, expected) in [("--trash", DeleteMode::Trash), ("--permanent", DeleteMode::Permanent)] {
+            let matches = create_test_command().try_get_matches_from(["apply-plan", "plan.json", flag]).unwrap();
+            assert_eq!(ApplyPlanCommand::new(&matches).unwrap().cleaner.delete_mode(), expected);
+        }
+    }
+
+    #[test]
+    fn test_apply_plan_dry_run_keeps_everything() {
+        let temp_dir = TempDir::new().unwrap();
+        let (plan, venv) = write_plan(&temp_dir);
+
+        let matches = create_test_command()
+            .try_get_matches_from(["apply-plan", plan.to_str().unwrap(), "--dry-run"])
+            .unwrap();
+        assert!(ApplyPlanCommand::new(&matches).unwrap().run(&mut "".as_bytes()).unwrap() > 0);
+        assert!(venv.exists());
+    }
+}
@@ -0,0 +1,105 @@
+//! `audit` subcommand for VenvCleaner
+//!
+//! This module checks the hash-chained audit log written when the policy
+//! sets `audit_log`. `audit verify` recomputes every entry's hash and its
+//! link to the previous entry, and fails if any entry was edited, removed
+//! or reordered.
+
+use std::path::PathBuf;
+use clap::ArgMatches;
+use colored::*;
+use tracing::info;
+
+use crate::core::{AuditLog, AuditVerification, Config, VenvCleanerError, Result};
+
+/// Handler for the `audit` subcommand
+pub struct AuditCommand {
+    /// The log to check
+    log: AuditLog,
+}
+
+impl AuditCommand {
+    /// Create a new AuditCommand from the `audit verify` arguments
+    ///
+    /// # Arguments
+    /// * `matches` - Parsed arguments of the `verify` subcommand
+    ///
+    /// # Returns
+    /// A new AuditCommand, or an error if no log is given or configured
+    pub fn new(matches: &ArgMatches) -> Result<Self> {
+        let path = match matches.get_one::<String>("log") {
+            Some(path) => PathBuf::from(path),
+            None => Config::load()?.policy.audit_log_path().ok_or_else(|| {
+                VenvCleanerError::InvalidArgument(
+                    "No audit log configured: set audit_log in the [policy] table or pass --log".to_string(),
+                )
+            })?,
+        };
+
+        Ok(Self { log: AuditLog::new(path) })
+    }
+
+    /// Execute `audit verify`
+    ///
+    /// # Returns
+    /// The verification result, or an error if the chain is broken
+    pub fn execute(&self) -> Result<AuditVerification> {
+        info!("Verifying audit log {}", self.log.path().display());
+
+        let verification = self.log.verify()?;
+        match &verification.broken {
+            None => {
+                println!(
+                    "✅ {} {} ({} entries intact)",
+                    "Audit log verified:".green().bold(),
+                    self.log.path().display(),
+                    verification.valid_entries
+                );
+                println!("   Last hash: {}", verification.last_hash);
+                Ok(verification)
+            }
+            Some((line, message)) => {
+                println!(
+                    "❌ {} {} entries intact before line {}",
+                    "Audit log broken:".red().bold(),
+                    verification.valid_entries,
+                    line
+                );
+                Err(VenvCleanerError::AuditChainBroken {
+                    path: self.log.path().display().to_string(),
+                    line: *line,
+                    message: message.clone(),
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+    use crate::core::AuditAction;
+
+    fn create_test_command() -> clap::Command {
+        clap::Command::new("verify").arg(clap::Arg::new("log").long("log"))
+    }
+
+    #[test]
+    fn test_verify_intact_and_broken_logs() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("audit.log");
+        let log = AuditLog::new(path.clone());
+        log.append(AuditAction::Delete, Path::new("/p/.venv"), 10).unwrap();
+
+        let matches = create_test_command().try_get_matches_from(["verify", "--log", path.to_str().unwrap()]).unwrap();
+        assert_eq!(AuditCommand::new(&matches).unwrap().execute().unwrap().valid_entries, 1);
+
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::write(&path, contents.replace("/p/.venv", "/q/.venv")).unwrap();
+        let error = AuditCommand::new(&matches).unwrap().execute().unwrap_err();
+        assert!(matches!(error, VenvCleanerError::AuditChainBroken { line: 1, .. }));
+    }
+}
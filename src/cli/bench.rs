@@ -0,0 +1,160 @@
+//! Hidden `bench` subcommand for VenvCleaner
+//!
+//! This module runs the scanner several times over a directory and reports
+//! the fastest and median times of the walk and of sizing the .venv
+//! directories it found, separately, so scanner changes can be compared on
+//! real data (e.g. against a tree made with `--generate-fixture`).
+
+use std::time::{Duration, Instant};
+use clap::ArgMatches;
+use colored::*;
+use tracing::info;
+
+use crate::core::{concurrency, VenvCleaner, Result};
+use super::resolve_base_directory;
+
+/// Number of runs when `--runs` is not given
+const DEFAULT_RUNS: usize = 5;
+
+/// Timings of every run of a benchmark
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BenchReport {
+    /// .venv directories found per run
+    pub venvs: usize,
+    /// How long each walk took
+    pub walk: Vec<Duration>,
+    /// How long sizing the .venv directories took in each run
+    pub sizing: Vec<Duration>,
+}
+
+impl BenchReport {
+    /// Get the shortest of a set of timings
+    pub fn min(timings: &[Duration]) -> Duration {
+        timings.iter().min().copied().unwrap_or_default()
+    }
+
+    /// Get the median of a set of timings (the lower one for an even count)
+    pub fn median(timings: &[Duration]) -> Duration {
+        let mut sorted = timings.to_vec();
+        sorted.sort();
+        sorted.get(sorted.len().saturating_sub(1) / 2).copied().unwrap_or_default()
+    }
+}
+
+/// Handler for the `bench` subcommand
+pub struct BenchCommand {
+    /// The core VenvCleaner instance
+    cleaner: VenvCleaner,
+    /// How many times to run the scanner
+    runs: usize,
+}
+
+impl BenchCommand {
+    /// Create a new BenchCommand from the `bench` subcommand arguments
+    ///
+    /// # Arguments
+    /// * `matches` - Parsed arguments of the `bench` subcommand
+    ///
+    /// # Returns
+    /// A new BenchCommand instance or an error
+    pub fn new(matches: &ArgMatches) -> Result<Self> {
+        let base_directory = resolve_base_directory(matches)?;
+        let cleaner = VenvCleaner::new(base_directory, true, false, false, matches.get_count("verbose"))
+            .with_scan_threads(matches.get_one::<usize>("scan-threads").copied());
+
+        Ok(Self {
+            cleaner,
+            runs: matches.get_one::<usize>("runs").copied().unwrap_or(DEFAULT_RUNS).max(1),
+        })
+    }
+
+    /// Execute the bench subcommand
+    ///
+    /// # Returns
+    /// The timings of every run
+    pub fn execute(&self) -> Result<BenchReport> {
+        info!("Executing bench subcommand");
+
+        let mut report = BenchReport::default();
+        for run in 1..=self.runs {
+            let started = Instant::now();
+            let paths = self.cleaner.walk_venv_paths();
+            report.walk.push(started.elapsed());
+
+            // Sized like a scan does it, on the scan threads
+            let started = Instant::now();
+            let sized = concurrency::run_parallel(
+                &paths,
+                self.cleaner.scan_threads(),
+                |path| self.cleaner.analyze_venv_directory(path),
+                |_, _| {},
+            );
+            report.sizing.push(started.elapsed());
+            report.venvs = paths.len();
+
+            let failed = sized.iter().filter(|result| result.is_err()).count();
+            info!("Run {}: {} .venv directories, {} could not be sized", run, paths.len(), failed);
+        }
+
+        println!(
+            "{}",
+            format!(
+                "Benchmark of {} ({} runs, {} .venv directories, sizing threads: {})",
+                self.cleaner.base_directory().display(),
+                self.runs,
+                report.venvs,
+                self.cleaner.scan_threads()
+            )
+            .bold()
+        );
+        println!("  {:<8} {:>12} {:>12}", "", "min", "median");
+        for (label, timings) in [("Walk", &report.walk), ("Sizing", &report.sizing)] {
+            println!(
+                "  {:<8} {:>12} {:>12}",
+                label,
+                format!("{:.1?}", BenchReport::min(timings)),
+                format!("{:.1?}", BenchReport::median(timings)).green()
+            );
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_test_command() -> clap::Command {
+        clap::Command::new("bench")
+            .arg(clap::Arg::new("directory").index(1))
+            .arg(clap::Arg::new("runs").long("runs").value_parser(clap::value_parser!(usize)))
+            .arg(clap::Arg::new("scan-threads").long("scan-threads").value_parser(clap::value_parser!(usize)))
+            .arg(clap::Arg::new("verbose").short('v').action(clap::ArgAction::Count))
+    }
+
+    #[test]
+    fn test_bench_times_every_run() {
+        let temp_dir = TempDir::new().unwrap();
+        for project in ["a", "b"] {
+            fs::create_dir_all(temp_dir.path().join(project).join(".venv").join("bin")).unwrap();
+        }
+
+        let matches = create_test_command()
+            .try_get_matches_from(["bench", temp_dir.path().to_str().unwrap(), "--runs", "3"])
+            .unwrap();
+        let report = BenchCommand::new(&matches).unwrap().execute().unwrap();
+        assert_eq!(report.venvs, 2);
+        assert_eq!((report.walk.len(), report.sizing.len()), (3, 3));
+    }
+
+    #[test]
+    fn test_min_and_median() {
+        let timings = [Duration::from_millis(30), Duration::from_millis(10), Duration::from_millis(20), Duration::from_millis(40)];
+        assert_eq!(BenchReport::min(&timings), Duration::from_millis(10));
+        assert_eq!(BenchReport::median(&timings), Duration::from_millis(20));
+        assert_eq!(BenchReport::median(&[]), Duration::ZERO);
+    }
+}
@@ -0,0 +1,142 @@
+//! `diff` subcommand for VenvCleaner
+//!
+//! This module compares two scans saved with `--query --progress json` or
+//! `--output json` and reports the .venv directories removed, added and
+//! grown between them, for before/after verification of cleanup campaigns.
+
+use std::path::PathBuf;
+use clap::ArgMatches;
+use colored::*;
+use tracing::info;
+
+use crate::core::{ByteSize, ScanDiff, ScanSnapshot, VenvCleanerError, Result};
+
+/// Handler for the `diff` subcommand
+pub struct DiffCommand {
+    /// The earlier scan
+    before: PathBuf,
+    /// The later scan
+    after: PathBuf,
+}
+
+impl DiffCommand {
+    /// Create a new DiffCommand from the `diff` subcommand arguments
+    ///
+    /// # Arguments
+    /// * `matches` - Parsed arguments of the `diff` subcommand
+    ///
+    /// # Returns
+    /// A new DiffCommand instance or an error
+    pub fn new(matches: &ArgMatches) -> Result<Self> {
+        let path_of = |name: &str| {
+            matches.get_one::<String>(name).map(PathBuf::from).ok_or_else(|| {
+                VenvCleanerError::InvalidArgument(format!("--{} is required", name))
+            })
+        };
+
+        Ok(Self {
+            before: path_of("before")?,
+            after: path_of("after")?,
+        })
+    }
+
+    /// Execute the diff subcommand
+    ///
+    /// # Returns
+    /// The differences between the scans
+    pub fn execute(&self) -> Result<ScanDiff> {
+        info!("Executing diff subcommand");
+
+        let before = ScanSnapshot::load(&self.before)?;
+        let after = ScanSnapshot::load(&self.after)?;
+        let diff = ScanDiff::between(&before, &after);
+
+        println!(
+            "{} {} ({} .venv, {}) → {} ({} .venv, {})",
+            "Comparing".bold(),
+            self.before.display(),
+            before.len(),
+            ByteSize::new(before.total_bytes()),
+            self.after.display(),
+            after.len(),
+            ByteSize::new(after.total_bytes())
+        );
+
+        if diff.is_empty() {
+            println!("\n{}", "No .venv directories were added, removed or grown.".green());
+            return Ok(diff);
+        }
+
+        if !diff.removed.is_empty() {
+            println!("\n{}", format!("Removed ({})", diff.removed.len()).green().bold());
+            for (path, size) in &diff.removed {
+                println!("  - {}  {}", path.display(), ByteSize::new(*size).to_string().dimmed());
+            }
+        }
+
+        if !diff.added.is_empty() {
+            println!("\n{}", format!("Added ({})", diff.added.len()).yellow().bold());
+            for (path, size) in &diff.added {
+                println!("  + {}  {}", path.display(), ByteSize::new(*size).to_string().dimmed());
+            }
+        }
+
+        if !diff.grown.is_empty() {
+            println!("\n{}", format!("Grown ({})", diff.grown.len()).yellow().bold());
+            for grown in &diff.grown {
+                println!(
+                    "  ↑ {}  {} → {} (+{})",
+                    grown.path.display(),
+                    ByteSize::new(grown.before_bytes),
+                    ByteSize::new(grown.after_bytes),
+                    ByteSize::new(grown.growth_bytes())
+                );
+            }
+        }
+
+        println!(
+            "\n{} {} removed, {} added, {} grown",
+            "Summary:".bold(),
+            ByteSize::new(diff.removed_bytes()).to_string().green(),
+            ByteSize::new(diff.added_bytes()).to_string().yellow(),
+            ByteSize::new(diff.grown_bytes()).to_string().yellow()
+        );
+
+        Ok(diff)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_test_command() -> clap::Command {
+        clap::Command::new("diff")
+            .arg(clap::Arg::new("before").long("before"))
+            .arg(clap::Arg::new("after").long("after"))
+    }
+
+    #[test]
+    fn test_diff_command() {
+        let temp_dir = TempDir::new().unwrap();
+        let before = temp_dir.path().join("before.json");
+        let after = temp_dir.path().join("after.json");
+        fs::write(&before, "{\"event\":\"venv_found\",\"path\":\"/p/.venv\",\"size_bytes\":10}\n").unwrap();
+        fs::write(&after, "{\"event\":\"summary\",\"found\":0,\"deleted\":0,\"freed_bytes\":0,\"errors\":0}\n").unwrap();
+
+        let matches = create_test_command()
+            .try_get_matches_from(["diff", "--before", before.to_str().unwrap(), "--after", after.to_str().unwrap()])
+            .unwrap();
+        let diff = DiffCommand::new(&matches).unwrap().execute().unwrap();
+        assert_eq!(diff.removed_bytes(), 10);
+        assert!(diff.added.is_empty());
+    }
+
+    #[test]
+    fn test_diff_requires_both_scans() {
+        let matches = create_test_command().try_get_matches_from(["diff", "--before", "a.json"]).unwrap();
+        assert!(matches!(DiffCommand::new(&matches), Err(VenvCleanerError::InvalidArgument(_))));
+    }
+}
@@ -0,0 +1,160 @@
+//! `explain` subcommand for VenvCleaner
+//!
+//! This module prints where the space inside one .venv goes: a tree of its
+//! largest directories and files (packages, caches a slim pass would
+//! reclaim, native libraries) a few levels deep, the largest shared
+//! libraries anywhere in it, and how much slimming would reclaim compared
+//! with deleting, to help decide between the two.
+
+use std::path::PathBuf;
+use clap::ArgMatches;
+use colored::*;
+use tracing::info;
+
+use crate::core::{ByteSize, FileUtils, Result, SizeNode, SizeTree, VenvCleanerError};
+
+/// Handler for the `explain` subcommand
+pub struct ExplainCommand {
+    /// The .venv to explain
+    venv_path: PathBuf,
+    /// Levels shown below the .venv
+    depth: usize,
+}
+
+impl ExplainCommand {
+    /// Create a new ExplainCommand from the `explain` subcommand arguments
+    ///
+    /// # Arguments
+    /// * `matches` - Parsed arguments of the `explain` subcommand
+    ///
+    /// # Returns
+    /// A new ExplainCommand instance or an error if PATH is not a .venv
+    pub fn new(matches: &ArgMatches) -> Result<Self> {
+        let path = matches
+            .get_one::<String>("path")
+            .map(PathBuf::from)
+            .ok_or_else(|| VenvCleanerError::InvalidArgument("explain needs the path of a .venv".to_string()))?;
+
+        // A project directory stands for the .venv inside it; any environment with a pyvenv.cfg will do
+        let is_venv = |path: &PathBuf| FileUtils::is_valid_venv_directory(path) || path.join("pyvenv.cfg").is_file();
+        let venv_path = if is_venv(&path) { path.clone() } else { path.join(".venv") };
+        if !is_venv(&venv_path) {
+            return Err(VenvCleanerError::InvalidArgument(format!(
+                "{} is neither a .venv nor a project containing one",
+                path.display()
+            )));
+        }
+
+        Ok(Self {
+            venv_path,
+            depth: matches.get_one::<usize>("depth").copied().unwrap_or(SizeTree::DEFAULT_DEPTH),
+        })
+    }
+
+    /// Execute the explain subcommand
+    pub fn execute(&self) -> Result<SizeTree> {
+        info!("Executing explain subcommand for {}", self.venv_path.display());
+
+        let tree = SizeTree::build(&self.venv_path, self.depth)?;
+        let root = tree.root();
+        println!("📦 {} {}", root.name().bold(), ByteSize::new(root.bytes()).to_string().cyan());
+        Self::print_children(root, root.bytes(), "");
+
+        if !tree.shared_libraries().is_empty() {
+            println!("\n{}", "Largest shared libraries:".bold());
+            for (path, bytes) in tree.shared_libraries() {
+                let shown = path.strip_prefix(&self.venv_path).unwrap_or(path);
+                println!("  {:>10}  {}", ByteSize::new(*bytes).to_string(), shown.display());
+            }
+        }
+
+        println!(
+            "\n✂️  Slimming would reclaim {} ({}); deleting frees {}",
+            ByteSize::new(tree.slimmable_bytes()).to_string().cyan(),
+            Self::format_share(tree.slimmable_bytes(), root.bytes()),
+            ByteSize::new(root.bytes()).to_string().cyan()
+        );
+
+        Ok(tree)
+    }
+
+    /// Print the entries of a node as tree branches
+    ///
+    /// # Arguments
+    /// * `node` - The node whose entries to print
+    /// * `total` - The size of the whole .venv, for the shares
+    /// * `indent` - The branch lines drawn for the levels above
+    fn print_children(node: &SizeNode, total: u64, indent: &str) {
+        let (hidden, hidden_bytes) = node.hidden();
+        let count = node.children().len() + usize::from(hidden > 0);
+
+        for (i, child) in node.children().iter().enumerate() {
+            let last = i + 1 == count;
+            let label = child.kind().label().map(|label| format!(" {}", label.dimmed())).unwrap_or_default();
+            println!(
+                "{}{} {}  {} {}{}",
+                indent,
+                if last { "└──" } else { "├──" },
+                child.name(),
+                ByteSize::new(child.bytes()).to_string().cyan(),
+                Self::format_share(child.bytes(), total).dimmed(),
+                label
+            );
+            Self::print_children(child, total, &format!("{}{}", indent, if last { "    " } else { "│   " }));
+        }
+
+        // Entries below the depth shown are left for a deeper run
+        if hidden > 0 && !node.children().is_empty() {
+            println!(
+                "{}└── {}",
+                indent,
+                format!("{} more, {}", hidden, ByteSize::new(hidden_bytes)).dimmed()
+            );
+        }
+    }
+
+    /// Format a part of the .venv's size as a percentage
+    fn format_share(bytes: u64, total: u64) -> String {
+        if total == 0 {
+            return "0%".to_string();
+        }
+        format!("{:.0}%", bytes as f64 * 100.0 / total as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Command;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_test_command() -> Command {
+        Command::new("explain")
+            .arg(clap::Arg::new("path").index(1))
+            .arg(clap::Arg::new("depth").long("depth").value_parser(clap::value_parser!(usize)))
+    }
+
+    #[test]
+    fn test_explain_command_execute() {
+        let temp_dir = TempDir::new().unwrap();
+        let site = temp_dir.path().join(".venv").join("lib").join("python3.12").join("site-packages");
+        fs::create_dir_all(site.join("numpy").join("__pycache__")).unwrap();
+        fs::create_dir_all(temp_dir.path().join(".venv").join("bin")).unwrap();
+        fs::write(temp_dir.path().join(".venv").join("pyvenv.cfg"), "home = /usr/bin\n").unwrap();
+        fs::write(site.join("numpy").join("core.so"), vec![0u8; 500]).unwrap();
+        fs::write(site.join("numpy").join("__pycache__").join("a.pyc"), vec![0u8; 50]).unwrap();
+
+        // The project directory stands for its .venv
+        let matches = create_test_command()
+            .try_get_matches_from(["explain", temp_dir.path().to_str().unwrap(), "--depth", "2"])
+            .unwrap();
+        let tree = ExplainCommand::new(&matches).unwrap().execute().unwrap();
+        assert_eq!(tree.slimmable_bytes(), 50);
+        assert_eq!(tree.shared_libraries().len(), 1);
+
+        let empty = TempDir::new().unwrap();
+        let matches = create_test_command().try_get_matches_from(["explain", empty.path().to_str().unwrap()]).unwrap();
+        assert!(ExplainCommand::new(&matches).is_err());
+    }
+}
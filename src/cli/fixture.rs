@@ -0,0 +1,116 @@
+//! Dev-only `--generate-fixture DIR` handler
+//!
+//! Fabricates a synthetic tree of .venv folders for benchmarking the scanner
+//! and exercising the TUI/GUI with realistic data. Only registered in debug
+//! builds.
+
+use std::path::PathBuf;
+use clap::ArgMatches;
+use colored::*;
+use tracing::info;
+
+use crate::core::{ByteSize, FileUtils, Result, VenvCleanerError};
+use crate::fixtures::{FixtureGenerator, FixtureSpec};
+
+/// Handler for the `--generate-fixture` flag
+pub struct FixtureCommand {
+    /// Directory to generate the tree in
+    root: PathBuf,
+    /// Description of the tree to generate
+    spec: FixtureSpec,
+}
+
+impl FixtureCommand {
+    /// Create a new FixtureCommand from command line arguments
+    ///
+    /// # Arguments
+    /// * `matches` - Parsed arguments containing `generate-fixture` and the `fixture-*` options
+    ///
+    /// # Returns
+    /// A new FixtureCommand instance or an error
+    pub fn new(matches: &ArgMatches) -> Result<Self> {
+        let root = matches
+            .get_one::<String>("generate-fixture")
+            .map(PathBuf::from)
+            .ok_or_else(|| VenvCleanerError::InvalidArgument("--generate-fixture requires a directory".to_string()))?;
+
+        let count = matches.get_one::<usize>("fixture-count").copied().unwrap_or(100);
+        let max_age = matches.get_one::<u64>("fixture-max-age").copied().unwrap_or(180);
+        let size = match matches.get_one::<String>("fixture-size") {
+            Some(size) => FileUtils::parse_size(size)?,
+            None => 64 * 1024,
+        };
+
+        let spec = FixtureSpec::new(count)
+            .with_venv_size(size)
+            .with_max_age_days(max_age);
+
+        Ok(Self { root, spec })
+    }
+
+    /// Generate the fixture tree
+    pub fn execute(&self) -> Result<()> {
+        info!("Generating fixture tree in {}", self.root.display());
+
+        let venvs = FixtureGenerator::generate(&self.root, &self.spec)?;
+
+        println!(
+            "✅ Generated {} .venv folders ({} each, up to {} days old) in {}",
+            venvs.len().to_string().green(),
+            ByteSize::new(self.spec.venv_size()),
+            self.spec.max_age_days(),
+            self.root.display().to_string().cyan()
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Command;
+    use tempfile::TempDir;
+
+    fn create_test_command() -> Command {
+        Command::new("test")
+            .arg(clap::Arg::new("generate-fixture").long("generate-fixture"))
+            .arg(clap::Arg::new("fixture-count").long("fixture-count").value_parser(clap::value_parser!(usize)))
+            .arg(clap::Arg::new("fixture-size").long("fixture-size"))
+            .arg(clap::Arg::new("fixture-max-age").long("fixture-max-age").value_parser(clap::value_parser!(u64)))
+    }
+
+    #[test]
+    fn test_fixture_command_generates_tree() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("fixture");
+        let matches = create_test_command().try_get_matches_from([
+            "test",
+            "--generate-fixture",
+            root.to_str().unwrap(),
+            "--fixture-count",
+            "3",
+            "--fixture-size",
+            "8K",
+        ]).unwrap();
+
+        let command = FixtureCommand::new(&matches).unwrap();
+        assert_eq!(command.spec.count(), 3);
+        assert_eq!(command.spec.venv_size(), 8 * 1024);
+        assert!(command.execute().is_ok());
+        assert!(root.join("group-2").join("project-0002").join(".venv").is_dir());
+    }
+
+    #[test]
+    fn test_fixture_command_invalid_size() {
+        let matches = create_test_command().try_get_matches_from([
+            "test",
+            "--generate-fixture",
+            "/tmp/unused",
+            "--fixture-size",
+            "lots",
+        ]).unwrap();
+
+        assert!(FixtureCommand::new(&matches).is_err());
+    }
+}
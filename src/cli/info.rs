@@ -0,0 +1,219 @@
+//! `info` subcommand for VenvCleaner
+//!
+//! This module prints how the binary was built and how it sees the system:
+//! the enabled features, where the config file, caches and archives live,
+//! and which platform integrations (trash, D-Bus, editor, git) are usable.
+//! Its output is meant to be pasted into support requests.
+
+use std::path::Path;
+use colored::*;
+use tracing::info;
+
+use crate::core::{archive, editor, help, paths, shell, ActivityCache, Config, CrashReporter, ScanCheckpoint, Result};
+
+/// One labelled value of the `info` output
+#[derive(Debug, Clone, PartialEq)]
+pub struct InfoLine {
+    /// What the value describes
+    pub label: &'static str,
+    /// The value
+    pub value: String,
+}
+
+/// A titled group of lines of the `info` output
+#[derive(Debug, Clone, PartialEq)]
+pub struct InfoSection {
+    /// Section title
+    pub title: &'static str,
+    /// Lines in the section
+    pub lines: Vec<InfoLine>,
+}
+
+impl InfoSection {
+    /// Create a section
+    fn new(title: &'static str, lines: Vec<(&'static str, String)>) -> Self {
+        Self {
+            title,
+            lines: lines.into_iter().map(|(label, value)| InfoLine { label, value }).collect(),
+        }
+    }
+}
+
+/// Handler for the `info` subcommand
+#[derive(Debug, Default)]
+pub struct InfoCommand;
+
+impl InfoCommand {
+    /// Create a new InfoCommand
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Collect the build and platform information
+    pub fn sections(&self) -> Vec<InfoSection> {
+        vec![self.build_section(), self.paths_section(), self.integrations_section()]
+    }
+
+    /// Execute the info subcommand
+    pub fn execute(&self) -> Result<()> {
+        info!("Executing info subcommand");
+
+        for section in self.sections() {
+            println!("{}", section.title.bold());
+            for line in &section.lines {
+                println!("  {:<18} {}", format!("{}:", line.label), line.value);
+            }
+            println!();
+        }
+
+        Ok(())
+    }
+
+    /// Version, target and compiled-in features
+    fn build_section(&self) -> InfoSection {
+        let features: Vec<&str> = [
+            ("cli", cfg!(feature = "cli")),
+            ("tui", cfg!(feature = "tui")),
+            ("gui", cfg!(feature = "gui")),
+            ("dbus", cfg!(feature = "dbus")),
+            ("async", cfg!(feature = "async")),
+        ]
+        .into_iter()
+        .filter_map(|(name, enabled)| enabled.then_some(name))
+        .collect();
+
+        InfoSection::new("Build", vec![
+            ("Version", env!("CARGO_PKG_VERSION").to_string()),
+            ("Commit", format!("{} ({})", help::ABOUT.commit, help::ABOUT.build_date)),
+            ("Platform", format!("{} ({})", std::env::consts::OS, std::env::consts::ARCH)),
+            ("Profile", if cfg!(debug_assertions) { "debug" } else { "release" }.to_string()),
+            ("Features", features.join(", ")),
+        ])
+    }
+
+    /// Locations of the config file, caches, archives and crash reports
+    fn paths_section(&self) -> InfoSection {
+        let config_path = Config::default_path();
+        let config_status = match config_path.as_deref() {
+            Some(path) if path.exists() => match Config::load_from(path) {
+                Ok(_) => "found".to_string(),
+                Err(e) => format!("invalid: {}", e),
+            },
+            Some(_) => "not present, using defaults".to_string(),
+            None => "no config directory on this platform".to_string(),
+        };
+        let policy_path = Config::system_path();
+        let policy_status = match policy_path.as_deref() {
+            Some(path) if path.exists() => match Config::load_from(path) {
+                Ok(_) => "found".to_string(),
+                Err(e) => format!("invalid: {}", e),
+            },
+            Some(_) => "not present".to_string(),
+            None => "no system policy on this platform".to_string(),
+        };
+        let crash_reports = match Config::load().ok().and_then(|config| CrashReporter::from_config(&config.telemetry)) {
+            Some(reporter) => format!("enabled, {}", reporter.report_dir().display()),
+            None => "disabled".to_string(),
+        };
+
+        let state_root = match std::env::var_os(paths::HOME_ENV) {
+            Some(home) if !home.is_empty() => format!("{} (from ${})", Path::new(&home).display(), paths::HOME_ENV),
+            _ => format!("platform defaults (set ${} to move everything)", paths::HOME_ENV),
+        };
+
+        InfoSection::new("Paths", vec![
+            ("State root", state_root),
+            ("Config file", format!("{} ({})", describe_path(config_path.as_deref()), config_status)),
+            ("Policy file", format!("{} ({})", describe_path(policy_path.as_deref()), policy_status)),
+            ("Activity cache", describe_path(ActivityCache::default_path().as_deref())),
+            ("Cache directory", describe_path(paths::cache_dir().as_deref())),
+            ("Scan checkpoints", describe_path(ScanCheckpoint::default_dir().as_deref())),
+            ("Data directory", describe_path(paths::data_dir().as_deref())),
+            ("Log directory", describe_path(paths::log_dir().as_deref())),
+            ("Archives", archive::default_archive_dir().display().to_string()),
+            ("Crash reports", crash_reports),
+        ])
+    }
+
+    /// Platform integrations and the external tools they need
+    fn integrations_section(&self) -> InfoSection {
+        let trash = if cfg!(any(target_os = "linux", target_os = "macos", target_os = "windows")) {
+            "supported (--trash)"
+        } else {
+            "not supported on this platform"
+        };
+        let dbus = if !(cfg!(feature = "dbus") && cfg!(target_os = "linux")) {
+            "not built (--features dbus, Linux only)".to_string()
+        } else if std::env::var_os("DBUS_SESSION_BUS_ADDRESS").is_some() {
+            "available (--dbus), session bus found".to_string()
+        } else {
+            "built, but no session bus found".to_string()
+        };
+        let editor = editor::resolve_template(Config::load().ok().and_then(|c| c.editor).as_deref())
+            .unwrap_or_else(|| "none found (set `editor` in the config file)".to_string());
+
+        InfoSection::new("Integrations", vec![
+            ("Trash", trash.to_string()),
+            ("D-Bus service", dbus),
+            ("Editor", editor),
+            ("git", tool_status("git", "project activity")),
+            ("strip", tool_status("strip", "slim --strip")),
+            ("curl", tool_status("curl", "crash report submission")),
+        ])
+    }
+}
+
+/// Format an optional path, naming missing ones
+fn describe_path(path: Option<&Path>) -> String {
+    path.map(Path::display)
+        .map(|p| p.to_string())
+        .unwrap_or_else(|| "unavailable".to_string())
+}
+
+/// Describe whether an external tool is on PATH and what it is used for
+fn tool_status(name: &str, used_for: &str) -> String {
+    if shell::is_on_path(name) {
+        format!("found (used for {})", used_for)
+    } else {
+        format!("not found ({} unavailable)", used_for)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn find<'a>(sections: &'a [InfoSection], title: &str, label: &str) -> &'a str {
+        sections
+            .iter()
+            .find(|s| s.title == title)
+            .and_then(|s| s.lines.iter().find(|l| l.label == label))
+            .map(|l| l.value.as_str())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_build_section_lists_features() {
+        let sections = InfoCommand::new().sections();
+        let features = find(&sections, "Build", "Features");
+        assert!(features.contains("cli"));
+        assert_eq!(features.contains("tui"), cfg!(feature = "tui"));
+        assert_eq!(find(&sections, "Build", "Version"), env!("CARGO_PKG_VERSION"));
+        assert!(find(&sections, "Build", "Commit").starts_with(help::ABOUT.commit));
+    }
+
+    #[test]
+    fn test_paths_section() {
+        let sections = InfoCommand::new().sections();
+        let archives = PathBuf::from(find(&sections, "Paths", "Archives"));
+        assert_eq!(archives, archive::default_archive_dir());
+        assert!(find(&sections, "Paths", "Config file").contains("config.toml"));
+    }
+
+    #[test]
+    fn test_tool_status() {
+        assert!(tool_status("definitely-not-a-real-tool-xyz", "testing").starts_with("not found"));
+        assert_eq!(describe_path(None), "unavailable");
+    }
+}
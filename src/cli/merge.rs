@@ -0,0 +1,123 @@
+//! `merge` subcommand for VenvCleaner
+//!
+//! This module combines the JSON scan reports of several machines into one
+//! table of per-host totals and, with `--html`, a standalone HTML page
+//! listing every .venv with a host column, for team-wide disk reviews.
+
+use std::fs;
+use std::path::PathBuf;
+use clap::ArgMatches;
+use colored::*;
+use tracing::info;
+
+use crate::core::{ByteSize, MergedReport, VenvCleanerError, Result};
+
+/// Handler for the `merge` subcommand
+pub struct MergeCommand {
+    /// The reports to combine, one per machine
+    reports: Vec<PathBuf>,
+    /// Where to write the HTML report, if anywhere
+    html: Option<PathBuf>,
+}
+
+impl MergeCommand {
+    /// Create a new MergeCommand from the `merge` subcommand arguments
+    ///
+    /// # Arguments
+    /// * `matches` - Parsed arguments of the `merge` subcommand
+    ///
+    /// # Returns
+    /// A new MergeCommand instance or an error
+    pub fn new(matches: &ArgMatches) -> Result<Self> {
+        let reports: Vec<PathBuf> = matches
+            .get_many::<String>("reports")
+            .map(|reports| reports.map(PathBuf::from).collect())
+            .unwrap_or_default();
+        if reports.is_empty() {
+            return Err(VenvCleanerError::InvalidArgument("At least one report is required".to_string()));
+        }
+
+        Ok(Self {
+            reports,
+            html: matches.get_one::<String>("html").map(PathBuf::from),
+        })
+    }
+
+    /// Execute the merge subcommand
+    ///
+    /// # Returns
+    /// The merged report
+    pub fn execute(&self) -> Result<MergedReport> {
+        info!("Executing merge subcommand");
+
+        let mut report = MergedReport::new();
+        for path in &self.reports {
+            let added = report.add_file(path)?;
+            info!("Read {} .venv directories from {}", added, path.display());
+        }
+
+        let summaries = report.host_summaries();
+        println!("{}", format!("Merged {} reports", self.reports.len()).bold());
+        println!("\n  {:<24} {:>6} {:>12} {:>12}", "Host", ".venv", "Size", "Reclaimable");
+        for summary in &summaries {
+            println!(
+                "  {:<24} {:>6} {:>12} {:>12}",
+                summary.host,
+                summary.count,
+                ByteSize::new(summary.total_bytes).to_string(),
+                ByteSize::new(summary.reclaimable_bytes).to_string().yellow()
+            );
+        }
+        println!(
+            "  {:<24} {:>6} {:>12} {:>12}",
+            "Total".bold(),
+            report.venvs().len(),
+            ByteSize::new(summaries.iter().map(|summary| summary.total_bytes).sum()).to_string(),
+            ByteSize::new(summaries.iter().map(|summary| summary.reclaimable_bytes).sum()).to_string().yellow()
+        );
+        println!("\n{}", "Reclaimable: not used for over 90 days".dimmed());
+
+        if let Some(html) = &self.html {
+            fs::write(html, report.to_html())?;
+            println!("{} {}", "HTML report written to".green(), html.display());
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_test_command() -> clap::Command {
+        clap::Command::new("merge")
+            .arg(clap::Arg::new("reports").num_args(0..))
+            .arg(clap::Arg::new("html").long("html"))
+    }
+
+    #[test]
+    fn test_merge_command_writes_html() {
+        let temp_dir = TempDir::new().unwrap();
+        let first = temp_dir.path().join("alpha.json");
+        let second = temp_dir.path().join("beta.json");
+        let html = temp_dir.path().join("report.html");
+        fs::write(&first, "{\"event\":\"venv_found\",\"path\":\"/a/.venv\",\"size_bytes\":10}\n").unwrap();
+        fs::write(&second, "[{\"path\":\"/b/.venv\",\"size_bytes\":20}]").unwrap();
+
+        let matches = create_test_command()
+            .try_get_matches_from(["merge", first.to_str().unwrap(), second.to_str().unwrap(), "--html", html.to_str().unwrap()])
+            .unwrap();
+        let report = MergeCommand::new(&matches).unwrap().execute().unwrap();
+        let hosts: Vec<String> = report.host_summaries().into_iter().map(|summary| summary.host).collect();
+        assert_eq!(hosts, vec!["alpha", "beta"]);
+        assert!(fs::read_to_string(&html).unwrap().contains("<td>beta</td><td>/b/.venv</td>"));
+    }
+
+    #[test]
+    fn test_merge_requires_reports() {
+        let matches = create_test_command().try_get_matches_from(["merge"]).unwrap();
+        assert!(matches!(MergeCommand::new(&matches), Err(VenvCleanerError::InvalidArgument(_))));
+    }
+}
@@ -4,13 +4,59 @@
 //! It provides functionality for interactive and non-interactive .venv directory management.
 
 use clap::ArgMatches;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::io::{self, Write};
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
 use colored::*;
 use indicatif::{ProgressBar, ProgressStyle};
-use log::info;
-
-use crate::core::{VenvCleaner, VenvCleanerError, VenvInfo, Result};
+use tracing::{info, warn};
+
+#[cfg(feature = "decision-hook")]
+use crate::core::DecisionHook;
+use crate::core::{archive, deletion_eta, escalation, git, inodes, ownership, recommendations, run_lock, safety, size_deltas, summary_cache, temp_venvs, timestamps, ActivityCache, AgeBucket, ArchivedVenv, BatchOptions, ByteSize, Config, ConfirmationProvider, ConfirmationRequest, DeleteMode, DeletionEstimator, Detector, EscalationHelper, ExportFormat, FileUtils, FsBackend, HookDecision, IncludeSet, LockMode, OfflineIndex, OwnerFilter, PermissionReport, ProgressEvent, PendingDeletion, ProjectArtifact, ProjectArtifacts, RecommendationHistory, RootSummary, RunLock, RunSummary, ScanCheckpoint, ScanConfig, ScanMetadata, SizeDeltas, SortKey, SortOrder, TrashLedger, VenvCleaner, VenvCleanerError, VenvFilter, VenvInfo, Result};
+
+pub mod advise;
+pub mod apply_plan;
+pub mod audit;
+pub mod bench;
+pub mod diff;
+pub mod explain;
+pub mod fixture;
+pub mod info;
+pub mod merge;
+pub mod pager;
+pub mod policy;
+pub mod prompt;
+pub mod prompt_summary;
+pub mod refresh_cache;
+pub mod robot;
+pub mod slim;
+pub mod stats;
+pub mod trash;
+pub mod unarchive;
+
+pub use advise::AdviseCommand;
+pub use apply_plan::ApplyPlanCommand;
+pub use audit::AuditCommand;
+pub use bench::{BenchCommand, BenchReport};
+pub use diff::DiffCommand;
+pub use explain::ExplainCommand;
+pub use fixture::FixtureCommand;
+pub use info::InfoCommand;
+pub use merge::MergeCommand;
+pub use pager::Pager;
+pub use policy::PolicyCommand;
+pub use prompt::Prompter;
+pub use prompt_summary::PromptSummaryCommand;
+pub use refresh_cache::RefreshCacheCommand;
+pub use robot::RobotMode;
+pub use slim::SlimCommand;
+pub use stats::StatsCommand;
+pub use trash::TrashCommand;
+pub use unarchive::UnarchiveCommand;
 
 /// CLI mode handler for VenvCleaner
 pub struct CliMode {
@@ -20,6 +66,537 @@ pub struct CliMode {
     query_mode: bool,
     /// Whether to show progress bars
     show_progress: bool,
+    /// Whether to offer removing the project's build artifacts as well
+    deep_clean: bool,
+    /// Whether non-interactive runs may delete .venv directories holding user data
+    include_user_data: bool,
+    /// Whether non-interactive runs may delete the build artifacts found by a deep clean
+    include_artifacts: bool,
+    /// How progress is reported on stdout
+    progress_format: ProgressFormat,
+    /// Whether query mode shows the last pip install time column
+    show_last_install: bool,
+    /// Whether query mode shows the project activity column
+    show_activity: bool,
+    /// Whether query mode shows the change in size since the previous scan
+    show_size_delta: bool,
+    /// Report query mode prints instead of its table (`--output`)
+    output: Option<ExportFormat>,
+    /// Whether query mode streams rows as found instead of keeping the scan (`--low-memory`)
+    low_memory: bool,
+    /// How query mode groups its table
+    group_by: GroupBy,
+    /// How query mode sorts its table
+    sort_by: SortBy,
+    /// How often query mode rescans and redraws its table (`--watch`), None to list once
+    watch: Option<Duration>,
+    /// Whether query mode shows the file count column
+    show_file_count: bool,
+    /// Whether query mode shows the inode column and per file system inode totals
+    show_inodes: bool,
+    /// Whether to only report whether each .venv could be deleted
+    check_permissions: bool,
+    /// Scan roots inside a virtual environment, with the environment each lies in
+    inside_environments: Vec<(PathBuf, PathBuf)>,
+    /// Whether administrators are offered to retry denied deletions with sudo or pkexec
+    allow_escalation: bool,
+    /// How cleanups lock their roots against other instances, None with `--force-lock`
+    lock_mode: Option<LockMode>,
+    /// File the final summary is written to as JSON, whatever the output mode
+    summary_json: Option<PathBuf>,
+    /// Command consulted before each .venv in cleanup mode (`decision_hook` config key)
+    decision_hook: Option<String>,
+    /// Reads prompt answers, giving up after `--confirm-timeout`
+    prompter: Prompter,
+    /// Answers the questions asked before each deletion (the prompter unless replaced)
+    confirmation: Arc<dyn ConfirmationProvider>,
+}
+
+/// Output format for progress reporting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressFormat {
+    /// Colored, human-readable output with prompts
+    Human,
+    /// Line-delimited JSON progress events (see `ProgressEvent`)
+    Json,
+}
+
+impl ProgressFormat {
+    /// Parse a progress format name as accepted by `--progress`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "human" => Some(ProgressFormat::Human),
+            "json" => Some(ProgressFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Keys query mode can group its table by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    /// A single table, no grouping
+    None,
+    /// One section per age bucket (see `AgeBucket`)
+    Age,
+}
+
+impl GroupBy {
+    /// Parse a group-by key as accepted by `--group-by`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "none" => Some(GroupBy::None),
+            "age" => Some(GroupBy::Age),
+            _ => None,
+        }
+    }
+}
+
+/// Keys query mode can sort its table by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortBy {
+    /// By path
+    Location,
+    /// Largest first
+    Size,
+    /// Newest first
+    Created,
+    /// Most recently used first
+    LastUsed,
+    /// Most files first
+    Files,
+}
+
+impl SortBy {
+    /// Every key, in the order of the table's columns
+    pub const ALL: [SortBy; 5] = [SortBy::Location, SortBy::Size, SortBy::Created, SortBy::LastUsed, SortBy::Files];
+
+    /// Parse a sort key as accepted by `--sort`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "location" => Some(SortBy::Location),
+            "size" => Some(SortBy::Size),
+            "created" => Some(SortBy::Created),
+            "last-used" => Some(SortBy::LastUsed),
+            "files" => Some(SortBy::Files),
+            _ => None,
+        }
+    }
+
+    /// Get the shared sort key this option sorts by
+    pub fn key(self) -> SortKey {
+        match self {
+            SortBy::Location => SortKey::Path,
+            SortBy::Size => SortKey::Size,
+            SortBy::Created => SortKey::Created,
+            SortBy::LastUsed => SortKey::LastModified,
+            SortBy::Files => SortKey::Files,
+        }
+    }
+
+    /// Get the heading of the column this key sorts by
+    pub fn label(self) -> &'static str {
+        match self {
+            SortBy::Location => "Location",
+            SortBy::Size => "Size",
+            SortBy::Created => "Created",
+            SortBy::LastUsed => "Last Used",
+            SortBy::Files => "Files",
+        }
+    }
+
+    /// Get the key of the next column, wrapping around
+    ///
+    /// # Arguments
+    /// * `with_files` - Whether the table shows the file count column
+    pub fn next(self, with_files: bool) -> Self {
+        let position = Self::ALL.iter().position(|key| *key == self).unwrap_or_default();
+        Self::ALL
+            .iter()
+            .cycle()
+            .skip(position + 1)
+            .find(|key| with_files || **key != SortBy::Files)
+            .copied()
+            .unwrap_or(self)
+    }
+}
+
+/// What a line typed between `--watch` refreshes asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchCommand {
+    /// Rescan now (an empty line or anything unknown)
+    Refresh,
+    /// Sort by the next column (`s`)
+    NextSort,
+    /// Reverse the sort order (`r`)
+    Reverse,
+    /// Stop watching (`q`)
+    Quit,
+}
+
+impl WatchCommand {
+    /// Parse a line typed between refreshes
+    pub fn from_line(line: &str) -> Self {
+        match line.trim().to_lowercase().as_str() {
+            "s" => WatchCommand::NextSort,
+            "r" => WatchCommand::Reverse,
+            "q" => WatchCommand::Quit,
+            _ => WatchCommand::Refresh,
+        }
+    }
+}
+
+/// Resolve and validate the directory to search from the `directory` argument
+///
+/// # Arguments
+/// * `matches` - Parsed command line arguments containing an optional `directory`
+///
+/// # Returns
+/// The directory to search (the current directory when none was given) or an error
+pub fn resolve_base_directory(matches: &ArgMatches) -> Result<PathBuf> {
+    let base_directory = if let Some(dir) = matches.get_one::<String>("directory") {
+        PathBuf::from(dir)
+    } else {
+        std::env::current_dir()?
+    };
+
+    validate_directory(base_directory)
+}
+
+/// Resolve the directories given after the first one, for multi-root scans
+///
+/// # Arguments
+/// * `matches` - Parsed command line arguments
+///
+/// # Returns
+/// The further directories to scan, or an error if one is not a directory
+pub fn resolve_extra_roots(matches: &ArgMatches) -> Result<Vec<PathBuf>> {
+    matches
+        .get_many::<String>("directory")
+        .into_iter()
+        .flatten()
+        .skip(1)
+        .map(|dir| validate_directory(PathBuf::from(dir)))
+        .collect()
+}
+
+/// Refuse a recursive force run as root over `/` or `/home` unless confirmed
+///
+/// # Arguments
+/// * `as_root` - Whether VenvCleaner runs as root
+/// * `recursive` - Whether subdirectories are searched
+/// * `force_mode` - Whether deletions skip the prompts
+/// * `confirmed` - Whether `--i-know-what-im-doing` was passed
+/// * `roots` - The directories to be scanned
+///
+/// # Returns
+/// An error naming the dangerous roots if the run needs confirming
+pub fn check_force_run_as_root(
+    as_root: bool,
+    recursive: bool,
+    force_mode: bool,
+    confirmed: bool,
+    roots: &[&Path],
+) -> Result<()> {
+    if !as_root || !recursive || !force_mode || confirmed {
+        return Ok(());
+    }
+    let dangerous = safety::dangerous_roots(roots);
+    if dangerous.is_empty() {
+        return Ok(());
+    }
+    let names: Vec<String> = dangerous.iter().map(|root| root.display().to_string()).collect();
+    Err(VenvCleanerError::InvalidArgument(format!(
+        "Refusing to force-delete recursively under {} as root; pass --i-know-what-im-doing if this is intended",
+        names.join(", ")
+    )))
+}
+
+/// Refuse a force run from inside a virtual environment unless confirmed
+///
+/// # Arguments
+/// * `force_mode` - Whether deletions skip the prompts (and are not a dry run)
+/// * `confirmed` - Whether `--i-know-what-im-doing` was passed
+/// * `inside` - The scan roots inside an environment, with the environment each lies in
+///
+/// # Returns
+/// An error naming the first such root if the run needs confirming
+pub fn check_force_run_inside_environment(force_mode: bool, confirmed: bool, inside: &[(PathBuf, PathBuf)]) -> Result<()> {
+    match inside.first() {
+        Some((root, environment)) if force_mode && !confirmed => Err(VenvCleanerError::InvalidArgument(format!(
+            "Refusing to force-delete from {}, which is inside {}; pass --i-know-what-im-doing if this is intended",
+            root.display(),
+            environment.display()
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Resolve every directory to scan, falling back to the configured roots
+///
+/// Directories given on the command line win; without any, the `[scan]
+/// roots` of the config (or `VENV_CLEANER_ROOTS`) are scanned, skipping
+/// those that do not exist on this machine, and then the current directory.
+///
+/// # Arguments
+/// * `matches` - Parsed command line arguments
+/// * `scan` - The `[scan]` table of the config file
+///
+/// # Returns
+/// The base directory and the further directories to scan, or an error if
+/// a directory given on the command line is not a directory
+pub fn resolve_scan_roots(matches: &ArgMatches, scan: &ScanConfig) -> Result<(PathBuf, Vec<PathBuf>)> {
+    if matches.contains_id("directory") {
+        return Ok((resolve_base_directory(matches)?, resolve_extra_roots(matches)?));
+    }
+
+    let mut roots = scan.root_paths().into_iter().filter_map(|root| match validate_directory(root) {
+        Ok(root) => Some(root),
+        Err(e) => {
+            warn!("Skipping configured scan root: {}", e);
+            None
+        }
+    });
+    match roots.next() {
+        Some(base_directory) => Ok((base_directory, roots.collect())),
+        None => Ok((resolve_base_directory(matches)?, Vec::new())),
+    }
+}
+
+/// Check that a directory to scan exists and is a directory
+fn validate_directory(base_directory: PathBuf) -> Result<PathBuf> {
+    if !base_directory.exists() {
+        return Err(VenvCleanerError::PathError {
+            path: base_directory.display().to_string(),
+            message: "Directory does not exist".to_string(),
+        });
+    }
+
+    if !base_directory.is_dir() {
+        return Err(VenvCleanerError::PathError {
+            path: base_directory.display().to_string(),
+            message: "Path is not a directory".to_string(),
+        });
+    }
+
+    Ok(base_directory)
+}
+
+/// Resolve where .venv directories are archived to, if archiving was requested
+///
+/// `--archive-dir DIR` implies `--archive`; plain `--archive` uses the
+/// default archive directory.
+///
+/// # Arguments
+/// * `matches` - Parsed command line arguments
+///
+/// # Returns
+/// The archive directory, or None when .venv directories are deleted
+pub fn resolve_archive_dir(matches: &ArgMatches) -> Option<PathBuf> {
+    match matches.get_one::<String>("archive-dir") {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None if matches.get_flag("archive") => Some(archive::default_archive_dir()),
+        None => None,
+    }
+}
+
+/// Resolve how long prompts wait, from `--confirm-timeout` and `--default-answer`
+///
+/// # Arguments
+/// * `matches` - Parsed command line arguments
+///
+/// # Returns
+/// The prompter, or an error for an invalid timeout
+pub fn resolve_prompter(matches: &ArgMatches) -> Result<Prompter> {
+    let timeout = matches.get_one::<String>("confirm-timeout").map(|text| prompt::parse_timeout(text)).transpose()?;
+    let default_answer = matches.get_one::<String>("default-answer").map(String::as_str).unwrap_or("n");
+    Ok(Prompter::new(timeout, default_answer))
+}
+
+/// Plan file written by `--from-index` when `--plan` is not given
+pub const DEFAULT_PLAN_FILE: &str = "venv_cleaner-plan.json";
+
+/// Resolve the saved scan listed instead of the disk, from `--from-index FILE`
+///
+/// Deletions are added to the `--plan FILE`, by default
+/// `venv_cleaner-plan.json` in the current directory.
+///
+/// # Arguments
+/// * `matches` - Parsed command line arguments
+///
+/// # Returns
+/// The offline index, None when the disk is scanned, or an error if a file cannot be read
+pub fn resolve_offline_index(matches: &ArgMatches) -> Result<Option<OfflineIndex>> {
+    let Some(index) = matches.get_one::<String>("from-index") else {
+        return Ok(None);
+    };
+    let plan = matches.get_one::<String>("plan").map(PathBuf::from).unwrap_or_else(|| PathBuf::from(DEFAULT_PLAN_FILE));
+    OfflineIndex::load(Path::new(index), plan).map(Some)
+}
+
+/// Resolve the per-.venv sizing budget from `--size-timeout SECONDS`
+///
+/// # Arguments
+/// * `matches` - Parsed command line arguments
+///
+/// # Returns
+/// The budget, or None when sizing is not limited (the flag is absent or 0)
+pub fn resolve_size_timeout(matches: &ArgMatches) -> Option<Duration> {
+    match matches.get_one::<u64>("size-timeout") {
+        Some(0) | None => None,
+        Some(&seconds) => Some(Duration::from_secs(seconds)),
+    }
+}
+
+/// Resolve whose .venv directories are listed from `--mine`, `--all-users` and `--user NAME`
+///
+/// # Arguments
+/// * `matches` - Parsed command line arguments
+///
+/// # Returns
+/// The owner filter (the current user's by default), or an error for unknown users
+pub fn resolve_owner_filter(matches: &ArgMatches) -> Result<OwnerFilter> {
+    if let Some(name) = matches.get_one::<String>("user") {
+        return ownership::resolve_user(name).map(OwnerFilter::User);
+    }
+    if matches.get_flag("all-users") {
+        return Ok(OwnerFilter::AllUsers);
+    }
+    Ok(OwnerFilter::Mine)
+}
+
+/// Resolve the age and size criteria from `--older-than AGE`, `--min-size SIZE` and the config
+///
+/// # Arguments
+/// * `matches` - Parsed command line arguments
+/// * `scan` - The `[scan]` table of the config file, with any `--profile` applied
+///
+/// # Returns
+/// The filter (keeping everything when neither is given), or an error for invalid values
+pub fn resolve_venv_filter(matches: &ArgMatches, scan: &ScanConfig) -> Result<VenvFilter> {
+    let older_than = matches.get_one::<String>("older-than").or(scan.older_than.as_ref());
+    let older_than = older_than.map(|age| trash::parse_older_than(age)).transpose()?;
+    let min_size = match matches.get_one::<String>("min-size") {
+        Some(size) => Some(ByteSize::parse(size)?),
+        None => scan.min_size,
+    };
+    Ok(VenvFilter::default().with_older_than(older_than).with_min_size(min_size))
+}
+
+/// Resolve the subtrees scans are limited to from `--include GLOB`
+///
+/// # Arguments
+/// * `matches` - Parsed command line arguments
+///
+/// # Returns
+/// The include patterns (none when not given), or an error for an invalid pattern
+pub fn resolve_includes(matches: &ArgMatches) -> Result<IncludeSet> {
+    let patterns: Vec<String> = matches.get_many::<String>("include").into_iter().flatten().cloned().collect();
+    IncludeSet::new(&patterns)
+}
+
+/// Resolve the size guard from `--exclude-size-above SIZE`
+///
+/// # Arguments
+/// * `matches` - Parsed command line arguments
+///
+/// # Returns
+/// The size above which matches need an interactive confirmation (200 GB
+/// unless given), None for `none`, or an error for an invalid size
+pub fn resolve_exclude_size_above(matches: &ArgMatches) -> Result<Option<ByteSize>> {
+    match matches.get_one::<String>("exclude-size-above").map(String::as_str) {
+        None => Ok(Some(safety::DEFAULT_EXCLUDE_SIZE_ABOVE)),
+        Some("none") | Some("off") => Ok(None),
+        Some(size) => ByteSize::parse(size).map(Some),
+    }
+}
+
+/// Resolve whether scans skip hidden directories from `--skip-hidden`, `--include-hidden` and the config
+///
+/// # Arguments
+/// * `matches` - Parsed command line arguments
+/// * `scan` - The `[scan]` table of the config file
+///
+/// # Returns
+/// True when hidden directories are skipped (false by default)
+pub fn resolve_skip_hidden(matches: &ArgMatches, scan: &ScanConfig) -> bool {
+    if matches.get_flag("skip-hidden") {
+        return true;
+    }
+    if matches.get_flag("include-hidden") {
+        return false;
+    }
+    scan.skip_hidden.unwrap_or(false)
+}
+
+/// Resolve the temp directories scanned for abandoned environments from `--scan-temp` and the config
+///
+/// # Arguments
+/// * `matches` - Parsed command line arguments
+/// * `scan` - The `[scan]` table of the config file
+///
+/// # Returns
+/// The system temp directories, or none unless asked for
+pub fn resolve_temp_roots(matches: &ArgMatches, scan: &ScanConfig) -> Vec<PathBuf> {
+    if matches.get_flag("scan-temp") || scan.scan_temp.unwrap_or(false) {
+        temp_venvs::temp_roots()
+    } else {
+        Vec::new()
+    }
+}
+
+/// Resolve where deleted folders go from `--trash`, `--permanent` and the `delete_mode` config key
+///
+/// # Arguments
+/// * `matches` - Parsed command line arguments
+/// * `config` - The `delete_mode` config key, if set
+///
+/// # Returns
+/// The deletion mode (the trash when there is one, by default), or an error for an unknown config value
+pub fn resolve_delete_mode(matches: &ArgMatches, config: Option<&str>) -> Result<DeleteMode> {
+    if matches.get_flag("trash") {
+        Ok(DeleteMode::Trash)
+    } else if matches.get_flag("permanent") {
+        Ok(DeleteMode::Permanent)
+    } else {
+        DeleteMode::from_config(config)
+    }
+}
+
+/// Optional columns of the query mode table
+#[derive(Debug, Default)]
+struct QueryColumns {
+    /// Whether the table has an "Owner" column
+    show_owner: bool,
+    /// Whether the table has a "Last Commit" column
+    show_last_commit: bool,
+    /// Project activity by .venv path, when the "Project Activity" column is shown
+    activity: Option<HashMap<PathBuf, Option<DateTime<Utc>>>>,
+    /// Change in size since the previous scan, when the "Δ Last Scan" column is shown
+    size_deltas: Option<SizeDeltas>,
+}
+
+/// Outcome of processing a single .venv directory in cleanup mode
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ProcessOutcome {
+    /// Whether the .venv directory itself was deleted
+    deleted: bool,
+    /// Total bytes freed (the .venv plus any deep-cleaned build artifacts)
+    freed_bytes: u64,
+    /// What decided the outcome, e.g. `--force` or `declined at the prompt`
+    reason: &'static str,
+}
+
+impl ProcessOutcome {
+    /// A .venv left in place
+    fn kept(reason: &'static str) -> Self {
+        Self { deleted: false, freed_bytes: 0, reason }
+    }
+
+    /// A deleted .venv
+    fn deleted(freed_bytes: u64, reason: &'static str) -> Self {
+        Self { deleted: true, freed_bytes, reason }
+    }
 }
 
 impl CliMode {
@@ -31,34 +608,90 @@ impl CliMode {
     /// # Returns
     /// A new CliMode instance or an error
     pub fn new(matches: &ArgMatches) -> Result<Self> {
-        // Determine the base directory
-        let base_directory = if let Some(dir) = matches.get_one::<String>("directory") {
-            PathBuf::from(dir)
-        } else {
-            std::env::current_dir()?
-        };
-
-        // Validate that the directory exists
-        if !base_directory.exists() {
-            return Err(VenvCleanerError::PathError {
-                path: base_directory.display().to_string(),
-                message: "Directory does not exist".to_string(),
-            });
-        }
-
-        if !base_directory.is_dir() {
-            return Err(VenvCleanerError::PathError {
-                path: base_directory.display().to_string(),
-                message: "Path is not a directory".to_string(),
-            });
-        }
+        let config = Config::load()?.with_profile(matches.get_one::<String>("profile").map(String::as_str))?;
+        let (base_directory, extra_roots) = resolve_scan_roots(matches, &config.scan)?;
 
         // Extract other options
         let recursive = matches.get_flag("recursive");
         let force_mode = matches.get_flag("force");
+        config.policy.check_force(force_mode)?;
+        let roots: Vec<&Path> = std::iter::once(base_directory.as_path()).chain(extra_roots.iter().map(PathBuf::as_path)).collect();
+        check_force_run_as_root(
+            ownership::is_root(),
+            recursive,
+            force_mode,
+            matches.get_flag("i-know-what-im-doing"),
+            &roots,
+        )?;
         let dry_run = matches.get_flag("dry-run");
         let query_mode = matches.get_flag("query");
         let verbosity = matches.get_count("verbose");
+        let deep_clean = matches.get_flag("deep-clean");
+        let include_user_data = matches.get_flag("include-user-data");
+        let include_artifacts = matches.get_flag("include-artifacts");
+        let delete_mode = resolve_delete_mode(matches, config.delete_mode.as_deref())?;
+        let respect_gitignore = matches.get_flag("respect-gitignore");
+        let show_last_install = matches.get_flag("last-install");
+        let show_activity = matches.get_flag("activity");
+        let show_size_delta = matches.get_flag("size-delta");
+        let check_permissions = matches.get_flag("check-permissions");
+        let allow_escalation = matches.get_flag("allow-escalation");
+        let lock_mode = if matches.get_flag("force-lock") {
+            None
+        } else if matches.get_flag("wait") {
+            Some(LockMode::Wait)
+        } else {
+            Some(LockMode::FailFast)
+        };
+        let prompter = resolve_prompter(matches)?;
+        let progress_format = matches
+            .get_one::<String>("progress")
+            .and_then(|name| ProgressFormat::from_name(name))
+            .unwrap_or(ProgressFormat::Human);
+        let output = matches.get_one::<String>("output").and_then(|name| ExportFormat::from_name(name));
+        let low_memory = matches.get_flag("low-memory");
+        let group_by = matches
+            .get_one::<String>("group-by")
+            .and_then(|name| GroupBy::from_name(name))
+            .unwrap_or(GroupBy::None);
+        let sort_by = matches
+            .get_one::<String>("sort")
+            .and_then(|name| SortBy::from_name(name))
+            .unwrap_or(SortBy::Size);
+        // Sorting by file count without showing it would be puzzling
+        let show_file_count = matches.get_flag("file-count") || sort_by == SortBy::Files;
+        let show_inodes = matches.get_flag("inodes");
+        let watch = matches.get_one::<String>("watch").map(|interval| prompt::parse_timeout(interval)).transpose()?;
+
+        // The JSON stream cannot carry interactive prompts
+        if progress_format == ProgressFormat::Json && !query_mode && !force_mode {
+            return Err(VenvCleanerError::InvalidArgument(
+                "--progress json requires --query or --force".to_string(),
+            ));
+        }
+
+        // Reports only list, and own stdout
+        if output.is_some() && !query_mode {
+            return Err(VenvCleanerError::InvalidArgument("--output requires --query".to_string()));
+        }
+        if low_memory && (!query_mode || progress_format == ProgressFormat::Json) {
+            return Err(VenvCleanerError::InvalidArgument(
+                "--low-memory requires --query and cannot be combined with --progress json".to_string(),
+            ));
+        }
+        if watch.is_some() && (!query_mode || output.is_some() || low_memory || progress_format == ProgressFormat::Json) {
+            return Err(VenvCleanerError::InvalidArgument(
+                "--watch requires --query and redraws its table, so it cannot be combined with --output, --low-memory or --progress json".to_string(),
+            ));
+        }
+        if watch.is_some_and(|interval| interval.is_zero()) {
+            return Err(VenvCleanerError::InvalidArgument("--watch needs an interval of at least 1s".to_string()));
+        }
+        if output.is_some() && progress_format == ProgressFormat::Json {
+            return Err(VenvCleanerError::InvalidArgument(
+                "--output and --progress json both write to stdout".to_string(),
+            ));
+        }
 
         // Create the VenvCleaner instance
         let cleaner = VenvCleaner::new(
@@ -67,30 +700,419 @@ impl CliMode {
             force_mode,
             dry_run,
             verbosity,
-        );
+        )
+        .with_policy(config.policy)
+        .with_delete_mode(delete_mode)
+        // Between prompts there is time to remove the previous .venv
+        .with_background_removal(!force_mode)
+        .with_gitignore(respect_gitignore)
+        .with_skip_hidden(resolve_skip_hidden(matches, &config.scan))
+        .with_validation(!matches.get_flag("no-validate"))
+        .with_excludes(config.scan.exclude_paths())
+        .with_includes(resolve_includes(matches)?)
+        .with_detectors(Detector::from_configs(&config.detectors)?)
+        .with_delete_hooks(config.hooks)
+        .with_archive_dir(resolve_archive_dir(matches))
+        .with_size_timeout(resolve_size_timeout(matches))
+        .with_scan_threads(matches.get_one::<usize>("scan-threads").copied())
+        .with_delete_jobs(matches.get_one::<usize>("delete-jobs").copied())
+        .with_extra_roots(extra_roots)
+        .with_temp_roots(resolve_temp_roots(matches, &config.scan))
+        .with_owner_filter(resolve_owner_filter(matches)?)
+        .with_venv_filter(resolve_venv_filter(matches, &config.scan)?)
+        .with_exclude_size_above(resolve_exclude_size_above(matches)?)
+        .with_offline_index(resolve_offline_index(matches)?)
+        .with_low_memory(low_memory)
+        // Low-memory runs write no caches, checkpoints included
+        .with_checkpoints(ScanCheckpoint::default_dir().filter(|_| !low_memory), matches.get_flag("resume"));
+        let inside_environments = cleaner.roots_inside_environments();
+        check_force_run_inside_environment(
+            force_mode && !dry_run,
+            matches.get_flag("i-know-what-im-doing"),
+            &inside_environments,
+        )?;
 
         Ok(Self {
             cleaner,
             query_mode,
-            show_progress: verbosity == 0, // Show progress only when not in verbose mode
+            show_progress: verbosity == 0 && !pager::is_active(), // Show progress only when not in verbose mode or paging
+            deep_clean,
+            include_user_data,
+            include_artifacts,
+            progress_format,
+            show_last_install,
+            show_activity,
+            show_size_delta,
+            output,
+            low_memory,
+            group_by,
+            sort_by,
+            watch,
+            show_file_count,
+            show_inodes,
+            check_permissions,
+            inside_environments,
+            allow_escalation,
+            lock_mode,
+            summary_json: matches.get_one::<String>("summary-json").map(PathBuf::from),
+            decision_hook: config.decision_hook,
+            confirmation: Arc::new(prompter.clone()),
+            prompter,
         })
     }
 
+    /// Answer the questions asked before each deletion with a custom provider instead of stdin
+    ///
+    /// # Arguments
+    /// * `provider` - The provider, e.g. a closure checking an approval ticket
+    pub fn with_confirmation_provider(mut self, provider: Arc<dyn ConfirmationProvider>) -> Self {
+        self.confirmation = provider;
+        self
+    }
+
+    /// Scan and delete through another file system than the disk, e.g. a `MemoryFs` in tests
+    ///
+    /// # Arguments
+    /// * `fs` - The file system
+    pub fn with_fs_backend(mut self, fs: Arc<dyn FsBackend>) -> Self {
+        self.cleaner = self.cleaner.with_fs_backend(fs);
+        self
+    }
+
     /// Execute the CLI mode operations
     pub fn execute(&self) -> Result<()> {
         info!("Executing CLI mode");
 
+        // Held until the run ends, so a concurrent cleanup of the same roots waits or stops
+        let started = Utc::now();
+        let _locks = self.lock_roots()?;
+
+        if self.progress_format == ProgressFormat::Json {
+            return self.execute_json(started);
+        }
+        if self.low_memory {
+            return self.execute_low_memory(started);
+        }
+        if let Some(format) = self.output {
+            return self.execute_report(format, started);
+        }
+        if let Some(interval) = self.watch {
+            return self.execute_watch(interval);
+        }
+
         // Print initial information
         self.print_header();
+        if !self.confirm_roots_inside_environments()? {
+            println!("{}", "Nothing scanned.".dimmed());
+            return Ok(());
+        }
 
-        // Find .venv directories
-        let venv_dirs = self.find_venv_directories()?;
+        // Settle interrupted deletions first, so restored .venv directories are scanned
+        self.handle_pending_deletions(&self.cleaner.find_pending_deletions())?;
+
+        // Find .venv directories; archived ones alone are still worth reporting
+        let result = self.find_venv_directories();
+        let archived = self.cleaner.last_archived_venvs();
+        let venv_dirs = match result {
+            Ok(venv_dirs) => venv_dirs,
+            Err(VenvCleanerError::NoVenvFound) if !archived.is_empty() => Vec::new(),
+            Err(e) => {
+                // An audited run that found nothing still leaves its summary
+                if matches!(e, VenvCleanerError::NoVenvFound) && !self.check_permissions {
+                    self.write_summary(&self.start_summary(started))?;
+                }
+                return Err(e);
+            }
+        };
 
-        if self.query_mode {
-            self.handle_query_mode(&venv_dirs)
+        if self.check_permissions {
+            return self.handle_permission_check(&venv_dirs);
+        }
+
+        let summary = if self.query_mode {
+            self.handle_query_mode(&venv_dirs, SortOrder::new(self.sort_by.key()))?;
+            // Only a full listing describes the disk for prompt-summary
+            if !self.cleaner.venv_filter().is_active() && self.cleaner.offline_index().is_none() && !self.cleaner.is_cancelled() {
+                summary_cache::record_scan(&self.cleaner.roots(), &venv_dirs);
+            }
+            self.start_summary(started).with_found(venv_dirs.len())
         } else {
-            self.handle_cleanup_mode(&venv_dirs)
+            self.handle_cleanup_mode(&venv_dirs, self.start_summary(started))?
+        };
+        self.write_summary(&summary)?;
+
+        self.handle_archived(&archived)
+    }
+
+    /// Point out scan roots inside a virtual environment, and have an interactive cleanup confirm them
+    ///
+    /// Force runs were confirmed with `--i-know-what-im-doing` already, and
+    /// runs that delete nothing only get the warning.
+    ///
+    /// # Returns
+    /// Whether to scan
+    fn confirm_roots_inside_environments(&self) -> Result<bool> {
+        for (root, environment) in &self.inside_environments {
+            println!(
+                "⚠️  {}",
+                format!(
+                    "{} is inside {}; the .venv directories found there may be test data of its packages",
+                    root.display(),
+                    environment.display()
+                )
+                .yellow()
+            );
+        }
+        if self.inside_environments.is_empty()
+            || self.query_mode
+            || self.check_permissions
+            || self.cleaner.is_dry_run()
+            || self.cleaner.is_force_mode()
+        {
+            return Ok(true);
         }
+        self.prompt_yes_no("Scan inside the virtual environment anyway?")
+    }
+
+    /// Describe the scan of this run for the metadata ending reports
+    fn scan_metadata(&self, started: DateTime<Utc>) -> ScanMetadata {
+        ScanMetadata::new(&self.cleaner.roots(), self.cleaner.is_recursive(), started)
+    }
+
+    /// Start the summary of this run for `--summary-json`
+    fn start_summary(&self, started: DateTime<Utc>) -> RunSummary {
+        RunSummary::new(started, &self.cleaner.roots(), self.query_mode, self.cleaner.is_dry_run())
+    }
+
+    /// Write the summary to the `--summary-json` file, if one was given
+    fn write_summary(&self, summary: &RunSummary) -> Result<()> {
+        match &self.summary_json {
+            Some(path) => summary.write_to(path),
+            None => Ok(()),
+        }
+    }
+
+    /// Lock the scanned roots if this run may delete anything
+    ///
+    /// # Returns
+    /// The held locks (none for read-only runs or with `--force-lock`), or
+    /// `AlreadyRunning` if another instance holds one and `--wait` was not given
+    fn lock_roots(&self) -> Result<Vec<RunLock>> {
+        if self.query_mode || self.check_permissions || self.cleaner.is_dry_run() {
+            return Ok(Vec::new());
+        }
+        let Some(mode) = self.lock_mode else {
+            warn!("--force-lock: not checking for other instances cleaning the same directories");
+            return Ok(Vec::new());
+        };
+
+        let roots = self.cleaner.roots();
+        let lock_dir = run_lock::default_lock_dir();
+        match RunLock::acquire_all(&roots, &lock_dir, LockMode::FailFast) {
+            Err(VenvCleanerError::AlreadyRunning { root, .. }) if mode == LockMode::Wait => {
+                // On stderr, so the JSON progress stream stays clean
+                eprintln!("⏳ Another instance is cleaning {}; waiting for it to finish...", root);
+                RunLock::acquire_all(&roots, &lock_dir, LockMode::Wait)
+            }
+            result => result,
+        }
+    }
+
+    /// Execute query mode printing nothing but a report to stdout
+    ///
+    /// The rows are sorted as `--sort` says; an empty scan gives an empty report.
+    fn execute_report(&self, format: ExportFormat, started: DateTime<Utc>) -> Result<()> {
+        let mut venv_dirs = match self.cleaner.find_venv_directories() {
+            Ok(venv_dirs) => venv_dirs,
+            Err(VenvCleanerError::NoVenvFound) => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        SortOrder::new(self.sort_by.key()).sort(&mut venv_dirs);
+
+        let rows: Vec<&VenvInfo> = venv_dirs.iter().collect();
+        let metadata = self.scan_metadata(started).with_finished(Utc::now());
+        print!("{}", format.reporter().render(&rows, &metadata));
+        self.write_summary(&self.start_summary(started).with_found(venv_dirs.len()))
+    }
+
+    /// Execute query mode keeping none of the .venv directories found
+    ///
+    /// With `--output` each row goes to stdout as soon as it is found, in walk
+    /// order, and the headline to stderr; otherwise only the headline is
+    /// printed. No table, recommendations or caches need the whole scan.
+    fn execute_low_memory(&self, started: DateTime<Utc>) -> Result<()> {
+        let reporter = self.output.map(ExportFormat::reporter);
+        let mut stdout = io::stdout().lock();
+        if let Some(reporter) = reporter {
+            write!(stdout, "{}", reporter.header())?;
+        }
+
+        let (mut count, mut total_size) = (0, ByteSize::default());
+        let mut write_error = None;
+        let result = self.cleaner.find_venv_directories_with(|venv_info| {
+            if let Some(reporter) = reporter {
+                if let Err(e) = write!(stdout, "{}", reporter.row(count, venv_info, started)) {
+                    write_error.get_or_insert(e);
+                }
+            }
+            count += 1;
+            total_size += venv_info.size();
+        });
+        match result {
+            Ok(_) | Err(VenvCleanerError::NoVenvFound) => {}
+            Err(e) => return Err(e),
+        }
+        if let Some(e) = write_error {
+            return Err(e.into());
+        }
+
+        let headline = format!(
+            "{} {} .venv directories found, total size: {}",
+            "Summary:".bold(),
+            count.to_string().cyan(),
+            total_size.to_string().cyan()
+        );
+        match reporter {
+            Some(reporter) => {
+                write!(stdout, "{}", reporter.footer(count, &self.scan_metadata(started).with_finished(Utc::now())))?;
+                eprintln!("{}", headline);
+            }
+            None => writeln!(stdout, "{}", headline)?,
+        }
+        drop(stdout);
+        self.write_summary(&self.start_summary(started).with_found(count))
+    }
+
+    /// Execute the CLI operations emitting line-delimited JSON progress events
+    ///
+    /// Nothing but events is written to stdout, and venvs are reported in
+    /// path order so the stream is deterministic for a given tree.
+    fn execute_json(&self, started: DateTime<Utc>) -> Result<()> {
+        if let Some(warning) = self.cleaner.trash_warning() {
+            warn!("{}", warning);
+        }
+        let metadata = self.scan_metadata(started);
+        Self::emit(&ProgressEvent::scan_started(&metadata));
+
+        // Each .venv is reported as soon as it is sized, not when the whole scan is done
+        let found = self.cleaner.find_venv_directories_with(|venv_info| Self::emit(&ProgressEvent::venv_found(venv_info)));
+        let mut venv_dirs = match found {
+            Ok(venv_dirs) => venv_dirs,
+            Err(VenvCleanerError::NoVenvFound) => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        venv_dirs.sort_by(|a, b| a.compare_by_path(b));
+        for folder in self.cleaner.last_invalid_venvs() {
+            warn!("Skipped {}: named .venv but not a virtual environment (--no-validate includes it)", folder.display());
+        }
+
+        let mut summary = self.start_summary(started).with_found(venv_dirs.len());
+
+        if !self.query_mode {
+            // Without a prompt, only --include-user-data confirms deleting user files
+            let (to_delete, held_back): (Vec<VenvInfo>, Vec<VenvInfo>) = venv_dirs
+                .iter()
+                .cloned()
+                .partition(|venv_info| self.include_user_data || !venv_info.has_user_data());
+
+            // Entries are only counted for real deletions; dry runs finish instantly
+            let mut estimator = if self.cleaner.is_dry_run() {
+                DeletionEstimator::from_counts([])
+            } else {
+                DeletionEstimator::for_venvs(&to_delete)
+            };
+            let mut report = |venv_info: &VenvInfo, result: &Result<()>| {
+                let (freed, error) = match result {
+                    Ok(()) => {
+                        summary.record_deleted(venv_info.size().bytes());
+                        summary.record_decision(
+                            venv_info.path().display().to_string(),
+                            true,
+                            self.decision_reason(venv_info, "--force"),
+                        );
+                        (venv_info.size().bytes(), None)
+                    }
+                    Err(e) => {
+                        summary.record_error(venv_info.path().display().to_string(), e);
+                        (0, Some(e.to_string()))
+                    }
+                };
+
+                estimator.record(venv_info.path());
+                Self::emit(&ProgressEvent::DeleteDone {
+                    path: venv_info.path().display().to_string(),
+                    freed_bytes: freed,
+                    dry_run: self.cleaner.is_dry_run(),
+                    error,
+                    eta_seconds: estimator.remaining().map(|eta| eta.as_secs()),
+                });
+            };
+
+            for venv_info in &held_back {
+                report(venv_info, &Err(Self::user_data_error(venv_info)));
+            }
+
+            // The core prints a human-readable line for dry runs, so only its checks are run
+            if self.cleaner.is_dry_run() {
+                for venv_info in &to_delete {
+                    let checked = self.cleaner.check_policy(venv_info).and_then(|()| self.cleaner.check_owner(venv_info));
+                    report(venv_info, &checked);
+                }
+            } else {
+                self.cleaner.delete_many(&to_delete, &BatchOptions::new(), |item| report(&item.venv, &item.result));
+            }
+        }
+
+        let finished = Utc::now();
+        Self::emit(&ProgressEvent::Summary {
+            found: venv_dirs.len(),
+            deleted: summary.deleted(),
+            freed_bytes: summary.freed_bytes(),
+            errors: summary.errors().len(),
+            finished_at: timestamps::to_rfc3339(&finished),
+            duration_seconds: metadata.with_finished(finished).duration_seconds(),
+        });
+
+        self.write_summary(&summary)
+    }
+
+    /// Error for a .venv kept because it holds user data and nothing confirmed deleting it
+    fn user_data_error(venv_info: &VenvInfo) -> VenvCleanerError {
+        VenvCleanerError::InvalidArgument(format!(
+            "{} contains user data ({}); pass --include-user-data to delete it without a prompt",
+            venv_info.path().display(),
+            ByteSize::new(venv_info.user_data_bytes())
+        ))
+    }
+
+    /// Describe the user data found in a .venv, naming the largest files
+    fn describe_user_data(venv_info: &VenvInfo) -> String {
+        let names: Vec<String> = venv_info
+            .user_data()
+            .iter()
+            .take(3)
+            .map(|file| {
+                file.path
+                    .strip_prefix(venv_info.path())
+                    .unwrap_or(&file.path)
+                    .display()
+                    .to_string()
+            })
+            .collect();
+        let more = venv_info.user_data().len().saturating_sub(names.len());
+
+        format!(
+            "{} file(s), {}: {}{}",
+            venv_info.user_data().len(),
+            ByteSize::new(venv_info.user_data_bytes()),
+            names.join(", "),
+            if more > 0 { format!(" and {} more", more) } else { String::new() }
+        )
+    }
+
+    /// Write a single progress event to stdout as one line of JSON
+    fn emit(event: &ProgressEvent) {
+        println!("{}", event.to_json_line());
     }
 
     /// Find .venv directories with optional progress indication
@@ -108,17 +1130,136 @@ impl CliMode {
             None
         };
 
-        let result = self.cleaner.find_venv_directories();
+        // Show results as they stream in, which matters most for broad priority scans
+        let mut found = 0usize;
+        let result = self.cleaner.find_venv_directories_with(|venv_info| {
+            found += 1;
+            if let Some(pb) = &progress {
+                pb.set_message(format!("Searching for .venv directories... {} found (latest: {})",
+                        found,
+                        venv_info.project_name().unwrap_or_default()));
+            }
+        });
 
         if let Some(pb) = progress {
             pb.finish_with_message("Search completed");
         }
 
+        // -vv shows where a slow scan spent its time; stderr keeps machine output clean
+        if self.cleaner.verbosity() >= 2 {
+            let stats = self.cleaner.last_scan_stats();
+            if !stats.is_empty() {
+                eprintln!("{}", "Scan statistics (slowest first):".bold());
+                for directory in stats {
+                    eprintln!("  {}", directory);
+                }
+            }
+        }
+        self.report_unreadable_directories();
+        self.report_invalid_venvs();
+
         result
     }
 
+    /// Report the directories the scan could not read in one line, listing them with -vv
+    fn report_unreadable_directories(&self) {
+        let unreadable = self.cleaner.last_unreadable_directories();
+        if unreadable.is_empty() {
+            return;
+        }
+        let noun = if unreadable.len() == 1 { "directory" } else { "directories" };
+        if self.cleaner.verbosity() >= 2 {
+            eprintln!("{}", format!("⚠️  Skipped {} unreadable {}:", unreadable.len(), noun).yellow());
+            for directory in &unreadable {
+                eprintln!("  {}", directory.display());
+            }
+        } else {
+            eprintln!(
+                "{} {}",
+                format!("⚠️  Skipped {} unreadable {}", unreadable.len(), noun).yellow(),
+                "(-vv lists them)".dimmed()
+            );
+        }
+    }
+
+    /// Tell which folders named .venv were skipped as not virtual environments
+    ///
+    /// Forced runs list every one, since nobody reviews what they delete;
+    /// otherwise a count is printed unless `-v` is given.
+    fn report_invalid_venvs(&self) {
+        let invalid = self.cleaner.last_invalid_venvs();
+        if invalid.is_empty() {
+            return;
+        }
+        let summary = if invalid.len() == 1 {
+            "⚠️  Skipped 1 folder named .venv that does not look like a virtual environment".to_string()
+        } else {
+            format!("⚠️  Skipped {} folders named .venv that do not look like virtual environments", invalid.len())
+        };
+        if self.cleaner.is_force_mode() || self.cleaner.verbosity() >= 1 {
+            eprintln!("{} {}", format!("{}:", summary).yellow(), "(--no-validate includes them)".dimmed());
+            for folder in &invalid {
+                eprintln!("  ⏭️  {}", folder.display());
+            }
+        } else {
+            eprintln!("{} {}", summary.yellow(), "(-v lists them, --no-validate includes them)".dimmed());
+        }
+    }
+
+    /// Rescan and redraw the query mode table every interval until `q` is typed
+    ///
+    /// Between refreshes a typed line changes the view: `s` sorts by the
+    /// next column, `r` reverses the order, `q` stops and anything else
+    /// (such as Enter alone) rescans at once. The space freed or taken up
+    /// since the first scan is shown above the table.
+    fn execute_watch(&self, interval: Duration) -> Result<()> {
+        let mut sort_by = self.sort_by;
+        let mut reverse = false;
+        let mut first_total = None;
+
+        loop {
+            let venv_dirs = match self.cleaner.find_venv_directories() {
+                Ok(venv_dirs) => venv_dirs,
+                Err(VenvCleanerError::NoVenvFound) => Vec::new(),
+                Err(e) => return Err(e),
+            };
+            let total: u64 = venv_dirs.iter().map(|venv| venv.size().bytes()).sum();
+            let first_total = *first_total.get_or_insert(total);
+
+            // Clear the screen and start at the top, as `watch` does
+            print!("\x1B[2J\x1B[H");
+            println!(
+                "{} every {}s, sorted by {}{} at {}",
+                "Watching".bold(),
+                interval.as_secs(),
+                sort_by.label(),
+                if reverse { " (reversed)" } else { "" },
+                timestamps::format_time(&Utc::now())
+            );
+            println!("{}", "Enter: rescan now · s: sort by the next column · r: reverse · q: quit".dimmed());
+            match total.cmp(&first_total) {
+                std::cmp::Ordering::Less => println!("♻️  {} freed since watching began", ByteSize::new(first_total - total).to_string().green()),
+                std::cmp::Ordering::Greater => println!("📈 {} more since watching began", ByteSize::new(total - first_total).to_string().yellow()),
+                std::cmp::Ordering::Equal => {}
+            }
+            self.handle_query_mode(&venv_dirs, SortOrder::new(sort_by.key()).with_reverse(reverse))?;
+            io::stdout().flush()?;
+
+            match prompt::read_line_within(interval).map(|line| WatchCommand::from_line(&line)) {
+                Some(WatchCommand::Quit) => return Ok(()),
+                Some(WatchCommand::NextSort) => sort_by = sort_by.next(self.show_file_count),
+                Some(WatchCommand::Reverse) => reverse = !reverse,
+                Some(WatchCommand::Refresh) | None => {}
+            }
+        }
+    }
+
     /// Handle query mode (list .venv directories with information)
-    fn handle_query_mode(&self, venv_dirs: &[VenvInfo]) -> Result<()> {
+    ///
+    /// # Arguments
+    /// * `venv_dirs` - The .venv directories found
+    /// * `order` - How to sort the table
+    fn handle_query_mode(&self, venv_dirs: &[VenvInfo], order: SortOrder) -> Result<()> {
         println!("\n{}", "Found .venv directories:".bold().green());
         println!("{}", "=".repeat(80).dimmed());
 
@@ -127,66 +1268,137 @@ impl CliMode {
             return Ok(());
         }
 
-        // Sort by size (largest first) for query mode
+        // Sort by size (largest first) for query mode, unless --sort says otherwise
         let mut sorted_dirs = venv_dirs.to_vec();
-        sorted_dirs.sort_by(|a, b| a.compare_by_size(b));
+        order.sort(&mut sorted_dirs);
+
+        // Projects in git repositories get their last commit next to the last-used date;
+        // asking git is slow, so commits (and activity) come from the activity cache
+        let mut cache = ActivityCache::load_default();
+        if self.cleaner.offline_index().is_none() {
+            sorted_dirs = cache.with_last_commits(sorted_dirs);
+        }
+        let activity = self.show_activity.then(|| Self::project_activity(&mut cache, &sorted_dirs));
+        if let Err(e) = cache.save() {
+            warn!("Failed to save activity cache: {}", e);
+        }
 
         // Calculate totals
-        let total_size: u64 = venv_dirs.iter().map(|v| v.size_bytes()).sum();
+        let total_size: ByteSize = venv_dirs.iter().map(VenvInfo::size).sum();
         let total_count = venv_dirs.len();
 
+        // Every listing of this machine records its sizes for the next one to compare with
+        let size_deltas = self.cleaner.offline_index().is_none().then(|| size_deltas::record_scan(&sorted_dirs));
+
+        // Root and --all-users see other users' environments, so say whose each one is
+        let columns = QueryColumns {
+            show_owner: ownership::is_root() || self.cleaner.owner_filter().includes_others(),
+            show_last_commit: sorted_dirs.iter().any(|v| v.project_last_commit().is_some()),
+            activity,
+            size_deltas: size_deltas.filter(|_| self.show_size_delta),
+        };
+
         // Print header
-        println!(
+        let mut width = if self.show_last_install { 141 } else { 120 };
+        if columns.show_owner {
+            width += 13;
+        }
+        if columns.show_last_commit {
+            width += 13;
+        }
+        if columns.activity.is_some() {
+            width += 17;
+        }
+        if columns.size_deltas.is_some() {
+            width += 13;
+        }
+        if self.show_file_count {
+            width += 11;
+        }
+        if self.show_inodes {
+            width += 11;
+        }
+        print!(
             "{:<60} {:<12} {:<20} {:<20}",
             "Location".bold(),
             "Size".bold(),
             "Created".bold(),
             "Last Used".bold()
         );
-        println!("{}", "-".repeat(120).dimmed());
-
-        // Print each .venv directory
-        for venv_info in &sorted_dirs {
-            let location = self.format_location_for_display(&venv_info.location(), 58);
-            let size = if venv_info.size_bytes() > 1024 * 1024 * 1024 {
-                venv_info.size_formatted().red().to_string()
-            } else if venv_info.size_bytes() > 100 * 1024 * 1024 {
-                venv_info.size_formatted().yellow().to_string()
-            } else {
-                venv_info.size_formatted().normal().to_string()
-            };
-
-            let age_color = if venv_info.is_recently_used() {
-                "green"
-            } else if venv_info.is_old() {
-                "red"
-            } else {
-                "normal"
-            };
+        if self.show_file_count {
+            print!(" {:<10}", "Files".bold());
+        }
+        if self.show_inodes {
+            print!(" {:<10}", "Inodes".bold());
+        }
+        if columns.show_owner {
+            print!(" {:<12}", "Owner".bold());
+        }
+        if columns.show_last_commit {
+            print!(" {:<12}", "Last Commit".bold());
+        }
+        if columns.activity.is_some() {
+            print!(" {:<16}", "Project Activity".bold());
+        }
+        if columns.size_deltas.is_some() {
+            print!(" {:<12}", "Δ Last Scan".bold());
+        }
+        if self.show_last_install {
+            print!(" {:<20}", "Last Install".bold());
+        }
+        println!();
+        println!("{}", "-".repeat(width).dimmed());
 
-            let last_used = match age_color {
-                "green" => venv_info.last_modified_formatted().green().to_string(),
-                "red" => venv_info.last_modified_formatted().red().to_string(),
-                _ => venv_info.last_modified_formatted(),
-            };
+        // Print each .venv directory, optionally under one heading per group
+        match self.group_by {
+            GroupBy::None => {
+                for venv_info in &sorted_dirs {
+                    self.print_query_row(venv_info, &columns);
+                }
+            }
+            GroupBy::Age => {
+                for bucket in AgeBucket::ALL {
+                    let rows: Vec<&VenvInfo> = sorted_dirs.iter().filter(|v| v.age_bucket() == bucket).collect();
+                    if rows.is_empty() {
+                        continue;
+                    }
 
-            println!(
-                "{:<60} {:<12} {:<20} {:<20}",
-                location,
-                size,
-                venv_info.created_formatted().dimmed(),
-                last_used
-            );
+                    let bucket_size: ByteSize = rows.iter().map(|v| v.size()).sum();
+                    println!(
+                        "{} ({} directories, {})",
+                        format!("▸ Last used {}", bucket.label()).bold(),
+                        rows.len(),
+                        bucket_size.to_string().cyan()
+                    );
+                    for venv_info in rows {
+                        self.print_query_row(venv_info, &columns);
+                    }
+                }
+            }
         }
 
         // Print summary
-        println!("{}", "-".repeat(120).dimmed());
+        println!("{}", "-".repeat(width).dimmed());
         println!(
             "\n{} {} .venv directories found, total size: {}",
             "Summary:".bold(),
             total_count.to_string().cyan(),
-            Self::format_size(total_size).cyan()
+            total_size.to_string().cyan()
         );
+        self.print_root_subtotals(venv_dirs);
+        self.print_inode_usage(venv_dirs);
+
+        // Sizes marked with ≥ stopped at --size-timeout; say how to get them in full
+        let partial = sorted_dirs.iter().filter(|v| v.is_size_partial()).count();
+        if partial > 0 {
+            let timeout = self.cleaner.size_timeout().map(|t| t.as_secs()).unwrap_or_default();
+            println!(
+                "{} {} sizes (marked ≥) are partial: sizing stopped after {}s. Rerun with --size-timeout 0 to size them fully.",
+                "Note:".yellow().bold(),
+                partial,
+                timeout
+            );
+        }
 
         // Show recommendations
         self.print_recommendations(&sorted_dirs);
@@ -194,89 +1406,727 @@ impl CliMode {
         Ok(())
     }
 
+    /// Print one row of the query mode table
+    ///
+    /// # Arguments
+    /// * `venv_info` - The .venv directory to print
+    /// * `columns` - The optional columns of the table
+    fn print_query_row(&self, venv_info: &VenvInfo, columns: &QueryColumns) {
+        // Keep the badges visible by truncating the path a little more
+        let mut badges = Vec::new();
+        if venv_info.has_user_data() {
+            badges.push("[user data]".red());
+        } else if venv_info.has_editable_install() {
+            badges.push("[editable]".magenta());
+        }
+        if let Some(kind) = venv_info.project_kind() {
+            badges.push(format!("[{}]", kind.badge()).cyan());
+        }
+        if venv_info.removable_volume().is_some() {
+            badges.push("[removable]".yellow());
+        }
+        let badges_width: usize = badges.iter().map(|badge| badge.chars().count() + 1).sum();
+        let mut location = self.format_location_for_display(&venv_info.location(), 58 - badges_width);
+        for badge in badges {
+            location = format!("{} {}", location, badge);
+        }
+        let size = if venv_info.size().bytes() > 1024 * 1024 * 1024 {
+            venv_info.size_formatted().red().to_string()
+        } else if venv_info.size().bytes() > 100 * 1024 * 1024 {
+            venv_info.size_formatted().yellow().to_string()
+        } else {
+            venv_info.size_formatted().normal().to_string()
+        };
+
+        let age_color = if venv_info.is_recently_used() {
+            "green"
+        } else if venv_info.is_old() {
+            "red"
+        } else {
+            "normal"
+        };
+
+        let last_used = match age_color {
+            "green" => venv_info.last_modified_formatted().green().to_string(),
+            "red" => venv_info.last_modified_formatted().red().to_string(),
+            _ => venv_info.last_modified_formatted(),
+        };
+
+        print!(
+            "{:<60} {:<12} {:<20} {:<20}",
+            location,
+            size,
+            venv_info.created_formatted().dimmed(),
+            last_used
+        );
+        if self.show_file_count {
+            // Environments with 100k+ files take long to delete and use up inodes
+            let files = venv_info.file_count_formatted();
+            let files = if venv_info.file_count().unwrap_or(0) >= 100_000 { files.yellow().to_string() } else { files };
+            print!(" {:<10}", files);
+        }
+        if self.show_inodes {
+            let inodes = venv_info.inode_count().map(FileUtils::format_count).unwrap_or_else(|| "-".to_string());
+            print!(" {:<10}", inodes);
+        }
+        if columns.show_owner {
+            print!(" {:<12}", ownership::describe_owner(venv_info.owner_uid()));
+        }
+        if columns.show_last_commit {
+            // An old .venv of a project that is still committed to is probably still needed
+            let last_commit = venv_info.project_last_commit_formatted();
+            let last_commit = if venv_info.is_project_active() { last_commit.green().to_string() } else { last_commit };
+            print!(" {:<12}", last_commit);
+        }
+        if let Some(activity) = &columns.activity {
+            let activity = activity
+                .get(venv_info.path())
+                .copied()
+                .flatten()
+                .map(|time| timestamps::format_date(&time))
+                .unwrap_or_else(|| "-".to_string());
+            print!(" {:<16}", activity);
+        }
+        if let Some(size_deltas) = &columns.size_deltas {
+            // The environments that grew the most are often the ones worth a look
+            let delta = size_deltas.describe(venv_info.path());
+            let delta = match size_deltas.get(venv_info.path()) {
+                _ if size_deltas.is_top_growth(venv_info.path()) => delta.red().bold().to_string(),
+                Some(bytes) if bytes > 0 => delta.yellow().to_string(),
+                Some(bytes) if bytes < 0 => delta.green().to_string(),
+                _ => delta.dimmed().to_string(),
+            };
+            print!(" {:<12}", delta);
+        }
+        if self.show_last_install {
+            print!(" {:<20}", venv_info.last_install_formatted());
+        }
+        println!();
+    }
+
+    /// Look up the last activity of each .venv's project
+    ///
+    /// Walking project trees is slow, so values come from the activity cache
+    /// when they are less than a day old.
+    ///
+    /// # Arguments
+    /// * `cache` - The activity cache
+    /// * `venv_dirs` - The .venv directories in the table
+    ///
+    /// # Returns
+    /// The project activity by .venv path
+    fn project_activity(cache: &mut ActivityCache, venv_dirs: &[VenvInfo]) -> HashMap<PathBuf, Option<DateTime<Utc>>> {
+        venv_dirs
+            .iter()
+            .map(|venv_info| (venv_info.path().to_path_buf(), cache.activity(venv_info)))
+            .collect()
+    }
+
     /// Handle cleanup mode (delete .venv directories)
-    fn handle_cleanup_mode(&self, venv_dirs: &[VenvInfo]) -> Result<()> {
+    ///
+    /// # Arguments
+    /// * `venv_dirs` - The .venv directories found
+    /// * `summary` - The run summary to record the outcome in
+    ///
+    /// # Returns
+    /// The summary with every deletion and failure recorded
+    fn handle_cleanup_mode(&self, venv_dirs: &[VenvInfo], mut summary: RunSummary) -> Result<RunSummary> {
+        summary = summary.with_found(venv_dirs.len());
         if venv_dirs.is_empty() {
             println!("{}", "No .venv directories found.".yellow());
-            return Ok(());
+            return Ok(summary);
         }
 
         println!("\n{} {} .venv directories found:",
                 "Found".green(),
                 venv_dirs.len().to_string().cyan());
 
-        let mut deleted_count = 0;
         let mut total_freed = 0u64;
         let mut errors = Vec::new();
 
-        for venv_info in venv_dirs {
-            match self.process_venv_directory(venv_info) {
-                Ok(deleted) => {
-                    if deleted {
-                        deleted_count += 1;
-                        total_freed += venv_info.size_bytes();
-                    }
-                }
+        // Force mode deletes back to back, so the remaining time can be estimated
+        let mut estimate = (self.cleaner.is_force_mode() && !self.cleaner.is_dry_run() && venv_dirs.len() > 1)
+            .then(|| DeletionEstimator::for_venvs(venv_dirs));
+
+        // A force run is one batch; each prompt in an interactive run is its own
+        let batch_limit = self.cleaner.policy().max_batch_size.filter(|_| self.cleaner.is_force_mode());
+
+        let mut results = Vec::new();
+        let mut deletions = 0;
+        for (index, venv_info) in venv_dirs.iter().enumerate() {
+            if batch_limit.is_some_and(|limit| deletions >= limit) {
+                println!(
+                    "\n⏭️  {}",
+                    format!(
+                        "Stopped after {} deletions, the most the policy allows at once; {} .venv directories were left alone",
+                        deletions,
+                        venv_dirs.len() - index
+                    )
+                    .yellow()
+                );
+                break;
+            }
+            if let Some(estimator) = &estimate {
+                if index > 0 {
+                    println!(
+                        "⏱️  {} remaining, {}",
+                        venv_dirs.len() - index,
+                        deletion_eta::format_eta(estimator.remaining()).dimmed()
+                    );
+                }
+            }
+            let result = self.process_venv_directory(venv_info);
+            if let Some(estimator) = &mut estimate {
+                estimator.record(venv_info.path());
+            }
+            if result.as_ref().is_ok_and(|outcome| outcome.deleted) {
+                deletions += 1;
+            }
+            results.push((venv_info, result));
+        }
+
+        // Interactive runs remove the renamed .venv directories in the background
+        let mut failed_removals = self.finish_background_removals(venv_dirs);
+        let mut deleted_count = 0;
+        for (venv_info, result) in results {
+            let result = match failed_removals.remove(venv_info.path()) {
+                Some(error) => Err(error),
+                None => result,
+            };
+            match result {
+                Ok(outcome) => {
+                    if outcome.deleted {
+                        deleted_count += 1;
+                        summary.record_deleted(outcome.freed_bytes);
+                    }
+                    let reason = self.decision_reason(venv_info, outcome.reason);
+                    let action = if outcome.deleted { "Deleted" } else { "Kept" };
+                    info!("{} {}: {}", action, venv_info.path().display(), reason);
+                    summary.record_decision(venv_info.path().display().to_string(), outcome.deleted, reason);
+                    total_freed += outcome.freed_bytes;
+                }
+                Err(e) => {
+                    summary.record_error(venv_info.path().display().to_string(), &e);
+                    errors.push((venv_info.path().display().to_string(), e));
+                }
+            }
+        }
+
+        // Print summary
+        self.print_cleanup_summary(deleted_count, total_freed, &errors);
+        self.print_root_subtotals(venv_dirs);
+
+        Ok(summary)
+    }
+
+    /// Report whether each .venv could be deleted, without deleting anything
+    fn handle_permission_check(&self, venv_dirs: &[VenvInfo]) -> Result<()> {
+        if venv_dirs.is_empty() {
+            println!("{}", "No .venv directories found.".yellow());
+            return Ok(());
+        }
+
+        println!("\n{}", "Permission check (nothing is deleted):".bold().cyan());
+        let mut deletable = 0;
+        for venv_info in venv_dirs {
+            let report = PermissionReport::check(venv_info.path());
+            if report.is_deletable() {
+                deletable += 1;
+                println!("✅ {}", venv_info.path().display());
+                continue;
+            }
+
+            println!("❌ {}", venv_info.path().display().to_string().red());
+            for problem in &report.problems {
+                println!("     {}", problem.describe());
+            }
+            if report.truncated {
+                println!("     {}", "... and more".dimmed());
+            }
+        }
+
+        let blocked = venv_dirs.len() - deletable;
+        println!(
+            "\n{} of {} .venv directories can be deleted",
+            deletable.to_string().green(),
+            venv_dirs.len()
+        );
+        if blocked > 0 {
+            println!(
+                "{} {} would fail; fix their ownership (e.g. `sudo chown -R $USER PATH`) or attributes first",
+                "⚠️".yellow(),
+                blocked
+            );
+        }
+
+        Ok(())
+    }
+
+    /// List archived .venv directories and, in interactive cleanup, offer to
+    /// restore them or delete their archives
+    fn handle_archived(&self, archived: &[ArchivedVenv]) -> Result<()> {
+        if archived.is_empty() {
+            return Ok(());
+        }
+
+        println!("\n{}", "Archived .venv directories:".bold().cyan());
+        println!(
+            "{:<60} {:<12} {:<12} {:<20}",
+            "Location".bold(),
+            "Compressed".bold(),
+            "Original".bold(),
+            "Archived".bold()
+        );
+        println!("{}", "-".repeat(107).dimmed());
+        for entry in archived {
+            let compressed = match entry.compressed_size() {
+                Some(size) => ByteSize::new(size).to_string(),
+                None => "missing".red().to_string(),
+            };
+            println!(
+                "{:<60} {:<12} {:<12} {:<20}",
+                self.format_location_for_display(&entry.location(), 58),
+                compressed,
+                ByteSize::new(entry.stub().original_size()).to_string().dimmed(),
+                timestamps::format_date_time(entry.stub().archived_at()).dimmed()
+            );
+        }
+
+        let compressed_total: u64 = archived.iter().filter_map(|a| a.compressed_size()).sum();
+        println!(
+            "{} {} archived, {} on disk",
+            "Archives:".bold(),
+            archived.len().to_string().cyan(),
+            ByteSize::new(compressed_total).to_string().cyan()
+        );
+
+        // Archives are never touched without asking, so force mode only lists them
+        if self.query_mode || self.cleaner.is_force_mode() {
+            println!("💡 Restore with: {}", "venv_cleaner unarchive -r".green());
+            return Ok(());
+        }
+
+        for entry in archived {
+            println!("\n📦 {}", entry.location().cyan());
+            match self.prompt_archive_action()? {
+                'r' => match self.cleaner.restore_archived_venv(entry) {
+                    Ok(venv_path) => println!("✅ {} {}", "Restored".green(), venv_path.display()),
+                    Err(e) => println!("❌ {}", e.to_string().red()),
+                },
+                'd' => match self.cleaner.delete_archived_venv(entry) {
+                    Ok(freed) => println!("✅ {} ({} freed)", "Archive deleted".green(), ByteSize::new(freed)),
+                    Err(e) => println!("❌ {}", e.to_string().red()),
+                },
+                _ => println!("⏭️  {}", "Skipped".dimmed()),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// List deletions interrupted after the rename and, except in query mode,
+    /// ask whether to finish or undo each of them
+    ///
+    /// Force mode asks as well: a leftover was never offered in this run, so
+    /// `--force` is no answer for it.
+    fn handle_pending_deletions(&self, pending: &[PendingDeletion]) -> Result<()> {
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        println!("\n{}", "Interrupted deletions:".bold().yellow());
+        for entry in pending {
+            let started = entry
+                .started()
+                .map(timestamps::format_date_time)
+                .unwrap_or_else(|| "-".to_string());
+            println!(
+                "{:<60} {:<12} started {}",
+                self.format_location_for_display(&entry.path().display().to_string(), 58),
+                ByteSize::new(entry.size_bytes()).to_string(),
+                started.dimmed()
+            );
+        }
+
+        if self.query_mode || self.check_permissions {
+            println!("💡 Run a cleanup to finish deleting or restore them");
+            return Ok(());
+        }
+
+        for entry in pending {
+            println!("\n🗑️  {}", entry.path().display().to_string().cyan());
+            match self.prompt_pending_action()? {
+                'f' => match self.cleaner.finish_pending_deletion(entry) {
+                    Ok(freed) => println!("✅ {} ({} freed)", "Deletion finished".green(), ByteSize::new(freed)),
+                    Err(e) => println!("❌ {}", e.to_string().red()),
+                },
+                'r' => match self.cleaner.restore_pending_deletion(entry) {
+                    Ok(venv_path) => println!("✅ {} {}", "Restored".green(), venv_path.display()),
+                    Err(e) => println!("❌ {}", e.to_string().red()),
+                },
+                _ => println!("⏭️  {}", "Skipped".dimmed()),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Ask what to do with an interrupted deletion: (f)inish, (r)estore or (s)kip
+    fn prompt_pending_action(&self) -> Result<char> {
+        print!("{} (f/r/S): ", "Finish deleting, restore under its old name or skip?".bold());
+        io::stdout().flush()?;
+
+        let input = self.prompter.read_answer()?;
+
+        Ok(match input.trim().to_lowercase().as_str() {
+            "f" | "finish" => 'f',
+            "r" | "restore" => 'r',
+            _ => 's',
+        })
+    }
+
+    /// Ask what to do with an archived .venv: (r)estore, (d)elete archive or (s)kip
+    fn prompt_archive_action(&self) -> Result<char> {
+        print!("{} (r/d/S): ", "Restore, delete archive or skip?".bold());
+        io::stdout().flush()?;
+
+        let input = self.prompter.read_answer()?;
+
+        Ok(match input.trim().to_lowercase().as_str() {
+            "r" | "restore" => 'r',
+            "d" | "delete" => 'd',
+            _ => 's',
+        })
+    }
+
+    /// Process a single .venv directory (prompt and potentially delete)
+    fn process_venv_directory(&self, venv_info: &VenvInfo) -> Result<ProcessOutcome> {
+        let location = venv_info.location();
+        let size = venv_info.size_formatted();
+        let age_days = venv_info.age_in_days();
+
+        // Show information about this .venv
+        println!("\n{}", "─".repeat(60).dimmed());
+        println!("📁 {}", location.cyan());
+        println!("📏 Size: {}", size);
+        println!("📅 Last used: {} ({} days ago)",
+                venv_info.last_modified_formatted().dimmed(),
+                age_days);
+        // Git is only asked about the .venv on screen, and only when someone is deciding
+        let last_commit = if self.cleaner.is_force_mode() { None } else { venv_info.parent_path().and_then(git::last_commit_time) };
+        if let Some(committed) = last_commit {
+            println!("🌱 Last commit: {} ({} days ago)",
+                    timestamps::format_date(&committed).dimmed(),
+                    (chrono::Utc::now() - committed).num_days());
+        }
+        if self.show_last_install {
+            println!("📦 Last pip install: {}", venv_info.last_install_formatted().dimmed());
+        }
+        if venv_info.has_editable_install() {
+            println!("✏️  {}", "The project is installed in editable mode here; deleting breaks its development setup".magenta());
+        }
+        if venv_info.has_user_data() {
+            println!("🗃️  {} {}", "Contains user data:".red().bold(), Self::describe_user_data(venv_info));
+        }
+        if let Some(volume) = venv_info.removable_volume() {
+            println!("⏏️  On the removable drive at {}; it must stay plugged in until the deletion is done", volume.display());
+        }
+
+        // Add age-based coloring and warnings
+        if venv_info.is_project_active() {
+            println!("💡 {}", "This .venv hasn't changed in over 90 days, but its project has recent commits".cyan());
+        } else if venv_info.is_old() {
+            println!("⚠️  {}", "This .venv hasn't been used in over 90 days".yellow());
+        } else if venv_info.is_recently_used() {
+            println!("✨ {}", "This .venv was used recently".green());
+        }
+
+        if let Some(criteria) = self.cleaner.venv_filter().explain(venv_info) {
+            println!("🎯 Selected by: {}", criteria.dimmed());
+        }
+
+        // In deep clean mode, show the project's build artifacts and the combined size
+        let artifacts = if self.deep_clean {
+            self.cleaner.find_project_artifacts(venv_info)?
+        } else {
+            Vec::new()
+        };
+        if !artifacts.is_empty() {
+            let artifacts_size = ProjectArtifacts::total_size(&artifacts);
+            let names: Vec<String> = artifacts.iter().map(|a| format!("{}/", a.name())).collect();
+            println!("🧱 Build artifacts: {} ({})", names.join(", ").cyan(), ByteSize::new(artifacts_size));
+            println!("📦 Combined size: {}",
+                    (venv_info.size() + ByteSize::new(artifacts_size)).to_string().bold());
+        }
+
+        // A decision hook can keep a .venv, or delete it as if in force mode
+        let decision = self.hook_decision(venv_info);
+        let force_reason = if decision == Some(HookDecision::Delete) { "decision hook" } else { "--force" };
+        let force = match decision {
+            Some(HookDecision::Keep) => {
+                println!("⏭️  {}", "Kept by the decision hook".dimmed());
+                return Ok(ProcessOutcome::kept("kept by the decision hook"));
+            }
+            Some(HookDecision::Ask) if self.cleaner.is_force_mode() => {
+                println!("⏭️  {}", "Skipped: the decision hook asked for confirmation, which --force cannot give".yellow());
+                return Ok(ProcessOutcome::kept("the decision hook asked for a confirmation --force cannot give"));
+            }
+            // The hook stands in for --force, so a policy disabling --force has it confirmed instead
+            Some(HookDecision::Delete) if self.cleaner.policy().check_force(true).is_err() => {
+                println!("🔒 {}", "The decision hook chose to delete, but the policy disables --force; asking instead".yellow());
+                false
+            }
+            Some(HookDecision::Delete) => self.cleaner.is_force_mode() || self.within_force_delete_limit(venv_info),
+            Some(HookDecision::Ask) | None => self.cleaner.is_force_mode(),
+        };
+
+        // Misdetected data directories can be huge; those always need a person to confirm
+        let outlier = self.cleaner.is_size_outlier(venv_info);
+        if outlier {
+            println!("🚨 {}", self.outlier_warning().red().bold());
+            if self.cleaner.is_force_mode() {
+                println!("⏭️  {}", "Skipped: run without --force to confirm deleting it".yellow());
+                return Ok(ProcessOutcome::kept("larger than --exclude-size-above"));
+            }
+        }
+        let force = force && !outlier;
+
+        // In force mode, delete without asking unless user data needs confirming
+        if force && venv_info.has_user_data() && !self.include_user_data {
+            println!("⏭️  {}", "Skipped: contains user data (pass --include-user-data to delete it)".yellow());
+            return Ok(ProcessOutcome::kept("contains user data"));
+        }
+        if force {
+            println!("🗑️  {}", self.deleting_message(self.cleaner.is_force_mode()).red());
+            if !self.delete_unless_vanished(venv_info)? {
+                return Ok(ProcessOutcome::kept("already removed outside VenvCleaner"));
+            }
+            println!("✅ {}", self.deleted_message().green());
+            let mut outcome = ProcessOutcome::deleted(venv_info.size().bytes(), force_reason);
+            // Artifacts were never shown to anyone, so only --include-artifacts deletes them
+            if !artifacts.is_empty() && !self.include_artifacts {
+                println!("⏭️  {}", "Build artifacts kept (pass --include-artifacts to delete them without asking)".yellow());
+            } else {
+                for artifact in &artifacts {
+                    outcome.freed_bytes += self.delete_artifact(artifact);
+                }
+            }
+            return Ok(outcome);
+        }
+
+        // Ask for confirmation; the file count in the first question hints at how long deleting takes
+        if self.cleaner.archive_dir().is_none() && self.cleaner.offline_index().is_none() {
+            if self.cleaner.is_using_trash() {
+                println!("♻️  {}", "Goes to the trash and can be restored".cyan());
+            } else {
+                println!("🔥 {}", "Deleted permanently; it cannot be restored".red());
+            }
+        }
+        for request in self.cleaner.deletion_requests(venv_info) {
+            if matches!(request, ConfirmationRequest::DeleteUserData(_)) && self.include_user_data {
+                continue;
+            }
+            if !self.confirmation.confirm(&request)? {
+                let skipped = match request {
+                    ConfirmationRequest::DeleteUserData(_) => "Skipped (user data kept)",
+                    ConfirmationRequest::DeleteOversized(_) => "Skipped (kept as a possible data directory)",
+                    _ => "Skipped",
+                };
+                println!("⏭️  {}", skipped.dimmed());
+                return Ok(ProcessOutcome::kept(request.declined_reason()));
+            }
+        }
+
+        println!("🗑️  {}", self.deleting_message(false).yellow());
+        if !self.delete_unless_vanished(venv_info)? {
+            return Ok(ProcessOutcome::kept("already removed outside VenvCleaner"));
+        }
+        println!("✅ {}", self.deleted_message().green());
+        let mut outcome = ProcessOutcome::deleted(venv_info.size().bytes(), "confirmed at the prompt");
+
+        // Each build artifact is confirmed on its own, like the .venv
+        for artifact in &artifacts {
+            let request = ConfirmationRequest::DeleteArtifact { venv: venv_info, artifact };
+            if self.confirmation.confirm(&request)? {
+                outcome.freed_bytes += self.delete_artifact(artifact);
+            } else {
+                println!("⏭️  {}", format!("{}/ kept", artifact.name()).dimmed());
+            }
+        }
+
+        Ok(outcome)
+    }
+
+    /// Delete a build artifact, reporting the outcome
+    ///
+    /// A failure is reported and does not stop the other artifacts.
+    ///
+    /// # Returns
+    /// The bytes freed, 0 if the deletion failed
+    fn delete_artifact(&self, artifact: &ProjectArtifact) -> u64 {
+        match self.cleaner.delete_project_artifact(artifact) {
+            Ok(freed) => {
+                println!("✅ {}", format!("{}/ deleted", artifact.name()).green());
+                freed
+            }
+            Err(e) => {
+                println!("❌ {}", format!("{}/ kept: {}", artifact.name(), e).red());
+                0
+            }
+        }
+    }
+
+    /// Ask the configured decision hook what to do with a .venv
+    ///
+    /// # Returns
+    /// The hook's decision, `Ask` if it failed, or None without a hook
+    fn hook_decision(&self, venv_info: &VenvInfo) -> Option<HookDecision> {
+        let command = self.decision_hook.as_deref()?;
+
+        #[cfg(feature = "decision-hook")]
+        {
+            match DecisionHook::new(command).and_then(|hook| hook.decide(venv_info)) {
+                Ok(decision) => Some(decision),
                 Err(e) => {
-                    errors.push((venv_info.path().display().to_string(), e));
+                    println!("⚠️  {}", format!("{}; asking instead", e).yellow());
+                    Some(HookDecision::Ask)
                 }
             }
         }
+        #[cfg(not(feature = "decision-hook"))]
+        {
+            let _ = (command, venv_info);
+            println!("⚠️  {}", "decision_hook is set, but this build lacks the decision-hook feature; asking instead".yellow());
+            Some(HookDecision::Ask)
+        }
+    }
 
-        // Print summary
-        self.print_cleanup_summary(deleted_count, total_freed, &errors);
+    /// Describe why a .venv was deleted or kept, leading with the criteria it met when they are set
+    ///
+    /// # Arguments
+    /// * `venv_info` - The .venv
+    /// * `reason` - What decided it, e.g. `--force`
+    fn decision_reason(&self, venv_info: &VenvInfo, reason: &str) -> String {
+        match self.cleaner.venv_filter().explain(venv_info) {
+            Some(criteria) => format!("{} ({})", criteria, reason),
+            None => reason.to_string(),
+        }
+    }
 
-        Ok(())
+    /// Describe why a .venv above `--exclude-size-above` is not deleted automatically
+    fn outlier_warning(&self) -> String {
+        format!(
+            "Larger than {}: this may be a data directory mistaken for a virtual environment",
+            self.cleaner.exclude_size_above().unwrap_or_default()
+        )
     }
 
-    /// Process a single .venv directory (prompt and potentially delete)
-    fn process_venv_directory(&self, venv_info: &VenvInfo) -> Result<bool> {
-        let location = venv_info.location();
-        let size = venv_info.size_formatted();
-        let age_days = venv_info.age_in_days();
+    /// Check whether a .venv is small enough for the policy to allow deleting it without a prompt
+    fn within_force_delete_limit(&self, venv_info: &VenvInfo) -> bool {
+        self.cleaner.policy().max_force_delete_size.is_none_or(|limit| venv_info.size() <= limit)
+    }
 
-        // Show information about this .venv
-        println!("\n{}", "─".repeat(60).dimmed());
-        println!("📁 {}", location.cyan());
-        println!("📏 Size: {}", size);
-        println!("📅 Last used: {} ({} days ago)",
-                venv_info.last_modified_formatted().dimmed(),
-                age_days);
+    /// Delete a .venv, treating one already removed by something else as skipped
+    ///
+    /// # Returns
+    /// Whether this run deleted the .venv
+    fn delete_unless_vanished(&self, venv_info: &VenvInfo) -> Result<bool> {
+        match self.cleaner.delete_venv_directory(venv_info) {
+            Ok(()) => Ok(true),
+            Err(e) if e.is_vanished() => {
+                println!("⏭️  {}", "Already removed outside VenvCleaner".dimmed());
+                Ok(false)
+            }
+            Err(e) if e.is_permission_denied() => self.offer_escalation(venv_info, e),
+            Err(e) => Err(e),
+        }
+    }
 
-        // Add age-based coloring and warnings
-        if venv_info.is_old() {
-            println!("⚠️  {}", "This .venv hasn't been used in over 90 days".yellow());
-        } else if venv_info.is_recently_used() {
-            println!("✨ {}", "This .venv was used recently".green());
+    /// Wait for the .venv directories still being removed in the background
+    ///
+    /// A removal denied partway is offered for escalation like an inline one.
+    ///
+    /// # Arguments
+    /// * `venv_dirs` - The .venv directories of the run
+    ///
+    /// # Returns
+    /// The error of each .venv whose removal failed, by path
+    fn finish_background_removals(&self, venv_dirs: &[VenvInfo]) -> HashMap<PathBuf, VenvCleanerError> {
+        let mut failed = HashMap::new();
+        for (venv_path, error) in self.cleaner.finish_background_removals() {
+            let retried = match venv_dirs.iter().find(|venv_info| venv_info.path() == venv_path) {
+                Some(venv_info) if error.is_permission_denied() => self.offer_escalation(venv_info, error),
+                _ => Err(error),
+            };
+            if let Err(e) = retried {
+                println!("❌ {}", e.to_string().red());
+                failed.insert(venv_path, e);
+            }
         }
+        failed
+    }
 
-        // In force mode, delete without asking
-        if self.cleaner.is_force_mode() {
-            println!("🗑️  {}", "Force mode: deleting...".red());
-            self.cleaner.delete_venv_directory(venv_info)?;
-            println!("✅ {}", "Deleted successfully".green());
-            return Ok(true);
+    /// Offer an administrator to retry a denied deletion as root
+    ///
+    /// Only interactive runs with `--allow-escalation` ask; otherwise the
+    /// original error is returned, with a hint for administrators.
+    ///
+    /// # Arguments
+    /// * `venv_info` - The .venv whose deletion was denied
+    /// * `error` - The permission error
+    ///
+    /// # Returns
+    /// Whether the retry deleted the .venv, or the error
+    fn offer_escalation(&self, venv_info: &VenvInfo, error: VenvCleanerError) -> Result<bool> {
+        if !escalation::is_admin() || self.cleaner.is_force_mode() {
+            return Err(error);
+        }
+        let Some(helper) = EscalationHelper::detect() else {
+            return Err(error);
+        };
+        if !self.allow_escalation {
+            println!("💡 {}", format!("Pass --allow-escalation to retry denied deletions with {}", helper.program()).dimmed());
+            return Err(error);
         }
 
-        // Ask user for confirmation
-        print!("\n{} (y/N): ", "Delete this .venv directory?".bold());
-        io::stdout().flush()?;
+        println!("🔒 {}", format!("Permission denied: {}", venv_info.path().display()).yellow());
+        if !self.prompt_yes_no(&format!("Retry as root with {}?", helper.program()))? {
+            return Err(error);
+        }
+        self.cleaner.delete_with_escalation(venv_info, helper, error.marked_path())?;
+        Ok(true)
+    }
 
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
+    /// Message shown while a .venv is being removed
+    fn deleting_message(&self, force: bool) -> &'static str {
+        if self.cleaner.archive_dir().is_some() {
+            return if force { "Force mode: archiving..." } else { "Archiving..." };
+        }
+
+        match (force, self.cleaner.is_using_trash()) {
+            (true, true) => "Force mode: moving to trash...",
+            (true, false) => "Force mode: deleting...",
+            (false, true) => "Moving to trash...",
+            (false, false) => "Deleting...",
+        }
+    }
 
-        let should_delete = input.trim().to_lowercase();
-        if should_delete == "y" || should_delete == "yes" {
-            println!("🗑️  {}", "Deleting...".yellow());
-            self.cleaner.delete_venv_directory(venv_info)?;
-            println!("✅ {}", "Deleted successfully".green());
-            Ok(true)
+    /// Message shown once a .venv has been removed
+    fn deleted_message(&self) -> &'static str {
+        if self.cleaner.offline_index().is_some() {
+            "Added to the deletion plan"
+        } else if self.cleaner.archive_dir().is_some() {
+            "Archived successfully"
         } else {
-            println!("⏭️  {}", "Skipped".dimmed());
-            Ok(false)
+            "Deleted successfully"
         }
     }
 
+    /// Ask the user a yes/no question on stdin (defaults to no)
+    fn prompt_yes_no(&self, question: &str) -> Result<bool> {
+        self.prompter.ask_yes_no(question)
+    }
+
     /// Print the application header
     fn print_header(&self) {
         println!("{}", "VenvCleaner".bold().green());
@@ -284,7 +2134,14 @@ impl CliMode {
         println!();
 
         // Show current configuration
-        println!("🔍 Searching in: {}", self.cleaner.base_directory().display().to_string().cyan());
+        let roots: Vec<String> = self.cleaner.roots().iter().map(|root| root.display().to_string()).collect();
+        match self.cleaner.offline_index() {
+            Some(offline) => println!(
+                "📇 Listing a saved scan; deletions are added to {}",
+                offline.plan().path().display().to_string().cyan()
+            ),
+            None => println!("🔍 Searching in: {}", roots.join(", ").cyan()),
+        }
 
         if self.cleaner.is_recursive() {
             println!("📂 Mode: {}", "Recursive search".yellow());
@@ -300,33 +2157,97 @@ impl CliMode {
             println!("⚡ {}", "FORCE MODE - Will delete without prompting".red().bold());
         }
 
+        // Where deleted folders go is worth knowing before the first prompt, not after
+        if !self.query_mode && self.cleaner.archive_dir().is_none() && self.cleaner.offline_index().is_none() {
+            let mode = format!("DELETE MODE - {}", self.cleaner.delete_mode_description());
+            if self.cleaner.is_using_trash() {
+                println!("♻️  {}", mode.cyan().bold());
+            } else {
+                println!("🔥 {}", mode.red().bold());
+            }
+        }
+
+        let policy = self.cleaner.policy();
+        if !policy.is_empty() {
+            let mut rules = Vec::new();
+            if policy.require_trash {
+                rules.push("trash required".to_string());
+            }
+            if !policy.protected_paths.is_empty() {
+                rules.push(format!("{} protected paths", policy.protected_paths.len()));
+            }
+            if let Some(limit) = &policy.max_force_delete_size {
+                rules.push(format!("force deletes up to {}", limit));
+            }
+            if policy.disable_force {
+                rules.push("no --force".to_string());
+            }
+            if let Some(limit) = policy.max_batch_size {
+                rules.push(format!("at most {} deletions at once", limit));
+            }
+            println!("🔒 {} {}", "POLICY -".cyan(), rules.join(", ").cyan());
+        }
+
+        if let Some(warning) = self.cleaner.trash_warning() {
+            println!("⚠️  {}", warning.yellow());
+        }
+
+        if self.cleaner.verbosity() > 0 {
+            let filesystem = self.cleaner.filesystem()
+                .map(|filesystem| filesystem.describe())
+                .unwrap_or_else(|| "unknown".to_string());
+            println!("💽 Filesystem: {}", filesystem);
+            println!("🧵 Concurrency: {} scan threads, {} delete jobs",
+                    self.cleaner.scan_threads(), self.cleaner.delete_jobs());
+        }
+
+        if let Some(dir) = self.cleaner.archive_dir() {
+            println!("📦 {} {}", "ARCHIVE MODE - Folders will be compressed to".cyan(), dir.display().to_string().cyan());
+        }
+
         if self.query_mode {
             println!("📊 {}", "QUERY MODE - Will only display information".blue().bold());
         }
+
+        if self.deep_clean && !self.query_mode {
+            println!("🧱 {}", "DEEP CLEAN - Will also remove build/, dist/, *.egg-info and .tox (each confirmed; --force needs --include-artifacts)".cyan());
+        }
     }
 
     /// Print recommendations based on found .venv directories
     fn print_recommendations(&self, venv_dirs: &[VenvInfo]) {
-        let old_dirs: Vec<_> = venv_dirs.iter().filter(|v| v.is_old()).collect();
-        let large_dirs: Vec<_> = venv_dirs.iter().filter(|v| v.size_bytes() > 500 * 1024 * 1024).collect();
-
-        if !old_dirs.is_empty() || !large_dirs.is_empty() {
+        let all = recommendations::recommend(venv_dirs, &self.cleaner.roots());
+        let now = Utc::now();
+        let mut history = RecommendationHistory::default_path()
+            .map(RecommendationHistory::load)
+            .unwrap_or_default();
+        let (quiet, shown): (Vec<_>, Vec<_>) = all.iter().partition(|r| history.is_quiet(r, now));
+
+        if !shown.is_empty() {
             println!("\n{}", "Recommendations:".bold().yellow());
         }
-
-        if !old_dirs.is_empty() {
-            println!("🧹 {} old .venv directories (>90 days) could be cleaned up",
-                    old_dirs.len().to_string().red());
+        for recommendation in &shown {
+            println!("{} {} ({})",
+                    recommendation.kind().icon(),
+                    recommendation.title(),
+                    ByteSize::new(recommendation.bytes()).to_string().yellow());
+            println!("   💡 {}", recommendation.command().green());
+            if let Some(link) = recommendation.kind().doc_link() {
+                println!("   📖 {}", link.underline());
+            }
         }
-
-        if !large_dirs.is_empty() {
-            println!("📦 {} large .venv directories (>500MB) are taking significant space",
-                    large_dirs.len().to_string().yellow());
+        if !quiet.is_empty() {
+            println!("\n{}", format!(
+                "{} unchanged {} shown in the last {}h hidden",
+                quiet.len(),
+                if quiet.len() == 1 { "recommendation" } else { "recommendations" },
+                recommendations::COOLDOWN_HOURS
+            ).dimmed());
         }
 
-        if !old_dirs.is_empty() {
-            println!("\n💡 Consider running: {} to clean up old directories",
-                    "venv_cleaner -r -f".green());
+        history.mark_shown(shown, now);
+        if let Err(e) = history.save() {
+            warn!("Failed to save recommendation history: {}", e);
         }
     }
 
@@ -336,12 +2257,26 @@ impl CliMode {
         println!("{}", "Cleanup Summary".bold().green());
         println!("{}", "=".repeat(60).green());
 
+        let verb = if self.cleaner.offline_index().is_some() {
+            "planned for deletion"
+        } else if self.cleaner.archive_dir().is_some() {
+            "archived"
+        } else {
+            "deleted"
+        };
         if self.cleaner.is_dry_run() {
-            println!("🧪 {} directories would be deleted", deleted_count.to_string().cyan());
-            println!("💾 {} would be freed", Self::format_size(total_freed).cyan());
+            println!("🧪 {} directories would be {}", deleted_count.to_string().cyan(), verb);
+            println!("💾 {} would be freed", ByteSize::new(total_freed).to_string().cyan());
         } else {
-            println!("✅ {} directories deleted", deleted_count.to_string().green());
-            println!("💾 {} freed", Self::format_size(total_freed).green());
+            println!("✅ {} directories {}", deleted_count.to_string().green(), verb);
+            match self.cleaner.offline_index() {
+                Some(offline) => println!(
+                    "💾 {} will be freed by `venv_cleaner apply-plan {}` on the scanned machine",
+                    ByteSize::new(total_freed).to_string().green(),
+                    offline.plan().path().display()
+                ),
+                None => println!("💾 {} freed", ByteSize::new(total_freed).to_string().green()),
+            }
         }
 
         if !errors.is_empty() {
@@ -354,33 +2289,87 @@ impl CliMode {
         if deleted_count > 0 && !self.cleaner.is_dry_run() {
             println!("\n🎉 {}", "Cleanup completed successfully!".green().bold());
         }
+
+        if deleted_count > 0 && self.cleaner.is_using_trash() && !self.cleaner.is_dry_run() {
+            self.print_trash_hint();
+        }
     }
 
-    /// Format a location string for display, truncating if necessary
-    fn format_location_for_display(&self, location: &str, max_width: usize) -> String {
-        if location.len() <= max_width {
-            location.to_string()
-        } else {
-            format!("...{}", &location[location.len() - max_width + 3..])
+    /// Print the count, size and reclaimable space found under each root of a multi-root scan
+    fn print_root_subtotals(&self, venv_dirs: &[VenvInfo]) {
+        let roots = self.cleaner.roots();
+        if roots.len() < 2 {
+            return;
+        }
+
+        println!("\n{}", "Per root:".bold());
+        for summary in RootSummary::for_roots(&roots, venv_dirs) {
+            println!(
+                "  {:<40} {:>4} .venv {:>12} {:>12} reclaimable (>90 days)",
+                self.format_location_for_display(&summary.root.display().to_string(), 40),
+                summary.count,
+                ByteSize::new(summary.total_bytes).to_string(),
+                ByteSize::new(summary.reclaimable_bytes).to_string().yellow()
+            );
         }
     }
 
-    /// Format a size in bytes to a human-readable string
-    fn format_size(bytes: u64) -> String {
-        const KB: u64 = 1024;
-        const MB: u64 = KB * 1024;
-        const GB: u64 = MB * 1024;
+    /// Print the inodes used by .venv directories per file system
+    ///
+    /// With `--inodes` every file system is listed; otherwise only those
+    /// running out of inodes, since that fails builds as surely as a full disk.
+    fn print_inode_usage(&self, venv_dirs: &[VenvInfo]) {
+        let filesystems = inodes::by_filesystem(venv_dirs);
+        let shown: Vec<_> = filesystems
+            .iter()
+            .filter(|fs| self.show_inodes || fs.usage.is_some_and(|usage| usage.is_low()))
+            .collect();
+        if shown.is_empty() {
+            return;
+        }
 
-        if bytes >= GB {
-            format!("{:.2} GB", bytes as f64 / GB as f64)
-        } else if bytes >= MB {
-            format!("{:.2} MB", bytes as f64 / MB as f64)
-        } else if bytes >= KB {
-            format!("{:.2} KB", bytes as f64 / KB as f64)
-        } else {
-            format!("{} bytes", bytes)
+        println!("\n{}", "Inodes:".bold());
+        for filesystem in shown {
+            let usage = match (filesystem.usage, filesystem.venv_percent()) {
+                (Some(usage), Some(venv_percent)) => {
+                    let free = format!(
+                        "{} of {} free ({:.1}%)",
+                        FileUtils::format_count(usage.free()),
+                        FileUtils::format_count(usage.total()),
+                        usage.free_percent()
+                    );
+                    let free = if usage.is_low() { free.red().bold().to_string() } else { free };
+                    format!("{:.1}% of the file system, {}", venv_percent, free)
+                }
+                _ => "no fixed inode limit".dimmed().to_string(),
+            };
+            println!(
+                "  {:<30} {:>4} .venv {:>12} inodes  {}",
+                self.format_location_for_display(&filesystem.mount_point.display().to_string(), 30),
+                filesystem.venv_count,
+                FileUtils::format_count(filesystem.venv_inodes).cyan(),
+                usage
+            );
         }
     }
+
+    /// Remind the user that trashed folders still take up space
+    fn print_trash_hint(&self) {
+        let Some(ledger) = TrashLedger::default_path().map(TrashLedger::load) else {
+            return;
+        };
+        println!(
+            "♻️  {} .venv directories ({}) moved to the trash by VenvCleaner still use disk space; free them with `venv_cleaner trash purge --older-than {}`",
+            ledger.entries().len(),
+            ByteSize::new(ledger.total_bytes()),
+            trash::DEFAULT_OLDER_THAN
+        );
+    }
+
+    /// Format a location string for display, truncating if necessary
+    fn format_location_for_display(&self, location: &str, max_width: usize) -> String {
+        FileUtils::truncate_start(location, max_width)
+    }
 }
 
 #[cfg(test)]
@@ -388,22 +2377,74 @@ mod tests {
     use super::*;
     use clap::Command;
     use tempfile::TempDir;
+    use std::sync::Mutex;
 
     fn create_test_command() -> Command {
         Command::new("test")
-            .arg(clap::Arg::new("directory").index(1))
+            .arg(clap::Arg::new("directory").index(1).num_args(1..))
             .arg(clap::Arg::new("recursive").short('r').action(clap::ArgAction::SetTrue))
             .arg(clap::Arg::new("force").short('f').action(clap::ArgAction::SetTrue))
             .arg(clap::Arg::new("query").short('q').action(clap::ArgAction::SetTrue))
             .arg(clap::Arg::new("dry-run").long("dry-run").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("check-permissions").long("check-permissions").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("allow-escalation").long("allow-escalation").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("wait").long("wait").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("force-lock").long("force-lock").action(clap::ArgAction::SetTrue))
             .arg(clap::Arg::new("verbose").short('v').action(clap::ArgAction::Count))
+            .arg(clap::Arg::new("deep-clean").long("deep-clean").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("include-user-data").long("include-user-data").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("include-artifacts").long("include-artifacts").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("progress").long("progress"))
+            .arg(clap::Arg::new("output").long("output"))
+            .arg(clap::Arg::new("resume").long("resume").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("low-memory").long("low-memory").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("trash").long("trash").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("permanent").long("permanent").action(clap::ArgAction::SetTrue).conflicts_with("trash"))
+            .arg(clap::Arg::new("respect-gitignore").long("respect-gitignore").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("no-validate").long("no-validate").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("skip-hidden").long("skip-hidden").action(clap::ArgAction::SetTrue).overrides_with("include-hidden"))
+            .arg(clap::Arg::new("include-hidden").long("include-hidden").action(clap::ArgAction::SetTrue).overrides_with("skip-hidden"))
+            .arg(clap::Arg::new("scan-temp").long("scan-temp").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("include").long("include").action(clap::ArgAction::Append))
+            .arg(clap::Arg::new("last-install").long("last-install").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("activity").long("activity").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("size-delta").long("size-delta").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("archive").long("archive").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("archive-dir").long("archive-dir"))
+            .arg(clap::Arg::new("group-by").long("group-by"))
+            .arg(clap::Arg::new("sort").long("sort"))
+            .arg(clap::Arg::new("watch").long("watch"))
+            .arg(clap::Arg::new("file-count").long("file-count").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("inodes").long("inodes").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("summary-json").long("summary-json"))
+            .arg(clap::Arg::new("size-timeout").long("size-timeout").value_parser(clap::value_parser!(u64)))
+            .arg(clap::Arg::new("scan-threads").long("scan-threads").value_parser(clap::value_parser!(usize)))
+            .arg(clap::Arg::new("delete-jobs").long("delete-jobs").value_parser(clap::value_parser!(usize)))
+            .arg(clap::Arg::new("mine").long("mine").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("all-users").long("all-users").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("user").long("user"))
+            .arg(clap::Arg::new("older-than").long("older-than"))
+            .arg(clap::Arg::new("profile").long("profile"))
+            .arg(clap::Arg::new("min-size").long("min-size"))
+            .arg(clap::Arg::new("exclude-size-above").long("exclude-size-above"))
+            .arg(clap::Arg::new("from-index").long("from-index"))
+            .arg(clap::Arg::new("plan").long("plan"))
+            .arg(clap::Arg::new("confirm-timeout").long("confirm-timeout"))
+            .arg(clap::Arg::new("default-answer").long("default-answer"))
+            .arg(clap::Arg::new("i-know-what-im-doing").long("i-know-what-im-doing").action(clap::ArgAction::SetTrue))
+    }
+
+    /// Create a .venv with the layout `FileUtils::is_valid_venv_directory` expects
+    fn create_venv(venv_path: &std::path::Path) {
+        std::fs::create_dir_all(venv_path.join("bin")).unwrap();
+        std::fs::write(venv_path.join("pyvenv.cfg"), "home = /usr/bin\n").unwrap();
     }
 
     #[test]
     fn test_cli_mode_creation() {
         let temp_dir = TempDir::new().unwrap();
         let cmd = create_test_command();
-        let matches = cmd.try_get_matches_from(&[
+        let matches = cmd.try_get_matches_from([
             "test",
             temp_dir.path().to_str().unwrap()
         ]).unwrap();
@@ -415,7 +2456,7 @@ mod tests {
     #[test]
     fn test_cli_mode_nonexistent_directory() {
         let cmd = create_test_command();
-        let matches = cmd.try_get_matches_from(&[
+        let matches = cmd.try_get_matches_from([
             "test",
             "/nonexistent/directory"
         ]).unwrap();
@@ -425,18 +2466,443 @@ mod tests {
     }
 
     #[test]
-    fn test_format_size() {
-        assert_eq!(CliMode::format_size(500), "500 bytes");
-        assert_eq!(CliMode::format_size(1536), "1.50 KB");
-        assert_eq!(CliMode::format_size(1024 * 1024 * 2), "2.00 MB");
-        assert_eq!(CliMode::format_size(1024 * 1024 * 1024), "1.00 GB");
+    fn test_output_requires_query_mode() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().to_str().unwrap();
+
+        let matches = create_test_command().try_get_matches_from(["test", "--output", "csv", path]).unwrap();
+        assert!(matches!(CliMode::new(&matches), Err(VenvCleanerError::InvalidArgument(_))));
+
+        let matches = create_test_command()
+            .try_get_matches_from(["test", "-q", "--output", "markdown", path])
+            .unwrap();
+        assert_eq!(CliMode::new(&matches).unwrap().output, Some(ExportFormat::Markdown));
+    }
+
+    #[test]
+    fn test_low_memory_query_streams_rows() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = temp_dir.path().join("project").join(".venv");
+        std::fs::create_dir_all(venv.join("bin")).unwrap();
+        std::fs::write(venv.join("pyvenv.cfg"), "home = /usr/bin\n").unwrap();
+        let path = temp_dir.path().to_str().unwrap();
+
+        let matches = create_test_command().try_get_matches_from(["test", "-r", "--low-memory", path]).unwrap();
+        assert!(matches!(CliMode::new(&matches), Err(VenvCleanerError::InvalidArgument(_))));
+
+        let summary = temp_dir.path().join("summary.json");
+        let matches = create_test_command()
+            .try_get_matches_from(["test", "-q", "-r", "--low-memory", "--output", "csv", "--summary-json", summary.to_str().unwrap(), path])
+            .unwrap();
+        CliMode::new(&matches).unwrap().execute().unwrap();
+        let summary: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&summary).unwrap()).unwrap();
+        assert_eq!(summary["found"], 1);
+    }
+
+    #[test]
+    fn test_progress_json_requires_non_interactive_mode() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().to_str().unwrap();
+
+        let matches = create_test_command()
+            .try_get_matches_from(["test", "--progress", "json", path])
+            .unwrap();
+        assert!(matches!(CliMode::new(&matches), Err(VenvCleanerError::InvalidArgument(_))));
+
+        let matches = create_test_command()
+            .try_get_matches_from(["test", "--progress", "json", "-q", path])
+            .unwrap();
+        assert_eq!(CliMode::new(&matches).unwrap().progress_format, ProgressFormat::Json);
+    }
+
+    #[test]
+    fn test_progress_json_execute() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("proj").join(".venv")).unwrap();
+
+        let matches = create_test_command()
+            .try_get_matches_from(["test", "--progress", "json", "-r", "-f", "--dry-run", temp_dir.path().to_str().unwrap()])
+            .unwrap();
+        let cli_mode = CliMode::new(&matches).unwrap();
+        assert!(cli_mode.execute().is_ok());
+
+        // Dry run must leave the tree untouched
+        assert!(temp_dir.path().join("proj").join(".venv").exists());
+    }
+
+    #[test]
+    fn test_progress_json_dry_run_checks_the_policy() {
+        let temp_dir = TempDir::new().unwrap();
+        create_venv(&temp_dir.path().join("proj").join(".venv"));
+        let summary_path = temp_dir.path().join("summary.json");
+
+        let matches = create_test_command()
+            .try_get_matches_from([
+                "test", "--progress", "json", "-r", "-f", "--dry-run", "--summary-json", summary_path.to_str().unwrap(),
+                temp_dir.path().to_str().unwrap(),
+            ])
+            .unwrap();
+        let mut cli_mode = CliMode::new(&matches).unwrap();
+        let policy = crate::core::PolicyConfig { protected_paths: vec![temp_dir.path().join("proj")], ..Default::default() };
+        cli_mode.cleaner = cli_mode.cleaner.clone().with_policy(policy);
+        cli_mode.execute().unwrap();
+
+        let written: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&summary_path).unwrap()).unwrap();
+        assert_eq!(written["found"], 1);
+        assert_eq!(written["deleted"], 0);
+        assert!(written["errors"][0]["error"].as_str().unwrap().contains("protected"), "{}", written);
+    }
+
+    #[test]
+    fn test_force_keeps_venv_with_user_data() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = temp_dir.path().join("proj").join(".venv");
+        create_venv(&venv);
+        std::fs::write(venv.join("results.csv"), vec![0u8; 2 * 1024 * 1024]).unwrap();
+        let root = temp_dir.path().to_str().unwrap();
+
+        let matches = create_test_command().try_get_matches_from(["test", "-r", "-f", "--permanent", root]).unwrap();
+        CliMode::new(&matches).unwrap().execute().unwrap();
+        assert!(venv.exists());
+
+        let matches = create_test_command()
+            .try_get_matches_from(["test", "-r", "-f", "--permanent", "--include-user-data", root])
+            .unwrap();
+        CliMode::new(&matches).unwrap().execute().unwrap();
+        assert!(!venv.exists());
+    }
+
+    #[test]
+    fn test_folders_not_like_venvs_are_skipped() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = temp_dir.path().join("app").join(".venv");
+        create_venv(&venv);
+        // A project's own folder that happens to be called .venv
+        let lookalike = temp_dir.path().join("notes").join(".venv");
+        std::fs::create_dir_all(&lookalike).unwrap();
+        std::fs::write(lookalike.join("todo.txt"), "keep").unwrap();
+        let summary_path = temp_dir.path().join("summary.json");
+
+        let matches = create_test_command()
+            .try_get_matches_from([
+                "test", "-r", "-f", "--permanent", "--summary-json", summary_path.to_str().unwrap(), temp_dir.path().to_str().unwrap(),
+            ])
+            .unwrap();
+        CliMode::new(&matches).unwrap().execute().unwrap();
+        assert!(!venv.exists());
+        assert!(lookalike.exists());
+
+        let written: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&summary_path).unwrap()).unwrap();
+        assert_eq!((written["found"].as_u64(), written["deleted"].as_u64()), (Some(1), Some(1)));
+
+        // --no-validate offers it like any other .venv
+        let matches = create_test_command()
+            .try_get_matches_from(["test", "-r", "-q", "--no-validate", temp_dir.path().to_str().unwrap()])
+            .unwrap();
+        let cli_mode = CliMode::new(&matches).unwrap();
+        assert_eq!(cli_mode.find_venv_directories().unwrap().len(), 1);
+        assert!(cli_mode.cleaner.last_invalid_venvs().is_empty());
+    }
+
+    #[test]
+    fn test_extra_roots() {
+        let temp_dir = TempDir::new().unwrap();
+        let (first, second) = (temp_dir.path().join("work"), temp_dir.path().join("src"));
+        std::fs::create_dir_all(&first).unwrap();
+        std::fs::create_dir_all(&second).unwrap();
+
+        let matches = create_test_command()
+            .try_get_matches_from(["test", first.to_str().unwrap(), second.to_str().unwrap()])
+            .unwrap();
+        assert_eq!(resolve_extra_roots(&matches).unwrap(), vec![second.clone()]);
+        let cli_mode = CliMode::new(&matches).unwrap();
+        assert_eq!(cli_mode.cleaner.roots(), vec![first.as_path(), second.as_path()]);
+
+        let missing = temp_dir.path().join("missing");
+        let matches = create_test_command()
+            .try_get_matches_from(["test", first.to_str().unwrap(), missing.to_str().unwrap()])
+            .unwrap();
+        assert!(matches!(resolve_extra_roots(&matches), Err(VenvCleanerError::PathError { .. })));
+    }
+
+    #[test]
+    fn test_resolve_owner_filter() {
+        let parse = |args: &[&str]| create_test_command().try_get_matches_from(args).unwrap();
+        assert_eq!(resolve_owner_filter(&parse(&["test"])).unwrap(), OwnerFilter::Mine);
+        assert_eq!(resolve_owner_filter(&parse(&["test", "--mine"])).unwrap(), OwnerFilter::Mine);
+        assert_eq!(resolve_owner_filter(&parse(&["test", "--all-users"])).unwrap(), OwnerFilter::AllUsers);
+        assert_eq!(resolve_owner_filter(&parse(&["test", "--user", "1001"])).unwrap(), OwnerFilter::User(1001));
+        assert!(resolve_owner_filter(&parse(&["test", "--user", "no-such-user-venv-cleaner"])).is_err());
+    }
+
+    #[test]
+    fn test_resolve_exclude_size_above() {
+        let parse = |args: &[&str]| create_test_command().try_get_matches_from(args).unwrap();
+        assert_eq!(resolve_exclude_size_above(&parse(&["test"])).unwrap(), Some(safety::DEFAULT_EXCLUDE_SIZE_ABOVE));
+        assert_eq!(resolve_exclude_size_above(&parse(&["test", "--exclude-size-above", "50GB"])).unwrap(), Some(ByteSize::new(50 * ByteSize::GB)));
+        assert_eq!(resolve_exclude_size_above(&parse(&["test", "--exclude-size-above", "none"])).unwrap(), None);
+        assert!(resolve_exclude_size_above(&parse(&["test", "--exclude-size-above", "huge"])).is_err());
+    }
+
+    #[test]
+    fn test_resolve_venv_filter() {
+        let parse = |args: &[&str]| create_test_command().try_get_matches_from(args).unwrap();
+        assert_eq!(resolve_venv_filter(&parse(&["test"]), &ScanConfig::default()).unwrap(), VenvFilter::default());
+        assert_eq!(
+            resolve_venv_filter(&parse(&["test", "--older-than", "90d", "--min-size", "200MB"]), &ScanConfig::default()).unwrap(),
+            VenvFilter::default()
+                .with_older_than(Some(chrono::Duration::days(90)))
+                .with_min_size(Some(ByteSize::new(200 * ByteSize::MB)))
+        );
+        assert!(resolve_venv_filter(&parse(&["test", "--older-than", "soon"]), &ScanConfig::default()).is_err());
+        assert!(resolve_venv_filter(&parse(&["test", "--min-size", "big"]), &ScanConfig::default()).is_err());
+
+        // A profile's thresholds apply unless the command line gives its own
+        let scan = ScanConfig { older_than: Some("30d".to_string()), min_size: Some(ByteSize::new(ByteSize::GB)), ..ScanConfig::default() };
+        assert_eq!(
+            resolve_venv_filter(&parse(&["test", "--older-than", "90d"]), &scan).unwrap(),
+            VenvFilter::default()
+                .with_older_than(Some(chrono::Duration::days(90)))
+                .with_min_size(Some(ByteSize::new(ByteSize::GB)))
+        );
+    }
+
+    #[test]
+    fn test_resolve_skip_hidden() {
+        let parse = |args: &[&str]| create_test_command().try_get_matches_from(args).unwrap();
+        let skipping = ScanConfig { skip_hidden: Some(true), ..ScanConfig::default() };
+        assert!(!resolve_skip_hidden(&parse(&["test"]), &ScanConfig::default()));
+        assert!(resolve_skip_hidden(&parse(&["test"]), &skipping));
+        assert!(resolve_skip_hidden(&parse(&["test", "--skip-hidden"]), &ScanConfig::default()));
+        assert!(!resolve_skip_hidden(&parse(&["test", "--include-hidden"]), &skipping));
+        assert!(!resolve_skip_hidden(&parse(&["test", "--skip-hidden", "--include-hidden"]), &ScanConfig::default()));
+    }
+
+    #[test]
+    fn test_resolve_temp_roots() {
+        let parse = |args: &[&str]| create_test_command().try_get_matches_from(args).unwrap();
+        let scanning = ScanConfig { scan_temp: Some(true), ..ScanConfig::default() };
+        assert!(resolve_temp_roots(&parse(&["test"]), &ScanConfig::default()).is_empty());
+        assert_eq!(resolve_temp_roots(&parse(&["test", "--scan-temp"]), &ScanConfig::default()), temp_venvs::temp_roots());
+        assert_eq!(resolve_temp_roots(&parse(&["test"]), &scanning), temp_venvs::temp_roots());
+    }
+
+    #[test]
+    fn test_resolve_delete_mode() {
+        let parse = |args: &[&str]| create_test_command().try_get_matches_from(args).unwrap();
+        assert_eq!(resolve_delete_mode(&parse(&["test"]), None).unwrap(), DeleteMode::TrashIfAvailable);
+        assert_eq!(resolve_delete_mode(&parse(&["test"]), Some("permanent")).unwrap(), DeleteMode::Permanent);
+        // The command line wins over the config
+        assert_eq!(resolve_delete_mode(&parse(&["test", "--trash"]), Some("permanent")).unwrap(), DeleteMode::Trash);
+        assert_eq!(resolve_delete_mode(&parse(&["test", "--permanent"]), Some("trash")).unwrap(), DeleteMode::Permanent);
+        assert!(create_test_command().try_get_matches_from(["test", "--trash", "--permanent"]).is_err());
+        assert!(resolve_delete_mode(&parse(&["test"]), Some("shred")).is_err());
+    }
+
+    #[test]
+    fn test_resolve_prompter() {
+        let matches = create_test_command().try_get_matches_from(["test", "--confirm-timeout", "30s"]).unwrap();
+        assert_eq!(resolve_prompter(&matches).unwrap(), Prompter::new(Some(std::time::Duration::from_secs(30)), "n"));
+
+        let matches = create_test_command().try_get_matches_from(["test"]).unwrap();
+        assert_eq!(resolve_prompter(&matches).unwrap(), Prompter::new(None, "n"));
+
+        let matches = create_test_command().try_get_matches_from(["test", "--confirm-timeout", "soon"]).unwrap();
+        assert!(matches!(CliMode::new(&matches), Err(VenvCleanerError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn test_force_run_inside_environment_needs_confirming() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = temp_dir.path().join("app").join(".venv");
+        create_venv(&venv);
+        std::fs::create_dir_all(venv.join("lib")).unwrap();
+        let root = venv.join("lib");
+
+        let matches = create_test_command().try_get_matches_from(["test", "-f", root.to_str().unwrap()]).unwrap();
+        let error = CliMode::new(&matches).err().unwrap();
+        assert!(error.to_string().contains("--i-know-what-im-doing"));
+
+        let matches = create_test_command()
+            .try_get_matches_from(["test", "-f", "--i-know-what-im-doing", root.to_str().unwrap()])
+            .unwrap();
+        assert_eq!(CliMode::new(&matches).unwrap().inside_environments.len(), 1);
+
+        // Looking without deleting only warns
+        let matches = create_test_command().try_get_matches_from(["test", "-q", root.to_str().unwrap()]).unwrap();
+        assert!(CliMode::new(&matches).is_ok());
+        assert!(check_force_run_inside_environment(true, false, &[]).is_ok());
+    }
+
+    #[test]
+    fn test_force_run_as_root_needs_confirming() {
+        let temp_dir = TempDir::new().unwrap();
+        let roots = [Path::new("/")];
+        assert!(matches!(
+            check_force_run_as_root(true, true, true, false, &roots),
+            Err(VenvCleanerError::InvalidArgument(_))
+        ));
+        assert!(check_force_run_as_root(true, true, true, true, &roots).is_ok());
+        assert!(check_force_run_as_root(false, true, true, false, &roots).is_ok());
+        assert!(check_force_run_as_root(true, false, true, false, &roots).is_ok());
+        assert!(check_force_run_as_root(true, true, true, false, &[temp_dir.path()]).is_ok());
+    }
+
+    #[test]
+    fn test_resolve_scan_roots() {
+        let temp_dir = TempDir::new().unwrap();
+        let (first, second) = (temp_dir.path().join("first"), temp_dir.path().join("second"));
+        std::fs::create_dir(&first).unwrap();
+        std::fs::create_dir(&second).unwrap();
+        let scan = ScanConfig {
+            roots: vec![temp_dir.path().join("missing"), first.clone(), second.clone()],
+            ..ScanConfig::default()
+        };
+        let parse = |args: &[&str]| create_test_command().try_get_matches_from(args).unwrap();
+
+        // Configured roots that do not exist here are skipped
+        assert_eq!(resolve_scan_roots(&parse(&["test"]), &scan).unwrap(), (first.clone(), vec![second.clone()]));
+
+        // A directory on the command line replaces the configured roots
+        let (base, extra) = resolve_scan_roots(&parse(&["test", second.to_str().unwrap()]), &scan).unwrap();
+        assert_eq!((base, extra), (second, Vec::new()));
+    }
+
+    #[test]
+    fn test_progress_format_from_name() {
+        assert_eq!(ProgressFormat::from_name("human"), Some(ProgressFormat::Human));
+        assert_eq!(ProgressFormat::from_name("json"), Some(ProgressFormat::Json));
+        assert_eq!(ProgressFormat::from_name("xml"), None);
+    }
+
+    #[test]
+    fn test_query_grouped_by_age() {
+        let temp_dir = TempDir::new().unwrap();
+        create_venv(&temp_dir.path().join("proj").join(".venv"));
+        assert_eq!(GroupBy::from_name("age"), Some(GroupBy::Age));
+        assert_eq!(GroupBy::from_name("python"), None);
+
+        let matches = create_test_command()
+            .try_get_matches_from(["test", "-q", "-r", "--group-by", "age", temp_dir.path().to_str().unwrap()])
+            .unwrap();
+        let cli_mode = CliMode::new(&matches).unwrap();
+        assert_eq!(cli_mode.group_by, GroupBy::Age);
+        assert!(cli_mode.execute().is_ok());
+    }
+
+    #[test]
+    fn test_query_sorted_by_file_count() {
+        let temp_dir = TempDir::new().unwrap();
+        create_venv(&temp_dir.path().join("proj").join(".venv"));
+        assert_eq!(SortBy::from_name("files"), Some(SortBy::Files));
+        assert_eq!(SortBy::from_name("age"), None);
+
+        let matches = create_test_command()
+            .try_get_matches_from(["test", "-q", "-r", "--sort", "files", temp_dir.path().to_str().unwrap()])
+            .unwrap();
+        let cli_mode = CliMode::new(&matches).unwrap();
+        assert_eq!(cli_mode.sort_by, SortBy::Files);
+        assert!(cli_mode.show_file_count);
+        assert!(cli_mode.execute().is_ok());
+    }
+
+    #[test]
+    fn test_watch_sort_cycling() {
+        assert_eq!(SortBy::Size.next(false), SortBy::Created);
+        assert_eq!(SortBy::LastUsed.next(false), SortBy::Location);
+        assert_eq!(SortBy::LastUsed.next(true), SortBy::Files);
+        assert_eq!(SortBy::Files.next(true), SortBy::Location);
+        assert_eq!(WatchCommand::from_line("s\n"), WatchCommand::NextSort);
+        assert_eq!(WatchCommand::from_line("Q"), WatchCommand::Quit);
+        assert_eq!(WatchCommand::from_line("\n"), WatchCommand::Refresh);
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().to_str().unwrap();
+        let parse = |args: &[&str]| CliMode::new(&create_test_command().try_get_matches_from(args).unwrap());
+        assert_eq!(parse(&["test", "-q", "--watch", "30s", path]).unwrap().watch, Some(Duration::from_secs(30)));
+        assert!(parse(&["test", "--watch", "30s", path]).is_err());
+        assert!(parse(&["test", "-q", "--watch", "30s", "--output", "csv", path]).is_err());
+        assert!(parse(&["test", "-q", "--watch", "0s", path]).is_err());
+    }
+
+    #[test]
+    fn test_summary_json_written_for_dry_run_cleanup() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = temp_dir.path().join("proj").join(".venv");
+        create_venv(&venv);
+        let summary_path = temp_dir.path().join("summary.json");
+
+        let matches = create_test_command()
+            .try_get_matches_from([
+                "test", "-r", "-f", "--dry-run", "--min-size", "1", "--summary-json", summary_path.to_str().unwrap(),
+                temp_dir.path().to_str().unwrap(),
+            ])
+            .unwrap();
+        let cli_mode = CliMode::new(&matches).unwrap();
+        assert!(cli_mode.execute().is_ok());
+
+        let written: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&summary_path).unwrap()).unwrap();
+        assert_eq!(written["found"], 1);
+        assert_eq!(written["deleted"], 1);
+        assert_eq!(written["dry_run"], true);
+        assert_eq!(written["decisions"][0]["action"], "deleted");
+        let reason = written["decisions"][0]["reason"].as_str().unwrap();
+        assert!(reason.starts_with("size ") && reason.ends_with(">= 1 bytes (--force)"), "{}", reason);
+    }
+
+    #[test]
+    fn test_cleanup_records_failed_deletions() {
+        use crate::core::{FsOp, MemoryFs};
+
+        // The base directory must exist on disk; the environments only exist in memory
+        let temp_dir = TempDir::new().unwrap();
+        let summary_path = temp_dir.path().join("summary.json");
+        let fs = Arc::new(MemoryFs::new());
+        let (api, locked) = (temp_dir.path().join("api").join(".venv"), temp_dir.path().join("locked").join(".venv"));
+        fs.add_venv(&api, 100);
+        fs.add_venv(&locked, 200);
+        fs.fail(&locked, FsOp::Rename, std::io::ErrorKind::PermissionDenied);
+
+        let matches = create_test_command()
+            .try_get_matches_from([
+                "test", "-r", "-f", "--permanent", "--summary-json", summary_path.to_str().unwrap(), temp_dir.path().to_str().unwrap(),
+            ])
+            .unwrap();
+        CliMode::new(&matches).unwrap().with_fs_backend(fs.clone()).execute().unwrap();
+        assert!(!fs.contains(&api));
+        assert!(fs.contains(&locked));
+
+        let written: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&summary_path).unwrap()).unwrap();
+        assert_eq!((written["found"].as_u64(), written["deleted"].as_u64()), (Some(2), Some(1)));
+        assert_eq!(written["errors"][0]["path"], locked.display().to_string());
+    }
+
+    #[test]
+    fn test_query_with_inodes() {
+        let temp_dir = TempDir::new().unwrap();
+        create_venv(&temp_dir.path().join("proj").join(".venv"));
+
+        let matches = create_test_command()
+            .try_get_matches_from(["test", "-q", "-r", "--inodes", temp_dir.path().to_str().unwrap()])
+            .unwrap();
+        let cli_mode = CliMode::new(&matches).unwrap();
+        assert!(cli_mode.show_inodes);
+        assert!(cli_mode.execute().is_ok());
+    }
+
+    #[test]
+    fn test_resolve_size_timeout() {
+        let parse = |args: &[&str]| create_test_command().try_get_matches_from(args).unwrap();
+        assert_eq!(resolve_size_timeout(&parse(&["test"])), None);
+        assert_eq!(resolve_size_timeout(&parse(&["test", "--size-timeout", "0"])), None);
+        assert_eq!(resolve_size_timeout(&parse(&["test", "--size-timeout", "30"])), Some(Duration::from_secs(30)));
     }
 
     #[test]
     fn test_format_location_for_display() {
         let temp_dir = TempDir::new().unwrap();
         let cmd = create_test_command();
-        let matches = cmd.try_get_matches_from(&[
+        let matches = cmd.try_get_matches_from([
             "test",
             temp_dir.path().to_str().unwrap()
         ]).unwrap();
@@ -451,5 +2917,83 @@ mod tests {
         let formatted = cli_mode.format_location_for_display(long_path, 20);
         assert!(formatted.len() <= 20);
         assert!(formatted.starts_with("..."));
+
+        // Multi-byte names are cut between characters, not inside one
+        let unicode_path = format!("/tmp/{}", "ü".repeat(100));
+        let formatted = cli_mode.format_location_for_display(&unicode_path, 58);
+        assert_eq!(formatted.chars().count(), 58);
+    }
+
+    #[test]
+    fn test_deep_clean_confirms_each_artifact() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("proj");
+        create_venv(&project.join(".venv"));
+        std::fs::create_dir_all(project.join("build")).unwrap();
+        std::fs::create_dir_all(project.join("dist")).unwrap();
+
+        let matches = create_test_command()
+            .try_get_matches_from(["test", temp_dir.path().to_str().unwrap(), "-r", "--deep-clean"])
+            .unwrap();
+        let asked = Arc::new(Mutex::new(Vec::new()));
+        let questions = Arc::clone(&asked);
+        let cli_mode = CliMode::new(&matches).unwrap().with_confirmation_provider(Arc::new(move |request: &ConfirmationRequest<'_>| {
+            questions.lock().unwrap().push(request.question());
+            Ok(!matches!(request, ConfirmationRequest::DeleteArtifact { artifact, .. } if artifact.name() == "dist"))
+        }));
+
+        let venv_info = cli_mode.cleaner.find_venv_directories().unwrap().remove(0);
+        assert!(cli_mode.process_venv_directory(&venv_info).unwrap().deleted);
+        let asked = asked.lock().unwrap();
+        assert_eq!(asked.len(), 3);
+        assert!(asked[1].starts_with("Also delete build/"));
+        assert!(!project.join("build").exists());
+        assert!(project.join("dist").exists());
+
+        // --force keeps them unless --include-artifacts opts in, and never asks
+        for (include, kept) in [(false, true), (true, false)] {
+            create_venv(&project.join(".venv"));
+            let mut args = vec!["test", temp_dir.path().to_str().unwrap(), "-r", "--deep-clean", "-f", "--permanent"];
+            if include {
+                args.push("--include-artifacts");
+            }
+            let matches = create_test_command().try_get_matches_from(args).unwrap();
+            let cli_mode = CliMode::new(&matches).unwrap().with_confirmation_provider(Arc::new(|_: &ConfirmationRequest<'_>| {
+                panic!("--force must not ask")
+            }));
+            let venv_info = cli_mode.cleaner.find_venv_directories().unwrap().remove(0);
+            assert!(cli_mode.process_venv_directory(&venv_info).unwrap().deleted);
+            assert_eq!(project.join("dist").exists(), kept);
+        }
+    }
+
+    #[cfg(all(feature = "decision-hook", unix))]
+    #[test]
+    fn test_decision_hook_delete_respects_disabled_force() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let venv_path = temp_dir.path().join("proj").join(".venv");
+        create_venv(&venv_path);
+        let script = temp_dir.path().join("decide.sh");
+        std::fs::write(&script, "#!/bin/sh\ncat > /dev/null\necho delete\n").unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let matches = create_test_command().try_get_matches_from(["test", temp_dir.path().to_str().unwrap(), "-r"]).unwrap();
+        let asked = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = Arc::clone(&asked);
+        let mut cli_mode = CliMode::new(&matches).unwrap().with_confirmation_provider(Arc::new(move |_: &ConfirmationRequest<'_>| {
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(false)
+        }));
+        cli_mode.decision_hook = Some(script.display().to_string());
+        let policy = crate::core::PolicyConfig { disable_force: true, ..Default::default() };
+        cli_mode.cleaner = cli_mode.cleaner.clone().with_policy(policy);
+
+        let venv_info = cli_mode.cleaner.find_venv_directories().unwrap().remove(0);
+        let outcome = cli_mode.process_venv_directory(&venv_info).unwrap();
+        assert!(!outcome.deleted);
+        assert_eq!(asked.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert!(venv_path.exists());
     }
 }
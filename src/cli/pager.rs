@@ -0,0 +1,182 @@
+//! Paging long CLI output
+//!
+//! `--pager` sends everything the run prints through `$PAGER` (`less` when
+//! unset), as git does, so query results with hundreds of .venv directories
+//! can be scrolled and searched. Colours chosen for the terminal are kept,
+//! and unless `$LESS` is set `less` gets `FRX`: pass colour codes through,
+//! quit at once when everything fits on one screen, and leave the output in
+//! the scrollback. Nothing is paged when stdout is not a terminal, or when
+//! `$PAGER` is empty or `cat`.
+
+use std::env;
+use std::io::{self, IsTerminal, Write};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::debug;
+
+use crate::core::{Result, VenvCleanerError};
+
+/// Pager used when `$PAGER` is not set
+pub const DEFAULT_PAGER: &str = "less";
+
+/// `$LESS` given to the pager when unset: raw colours, quit on one screen, no clearing
+const DEFAULT_LESS: &str = "FRX";
+
+/// Whether output is currently going through a pager
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Check whether output is currently going through a pager
+///
+/// Spinners are hidden then, since they would draw over the pager.
+pub fn is_active() -> bool {
+    ACTIVE.load(Ordering::Relaxed)
+}
+
+/// Pick the pager command
+///
+/// # Arguments
+/// * `pager_env` - The value of `$PAGER`, if set
+///
+/// # Returns
+/// The command, or None if paging is turned off
+pub fn resolve_command(pager_env: Option<&str>) -> Option<String> {
+    match pager_env.map(str::trim) {
+        None => Some(DEFAULT_PAGER.to_string()),
+        Some("") | Some("cat") => None,
+        Some(command) => Some(command.to_string()),
+    }
+}
+
+/// Build the command starting a pager
+///
+/// Like editor templates, the command is split on whitespace and run
+/// without a shell.
+///
+/// # Arguments
+/// * `template` - The pager command, e.g. `less -S`
+///
+/// # Returns
+/// The command, reading from a pipe, or an error if the template is empty
+pub fn pager_command(template: &str) -> Result<Command> {
+    let mut words = template.split_whitespace();
+    let program = words
+        .next()
+        .ok_or_else(|| VenvCleanerError::InvalidArgument("Pager command is empty".to_string()))?;
+
+    let mut command = Command::new(program);
+    command.args(words).stdin(Stdio::piped());
+    if env::var_os("LESS").is_none() {
+        command.env("LESS", DEFAULT_LESS);
+    }
+    Ok(command)
+}
+
+/// A running pager that stdout is redirected to until it is dropped
+pub struct Pager {
+    /// The pager process
+    child: Child,
+    /// The terminal's stdout, put back when the pager is dropped
+    #[cfg(unix)]
+    saved_stdout: libc::c_int,
+    /// The SIGPIPE handler before paging
+    #[cfg(unix)]
+    saved_sigpipe: libc::sighandler_t,
+}
+
+impl Pager {
+    /// Start the pager and send stdout through it, if stdout is a terminal
+    ///
+    /// # Returns
+    /// The running pager, None if there is nothing to page to, or an error if it could not start
+    pub fn start() -> Result<Option<Self>> {
+        if !io::stdout().is_terminal() {
+            return Ok(None);
+        }
+        let Some(template) = resolve_command(env::var("PAGER").ok().as_deref()) else {
+            return Ok(None);
+        };
+        Self::spawn(&template)
+    }
+
+    /// Start a pager command and redirect stdout to it
+    #[cfg(unix)]
+    fn spawn(template: &str) -> Result<Option<Self>> {
+        use std::os::unix::io::AsRawFd;
+
+        // Decided while stdout is still the terminal, then kept for the pipe
+        let colorize = colored::control::SHOULD_COLORIZE.should_colorize();
+
+        let mut child = pager_command(template)?
+            .spawn()
+            .map_err(|e| VenvCleanerError::Io(format!("Failed to start pager '{}': {}", template, e)))?;
+        let Some(pipe) = child.stdin.take() else {
+            return Ok(None);
+        };
+        io::stdout().flush()?;
+
+        // SAFETY: both descriptors are open; the pipe stays open through its copy on stdout
+        let saved_stdout = unsafe { libc::dup(libc::STDOUT_FILENO) };
+        if saved_stdout < 0 || unsafe { libc::dup2(pipe.as_raw_fd(), libc::STDOUT_FILENO) } < 0 {
+            let error = io::Error::last_os_error();
+            if saved_stdout >= 0 {
+                // SAFETY: the descriptor was just duplicated and is not used elsewhere
+                unsafe { libc::close(saved_stdout) };
+            }
+            return Err(error.into());
+        }
+        drop(pipe);
+
+        // Quitting the pager early ends the run quietly instead of failing every print
+        // SAFETY: restoring the default action of a signal is always sound
+        let saved_sigpipe = unsafe { libc::signal(libc::SIGPIPE, libc::SIG_DFL) };
+
+        colored::control::set_override(colorize);
+        ACTIVE.store(true, Ordering::Relaxed);
+        debug!("Paging output through {}", template);
+        Ok(Some(Self { child, saved_stdout, saved_sigpipe }))
+    }
+
+    /// Platforms without `dup2` print straight to the terminal
+    #[cfg(not(unix))]
+    fn spawn(_template: &str) -> Result<Option<Self>> {
+        Ok(None)
+    }
+}
+
+impl Drop for Pager {
+    /// Put the terminal back on stdout and wait until the user has quit the pager
+    fn drop(&mut self) {
+        let _ = io::stdout().flush();
+        #[cfg(unix)]
+        // SAFETY: the saved descriptor is still open; replacing stdout closes the pipe, ending the pager's input
+        unsafe {
+            libc::dup2(self.saved_stdout, libc::STDOUT_FILENO);
+            libc::close(self.saved_stdout);
+            libc::signal(libc::SIGPIPE, self.saved_sigpipe);
+        }
+        let _ = self.child.wait();
+        ACTIVE.store(false, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_command() {
+        assert_eq!(resolve_command(None).as_deref(), Some(DEFAULT_PAGER));
+        assert_eq!(resolve_command(Some("most")).as_deref(), Some("most"));
+        assert_eq!(resolve_command(Some("")), None);
+        assert_eq!(resolve_command(Some("cat")), None);
+    }
+
+    #[test]
+    fn test_pager_command_splits_arguments() {
+        let command = pager_command("less -S --mouse").unwrap();
+        assert_eq!(command.get_program(), "less");
+        let args: Vec<_> = command.get_args().map(|a| a.to_string_lossy().to_string()).collect();
+        assert_eq!(args, vec!["-S", "--mouse"]);
+        assert!(pager_command("  ").is_err());
+    }
+}
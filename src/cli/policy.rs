@@ -0,0 +1,294 @@
+//! `policy simulate` subcommand for VenvCleaner
+//!
+//! This module runs the rules of an unattended cleanup over a scan, or over
+//! a report saved with `--query --progress json`, and prints what each rule
+//! would keep and what would be deleted, with totals, without touching
+//! anything. Afterwards the thresholds can be changed at a prompt
+//! (`older-than=60d min-size=500MB`) and the same scan simulated again, so
+//! they can be tuned before a cron job or service runs with `--force`.
+
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::PathBuf;
+use clap::ArgMatches;
+use colored::*;
+use tracing::info;
+
+use crate::core::{safety, ByteSize, Config, OfflineIndex, PolicyConfig, PolicySimulation, SimulationRule, SimulationRules, VenvCleaner, VenvCleanerError, VenvFilter, VenvInfo, Result};
+use super::{resolve_base_directory, trash, DEFAULT_PLAN_FILE};
+
+/// Number of .venv directories listed under each rule
+const LISTED_VENVS: usize = 3;
+
+/// Thresholds of a simulation, as typed on the command line or at the prompt
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Thresholds {
+    /// `--older-than`, e.g. `90d`
+    pub older_than: Option<String>,
+    /// `--min-size`, e.g. `200MB`
+    pub min_size: Option<String>,
+    /// `--exclude-size-above`, None for the default and `none` to turn it off
+    pub exclude_size_above: Option<String>,
+}
+
+impl Thresholds {
+    /// Change thresholds from `key=value` words, e.g. `older-than=60d min-size=none`
+    ///
+    /// `none` clears a threshold; for `exclude-size-above` it turns the guard off.
+    ///
+    /// # Arguments
+    /// * `line` - The words typed at the prompt
+    ///
+    /// # Returns
+    /// An error naming an unknown key or invalid value; nothing changes then
+    pub fn apply(&mut self, line: &str) -> Result<()> {
+        let mut changed = self.clone();
+        for word in line.split_whitespace() {
+            let (key, value) = word.split_once('=').ok_or_else(|| {
+                VenvCleanerError::InvalidArgument(format!("Expected key=value, got '{}'", word))
+            })?;
+            let value = Some(value.to_string());
+            match key.trim_start_matches("--") {
+                "older-than" => changed.older_than = value.filter(|v| v != "none"),
+                "min-size" => changed.min_size = value.filter(|v| v != "none"),
+                "exclude-size-above" => changed.exclude_size_above = value,
+                other => {
+                    return Err(VenvCleanerError::InvalidArgument(format!(
+                        "Unknown threshold '{}' (expected older-than, min-size or exclude-size-above)",
+                        other
+                    )))
+                }
+            }
+        }
+        changed.rules(PolicyConfig::default(), false)?;
+        *self = changed;
+        Ok(())
+    }
+
+    /// Build the rules of an unattended run from these thresholds
+    ///
+    /// # Arguments
+    /// * `policy` - The policy of the config files
+    /// * `include_user_data` - Whether `--include-user-data` is given
+    pub fn rules(&self, policy: PolicyConfig, include_user_data: bool) -> Result<SimulationRules> {
+        let older_than = self.older_than.as_deref().map(trash::parse_older_than).transpose()?;
+        let min_size = self.min_size.as_deref().map(ByteSize::parse).transpose()?;
+        let exclude_size_above = match self.exclude_size_above.as_deref() {
+            None => Some(safety::DEFAULT_EXCLUDE_SIZE_ABOVE),
+            Some("none") | Some("off") => None,
+            Some(size) => Some(ByteSize::parse(size)?),
+        };
+        Ok(SimulationRules {
+            filter: VenvFilter::default().with_older_than(older_than).with_min_size(min_size),
+            policy,
+            exclude_size_above,
+            include_user_data,
+        })
+    }
+
+    /// Describe the thresholds for the report heading
+    fn describe(&self) -> String {
+        format!(
+            "older-than={} min-size={} exclude-size-above={}",
+            self.older_than.as_deref().unwrap_or("none"),
+            self.min_size.as_deref().unwrap_or("none"),
+            self.exclude_size_above.as_deref().unwrap_or("200GB"),
+        )
+    }
+}
+
+/// Handler for the `policy simulate` subcommand
+pub struct PolicyCommand {
+    /// The core VenvCleaner instance, scanning the disk or a saved report
+    cleaner: VenvCleaner,
+    /// Thresholds of the first simulation
+    thresholds: Thresholds,
+    /// Whether .venv directories holding user data are deleted too
+    include_user_data: bool,
+}
+
+impl PolicyCommand {
+    /// Create a new PolicyCommand from the `policy simulate` subcommand arguments
+    ///
+    /// # Arguments
+    /// * `matches` - Parsed arguments of the `simulate` subcommand
+    ///
+    /// # Returns
+    /// A new PolicyCommand instance or an error
+    pub fn new(matches: &ArgMatches) -> Result<Self> {
+        let base_directory = resolve_base_directory(matches)?;
+        let recursive = matches.get_flag("recursive");
+        let verbosity = matches.get_count("verbose");
+        let config = Config::load()?;
+
+        // The plan is never written, since nothing is deleted
+        let offline = matches
+            .get_one::<String>("from-index")
+            .map(|index| OfflineIndex::load(std::path::Path::new(index), PathBuf::from(DEFAULT_PLAN_FILE)))
+            .transpose()?;
+        let cleaner = VenvCleaner::new(base_directory, recursive, true, true, verbosity)
+            .with_policy(config.policy)
+            .with_excludes(config.scan.exclude_paths())
+            .with_offline_index(offline);
+
+        let thresholds = Thresholds {
+            older_than: matches.get_one::<String>("older-than").cloned(),
+            min_size: matches.get_one::<String>("min-size").cloned(),
+            exclude_size_above: matches.get_one::<String>("exclude-size-above").cloned(),
+        };
+        // Invalid thresholds are reported before the scan
+        thresholds.rules(PolicyConfig::default(), false)?;
+
+        Ok(Self { cleaner, thresholds, include_user_data: matches.get_flag("include-user-data") })
+    }
+
+    /// Execute the simulate subcommand, offering to change thresholds when stdin is a terminal
+    pub fn execute(&self) -> Result<()> {
+        let stdin = io::stdin();
+        let interactive = stdin.is_terminal();
+        self.run(&mut stdin.lock(), interactive).map(|_| ())
+    }
+
+    /// Simulate, then read changed thresholds from `input` until an empty line
+    ///
+    /// # Arguments
+    /// * `input` - Where changed thresholds come from
+    /// * `interactive` - Whether to prompt for changed thresholds at all
+    ///
+    /// # Returns
+    /// The last simulation, or None if no .venv directories were found
+    pub fn run(&self, input: &mut impl BufRead, interactive: bool) -> Result<Option<PolicySimulation>> {
+        info!("Executing policy simulate subcommand");
+
+        let venv_dirs = match self.cleaner.find_venv_directories() {
+            Ok(venv_dirs) => venv_dirs,
+            Err(VenvCleanerError::NoVenvFound) => {
+                println!("{}", "No .venv directories found.".yellow());
+                return Ok(None);
+            }
+            Err(e) => return Err(e),
+        };
+
+        let mut thresholds = self.thresholds.clone();
+        let mut simulation = self.simulate(&venv_dirs, &thresholds)?;
+        if !interactive {
+            return Ok(Some(simulation));
+        }
+        loop {
+            print!("\n{} ", "Change thresholds (e.g. older-than=60d min-size=500MB), or Enter to finish:".bold());
+            io::stdout().flush()?;
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 || line.trim().is_empty() {
+                break;
+            }
+            match thresholds.apply(&line) {
+                Ok(()) => simulation = self.simulate(&venv_dirs, &thresholds)?,
+                Err(e) => println!("❌ {}", e.to_string().red()),
+            }
+        }
+        Ok(Some(simulation))
+    }
+
+    /// Run one simulation over the scan and print its report
+    fn simulate(&self, venv_dirs: &[VenvInfo], thresholds: &Thresholds) -> Result<PolicySimulation> {
+        let rules = thresholds.rules(self.cleaner.policy().clone(), self.include_user_data)?;
+        let simulation = PolicySimulation::run(venv_dirs, &rules);
+        let total: u64 = venv_dirs.iter().map(|venv| venv.size().bytes()).sum();
+
+        println!(
+            "\n🧪 {} {} .venv directories ({}) with {}; nothing is deleted",
+            "Simulating".bold(),
+            venv_dirs.len().to_string().cyan(),
+            ByteSize::new(total),
+            thresholds.describe().cyan()
+        );
+        for rule in SimulationRule::ALL {
+            let kept = simulation.kept_by(rule);
+            if kept.is_empty() {
+                continue;
+            }
+            let bytes: u64 = kept.iter().map(|venv| venv.size().bytes()).sum();
+            println!("   ✋ Kept {:>4} ({:>10}): {}", kept.len(), ByteSize::new(bytes).to_string(), rule.describe());
+            for venv in kept.iter().take(LISTED_VENVS) {
+                println!("        {}", venv.location().dimmed());
+            }
+            if kept.len() > LISTED_VENVS {
+                println!("        {}", format!("… and {} more", kept.len() - LISTED_VENVS).dimmed());
+            }
+        }
+
+        let (kept, kept_bytes) = simulation.kept_totals();
+        println!(
+            "   🗑️  {} {} .venv directories, freeing {}",
+            "Would delete".bold(),
+            simulation.deleted().len().to_string().green(),
+            ByteSize::new(simulation.deleted_bytes()).to_string().green()
+        );
+        for venv in simulation.deleted().iter().take(LISTED_VENVS) {
+            let why = rules.filter.explain(venv).unwrap_or_else(|| "no criteria set".to_string());
+            println!("        {} {}", venv.location().dimmed(), format!("({})", why).dimmed());
+        }
+        if simulation.deleted().len() > LISTED_VENVS {
+            println!("        {}", format!("… and {} more", simulation.deleted().len() - LISTED_VENVS).dimmed());
+        }
+        println!("   ✋ Would keep {} ({})", kept, ByteSize::new(kept_bytes));
+        Ok(simulation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Command;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_test_command() -> Command {
+        Command::new("simulate")
+            .arg(clap::Arg::new("directory").index(1))
+            .arg(clap::Arg::new("recursive").short('r').action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("older-than").long("older-than"))
+            .arg(clap::Arg::new("min-size").long("min-size"))
+            .arg(clap::Arg::new("exclude-size-above").long("exclude-size-above"))
+            .arg(clap::Arg::new("include-user-data").long("include-user-data").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("from-index").long("from-index"))
+            .arg(clap::Arg::new("verbose").short('v').action(clap::ArgAction::Count))
+    }
+
+    #[test]
+    fn test_thresholds_apply() {
+        let mut thresholds = Thresholds { older_than: Some("90d".to_string()), ..Thresholds::default() };
+        thresholds.apply("older-than=60d min-size=500MB").unwrap();
+        assert_eq!(thresholds.older_than.as_deref(), Some("60d"));
+        assert_eq!(thresholds.min_size.as_deref(), Some("500MB"));
+
+        thresholds.apply("--min-size=none exclude-size-above=none").unwrap();
+        assert_eq!(thresholds.min_size, None);
+        assert_eq!(thresholds.rules(PolicyConfig::default(), false).unwrap().exclude_size_above, None);
+
+        // A bad value leaves every threshold as it was
+        assert!(thresholds.apply("older-than=30d min-size=huge").is_err());
+        assert_eq!(thresholds.older_than.as_deref(), Some("60d"));
+        assert!(thresholds.apply("age=30d").is_err());
+    }
+
+    #[test]
+    fn test_simulate_deletes_nothing_and_reruns() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = temp_dir.path().join("project").join(".venv");
+        fs::create_dir_all(venv.join("bin")).unwrap();
+        fs::write(venv.join("pyvenv.cfg"), "home = /usr/bin\n").unwrap();
+
+        let matches = create_test_command()
+            .try_get_matches_from(["simulate", "-r", "--min-size", "1", temp_dir.path().to_str().unwrap()])
+            .unwrap();
+        let command = PolicyCommand::new(&matches).unwrap();
+        let simulation = command.run(&mut "".as_bytes(), false).unwrap().unwrap();
+        assert_eq!(simulation.deleted().len(), 1);
+        assert!(venv.exists());
+
+        // Raising the size threshold at the prompt keeps it instead
+        let simulation = command.run(&mut "min-size=1GB\n\n".as_bytes(), true).unwrap().unwrap();
+        assert!(simulation.deleted().is_empty());
+        assert_eq!(simulation.kept_by(SimulationRule::CriteriaNotMet).len(), 1);
+    }
+}
@@ -0,0 +1,183 @@
+//! Reading answers to interactive prompts, optionally with a time limit
+//!
+//! Semi-automated runs sometimes end up at a prompt nobody is watching.
+//! `--confirm-timeout 30s` makes every prompt of the cleanup give up after
+//! that long and take the `--default-answer` (`n` unless given), as if it
+//! had been typed, so a forgotten session cannot hang a CI job forever.
+//!
+//! Stdin is read on a background thread so waiting can stop; an answer typed
+//! after a prompt timed out goes to the next prompt.
+
+use std::io::{self, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
+use colored::*;
+
+use crate::core::{ConfirmationProvider, ConfirmationRequest, Result, VenvCleanerError};
+
+/// Lines read from stdin by the background reader
+static STDIN_LINES: OnceLock<Mutex<Receiver<String>>> = OnceLock::new();
+
+/// Where prompt answers come from and how long to wait for them
+#[derive(Debug, Clone, PartialEq)]
+pub struct Prompter {
+    /// How long to wait for an answer, or None to wait forever
+    timeout: Option<Duration>,
+    /// The answer taken when the wait runs out
+    default_answer: String,
+}
+
+impl Prompter {
+    /// Create a prompter giving up after a time limit
+    ///
+    /// # Arguments
+    /// * `timeout` - How long to wait for each answer, or None to wait forever
+    /// * `default_answer` - The answer taken when the wait runs out
+    pub fn new(timeout: Option<Duration>, default_answer: &str) -> Self {
+        Self { timeout, default_answer: default_answer.to_string() }
+    }
+
+    /// Read one answer from stdin
+    ///
+    /// # Returns
+    /// The line typed (empty at the end of input), or the default answer after the timeout
+    pub fn read_answer(&self) -> Result<String> {
+        let Some(timeout) = self.timeout else {
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            return Ok(input);
+        };
+        let lines = STDIN_LINES.get_or_init(|| Mutex::new(spawn_stdin_reader()));
+        let lines = lines.lock().map_err(|_| VenvCleanerError::Io("Stdin is unavailable".to_string()))?;
+        Ok(self.wait_for_answer(&lines, timeout))
+    }
+
+    /// Ask a yes/no question on stdin (defaults to no)
+    ///
+    /// # Arguments
+    /// * `question` - The question, without the `(y/N)` hint
+    pub fn ask_yes_no(&self, question: &str) -> Result<bool> {
+        print!("\n{} (y/N): ", question.bold());
+        io::stdout().flush()?;
+
+        let answer = self.read_answer()?.trim().to_lowercase();
+        Ok(answer == "y" || answer == "yes")
+    }
+
+    /// Wait for a line from the reader, taking the default answer after the timeout
+    fn wait_for_answer(&self, lines: &Receiver<String>, timeout: Duration) -> String {
+        match lines.recv_timeout(timeout) {
+            Ok(line) => line,
+            Err(RecvTimeoutError::Timeout) => {
+                println!(
+                    "{}",
+                    format!("(no answer after {}s, answering \"{}\")", timeout.as_secs(), self.default_answer).dimmed()
+                );
+                self.default_answer.clone()
+            }
+            // The end of input declines, as without a timeout
+            Err(RecvTimeoutError::Disconnected) => String::new(),
+        }
+    }
+}
+
+impl ConfirmationProvider for Prompter {
+    fn confirm(&self, request: &ConfirmationRequest<'_>) -> Result<bool> {
+        self.ask_yes_no(&request.question())
+    }
+}
+
+/// Wait a while for a line typed on stdin, e.g. a key between `--watch` refreshes
+///
+/// # Arguments
+/// * `timeout` - How long to wait
+///
+/// # Returns
+/// The line typed, or None once the time is up (also when stdin is closed)
+pub fn read_line_within(timeout: Duration) -> Option<String> {
+    let lines = STDIN_LINES.get_or_init(|| Mutex::new(spawn_stdin_reader()));
+    let lines = lines.lock().ok()?;
+    match lines.recv_timeout(timeout) {
+        Ok(line) => Some(line),
+        Err(RecvTimeoutError::Timeout) => None,
+        // Nothing can be typed any more, so just wait out the interval
+        Err(RecvTimeoutError::Disconnected) => {
+            thread::sleep(timeout);
+            None
+        }
+    }
+}
+
+/// Start reading stdin line by line on a background thread
+fn spawn_stdin_reader() -> Receiver<String> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || loop {
+        let mut line = String::new();
+        match io::stdin().read_line(&mut line) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+    receiver
+}
+
+/// Parse a prompt timeout such as `30s`, `5m` or `1h`; a bare number means seconds
+///
+/// # Arguments
+/// * `text` - The timeout to parse
+///
+/// # Returns
+/// The timeout, or an error naming the accepted units
+pub fn parse_timeout(text: &str) -> Result<Duration> {
+    let text = text.trim();
+    let invalid = || VenvCleanerError::InvalidArgument(format!(
+        "Invalid timeout '{}': use a number followed by s, m or h (e.g. 30s)",
+        text
+    ));
+
+    let (number, unit) = match text.find(|c: char| !c.is_ascii_digit()) {
+        Some(index) => text.split_at(index),
+        None => (text, "s"),
+    };
+    let value: u64 = number.parse().map_err(|_| invalid())?;
+
+    match unit {
+        "s" => Ok(Duration::from_secs(value)),
+        "m" => Ok(Duration::from_secs(value * 60)),
+        "h" => Ok(Duration::from_secs(value * 3600)),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timeout() {
+        assert_eq!(parse_timeout("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_timeout("2m").unwrap(), Duration::from_secs(120));
+        assert_eq!(parse_timeout("45").unwrap(), Duration::from_secs(45));
+        assert!(matches!(parse_timeout("3d"), Err(VenvCleanerError::InvalidArgument(_))));
+        assert!(parse_timeout("soon").is_err());
+    }
+
+    #[test]
+    fn test_wait_takes_default_answer_after_timeout() {
+        let prompter = Prompter::new(Some(Duration::from_millis(10)), "n");
+        let (sender, receiver) = mpsc::channel();
+        assert_eq!(prompter.wait_for_answer(&receiver, Duration::from_millis(10)), "n");
+
+        sender.send("y\n".to_string()).unwrap();
+        assert_eq!(prompter.wait_for_answer(&receiver, Duration::from_millis(10)), "y\n");
+
+        drop(sender);
+        assert_eq!(prompter.wait_for_answer(&receiver, Duration::from_millis(10)), "");
+    }
+}
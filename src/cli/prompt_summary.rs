@@ -0,0 +1,106 @@
+//! `prompt-summary` subcommand for VenvCleaner
+//!
+//! This module prints the summary of the last full scan on one line, such
+//! as `venvs: 37 / 52.0GB`, for a shell prompt or a message of the day. It
+//! only reads the cached summary (see `core::summary_cache`) and never scans,
+//! so it returns in a few milliseconds; with no summary cached yet it prints
+//! nothing rather than slowing the prompt down or cluttering it.
+
+use std::path::PathBuf;
+use chrono::{Duration, Utc};
+use clap::ArgMatches;
+
+use crate::core::summary_cache::{self, ScanSummary};
+use crate::core::Result;
+
+/// Handler for the `prompt-summary` subcommand
+#[derive(Debug)]
+pub struct PromptSummaryCommand {
+    /// The summary file, None where there is no cache directory
+    path: Option<PathBuf>,
+    /// The line printed, with `{count}`, `{size}`, `{old}` and `{age}` filled in
+    format: String,
+    /// Print nothing for a summary older than this
+    max_age: Option<Duration>,
+}
+
+impl PromptSummaryCommand {
+    /// Create a new PromptSummaryCommand from the `prompt-summary` subcommand arguments
+    ///
+    /// # Arguments
+    /// * `matches` - Parsed arguments of the `prompt-summary` subcommand
+    ///
+    /// # Returns
+    /// A new PromptSummaryCommand instance or an error
+    pub fn new(matches: &ArgMatches) -> Result<Self> {
+        let max_age = matches
+            .get_one::<String>("max-age")
+            .map(|age| super::trash::parse_older_than(age))
+            .transpose()?;
+        Ok(Self {
+            path: ScanSummary::default_path(),
+            format: matches
+                .get_one::<String>("format")
+                .cloned()
+                .unwrap_or_else(|| summary_cache::DEFAULT_FORMAT.to_string()),
+            max_age,
+        })
+    }
+
+    /// Read the summary from this file instead of the cache directory
+    pub fn with_path(mut self, path: PathBuf) -> Self {
+        self.path = Some(path);
+        self
+    }
+
+    /// Get the line to print, None when there is no recent enough summary
+    pub fn line(&self) -> Option<String> {
+        let summary = ScanSummary::load(self.path.as_deref()?)?;
+        let now = Utc::now();
+        if self.max_age.is_some_and(|max_age| now - summary.at > max_age) {
+            return None;
+        }
+        Some(summary.render(&self.format, now))
+    }
+
+    /// Execute the prompt-summary subcommand
+    pub fn execute(&self) -> Result<()> {
+        if let Some(line) = self.line() {
+            println!("{}", line);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use clap::{Arg, Command};
+    use tempfile::TempDir;
+
+    fn command(args: &[&str]) -> PromptSummaryCommand {
+        let matches = Command::new("prompt-summary")
+            .arg(Arg::new("format").long("format"))
+            .arg(Arg::new("max-age").long("max-age"))
+            .try_get_matches_from(args)
+            .unwrap();
+        PromptSummaryCommand::new(&matches).unwrap()
+    }
+
+    #[test]
+    fn test_prompt_line() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("summary.json");
+        assert_eq!(command(&["prompt-summary"]).with_path(path.clone()).line(), None);
+
+        let summary = ScanSummary::of(&[Path::new("/w")], &[], Utc::now() - Duration::days(3));
+        summary.save(&path).unwrap();
+        assert_eq!(command(&["prompt-summary"]).with_path(path.clone()).line().as_deref(), Some("venvs: 0 / 0B"));
+        assert_eq!(
+            command(&["prompt-summary", "--format", "{count} venvs, scanned {age} ago"]).with_path(path.clone()).line().as_deref(),
+            Some("0 venvs, scanned 3d ago")
+        );
+        assert_eq!(command(&["prompt-summary", "--max-age", "1d"]).with_path(path).line(), None);
+    }
+}
@@ -0,0 +1,133 @@
+//! `refresh-cache` subcommand for VenvCleaner
+//!
+//! This module rescans the configured roots without printing anything, for
+//! cron or a systemd timer, and rewrites what interactive runs would
+//! otherwise compute on the spot: the summary `prompt-summary` prints, a
+//! sample of the size history and the project activity of every .venv
+//! found. The activity is recomputed even where the cached value is still
+//! fresh, so a session started the next morning finds every project cached.
+//! Only errors are reported, on stderr with a failing exit status.
+
+use chrono::Utc;
+use clap::ArgMatches;
+use tracing::{info, warn};
+
+use crate::core::{size_history, ActivityCache, Config, Detector, ScanSummary, VenvCleaner, VenvCleanerError, Result};
+use super::resolve_scan_roots;
+
+/// Handler for the `refresh-cache` subcommand
+pub struct RefreshCacheCommand {
+    /// The core VenvCleaner instance
+    cleaner: VenvCleaner,
+}
+
+impl RefreshCacheCommand {
+    /// Create a new RefreshCacheCommand from the `refresh-cache` subcommand arguments
+    ///
+    /// # Arguments
+    /// * `matches` - Parsed arguments of the `refresh-cache` subcommand
+    ///
+    /// # Returns
+    /// A new RefreshCacheCommand instance or an error
+    pub fn new(matches: &ArgMatches) -> Result<Self> {
+        let config = Config::load()?.with_profile(matches.get_one::<String>("profile").map(String::as_str))?;
+        let (base_directory, extra_roots) = resolve_scan_roots(matches, &config.scan)?;
+        let verbosity = matches.get_count("verbose");
+
+        // The caches describe every .venv, so the age and size filters of the
+        // config are left out; a root that cannot be read (an unmounted share,
+        // say) should not keep the others from being refreshed
+        let cleaner = VenvCleaner::new(base_directory, true, false, false, verbosity)
+            .with_policy(config.policy)
+            .with_skip_hidden(config.scan.skip_hidden.unwrap_or(false))
+            .with_excludes(config.scan.exclude_paths())
+            .with_detectors(Detector::from_configs(&config.detectors)?)
+            .with_extra_roots(extra_roots)
+            .with_partial_roots(true);
+
+        Ok(Self { cleaner })
+    }
+
+    /// Rescan the roots and refresh the activity of every .venv found
+    ///
+    /// # Arguments
+    /// * `activity` - The activity cache to refresh
+    ///
+    /// # Returns
+    /// The summary of the scan
+    pub fn refresh(&self, activity: &mut ActivityCache) -> Result<ScanSummary> {
+        let venv_dirs = match self.cleaner.find_venv_directories() {
+            Ok(venv_dirs) => venv_dirs,
+            Err(VenvCleanerError::NoVenvFound) => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        for (root, error) in self.cleaner.last_failed_roots() {
+            warn!("Could not refresh {}: {}", root.display(), error);
+        }
+
+        for venv_info in &venv_dirs {
+            activity.refresh(venv_info);
+        }
+        Ok(ScanSummary::of(&self.cleaner.roots(), &venv_dirs, Utc::now()))
+    }
+
+    /// Execute the refresh-cache subcommand
+    pub fn execute(&self) -> Result<()> {
+        info!("Executing refresh-cache subcommand");
+
+        let mut activity = ActivityCache::load_default();
+        let summary = self.refresh(&mut activity)?;
+        activity.save()?;
+        if let Some(path) = ScanSummary::default_path() {
+            summary.save(&path)?;
+        }
+        size_history::record_scan(&self.cleaner.roots(), summary.venv_bytes);
+
+        info!(
+            "Refreshed the cache: {} .venv directories, {} bytes",
+            summary.venv_count, summary.venv_bytes
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use clap::{Arg, Command};
+    use tempfile::TempDir;
+
+    fn command(args: &[&str]) -> RefreshCacheCommand {
+        let matches = Command::new("refresh-cache")
+            .arg(Arg::new("directory").num_args(1..))
+            .arg(Arg::new("profile").long("profile"))
+            .arg(Arg::new("verbose").short('v').action(clap::ArgAction::Count))
+            .try_get_matches_from(args)
+            .unwrap();
+        RefreshCacheCommand::new(&matches).unwrap()
+    }
+
+    #[test]
+    fn test_refresh_covers_every_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let work = temp_dir.path().join("work");
+        let src = temp_dir.path().join("src");
+        for project in [work.join("a").join("deep"), src.join("b")] {
+            fs::create_dir_all(project.join(".venv").join("bin")).unwrap();
+            fs::write(project.join(".venv").join("pyvenv.cfg"), "home = /usr/bin\n").unwrap();
+            fs::write(project.join("main.py"), "print()\n").unwrap();
+        }
+
+        let mut activity = ActivityCache::in_memory();
+        let summary = command(&["refresh-cache", work.to_str().unwrap(), src.to_str().unwrap()]).refresh(&mut activity).unwrap();
+        assert_eq!(summary.roots, vec![work, src]);
+        assert_eq!(summary.venv_count, 2);
+
+        // An empty root still gets a summary, of nothing
+        let empty = temp_dir.path().join("empty");
+        fs::create_dir(&empty).unwrap();
+        let summary = command(&["refresh-cache", empty.to_str().unwrap()]).refresh(&mut activity).unwrap();
+        assert_eq!((summary.venv_count, summary.venv_bytes), (0, 0));
+    }
+}
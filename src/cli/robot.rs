@@ -0,0 +1,411 @@
+//! `--robot` mode: drive VenvCleaner with JSON commands on stdin
+//!
+//! Each line on stdin is one JSON request and produces exactly one JSON line
+//! on stdout. Scan results are kept between requests, so editor plugins can
+//! scan once and then issue `delete` and `stats` requests without rescanning.
+//!
+//! Requests look like `{"id": 1, "command": "scan", "root": "/path", "recursive": true}`,
+//! `{"command": "delete", "paths": ["/path/proj/.venv"]}`, `{"command": "stats"}`
+//! and `{"command": "quit"}`. The optional `id` is echoed back in the response.
+
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use clap::ArgMatches;
+use tracing::{debug, info};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::core::{timestamps, Config, DeleteHooks, DeleteMode, FileUtils, PolicyConfig, VenvCleaner, VenvCleanerError, VenvInfo, VenvStats, Result};
+use super::{resolve_base_directory, resolve_delete_mode};
+
+/// A request read from stdin
+#[derive(Debug, Deserialize)]
+struct RobotRequest {
+    /// Caller-supplied identifier echoed back in the response
+    id: Option<Value>,
+    /// The command to execute
+    #[serde(flatten)]
+    command: RobotCommand,
+}
+
+/// Commands understood by robot mode
+#[derive(Debug, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+enum RobotCommand {
+    /// Scan for .venv directories, optionally overriding the root and recursion
+    Scan {
+        root: Option<String>,
+        recursive: Option<bool>,
+    },
+    /// Delete .venv directories found by the last scan
+    Delete { paths: Vec<String> },
+    /// Aggregate statistics over the last scan
+    Stats,
+    /// Stop reading commands
+    Quit,
+}
+
+/// A response written to stdout
+#[derive(Debug, Serialize)]
+struct RobotResponse {
+    /// The request identifier, if one was given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<Value>,
+    /// Whether the command succeeded
+    ok: bool,
+    /// Error message for failed commands
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    /// Command-specific payload
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    payload: Option<ResponsePayload>,
+}
+
+/// Command-specific response data
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum ResponsePayload {
+    /// Result of a `scan` command
+    Scan { venvs: Vec<VenvEntry> },
+    /// Result of a `delete` command
+    Delete { results: Vec<DeleteResult> },
+    /// Result of a `stats` command
+    Stats {
+        total_count: usize,
+        total_bytes: u64,
+        by_python: Vec<PythonEntry>,
+    },
+}
+
+/// A .venv directory as reported to robot clients
+#[derive(Debug, Serialize)]
+struct VenvEntry {
+    path: String,
+    size_bytes: u64,
+    size_partial: bool,
+    created: String,
+    last_modified: String,
+    python_version: Option<String>,
+    /// Bytes of files that look like user data; clients should confirm before deleting
+    user_data_bytes: u64,
+}
+
+impl From<&VenvInfo> for VenvEntry {
+    fn from(venv_info: &VenvInfo) -> Self {
+        Self {
+            path: venv_info.path().display().to_string(),
+            size_bytes: venv_info.size().bytes(),
+            size_partial: venv_info.is_size_partial(),
+            created: timestamps::to_rfc3339(venv_info.created()),
+            last_modified: timestamps::to_rfc3339(venv_info.last_modified()),
+            python_version: venv_info.python_version().map(|v| v.to_string()),
+            user_data_bytes: venv_info.user_data_bytes(),
+        }
+    }
+}
+
+/// Outcome of deleting a single path
+#[derive(Debug, Serialize)]
+struct DeleteResult {
+    path: String,
+    ok: bool,
+    freed_bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Per-Python-version totals for the `stats` command
+#[derive(Debug, Serialize)]
+struct PythonEntry {
+    version: String,
+    count: usize,
+    total_bytes: u64,
+    eol: bool,
+}
+
+/// Robot mode session holding the most recent scan results
+pub struct RobotMode {
+    /// Default directory for scans
+    base_directory: PathBuf,
+    /// Default recursion setting for scans
+    recursive: bool,
+    /// Whether deletions are simulated
+    dry_run: bool,
+    /// Where deleted directories go
+    delete_mode: DeleteMode,
+    /// Verbosity level passed to the core
+    verbosity: u8,
+    /// Deletion restrictions from the system policy and user config
+    policy: PolicyConfig,
+    /// Commands run before and after each deletion
+    delete_hooks: DeleteHooks,
+    /// .venv directories found by the last scan
+    venvs: Vec<VenvInfo>,
+}
+
+impl RobotMode {
+    /// Create a new RobotMode instance from command line arguments
+    ///
+    /// # Arguments
+    /// * `matches` - Parsed command line arguments
+    ///
+    /// # Returns
+    /// A new RobotMode instance or an error
+    pub fn new(matches: &ArgMatches) -> Result<Self> {
+        let config = Config::load()?;
+        Ok(Self {
+            base_directory: resolve_base_directory(matches)?,
+            recursive: matches.get_flag("recursive"),
+            dry_run: matches.get_flag("dry-run"),
+            delete_mode: resolve_delete_mode(matches, config.delete_mode.as_deref())?,
+            verbosity: matches.get_count("verbose"),
+            policy: config.policy,
+            delete_hooks: config.hooks,
+            venvs: Vec::new(),
+        })
+    }
+
+    /// Read requests from `input` and write responses to `output` until EOF or `quit`
+    pub fn run<R: BufRead, W: Write>(&mut self, input: R, mut output: W) -> Result<()> {
+        info!("Starting robot mode");
+
+        for line in input.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (response, quit) = self.handle_line(&line);
+            writeln!(output, "{}", serde_json::to_string(&response).unwrap_or_default())?;
+            output.flush()?;
+
+            if quit {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Handle a single request line, returning the response and whether to stop
+    fn handle_line(&mut self, line: &str) -> (RobotResponse, bool) {
+        let request: RobotRequest = match serde_json::from_str(line) {
+            Ok(request) => request,
+            Err(e) => return (Self::failure(None, format!("Invalid request: {}", e)), false),
+        };
+        debug!("Robot request: {:?}", request.command);
+
+        let id = request.id;
+        match request.command {
+            RobotCommand::Scan { root, recursive } => match self.scan(root, recursive) {
+                Ok(venvs) => (Self::success(id, ResponsePayload::Scan { venvs }), false),
+                Err(e) => (Self::failure(id, e.to_string()), false),
+            },
+            RobotCommand::Delete { paths } => {
+                let results = self.delete(&paths);
+                (Self::success(id, ResponsePayload::Delete { results }), false)
+            }
+            RobotCommand::Stats => (Self::success(id, self.stats()), false),
+            RobotCommand::Quit => (RobotResponse { id, ok: true, error: None, payload: None }, true),
+        }
+    }
+
+    /// Scan for .venv directories and remember the results
+    fn scan(&mut self, root: Option<String>, recursive: Option<bool>) -> Result<Vec<VenvEntry>> {
+        let base_directory = root.map(PathBuf::from).unwrap_or_else(|| self.base_directory.clone());
+        if !base_directory.is_dir() {
+            return Err(VenvCleanerError::PathError {
+                path: base_directory.display().to_string(),
+                message: "Path is not a directory".to_string(),
+            });
+        }
+
+        let cleaner = self.cleaner(base_directory, recursive.unwrap_or(self.recursive));
+        self.venvs = match cleaner.find_venv_directories() {
+            Ok(venvs) => venvs,
+            Err(VenvCleanerError::NoVenvFound) => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        self.venvs.sort_by(|a, b| a.compare_by_path(b));
+
+        Ok(self.venvs.iter().map(VenvEntry::from).collect())
+    }
+
+    /// Delete .venv directories that were reported by the last scan
+    ///
+    /// Paths that were not part of the last scan are refused, so a client can
+    /// never use robot mode to remove arbitrary directories.
+    fn delete(&mut self, paths: &[String]) -> Vec<DeleteResult> {
+        let cleaner = self.cleaner(self.base_directory.clone(), self.recursive);
+        let mut results = Vec::with_capacity(paths.len());
+
+        for path in paths {
+            let position = FileUtils::position_of_path(self.venvs.iter().map(VenvInfo::path), path);
+            let result = match position {
+                Some(index) => {
+                    let venv_info = &self.venvs[index];
+                    let size = venv_info.size().bytes();
+                    // The core prints a human-readable line for dry runs, so skip it
+                    let outcome = if self.dry_run { Ok(()) } else { cleaner.delete_venv_directory(venv_info) };
+                    match outcome {
+                        Ok(()) => {
+                            if !self.dry_run {
+                                self.venvs.remove(index);
+                            }
+                            DeleteResult { path: path.clone(), ok: true, freed_bytes: size, error: None }
+                        }
+                        Err(e) => DeleteResult { path: path.clone(), ok: false, freed_bytes: 0, error: Some(e.to_string()) },
+                    }
+                }
+                None => DeleteResult {
+                    path: path.clone(),
+                    ok: false,
+                    freed_bytes: 0,
+                    error: Some("Path was not found by the last scan".to_string()),
+                },
+            };
+            results.push(result);
+        }
+
+        results
+    }
+
+    /// Build the statistics payload for the last scan
+    fn stats(&self) -> ResponsePayload {
+        let totals = VenvStats::from_venvs(&self.venvs);
+        let today = chrono::Utc::now().date_naive();
+        let by_python = VenvStats::by_python_version(&self.venvs, today)
+            .into_iter()
+            .map(|group| PythonEntry {
+                version: group.label(),
+                count: group.count(),
+                total_bytes: group.total_bytes(),
+                eol: group.is_eol(),
+            })
+            .collect();
+
+        ResponsePayload::Stats {
+            total_count: totals.total_count(),
+            total_bytes: totals.total_bytes(),
+            by_python,
+        }
+    }
+
+    /// Create a VenvCleaner for the given search settings
+    fn cleaner(&self, base_directory: PathBuf, recursive: bool) -> VenvCleaner {
+        // Robot clients confirm deletions themselves, so the core runs in force mode
+        VenvCleaner::new(base_directory, recursive, true, self.dry_run, self.verbosity)
+            .with_policy(self.policy.clone())
+            .with_delete_hooks(self.delete_hooks.clone())
+            .with_delete_mode(self.delete_mode)
+    }
+
+    /// Build a successful response
+    fn success(id: Option<Value>, payload: ResponsePayload) -> RobotResponse {
+        RobotResponse { id, ok: true, error: None, payload: Some(payload) }
+    }
+
+    /// Build a failed response
+    fn failure(id: Option<Value>, error: String) -> RobotResponse {
+        RobotResponse { id, ok: false, error: Some(error), payload: None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Command;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_test_command() -> Command {
+        Command::new("test")
+            .arg(clap::Arg::new("directory").index(1))
+            .arg(clap::Arg::new("recursive").short('r').action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("dry-run").long("dry-run").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("trash").long("trash").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("permanent").long("permanent").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("verbose").short('v').action(clap::ArgAction::Count))
+    }
+
+    fn run_session(dir: &TempDir, extra: &[&str], input: &str) -> Vec<Value> {
+        let mut args = vec!["test", "-r"];
+        args.extend_from_slice(extra);
+        args.push(dir.path().to_str().unwrap());
+        let matches = create_test_command().try_get_matches_from(args).unwrap();
+
+        let mut robot = RobotMode::new(&matches).unwrap();
+        let mut output = Vec::new();
+        robot.run(input.as_bytes(), &mut output).unwrap();
+
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_scan_and_stats() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = temp_dir.path().join("proj").join(".venv");
+        fs::create_dir_all(venv.join("bin")).unwrap();
+        fs::write(venv.join("pyvenv.cfg"), "version = 3.12.1\n").unwrap();
+
+        let responses = run_session(&temp_dir, &[], "{\"id\":1,\"command\":\"scan\"}\n{\"command\":\"stats\"}\n");
+
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0]["id"], 1);
+        assert_eq!(responses[0]["ok"], true);
+        assert_eq!(responses[0]["venvs"][0]["python_version"], "3.12.1");
+        assert_eq!(responses[1]["total_count"], 1);
+        assert_eq!(responses[1]["by_python"][0]["version"], "3.12");
+    }
+
+    #[test]
+    fn test_delete_only_scanned_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = temp_dir.path().join("proj").join(".venv");
+        fs::create_dir_all(venv.join("bin")).unwrap();
+        fs::write(venv.join("pyvenv.cfg"), "home = /usr/bin\n").unwrap();
+        let other = temp_dir.path().join("precious");
+        fs::create_dir_all(&other).unwrap();
+
+        let input = format!(
+            "{{\"command\":\"scan\"}}\n{{\"command\":\"delete\",\"paths\":[\"{}\",\"{}\"]}}\n",
+            venv.display(),
+            other.display()
+        );
+        let responses = run_session(&temp_dir, &["--permanent"], &input);
+
+        let results = &responses[1]["results"];
+        assert_eq!(results[0]["ok"], true);
+        assert_eq!(results[1]["ok"], false);
+        assert!(!venv.exists());
+        assert!(other.exists());
+    }
+
+    #[test]
+    fn test_delete_mode_follows_the_flags() {
+        for (flag, expected) in [("--trash", DeleteMode::Trash), ("--permanent", DeleteMode::Permanent)] {
+            let matches = create_test_command().try_get_matches_from(["test", flag]).unwrap();
+            let robot = RobotMode::new(&matches).unwrap();
+            assert_eq!(robot.cleaner(PathBuf::from("."), false).delete_mode(), expected);
+        }
+    }
+
+    #[test]
+    fn test_invalid_request_and_quit() {
+        let temp_dir = TempDir::new().unwrap();
+        let responses = run_session(
+            &temp_dir,
+            &["--dry-run"],
+            "not json\n{\"command\":\"quit\"}\n{\"command\":\"scan\"}\n",
+        );
+
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0]["ok"], false);
+        assert!(responses[0]["error"].as_str().unwrap().contains("Invalid request"));
+        assert_eq!(responses[1]["ok"], true);
+    }
+}
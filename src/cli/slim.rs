@@ -0,0 +1,169 @@
+//! `slim` subcommand for VenvCleaner
+//!
+//! This module reclaims space from .venv directories without deleting them,
+//! removing `__pycache__` folders, test suites bundled inside installed
+//! packages, pip caches and orphaned `*.dist-info` folders, optionally
+//! stripping debug symbols from shared libraries (`--strip`), and reports the
+//! bytes saved per environment and in total.
+
+use clap::ArgMatches;
+use colored::*;
+use tracing::info;
+
+use crate::core::{ByteSize, VenvCleaner, VenvCleanerError, Result};
+use super::resolve_base_directory;
+
+/// Handler for the `slim` subcommand
+pub struct SlimCommand {
+    /// The core VenvCleaner instance
+    cleaner: VenvCleaner,
+    /// Whether to strip debug symbols from shared libraries
+    strip_symbols: bool,
+}
+
+impl SlimCommand {
+    /// Create a new SlimCommand from the `slim` subcommand arguments
+    ///
+    /// # Arguments
+    /// * `matches` - Parsed arguments of the `slim` subcommand
+    ///
+    /// # Returns
+    /// A new SlimCommand instance or an error
+    pub fn new(matches: &ArgMatches) -> Result<Self> {
+        let base_directory = resolve_base_directory(matches)?;
+        let recursive = matches.get_flag("recursive");
+        let dry_run = matches.get_flag("dry-run");
+        let verbosity = matches.get_count("verbose");
+
+        // Slimming keeps every environment usable, so it never prompts
+        let cleaner = VenvCleaner::new(base_directory, recursive, true, dry_run, verbosity);
+
+        Ok(Self {
+            cleaner,
+            strip_symbols: matches.get_flag("strip"),
+        })
+    }
+
+    /// Execute the slim subcommand
+    pub fn execute(&self) -> Result<u64> {
+        info!("Executing slim subcommand");
+
+        let mut venv_dirs = match self.cleaner.find_venv_directories() {
+            Ok(venv_dirs) => venv_dirs,
+            Err(VenvCleanerError::NoVenvFound) => {
+                println!("{}", "No .venv directories found.".yellow());
+                return Ok(0);
+            }
+            Err(e) => return Err(e),
+        };
+        venv_dirs.sort_by(|a, b| a.compare_by_path(b));
+
+        let verb = if self.cleaner.is_dry_run() { "Would reclaim" } else { "Reclaimed" };
+        let mut total_saved = 0u64;
+        let mut failures = 0usize;
+
+        for venv_info in &venv_dirs {
+            match self.cleaner.slim_venv_directory(venv_info, self.strip_symbols) {
+                Ok(report) => {
+                    let stripped = if report.stripped() > 0 {
+                        format!(", {} libraries stripped", report.stripped())
+                    } else {
+                        String::new()
+                    };
+                    println!(
+                        "✂️  {} {} from {} ({} folders{})",
+                        verb,
+                        ByteSize::new(report.bytes_saved()).to_string().cyan(),
+                        venv_info.location(),
+                        report.removed().len(),
+                        stripped
+                    );
+                    for (path, error) in report.failed() {
+                        println!("   ❌ {}: {}", path.display(), error.red());
+                    }
+                    failures += report.failed().len();
+                    total_saved += report.bytes_saved();
+                }
+                Err(e) => {
+                    println!("❌ {}: {}", venv_info.location(), e.to_string().red());
+                    failures += 1;
+                }
+            }
+        }
+
+        println!(
+            "\n{} {} {} across {} .venv directories",
+            "Summary:".bold(),
+            verb,
+            ByteSize::new(total_saved).to_string().cyan(),
+            venv_dirs.len()
+        );
+        if failures > 0 {
+            println!("⚠️  {} items could not be slimmed", failures.to_string().red());
+        }
+        if self.strip_symbols && self.cleaner.is_dry_run() {
+            println!("{}", "Symbol stripping is skipped in dry-run mode".dimmed());
+        }
+
+        Ok(total_saved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Command;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_test_command() -> Command {
+        Command::new("slim")
+            .arg(clap::Arg::new("directory").index(1))
+            .arg(clap::Arg::new("recursive").short('r').action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("dry-run").long("dry-run").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("strip").long("strip").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("verbose").short('v').action(clap::ArgAction::Count))
+    }
+
+    fn create_cached_venv(root: &std::path::Path) -> std::path::PathBuf {
+        let pycache = root.join("project").join(".venv").join("lib").join("python3.12")
+            .join("site-packages").join("pkg").join("__pycache__");
+        fs::create_dir_all(&pycache).unwrap();
+        fs::create_dir_all(root.join("project").join(".venv").join("bin")).unwrap();
+        fs::write(pycache.join("mod.pyc"), vec![0u8; 64]).unwrap();
+        pycache
+    }
+
+    #[test]
+    fn test_slim_command_execute() {
+        let temp_dir = TempDir::new().unwrap();
+        let pycache = create_cached_venv(temp_dir.path());
+
+        let matches = create_test_command()
+            .try_get_matches_from(["slim", "-r", temp_dir.path().to_str().unwrap()])
+            .unwrap();
+        assert_eq!(SlimCommand::new(&matches).unwrap().execute().unwrap(), 64);
+        assert!(!pycache.exists());
+    }
+
+    #[test]
+    fn test_slim_command_dry_run() {
+        let temp_dir = TempDir::new().unwrap();
+        let pycache = create_cached_venv(temp_dir.path());
+
+        let matches = create_test_command()
+            .try_get_matches_from(["slim", "-r", "--dry-run", temp_dir.path().to_str().unwrap()])
+            .unwrap();
+        assert_eq!(SlimCommand::new(&matches).unwrap().execute().unwrap(), 64);
+        assert!(pycache.exists());
+    }
+
+    #[test]
+    fn test_slim_command_no_venvs() {
+        let temp_dir = TempDir::new().unwrap();
+        let matches = create_test_command()
+            .try_get_matches_from(["slim", temp_dir.path().to_str().unwrap()])
+            .unwrap();
+        assert_eq!(SlimCommand::new(&matches).unwrap().execute().unwrap(), 0);
+    }
+}
@@ -0,0 +1,282 @@
+//! `stats` subcommand for VenvCleaner
+//!
+//! This module prints aggregate statistics about the .venv directories found,
+//! optionally grouped by the Python interpreter version they were built with
+//! (`stats --by-python`) to highlight environments tied to end-of-life releases,
+//! or broken down by content type (`stats --breakdown`) to show how much a
+//! `slim` pass would reclaim. `stats --by-package` adds up the heaviest
+//! packages across all environments, showing what a shared environment or
+//! uv's package cache would save. Each run also adds to the size history and,
+//! once it spans a day, projects when the disk fills up at the current rate;
+//! its totals become the summary `prompt-summary` prints.
+
+use clap::ArgMatches;
+use colored::*;
+use tracing::info;
+
+use crate::core::{peek, size_history, summary_cache, ByteSize, SizeBreakdown, SizeCategory, VenvCleaner, VenvCleanerError, VenvInfo, VenvStats, Result};
+use super::resolve_base_directory;
+
+/// Handler for the `stats` subcommand
+pub struct StatsCommand {
+    /// The core VenvCleaner instance
+    cleaner: VenvCleaner,
+    /// Whether to group the results by Python version
+    by_python: bool,
+    /// Whether to break the size down by content type
+    breakdown: bool,
+    /// Whether to add up the heaviest packages across all .venv directories
+    by_package: bool,
+}
+
+impl StatsCommand {
+    /// Create a new StatsCommand from the `stats` subcommand arguments
+    ///
+    /// # Arguments
+    /// * `matches` - Parsed arguments of the `stats` subcommand
+    ///
+    /// # Returns
+    /// A new StatsCommand instance or an error
+    pub fn new(matches: &ArgMatches) -> Result<Self> {
+        let base_directory = resolve_base_directory(matches)?;
+        let recursive = matches.get_flag("recursive");
+        let verbosity = matches.get_count("verbose");
+
+        // Statistics never delete anything, so force and dry-run are irrelevant
+        let cleaner = VenvCleaner::new(base_directory, recursive, false, false, verbosity);
+
+        Ok(Self {
+            cleaner,
+            by_python: matches.get_flag("by-python"),
+            breakdown: matches.get_flag("breakdown"),
+            by_package: matches.get_flag("by-package"),
+        })
+    }
+
+    /// Execute the stats subcommand
+    pub fn execute(&self) -> Result<()> {
+        info!("Executing stats subcommand");
+
+        let venv_dirs = match self.cleaner.find_venv_directories() {
+            Ok(venv_dirs) => venv_dirs,
+            Err(VenvCleanerError::NoVenvFound) => {
+                println!("{}", "No .venv directories found.".yellow());
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        };
+
+        self.print_totals(&venv_dirs);
+
+        if self.by_python {
+            self.print_by_python(&venv_dirs);
+        }
+
+        if self.breakdown {
+            self.print_breakdown(&venv_dirs);
+        }
+
+        if self.by_package {
+            self.print_by_package(&venv_dirs);
+        }
+
+        Ok(())
+    }
+
+    /// Print the overall totals
+    fn print_totals(&self, venv_dirs: &[VenvInfo]) {
+        let stats = VenvStats::from_venvs(venv_dirs);
+
+        println!("{}", "VenvCleaner Statistics".bold().green());
+        println!("{}", "=".repeat(60).dimmed());
+        println!("🔍 Searched: {}", self.cleaner.base_directory().display().to_string().cyan());
+        println!("📁 .venv directories: {}", stats.total_count().to_string().cyan());
+        println!("💾 Total size: {}", ByteSize::new(stats.total_bytes()).to_string().cyan());
+
+        summary_cache::record_scan(&self.cleaner.roots(), venv_dirs);
+        match size_history::record_scan(&self.cleaner.roots(), stats.total_bytes()) {
+            Some(projection) => {
+                let rate = format!(
+                    "{}{} per day over {:.0} days",
+                    if projection.bytes_per_day < 0.0 { "-" } else { "+" },
+                    ByteSize::new(projection.bytes_per_day.abs() as u64),
+                    projection.span_days
+                );
+                let summary = match projection.days_until_full() {
+                    Some(days) if days < 90.0 => projection.summary().red().bold().to_string(),
+                    _ => projection.summary(),
+                };
+                println!(
+                    "📈 Growth: {} ({} free, {})",
+                    rate.cyan(),
+                    ByteSize::new(projection.free_bytes),
+                    summary
+                );
+            }
+            None => println!("{}", "📈 Run stats again on another day to see when the disk fills up".dimmed()),
+        }
+    }
+
+    /// Print the per-interpreter breakdown
+    fn print_by_python(&self, venv_dirs: &[VenvInfo]) {
+        let today = chrono::Utc::now().date_naive();
+        let groups = VenvStats::by_python_version(venv_dirs, today);
+
+        println!("\n{}", "By Python version:".bold());
+        println!("{:<10} {:>8} {:>14}  {}", "Python".bold(), "Venvs".bold(), "Size".bold(), "Status".bold());
+        println!("{}", "-".repeat(60).dimmed());
+
+        for group in &groups {
+            let status = if group.is_eol() {
+                "end-of-life".red().to_string()
+            } else if group.version().is_none() {
+                "no pyvenv.cfg version".dimmed().to_string()
+            } else {
+                "supported".green().to_string()
+            };
+
+            println!(
+                "{:<10} {:>8} {:>14}  {}",
+                group.label(),
+                group.count(),
+                ByteSize::new(group.total_bytes()).to_string(),
+                status
+            );
+        }
+
+        let eol: Vec<_> = groups.iter().filter(|g| g.is_eol()).collect();
+        if !eol.is_empty() {
+            let count: usize = eol.iter().map(|g| g.count()).sum();
+            let bytes: u64 = eol.iter().map(|g| g.total_bytes()).sum();
+            println!(
+                "\n⚠️  {} .venv directories ({}) are built on end-of-life Python releases",
+                count.to_string().red(),
+                ByteSize::new(bytes).to_string().red()
+            );
+        }
+    }
+
+    /// Print the combined size breakdown by content type
+    fn print_breakdown(&self, venv_dirs: &[VenvInfo]) {
+        let mut total = SizeBreakdown::default();
+        for venv_info in venv_dirs {
+            total.merge(&SizeBreakdown::for_venv(venv_info.path()));
+        }
+
+        println!("\n{}", "By content type:".bold());
+        println!("{:<20} {:>14} {:>8}", "Category".bold(), "Size".bold(), "Share".bold());
+        println!("{}", "-".repeat(60).dimmed());
+
+        for category in SizeCategory::ALL {
+            println!(
+                "{:<20} {:>14} {:>7.1}%",
+                category.display_name(),
+                ByteSize::new(total.bytes(category)).to_string(),
+                total.percent(category)
+            );
+        }
+
+        if total.slimmable() > 0 {
+            println!(
+                "\n✂️  {} reclaimable without deleting any .venv (run `venv_cleaner slim`)",
+                ByteSize::new(total.slimmable()).to_string().green()
+            );
+        }
+    }
+
+    /// Print the heaviest packages, added up across all .venv directories
+    fn print_by_package(&self, venv_dirs: &[VenvInfo]) {
+        let groups = VenvStats::by_package(venv_dirs);
+
+        println!("\n{}", "Heaviest packages:".bold());
+        println!("{:<30} {:>8} {:>14}", "Package".bold(), "Venvs".bold(), "Size".bold());
+        println!("{}", "-".repeat(60).dimmed());
+
+        for group in groups.iter().take(peek::DEFAULT_PACKAGE_LIMIT) {
+            println!(
+                "{:<30} {:>8} {:>14}",
+                group.name(),
+                group.venv_count(),
+                ByteSize::new(group.total_bytes()).to_string()
+            );
+        }
+
+        if let Some(heaviest) = groups.iter().find(|group| group.venv_count() > 1) {
+            println!(
+                "\n📦 {} appears in {} .venv directories consuming {} total",
+                heaviest.name().cyan(),
+                heaviest.venv_count().to_string().cyan(),
+                ByteSize::new(heaviest.total_bytes()).to_string().yellow()
+            );
+            println!(
+                "   {}",
+                "A shared environment, or uv's cache (which links packages instead of copying them), keeps one copy".dimmed()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Command;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_test_command() -> Command {
+        Command::new("stats")
+            .arg(clap::Arg::new("directory").index(1))
+            .arg(clap::Arg::new("recursive").short('r').action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("by-python").long("by-python").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("breakdown").long("breakdown").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("by-package").long("by-package").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("verbose").short('v').action(clap::ArgAction::Count))
+    }
+
+    #[test]
+    fn test_stats_command_creation() {
+        let temp_dir = TempDir::new().unwrap();
+        let matches = create_test_command().try_get_matches_from([
+            "stats",
+            "--by-python",
+            temp_dir.path().to_str().unwrap(),
+        ]).unwrap();
+
+        let stats = StatsCommand::new(&matches).unwrap();
+        assert!(stats.by_python);
+    }
+
+    #[test]
+    fn test_stats_command_execute() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = temp_dir.path().join("project").join(".venv");
+        fs::create_dir_all(&venv).unwrap();
+        fs::write(venv.join("pyvenv.cfg"), "version = 3.7.9\n").unwrap();
+
+        let matches = create_test_command().try_get_matches_from([
+            "stats",
+            "-r",
+            "--by-python",
+            "--breakdown",
+            "--by-package",
+            temp_dir.path().to_str().unwrap(),
+        ]).unwrap();
+
+        let stats = StatsCommand::new(&matches).unwrap();
+        assert!(stats.breakdown);
+        assert!(stats.by_package);
+        assert!(stats.execute().is_ok());
+    }
+
+    #[test]
+    fn test_stats_command_no_venvs() {
+        let temp_dir = TempDir::new().unwrap();
+        let matches = create_test_command().try_get_matches_from([
+            "stats",
+            temp_dir.path().to_str().unwrap(),
+        ]).unwrap();
+
+        let stats = StatsCommand::new(&matches).unwrap();
+        assert!(stats.execute().is_ok());
+    }
+}
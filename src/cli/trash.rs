@@ -0,0 +1,204 @@
+//! `trash` subcommand for VenvCleaner
+//!
+//! This module manages the .venv directories VenvCleaner moved to the trash
+//! with `--trash`. `trash list` shows what is still recorded and how much
+//! space it holds; `trash purge --older-than 30d` permanently removes the
+//! entries trashed before then, leaving the rest of the trash untouched.
+
+use chrono::Duration;
+use clap::ArgMatches;
+use colored::*;
+use tracing::info;
+
+use crate::core::{timestamps, ByteSize, PurgeReport, TrashLedger, VenvCleanerError, Result};
+
+/// Default age of the entries removed by `trash purge`
+pub const DEFAULT_OLDER_THAN: &str = "30d";
+
+/// What the `trash` subcommand should do
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrashAction {
+    /// List the recorded entries
+    List,
+    /// Purge entries trashed longer ago than the duration
+    Purge { older_than: Duration, dry_run: bool },
+}
+
+/// Handler for the `trash` subcommand
+pub struct TrashCommand {
+    /// The action to perform
+    action: TrashAction,
+}
+
+impl TrashCommand {
+    /// Create a new TrashCommand from the `trash` subcommand arguments
+    ///
+    /// # Arguments
+    /// * `matches` - Parsed arguments of the `trash` subcommand
+    ///
+    /// # Returns
+    /// A new TrashCommand instance or an error
+    pub fn new(matches: &ArgMatches) -> Result<Self> {
+        let action = match matches.subcommand() {
+            Some(("purge", purge_matches)) => {
+                let older_than = purge_matches
+                    .get_one::<String>("older-than")
+                    .map(String::as_str)
+                    .unwrap_or(DEFAULT_OLDER_THAN);
+                TrashAction::Purge {
+                    older_than: parse_older_than(older_than)?,
+                    dry_run: purge_matches.get_flag("dry-run"),
+                }
+            }
+            _ => TrashAction::List,
+        };
+
+        Ok(Self { action })
+    }
+
+    /// Get the action this command performs
+    pub fn action(&self) -> &TrashAction {
+        &self.action
+    }
+
+    /// Execute the trash subcommand
+    pub fn execute(&self) -> Result<()> {
+        info!("Executing trash subcommand");
+
+        let path = TrashLedger::default_path().ok_or_else(|| {
+            VenvCleanerError::FeatureNotAvailable("No data directory on this platform".to_string())
+        })?;
+        let mut ledger = TrashLedger::load(path);
+
+        match &self.action {
+            TrashAction::List => {
+                Self::print_list(&ledger);
+                Ok(())
+            }
+            TrashAction::Purge { older_than, dry_run } => {
+                let report = ledger.purge(*older_than, *dry_run)?;
+                Self::print_purge(&report, *dry_run);
+                Ok(())
+            }
+        }
+    }
+
+    /// Print the recorded entries and their total size
+    fn print_list(ledger: &TrashLedger) {
+        if ledger.entries().is_empty() {
+            println!("{}", "VenvCleaner has not moved any .venv directories to the trash.".yellow());
+            return;
+        }
+
+        for entry in ledger.entries() {
+            println!(
+                "♻️  {}  {}  {}",
+                timestamps::format_date_time(&entry.trashed_at).dimmed(),
+                ByteSize::new(entry.size_bytes),
+                entry.path.display()
+            );
+        }
+        println!(
+            "\n{} {} .venv directories ({}) moved to the trash by VenvCleaner",
+            "Summary:".bold(),
+            ledger.entries().len(),
+            ByteSize::new(ledger.total_bytes())
+        );
+    }
+
+    /// Print what a purge removed
+    fn print_purge(report: &PurgeReport, dry_run: bool) {
+        if report.purged.is_empty() && report.gone.is_empty() {
+            println!("{}", "No trashed .venv directories are old enough to purge.".yellow());
+            return;
+        }
+
+        let verb = if dry_run { "Would purge" } else { "Purged" };
+        for entry in &report.purged {
+            println!("🗑️  {}: {}", verb, entry.path.display());
+        }
+        if !report.gone.is_empty() {
+            println!(
+                "ℹ️  {} .venv directories were already restored or removed from the trash",
+                report.gone.len()
+            );
+        }
+        println!(
+            "\n{} {} {} .venv directories ({})",
+            "Summary:".bold(),
+            verb,
+            report.purged.len(),
+            ByteSize::new(report.freed_bytes())
+        );
+    }
+}
+
+/// Parse an age such as `30d`, `2w` or `12h`; a bare number means days
+///
+/// # Arguments
+/// * `text` - The age to parse
+///
+/// # Returns
+/// The age, or an error naming the accepted units
+pub fn parse_older_than(text: &str) -> Result<Duration> {
+    let text = text.trim();
+    let invalid = || VenvCleanerError::InvalidArgument(format!(
+        "Invalid age '{}': use a number followed by h, d or w (e.g. 30d)",
+        text
+    ));
+
+    let (number, unit) = match text.find(|c: char| !c.is_ascii_digit()) {
+        Some(index) => text.split_at(index),
+        None => (text, "d"),
+    };
+    let value: i64 = number.parse().map_err(|_| invalid())?;
+
+    match unit {
+        "h" => Ok(Duration::hours(value)),
+        "d" => Ok(Duration::days(value)),
+        "w" => Ok(Duration::weeks(value)),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_command() -> clap::Command {
+        clap::Command::new("trash").subcommand(
+            clap::Command::new("purge")
+                .arg(clap::Arg::new("older-than").long("older-than"))
+                .arg(clap::Arg::new("dry-run").long("dry-run").action(clap::ArgAction::SetTrue)),
+        )
+    }
+
+    #[test]
+    fn test_parse_older_than() {
+        assert_eq!(parse_older_than("30d").unwrap(), Duration::days(30));
+        assert_eq!(parse_older_than("2w").unwrap(), Duration::weeks(2));
+        assert_eq!(parse_older_than("12h").unwrap(), Duration::hours(12));
+        assert_eq!(parse_older_than("7").unwrap(), Duration::days(7));
+        assert!(parse_older_than("30m").is_err());
+        assert!(parse_older_than("d").is_err());
+    }
+
+    #[test]
+    fn test_purge_action() {
+        let matches = create_test_command()
+            .try_get_matches_from(["trash", "purge", "--older-than", "2w", "--dry-run"])
+            .unwrap();
+        let command = TrashCommand::new(&matches).unwrap();
+        assert_eq!(command.action(), &TrashAction::Purge { older_than: Duration::weeks(2), dry_run: true });
+
+        let matches = create_test_command().try_get_matches_from(["trash", "purge"]).unwrap();
+        let command = TrashCommand::new(&matches).unwrap();
+        assert_eq!(command.action(), &TrashAction::Purge { older_than: Duration::days(30), dry_run: false });
+    }
+
+    #[test]
+    fn test_list_is_the_default() {
+        let matches = create_test_command().try_get_matches_from(["trash"]).unwrap();
+        assert_eq!(TrashCommand::new(&matches).unwrap().action(), &TrashAction::List);
+    }
+}
@@ -0,0 +1,230 @@
+//! `unarchive` subcommand for VenvCleaner
+//!
+//! This module restores .venv directories that were archived with
+//! `--archive`. Each path is either a `.venv.archived` stub file or a project
+//! directory holding one; with `-r` directories are searched recursively for
+//! stubs. `--delete` discards the archives instead of restoring them.
+
+use std::path::{Path, PathBuf};
+use clap::ArgMatches;
+use colored::*;
+use tracing::info;
+
+use crate::core::archive::{self, ArchiveStub, VenvArchiver};
+use crate::core::{sorting, ByteSize, VenvCleaner, VenvCleanerError, Result};
+
+/// Handler for the `unarchive` subcommand
+pub struct UnarchiveCommand {
+    /// Stub files or project directories to restore
+    paths: Vec<PathBuf>,
+    /// Whether to search directories recursively for stubs
+    recursive: bool,
+    /// Whether to only report what would be restored
+    dry_run: bool,
+    /// Whether to delete the archives instead of restoring them
+    delete: bool,
+}
+
+impl UnarchiveCommand {
+    /// Create a new UnarchiveCommand from the `unarchive` subcommand arguments
+    ///
+    /// # Arguments
+    /// * `matches` - Parsed arguments of the `unarchive` subcommand
+    ///
+    /// # Returns
+    /// A new UnarchiveCommand instance or an error
+    pub fn new(matches: &ArgMatches) -> Result<Self> {
+        let mut paths: Vec<PathBuf> = matches
+            .get_many::<String>("paths")
+            .map(|values| values.map(PathBuf::from).collect())
+            .unwrap_or_default();
+        if paths.is_empty() {
+            paths.push(std::env::current_dir()?);
+        }
+
+        for path in &paths {
+            if !path.exists() {
+                return Err(VenvCleanerError::PathError {
+                    path: path.display().to_string(),
+                    message: "Path does not exist".to_string(),
+                });
+            }
+        }
+
+        Ok(Self {
+            paths,
+            recursive: matches.get_flag("recursive"),
+            dry_run: matches.get_flag("dry-run"),
+            delete: matches.get_flag("delete"),
+        })
+    }
+
+    /// Execute the unarchive subcommand
+    ///
+    /// # Returns
+    /// The number of .venv directories restored or discarded (or that would be)
+    pub fn execute(&self) -> Result<usize> {
+        info!("Executing unarchive subcommand");
+
+        let stubs = self.find_stubs();
+        if stubs.is_empty() {
+            println!("{}", "No archived .venv directories found.".yellow());
+            return Ok(0);
+        }
+
+        let mut processed = 0;
+        let mut failures = 0;
+
+        for stub_path in &stubs {
+            match self.process_stub(stub_path) {
+                Ok(message) => {
+                    println!("📦 {}", message);
+                    processed += 1;
+                }
+                Err(e) => {
+                    println!("❌ {}: {}", stub_path.display(), e.to_string().red());
+                    failures += 1;
+                }
+            }
+        }
+
+        let verb = match (self.delete, self.dry_run) {
+            (true, true) => "Would delete archives of",
+            (true, false) => "Deleted archives of",
+            (false, true) => "Would restore",
+            (false, false) => "Restored",
+        };
+        println!("\n{} {} {} .venv directories", "Summary:".bold(), verb, processed);
+        if failures > 0 {
+            println!("⚠️  {} archives could not be processed", failures.to_string().red());
+        }
+
+        Ok(processed)
+    }
+
+    /// Restore (or discard) a single archived .venv
+    ///
+    /// # Returns
+    /// A message describing what was done
+    fn process_stub(&self, stub_path: &Path) -> Result<String> {
+        let stub = ArchiveStub::read(stub_path)?;
+
+        match (self.delete, self.dry_run) {
+            (true, true) => Ok(format!(
+                "DRY RUN: Would delete archive {} ({})",
+                stub.archive_path().display(),
+                ByteSize::new(archive::archive_size(stub.archive_path()).unwrap_or(0))
+            )),
+            (true, false) => {
+                let freed = VenvArchiver::delete_archive(stub_path)?;
+                Ok(format!("{} archive of {} ({} freed)", "Deleted".green(), stub.venv_path().display(), ByteSize::new(freed)))
+            }
+            (false, true) => Ok(format!(
+                "DRY RUN: Would restore {} ({}) from {}",
+                stub.venv_path().display(),
+                ByteSize::new(stub.original_size()),
+                stub.archive_path().display()
+            )),
+            (false, false) => {
+                let venv_path = VenvArchiver::unarchive(stub_path)?;
+                Ok(format!("{} {}", "Restored".green(), venv_path.display()))
+            }
+        }
+    }
+
+    /// Collect the stub files named by or found under the given paths
+    fn find_stubs(&self) -> Vec<PathBuf> {
+        let mut stubs = Vec::new();
+
+        for path in &self.paths {
+            if path.is_file() {
+                stubs.push(path.clone());
+            } else {
+                let cleaner = VenvCleaner::new(path.clone(), self.recursive, false, self.dry_run, 0);
+                stubs.extend(cleaner.walk_archive_stubs());
+            }
+        }
+
+        stubs.sort_by(|a, b| sorting::compare_paths(a, b));
+        stubs.dedup();
+        stubs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use clap::Command;
+    use std::fs;
+    use tempfile::TempDir;
+    use crate::core::archive::STUB_FILE_NAME;
+    use crate::core::{ByteSize, VenvInfo};
+
+    fn create_test_command() -> Command {
+        Command::new("unarchive")
+            .arg(clap::Arg::new("paths").num_args(0..))
+            .arg(clap::Arg::new("recursive").short('r').action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("dry-run").long("dry-run").action(clap::ArgAction::SetTrue))
+            .arg(clap::Arg::new("delete").long("delete").action(clap::ArgAction::SetTrue))
+    }
+
+    fn archive_venv(root: &Path, project: &str) -> PathBuf {
+        let venv = root.join(project).join(".venv");
+        fs::create_dir_all(venv.join("bin")).unwrap();
+        fs::write(venv.join("pyvenv.cfg"), "version = 3.12.1\n").unwrap();
+
+        let venv_info = VenvInfo::new(venv.clone(), ByteSize::new(18), Utc::now(), Utc::now());
+        VenvArchiver::new(root.join("archives")).archive(&venv_info).unwrap();
+        venv
+    }
+
+    #[test]
+    fn test_unarchive_recursive() {
+        let temp_dir = TempDir::new().unwrap();
+        let first = archive_venv(temp_dir.path(), "first");
+        let second = archive_venv(&temp_dir.path().join("nested"), "second");
+
+        let matches = create_test_command()
+            .try_get_matches_from(["unarchive", "-r", temp_dir.path().to_str().unwrap()])
+            .unwrap();
+        assert_eq!(UnarchiveCommand::new(&matches).unwrap().execute().unwrap(), 2);
+        assert!(first.join("pyvenv.cfg").is_file());
+        assert!(second.join("pyvenv.cfg").is_file());
+    }
+
+    #[test]
+    fn test_unarchive_dry_run_and_delete() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = archive_venv(temp_dir.path(), "webapp");
+        let stub = temp_dir.path().join("webapp").join(STUB_FILE_NAME);
+
+        let matches = create_test_command()
+            .try_get_matches_from(["unarchive", "--dry-run", stub.to_str().unwrap()])
+            .unwrap();
+        assert_eq!(UnarchiveCommand::new(&matches).unwrap().execute().unwrap(), 1);
+        assert!(!venv.exists());
+        assert!(stub.is_file());
+
+        let matches = create_test_command()
+            .try_get_matches_from(["unarchive", "--delete", stub.to_str().unwrap()])
+            .unwrap();
+        assert_eq!(UnarchiveCommand::new(&matches).unwrap().execute().unwrap(), 1);
+        assert!(!venv.exists());
+        assert!(!stub.exists());
+    }
+
+    #[test]
+    fn test_unarchive_without_stubs() {
+        let temp_dir = TempDir::new().unwrap();
+        let matches = create_test_command()
+            .try_get_matches_from(["unarchive", temp_dir.path().to_str().unwrap()])
+            .unwrap();
+        assert_eq!(UnarchiveCommand::new(&matches).unwrap().execute().unwrap(), 0);
+
+        let missing = create_test_command()
+            .try_get_matches_from(["unarchive", "/nonexistent/stub"])
+            .unwrap();
+        assert!(UnarchiveCommand::new(&missing).is_err());
+    }
+}
@@ -0,0 +1,345 @@
+//! Last activity of the project owning a .venv
+//!
+//! A project's activity is the newer of its last git commit and the newest
+//! modification time of its own files, ignoring the .venv and other
+//! generated directories. Walking a project tree and asking git are both
+//! expensive, so activity and last commits are kept in a small JSON cache
+//! under the user cache directory and reused for a day.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+use tracing::debug;
+
+use super::{git, paths, Result, VenvInfo};
+
+/// Directory names never counted as project activity
+pub const IGNORED_DIRS: &[&str] = &[
+    ".git", "__pycache__", "node_modules", ".tox", ".nox", ".mypy_cache",
+    ".pytest_cache", ".ruff_cache", "build", "dist",
+];
+
+/// Deepest directory level inspected below the project directory
+pub const MAX_SCAN_DEPTH: usize = 8;
+
+/// How long a cached activity value stays valid, in hours
+pub const CACHE_TTL_HOURS: i64 = 24;
+
+/// Find the newest modification time of the project's own files
+///
+/// The .venv, any other virtual environment (a directory holding
+/// `pyvenv.cfg`), bytecode and the directories in `IGNORED_DIRS` are skipped.
+///
+/// # Arguments
+/// * `project_dir` - The project directory
+/// * `venv_path` - The project's .venv, which is skipped
+///
+/// # Returns
+/// The newest file modification time, or None if there are no files
+pub fn newest_source_mtime(project_dir: &Path, venv_path: &Path) -> Option<DateTime<Utc>> {
+    WalkDir::new(project_dir)
+        .max_depth(MAX_SCAN_DEPTH)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|entry| !is_ignored(entry.path(), entry.file_type().is_dir(), venv_path))
+        .flatten()
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| entry.path().extension().map(|e| e != "pyc").unwrap_or(true))
+        .filter_map(|entry| entry.metadata().ok()?.modified().ok())
+        .max()
+        .map(DateTime::<Utc>::from)
+}
+
+/// Check whether a walked entry is excluded from the activity scan
+fn is_ignored(path: &Path, is_dir: bool, venv_path: &Path) -> bool {
+    if !is_dir {
+        return false;
+    }
+    let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+
+    path == venv_path
+        || IGNORED_DIRS.iter().any(|dir| *dir == name)
+        || name.ends_with(".egg-info")
+        || path.join("pyvenv.cfg").is_file()
+}
+
+/// Compute a project's last activity without the cache
+///
+/// # Arguments
+/// * `venv_info` - The project's .venv (its git commit time is reused)
+///
+/// # Returns
+/// The newer of the last commit and the newest source file, if either is known
+pub fn project_activity(venv_info: &VenvInfo) -> Option<DateTime<Utc>> {
+    let project_dir = venv_info.parent_path()?;
+    let newest_file = newest_source_mtime(project_dir, venv_info.path());
+    newest_file.max(venv_info.project_last_commit().copied())
+}
+
+/// A cached timestamp
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedTime {
+    /// The cached time, if the project has one
+    time: Option<DateTime<Utc>>,
+    /// When the value was computed
+    computed_at: DateTime<Utc>,
+}
+
+impl CachedTime {
+    /// Cache a value computed now
+    fn new(time: Option<DateTime<Utc>>) -> Self {
+        Self { time, computed_at: Utc::now() }
+    }
+
+    /// Check whether the value is recent enough to reuse
+    fn is_fresh(&self) -> bool {
+        Utc::now() - self.computed_at < Duration::hours(CACHE_TTL_HOURS)
+    }
+}
+
+/// Cached values by project directory, as stored on disk
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheEntries {
+    /// Last activity of each project
+    #[serde(default)]
+    activity: HashMap<PathBuf, CachedTime>,
+    /// Last git commit touching each project
+    #[serde(default)]
+    last_commit: HashMap<PathBuf, CachedTime>,
+}
+
+/// On-disk cache of project activity and last commits, keyed by project directory
+#[derive(Debug)]
+pub struct ActivityCache {
+    /// Where the cache is stored (None keeps it in memory only)
+    path: Option<PathBuf>,
+    /// Cached values by project directory
+    entries: CacheEntries,
+    /// Whether entries changed since loading
+    dirty: bool,
+}
+
+impl ActivityCache {
+    /// Get the default location of the cache file
+    pub fn default_path() -> Option<PathBuf> {
+        paths::cache_dir().map(|dir| dir.join("activity.json"))
+    }
+
+    /// Load the cache from its default location
+    pub fn load_default() -> Self {
+        match Self::default_path() {
+            Some(path) => Self::load(path),
+            None => Self::in_memory(),
+        }
+    }
+
+    /// Load the cache from a file; a missing or unreadable file gives an empty cache
+    ///
+    /// # Arguments
+    /// * `path` - Path to the cache file
+    pub fn load(path: PathBuf) -> Self {
+        let entries = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        Self { path: Some(path), entries, dirty: false }
+    }
+
+    /// Create a cache that is never written to disk
+    pub fn in_memory() -> Self {
+        Self { path: None, entries: CacheEntries::default(), dirty: false }
+    }
+
+    /// Get a project's last activity, computing it if not cached or expired
+    ///
+    /// # Arguments
+    /// * `venv_info` - The project's .venv
+    pub fn activity(&mut self, venv_info: &VenvInfo) -> Option<DateTime<Utc>> {
+        let project_dir = venv_info.parent_path()?;
+        match self.entries.activity.get(project_dir) {
+            Some(cached) if cached.is_fresh() => cached.time,
+            _ => self.refresh(venv_info),
+        }
+    }
+
+    /// Get the last git commit touching a project, asking git if not cached or expired
+    ///
+    /// # Arguments
+    /// * `venv_info` - The project's .venv
+    pub fn last_commit(&mut self, venv_info: &VenvInfo) -> Option<DateTime<Utc>> {
+        let project_dir = venv_info.parent_path()?;
+        match self.entries.last_commit.get(project_dir) {
+            Some(cached) if cached.is_fresh() => cached.time,
+            _ => self.refresh_last_commit(project_dir.to_path_buf()),
+        }
+    }
+
+    /// Fill in the last git commit of each .venv's project
+    ///
+    /// # Arguments
+    /// * `venv_dirs` - The .venv directories
+    ///
+    /// # Returns
+    /// The same directories, each with its project's last commit
+    pub fn with_last_commits(&mut self, venv_dirs: Vec<VenvInfo>) -> Vec<VenvInfo> {
+        venv_dirs
+            .into_iter()
+            .map(|venv_info| {
+                let last_commit = self.last_commit(&venv_info);
+                venv_info.with_project_last_commit(last_commit)
+            })
+            .collect()
+    }
+
+    /// Recompute a project's last activity and commit, even if fresh values are cached
+    ///
+    /// # Arguments
+    /// * `venv_info` - The project's .venv
+    pub fn refresh(&mut self, venv_info: &VenvInfo) -> Option<DateTime<Utc>> {
+        let project_dir = venv_info.parent_path()?.to_path_buf();
+        let last_commit = self.refresh_last_commit(project_dir.clone());
+
+        debug!("Computing activity of {}", project_dir.display());
+        let activity = newest_source_mtime(&project_dir, venv_info.path()).max(last_commit);
+        self.entries.activity.insert(project_dir, CachedTime::new(activity));
+        self.dirty = true;
+        activity
+    }
+
+    /// Ask git for a project's last commit and cache it
+    fn refresh_last_commit(&mut self, project_dir: PathBuf) -> Option<DateTime<Utc>> {
+        let last_commit = git::last_commit_time(&project_dir);
+        self.entries.last_commit.insert(project_dir, CachedTime::new(last_commit));
+        self.dirty = true;
+        last_commit
+    }
+
+    /// Write the cache back to disk if anything changed
+    pub fn save(&mut self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if !self.dirty {
+            return Ok(());
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = serde_json::to_string(&self.entries)
+            .map_err(|e| super::VenvCleanerError::Io(format!("Failed to encode activity cache: {}", e)))?;
+        fs::write(path, contents)?;
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ByteSize;
+    use tempfile::TempDir;
+
+    fn set_mtime(path: &Path, days_ago: i64) {
+        let time = std::time::SystemTime::now() - std::time::Duration::from_secs((days_ago * 86400) as u64);
+        fs::File::options().write(true).open(path).unwrap().set_modified(time).unwrap();
+    }
+
+    fn create_project(root: &Path) -> VenvInfo {
+        let project = root.join("proj");
+        fs::create_dir_all(project.join(".venv").join("lib")).unwrap();
+        fs::create_dir_all(project.join("src")).unwrap();
+        fs::write(project.join(".venv").join("lib").join("fresh.py"), "").unwrap();
+        fs::write(project.join("src").join("app.py"), "").unwrap();
+        set_mtime(&project.join("src").join("app.py"), 40);
+
+        let old = Utc::now() - Duration::days(200);
+        VenvInfo::new(project.join(".venv"), ByteSize::new(0), old, old)
+    }
+
+    #[test]
+    fn test_newest_source_mtime_skips_venv() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv_info = create_project(temp_dir.path());
+
+        // The fresh file inside .venv does not count
+        let activity = project_activity(&venv_info).unwrap();
+        let days = (Utc::now() - activity).num_days();
+        assert!((39..=40).contains(&days), "activity {} days ago", days);
+    }
+
+    #[test]
+    fn test_activity_prefers_newer_commit() {
+        let temp_dir = TempDir::new().unwrap();
+        let committed = Utc::now() - Duration::days(2);
+        let venv_info = create_project(temp_dir.path()).with_project_last_commit(Some(committed));
+        assert_eq!(project_activity(&venv_info), Some(committed));
+    }
+
+    #[test]
+    fn test_cache_reuses_values() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv_info = create_project(temp_dir.path());
+        let cache_path = temp_dir.path().join("cache").join("activity.json");
+
+        let first = {
+            let mut cache = ActivityCache::load(cache_path.clone());
+            let activity = cache.activity(&venv_info);
+            cache.save().unwrap();
+            activity
+        };
+        assert!(cache_path.is_file());
+
+        // A newer file is not seen while the cached value is fresh
+        fs::write(temp_dir.path().join("proj").join("src").join("new.py"), "").unwrap();
+        let mut cache = ActivityCache::load(cache_path);
+        assert_eq!(cache.activity(&venv_info), first);
+        assert!(first.is_some());
+
+        // A refresh sees it, and later lookups get the new value
+        let refreshed = cache.refresh(&venv_info);
+        assert!(refreshed > first);
+        assert_eq!(cache.activity(&venv_info), refreshed);
+    }
+
+    #[test]
+    fn test_last_commit_is_cached() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv_info = create_project(temp_dir.path());
+        let project = temp_dir.path().join("proj");
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .arg("-C")
+                .arg(&project)
+                .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .output()
+                .map(|o| o.status.success())
+                .unwrap_or(false)
+        };
+        if !git(&["init", "-q"]) {
+            // git is not installed; nothing more to check
+            return;
+        }
+        assert!(git(&["add", "src"]));
+        assert!(git(&["commit", "-q", "-m", "init"]));
+
+        let mut cache = ActivityCache::in_memory();
+        let committed = cache.last_commit(&venv_info);
+        assert!(committed.is_some());
+        let filled = cache.with_last_commits(vec![venv_info.clone()]);
+        assert_eq!(filled[0].project_last_commit().copied(), committed);
+
+        // Git is not asked again while the cached value is fresh
+        fs::remove_dir_all(project.join(".git")).unwrap();
+        assert_eq!(cache.last_commit(&venv_info), committed);
+
+        // A refresh asks again, and the activity falls back to the files
+        let refreshed = cache.refresh(&venv_info);
+        assert!(cache.last_commit(&venv_info).is_none());
+        assert!(refreshed < committed);
+    }
+}
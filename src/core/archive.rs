@@ -0,0 +1,444 @@
+//! Archiving .venv directories instead of deleting them
+//!
+//! Archiving is the middle ground between keeping and deleting an
+//! environment: the .venv is packed into a zstd-compressed tarball in the
+//! archive directory and replaced by a small JSON stub file
+//! (`.venv.archived`) in the project, which records where the archive lives.
+//! Unarchiving unpacks the tarball back into place and removes the stub.
+//!
+//! Scans recognize stub files so archived environments can be listed next to
+//! live ones, with the size they take up in compressed form.
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use tracing::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+
+use super::{paths, Result, VenvCleanerError, VenvInfo};
+
+/// Name of the stub file left in the project in place of the .venv
+pub const STUB_FILE_NAME: &str = ".venv.archived";
+
+/// File extension of archive files
+pub const ARCHIVE_EXTENSION: &str = "tar.zst";
+
+/// zstd compression level used for new archives
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// Get the default directory archives are written to
+///
+/// # Returns
+/// `<data dir>/archives` (see `paths::data_dir`; e.g. `~/.local/share/venv_cleaner/archives` on Linux)
+pub fn default_archive_dir() -> PathBuf {
+    paths::data_dir()
+        .unwrap_or_else(|| PathBuf::from("venv_cleaner"))
+        .join("archives")
+}
+
+/// Contents of a stub file describing an archived .venv
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchiveStub {
+    /// Where the .venv lived (and is restored to)
+    venv_path: PathBuf,
+    /// The compressed archive holding the .venv
+    archive_path: PathBuf,
+    /// Size of the .venv before archiving, in bytes
+    original_size: u64,
+    /// When the .venv was archived
+    archived_at: DateTime<Utc>,
+    /// Python version from pyvenv.cfg, if known
+    python_version: Option<String>,
+}
+
+impl ArchiveStub {
+    /// Get the stub file path for a .venv directory
+    ///
+    /// # Arguments
+    /// * `venv_path` - Path to the .venv directory
+    pub fn stub_path_for(venv_path: &Path) -> PathBuf {
+        venv_path.with_file_name(STUB_FILE_NAME)
+    }
+
+    /// Read a stub file
+    ///
+    /// # Arguments
+    /// * `stub_path` - Path to the `.venv.archived` file
+    pub fn read(stub_path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(stub_path)?;
+        serde_json::from_str(&content).map_err(|e| VenvCleanerError::InvalidArgument(format!(
+            "{} is not a valid archive stub: {}",
+            stub_path.display(),
+            e
+        )))
+    }
+
+    /// Write the stub file next to the (removed) .venv
+    fn write(&self) -> Result<PathBuf> {
+        let stub_path = Self::stub_path_for(&self.venv_path);
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| VenvCleanerError::Io(format!("Failed to serialize archive stub: {}", e)))?;
+        fs::write(&stub_path, content + "\n")?;
+        Ok(stub_path)
+    }
+
+    /// Get the path the .venv is restored to
+    pub fn venv_path(&self) -> &Path {
+        &self.venv_path
+    }
+
+    /// Get the path of the compressed archive
+    pub fn archive_path(&self) -> &Path {
+        &self.archive_path
+    }
+
+    /// Get the size of the .venv before archiving
+    pub fn original_size(&self) -> u64 {
+        self.original_size
+    }
+
+    /// Get when the .venv was archived
+    pub fn archived_at(&self) -> &DateTime<Utc> {
+        &self.archived_at
+    }
+
+    /// Get the Python version recorded at archive time
+    pub fn python_version(&self) -> Option<&str> {
+        self.python_version.as_deref()
+    }
+}
+
+/// An archived .venv found while scanning, read from its stub file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchivedVenv {
+    /// Path to the `.venv.archived` stub file
+    stub_path: PathBuf,
+    /// Contents of the stub file
+    stub: ArchiveStub,
+    /// Size of the compressed archive, or None if the archive is missing
+    compressed_size: Option<u64>,
+}
+
+impl ArchivedVenv {
+    /// Load an archived .venv from its stub file
+    ///
+    /// # Arguments
+    /// * `stub_path` - Path to the `.venv.archived` stub file
+    pub fn load(stub_path: &Path) -> Result<Self> {
+        let stub = ArchiveStub::read(stub_path)?;
+        let compressed_size = archive_size(stub.archive_path());
+        Ok(Self {
+            stub_path: stub_path.to_path_buf(),
+            stub,
+            compressed_size,
+        })
+    }
+
+    /// Get the path to the stub file
+    pub fn stub_path(&self) -> &Path {
+        &self.stub_path
+    }
+
+    /// Get the stub contents
+    pub fn stub(&self) -> &ArchiveStub {
+        &self.stub
+    }
+
+    /// Get the compressed archive size, or None if the archive is missing
+    pub fn compressed_size(&self) -> Option<u64> {
+        self.compressed_size
+    }
+
+    /// Get the project directory the .venv is restored into, for display
+    pub fn location(&self) -> String {
+        self.stub_path
+            .parent()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| self.stub_path.display().to_string())
+    }
+}
+
+/// Packs .venv directories into compressed archives and restores them
+#[derive(Debug, Clone)]
+pub struct VenvArchiver {
+    /// Directory archives are written to
+    archive_dir: PathBuf,
+}
+
+impl VenvArchiver {
+    /// Create a new VenvArchiver
+    ///
+    /// # Arguments
+    /// * `archive_dir` - Directory archives are written to (created on demand)
+    pub fn new(archive_dir: PathBuf) -> Self {
+        Self { archive_dir }
+    }
+
+    /// Get the directory archives are written to
+    pub fn archive_dir(&self) -> &Path {
+        &self.archive_dir
+    }
+
+    /// Archive a .venv directory and replace it with a stub file
+    ///
+    /// The archive is written under a temporary name and only renamed into
+    /// place once complete, so an interrupted run never leaves a truncated
+    /// archive that looks valid.
+    ///
+    /// # Arguments
+    /// * `venv_info` - Information about the .venv directory to archive
+    ///
+    /// # Returns
+    /// The stub describing the new archive
+    pub fn archive(&self, venv_info: &VenvInfo) -> Result<ArchiveStub> {
+        let venv_path = venv_info.path();
+        let venv_name = venv_path.file_name().ok_or_else(|| VenvCleanerError::PathError {
+            path: venv_path.display().to_string(),
+            message: "Cannot archive a path without a directory name".to_string(),
+        })?;
+
+        fs::create_dir_all(&self.archive_dir)?;
+        let archive_path = self.next_archive_path(venv_info);
+        let partial_path = archive_path.with_extension("zst.partial");
+        debug!("Archiving {} to {}", venv_path.display(), archive_path.display());
+
+        let encoder = zstd::Encoder::new(File::create(&partial_path)?, COMPRESSION_LEVEL)?;
+        let mut builder = tar::Builder::new(encoder);
+        // Interpreter links (bin/python -> /usr/bin/python3) must stay links
+        builder.follow_symlinks(false);
+        let written = builder
+            .append_dir_all(venv_name, venv_path)
+            .and_then(|_| builder.into_inner())
+            .and_then(|encoder| encoder.finish());
+        if let Err(e) = written {
+            let _ = fs::remove_file(&partial_path);
+            return Err(VenvCleanerError::Io(format!("Failed to archive {}: {}", venv_path.display(), e)));
+        }
+        fs::rename(&partial_path, &archive_path)?;
+
+        fs::remove_dir_all(venv_path).map_err(|e| VenvCleanerError::Io(format!(
+            "Archived to {} but failed to remove {}: {}",
+            archive_path.display(),
+            venv_path.display(),
+            e
+        )))?;
+
+        let stub = ArchiveStub {
+            venv_path: venv_path.to_path_buf(),
+            archive_path,
+            original_size: venv_info.size().bytes(),
+            archived_at: Utc::now(),
+            python_version: venv_info.python_version().map(|v| v.to_string()),
+        };
+        let stub_path = stub.write()?;
+        info!("Archived {} ({})", venv_path.display(), stub_path.display());
+
+        Ok(stub)
+    }
+
+    /// Restore an archived .venv from its stub file
+    ///
+    /// The archive and stub are removed once the .venv is back in place.
+    ///
+    /// # Arguments
+    /// * `stub_path` - Path to the `.venv.archived` stub file
+    ///
+    /// # Returns
+    /// The path of the restored .venv directory
+    pub fn unarchive(stub_path: &Path) -> Result<PathBuf> {
+        let stub = ArchiveStub::read(stub_path)?;
+        let venv_path = stub.venv_path().to_path_buf();
+
+        if venv_path.exists() {
+            return Err(VenvCleanerError::PathError {
+                path: venv_path.display().to_string(),
+                message: "A .venv already exists here; remove it before restoring the archive".to_string(),
+            });
+        }
+        if !stub.archive_path().is_file() {
+            return Err(VenvCleanerError::PathError {
+                path: stub.archive_path().display().to_string(),
+                message: "Archive file is missing".to_string(),
+            });
+        }
+
+        let project_dir = venv_path.parent().ok_or_else(|| VenvCleanerError::PathError {
+            path: venv_path.display().to_string(),
+            message: "Archived .venv has no parent directory".to_string(),
+        })?;
+
+        debug!("Restoring {} from {}", venv_path.display(), stub.archive_path().display());
+        let decoder = zstd::Decoder::new(File::open(stub.archive_path())?)?;
+        let mut archive = tar::Archive::new(decoder);
+        archive.set_preserve_permissions(true);
+        archive.set_preserve_mtime(true);
+        if let Err(e) = archive.unpack(project_dir) {
+            // Leave the archive and stub alone so the restore can be retried
+            let _ = fs::remove_dir_all(&venv_path);
+            return Err(VenvCleanerError::Io(format!("Failed to restore {}: {}", venv_path.display(), e)));
+        }
+
+        fs::remove_file(stub.archive_path())?;
+        fs::remove_file(stub_path)?;
+        info!("Restored {}", venv_path.display());
+
+        Ok(venv_path)
+    }
+
+    /// Discard an archived .venv: remove its archive and stub file
+    ///
+    /// # Arguments
+    /// * `stub_path` - Path to the `.venv.archived` stub file
+    ///
+    /// # Returns
+    /// The number of bytes freed (0 if the archive was already missing)
+    pub fn delete_archive(stub_path: &Path) -> Result<u64> {
+        let stub = ArchiveStub::read(stub_path)?;
+        let freed = archive_size(stub.archive_path()).unwrap_or(0);
+
+        if stub.archive_path().exists() {
+            fs::remove_file(stub.archive_path())?;
+        }
+        fs::remove_file(stub_path)?;
+        info!("Deleted archive of {}", stub.venv_path().display());
+
+        Ok(freed)
+    }
+
+    /// Pick an unused archive file name for a .venv
+    fn next_archive_path(&self, venv_info: &VenvInfo) -> PathBuf {
+        let project = venv_info.project_name().unwrap_or_else(|| "venv".to_string());
+        let stem = format!("{}-{}", project, Utc::now().format("%Y%m%d-%H%M%S"));
+
+        let mut candidate = self.archive_dir.join(format!("{}.{}", stem, ARCHIVE_EXTENSION));
+        let mut counter = 1;
+        while candidate.exists() {
+            candidate = self.archive_dir.join(format!("{}-{}.{}", stem, counter, ARCHIVE_EXTENSION));
+            counter += 1;
+        }
+        candidate
+    }
+}
+
+/// Get the size of an archive file, if it exists
+///
+/// # Arguments
+/// * `archive_path` - Path to the archive
+pub fn archive_size(archive_path: &Path) -> Option<u64> {
+    fs::metadata(archive_path).ok().map(|m| m.len())
+}
+
+/// Check whether a directory holds an archive stub
+///
+/// # Arguments
+/// * `project_dir` - The project directory to check
+pub fn has_stub(project_dir: &Path) -> bool {
+    project_dir.join(STUB_FILE_NAME).is_file()
+}
+
+/// Load the archived .venv directories of stub files
+///
+/// Stub files that cannot be read are logged and skipped.
+///
+/// # Arguments
+/// * `stub_paths` - The stub files, as found by a scan
+pub fn load_archived(stub_paths: &[PathBuf]) -> Vec<ArchivedVenv> {
+    stub_paths
+        .iter()
+        .filter_map(|stub_path| match ArchivedVenv::load(stub_path) {
+            Ok(archived) => Some(archived),
+            Err(e) => {
+                warn!("Ignoring unreadable archive stub {}: {}", stub_path.display(), e);
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ByteSize;
+    use tempfile::TempDir;
+
+    fn create_venv(project: &Path) -> VenvInfo {
+        let venv = project.join(".venv");
+        fs::create_dir_all(venv.join("bin")).unwrap();
+        fs::write(venv.join("pyvenv.cfg"), "version = 3.11.4\n").unwrap();
+        fs::write(venv.join("bin").join("activate"), "# activate\n").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink("/usr/bin/python3", venv.join("bin").join("python")).unwrap();
+
+        VenvInfo::new(venv, ByteSize::new(64), Utc::now(), Utc::now())
+            .with_python_version(crate::core::PythonVersion::from_venv(&project.join(".venv")))
+    }
+
+    #[test]
+    fn test_archive_and_unarchive_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("webapp");
+        let venv_info = create_venv(&project);
+        let archiver = VenvArchiver::new(temp_dir.path().join("archives"));
+
+        let stub = archiver.archive(&venv_info).unwrap();
+        assert!(!project.join(".venv").exists());
+        assert!(has_stub(&project));
+        assert!(stub.archive_path().is_file());
+        assert!(stub.archive_path().to_string_lossy().ends_with(".tar.zst"));
+        assert_eq!(stub.python_version(), Some("3.11.4"));
+        assert_eq!(ArchiveStub::read(&project.join(STUB_FILE_NAME)).unwrap(), stub);
+
+        let restored = VenvArchiver::unarchive(&project.join(STUB_FILE_NAME)).unwrap();
+        assert_eq!(restored, project.join(".venv"));
+        assert_eq!(fs::read_to_string(restored.join("bin").join("activate")).unwrap(), "# activate\n");
+        #[cfg(unix)]
+        assert!(fs::symlink_metadata(restored.join("bin").join("python")).unwrap().file_type().is_symlink());
+        assert!(!has_stub(&project));
+        assert!(!stub.archive_path().exists());
+    }
+
+    #[test]
+    fn test_unarchive_refuses_existing_venv() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("webapp");
+        let venv_info = create_venv(&project);
+        let archiver = VenvArchiver::new(temp_dir.path().join("archives"));
+
+        let stub = archiver.archive(&venv_info).unwrap();
+        fs::create_dir_all(project.join(".venv")).unwrap();
+
+        assert!(VenvArchiver::unarchive(&project.join(STUB_FILE_NAME)).is_err());
+        assert!(stub.archive_path().exists());
+        assert!(has_stub(&project));
+    }
+
+    #[test]
+    fn test_archive_names_do_not_collide() {
+        let temp_dir = TempDir::new().unwrap();
+        let archiver = VenvArchiver::new(temp_dir.path().to_path_buf());
+        let venv_info = VenvInfo::new(temp_dir.path().join("proj").join(".venv"), ByteSize::new(0), Utc::now(), Utc::now());
+
+        let first = archiver.next_archive_path(&venv_info);
+        fs::write(&first, "").unwrap();
+        let second = archiver.next_archive_path(&venv_info);
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_load_archived_and_delete_archive() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("apps").join("webapp");
+        let venv_info = create_venv(&project);
+        let stub = VenvArchiver::new(temp_dir.path().join("archives")).archive(&venv_info).unwrap();
+
+        let archived = load_archived(&[ArchiveStub::stub_path_for(venv_info.path()), temp_dir.path().join(STUB_FILE_NAME)]);
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].location(), project.display().to_string());
+        assert_eq!(archived[0].compressed_size(), archive_size(stub.archive_path()));
+
+        let freed = VenvArchiver::delete_archive(archived[0].stub_path()).unwrap();
+        assert!(freed > 0);
+        assert!(!stub.archive_path().exists());
+        assert!(!has_stub(&project));
+    }
+}
@@ -0,0 +1,160 @@
+//! Project build artifact detection for "deep clean" operations
+//!
+//! This module finds the build artifacts that commonly accumulate next to a
+//! Python project's .venv folder (`build/`, `dist/`, `*.egg-info` and `.tox`),
+//! so they can optionally be removed together with the virtual environment.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+use super::{sorting, FileUtils, Result};
+
+/// Directory names that are treated as build artifacts of a project
+pub const ARTIFACT_DIR_NAMES: &[&str] = &["build", "dist", ".tox"];
+
+/// Directory name suffixes that are treated as build artifacts of a project
+pub const ARTIFACT_DIR_SUFFIXES: &[&str] = &[".egg-info"];
+
+/// A single build artifact directory belonging to a project
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectArtifact {
+    /// Full path to the artifact directory
+    path: PathBuf,
+    /// Size of the artifact directory in bytes
+    size_bytes: u64,
+}
+
+impl ProjectArtifact {
+    /// Create a new ProjectArtifact instance
+    ///
+    /// # Arguments
+    /// * `path` - Full path to the artifact directory
+    /// * `size_bytes` - Size of the directory in bytes
+    pub fn new(path: PathBuf, size_bytes: u64) -> Self {
+        Self { path, size_bytes }
+    }
+
+    /// Get the path to the artifact directory
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Get the size in bytes
+    pub fn size_bytes(&self) -> u64 {
+        self.size_bytes
+    }
+
+    /// Get the artifact directory name (e.g. `dist` or `mypkg.egg-info`)
+    pub fn name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// Utility struct for locating project build artifacts
+pub struct ProjectArtifacts;
+
+impl ProjectArtifacts {
+    /// Check whether a directory name looks like a build artifact
+    ///
+    /// # Arguments
+    /// * `name` - The directory name to check
+    ///
+    /// # Returns
+    /// True if the name matches one of the known artifact names or suffixes
+    pub fn is_artifact_name(name: &str) -> bool {
+        ARTIFACT_DIR_NAMES.contains(&name)
+            || ARTIFACT_DIR_SUFFIXES
+                .iter()
+                .any(|suffix| name.len() > suffix.len() && name.ends_with(suffix))
+    }
+
+    /// Find the build artifacts directly inside a project directory
+    ///
+    /// Only the top level of the project is inspected; nested `build/`
+    /// folders belong to sub-packages and are deliberately left alone.
+    ///
+    /// # Arguments
+    /// * `project_dir` - The project directory (the parent of the .venv)
+    ///
+    /// # Returns
+    /// The artifacts found, sorted by path
+    pub fn find(project_dir: &Path) -> Result<Vec<ProjectArtifact>> {
+        let mut artifacts = Vec::new();
+
+        for entry in fs::read_dir(project_dir)? {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    warn!("Error reading entry in {}: {}", project_dir.display(), e);
+                    continue;
+                }
+            };
+
+            // Never follow symlinks to directories outside the project
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            if is_dir && Self::is_artifact_name(&name) {
+                let size = FileUtils::calculate_directory_size(&entry.path())?;
+                debug!("Found build artifact: {} ({} bytes)", entry.path().display(), size);
+                artifacts.push(ProjectArtifact::new(entry.path(), size));
+            }
+        }
+
+        artifacts.sort_by(|a, b| sorting::compare_paths(&a.path, &b.path));
+        Ok(artifacts)
+    }
+
+    /// Calculate the combined size of a set of artifacts
+    pub fn total_size(artifacts: &[ProjectArtifact]) -> u64 {
+        artifacts.iter().map(|a| a.size_bytes()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_is_artifact_name() {
+        assert!(ProjectArtifacts::is_artifact_name("build"));
+        assert!(ProjectArtifacts::is_artifact_name("dist"));
+        assert!(ProjectArtifacts::is_artifact_name(".tox"));
+        assert!(ProjectArtifacts::is_artifact_name("mypkg.egg-info"));
+        assert!(!ProjectArtifacts::is_artifact_name(".egg-info"));
+        assert!(!ProjectArtifacts::is_artifact_name("src"));
+        assert!(!ProjectArtifacts::is_artifact_name(".venv"));
+    }
+
+    #[test]
+    fn test_find_artifacts() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+
+        fs::create_dir(project.join("build")).unwrap();
+        fs::write(project.join("build").join("lib.o"), "12345").unwrap();
+        fs::create_dir(project.join("dist")).unwrap();
+        fs::create_dir(project.join("mypkg.egg-info")).unwrap();
+        fs::create_dir(project.join("src")).unwrap();
+        fs::create_dir(project.join(".venv")).unwrap();
+        // A plain file named like an artifact must be ignored
+        fs::write(project.join(".tox"), "not a directory").unwrap();
+
+        let artifacts = ProjectArtifacts::find(project).unwrap();
+        let names: Vec<String> = artifacts.iter().map(|a| a.name()).collect();
+        assert_eq!(names, vec!["build", "dist", "mypkg.egg-info"]);
+        assert_eq!(ProjectArtifacts::total_size(&artifacts), 5);
+    }
+
+    #[test]
+    fn test_find_artifacts_empty_project() {
+        let temp_dir = TempDir::new().unwrap();
+        let artifacts = ProjectArtifacts::find(temp_dir.path()).unwrap();
+        assert!(artifacts.is_empty());
+    }
+}
@@ -0,0 +1,187 @@
+//! Scans and deletions for async services (feature `async`)
+//!
+//! Scanning and deleting block on the disk, so a service running on tokio
+//! would otherwise have to manage its own threads and channels to keep its
+//! executor free. `VenvCleaner::spawn_scan` and `VenvCleaner::spawn_delete`
+//! run the operation on tokio's blocking pool and return a `TaskHandle`:
+//! progress is read from it as the operation goes (each .venv found, each
+//! deletion finished), it can be cancelled at any time, and `finish` waits
+//! for the result. Dropping an unfinished handle cancels its operation, so a
+//! dashboard request that goes away does not leave a scan walking the disk.
+//! Cancellation behaves as for `CancellationToken`: a scan keeps what it
+//! found, and a batch deletion finishes the .venv being removed.
+
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use super::{BatchItem, BatchOptions, BatchResult, CancellationToken, Result, VenvCleaner, VenvCleanerError, VenvInfo};
+
+/// A scan running in the background, reporting each .venv as it is found
+pub type ScanTask = TaskHandle<VenvInfo, Vec<VenvInfo>>;
+
+/// A batch deletion running in the background, reporting each .venv as it is done
+pub type DeleteTask = TaskHandle<BatchItem, BatchResult>;
+
+/// An operation running on tokio's blocking pool
+///
+/// `E` is the progress event streamed while it runs, `R` its result.
+#[derive(Debug)]
+pub struct TaskHandle<E, R> {
+    /// Progress events, closed once the operation ends
+    events: mpsc::UnboundedReceiver<E>,
+    /// The operation, None once `finish` took it
+    task: Option<JoinHandle<Result<R>>>,
+    /// Stops the operation
+    cancellation: CancellationToken,
+}
+
+impl<E, R> TaskHandle<E, R> {
+    /// Wait for the next progress event
+    ///
+    /// # Returns
+    /// The event, or None once the operation ended and every event was read
+    pub async fn next_event(&mut self) -> Option<E> {
+        self.events.recv().await
+    }
+
+    /// Ask the operation to stop; `finish` still returns what it got done
+    pub fn cancel(&self) {
+        self.cancellation.cancel();
+    }
+
+    /// Get the token stopping the operation, e.g. to cancel it from another task
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancellation.clone()
+    }
+
+    /// Wait for the operation to end, discarding the events not read yet
+    ///
+    /// # Returns
+    /// The result of the operation, or an error if its thread panicked
+    pub async fn finish(mut self) -> Result<R> {
+        let Some(task) = self.task.take() else {
+            return Err(VenvCleanerError::Io("The operation was already finished".to_string()));
+        };
+        task.await
+            .map_err(|e| VenvCleanerError::Io(format!("The background operation failed: {}", e)))?
+    }
+}
+
+impl<E, R> Drop for TaskHandle<E, R> {
+    fn drop(&mut self) {
+        if self.task.is_some() {
+            self.cancellation.cancel();
+        }
+    }
+}
+
+impl VenvCleaner {
+    /// Find the .venv directories on tokio's blocking pool
+    ///
+    /// Must be called from within a tokio runtime. The scan gets a
+    /// cancellation token of its own, replacing any set with `with_cancellation`.
+    ///
+    /// # Returns
+    /// A handle streaming each .venv found and finishing with all of them
+    /// (see `find_venv_directories_with`)
+    pub fn spawn_scan(&self) -> ScanTask {
+        let cancellation = CancellationToken::new();
+        let cleaner = self.clone().with_cancellation(cancellation.clone());
+        let (events, receiver) = mpsc::unbounded_channel();
+        let task = tokio::task::spawn_blocking(move || {
+            // A receiver that went away only means nobody is watching
+            cleaner.find_venv_directories_with(|venv_info| {
+                let _ = events.send(venv_info.clone());
+            })
+        });
+        TaskHandle { events: receiver, task: Some(task), cancellation }
+    }
+
+    /// Delete .venv directories on tokio's blocking pool
+    ///
+    /// Must be called from within a tokio runtime.
+    ///
+    /// # Arguments
+    /// * `venvs` - The .venv directories to delete
+    /// * `options` - Parallelism, and a cancellation token shared with the handle if it has one
+    ///
+    /// # Returns
+    /// A handle streaming each finished .venv and finishing with the batch
+    /// (see `delete_many`)
+    pub fn spawn_delete(&self, venvs: Vec<VenvInfo>, options: BatchOptions) -> DeleteTask {
+        let cancellation = options.cancellation().cloned().unwrap_or_default();
+        let options = options.with_cancellation(cancellation.clone());
+        let cleaner = self.clone();
+        let (events, receiver) = mpsc::unbounded_channel();
+        let task = tokio::task::spawn_blocking(move || {
+            Ok(cleaner.delete_many(&venvs, &options, |item| {
+                let _ = events.send(item.clone());
+            }))
+        });
+        TaskHandle { events: receiver, task: Some(task), cancellation }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::MemoryFs;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    fn cleaner(venvs: usize) -> (VenvCleaner, Arc<MemoryFs>) {
+        let fs = Arc::new(MemoryFs::new());
+        for i in 0..venvs {
+            fs.add_venv(format!("/memory/work/p{}/.venv", i), 100);
+        }
+        let cleaner = VenvCleaner::new("/memory/work".into(), true, true, false, 0).with_fs_backend(fs.clone());
+        (cleaner, fs)
+    }
+
+    #[tokio::test]
+    async fn test_spawn_scan_streams_each_venv() {
+        let (cleaner, _fs) = cleaner(3);
+        let mut scan = cleaner.spawn_scan();
+        let mut streamed = Vec::new();
+        while let Some(venv_info) = scan.next_event().await {
+            streamed.push(venv_info.path().to_path_buf());
+        }
+        streamed.sort();
+
+        let found = scan.finish().await.unwrap();
+        assert_eq!(found.len(), 3);
+        assert_eq!(streamed[0], Path::new("/memory/work/p0/.venv"));
+        assert_eq!(streamed.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_spawn_delete_streams_and_cancels() {
+        let (cleaner, fs) = cleaner(2);
+        let venvs = cleaner.find_venv_directories().unwrap();
+
+        let mut deletion = cleaner.spawn_delete(venvs.clone(), BatchOptions::new());
+        assert!(deletion.next_event().await.unwrap().is_deleted());
+        let batch = deletion.finish().await.unwrap();
+        assert_eq!(batch.deleted(), 2);
+        assert!(!fs.contains("/memory/work/p0/.venv"));
+
+        // The caller's token is the handle's; cancelled before the batch starts, nothing is deleted
+        let (cleaner, fs) = self::cleaner(2);
+        let token = CancellationToken::new();
+        token.cancel();
+        let deletion = cleaner.spawn_delete(venvs, BatchOptions::new().with_cancellation(token));
+        assert!(deletion.cancellation_token().is_cancelled());
+        let batch = deletion.finish().await.unwrap();
+        assert_eq!(batch.cancelled(), 2);
+        assert!(fs.contains("/memory/work/p0/.venv"));
+    }
+
+    #[tokio::test]
+    async fn test_dropping_the_handle_cancels() {
+        let (cleaner, _fs) = cleaner(1);
+        let scan = cleaner.spawn_scan();
+        let token = scan.cancellation_token();
+        drop(scan);
+        assert!(token.is_cancelled());
+    }
+}
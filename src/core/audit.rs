@@ -0,0 +1,338 @@
+//! Append-only audit log of deletions
+//!
+//! On managed machines every deletion can be recorded with the user, time,
+//! path and size, one JSON object per line. Each entry carries the SHA-256
+//! hash of its own contents and of the entry before it, so editing,
+//! removing or reordering entries breaks the chain and is caught by
+//! `audit verify`. Cutting entries off the end cannot be detected from the
+//! log alone, which is why `audit verify` prints the last hash for keeping
+//! elsewhere. The log is enabled with `audit_log` in the `[policy]` table.
+//! Appends hold an exclusive `flock` on the log from reading the last hash
+//! to writing the new entry, so parallel deletions and concurrent runs
+//! chain their entries one after the other.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::debug;
+
+use super::run_lock::{self, LockMode};
+use super::{ownership, Result, VenvCleanerError};
+
+/// Previous hash of the first entry in a log
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// What happened to a .venv directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    /// Removed permanently
+    Delete,
+    /// Moved to the system trash
+    Trash,
+    /// Compressed into an archive and removed
+    Archive,
+}
+
+/// One line of the audit log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Position in the log, starting at 1
+    pub seq: u64,
+    /// When the action happened
+    pub timestamp: DateTime<Utc>,
+    /// Login name of the user who ran VenvCleaner
+    pub user: String,
+    /// What was done
+    pub action: AuditAction,
+    /// The .venv directory
+    pub path: PathBuf,
+    /// Its size in bytes
+    pub size_bytes: u64,
+    /// Hash of the previous entry (`GENESIS_HASH` for the first)
+    pub prev_hash: String,
+    /// Hash of this entry, covering every other field
+    pub hash: String,
+}
+
+impl AuditEntry {
+    /// Compute the hash of the entry from all fields but `hash`
+    pub fn compute_hash(&self) -> String {
+        let action = serde_json::to_string(&self.action).unwrap_or_default();
+        let mut hasher = Sha256::new();
+        hasher.update(format!(
+            "{}\n{}\n{}\n{}\n{}\n",
+            self.prev_hash,
+            self.seq,
+            // UTC keeps the hash stable when the log is checked in another time zone
+            self.timestamp.to_rfc3339(),
+            self.user,
+            action,
+        ));
+        // The path's own bytes, so names that are not valid UTF-8 still hash apart
+        hasher.update(self.path.as_os_str().as_encoded_bytes());
+        hasher.update(format!("\n{}", self.size_bytes));
+        hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+}
+
+/// Result of checking an audit log
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditVerification {
+    /// Number of entries that were checked and intact
+    pub valid_entries: usize,
+    /// Line number and description of the first problem, if any
+    pub broken: Option<(usize, String)>,
+    /// Hash of the last intact entry (`GENESIS_HASH` for an empty log)
+    pub last_hash: String,
+}
+
+impl AuditVerification {
+    /// Check whether the whole log is intact
+    pub fn is_intact(&self) -> bool {
+        self.broken.is_none()
+    }
+}
+
+/// An audit log file
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditLog {
+    /// Where the log is stored
+    path: PathBuf,
+}
+
+impl AuditLog {
+    /// Create a handle to the log at `path` (the file is created on first append)
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Get the location of the log
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append an entry for an action, chained to the current last entry
+    ///
+    /// # Arguments
+    /// * `action` - What was done
+    /// * `venv_path` - The .venv directory
+    /// * `size_bytes` - Its size
+    ///
+    /// # Returns
+    /// The entry written
+    pub fn append(&self, action: AuditAction, venv_path: &Path, size_bytes: u64) -> Result<AuditEntry> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().read(true).create(true).append(true).open(&self.path)?;
+        // Held until the file is closed, so no other append slips in after the last hash is read
+        run_lock::try_lock(&file, LockMode::Wait)?;
+
+        let (seq, prev_hash) = match self.last_entry(&mut file)? {
+            Some(last) => (last.seq + 1, last.hash),
+            None => (1, GENESIS_HASH.to_string()),
+        };
+        let mut entry = AuditEntry {
+            seq,
+            timestamp: Utc::now(),
+            user: current_user(),
+            action,
+            path: venv_path.to_path_buf(),
+            size_bytes,
+            prev_hash,
+            hash: String::new(),
+        };
+        entry.hash = entry.compute_hash();
+
+        let line = serde_json::to_string(&entry)
+            .map_err(|e| VenvCleanerError::Io(format!("Failed to encode audit entry: {}", e)))?;
+        writeln!(file, "{}", line)?;
+        file.sync_data()?;
+
+        debug!("Audit entry {} written for {}", entry.seq, venv_path.display());
+        Ok(entry)
+    }
+
+    /// Read the last entry of the log from its open, locked file
+    fn last_entry(&self, file: &mut File) -> Result<Option<AuditEntry>> {
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+        match contents.lines().rev().find(|line| !line.trim().is_empty()) {
+            Some(line) => serde_json::from_str(line).map(Some).map_err(|e| VenvCleanerError::Io(format!(
+                "The last line of the audit log {} is damaged: {}",
+                self.path.display(),
+                e
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    /// Check every entry's hash and its link to the previous entry
+    ///
+    /// # Returns
+    /// How many entries are intact and where the chain first breaks, if it does
+    pub fn verify(&self) -> Result<AuditVerification> {
+        let contents = fs::read_to_string(&self.path).map_err(|e| VenvCleanerError::PathError {
+            path: self.path.display().to_string(),
+            message: e.to_string(),
+        })?;
+
+        let mut prev_hash = GENESIS_HASH.to_string();
+        let mut valid_entries = 0;
+
+        for (index, line) in contents.lines().enumerate() {
+            let line_number = index + 1;
+            let expected_seq = valid_entries as u64 + 1;
+            let broken = |message: String| Ok(AuditVerification {
+                valid_entries,
+                broken: Some((line_number, message)),
+                last_hash: prev_hash.clone(),
+            });
+
+            let entry: AuditEntry = match serde_json::from_str(line) {
+                Ok(entry) => entry,
+                Err(e) => return broken(format!("not a valid entry: {}", e)),
+            };
+            if entry.seq != expected_seq {
+                return broken(format!("expected entry {}, found {}", expected_seq, entry.seq));
+            }
+            if entry.prev_hash != prev_hash {
+                return broken("does not follow the previous entry (an entry was removed or reordered)".to_string());
+            }
+            if entry.compute_hash() != entry.hash {
+                return broken("contents do not match the entry's hash (the entry was edited)".to_string());
+            }
+
+            prev_hash = entry.hash;
+            valid_entries += 1;
+        }
+
+        Ok(AuditVerification { valid_entries, broken: None, last_hash: prev_hash })
+    }
+}
+
+/// Get the login name of the current user
+///
+/// On Unix the effective user is looked up in the password database, as
+/// the owner filter does, so `sudo` or a changed `$USER` cannot misstate who
+/// deleted a .venv. Elsewhere the environment is all there is.
+fn current_user() -> String {
+    if let Some(uid) = ownership::current_uid() {
+        return ownership::describe_owner(Some(uid));
+    }
+    ["USER", "USERNAME"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|user| !user.is_empty()))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_log(temp_dir: &TempDir) -> AuditLog {
+        let log = AuditLog::new(temp_dir.path().join("logs").join("audit.log"));
+        log.append(AuditAction::Delete, Path::new("/work/a/.venv"), 100).unwrap();
+        log.append(AuditAction::Trash, Path::new("/work/b/.venv"), 200).unwrap();
+        log.append(AuditAction::Archive, Path::new("/work/c/.venv"), 300).unwrap();
+        log
+    }
+
+    #[test]
+    fn test_append_chains_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let log = create_log(&temp_dir);
+
+        let contents = fs::read_to_string(log.path()).unwrap();
+        let entries: Vec<AuditEntry> = contents.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].prev_hash, GENESIS_HASH);
+        assert_eq!(entries[1].prev_hash, entries[0].hash);
+        assert_eq!(entries[2].seq, 3);
+        assert_eq!(entries[2].action, AuditAction::Archive);
+
+        let verification = log.verify().unwrap();
+        assert!(verification.is_intact());
+        assert_eq!(verification.valid_entries, 3);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_user_comes_from_the_effective_uid() {
+        let uid = ownership::current_uid().unwrap();
+        assert_eq!(current_user(), ownership::user_name(uid).unwrap_or_else(|| uid.to_string()));
+    }
+
+    #[test]
+    fn test_verify_detects_edited_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let log = create_log(&temp_dir);
+
+        let contents = fs::read_to_string(log.path()).unwrap();
+        fs::write(log.path(), contents.replace("\"size_bytes\":200", "\"size_bytes\":2")).unwrap();
+
+        let verification = log.verify().unwrap();
+        assert_eq!(verification.valid_entries, 1);
+        let (line, message) = verification.broken.unwrap();
+        assert_eq!(line, 2);
+        assert!(message.contains("edited"));
+    }
+
+    #[test]
+    fn test_verify_detects_removed_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let log = create_log(&temp_dir);
+
+        let contents = fs::read_to_string(log.path()).unwrap();
+        let without_first: Vec<&str> = contents.lines().skip(1).collect();
+        fs::write(log.path(), without_first.join("\n")).unwrap();
+
+        let (line, message) = log.verify().unwrap().broken.unwrap();
+        assert_eq!(line, 1);
+        assert!(message.contains("expected entry 1"));
+    }
+
+    #[test]
+    fn test_parallel_appends_keep_the_chain() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("audit.log");
+
+        // Each thread opens the log itself, as separate runs would
+        std::thread::scope(|scope| {
+            for i in 0..8 {
+                let log = AuditLog::new(path.clone());
+                scope.spawn(move || log.append(AuditAction::Delete, &PathBuf::from(format!("/work/{}/.venv", i)), 1).unwrap());
+            }
+        });
+
+        let verification = AuditLog::new(path).verify().unwrap();
+        assert!(verification.is_intact());
+        assert_eq!(verification.valid_entries, 8);
+        assert_eq!(verification.last_hash.len(), 64);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hash_covers_raw_path_bytes() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let entry = |path: &[u8]| AuditEntry {
+            seq: 1,
+            timestamp: DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z").unwrap().with_timezone(&Utc),
+            user: "ci".to_string(),
+            action: AuditAction::Delete,
+            path: PathBuf::from(OsStr::from_bytes(path)),
+            size_bytes: 1,
+            prev_hash: GENESIS_HASH.to_string(),
+            hash: String::new(),
+        };
+        // Both display as "/work/\u{FFFD}/.venv"
+        assert_ne!(entry(b"/work/\xff/.venv").compute_hash(), entry(b"/work/\xfe/.venv").compute_hash());
+    }
+}
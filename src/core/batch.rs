@@ -0,0 +1,208 @@
+//! Deleting a batch of .venv directories and summarising the outcome
+//!
+//! The CLI, TUI and GUI all delete a selection in one go and then report
+//! how many went, how many failed or had already been removed elsewhere, and
+//! how much space was freed. `VenvCleaner::delete_many` runs the batch
+//! (in parallel up to the delete jobs, stopping early when cancelled) and
+//! returns a `BatchResult` holding every item's outcome and timing along
+//! with those totals, so the frontends only have to present them.
+
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+
+use super::{CancellationToken, Result, VenvCleanerError, VenvInfo};
+
+/// How a batch deletion is run
+#[derive(Debug, Clone, Default)]
+pub struct BatchOptions {
+    /// Number of .venv directories deleted at once, or None for the cleaner's delete jobs
+    jobs: Option<usize>,
+    /// Stops the batch in place of the cleaner's own token
+    cancellation: Option<CancellationToken>,
+}
+
+impl BatchOptions {
+    /// Create options running the batch as the cleaner is configured
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how many .venv directories are deleted at once
+    ///
+    /// # Arguments
+    /// * `jobs` - Parallel deletions (at least 1), or None for the cleaner's delete jobs
+    pub fn with_jobs(mut self, jobs: Option<usize>) -> Self {
+        self.jobs = jobs.map(|jobs| jobs.max(1));
+        self
+    }
+
+    /// Stop the batch with a token of its own, e.g. a Cancel button
+    ///
+    /// # Arguments
+    /// * `cancellation` - The token; deletions running when it is cancelled finish
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = Some(cancellation);
+        self
+    }
+
+    /// Get the number of parallel deletions, if set
+    pub fn jobs(&self) -> Option<usize> {
+        self.jobs
+    }
+
+    /// Get the batch's own cancellation token, if set
+    pub fn cancellation(&self) -> Option<&CancellationToken> {
+        self.cancellation.as_ref()
+    }
+}
+
+/// The outcome of deleting one .venv of a batch
+#[derive(Debug, Clone)]
+pub struct BatchItem {
+    /// The .venv
+    pub venv: VenvInfo,
+    /// Whether it was deleted, or why not
+    pub result: Result<()>,
+    /// How long deleting it took (zero if it was never started)
+    pub elapsed: Duration,
+}
+
+impl BatchItem {
+    /// Check whether the .venv was deleted
+    pub fn is_deleted(&self) -> bool {
+        self.result.is_ok()
+    }
+
+    /// Check whether the .venv had already been removed outside VenvCleaner
+    pub fn is_vanished(&self) -> bool {
+        matches!(&self.result, Err(e) if e.is_vanished())
+    }
+
+    /// Check whether the .venv was left alone because the batch was cancelled
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self.result, Err(VenvCleanerError::OperationCancelled))
+    }
+
+    /// Check whether deleting the .venv failed for any other reason
+    pub fn is_failed(&self) -> bool {
+        !self.is_deleted() && !self.is_vanished() && !self.is_cancelled()
+    }
+
+    /// Get the space freed by deleting the .venv (0 unless it was deleted)
+    pub fn freed_bytes(&self) -> u64 {
+        if self.is_deleted() {
+            self.venv.size().bytes()
+        } else {
+            0
+        }
+    }
+}
+
+/// The outcome of a batch deletion
+#[derive(Debug, Clone)]
+pub struct BatchResult {
+    /// Every .venv of the batch, in the order given
+    items: Vec<BatchItem>,
+    /// When the batch started
+    started_at: DateTime<Utc>,
+    /// How long the whole batch took
+    elapsed: Duration,
+}
+
+impl BatchResult {
+    /// Create a batch result
+    ///
+    /// # Arguments
+    /// * `items` - Every .venv of the batch, in the order given
+    /// * `started_at` - When the batch started
+    /// * `elapsed` - How long the whole batch took
+    pub fn new(items: Vec<BatchItem>, started_at: DateTime<Utc>, elapsed: Duration) -> Self {
+        Self { items, started_at, elapsed }
+    }
+
+    /// Get every .venv of the batch with its outcome, in the order given
+    pub fn items(&self) -> &[BatchItem] {
+        &self.items
+    }
+
+    /// Take the items out of the result
+    pub fn into_items(self) -> Vec<BatchItem> {
+        self.items
+    }
+
+    /// Get the items that were attempted, leaving out those skipped by a cancellation
+    pub fn attempted(&self) -> impl Iterator<Item = &BatchItem> {
+        self.items.iter().filter(|item| !item.is_cancelled())
+    }
+
+    /// Get the number of .venv directories in the batch
+    pub fn total(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Get the number of .venv directories deleted
+    pub fn deleted(&self) -> usize {
+        self.items.iter().filter(|item| item.is_deleted()).count()
+    }
+
+    /// Get the number already removed outside VenvCleaner
+    pub fn vanished(&self) -> usize {
+        self.items.iter().filter(|item| item.is_vanished()).count()
+    }
+
+    /// Get the number left alone because the batch was cancelled
+    pub fn cancelled(&self) -> usize {
+        self.items.iter().filter(|item| item.is_cancelled()).count()
+    }
+
+    /// Get the number that could not be deleted
+    pub fn failed(&self) -> usize {
+        self.items.iter().filter(|item| item.is_failed()).count()
+    }
+
+    /// Get the space freed by the batch
+    pub fn freed_bytes(&self) -> u64 {
+        self.items.iter().map(BatchItem::freed_bytes).sum()
+    }
+
+    /// Get when the batch started
+    pub fn started_at(&self) -> DateTime<Utc> {
+        self.started_at
+    }
+
+    /// Get how long the whole batch took
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ByteSize;
+
+    fn item(size: u64, result: Result<()>) -> BatchItem {
+        let venv = VenvInfo::new(format!("/p{}/.venv", size).into(), ByteSize::new(size), Utc::now(), Utc::now());
+        BatchItem { venv, result, elapsed: Duration::from_millis(size) }
+    }
+
+    #[test]
+    fn test_totals() {
+        let result = BatchResult::new(
+            vec![
+                item(10, Ok(())),
+                item(20, Ok(())),
+                item(30, Err(VenvCleanerError::VenvVanished { path: "/p30/.venv".to_string() })),
+                item(40, Err(VenvCleanerError::Io("busy".to_string()))),
+                item(50, Err(VenvCleanerError::OperationCancelled)),
+            ],
+            Utc::now(),
+            Duration::from_secs(1),
+        );
+
+        assert_eq!(result.total(), 5);
+        assert_eq!((result.deleted(), result.vanished(), result.failed(), result.cancelled()), (2, 1, 1, 1));
+        assert_eq!(result.freed_bytes(), 30);
+        assert_eq!(result.attempted().count(), 4);
+    }
+}
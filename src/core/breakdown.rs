@@ -0,0 +1,215 @@
+//! Size breakdown of a .venv by content type
+//!
+//! This module splits a virtual environment's size into buckets — compiled
+//! bytecode caches, native shared libraries, Python sources and everything
+//! else (data files, metadata, scripts) — and measures how much of it a
+//! slim pass would reclaim (`__pycache__` and bundled test suites).
+
+use std::path::Path;
+use walkdir::WalkDir;
+use tracing::warn;
+
+use super::slim;
+
+/// Content categories a .venv's files are sorted into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeCategory {
+    /// Compiled bytecode (`*.pyc`, `*.pyo`, anything inside `__pycache__`)
+    Bytecode,
+    /// Native extension modules and shared libraries
+    SharedLibraries,
+    /// Python source files
+    Sources,
+    /// Everything else: data files, metadata, scripts, headers
+    Data,
+}
+
+impl SizeCategory {
+    /// All categories in display order
+    pub const ALL: [SizeCategory; 4] = [
+        SizeCategory::Bytecode,
+        SizeCategory::SharedLibraries,
+        SizeCategory::Sources,
+        SizeCategory::Data,
+    ];
+
+    /// Categorize a file by its path
+    ///
+    /// # Arguments
+    /// * `path` - Path to the file
+    pub fn of(path: &Path) -> Self {
+        let in_pycache = path.components().any(|c| c.as_os_str() == "__pycache__");
+        let extension = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let file_name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+
+        if in_pycache || extension == "pyc" || extension == "pyo" {
+            SizeCategory::Bytecode
+        } else if matches!(extension.as_str(), "so" | "pyd" | "dylib" | "dll") || file_name.contains(".so.") {
+            SizeCategory::SharedLibraries
+        } else if extension == "py" || extension == "pyi" {
+            SizeCategory::Sources
+        } else {
+            SizeCategory::Data
+        }
+    }
+
+    /// Get the display name for this category
+    pub fn display_name(self) -> &'static str {
+        match self {
+            SizeCategory::Bytecode => "Bytecode (.pyc)",
+            SizeCategory::SharedLibraries => "Shared libraries",
+            SizeCategory::Sources => "Python sources",
+            SizeCategory::Data => "Data & other",
+        }
+    }
+}
+
+/// Bytes per content category, plus the amount a slim pass would reclaim
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SizeBreakdown {
+    /// Bytes of compiled bytecode
+    bytecode: u64,
+    /// Bytes of native shared libraries
+    shared_libraries: u64,
+    /// Bytes of Python sources
+    sources: u64,
+    /// Bytes of everything else
+    data: u64,
+    /// Bytes inside `__pycache__` and bundled test directories
+    slimmable: u64,
+}
+
+impl SizeBreakdown {
+    /// Measure the breakdown of a .venv directory
+    ///
+    /// # Arguments
+    /// * `venv_path` - Path to the .venv directory
+    ///
+    /// # Returns
+    /// The breakdown; unreadable entries are skipped
+    pub fn for_venv(venv_path: &Path) -> Self {
+        let mut breakdown = Self::default();
+        let slim_roots = slim::slim_candidates(venv_path);
+
+        for entry in WalkDir::new(venv_path).follow_links(false) {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    warn!("Skipping unreadable entry in {}: {}", venv_path.display(), e);
+                    continue;
+                }
+            };
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            breakdown.add(SizeCategory::of(entry.path()), size);
+            if slim_roots.iter().any(|root| entry.path().starts_with(root)) {
+                breakdown.slimmable += size;
+            }
+        }
+
+        breakdown
+    }
+
+    /// Add bytes to a category
+    pub fn add(&mut self, category: SizeCategory, bytes: u64) {
+        let bucket = match category {
+            SizeCategory::Bytecode => &mut self.bytecode,
+            SizeCategory::SharedLibraries => &mut self.shared_libraries,
+            SizeCategory::Sources => &mut self.sources,
+            SizeCategory::Data => &mut self.data,
+        };
+        *bucket = bucket.saturating_add(bytes);
+    }
+
+    /// Combine two breakdowns (e.g. to total several .venv directories)
+    pub fn merge(&mut self, other: &SizeBreakdown) {
+        for category in SizeCategory::ALL {
+            self.add(category, other.bytes(category));
+        }
+        self.slimmable = self.slimmable.saturating_add(other.slimmable);
+    }
+
+    /// Get the bytes in a category
+    pub fn bytes(&self, category: SizeCategory) -> u64 {
+        match category {
+            SizeCategory::Bytecode => self.bytecode,
+            SizeCategory::SharedLibraries => self.shared_libraries,
+            SizeCategory::Sources => self.sources,
+            SizeCategory::Data => self.data,
+        }
+    }
+
+    /// Get the total size in bytes over all categories
+    pub fn total(&self) -> u64 {
+        SizeCategory::ALL.iter().map(|c| self.bytes(*c)).sum()
+    }
+
+    /// Get the bytes a slim pass would reclaim
+    pub fn slimmable(&self) -> u64 {
+        self.slimmable
+    }
+
+    /// Get the share of a category in percent of the total
+    pub fn percent(&self, category: SizeCategory) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            total => self.bytes(category) as f64 * 100.0 / total as f64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_category_of() {
+        assert_eq!(SizeCategory::of(Path::new("pkg/__pycache__/mod.cpython-311.pyc")), SizeCategory::Bytecode);
+        assert_eq!(SizeCategory::of(Path::new("pkg/_speedups.cpython-311-x86_64-linux-gnu.so")), SizeCategory::SharedLibraries);
+        assert_eq!(SizeCategory::of(Path::new("numpy.libs/libopenblas.so.0")), SizeCategory::SharedLibraries);
+        assert_eq!(SizeCategory::of(Path::new("pkg/core.py")), SizeCategory::Sources);
+        assert_eq!(SizeCategory::of(Path::new("pkg/data/model.bin")), SizeCategory::Data);
+    }
+
+    #[test]
+    fn test_breakdown_for_venv() {
+        let temp_dir = TempDir::new().unwrap();
+        let pkg = temp_dir.path().join("lib").join("python3.11").join("site-packages").join("pkg");
+        fs::create_dir_all(pkg.join("__pycache__")).unwrap();
+        fs::create_dir_all(pkg.join("tests")).unwrap();
+        fs::write(pkg.join("__init__.py"), vec![0u8; 100]).unwrap();
+        fs::write(pkg.join("__pycache__").join("__init__.cpython-311.pyc"), vec![0u8; 50]).unwrap();
+        fs::write(pkg.join("_native.so"), vec![0u8; 400]).unwrap();
+        fs::write(pkg.join("tests").join("test_pkg.py"), vec![0u8; 30]).unwrap();
+        fs::write(temp_dir.path().join("pyvenv.cfg"), vec![0u8; 20]).unwrap();
+
+        let breakdown = SizeBreakdown::for_venv(temp_dir.path());
+        assert_eq!(breakdown.bytes(SizeCategory::Bytecode), 50);
+        assert_eq!(breakdown.bytes(SizeCategory::SharedLibraries), 400);
+        assert_eq!(breakdown.bytes(SizeCategory::Sources), 130);
+        assert_eq!(breakdown.bytes(SizeCategory::Data), 20);
+        assert_eq!(breakdown.total(), 600);
+        assert_eq!(breakdown.slimmable(), 80);
+    }
+
+    #[test]
+    fn test_merge_and_percent() {
+        let mut a = SizeBreakdown::default();
+        a.add(SizeCategory::Bytecode, 25);
+        let mut b = SizeBreakdown::default();
+        b.add(SizeCategory::Data, 75);
+        a.merge(&b);
+
+        assert_eq!(a.total(), 100);
+        assert_eq!(a.percent(SizeCategory::Bytecode), 25.0);
+        assert_eq!(SizeBreakdown::default().percent(SizeCategory::Data), 0.0);
+    }
+}
@@ -0,0 +1,224 @@
+//! Sizes in bytes, parsed and shown one way everywhere
+//!
+//! `ByteSize` wraps a byte count so sizes read from the command line and the
+//! config (`--min-size 200MB`, `max_force_delete_size = "5GB"`) and sizes
+//! shown in listings, reports and dialogs go through the same parsing and
+//! formatting. Units are binary: `1K` is 1024 bytes, matching how sizes are
+//! shown (`1.50 MB`). In config files and JSON a size is written either as
+//! a string with a unit or as a plain number of bytes; it is always
+//! serialized as the number, so reports keep their byte counts.
+
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Sub};
+use std::str::FromStr;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{Result, VenvCleanerError};
+
+/// A size in bytes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteSize(u64);
+
+impl ByteSize {
+    /// One kibibyte
+    pub const KB: u64 = 1024;
+    /// One mebibyte
+    pub const MB: u64 = Self::KB * 1024;
+    /// One gibibyte
+    pub const GB: u64 = Self::MB * 1024;
+    /// One tebibyte
+    pub const TB: u64 = Self::GB * 1024;
+
+    /// Create a size from a byte count
+    pub const fn new(bytes: u64) -> Self {
+        Self(bytes)
+    }
+
+    /// Get the size in bytes
+    pub const fn bytes(self) -> u64 {
+        self.0
+    }
+
+    /// Parse a human-readable size such as `500`, `10K`, `1.5MB` or `2GiB`
+    ///
+    /// # Arguments
+    /// * `text` - The size string to parse
+    ///
+    /// # Returns
+    /// The size, or an InvalidArgument error
+    pub fn parse(text: &str) -> Result<Self> {
+        let trimmed = text.trim();
+        let split = trimmed
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(trimmed.len());
+        let (number, unit) = trimmed.split_at(split);
+
+        let value: f64 = number
+            .parse()
+            .map_err(|_| VenvCleanerError::InvalidArgument(format!("Invalid size: {}", text)))?;
+
+        let multiplier = match unit.trim().to_uppercase().as_str() {
+            "" | "B" => 1,
+            "K" | "KB" | "KIB" => Self::KB,
+            "M" | "MB" | "MIB" => Self::MB,
+            "G" | "GB" | "GIB" => Self::GB,
+            "T" | "TB" | "TIB" => Self::TB,
+            _ => {
+                return Err(VenvCleanerError::InvalidArgument(format!("Unknown size unit in: {}", text)));
+            }
+        };
+
+        Ok(Self((value * multiplier as f64) as u64))
+    }
+
+    /// Format the size short enough for a shell prompt, e.g. `52.0GB`
+    pub fn compact(self) -> String {
+        let bytes = self.0;
+        if bytes >= Self::GB {
+            format!("{:.1}GB", bytes as f64 / Self::GB as f64)
+        } else if bytes >= Self::MB {
+            format!("{:.1}MB", bytes as f64 / Self::MB as f64)
+        } else if bytes >= Self::KB {
+            format!("{:.1}KB", bytes as f64 / Self::KB as f64)
+        } else {
+            format!("{}B", bytes)
+        }
+    }
+}
+
+/// Shown as KB, MB or GB with two decimals, or as plain bytes below 1 KB
+impl fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes = self.0;
+        if bytes >= Self::GB {
+            write!(f, "{:.2} GB", bytes as f64 / Self::GB as f64)
+        } else if bytes >= Self::MB {
+            write!(f, "{:.2} MB", bytes as f64 / Self::MB as f64)
+        } else if bytes >= Self::KB {
+            write!(f, "{:.2} KB", bytes as f64 / Self::KB as f64)
+        } else {
+            write!(f, "{} bytes", bytes)
+        }
+    }
+}
+
+impl FromStr for ByteSize {
+    type Err = VenvCleanerError;
+
+    fn from_str(text: &str) -> Result<Self> {
+        Self::parse(text)
+    }
+}
+
+impl From<u64> for ByteSize {
+    fn from(bytes: u64) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<ByteSize> for u64 {
+    fn from(size: ByteSize) -> Self {
+        size.0
+    }
+}
+
+impl Add for ByteSize {
+    type Output = ByteSize;
+
+    fn add(self, other: ByteSize) -> ByteSize {
+        ByteSize(self.0.saturating_add(other.0))
+    }
+}
+
+impl AddAssign for ByteSize {
+    fn add_assign(&mut self, other: ByteSize) {
+        *self = *self + other;
+    }
+}
+
+/// Saturates at zero, as sizes cannot be negative
+impl Sub for ByteSize {
+    type Output = ByteSize;
+
+    fn sub(self, other: ByteSize) -> ByteSize {
+        ByteSize(self.0.saturating_sub(other.0))
+    }
+}
+
+impl Sum for ByteSize {
+    fn sum<I: Iterator<Item = ByteSize>>(iter: I) -> Self {
+        iter.fold(ByteSize::default(), Add::add)
+    }
+}
+
+impl Serialize for ByteSize {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteSize {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        deserializer.deserialize_any(ByteSizeVisitor)
+    }
+}
+
+/// Reads a size written as a number of bytes or as a string with a unit
+struct ByteSizeVisitor;
+
+impl de::Visitor<'_> for ByteSizeVisitor {
+    type Value = ByteSize;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a size such as \"200MB\" or a number of bytes")
+    }
+
+    fn visit_u64<E: de::Error>(self, bytes: u64) -> std::result::Result<ByteSize, E> {
+        Ok(ByteSize(bytes))
+    }
+
+    fn visit_i64<E: de::Error>(self, bytes: i64) -> std::result::Result<ByteSize, E> {
+        u64::try_from(bytes)
+            .map(ByteSize)
+            .map_err(|_| E::custom(format!("Invalid size: {}", bytes)))
+    }
+
+    fn visit_str<E: de::Error>(self, text: &str) -> std::result::Result<ByteSize, E> {
+        ByteSize::parse(text).map_err(|e| E::custom(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_format() {
+        assert_eq!(ByteSize::parse("500").unwrap(), ByteSize::new(500));
+        assert_eq!("1.5MB".parse::<ByteSize>().unwrap().bytes(), ByteSize::MB * 3 / 2);
+        assert_eq!(ByteSize::parse("2 GiB").unwrap(), ByteSize::new(2 * ByteSize::GB));
+        assert!(ByteSize::parse("10X").is_err());
+
+        assert_eq!(ByteSize::new(512).to_string(), "512 bytes");
+        assert_eq!(ByteSize::new(1536).to_string(), "1.50 KB");
+        assert_eq!(ByteSize::new(5 * ByteSize::GB).to_string(), "5.00 GB");
+        assert!(ByteSize::parse("1GB").unwrap() > ByteSize::parse("999MB").unwrap());
+        assert_eq!([ByteSize::new(1), ByteSize::new(2)].into_iter().sum::<ByteSize>(), ByteSize::new(3));
+        assert_eq!(ByteSize::new(1) - ByteSize::new(2), ByteSize::default());
+    }
+
+    #[test]
+    fn test_serde() {
+        #[derive(Deserialize)]
+        struct Limits {
+            text: ByteSize,
+            number: ByteSize,
+        }
+
+        let limits: Limits = toml::from_str("text = \"5GB\"\nnumber = 1024\n").unwrap();
+        assert_eq!((limits.text.bytes(), limits.number.bytes()), (5 * ByteSize::GB, 1024));
+        assert!(toml::from_str::<Limits>("text = \"huge\"\nnumber = 1\n").is_err());
+        assert_eq!(serde_json::to_string(&ByteSize::new(2048)).unwrap(), "2048");
+    }
+}
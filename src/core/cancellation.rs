@@ -0,0 +1,72 @@
+//! Stopping a scan or batch deletion part way through
+//!
+//! A `CancellationToken` is shared between the thread running a long
+//! operation and whoever may want to stop it, such as the GUI's Cancel
+//! button. Scans check it between directories and stop walking, keeping the
+//! .venv directories found so far; batch deletions finish the directory
+//! being removed and fail the rest with `OperationCancelled`, so nothing is
+//! left half deleted.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A flag shared by every clone, set once the operation should stop
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    /// Whether cancellation was requested
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Create a token that is not cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask every holder of the token to stop
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Check whether cancellation was requested
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ByteSize, VenvCleaner, VenvCleanerError, VenvInfo};
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_clones_share_cancellation() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!clone.is_cancelled());
+        token.cancel();
+        assert!(clone.is_cancelled());
+    }
+
+    #[test]
+    fn test_cancelled_scan_and_deletion_stop() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = temp_dir.path().join("project").join(".venv");
+        fs::create_dir_all(venv.join("bin")).unwrap();
+        fs::write(venv.join("pyvenv.cfg"), "home = /usr/bin\n").unwrap();
+
+        let token = CancellationToken::new();
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, true, false, 0)
+            .with_cancellation(token.clone());
+        assert_eq!(cleaner.find_venv_directories().unwrap().len(), 1);
+
+        token.cancel();
+        assert!(matches!(cleaner.find_venv_directories(), Err(VenvCleanerError::NoVenvFound)));
+        let venv_info = VenvInfo::new(venv.clone(), ByteSize::new(0), chrono::Utc::now(), chrono::Utc::now());
+        let results = cleaner.delete_venv_directories(&[venv_info], |_, _| {});
+        assert!(matches!(results[0], Err(VenvCleanerError::OperationCancelled)));
+        assert!(venv.exists());
+    }
+}
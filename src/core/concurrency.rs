@@ -0,0 +1,147 @@
+//! Parallelism limits for scanning and deleting
+//!
+//! Sizing .venv directories and deleting them are both dominated by file
+//! system calls, so a few threads speed them up considerably on local disks.
+//! On network mounts the same parallelism mostly hammers the server, so the
+//! defaults drop there. Both limits can be overridden with `--scan-threads`
+//! and `--delete-jobs`.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Upper bound of the default number of scan threads
+pub const MAX_DEFAULT_SCAN_THREADS: usize = 8;
+
+/// Upper bound of the default number of parallel deletions
+pub const MAX_DEFAULT_DELETE_JOBS: usize = 4;
+
+/// Scan threads used by default on network mounts
+pub const NETWORK_SCAN_THREADS: usize = 2;
+
+/// Parallel deletions used by default on network mounts
+pub const NETWORK_DELETE_JOBS: usize = 1;
+
+/// Get the number of CPUs available to this process
+pub fn available_cpus() -> usize {
+    thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+}
+
+/// Get the default number of threads sizing .venv directories
+///
+/// # Arguments
+/// * `network` - Whether the scanned directory is on a network mount
+pub fn default_scan_threads(network: bool) -> usize {
+    if network {
+        NETWORK_SCAN_THREADS
+    } else {
+        available_cpus().clamp(1, MAX_DEFAULT_SCAN_THREADS)
+    }
+}
+
+/// Get the default number of .venv directories deleted at once
+///
+/// # Arguments
+/// * `network` - Whether the scanned directory is on a network mount
+pub fn default_delete_jobs(network: bool) -> usize {
+    if network {
+        NETWORK_DELETE_JOBS
+    } else {
+        available_cpus().clamp(1, MAX_DEFAULT_DELETE_JOBS)
+    }
+}
+
+/// Run `work` on every item using up to `jobs` threads
+///
+/// `on_done` is called on the calling thread as each item finishes, so
+/// callers can report progress without synchronising.
+///
+/// # Arguments
+/// * `items` - The items to process
+/// * `jobs` - Maximum number of threads (1 runs everything on the calling thread)
+/// * `work` - The work to do per item
+/// * `on_done` - Called with each item's index and result as it completes
+///
+/// # Returns
+/// The results in the order of `items`
+pub fn run_parallel<T, R, W, D>(items: &[T], jobs: usize, work: W, mut on_done: D) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    W: Fn(&T) -> R + Sync,
+    D: FnMut(usize, &R),
+{
+    let jobs = jobs.clamp(1, items.len().max(1));
+    if jobs == 1 {
+        return items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let result = work(item);
+                on_done(index, &result);
+                result
+            })
+            .collect();
+    }
+
+    let mut results: Vec<Option<R>> = items.iter().map(|_| None).collect();
+    let next = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        let (done_tx, done_rx) = mpsc::channel();
+        for _ in 0..jobs {
+            let done_tx = done_tx.clone();
+            let (next, work) = (&next, &work);
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let Some(item) = items.get(index) else { break };
+                if done_tx.send((index, work(item))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(done_tx);
+
+        for (index, result) in done_rx {
+            on_done(index, &result);
+            results[index] = Some(result);
+        }
+    });
+
+    // Every index below items.len() was claimed by exactly one worker
+    results.into_iter().flatten().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_respect_network_mounts() {
+        assert_eq!(default_scan_threads(true), NETWORK_SCAN_THREADS);
+        assert_eq!(default_delete_jobs(true), NETWORK_DELETE_JOBS);
+        assert!((1..=MAX_DEFAULT_SCAN_THREADS).contains(&default_scan_threads(false)));
+        assert!((1..=MAX_DEFAULT_DELETE_JOBS).contains(&default_delete_jobs(false)));
+    }
+
+    #[test]
+    fn test_run_parallel_keeps_order() {
+        let items: Vec<u64> = (1..=20).collect();
+        let mut reported = Vec::new();
+        let results = run_parallel(&items, 4, |n| n * n, |index, _| reported.push(index));
+
+        assert_eq!(results, items.iter().map(|n| n * n).collect::<Vec<_>>());
+        reported.sort();
+        assert_eq!(reported, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_run_parallel_single_job_and_empty() {
+        let mut order = Vec::new();
+        let results = run_parallel(&["a", "b"], 1, |s| s.to_uppercase(), |index, _| order.push(index));
+        assert_eq!(results, vec!["A", "B"]);
+        assert_eq!(order, vec![0, 1]);
+
+        let empty: Vec<u8> = Vec::new();
+        assert!(run_parallel(&empty, 8, |n| *n, |_, _| {}).is_empty());
+    }
+}
@@ -0,0 +1,738 @@
+//! User configuration file
+//!
+//! Settings that are tedious to repeat on every command line live in a TOML
+//! file at `~/.config/venv_cleaner/config.toml` (or the platform equivalent).
+//! A missing file is the same as an empty one; every key is optional.
+//!
+//! On shared machines administrators can deploy a system-wide file in the
+//! same format at `/etc/venv_cleaner/policy.toml`. The user config is merged
+//! over it, except for the `[policy]` table: protected paths, mandatory trash
+//! mode and the force-delete size limit always take the stricter of the two.
+//! The policy's `locked` list names further keys, such as `"editor"` or
+//! `"scan.skip_hidden"`, that keep the system file's value whatever the user
+//! config says.
+//!
+//! `VENV_CLEANER_ROOTS` and `VENV_CLEANER_EXCLUDES` replace the `[scan]`
+//! roots and excludes of both files, so a shared shell profile can set
+//! defaults for everyone without touching their config files.
+//!
+//! `[profiles.<name>]` tables bundle scan settings and detectors into
+//! presets that `--profile <name>` lays over the rest of the config, so a
+//! team can share "ml" or "web" settings instead of long command lines.
+
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use super::delete_hooks::DeleteHooks;
+use super::delete_mode::DeleteMode;
+use super::detectors::Detector;
+use super::telemetry::TelemetryConfig;
+use super::{paths, timestamps, ByteSize, Result, VenvCleanerError};
+
+/// File name of the configuration file inside the config directory
+pub const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// File name of the system-wide policy file
+pub const POLICY_FILE_NAME: &str = "policy.toml";
+
+/// Environment variable overriding the location of the policy file
+pub const POLICY_PATH_ENV: &str = "VENV_CLEANER_POLICY";
+
+/// Environment variable replacing `[scan] roots`, as a `PATH`-style list
+pub const ROOTS_ENV: &str = "VENV_CLEANER_ROOTS";
+
+/// Environment variable replacing `[scan] excludes`, as a `PATH`-style list
+pub const EXCLUDES_ENV: &str = "VENV_CLEANER_EXCLUDES";
+
+/// Settings read from the configuration file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Command used to open a project in an editor; `{dir}` is replaced by
+    /// the project directory (appended when missing), e.g. `"code {dir}"`
+    pub editor: Option<String>,
+    /// Command deciding keep/delete/ask for each .venv in cleanup mode, e.g.
+    /// `"python3 ~/bin/decide.py"` (needs the `decision-hook` feature)
+    pub decision_hook: Option<String>,
+    /// How dates are shown in the CLI, TUI and GUI, as a strftime pattern
+    /// such as `"%d/%m/%Y"` (default `"%Y-%m-%d"`, ISO-8601)
+    pub date_format: Option<String>,
+    /// Where deleted folders go unless `--trash` or `--permanent` is given:
+    /// `"auto"` (default, the trash when there is one), `"trash"` or `"permanent"`
+    pub delete_mode: Option<String>,
+    /// Whether path sorts ignore case, so `Project3` sorts between
+    /// `project2` and `project10`
+    pub sort_ignore_case: bool,
+    /// Appearance of the TUI (`[tui]` table)
+    pub tui: TuiConfig,
+    /// How directory trees are walked (`[scan]` table)
+    pub scan: ScanConfig,
+    /// Opt-in crash reporting (`[telemetry]` table)
+    pub telemetry: TelemetryConfig,
+    /// Restrictions on what may be deleted (`[policy]` table)
+    pub policy: PolicyConfig,
+    /// Further environment layouts found besides `.venv` (`[[detectors]]` tables)
+    pub detectors: Vec<DetectorConfig>,
+    /// Commands run before and after each deletion (`[hooks]` table)
+    pub hooks: DeleteHooks,
+    /// Named presets selected with `--profile` (`[profiles.<name>]` tables)
+    pub profiles: BTreeMap<String, ScanProfile>,
+}
+
+/// A named preset of scan settings read from a `[profiles.<name>]` table
+///
+/// Every key is optional. Detectors and excludes are added to those of the
+/// config; roots replace them when given, as do the other settings.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScanProfile {
+    /// What the profile is for, shown when an unknown profile is asked for
+    pub description: Option<String>,
+    /// Directories scanned when none is given on the command line (`~/` is expanded)
+    pub roots: Vec<PathBuf>,
+    /// Further directories never scanned (`~/` is expanded)
+    pub excludes: Vec<PathBuf>,
+    /// Whether recursive scans skip hidden directories such as `.cache`
+    pub skip_hidden: Option<bool>,
+    /// Only list .venv directories unused for this long, like `--older-than`
+    pub older_than: Option<String>,
+    /// Only list .venv directories at least this large, like `--min-size`
+    pub min_size: Option<ByteSize>,
+    /// Further environment layouts found besides `.venv` (`[[profiles.<name>.detectors]]` tables)
+    pub detectors: Vec<DetectorConfig>,
+}
+
+/// An environment layout read from a `[[detectors]]` table
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DetectorConfig {
+    /// Name of the layout, shown in logs
+    pub name: String,
+    /// Glob patterns the directory name must match, e.g. `".pyenv-local"`
+    pub patterns: Vec<String>,
+    /// Files or folders that must exist inside, e.g. `"pyvenv.cfg"`
+    pub markers: Vec<String>,
+    /// Glob patterns of relative paths that must each match something inside,
+    /// e.g. `"lib/python*/site-packages"`
+    pub validators: Vec<String>,
+}
+
+/// Deletion restrictions read from the `[policy]` table
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PolicyConfig {
+    /// Directories whose .venv folders must never be deleted (`~/` is expanded)
+    pub protected_paths: Vec<PathBuf>,
+    /// Always move folders to the trash instead of deleting them
+    pub require_trash: bool,
+    /// Largest .venv that may be deleted without a prompt, e.g. `"5GB"`
+    pub max_force_delete_size: Option<ByteSize>,
+    /// Record every deletion in this hash-chained audit log (`~/` is expanded)
+    pub audit_log: Option<PathBuf>,
+    /// Refuse `--force`, so every deletion is confirmed
+    pub disable_force: bool,
+    /// Most .venv directories deleted in one go (a selection, a plan or a force run)
+    pub max_batch_size: Option<usize>,
+    /// Config keys the user config cannot change, as dotted paths, e.g. `"tui.theme"`
+    pub locked: Vec<String>,
+}
+
+impl PolicyConfig {
+    /// Check whether the policy restricts anything
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Find the protected directory a path lies in
+    ///
+    /// # Arguments
+    /// * `path` - The .venv directory about to be deleted
+    ///
+    /// # Returns
+    /// The protected directory containing `path`, if any
+    pub fn protecting(&self, path: &Path) -> Option<PathBuf> {
+        let canonical = fs::canonicalize(path).ok();
+        self.protected_paths
+            .iter()
+            .map(|protected| expand_home(protected))
+            .find(|protected| {
+                path.starts_with(protected)
+                    || canonical.as_deref().map(|c| c.starts_with(protected)).unwrap_or(false)
+            })
+    }
+
+    /// Get the audit log location with `~` expanded
+    pub fn audit_log_path(&self) -> Option<PathBuf> {
+        self.audit_log.as_deref().map(expand_home)
+    }
+
+    /// Check that `--force` may be used
+    ///
+    /// # Arguments
+    /// * `force` - Whether the run was started with `--force`
+    ///
+    /// # Returns
+    /// An error if it was and the policy disables it
+    pub fn check_force(&self, force: bool) -> Result<()> {
+        if force && self.disable_force {
+            return Err(VenvCleanerError::PolicyViolation(
+                "--force is disabled; every deletion has to be confirmed".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check that a number of .venv directories may be deleted in one go
+    ///
+    /// # Arguments
+    /// * `count` - How many are about to be deleted
+    ///
+    /// # Returns
+    /// An error naming the limit if there are more than `max_batch_size`
+    pub fn check_batch_size(&self, count: usize) -> Result<()> {
+        match self.max_batch_size {
+            Some(limit) if count > limit => Err(VenvCleanerError::PolicyViolation(format!(
+                "{} .venv directories selected, but at most {} may be deleted at once",
+                count, limit
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check whether a config key is locked to the system file's value
+    ///
+    /// # Arguments
+    /// * `key` - Dotted path of the key, e.g. `"scan.skip_hidden"`
+    ///
+    /// # Returns
+    /// True if the key, or a table containing it, is locked
+    pub fn is_locked(&self, key: &str) -> bool {
+        self.locked
+            .iter()
+            .any(|locked| key == locked || key.strip_prefix(locked.as_str()).is_some_and(|rest| rest.starts_with('.')))
+    }
+
+    /// Combine two policies, keeping the stricter setting of each
+    ///
+    /// An audit log set by this policy wins over one set by `other`.
+    ///
+    /// # Arguments
+    /// * `other` - The policy to combine with
+    pub fn strictest(mut self, other: &PolicyConfig) -> Self {
+        for path in &other.protected_paths {
+            if !self.protected_paths.contains(path) {
+                self.protected_paths.push(path.clone());
+            }
+        }
+        self.require_trash |= other.require_trash;
+        self.disable_force |= other.disable_force;
+        self.max_batch_size = match (self.max_batch_size, other.max_batch_size) {
+            (Some(mine), Some(theirs)) => Some(mine.min(theirs)),
+            (mine, theirs) => mine.or(theirs),
+        };
+        for key in &other.locked {
+            if !self.locked.contains(key) {
+                self.locked.push(key.clone());
+            }
+        }
+        if self.audit_log.is_none() {
+            self.audit_log = other.audit_log.clone();
+        }
+
+        self.max_force_delete_size = match (self.max_force_delete_size, other.max_force_delete_size) {
+            (Some(mine), Some(theirs)) => Some(mine.min(theirs)),
+            (mine, theirs) => mine.or(theirs),
+        };
+        self
+    }
+}
+
+/// Expand a leading `~` to the home directory
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Scan settings read from the `[scan]` table
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScanConfig {
+    /// Whether recursive scans skip hidden directories such as `.cache` (default false)
+    pub skip_hidden: Option<bool>,
+    /// Directories scanned when none is given on the command line (`~/` is expanded)
+    pub roots: Vec<PathBuf>,
+    /// Directories never scanned, with everything below them (`~/` is expanded)
+    pub excludes: Vec<PathBuf>,
+    /// Only list .venv directories unused for this long, e.g. `"90d"` (`--older-than` wins)
+    pub older_than: Option<String>,
+    /// Only list .venv directories at least this large, e.g. `"200MB"` (`--min-size` wins)
+    pub min_size: Option<ByteSize>,
+    /// Whether scans also look for abandoned environments in the temp directories, like `--scan-temp`
+    pub scan_temp: Option<bool>,
+}
+
+impl ScanConfig {
+    /// Get the configured roots with `~` expanded
+    pub fn root_paths(&self) -> Vec<PathBuf> {
+        self.roots.iter().map(|root| expand_home(root)).collect()
+    }
+
+    /// Get the configured excludes with `~` expanded
+    pub fn exclude_paths(&self) -> Vec<PathBuf> {
+        self.excludes.iter().map(|exclude| expand_home(exclude)).collect()
+    }
+
+    /// Replace the roots and excludes with those set in the environment
+    ///
+    /// Each variable holds a list separated like `PATH` (`:` on Unix, `;`
+    /// on Windows); a variable set to an empty string clears the list.
+    ///
+    /// # Arguments
+    /// * `roots` - The value of `VENV_CLEANER_ROOTS`, if set
+    /// * `excludes` - The value of `VENV_CLEANER_EXCLUDES`, if set
+    pub fn with_env_overrides(mut self, roots: Option<&OsStr>, excludes: Option<&OsStr>) -> Self {
+        let split = |list: &OsStr| std::env::split_paths(list).filter(|path| !path.as_os_str().is_empty()).collect();
+        if let Some(roots) = roots {
+            debug!("Scan roots set by {}", ROOTS_ENV);
+            self.roots = split(roots);
+        }
+        if let Some(excludes) = excludes {
+            debug!("Scan excludes set by {}", EXCLUDES_ENV);
+            self.excludes = split(excludes);
+        }
+        self
+    }
+}
+
+/// TUI settings read from the `[tui]` table
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TuiConfig {
+    /// Base color theme: `"default"` or `"monochrome"` (for terminals without color)
+    pub theme: Option<String>,
+    /// Individual colors overriding the base theme (`[tui.colors]` table)
+    pub colors: ColorOverrides,
+    /// Whether to show progress in the terminal title and taskbar (default true)
+    pub terminal_progress: Option<bool>,
+    /// Markers of the .venv list: `"auto"` (default), `"emoji"` or `"ascii"`
+    pub glyphs: Option<String>,
+}
+
+/// Color overrides for the TUI
+///
+/// Each value is a color name (`"cyan"`, `"light-red"`, `"reset"`), an
+/// ANSI palette index (`"208"`) or a hex RGB value (`"#ff8800"`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ColorOverrides {
+    /// Borders and headings
+    pub primary: Option<String>,
+    /// Labels and secondary borders
+    pub secondary: Option<String>,
+    /// Positive states (recently used, selected for deletion)
+    pub success: Option<String>,
+    /// Warnings and large sizes
+    pub warning: Option<String>,
+    /// Errors, old environments and very large sizes
+    pub error: Option<String>,
+    /// De-emphasized text
+    pub muted: Option<String>,
+    /// Background of the highlighted row
+    pub selected: Option<String>,
+    /// Highlighted path and the focused pane's border
+    pub highlight: Option<String>,
+}
+
+impl Config {
+    /// Get the default location of the configuration file
+    ///
+    /// # Returns
+    /// `<config dir>/config.toml` (see `paths::config_dir`), or None if the platform has no config directory
+    pub fn default_path() -> Option<PathBuf> {
+        paths::config_dir().map(|dir| dir.join(CONFIG_FILE_NAME))
+    }
+
+    /// Get the location of the system-wide policy file
+    ///
+    /// # Returns
+    /// `$VENV_CLEANER_POLICY` if set, else `/etc/venv_cleaner/policy.toml`
+    /// (`%ProgramData%\venv_cleaner\policy.toml` on Windows)
+    pub fn system_path() -> Option<PathBuf> {
+        if let Some(path) = std::env::var_os(POLICY_PATH_ENV) {
+            return Some(PathBuf::from(path));
+        }
+        if cfg!(windows) {
+            std::env::var_os("ProgramData").map(|dir| PathBuf::from(dir).join("venv_cleaner").join(POLICY_FILE_NAME))
+        } else {
+            Some(PathBuf::from("/etc/venv_cleaner").join(POLICY_FILE_NAME))
+        }
+    }
+
+    /// Load the configuration from its default locations
+    ///
+    /// # Returns
+    /// The user config merged over the system policy file (defaults when
+    /// there are neither) or an error if either file is invalid
+    pub fn load() -> Result<Self> {
+        let mut config = Self::load_layered(Self::system_path().as_deref(), Self::default_path().as_deref())?;
+        config.scan = config.scan.with_env_overrides(
+            std::env::var_os(ROOTS_ENV).as_deref(),
+            std::env::var_os(EXCLUDES_ENV).as_deref(),
+        );
+        Ok(config)
+    }
+
+    /// Lay a named profile over this configuration
+    ///
+    /// # Arguments
+    /// * `name` - The profile given with `--profile`, None for none
+    ///
+    /// # Returns
+    /// The configuration with the profile applied, or an error naming the
+    /// defined profiles if there is none by that name
+    pub fn with_profile(mut self, name: Option<&str>) -> Result<Self> {
+        let Some(name) = name else {
+            return Ok(self);
+        };
+        let Some(profile) = self.profiles.get(name).cloned() else {
+            let defined: Vec<String> = self
+                .profiles
+                .iter()
+                .map(|(name, profile)| match &profile.description {
+                    Some(description) => format!("{} ({})", name, description),
+                    None => name.clone(),
+                })
+                .collect();
+            return Err(VenvCleanerError::InvalidArgument(if defined.is_empty() {
+                format!("Unknown profile `{}`: no [profiles.<name>] tables are configured", name)
+            } else {
+                format!("Unknown profile `{}`; configured profiles: {}", name, defined.join(", "))
+            }));
+        };
+
+        debug!("Applying scan profile {}", name);
+        if !profile.roots.is_empty() {
+            self.scan.roots = profile.roots;
+        }
+        self.scan.excludes.extend(profile.excludes);
+        self.scan.skip_hidden = profile.skip_hidden.or(self.scan.skip_hidden);
+        self.scan.older_than = profile.older_than.or(self.scan.older_than);
+        self.scan.min_size = profile.min_size.or(self.scan.min_size);
+        self.detectors.extend(profile.detectors);
+        Ok(self)
+    }
+
+    /// Load the configuration from a specific file
+    ///
+    /// # Arguments
+    /// * `path` - Path to the TOML file
+    ///
+    /// # Returns
+    /// The configuration (defaults when the file does not exist) or an error if it is invalid
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let table = Self::read_table(path)?;
+        Self::from_table(table, path)
+    }
+
+    /// Load a user config merged over a system policy file
+    ///
+    /// Keys set in the user file replace those of the system file, tables
+    /// are merged key by key, and the `[policy]` table takes the stricter
+    /// setting of both files. Keys the system file's policy locks are
+    /// dropped from the user file first.
+    ///
+    /// # Arguments
+    /// * `system` - The system-wide policy file, if any
+    /// * `user` - The user's config file, if any
+    pub fn load_layered(system: Option<&Path>, user: Option<&Path>) -> Result<Self> {
+        let (system_table, system_config) = match system {
+            Some(path) => {
+                let table = Self::read_table(path)?;
+                (table.clone(), Self::from_table(table, path)?)
+            }
+            None => (toml::Table::new(), Self::default()),
+        };
+        let (mut user_table, user_config) = match user {
+            Some(path) => {
+                let table = Self::read_table(path)?;
+                (table.clone(), Self::from_table(table, path)?)
+            }
+            None => (toml::Table::new(), Self::default()),
+        };
+        for key in &system_config.policy.locked {
+            if remove_key(&mut user_table, key) {
+                debug!("Ignoring {} in the user config: it is locked by the system policy", key);
+            }
+        }
+
+        let mut merged = system_table;
+        merge_tables(&mut merged, user_table);
+        let mut config = Self::from_table(merged, user.or(system).unwrap_or(Path::new(CONFIG_FILE_NAME)))?;
+        config.policy = system_config.policy.strictest(&user_config.policy);
+        Ok(config)
+    }
+
+    /// Read a TOML file into a table; a missing file gives an empty table
+    fn read_table(path: &Path) -> Result<toml::Table> {
+        if !path.exists() {
+            debug!("No config file at {}", path.display());
+            return Ok(toml::Table::new());
+        }
+
+        let contents = fs::read_to_string(path)?;
+        contents.parse::<toml::Table>().map_err(|e| VenvCleanerError::ConfigError {
+            path: path.display().to_string(),
+            message: e.message().to_string(),
+        })
+    }
+
+    /// Decode and validate a config table read from `path`
+    fn from_table(table: toml::Table, path: &Path) -> Result<Self> {
+        let config_error = |message: String| VenvCleanerError::ConfigError {
+            path: path.display().to_string(),
+            message,
+        };
+
+        let config: Self = toml::Value::Table(table)
+            .try_into()
+            .map_err(|e: toml::de::Error| config_error(e.message().to_string()))?;
+        if let Some(format) = &config.date_format {
+            timestamps::validate_date_format(format).map_err(|e| config_error(e.to_string()))?;
+        }
+        DeleteMode::from_config(config.delete_mode.as_deref()).map_err(|e| config_error(e.to_string()))?;
+        Detector::from_configs(&config.detectors).map_err(|e| config_error(e.to_string()))?;
+        for (name, profile) in &config.profiles {
+            Detector::from_configs(&profile.detectors).map_err(|e| config_error(format!("profile {}: {}", name, e)))?;
+        }
+        Ok(config)
+    }
+}
+
+/// Remove a key given as a dotted path from a table
+///
+/// # Returns
+/// Whether the key was there
+fn remove_key(table: &mut toml::Table, key: &str) -> bool {
+    match key.split_once('.') {
+        Some((outer, rest)) => match table.get_mut(outer) {
+            Some(toml::Value::Table(inner)) => remove_key(inner, rest),
+            _ => false,
+        },
+        None => table.remove(key).is_some(),
+    }
+}
+
+/// Merge `overlay` into `base`, recursing into tables present in both
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(table)) => merge_tables(existing, table),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_missing_config_is_default() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = Config::load_from(&temp_dir.path().join(CONFIG_FILE_NAME)).unwrap();
+        assert_eq!(config, Config::default());
+        assert_eq!(config.editor, None);
+    }
+
+    #[test]
+    fn test_load_editor_template() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE_NAME);
+        fs::write(&path, "editor = \"code --new-window {dir}\"\n").unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(config.editor.as_deref(), Some("code --new-window {dir}"));
+    }
+
+    #[test]
+    fn test_load_tui_theme() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE_NAME);
+        fs::write(&path, "[tui]\ntheme = \"monochrome\"\n\n[tui.colors]\nerror = \"#ff5555\"\n").unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(config.tui.theme.as_deref(), Some("monochrome"));
+        assert_eq!(config.tui.colors.error.as_deref(), Some("#ff5555"));
+        assert_eq!(config.tui.colors.primary, None);
+    }
+
+    #[test]
+    fn test_invalid_config_is_an_error() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE_NAME);
+        fs::write(&path, "editr = \"vim\"\n").unwrap();
+
+        let error = Config::load_from(&path).unwrap_err();
+        assert!(matches!(error, VenvCleanerError::ConfigError { .. }));
+    }
+
+    #[test]
+    fn test_user_config_merged_over_system_policy() {
+        let temp_dir = TempDir::new().unwrap();
+        let system = temp_dir.path().join(POLICY_FILE_NAME);
+        let user = temp_dir.path().join(CONFIG_FILE_NAME);
+        fs::write(&system, "editor = \"vim\"\n\n[tui]\ntheme = \"monochrome\"\nterminal_progress = false\n\n[policy]\nprotected_paths = [\"/srv/shared\"]\nrequire_trash = true\nmax_force_delete_size = \"5GB\"\naudit_log = \"/var/log/venv_cleaner.log\"\n").unwrap();
+        fs::write(&user, "editor = \"code {dir}\"\n\n[tui]\ntheme = \"default\"\n\n[policy]\nrequire_trash = false\nmax_force_delete_size = \"10GB\"\naudit_log = \"/tmp/mine.log\"\n").unwrap();
+
+        let config = Config::load_layered(Some(&system), Some(&user)).unwrap();
+        assert_eq!(config.editor.as_deref(), Some("code {dir}"));
+        assert_eq!(config.tui.theme.as_deref(), Some("default"));
+        assert_eq!(config.tui.terminal_progress, Some(false));
+
+        // The user cannot relax the system policy
+        assert!(config.policy.require_trash);
+        assert_eq!(config.policy.max_force_delete_size, Some(ByteSize::new(5 * ByteSize::GB)));
+        assert_eq!(config.policy.protected_paths, vec![PathBuf::from("/srv/shared")]);
+        assert_eq!(config.policy.audit_log_path(), Some(PathBuf::from("/var/log/venv_cleaner.log")));
+    }
+
+    #[test]
+    fn test_locked_keys_keep_the_system_value() {
+        let temp_dir = TempDir::new().unwrap();
+        let (system, user) = (temp_dir.path().join(POLICY_FILE_NAME), temp_dir.path().join(CONFIG_FILE_NAME));
+        fs::write(&system, "editor = \"vim\"\n\n[scan]\nskip_hidden = true\n\n[policy]\nlocked = [\"editor\", \"scan.skip_hidden\"]\ndisable_force = true\nmax_batch_size = 20\n").unwrap();
+        fs::write(&user, "editor = \"code {dir}\"\n\n[scan]\nskip_hidden = false\nroots = [\"/work\"]\n\n[policy]\nmax_batch_size = 50\n").unwrap();
+
+        let config = Config::load_layered(Some(&system), Some(&user)).unwrap();
+        assert_eq!(config.editor.as_deref(), Some("vim"));
+        assert_eq!(config.scan.skip_hidden, Some(true));
+        // Keys next to a locked one still follow the user config
+        assert_eq!(config.scan.roots, vec![PathBuf::from("/work")]);
+        assert!(config.policy.is_locked("scan.skip_hidden") && !config.policy.is_locked("scan.roots"));
+
+        assert!(config.policy.check_force(false).is_ok());
+        assert!(matches!(config.policy.check_force(true), Err(VenvCleanerError::PolicyViolation(_))));
+        assert_eq!(config.policy.max_batch_size, Some(20));
+        assert!(config.policy.check_batch_size(20).is_ok());
+        assert!(config.policy.check_batch_size(21).is_err());
+    }
+
+    #[test]
+    fn test_policy_protecting() {
+        let policy = PolicyConfig {
+            protected_paths: vec![PathBuf::from("/srv/shared")],
+            ..PolicyConfig::default()
+        };
+        assert_eq!(policy.protecting(Path::new("/srv/shared/tool/.venv")), Some(PathBuf::from("/srv/shared")));
+        assert_eq!(policy.protecting(Path::new("/srv/sharedx/.venv")), None);
+        assert!(PolicyConfig::default().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_policy_size_is_an_error() {
+        let temp_dir = TempDir::new().unwrap();
+        let system = temp_dir.path().join(POLICY_FILE_NAME);
+        fs::write(&system, "[policy]\nmax_force_delete_size = \"huge\"\n").unwrap();
+
+        let error = Config::load_layered(Some(&system), None).unwrap_err();
+        assert!(matches!(error, VenvCleanerError::ConfigError { path, .. } if path.ends_with(POLICY_FILE_NAME)));
+    }
+
+    #[test]
+    fn test_profiles() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE_NAME);
+        fs::write(
+            &path,
+            r#"
+[scan]
+roots = ["~/work"]
+excludes = ["~/work/datasets"]
+
+[profiles.ml]
+description = "conda and pip caches"
+excludes = ["~/work/checkpoints"]
+older_than = "30d"
+min_size = "1GB"
+
+[[profiles.ml.detectors]]
+name = "conda"
+patterns = ["envs"]
+markers = ["conda-meta"]
+
+[profiles.web]
+roots = ["~/sites"]
+skip_hidden = true
+"#,
+        )
+        .unwrap();
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(config.clone().with_profile(None).unwrap(), config);
+
+        let ml = config.clone().with_profile(Some("ml")).unwrap();
+        assert_eq!(ml.scan.roots, vec![PathBuf::from("~/work")]);
+        assert_eq!(ml.scan.excludes, vec![PathBuf::from("~/work/datasets"), PathBuf::from("~/work/checkpoints")]);
+        assert_eq!((ml.scan.older_than.as_deref(), ml.scan.min_size), (Some("30d"), Some(ByteSize::new(ByteSize::GB))));
+        assert_eq!(ml.detectors[0].name, "conda");
+
+        let web = config.clone().with_profile(Some("web")).unwrap();
+        assert_eq!(web.scan.roots, vec![PathBuf::from("~/sites")]);
+        assert_eq!(web.scan.skip_hidden, Some(true));
+        assert!(web.detectors.is_empty());
+
+        let unknown = config.with_profile(Some("data")).unwrap_err().to_string();
+        assert!(unknown.contains("ml (conda and pip caches), web"), "{}", unknown);
+
+        fs::write(&path, "[profiles.big]\nmin_size = \"huge\"\n").unwrap();
+        assert!(matches!(Config::load_from(&path), Err(VenvCleanerError::ConfigError { .. })));
+    }
+
+    #[test]
+    fn test_load_date_format() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE_NAME);
+        fs::write(&path, "date_format = \"%d/%m/%Y\"\n").unwrap();
+        assert_eq!(Config::load_from(&path).unwrap().date_format.as_deref(), Some("%d/%m/%Y"));
+
+        fs::write(&path, "date_format = \"%Q\"\n").unwrap();
+        assert!(matches!(Config::load_from(&path), Err(VenvCleanerError::ConfigError { .. })));
+    }
+
+    #[test]
+    fn test_load_delete_mode() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE_NAME);
+        fs::write(&path, "delete_mode = \"permanent\"\n").unwrap();
+        assert_eq!(Config::load_from(&path).unwrap().delete_mode.as_deref(), Some("permanent"));
+
+        fs::write(&path, "delete_mode = \"shred\"\n").unwrap();
+        assert!(matches!(Config::load_from(&path), Err(VenvCleanerError::ConfigError { .. })));
+    }
+
+    #[test]
+    fn test_env_overrides_replace_scan_lists() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE_NAME);
+        fs::write(&path, "[scan]\nroots = [\"~/work\"]\nexcludes = [\"/data\"]\n").unwrap();
+        let scan = Config::load_from(&path).unwrap().scan;
+        assert_eq!(scan.exclude_paths(), vec![PathBuf::from("/data")]);
+
+        let roots = std::env::join_paths(["/lab/projects", "/lab/shared"]).unwrap();
+        let overridden = scan.clone().with_env_overrides(Some(&roots), None);
+        assert_eq!(overridden.root_paths(), vec![PathBuf::from("/lab/projects"), PathBuf::from("/lab/shared")]);
+        assert_eq!(overridden.excludes, scan.excludes);
+
+        // An empty variable clears the configured list
+        let cleared = scan.with_env_overrides(None, Some(OsStr::new("")));
+        assert!(cleared.excludes.is_empty());
+    }
+}
@@ -0,0 +1,103 @@
+//! Confirming deletions, for frontends and embedders with their own approval flow
+//!
+//! The core decides which questions a deletion needs: every .venv is
+//! confirmed once, and again if it holds user data or is larger than the
+//! size guard allows. How they are answered is up to a
+//! `ConfirmationProvider`: the CLI asks on the terminal, a frontend whose
+//! dialog already covered everything passes `AutoConfirm`, and tooling
+//! embedding the crate can plug in a ticket check or a chat approval. Any
+//! `Fn(&ConfirmationRequest) -> Result<bool>` closure is a provider.
+
+use super::{deletion_eta, ByteSize, ProjectArtifact, Result, VenvInfo};
+
+/// A question asked before deleting
+#[derive(Debug, Clone, Copy)]
+pub enum ConfirmationRequest<'a> {
+    /// Delete this .venv?
+    Delete(&'a VenvInfo),
+    /// The .venv holds user data that cannot be reinstalled; delete it anyway?
+    DeleteUserData(&'a VenvInfo),
+    /// The .venv is larger than the size guard allows, so perhaps a data directory; delete it anyway?
+    DeleteOversized(&'a VenvInfo),
+    /// Also delete this build artifact of the deleted .venv's project?
+    DeleteArtifact {
+        /// The .venv deleted
+        venv: &'a VenvInfo,
+        /// One of the project's build artifacts
+        artifact: &'a ProjectArtifact,
+    },
+}
+
+impl ConfirmationRequest<'_> {
+    /// Get the .venv the question is about
+    pub fn venv(&self) -> &VenvInfo {
+        match self {
+            ConfirmationRequest::Delete(venv)
+            | ConfirmationRequest::DeleteUserData(venv)
+            | ConfirmationRequest::DeleteOversized(venv)
+            | ConfirmationRequest::DeleteArtifact { venv, .. } => venv,
+        }
+    }
+
+    /// Get the question as put to the user
+    pub fn question(&self) -> String {
+        match self {
+            ConfirmationRequest::Delete(venv) => format!(
+                "Delete this .venv directory ({})?",
+                deletion_eta::describe_file_count(deletion_eta::file_count(std::slice::from_ref(*venv)))
+            ),
+            ConfirmationRequest::DeleteUserData(_) => {
+                "The user data above will be lost too and cannot be reinstalled. Really delete it?".to_string()
+            }
+            ConfirmationRequest::DeleteOversized(_) => {
+                "It is far larger than a virtual environment gets. Really delete it?".to_string()
+            }
+            ConfirmationRequest::DeleteArtifact { artifact, .. } => format!(
+                "Also delete {}/ ({})?",
+                artifact.name(),
+                ByteSize::new(artifact.size_bytes())
+            ),
+        }
+    }
+
+    /// Get why the .venv was kept when the answer is no, as recorded in run summaries
+    pub fn declined_reason(&self) -> &'static str {
+        match self {
+            ConfirmationRequest::Delete(_) => "declined at the prompt",
+            ConfirmationRequest::DeleteUserData(_) => "user data kept at the prompt",
+            ConfirmationRequest::DeleteOversized(_) => "kept at the prompt as a possible data directory",
+            ConfirmationRequest::DeleteArtifact { .. } => "build artifact kept at the prompt",
+        }
+    }
+}
+
+/// Answers the questions asked before deleting
+pub trait ConfirmationProvider: Send + Sync {
+    /// Answer a question
+    ///
+    /// # Arguments
+    /// * `request` - The question
+    ///
+    /// # Returns
+    /// Whether to go ahead, or an error that stops the whole run
+    fn confirm(&self, request: &ConfirmationRequest<'_>) -> Result<bool>;
+}
+
+impl<F> ConfirmationProvider for F
+where
+    F: Fn(&ConfirmationRequest<'_>) -> Result<bool> + Send + Sync,
+{
+    fn confirm(&self, request: &ConfirmationRequest<'_>) -> Result<bool> {
+        self(request)
+    }
+}
+
+/// Answers yes to everything, for deletions confirmed before they started
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AutoConfirm;
+
+impl ConfirmationProvider for AutoConfirm {
+    fn confirm(&self, _request: &ConfirmationRequest<'_>) -> Result<bool> {
+        Ok(true)
+    }
+}
@@ -0,0 +1,148 @@
+//! User-provided script deciding what happens to each .venv
+//!
+//! Sites often know things the scanner cannot, such as which projects their
+//! registry still lists as active. The `decision_hook` config key names a
+//! command that is run once per candidate: it receives the .venv's metadata
+//! as one line of JSON on stdin (the `venv_found` event of `--progress json`)
+//! and prints `keep`, `delete` or `ask`. Any interpreter works, e.g.
+//! `decision_hook = "lua ~/bin/decide.lua"` or `"python3 ~/bin/decide.py"`.
+//! Like editor templates, the command is split on whitespace and run without
+//! a shell.
+//!
+//! Running hooks requires the `decision-hook` feature; without it a
+//! configured hook is reported and ignored.
+
+#[cfg(feature = "decision-hook")]
+use std::io::Write;
+#[cfg(feature = "decision-hook")]
+use std::process::Stdio;
+#[cfg(feature = "decision-hook")]
+use tracing::debug;
+
+#[cfg(feature = "decision-hook")]
+use super::delete_hooks::{hook_command, hook_failed};
+#[cfg(feature = "decision-hook")]
+use super::{ProgressEvent, Result, VenvCleanerError, VenvInfo};
+
+/// What the hook decided for one .venv
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookDecision {
+    /// Leave the .venv alone
+    Keep,
+    /// Delete the .venv without asking
+    Delete,
+    /// Ask the user as usual
+    Ask,
+}
+
+impl HookDecision {
+    /// Parse the answer printed by a hook
+    ///
+    /// # Arguments
+    /// * `answer` - The hook's output; its first non-empty line counts
+    pub fn parse(answer: &str) -> Option<Self> {
+        let line = answer.lines().map(str::trim).find(|line| !line.is_empty())?;
+        match line.to_ascii_lowercase().as_str() {
+            "keep" => Some(HookDecision::Keep),
+            "delete" => Some(HookDecision::Delete),
+            "ask" => Some(HookDecision::Ask),
+            _ => None,
+        }
+    }
+}
+
+/// A decision hook command
+#[cfg(feature = "decision-hook")]
+#[derive(Debug, Clone)]
+pub struct DecisionHook {
+    /// The command line, split on whitespace when run
+    command: String,
+}
+
+#[cfg(feature = "decision-hook")]
+impl DecisionHook {
+    /// Create a hook running a command line
+    ///
+    /// # Arguments
+    /// * `command` - The `decision_hook` setting; a leading `~/` on words is expanded
+    ///
+    /// # Returns
+    /// The hook, or an error if the command is empty
+    pub fn new(command: &str) -> Result<Self> {
+        if command.trim().is_empty() {
+            return Err(VenvCleanerError::InvalidArgument("Decision hook command is empty".to_string()));
+        }
+        Ok(Self { command: command.to_string() })
+    }
+
+    /// Ask the hook about one .venv
+    ///
+    /// # Arguments
+    /// * `venv_info` - The candidate .venv
+    ///
+    /// # Returns
+    /// The decision, or an error if the hook failed or printed something else
+    pub fn decide(&self, venv_info: &VenvInfo) -> Result<HookDecision> {
+        let failed = |message: String| hook_failed(&self.command, message);
+
+        let mut child = hook_command(&self.command)?
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| failed(e.to_string()))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            // A hook that exits without reading its input is fine
+            let _ = writeln!(stdin, "{}", ProgressEvent::venv_found(venv_info).to_json_line());
+        }
+        let output = child.wait_with_output().map_err(|e| failed(e.to_string()))?;
+        if !output.status.success() {
+            return Err(failed(format!("exited with {}", output.status)));
+        }
+
+        let answer = String::from_utf8_lossy(&output.stdout);
+        let decision = HookDecision::parse(&answer)
+            .ok_or_else(|| failed(format!("expected keep, delete or ask, got {:?}", answer.trim())))?;
+        debug!("Decision hook chose {:?} for {}", decision, venv_info.path().display());
+        Ok(decision)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_decision() {
+        assert_eq!(HookDecision::parse("\n  Delete \n"), Some(HookDecision::Delete));
+        assert_eq!(HookDecision::parse("keep\nignored"), Some(HookDecision::Keep));
+        assert_eq!(HookDecision::parse("maybe"), None);
+        assert_eq!(HookDecision::parse(""), None);
+    }
+
+    #[cfg(all(feature = "decision-hook", unix))]
+    #[test]
+    fn test_hook_reads_metadata_from_stdin() {
+        use crate::core::ByteSize;
+        use chrono::Utc;
+        use std::os::unix::fs::PermissionsExt;
+        use std::path::PathBuf;
+
+        let venv = || VenvInfo::new(PathBuf::from("/p/keep-me/.venv"), ByteSize::new(10), Utc::now(), Utc::now());
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let script = temp_dir.path().join("decide.sh");
+        std::fs::write(&script, "#!/bin/sh\ngrep -q keep-me && echo keep || echo delete\n").unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let hook = DecisionHook::new(script.to_str().unwrap()).unwrap();
+        assert_eq!(hook.decide(&venv()).unwrap(), HookDecision::Keep);
+
+        // Failing hooks and unexpected answers are errors, never a decision
+        for command in ["false", "echo perhaps"] {
+            let hook = DecisionHook::new(command).unwrap();
+            assert!(matches!(hook.decide(&venv()), Err(VenvCleanerError::HookFailed { .. })));
+        }
+    }
+}
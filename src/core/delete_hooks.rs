@@ -0,0 +1,163 @@
+//! Commands run before and after each deletion
+//!
+//! Teams hook their own backups or notifications into the deletion
+//! lifecycle with the `[hooks]` table:
+//!
+//! ```toml
+//! [hooks]
+//! pre_delete = "~/bin/backup-venv"
+//! post_delete = "curl -fsS -X POST https://chat.example.com/hooks/venvs -d"
+//! ```
+//!
+//! Each command gets the .venv's path as its last argument, and its size in
+//! bytes in `VENV_CLEANER_SIZE_BYTES`. A pre-delete hook exiting non-zero
+//! keeps the .venv and fails its deletion; a failing post-delete hook is only
+//! logged, since the .venv is gone by then. Hooks run for trashing and
+//! archiving too, but not during dry runs. Like editor templates, commands
+//! are split on whitespace and run without a shell. Their output goes to the
+//! log rather than to stdout, which robot mode and `--progress json` own.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use super::{Result, VenvCleanerError, VenvInfo};
+
+/// Environment variable holding the size of the .venv a hook runs for
+pub const SIZE_ENV: &str = "VENV_CLEANER_SIZE_BYTES";
+
+/// Deletion hooks read from the `[hooks]` table
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DeleteHooks {
+    /// Run before each deletion; a non-zero exit keeps the .venv
+    pub pre_delete: Option<String>,
+    /// Run after each deletion
+    pub post_delete: Option<String>,
+}
+
+impl DeleteHooks {
+    /// Run the pre-delete hook, if there is one
+    ///
+    /// # Arguments
+    /// * `venv_info` - The .venv about to be deleted
+    ///
+    /// # Returns
+    /// Ok if there is no hook or it succeeded, or an error keeping the .venv
+    pub fn run_pre_delete(&self, venv_info: &VenvInfo) -> Result<()> {
+        match &self.pre_delete {
+            Some(command) => run(command, venv_info),
+            None => Ok(()),
+        }
+    }
+
+    /// Run the post-delete hook, if there is one, logging any failure
+    ///
+    /// # Arguments
+    /// * `venv_info` - The .venv that was just deleted
+    pub fn run_post_delete(&self, venv_info: &VenvInfo) {
+        if let Some(command) = &self.post_delete {
+            if let Err(e) = run(command, venv_info) {
+                warn!("{}", e);
+            }
+        }
+    }
+}
+
+/// Run one hook for a .venv and wait for it
+fn run(command: &str, venv_info: &VenvInfo) -> Result<()> {
+    debug!("Running hook `{}` for {}", command, venv_info.path().display());
+    let output = hook_command(command)?
+        .arg(venv_info.path())
+        .env(SIZE_ENV, venv_info.size().bytes().to_string())
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| hook_failed(command, e.to_string()))?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    for line in String::from_utf8_lossy(&output.stdout).lines().chain(stderr.lines()) {
+        info!("Hook `{}`: {}", command, line);
+    }
+    if !output.status.success() {
+        let message = match stderr.lines().rfind(|line| !line.trim().is_empty()) {
+            Some(line) => format!("exited with {}: {}", output.status, line.trim()),
+            None => format!("exited with {}", output.status),
+        };
+        return Err(hook_failed(command, message));
+    }
+    Ok(())
+}
+
+/// Build the error for a failed hook
+pub(crate) fn hook_failed(command: &str, message: String) -> VenvCleanerError {
+    VenvCleanerError::HookFailed { command: command.to_string(), message }
+}
+
+/// Build the command for a hook's command line
+///
+/// The line is split on whitespace; a leading `~/` on any word is expanded.
+///
+/// # Arguments
+/// * `command` - The configured command line
+///
+/// # Returns
+/// The command (it is not started), or an error if the line is empty
+pub(crate) fn hook_command(command: &str) -> Result<Command> {
+    let mut words = command.split_whitespace().map(expand_home);
+    let program = words.next().ok_or_else(|| hook_failed(command, "the command is empty".to_string()))?;
+    let mut hook = Command::new(program);
+    hook.args(words);
+    Ok(hook)
+}
+
+/// Expand a leading `~/` in one word of a command line
+fn expand_home(word: &str) -> PathBuf {
+    match (word.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => Path::new(word).to_path_buf(),
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::core::ByteSize;
+    use chrono::Utc;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_pre_delete_hook_gets_path_and_can_veto() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv_path = temp_dir.path().join(".venv");
+        let venv = VenvInfo::new(venv_path.clone(), ByteSize::new(42), Utc::now(), Utc::now());
+
+        // `touch` creates a file named after the .venv path argument
+        let hooks = DeleteHooks { pre_delete: Some("touch".to_string()), post_delete: None };
+        hooks.run_pre_delete(&venv).unwrap();
+        assert!(venv_path.is_file());
+        fs::remove_file(&venv_path).unwrap();
+
+        let hooks = DeleteHooks { pre_delete: Some("false".to_string()), post_delete: None };
+        assert!(matches!(hooks.run_pre_delete(&venv), Err(VenvCleanerError::HookFailed { .. })));
+    }
+
+    #[test]
+    fn test_hook_output_is_captured() {
+        let venv = VenvInfo::new(PathBuf::from("/nonexistent/.venv"), ByteSize::new(0), Utc::now(), Utc::now());
+
+        // `ls` complains about the missing path on stderr, which ends up in the error
+        let hooks = DeleteHooks { pre_delete: Some("ls".to_string()), post_delete: None };
+        let error = hooks.run_pre_delete(&venv).unwrap_err().to_string();
+        assert!(error.contains("/nonexistent/.venv"), "{}", error);
+    }
+
+    #[test]
+    fn test_missing_hooks_do_nothing() {
+        let venv = VenvInfo::new(PathBuf::from("/nonexistent/.venv"), ByteSize::new(0), Utc::now(), Utc::now());
+        let hooks = DeleteHooks { pre_delete: None, post_delete: Some("false".to_string()) };
+        assert!(DeleteHooks::default().run_pre_delete(&venv).is_ok());
+        // A failing post-delete hook is only logged
+        hooks.run_post_delete(&venv);
+    }
+}
@@ -0,0 +1,124 @@
+//! Whether deleted .venv folders go to the trash or are removed for good
+//!
+//! Deleting is hard to undo, so by default the CLI, TUI and GUI move
+//! folders to the system trash whenever there is one for the scanned
+//! directory, and only delete permanently with `--permanent` (or when no
+//! trash is available, which every frontend then says plainly). The
+//! `delete_mode` config key changes the default; `--trash` insists on the
+//! trash even where it is unlikely to work, such as a network share.
+
+use std::fmt;
+
+use super::{FilesystemInfo, Result, VenvCleanerError};
+
+/// How deleted .venv directories are removed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DeleteMode {
+    /// Move them to the trash if there is one, otherwise delete them permanently
+    #[default]
+    TrashIfAvailable,
+    /// Always move them to the trash (`--trash`)
+    Trash,
+    /// Delete them permanently (`--permanent`)
+    Permanent,
+}
+
+impl DeleteMode {
+    /// Names accepted by the `delete_mode` config key
+    pub const NAMES: [&'static str; 3] = ["auto", "trash", "permanent"];
+
+    /// Parse a mode from its config name
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "auto" => Some(DeleteMode::TrashIfAvailable),
+            "trash" => Some(DeleteMode::Trash),
+            "permanent" => Some(DeleteMode::Permanent),
+            _ => None,
+        }
+    }
+
+    /// Read the mode from the `delete_mode` config key
+    ///
+    /// # Arguments
+    /// * `name` - The configured name, if any
+    ///
+    /// # Returns
+    /// The mode (`TrashIfAvailable` when unset), or an error naming the accepted values
+    pub fn from_config(name: Option<&str>) -> Result<Self> {
+        match name {
+            None => Ok(Self::default()),
+            Some(name) => Self::from_name(name).ok_or_else(|| {
+                VenvCleanerError::InvalidArgument(format!(
+                    "Unknown delete_mode '{}' (expected one of: {})",
+                    name,
+                    Self::NAMES.join(", ")
+                ))
+            }),
+        }
+    }
+
+    /// Get the config name of the mode
+    pub fn name(self) -> &'static str {
+        match self {
+            DeleteMode::TrashIfAvailable => "auto",
+            DeleteMode::Trash => "trash",
+            DeleteMode::Permanent => "permanent",
+        }
+    }
+
+    /// Decide whether deletions in this mode use the trash
+    ///
+    /// # Arguments
+    /// * `filesystem` - The file system of the scanned directory, if known
+    pub fn uses_trash(self, filesystem: Option<&FilesystemInfo>) -> bool {
+        match self {
+            DeleteMode::Trash => true,
+            DeleteMode::Permanent => false,
+            DeleteMode::TrashIfAvailable => {
+                has_system_trash() && filesystem.is_none_or(FilesystemInfo::supports_trash)
+            }
+        }
+    }
+}
+
+impl fmt::Display for DeleteMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Check whether this system has a trash folders can be moved to
+///
+/// macOS and Windows always have one; elsewhere the freedesktop.org trash
+/// lives in the user's data directory, which accounts without a home lack.
+pub fn has_system_trash() -> bool {
+    if cfg!(any(target_os = "macos", target_os = "windows")) {
+        true
+    } else if cfg!(all(unix, not(target_os = "android"), not(target_os = "ios"))) {
+        dirs::data_local_dir().is_some()
+    } else {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delete_mode_from_config() {
+        assert_eq!(DeleteMode::from_config(None).unwrap(), DeleteMode::TrashIfAvailable);
+        assert_eq!(DeleteMode::from_config(Some("permanent")).unwrap(), DeleteMode::Permanent);
+        for name in DeleteMode::NAMES {
+            assert_eq!(DeleteMode::from_name(name).unwrap().name(), name);
+        }
+        assert!(DeleteMode::from_config(Some("shred")).is_err());
+    }
+
+    #[test]
+    fn test_uses_trash() {
+        assert!(DeleteMode::Trash.uses_trash(None));
+        assert!(!DeleteMode::Permanent.uses_trash(None));
+        assert_eq!(DeleteMode::TrashIfAvailable.uses_trash(None), has_system_trash());
+    }
+}
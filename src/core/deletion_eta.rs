@@ -0,0 +1,218 @@
+//! Time estimates for batch deletions
+//!
+//! Deleting a .venv costs roughly the same for every file and directory in
+//! it, whatever their sizes, so a batch is estimated by entry counts rather
+//! than bytes. The estimator takes the entry counts of every .venv up front
+//! (from the scan, or `FileUtils::count_items`), measures how many entries per second
+//! have been removed so far and extrapolates to what is left. Until the
+//! first .venv is done there is no measurement, and no estimate.
+//!
+//! For the same reason confirmation prompts state how many files a deletion
+//! removes ("contains ~48,200 files") next to how much space it frees.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use super::{FileUtils, VenvInfo};
+
+/// Count the files and directories a deletion has to remove
+///
+/// # Arguments
+/// * `path` - The directory to delete
+///
+/// # Returns
+/// The number of entries, including the directory itself (0 if it cannot be read)
+pub fn entry_count(path: &Path) -> u64 {
+    FileUtils::count_items(path)
+        .map(|(files, dirs)| (files + dirs + 1) as u64)
+        .unwrap_or(0)
+}
+
+/// Count the files in the .venv directories about to be deleted
+///
+/// The counts from the scan (`--file-count`) are used where there are any;
+/// the other directories are counted now.
+///
+/// # Arguments
+/// * `venvs` - The .venv directories to be deleted
+pub fn file_count(venvs: &[VenvInfo]) -> u64 {
+    venvs
+        .iter()
+        .map(|venv_info| {
+            venv_info
+                .file_count()
+                .or_else(|| FileUtils::count_items(venv_info.path()).ok().map(|(files, _)| files))
+                .unwrap_or(0) as u64
+        })
+        .sum()
+}
+
+/// Describe a file count for a confirmation, such as `contains ~48,200 files`
+///
+/// Counts from 1,000 up are rounded to three significant digits, since the
+/// files may change before the deletion and only the magnitude matters.
+///
+/// # Arguments
+/// * `files` - The number of files
+pub fn describe_file_count(files: u64) -> String {
+    let mut unit = 1;
+    while files / unit >= 1000 {
+        unit *= 10;
+    }
+    let rounded = (files + unit / 2) / unit * unit;
+    let approximate = if rounded == files { "" } else { "~" };
+    let noun = if files == 1 { "file" } else { "files" };
+    format!("contains {}{} {}", approximate, FileUtils::format_count(rounded), noun)
+}
+
+/// Estimates the remaining time of a batch deletion from measured throughput
+#[derive(Debug, Clone)]
+pub struct DeletionEstimator {
+    /// Entry counts of the .venv directories not finished yet
+    pending: HashMap<PathBuf, u64>,
+    /// Entries of the whole batch
+    total_entries: u64,
+    /// Entries of the .venv directories finished so far
+    done_entries: u64,
+    /// When the batch started
+    started: Instant,
+}
+
+impl DeletionEstimator {
+    /// Start estimating a batch of known entry counts
+    ///
+    /// # Arguments
+    /// * `counts` - Each .venv in the batch with its entry count
+    pub fn from_counts(counts: impl IntoIterator<Item = (PathBuf, u64)>) -> Self {
+        let pending: HashMap<PathBuf, u64> = counts.into_iter().collect();
+        let total_entries = pending.values().sum();
+        Self { pending, total_entries, done_entries: 0, started: Instant::now() }
+    }
+
+    /// Count the entries of a batch and start estimating it
+    ///
+    /// Counts made during the scan are reused; only uncounted .venv
+    /// directories are walked again.
+    ///
+    /// # Arguments
+    /// * `venvs` - The .venv directories about to be deleted
+    pub fn for_venvs(venvs: &[VenvInfo]) -> Self {
+        Self::from_counts(venvs.iter().map(|venv| {
+            let entries = match (venv.file_count(), venv.dir_count()) {
+                (Some(files), Some(dirs)) => (files + dirs + 1) as u64,
+                _ => entry_count(venv.path()),
+            };
+            (venv.path().to_path_buf(), entries)
+        }))
+    }
+
+    /// Record that a .venv was finished (deleted or failed)
+    ///
+    /// # Arguments
+    /// * `venv_path` - Its path; paths outside the batch are ignored
+    pub fn record(&mut self, venv_path: &Path) {
+        if let Some(entries) = self.pending.remove(venv_path) {
+            self.done_entries += entries;
+        }
+    }
+
+    /// Estimate the time left
+    ///
+    /// # Returns
+    /// None until anything has been measured
+    pub fn remaining(&self) -> Option<Duration> {
+        self.remaining_after(self.started.elapsed())
+    }
+
+    /// Estimate the time left after `elapsed` of deleting
+    fn remaining_after(&self, elapsed: Duration) -> Option<Duration> {
+        if self.done_entries == 0 || elapsed.is_zero() {
+            return None;
+        }
+        let per_entry = elapsed.as_secs_f64() / self.done_entries as f64;
+        let left = self.total_entries - self.done_entries;
+        Some(Duration::from_secs_f64(per_entry * left as f64))
+    }
+}
+
+/// Format an estimate for display, such as `about 1m 05s left`
+///
+/// # Arguments
+/// * `remaining` - The estimate, if there is one
+///
+/// # Returns
+/// The formatted estimate, or "estimating..." without one
+pub fn format_eta(remaining: Option<Duration>) -> String {
+    let Some(remaining) = remaining else {
+        return "estimating...".to_string();
+    };
+    let seconds = remaining.as_secs();
+    match seconds {
+        0 => "almost done".to_string(),
+        1..=59 => format!("about {}s left", seconds),
+        60..=3599 => format!("about {}m {:02}s left", seconds / 60, seconds % 60),
+        _ => format!("about {}h {:02}m left", seconds / 3600, (seconds % 3600) / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ByteSize;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_entry_count() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = temp_dir.path().join(".venv");
+        fs::create_dir_all(venv.join("bin")).unwrap();
+        fs::write(venv.join("pyvenv.cfg"), "").unwrap();
+        fs::write(venv.join("bin").join("python"), "").unwrap();
+
+        assert_eq!(entry_count(&venv), 4);
+
+        // Files the scan did not count are counted on demand
+        let now = chrono::Utc::now();
+        let uncounted = VenvInfo::new(venv.clone(), ByteSize::new(0), now, now);
+        let counted = VenvInfo::new(venv, ByteSize::new(0), now, now).with_item_counts(Some((100, 5)));
+        assert_eq!(file_count(&[uncounted, counted]), 102);
+    }
+
+    #[test]
+    fn test_remaining_extrapolates_throughput() {
+        let mut estimator = DeletionEstimator::from_counts([
+            (PathBuf::from("/a/.venv"), 250),
+            (PathBuf::from("/b/.venv"), 750),
+        ]);
+        assert_eq!(estimator.remaining_after(Duration::from_secs(5)), None);
+
+        // 250 entries in 10s leaves 750 entries, 30s at the same pace
+        estimator.record(Path::new("/a/.venv"));
+        estimator.record(Path::new("/a/.venv"));
+        estimator.record(Path::new("/elsewhere/.venv"));
+        assert_eq!(estimator.remaining_after(Duration::from_secs(10)), Some(Duration::from_secs(30)));
+
+        estimator.record(Path::new("/b/.venv"));
+        assert_eq!(estimator.remaining_after(Duration::from_secs(20)), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_describe_file_count() {
+        assert_eq!(describe_file_count(1), "contains 1 file");
+        assert_eq!(describe_file_count(312), "contains 312 files");
+        assert_eq!(describe_file_count(48_213), "contains ~48,200 files");
+        assert_eq!(describe_file_count(48_200), "contains 48,200 files");
+        assert_eq!(describe_file_count(1_996_000), "contains ~2,000,000 files");
+    }
+
+    #[test]
+    fn test_format_eta() {
+        assert_eq!(format_eta(None), "estimating...");
+        assert_eq!(format_eta(Some(Duration::from_millis(400))), "almost done");
+        assert_eq!(format_eta(Some(Duration::from_secs(42))), "about 42s left");
+        assert_eq!(format_eta(Some(Duration::from_secs(65))), "about 1m 05s left");
+        assert_eq!(format_eta(Some(Duration::from_secs(7380))), "about 2h 03m left");
+    }
+}
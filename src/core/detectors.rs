@@ -0,0 +1,156 @@
+//! Environment layouts besides `.venv`, defined in the config file
+//!
+//! In-house tools sometimes create environments under other names, such as
+//! `.pyenv-local/`. A `[[detectors]]` table describes such a layout without
+//! code changes: the directory name has to match one of its `patterns`,
+//! every `markers` entry has to exist inside it and every `validators` glob
+//! has to match at least one path inside it.
+//!
+//! ```toml
+//! [[detectors]]
+//! name = "pyenv-local"
+//! patterns = [".pyenv-local"]
+//! markers = ["pyvenv.cfg"]
+//! validators = ["lib/python*/site-packages"]
+//! ```
+
+use std::path::Path;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use walkdir::WalkDir;
+
+use super::config::DetectorConfig;
+use super::{Result, VenvCleanerError};
+
+/// A compiled `[[detectors]]` table
+#[derive(Debug, Clone)]
+pub struct Detector {
+    /// Name of the layout
+    name: String,
+    /// Patterns for the directory name
+    patterns: GlobSet,
+    /// Relative paths that must exist
+    markers: Vec<String>,
+    /// Relative path patterns that must each match, with the depth they need
+    validators: Vec<(GlobSet, usize)>,
+}
+
+impl Detector {
+    /// Compile a detector from its config table
+    ///
+    /// # Arguments
+    /// * `config` - The `[[detectors]]` table
+    ///
+    /// # Returns
+    /// The detector, or an error if it has no patterns or a glob is invalid
+    pub fn from_config(config: &DetectorConfig) -> Result<Self> {
+        let invalid = |message: String| {
+            VenvCleanerError::InvalidArgument(format!("Detector \"{}\": {}", config.name, message))
+        };
+        if config.patterns.is_empty() {
+            return Err(invalid("needs at least one name pattern".to_string()));
+        }
+
+        let glob_set = |patterns: &[String]| {
+            let mut builder = GlobSetBuilder::new();
+            for pattern in patterns {
+                builder.add(Glob::new(pattern).map_err(|e| invalid(e.to_string()))?);
+            }
+            builder.build().map_err(|e| invalid(e.to_string()))
+        };
+        let validators = config
+            .validators
+            .iter()
+            .map(|validator| Ok((glob_set(std::slice::from_ref(validator))?, Path::new(validator).components().count())))
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            name: config.name.clone(),
+            patterns: glob_set(&config.patterns)?,
+            markers: config.markers.clone(),
+            validators,
+        })
+    }
+
+    /// Compile every detector of the config file
+    ///
+    /// # Arguments
+    /// * `configs` - The `[[detectors]]` tables
+    pub fn from_configs(configs: &[DetectorConfig]) -> Result<Vec<Self>> {
+        configs.iter().map(Self::from_config).collect()
+    }
+
+    /// Get the name of the layout
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Check whether a directory name matches one of the patterns
+    pub fn matches_name(&self, name: &std::ffi::OsStr) -> bool {
+        self.patterns.is_match(name)
+    }
+
+    /// Check whether a directory is an environment of this layout
+    ///
+    /// # Arguments
+    /// * `path` - The directory to check
+    pub fn detects(&self, path: &Path) -> bool {
+        let Some(name) = path.file_name() else { return false };
+        self.matches_name(name)
+            && path.is_dir()
+            && self.markers.iter().all(|marker| path.join(marker).exists())
+            && self.validators.iter().all(|(validator, depth)| {
+                WalkDir::new(path)
+                    .min_depth(1)
+                    .max_depth(*depth)
+                    .into_iter()
+                    .filter_map(|entry| entry.ok())
+                    .any(|entry| entry.path().strip_prefix(path).is_ok_and(|relative| validator.is_match(relative)))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn pyenv_local() -> Detector {
+        Detector::from_config(&DetectorConfig {
+            name: "pyenv-local".to_string(),
+            patterns: vec![".pyenv-local".to_string()],
+            markers: vec!["pyvenv.cfg".to_string()],
+            validators: vec!["lib/python*/site-packages".to_string()],
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_detector_requires_markers_and_validators() {
+        let temp_dir = TempDir::new().unwrap();
+        let env = temp_dir.path().join(".pyenv-local");
+        fs::create_dir_all(&env).unwrap();
+        let detector = pyenv_local();
+        assert!(!detector.detects(&env));
+
+        fs::write(env.join("pyvenv.cfg"), "home = /usr/bin\n").unwrap();
+        assert!(!detector.detects(&env));
+
+        fs::create_dir_all(env.join("lib").join("python3.12").join("site-packages")).unwrap();
+        assert!(detector.detects(&env));
+        assert!(!detector.detects(temp_dir.path()));
+    }
+
+    #[test]
+    fn test_detector_config_errors() {
+        let no_patterns = DetectorConfig { name: "empty".to_string(), ..DetectorConfig::default() };
+        assert!(Detector::from_config(&no_patterns).is_err());
+
+        let bad_glob = DetectorConfig {
+            name: "bad".to_string(),
+            patterns: vec!["[unclosed".to_string()],
+            ..DetectorConfig::default()
+        };
+        assert!(matches!(Detector::from_config(&bad_glob), Err(VenvCleanerError::InvalidArgument(_))));
+    }
+}
@@ -0,0 +1,130 @@
+//! Opening a project in an editor
+//!
+//! Glancing at a project is often enough to tell whether its environment is
+//! still needed. The editor command comes from the `editor` template in the
+//! config file, then `$VISUAL` or `$EDITOR`, and finally VS Code's `code`
+//! launcher if it is on `PATH`. Templates are split on whitespace and run
+//! directly, without a shell, so project paths with spaces stay one argument.
+
+use std::env;
+use std::path::Path;
+use std::process::Command;
+use tracing::debug;
+
+use super::shell::is_on_path;
+use super::{FileUtils, Result, VenvCleanerError};
+
+/// Placeholder replaced by the project directory in editor templates
+pub const DIR_PLACEHOLDER: &str = "{dir}";
+
+/// Editor launcher tried when nothing is configured
+pub const FALLBACK_EDITOR: &str = "code";
+
+/// Pick the editor command template to use
+///
+/// # Arguments
+/// * `configured` - The `editor` template from the config file, if any
+///
+/// # Returns
+/// The first non-empty of the configured template, `$VISUAL`, `$EDITOR` and `code` (if installed)
+pub fn resolve_template(configured: Option<&str>) -> Option<String> {
+    configured
+        .map(str::to_string)
+        .into_iter()
+        .chain(env::var("VISUAL").ok())
+        .chain(env::var("EDITOR").ok())
+        .find(|template| !template.trim().is_empty())
+        .or_else(|| is_on_path(FALLBACK_EDITOR).then(|| FALLBACK_EDITOR.to_string()))
+}
+
+/// Build the command that opens a directory with an editor template
+///
+/// # Arguments
+/// * `template` - Editor command, with `{dir}` where the directory goes
+/// * `project_dir` - Directory to open
+///
+/// # Returns
+/// The command, or an error if the template is empty
+pub fn editor_command(template: &str, project_dir: &Path) -> Result<Command> {
+    let dir = project_dir.display().to_string();
+    let mut words = template.split_whitespace();
+    let program = words
+        .next()
+        .ok_or_else(|| VenvCleanerError::InvalidArgument("Editor command is empty".to_string()))?;
+
+    let mut command = Command::new(program);
+    let mut has_placeholder = false;
+    for word in words {
+        has_placeholder |= word.contains(DIR_PLACEHOLDER);
+        command.arg(word.replace(DIR_PLACEHOLDER, &dir));
+    }
+    if !has_placeholder {
+        command.arg(&dir);
+    }
+
+    command.current_dir(project_dir);
+    Ok(command)
+}
+
+/// Build the command that opens the project owning a .venv in the editor
+///
+/// # Arguments
+/// * `venv_path` - Path to the .venv directory
+/// * `configured` - The `editor` template from the config file, if any
+///
+/// # Returns
+/// The command to run (it is not started)
+pub fn project_editor_command(venv_path: &Path, configured: Option<&str>) -> Result<Command> {
+    FileUtils::ensure_exists(venv_path)?;
+    let template = resolve_template(configured).ok_or_else(|| {
+        VenvCleanerError::FeatureNotAvailable(
+            "No editor configured; set `editor` in the config file or $EDITOR".to_string(),
+        )
+    })?;
+    let project_dir = venv_path.parent().unwrap_or(venv_path);
+
+    let command = editor_command(&template, project_dir)?;
+    debug!("Editor command: {:?}", command);
+    Ok(command)
+}
+
+/// Open the project owning a .venv in the editor without waiting for it
+///
+/// # Arguments
+/// * `venv_path` - Path to the .venv directory
+/// * `configured` - The `editor` template from the config file, if any
+pub fn open_project(venv_path: &Path, configured: Option<&str>) -> Result<()> {
+    project_editor_command(venv_path, configured)?
+        .spawn()
+        .map_err(|e| VenvCleanerError::Io(format!("Failed to open editor: {}", e)))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(command: &Command) -> Vec<String> {
+        command.get_args().map(|a| a.to_string_lossy().to_string()).collect()
+    }
+
+    #[test]
+    fn test_editor_command_placeholder() {
+        let command = editor_command("code --new-window {dir}", Path::new("/home/me/my proj")).unwrap();
+        assert_eq!(command.get_program(), "code");
+        assert_eq!(args(&command), vec!["--new-window", "/home/me/my proj"]);
+    }
+
+    #[test]
+    fn test_editor_command_appends_dir() {
+        let command = editor_command("subl -n", Path::new("/home/me/proj")).unwrap();
+        assert_eq!(command.get_program(), "subl");
+        assert_eq!(args(&command), vec!["-n", "/home/me/proj"]);
+        assert!(editor_command("   ", Path::new("/home/me/proj")).is_err());
+    }
+
+    #[test]
+    fn test_resolve_template_prefers_config() {
+        assert_eq!(resolve_template(Some("zed {dir}")).as_deref(), Some("zed {dir}"));
+    }
+}
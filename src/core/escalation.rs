@@ -0,0 +1,202 @@
+//! Retrying deletions with administrator rights
+//!
+//! Environments created with `sudo pip` or by another user cannot be
+//! removed by an ordinary account. For administrators (members of the
+//! `sudo`, `wheel` or `admin` group) the CLI can retry such a deletion
+//! through `sudo`, or `pkexec` where polkit is used instead, after asking.
+//! This is only ever done with `--allow-escalation`, and only for a path
+//! that is still a real `.venv` directory, or one renamed for deletion
+//! (`.venv.deleting-<ts>`), since the helper runs `rm -rf` as root.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use super::{pending_delete, Result, VenvCleanerError};
+
+/// Groups whose members may run commands as root
+pub const ADMIN_GROUPS: &[&str] = &["sudo", "wheel", "admin"];
+
+/// Program used to run the deletion as root
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscalationHelper {
+    /// `sudo`, asking for the password in the terminal
+    Sudo,
+    /// `pkexec`, asking through the polkit agent
+    Pkexec,
+}
+
+impl EscalationHelper {
+    /// Find a helper on the PATH, preferring `sudo`
+    ///
+    /// # Returns
+    /// The helper, or None if neither is installed
+    pub fn detect() -> Option<Self> {
+        [EscalationHelper::Sudo, EscalationHelper::Pkexec]
+            .into_iter()
+            .find(|helper| is_on_path(helper.program()))
+    }
+
+    /// Get the program name of the helper
+    pub fn program(self) -> &'static str {
+        match self {
+            EscalationHelper::Sudo => "sudo",
+            EscalationHelper::Pkexec => "pkexec",
+        }
+    }
+
+    /// Build the command removing a .venv as root
+    ///
+    /// # Arguments
+    /// * `venv_path` - Path to the .venv directory
+    ///
+    /// # Returns
+    /// The command, or an error if the path is not a real .venv directory
+    pub fn removal_command(self, venv_path: &Path) -> Result<Command> {
+        check_removable(venv_path)?;
+        let mut command = Command::new(self.program());
+        if self == EscalationHelper::Sudo {
+            command.arg("--");
+        }
+        command.args(["rm", "-rf", "--"]).arg(venv_path);
+        Ok(command)
+    }
+
+    /// Remove a .venv as root, waiting for the helper to finish
+    ///
+    /// # Arguments
+    /// * `venv_path` - Path to the .venv directory, or to what a denied deletion left of it
+    ///
+    /// # Returns
+    /// An error if the helper was refused or failed
+    pub fn remove(self, venv_path: &Path) -> Result<()> {
+        let status = self.removal_command(venv_path)?.status().map_err(|e| {
+            VenvCleanerError::Io(format!("Failed to run {}: {}", self.program(), e))
+        })?;
+        if !status.success() {
+            return Err(VenvCleanerError::PermissionDenied {
+                path: format!("{} ({} failed: {})", venv_path.display(), self.program(), status),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Refuse anything but an absolute path to a real (not symlinked) .venv directory, or a renamed one
+fn check_removable(venv_path: &Path) -> Result<()> {
+    let is_venv_dir = fs::symlink_metadata(venv_path).map(|m| m.is_dir()).unwrap_or(false);
+    let is_venv_name = venv_path
+        .file_name()
+        .is_some_and(|name| name == ".venv" || pending_delete::is_marked_name(name));
+    if !venv_path.is_absolute() || !is_venv_name || !is_venv_dir {
+        return Err(VenvCleanerError::InvalidArgument(format!(
+            "{} is not a .venv directory; refusing to remove it as root",
+            venv_path.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Check whether a program is installed on the PATH
+fn is_on_path(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).any(|dir| dir.join(program).is_file()))
+        .unwrap_or(false)
+}
+
+/// Check whether the current user is an administrator who may escalate
+///
+/// Root needs no escalation, so it does not count.
+#[cfg(unix)]
+pub fn is_admin() -> bool {
+    // SAFETY: geteuid has no preconditions
+    if unsafe { libc::geteuid() } == 0 {
+        return false;
+    }
+    group_names().iter().any(|name| ADMIN_GROUPS.contains(&name.as_str()))
+}
+
+#[cfg(not(unix))]
+pub fn is_admin() -> bool {
+    false
+}
+
+/// Get the names of the groups the current user belongs to
+#[cfg(unix)]
+fn group_names() -> Vec<String> {
+    use std::ffi::CStr;
+
+    // SAFETY: a size of 0 only asks for the number of groups
+    let count = unsafe { libc::getgroups(0, std::ptr::null_mut()) };
+    if count <= 0 {
+        return Vec::new();
+    }
+    let mut groups = vec![0 as libc::gid_t; count as usize];
+    // SAFETY: groups has room for count entries
+    let count = unsafe { libc::getgroups(count, groups.as_mut_ptr()) };
+    if count < 0 {
+        return Vec::new();
+    }
+    groups.truncate(count as usize);
+
+    let mut buffer = vec![0 as libc::c_char; 16 * 1024];
+    groups
+        .into_iter()
+        .filter_map(|gid| {
+            // SAFETY: group is plain old data that getgrgid_r fills in
+            let mut group: libc::group = unsafe { std::mem::zeroed() };
+            let mut result: *mut libc::group = std::ptr::null_mut();
+            // SAFETY: every pointer is valid and the buffer length is correct
+            let status = unsafe { libc::getgrgid_r(gid, &mut group, buffer.as_mut_ptr(), buffer.len(), &mut result) };
+            if status != 0 || result.is_null() {
+                return None;
+            }
+            // SAFETY: on success gr_name points to a NUL-terminated string inside buffer
+            Some(unsafe { CStr::from_ptr(group.gr_name) }.to_string_lossy().into_owned())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_removal_command() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = temp_dir.path().join(".venv");
+        fs::create_dir(&venv).unwrap();
+
+        let command = EscalationHelper::Sudo.removal_command(&venv).unwrap();
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(command.get_program(), "sudo");
+        assert_eq!(args, vec!["--".as_ref(), "rm".as_ref(), "-rf".as_ref(), "--".as_ref(), venv.as_os_str()]);
+
+        let command = EscalationHelper::Pkexec.removal_command(&venv).unwrap();
+        assert_eq!(command.get_args().next().unwrap(), "rm");
+
+        // What a denied deletion left after renaming the .venv may be removed too
+        let marked = pending_delete::mark_for_deletion(&venv).unwrap();
+        assert!(EscalationHelper::Sudo.removal_command(&marked).is_ok());
+    }
+
+    #[test]
+    fn test_refuses_paths_that_are_not_venvs() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("project");
+        fs::create_dir(&project).unwrap();
+
+        assert!(EscalationHelper::Sudo.removal_command(&project).is_err());
+        assert!(EscalationHelper::Sudo.removal_command(Path::new(".venv")).is_err());
+        assert!(EscalationHelper::Sudo.removal_command(&project.join(".venv")).is_err());
+
+        #[cfg(unix)]
+        {
+            let link = temp_dir.path().join("other").join(".venv");
+            fs::create_dir(temp_dir.path().join("other")).unwrap();
+            std::os::unix::fs::symlink(&project, &link).unwrap();
+            assert!(EscalationHelper::Sudo.removal_command(&link).is_err());
+        }
+    }
+}
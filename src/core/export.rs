@@ -0,0 +1,468 @@
+//! Writing a list of .venv directories as a report
+//!
+//! Every format is a `Reporter` rendering the same rows: a plain text
+//! table, JSON, CSV, a Markdown table and an HTML page. `--query --output`
+//! prints one to stdout, and the GUI and TUI export exactly the rows they
+//! show, in the order shown, picking the reporter from the file extension,
+//! so a list shared with teammates or loaded into a spreadsheet looks the
+//! same wherever it came from. A new format is one more `ExportFormat` and
+//! `Reporter`.
+//!
+//! Scripts parse these reports, so their layout is fixed: columns and JSON
+//! fields always come in `HEADER` order, rows in the order given, and ages
+//! are counted from when the scan started rather than from when each row is
+//! written. Every report ends in the scan's metadata (see `scan_metadata`),
+//! and JSON reports are an object with the rows under `venvs` and the
+//! metadata under `scan`. The same rows and metadata give byte-identical
+//! output, which the golden files under `tests/golden` check.
+
+use std::fs;
+use std::path::Path;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::report_merge::escape_html;
+use super::{timestamps, Result, ScanMetadata, VenvCleanerError, VenvInfo};
+
+/// Renders a list of .venv directories in one file format
+///
+/// A report is its header, one piece per row and its footer, so rows can be
+/// streamed as a scan finds them (see `--low-memory`) and still give the
+/// same file as rendering the whole list.
+pub trait Reporter {
+    /// Render what comes before the first row
+    fn header(&self) -> String;
+
+    /// Render one row
+    ///
+    /// # Arguments
+    /// * `index` - The row's position, from 0
+    /// * `venv` - The .venv directory to report
+    /// * `as_of` - The report time ages are counted from
+    fn row(&self, index: usize, venv: &VenvInfo, as_of: DateTime<Utc>) -> String;
+
+    /// Render what comes after the last row, ending in the scan's metadata
+    ///
+    /// # Arguments
+    /// * `rows` - The number of rows written
+    /// * `metadata` - The scan the rows came from
+    fn footer(&self, rows: usize, metadata: &ScanMetadata) -> String;
+
+    /// Render the rows, in the order given
+    ///
+    /// # Arguments
+    /// * `venvs` - The .venv directories to report
+    /// * `metadata` - The scan they came from; ages are counted from its start
+    ///
+    /// # Returns
+    /// The report, ending in a line break
+    fn render(&self, venvs: &[&VenvInfo], metadata: &ScanMetadata) -> String {
+        let mut out = self.header();
+        for (index, venv) in venvs.iter().enumerate() {
+            out.push_str(&self.row(index, venv, metadata.started));
+        }
+        out.push_str(&self.footer(venvs.len(), metadata));
+        out
+    }
+}
+
+/// A file format for reports and exported lists
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// A plain text table with aligned columns
+    Table,
+    /// A JSON array of objects
+    Json,
+    /// Comma-separated values with a header row
+    Csv,
+    /// A Markdown table, e.g. for issues and wikis
+    Markdown,
+    /// A standalone HTML page with a table
+    Html,
+}
+
+impl ExportFormat {
+    /// Every format, in the order `--output` lists them
+    pub const ALL: [ExportFormat; 5] = [
+        ExportFormat::Table,
+        ExportFormat::Json,
+        ExportFormat::Csv,
+        ExportFormat::Markdown,
+        ExportFormat::Html,
+    ];
+
+    /// Parse a format name as accepted by `--output`
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|format| format.name() == name)
+    }
+
+    /// Get the name `--output` accepts for the format
+    pub fn name(self) -> &'static str {
+        match self {
+            ExportFormat::Table => "table",
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Markdown => "markdown",
+            ExportFormat::Html => "html",
+        }
+    }
+
+    /// Pick the format from a file's extension
+    ///
+    /// # Arguments
+    /// * `path` - The file to write, ending in `.txt`, `.json`, `.csv`, `.md` or `.html`
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_string_lossy().to_ascii_lowercase();
+        match extension.as_str() {
+            "txt" => Some(ExportFormat::Table),
+            "json" => Some(ExportFormat::Json),
+            "csv" => Some(ExportFormat::Csv),
+            "md" | "markdown" => Some(ExportFormat::Markdown),
+            "html" | "htm" => Some(ExportFormat::Html),
+            _ => None,
+        }
+    }
+
+    /// Get the usual file extension of the format
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Table => "txt",
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+        }
+    }
+
+    /// Get the reporter writing the format
+    pub fn reporter(self) -> &'static dyn Reporter {
+        match self {
+            ExportFormat::Table => &TableReporter,
+            ExportFormat::Json => &JsonReporter,
+            ExportFormat::Csv => &CsvReporter,
+            ExportFormat::Markdown => &MarkdownReporter,
+            ExportFormat::Html => &HtmlReporter,
+        }
+    }
+}
+
+/// One exported row
+///
+/// Fields serialize in declaration order, which must stay the `HEADER` order.
+#[derive(Debug, Serialize)]
+struct ExportRow {
+    path: String,
+    project: String,
+    size_bytes: u64,
+    size: String,
+    last_modified: String,
+    age_days: i64,
+    python_version: Option<String>,
+    file_count: Option<usize>,
+}
+
+impl ExportRow {
+    fn new(venv_info: &VenvInfo, as_of: DateTime<Utc>) -> Self {
+        Self {
+            path: venv_info.path().display().to_string(),
+            project: venv_info.project_name().unwrap_or_default(),
+            size_bytes: venv_info.size().bytes(),
+            size: venv_info.size_formatted(),
+            last_modified: timestamps::to_rfc3339(venv_info.last_modified()),
+            age_days: venv_info.age_in_days_at(as_of),
+            python_version: venv_info.python_version().map(|version| version.to_string()),
+            file_count: venv_info.file_count(),
+        }
+    }
+
+    /// Get the row's values as text, in header order
+    fn fields(&self) -> [String; 8] {
+        [
+            self.path.clone(),
+            self.project.clone(),
+            self.size_bytes.to_string(),
+            self.size.clone(),
+            self.last_modified.clone(),
+            self.age_days.to_string(),
+            self.python_version.clone().unwrap_or_default(),
+            self.file_count.map(|count| count.to_string()).unwrap_or_default(),
+        ]
+    }
+}
+
+/// Column names of every format, matching the JSON field names
+const HEADER: [&str; 8] = ["path", "project", "size_bytes", "size", "last_modified", "age_days", "python_version", "file_count"];
+
+/// Get the rows of a report, with their values in header order
+fn rows(venvs: &[&VenvInfo], as_of: DateTime<Utc>) -> Vec<[String; 8]> {
+    venvs.iter().map(|venv| ExportRow::new(venv, as_of).fields()).collect()
+}
+
+/// Writes a plain text table with aligned columns
+///
+/// Streamed rows cannot know the widest row to come, so their columns are
+/// only padded to the widths of the headings.
+#[derive(Debug, Clone, Copy)]
+pub struct TableReporter;
+
+impl TableReporter {
+    /// Join fields padded to the column widths
+    fn line(fields: &[String], widths: &[usize]) -> String {
+        let padded: Vec<String> = fields.iter().zip(widths).map(|(field, &width)| format!("{:<width$}", field)).collect();
+        padded.join("  ").trim_end().to_string() + "\n"
+    }
+
+    /// Render the headings and their underlines for the column widths
+    fn headings(widths: &[usize]) -> String {
+        Self::line(&HEADER.map(String::from), widths) + &Self::line(&widths.iter().map(|&width| "-".repeat(width)).collect::<Vec<_>>(), widths)
+    }
+}
+
+impl Reporter for TableReporter {
+    fn header(&self) -> String {
+        Self::headings(&HEADER.map(str::len))
+    }
+
+    fn row(&self, _index: usize, venv: &VenvInfo, as_of: DateTime<Utc>) -> String {
+        Self::line(&ExportRow::new(venv, as_of).fields(), &HEADER.map(str::len))
+    }
+
+    fn footer(&self, _rows: usize, metadata: &ScanMetadata) -> String {
+        let lines: String = metadata.fields().iter().map(|(label, value)| format!("{}: {}\n", label, value)).collect();
+        format!("\n{}", lines)
+    }
+
+    fn render(&self, venvs: &[&VenvInfo], metadata: &ScanMetadata) -> String {
+        let rows = rows(venvs, metadata.started);
+        let widths: Vec<usize> = (0..HEADER.len())
+            .map(|column| {
+                rows.iter().map(|row| row[column].chars().count()).chain([HEADER[column].len()]).max().unwrap_or(0)
+            })
+            .collect();
+        let mut out = Self::headings(&widths);
+        for row in &rows {
+            out.push_str(&Self::line(row, &widths));
+        }
+        out.push_str(&self.footer(rows.len(), metadata));
+        out
+    }
+}
+
+/// Writes a JSON object with the rows under `venvs` and the metadata under `scan`
+#[derive(Debug, Clone, Copy)]
+pub struct JsonReporter;
+
+impl JsonReporter {
+    /// Indent every line but the first of a pretty-printed value
+    fn indent(json: &str, indent: &str) -> String {
+        json.lines().collect::<Vec<_>>().join(&format!("\n{}", indent))
+    }
+}
+
+impl Reporter for JsonReporter {
+    fn header(&self) -> String {
+        "{\n  \"venvs\": [\n".to_string()
+    }
+
+    fn row(&self, index: usize, venv: &VenvInfo, as_of: DateTime<Utc>) -> String {
+        // Plain strings and numbers always serialize
+        let object = serde_json::to_string_pretty(&ExportRow::new(venv, as_of)).unwrap_or_default();
+        let separator = if index == 0 { "    " } else { ",\n    " };
+        format!("{}{}", separator, Self::indent(&object, "    "))
+    }
+
+    fn footer(&self, rows: usize, metadata: &ScanMetadata) -> String {
+        let close = if rows == 0 { "  ]" } else { "\n  ]" };
+        format!("{},\n  \"scan\": {}\n}}\n", close, Self::indent(&metadata.to_json_pretty(), "  "))
+    }
+}
+
+/// Writes comma-separated values with a header row
+///
+/// The metadata follows the rows as `# label: value` comment lines.
+#[derive(Debug, Clone, Copy)]
+pub struct CsvReporter;
+
+impl CsvReporter {
+    /// Quote a field when it contains a separator, quote or line break
+    fn field(value: &str) -> String {
+        if value.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    }
+}
+
+impl Reporter for CsvReporter {
+    fn header(&self) -> String {
+        HEADER.join(",") + "\n"
+    }
+
+    fn row(&self, _index: usize, venv: &VenvInfo, as_of: DateTime<Utc>) -> String {
+        let fields: Vec<String> = ExportRow::new(venv, as_of).fields().iter().map(|field| Self::field(field)).collect();
+        fields.join(",") + "\n"
+    }
+
+    fn footer(&self, _rows: usize, metadata: &ScanMetadata) -> String {
+        metadata.fields().iter().map(|(label, value)| format!("# {}: {}\n", label, value)).collect()
+    }
+}
+
+/// Writes a Markdown table
+#[derive(Debug, Clone, Copy)]
+pub struct MarkdownReporter;
+
+impl Reporter for MarkdownReporter {
+    fn header(&self) -> String {
+        format!("| {} |\n|{}\n", HEADER.join(" | "), " --- |".repeat(HEADER.len()))
+    }
+
+    fn row(&self, _index: usize, venv: &VenvInfo, as_of: DateTime<Utc>) -> String {
+        // A | would end the cell early
+        let fields: Vec<String> = ExportRow::new(venv, as_of).fields().iter().map(|field| field.replace('|', "\\|")).collect();
+        format!("| {} |\n", fields.join(" | "))
+    }
+
+    fn footer(&self, _rows: usize, metadata: &ScanMetadata) -> String {
+        let items: String = metadata.fields().iter().map(|(label, value)| format!("- {}: {}\n", label, value)).collect();
+        format!("\n{}", items)
+    }
+}
+
+/// Writes a standalone HTML page with a table
+#[derive(Debug, Clone, Copy)]
+pub struct HtmlReporter;
+
+impl Reporter for HtmlReporter {
+    fn header(&self) -> String {
+        let mut html = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>VenvCleaner report</title>\n\
+             <style>table{border-collapse:collapse}td,th{border:1px solid #ccc;padding:2px 6px}</style>\n</head>\n<body>\n<table>\n<tr>",
+        );
+        for column in HEADER {
+            html.push_str(&format!("<th>{}</th>", column));
+        }
+        html.push_str("</tr>\n");
+        html
+    }
+
+    fn row(&self, _index: usize, venv: &VenvInfo, as_of: DateTime<Utc>) -> String {
+        let cells: String = ExportRow::new(venv, as_of).fields().iter().map(|field| format!("<td>{}</td>", escape_html(field))).collect();
+        format!("<tr>{}</tr>\n", cells)
+    }
+
+    fn footer(&self, _rows: usize, metadata: &ScanMetadata) -> String {
+        let items: String = metadata
+            .fields()
+            .iter()
+            .map(|(label, value)| format!("<dt>{}</dt><dd>{}</dd>\n", label, escape_html(value)))
+            .collect();
+        format!("</table>\n<dl>\n{}</dl>\n</body>\n</html>\n", items)
+    }
+}
+
+/// Render a list of .venv directories
+///
+/// # Arguments
+/// * `venvs` - The rows, in the order to write them
+/// * `format` - The file format
+/// * `metadata` - The scan the rows came from
+///
+/// # Returns
+/// The file contents, the same for the same rows and metadata
+pub fn render(venvs: &[&VenvInfo], format: ExportFormat, metadata: &ScanMetadata) -> String {
+    format.reporter().render(venvs, metadata)
+}
+
+/// Write a list of .venv directories to a file, picking the format from its extension
+///
+/// # Arguments
+/// * `path` - The file to write, ending in the extension of a format
+/// * `venvs` - The rows, in the order to write them
+/// * `metadata` - The scan the rows came from
+///
+/// # Returns
+/// The format written, or an error for an unknown extension or a failed write
+pub fn write_file(path: &Path, venvs: &[&VenvInfo], metadata: &ScanMetadata) -> Result<ExportFormat> {
+    let format = ExportFormat::from_path(path).ok_or_else(|| {
+        VenvCleanerError::InvalidArgument(format!(
+            "{}: exports end in {}",
+            path.display(),
+            ExportFormat::ALL.map(|format| format!(".{}", format.extension())).join(", ")
+        ))
+    })?;
+    fs::write(path, render(venvs, format, metadata))?;
+    Ok(format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ByteSize;
+    use chrono::Utc;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn venv(path: &str) -> VenvInfo {
+        VenvInfo::new(PathBuf::from(path), ByteSize::new(2048), Utc::now(), Utc::now())
+    }
+
+    fn metadata() -> ScanMetadata {
+        ScanMetadata::new(&[Path::new("/p")], true, Utc::now())
+    }
+
+    #[test]
+    fn test_csv_quotes_awkward_paths() {
+        let (plain, awkward) = (venv("/p/app/.venv"), venv("/p/a,\"b\"/.venv"));
+        let csv = render(&[&plain, &awkward], ExportFormat::Csv, &metadata());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], HEADER.join(","));
+        assert!(lines[1].starts_with("/p/app/.venv,app,2048,"));
+        assert!(lines[2].starts_with("\"/p/a,\"\"b\"\"/.venv\","));
+    }
+
+    #[test]
+    fn test_write_file_picks_format_from_extension() {
+        let temp_dir = TempDir::new().unwrap();
+        let app = venv("/p/app/.venv");
+
+        let json = temp_dir.path().join("venvs.JSON");
+        assert_eq!(write_file(&json, &[&app], &metadata()).unwrap(), ExportFormat::Json);
+        let report: serde_json::Value = serde_json::from_str(&fs::read_to_string(&json).unwrap()).unwrap();
+        assert_eq!(report["venvs"][0]["path"], "/p/app/.venv");
+        assert_eq!(report["venvs"][0]["size_bytes"], 2048);
+        assert_eq!(report["scan"]["roots"][0], "/p");
+
+        assert!(write_file(&temp_dir.path().join("venvs.xlsx"), &[&app], &metadata()).is_err());
+    }
+
+    #[test]
+    fn test_reporters_escape_their_formats() {
+        let awkward = venv("/p/a|<b>/.venv");
+        let markdown = render(&[&awkward], ExportFormat::Markdown, &metadata());
+        assert!(markdown.lines().nth(2).unwrap().starts_with("| /p/a\\|<b>/.venv | a\\|<b> | 2048 |"));
+        let html = render(&[&awkward], ExportFormat::Html, &metadata());
+        assert!(html.contains("<td>/p/a|&lt;b&gt;/.venv</td>"));
+
+        let table = render(&[&awkward], ExportFormat::Table, &metadata());
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[0].starts_with("path            project  size_bytes"));
+        assert!(lines[2].starts_with("/p/a|<b>/.venv  a|<b>    2048"));
+        assert_eq!(ExportFormat::from_name("markdown"), Some(ExportFormat::Markdown));
+        let empty: serde_json::Value = serde_json::from_str(&render(&[], ExportFormat::Json, &metadata())).unwrap();
+        assert_eq!(empty["venvs"].as_array().unwrap().len(), 0);
+        let report: serde_json::Value = serde_json::from_str(&render(&[&awkward, &awkward], ExportFormat::Json, &metadata())).unwrap();
+        assert_eq!(report["venvs"].as_array().unwrap().len(), 2);
+        assert_eq!(report["scan"]["recursive"], true);
+        assert_eq!(ExportFormat::from_path(Path::new("list.htm")), Some(ExportFormat::Html));
+    }
+
+    #[test]
+    fn test_json_fields_follow_header_order() {
+        let app = venv("/p/app/.venv").with_item_counts(Some((10, 2)));
+        let object = serde_json::to_string(&ExportRow::new(&app, Utc::now())).unwrap();
+        let positions: Vec<usize> = HEADER.iter().map(|column| object.find(&format!("\"{}\":", column)).unwrap()).collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]), "{}", object);
+    }
+}
@@ -3,13 +3,16 @@
 //! This module provides utility functions for file and directory operations,
 //! including calculating directory sizes and checking permissions.
 
+use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 use std::fs;
 use std::io;
+use std::time::{Duration, Instant};
+use unicode_segmentation::UnicodeSegmentation;
 use walkdir::WalkDir;
-use log::{debug, warn};
+use tracing::{debug, warn};
 
-use super::{Result, VenvCleanerError};
+use super::{ByteSize, FsBackend, RealFs, Result, VenvCleanerError};
 
 /// Utility struct for file operations
 pub struct FileUtils;
@@ -23,6 +26,22 @@ impl FileUtils {
     /// # Returns
     /// Total size in bytes, or an error if the operation fails
     pub fn calculate_directory_size(path: &Path) -> Result<u64> {
+        Self::calculate_directory_size_within(path, None).map(|(size, _)| size)
+    }
+
+    /// Calculate the size of a directory, giving up once a time budget is spent
+    ///
+    /// On slow network file systems a single huge directory can take minutes
+    /// to walk. With a budget the walk stops early and the size found so far
+    /// is returned as a lower bound.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the directory to calculate size for
+    /// * `budget` - How long the walk may take, or None for no limit
+    ///
+    /// # Returns
+    /// The size in bytes and whether every file was counted
+    pub fn calculate_directory_size_within(path: &Path, budget: Option<Duration>) -> Result<(u64, bool)> {
         if !path.exists() {
             return Err(VenvCleanerError::PathError {
                 path: path.display().to_string(),
@@ -39,11 +58,18 @@ impl FileUtils {
 
         let mut total_size = 0u64;
         let mut error_count = 0;
+        let started = Instant::now();
+        let mut complete = true;
 
         debug!("Calculating size for directory: {}", path.display());
 
         // Walk through all files and directories recursively
         for entry in WalkDir::new(path).follow_links(false).into_iter() {
+            if budget.is_some_and(|budget| started.elapsed() >= budget) {
+                complete = false;
+                break;
+            }
+
             match entry {
                 Ok(entry) => {
                     if entry.file_type().is_file() {
@@ -69,8 +95,40 @@ impl FileUtils {
             debug!("Encountered {} errors while calculating directory size", error_count);
         }
 
-        debug!("Total size calculated: {} bytes", total_size);
-        Ok(total_size)
+        if complete {
+            debug!("Total size calculated: {} bytes", total_size);
+        } else {
+            warn!("Stopped sizing {} after {:?}; at least {} bytes", path.display(), started.elapsed(), total_size);
+        }
+        Ok((total_size, complete))
+    }
+
+    /// Check that a .venv found by an earlier scan is still there
+    ///
+    /// # Arguments
+    /// * `path` - Path to the .venv directory
+    ///
+    /// # Returns
+    /// `VenvCleanerError::VenvVanished` if it was removed since the scan
+    pub fn ensure_exists(path: &Path) -> Result<()> {
+        Self::ensure_exists_in(&RealFs, path)
+    }
+
+    /// Check that a .venv found by an earlier scan is still there, on a given file system
+    ///
+    /// # Arguments
+    /// * `fs` - The file system the .venv was found on
+    /// * `path` - Path to the .venv directory
+    ///
+    /// # Returns
+    /// `VenvCleanerError::VenvVanished` if it was removed since the scan
+    pub fn ensure_exists_in(fs: &dyn FsBackend, path: &Path) -> Result<()> {
+        match fs.symlink_metadata(path) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Err(VenvCleanerError::VenvVanished {
+                path: path.display().to_string(),
+            }),
+            _ => Ok(()),
+        }
     }
 
     /// Check if a directory can be deleted (has write permissions)
@@ -187,7 +245,19 @@ impl FileUtils {
     /// # Returns
     /// True if it appears to be a valid .venv directory
     pub fn is_valid_venv_directory(path: &Path) -> bool {
-        if !path.is_dir() || path.file_name() != Some(std::ffi::OsStr::new(".venv")) {
+        Self::is_valid_venv_directory_in(&RealFs, path)
+    }
+
+    /// Check if a path is a valid .venv directory on a given file system
+    ///
+    /// # Arguments
+    /// * `fs` - The file system to look in
+    /// * `path` - Path to check
+    ///
+    /// # Returns
+    /// True if it appears to be a valid .venv directory
+    pub fn is_valid_venv_directory_in(fs: &dyn FsBackend, path: &Path) -> bool {
+        if !fs.is_dir(path) || path.file_name() != Some(OsStr::new(".venv")) {
             return false;
         }
 
@@ -202,7 +272,7 @@ impl FileUtils {
 
         let mut found_items = 0;
         for item in &common_venv_items {
-            if path.join(item).exists() {
+            if fs.exists(&path.join(item)) {
                 found_items += 1;
             }
         }
@@ -211,25 +281,148 @@ impl FileUtils {
         found_items >= 2
     }
 
+    /// Identify a directory by its device and inode numbers
+    ///
+    /// Bind mounts and other replicated paths reach the same directory under
+    /// different names; they share this identity.
+    ///
+    /// # Arguments
+    /// * `path` - The directory to identify
+    ///
+    /// # Returns
+    /// The device and inode numbers, or None if unreadable or the platform has none
+    pub fn directory_identity(path: &Path) -> Option<(u64, u64)> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            fs::metadata(path).ok().map(|metadata| (metadata.dev(), metadata.ino()))
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = path;
+            None
+        }
+    }
+
+    /// Parse a human-readable size such as `500`, `10K`, `1.5MB` or `2GiB`
+    ///
+    /// Units are binary (1K = 1024 bytes) to match the sizes shown elsewhere.
+    ///
+    /// # Arguments
+    /// * `text` - The size string to parse
+    ///
+    /// # Returns
+    /// The size in bytes, or an InvalidArgument error
+    pub fn parse_size(text: &str) -> Result<u64> {
+        ByteSize::parse(text).map(ByteSize::bytes)
+    }
+
+    /// Find the path a client named by its text form
+    ///
+    /// Paths are compared as OS strings, so the comparison is exact. A path
+    /// that is not valid UTF-8 can only be reported to clients in its lossy
+    /// form (invalid bytes become U+FFFD); such a path is matched by that
+    /// form as well, but only when no other path shares it, so a lossy name
+    /// can never select the wrong directory.
+    ///
+    /// # Arguments
+    /// * `paths` - Candidate paths
+    /// * `text` - The path as sent by the client
+    ///
+    /// # Returns
+    /// The index of the matching path, if any
+    pub fn position_of_path<'a>(paths: impl IntoIterator<Item = &'a Path>, text: &str) -> Option<usize> {
+        let paths: Vec<&Path> = paths.into_iter().collect();
+        if let Some(index) = paths.iter().position(|p| p.as_os_str() == OsStr::new(text)) {
+            return Some(index);
+        }
+
+        let mut lossy = paths
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.to_str().is_none() && p.to_string_lossy() == text)
+            .map(|(index, _)| index);
+        match (lossy.next(), lossy.next()) {
+            (Some(index), None) => Some(index),
+            _ => None,
+        }
+    }
+
+    /// Check whether a path matches a case-insensitive search query
+    ///
+    /// Bytes that are not valid UTF-8 cannot be typed into a search box, so
+    /// they are matched as U+FFFD; the rest of the path still matches. A path
+    /// below the home directory also matches its `~/...` form.
+    ///
+    /// # Arguments
+    /// * `path` - Path to test
+    /// * `query` - The search text; an empty query matches everything
+    pub fn path_matches_search(path: &Path, query: &str) -> bool {
+        let query = query.to_lowercase();
+        query.is_empty()
+            || path.to_string_lossy().to_lowercase().contains(&query)
+            || Self::home_relative(path).to_lowercase().contains(&query)
+    }
+
+    /// Show a path below the home directory as `~/...`
+    pub fn home_relative(path: &Path) -> String {
+        match dirs::home_dir().and_then(|home| path.strip_prefix(home).ok().map(Path::to_path_buf)) {
+            Some(relative) => Path::new("~").join(relative).display().to_string(),
+            None => path.display().to_string(),
+        }
+    }
+
+    /// Format a count with thousands separators, such as `215,307`
+    pub fn format_count(count: u64) -> String {
+        let digits = count.to_string();
+        let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                formatted.push(',');
+            }
+            formatted.push(digit);
+        }
+        formatted
+    }
+
+    /// Shorten text to at most `max_width` characters, keeping its end
+    ///
+    /// Characters are counted and cut as grapheme clusters, so multi-byte
+    /// and combining characters are never split. The removed start is
+    /// replaced with `...`.
+    ///
+    /// # Arguments
+    /// * `text` - Text to shorten, usually a path
+    /// * `max_width` - Maximum number of characters of the result
+    pub fn truncate_start(text: &str, max_width: usize) -> String {
+        let graphemes: Vec<&str> = text.graphemes(true).collect();
+        if graphemes.len() <= max_width {
+            return text.to_string();
+        }
+
+        let keep = max_width.saturating_sub(3);
+        format!("...{}", graphemes[graphemes.len() - keep..].concat())
+    }
+
     /// Format a file path for display, shortening it if necessary
     ///
     /// # Arguments
     /// * `path` - Path to format
-    /// * `max_length` - Maximum length for the displayed path
+    /// * `max_length` - Maximum length for the displayed path, in characters
     ///
     /// # Returns
     /// Formatted path string
     pub fn format_path_for_display(path: &Path, max_length: usize) -> String {
         let path_str = path.display().to_string();
 
-        if path_str.len() <= max_length {
+        if path_str.graphemes(true).count() <= max_length {
             return path_str;
         }
 
         // Try to shorten by showing only the last few components
         let components: Vec<_> = path.components().collect();
         if components.len() <= 2 {
-            return path_str;
+            return Self::truncate_start(&path_str, max_length);
         }
 
         // Start with the last component and add previous ones until we exceed max_length
@@ -240,7 +433,7 @@ impl FileUtils {
             temp_path = Path::new(component).join(&temp_path);
             let temp_str = format!(".../{}", temp_path.display());
 
-            if temp_str.len() > max_length {
+            if temp_str.graphemes(true).count() > max_length {
                 break;
             }
 
@@ -249,7 +442,7 @@ impl FileUtils {
 
         if result.is_empty() {
             // If we can't fit even with shortening, just truncate
-            format!("...{}", &path_str[path_str.len().saturating_sub(max_length - 3)..])
+            Self::truncate_start(&path_str, max_length)
         } else {
             result
         }
@@ -280,6 +473,20 @@ mod tests {
         assert_eq!(size, 13); // "Hello, World!" is 13 bytes
     }
 
+    #[test]
+    fn test_calculate_directory_size_within_budget() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("a.txt"), "hello").unwrap();
+
+        let (size, complete) = FileUtils::calculate_directory_size_within(temp_dir.path(), None).unwrap();
+        assert_eq!((size, complete), (5, true));
+
+        // A spent budget stops the walk with a lower bound
+        let (size, complete) = FileUtils::calculate_directory_size_within(temp_dir.path(), Some(Duration::ZERO)).unwrap();
+        assert!(!complete);
+        assert!(size <= 5);
+    }
+
     #[test]
     fn test_calculate_directory_size_nonexistent() {
         let result = FileUtils::calculate_directory_size(Path::new("/nonexistent/path"));
@@ -351,6 +558,16 @@ mod tests {
         assert!(FileUtils::is_valid_venv_directory(&venv_dir));
     }
 
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(FileUtils::parse_size("500").unwrap(), 500);
+        assert_eq!(FileUtils::parse_size("10K").unwrap(), 10 * 1024);
+        assert_eq!(FileUtils::parse_size("1.5MB").unwrap(), 1024 * 1024 * 3 / 2);
+        assert_eq!(FileUtils::parse_size("2 GiB").unwrap(), 2 * 1024 * 1024 * 1024);
+        assert!(FileUtils::parse_size("ten").is_err());
+        assert!(FileUtils::parse_size("10X").is_err());
+    }
+
     #[test]
     fn test_format_path_for_display_short() {
         let path = Path::new("/short/path");
@@ -365,4 +582,63 @@ mod tests {
         assert!(formatted.len() <= 20);
         assert!(formatted.starts_with("..."));
     }
+
+    #[test]
+    fn test_position_of_path() {
+        let paths = [PathBuf::from("/work/a/.venv"), PathBuf::from("/work/b/.venv")];
+        let refs = || paths.iter().map(PathBuf::as_path);
+        assert_eq!(FileUtils::position_of_path(refs(), "/work/b/.venv"), Some(1));
+        assert_eq!(FileUtils::position_of_path(refs(), "/work/c/.venv"), None);
+        assert!(FileUtils::path_matches_search(&paths[0], "WORK/A"));
+        assert!(!FileUtils::path_matches_search(&paths[0], "work/b"));
+    }
+
+    #[test]
+    fn test_path_matches_search_of_home_relative_form() {
+        let Some(home) = dirs::home_dir() else { return };
+        let venv = home.join("projects").join("api").join(".venv");
+        assert!(FileUtils::path_matches_search(&venv, "~/Projects/api"));
+        assert!(!FileUtils::path_matches_search(&venv, "~/projects/web"));
+        assert!(!FileUtils::path_matches_search(Path::new("/srv/api/.venv"), "~/"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_position_of_non_utf8_path() {
+        use std::os::unix::ffi::OsStrExt;
+
+        // "caf\xe9" is Latin-1; its lossy form equals the real UTF-8 name "caf\u{fffd}"
+        let latin1 = Path::new(OsStr::from_bytes(b"/work/caf\xe9/.venv")).to_path_buf();
+        let other = Path::new(OsStr::from_bytes(b"/work/caf\xe8/.venv")).to_path_buf();
+        let replacement = PathBuf::from("/work/caf\u{fffd}/.venv");
+
+        let lossy = latin1.to_string_lossy().to_string();
+        assert_eq!(FileUtils::position_of_path([latin1.as_path()], &lossy), Some(0));
+        // Exact matches win over lossy ones
+        assert_eq!(FileUtils::position_of_path([latin1.as_path(), replacement.as_path()], &lossy), Some(1));
+        // Two paths sharing a lossy form are ambiguous
+        assert_eq!(FileUtils::position_of_path([latin1.as_path(), other.as_path()], &lossy), None);
+        assert!(FileUtils::path_matches_search(&latin1, "/work/caf"));
+    }
+
+    #[test]
+    fn test_format_count() {
+        assert_eq!(FileUtils::format_count(0), "0");
+        assert_eq!(FileUtils::format_count(999), "999");
+        assert_eq!(FileUtils::format_count(1000), "1,000");
+        assert_eq!(FileUtils::format_count(12_345_678), "12,345,678");
+    }
+
+    #[test]
+    fn test_truncate_start_keeps_whole_graphemes() {
+        assert_eq!(FileUtils::truncate_start("/short", 10), "/short");
+        assert_eq!(FileUtils::truncate_start("/home/üüüüüü", 8), "...üüüüü");
+        // "e" followed by a combining acute accent is one character
+        assert_eq!(FileUtils::truncate_start("/cafe\u{301}/x", 6), "...e\u{301}/x");
+
+        let long_name = "ü".repeat(300);
+        let formatted = FileUtils::format_path_for_display(&Path::new("/data").join(&long_name), 40);
+        assert_eq!(formatted.graphemes(true).count(), 40);
+        assert!(formatted.starts_with("..."));
+    }
 }
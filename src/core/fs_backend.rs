@@ -0,0 +1,672 @@
+//! The file system operations scans and deletions go through
+//!
+//! `VenvCleaner` walks, stats and removes directories through an
+//! `FsBackend`. `RealFs`, the default, is the disk. `MemoryFs` is an
+//! in-memory tree whose entries can be made to fail with a chosen error, so
+//! tests can exercise what a temp directory cannot reliably reproduce: a
+//! directory the scan is denied, a rename refused, a removal that fails
+//! halfway through a .venv. Details read from file contents (Python
+//! version, git history) still come from the disk, so a `MemoryFs` scan
+//! finds none.
+
+use std::collections::{BTreeMap, HashMap};
+use std::ffi::OsStr;
+use std::fmt;
+use std::io;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+use tracing::{debug, warn};
+use walkdir::WalkDir;
+
+use super::{platform, scan_priority, FileUtils, Result, VenvCleanerError};
+
+/// What a file system entry is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FsEntryKind {
+    /// A directory
+    Directory,
+    /// A regular file
+    File,
+    /// A symbolic link, never followed by walks
+    Symlink,
+}
+
+/// The metadata of an entry that scans use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FsMetadata {
+    /// What the entry is
+    pub kind: FsEntryKind,
+    /// Size in bytes
+    pub len: u64,
+    /// When the entry was last modified, if the platform records it
+    pub modified: Option<SystemTime>,
+    /// When the entry was created, if the platform records it
+    pub created: Option<SystemTime>,
+}
+
+impl FsMetadata {
+    /// Check whether the entry is a directory
+    pub fn is_dir(&self) -> bool {
+        self.kind == FsEntryKind::Directory
+    }
+}
+
+/// An entry of a directory listing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FsEntry {
+    /// Path of the entry
+    pub path: PathBuf,
+    /// What the entry is, without following symlinks
+    pub kind: FsEntryKind,
+}
+
+/// An entry reached by a walk
+#[derive(Debug, Clone, Copy)]
+pub struct WalkEntry<'a> {
+    /// Path of the entry
+    pub path: &'a Path,
+    /// What the entry is, without following symlinks
+    pub kind: FsEntryKind,
+    /// Levels below the walk's root (0 for the root)
+    pub depth: usize,
+}
+
+impl WalkEntry<'_> {
+    /// Check whether the entry is a directory
+    pub fn is_dir(&self) -> bool {
+        self.kind == FsEntryKind::Directory
+    }
+
+    /// Get the last component of the path (the whole path for `/`)
+    pub fn file_name(&self) -> &OsStr {
+        self.path.file_name().unwrap_or(self.path.as_os_str())
+    }
+}
+
+/// What a walk of a directory found
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DirectoryUsage {
+    /// Total size of the files in bytes
+    pub bytes: u64,
+    /// Number of files
+    pub files: usize,
+    /// Number of directories, not counting the walked directory itself
+    pub dirs: usize,
+    /// Whether the walk finished within the budget
+    pub complete: bool,
+}
+
+/// An entry a walk could not read
+#[derive(Debug)]
+pub struct WalkError {
+    /// The entry, when known
+    pub path: Option<PathBuf>,
+    /// Why reading it failed
+    pub error: io::Error,
+}
+
+/// Walks, stats and removes directories for `VenvCleaner`
+///
+/// Only the listing, metadata, rename and removal operations are required;
+/// walking, sizing and the checks built on them have default
+/// implementations in terms of those.
+pub trait FsBackend: fmt::Debug + Send + Sync {
+    /// Get the metadata of a path, following symlinks
+    fn metadata(&self, path: &Path) -> io::Result<FsMetadata>;
+
+    /// Get the metadata of a path without following a symlink
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FsMetadata>;
+
+    /// List the entries of a directory
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<FsEntry>>;
+
+    /// Rename a file or directory
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()>;
+
+    /// Remove a directory and everything in it
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()>;
+
+    /// Move a file or directory to the system trash
+    fn move_to_trash(&self, path: &Path) -> Result<()>;
+
+    /// Check whether a path exists, following symlinks
+    fn exists(&self, path: &Path) -> bool {
+        self.metadata(path).is_ok()
+    }
+
+    /// Check whether a path is a directory, following symlinks
+    fn is_dir(&self, path: &Path) -> bool {
+        self.metadata(path).is_ok_and(|metadata| metadata.is_dir())
+    }
+
+    /// Check whether a directory can be deleted
+    ///
+    /// # Returns
+    /// False if it is missing or cannot be listed for lack of permission
+    fn can_delete(&self, path: &Path) -> Result<bool> {
+        if !self.exists(path) {
+            return Ok(false);
+        }
+        match self.read_dir(path) {
+            Ok(_) => Ok(true),
+            Err(e) if e.kind() == io::ErrorKind::PermissionDenied => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Walk a tree depth first without following symlinks
+    ///
+    /// # Arguments
+    /// * `root` - Where the walk starts; it is reported at depth 0
+    /// * `prioritise` - Walk likely project roots first (see `scan_priority`)
+    /// * `descend` - Whether to report an entry and walk below it
+    /// * `visit` - Called with each entry, or the error reading it; `Break` ends the walk
+    fn walk(
+        &self,
+        root: &Path,
+        prioritise: bool,
+        descend: &mut dyn FnMut(&WalkEntry<'_>) -> bool,
+        visit: &mut dyn FnMut(std::result::Result<WalkEntry<'_>, WalkError>) -> ControlFlow<()>,
+    ) {
+        match self.metadata(root) {
+            Ok(metadata) => {
+                let _ = walk_from(self, root, metadata.kind, 0, prioritise, descend, visit);
+            }
+            Err(error) => {
+                let _ = visit(Err(WalkError { path: Some(root.to_path_buf()), error }));
+            }
+        }
+    }
+
+    /// Add up the sizes of the files in a directory
+    ///
+    /// # Arguments
+    /// * `path` - The directory
+    /// * `budget` - Stop after this long, if given
+    ///
+    /// # Returns
+    /// The size, and whether the walk finished within the budget
+    fn directory_size(&self, path: &Path, budget: Option<Duration>) -> Result<(u64, bool)> {
+        let usage = self.directory_usage(path, budget, &mut |_, _| {})?;
+        Ok((usage.bytes, usage.complete))
+    }
+
+    /// Size a directory and count its items in a single walk
+    ///
+    /// # Arguments
+    /// * `path` - The directory
+    /// * `budget` - Stop after this long, if given
+    /// * `on_file` - Called with each file and its size, e.g. to look for user data
+    ///
+    /// # Returns
+    /// The size and item counts, and whether the walk finished within the budget
+    fn directory_usage(
+        &self,
+        path: &Path,
+        budget: Option<Duration>,
+        on_file: &mut dyn FnMut(&Path, u64),
+    ) -> Result<DirectoryUsage> {
+        match self.metadata(path) {
+            Ok(metadata) if metadata.is_dir() => {}
+            Ok(_) => return Err(VenvCleanerError::PathError {
+                path: path.display().to_string(),
+                message: "Path is not a directory".to_string(),
+            }),
+            Err(_) => return Err(VenvCleanerError::PathError {
+                path: path.display().to_string(),
+                message: "Directory does not exist".to_string(),
+            }),
+        }
+
+        let started = Instant::now();
+        let mut usage = DirectoryUsage { complete: true, ..DirectoryUsage::default() };
+        self.walk(path, false, &mut |_| true, &mut |item| {
+            if budget.is_some_and(|budget| started.elapsed() >= budget) {
+                usage.complete = false;
+                return ControlFlow::Break(());
+            }
+            match item {
+                Ok(entry) if entry.kind == FsEntryKind::File => {
+                    usage.files += 1;
+                    match self.symlink_metadata(entry.path) {
+                        Ok(metadata) => {
+                            usage.bytes = usage.bytes.saturating_add(metadata.len);
+                            on_file(entry.path, metadata.len);
+                        }
+                        Err(e) => warn!("Failed to get metadata for {}: {}", entry.path.display(), e),
+                    }
+                }
+                Ok(entry) if entry.is_dir() && entry.depth > 0 => usage.dirs += 1,
+                Ok(_) => {}
+                Err(e) => warn!("Error walking directory {}: {}", path.display(), e.error),
+            }
+            ControlFlow::Continue(())
+        });
+        Ok(usage)
+    }
+}
+
+/// Walk below one entry for the default `FsBackend::walk`
+fn walk_from<B: FsBackend + ?Sized>(
+    fs: &B,
+    path: &Path,
+    kind: FsEntryKind,
+    depth: usize,
+    prioritise: bool,
+    descend: &mut dyn FnMut(&WalkEntry<'_>) -> bool,
+    visit: &mut dyn FnMut(std::result::Result<WalkEntry<'_>, WalkError>) -> ControlFlow<()>,
+) -> ControlFlow<()> {
+    let entry = WalkEntry { path, kind, depth };
+    if !descend(&entry) {
+        return ControlFlow::Continue(());
+    }
+    visit(Ok(entry))?;
+    if kind != FsEntryKind::Directory {
+        return ControlFlow::Continue(());
+    }
+
+    let mut children = match fs.read_dir(path) {
+        Ok(children) => children,
+        Err(error) => return visit(Err(WalkError { path: Some(path.to_path_buf()), error })),
+    };
+    if prioritise {
+        children.sort_by_cached_key(|child| {
+            let priority = if child.kind == FsEntryKind::Directory {
+                scan_priority::directory_priority(&child.path)
+            } else {
+                scan_priority::PRIORITY_NORMAL
+            };
+            (priority, child.path.file_name().map(OsStr::to_os_string))
+        });
+    }
+    for child in &children {
+        walk_from(fs, &child.path, child.kind, depth + 1, prioritise, descend, visit)?;
+    }
+    ControlFlow::Continue(())
+}
+
+/// The disk, through `std::fs` and walkdir
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RealFs;
+
+impl RealFs {
+    /// Convert std metadata
+    fn convert(metadata: std::fs::Metadata) -> FsMetadata {
+        let kind = if metadata.is_dir() {
+            FsEntryKind::Directory
+        } else if metadata.is_symlink() {
+            FsEntryKind::Symlink
+        } else {
+            FsEntryKind::File
+        };
+        FsMetadata { kind, len: metadata.len(), modified: metadata.modified().ok(), created: metadata.created().ok() }
+    }
+
+    /// Get what a walkdir entry is
+    fn kind_of(entry: &walkdir::DirEntry) -> FsEntryKind {
+        if entry.file_type().is_dir() {
+            FsEntryKind::Directory
+        } else if entry.file_type().is_symlink() {
+            FsEntryKind::Symlink
+        } else {
+            FsEntryKind::File
+        }
+    }
+}
+
+impl FsBackend for RealFs {
+    fn metadata(&self, path: &Path) -> io::Result<FsMetadata> {
+        std::fs::metadata(path).map(Self::convert)
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FsMetadata> {
+        std::fs::symlink_metadata(path).map(Self::convert)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<FsEntry>> {
+        std::fs::read_dir(path)?
+            .map(|entry| {
+                let entry = entry?;
+                let file_type = entry.file_type()?;
+                let kind = if file_type.is_dir() {
+                    FsEntryKind::Directory
+                } else if file_type.is_symlink() {
+                    FsEntryKind::Symlink
+                } else {
+                    FsEntryKind::File
+                };
+                Ok(FsEntry { path: entry.path(), kind })
+            })
+            .collect()
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        std::fs::rename(from, to)
+    }
+
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()> {
+        std::fs::remove_dir_all(path)
+    }
+
+    fn move_to_trash(&self, path: &Path) -> Result<()> {
+        platform::move_to_trash(path)
+    }
+
+    fn can_delete(&self, path: &Path) -> Result<bool> {
+        FileUtils::can_delete_directory(path)
+    }
+
+    fn walk(
+        &self,
+        root: &Path,
+        prioritise: bool,
+        descend: &mut dyn FnMut(&WalkEntry<'_>) -> bool,
+        visit: &mut dyn FnMut(std::result::Result<WalkEntry<'_>, WalkError>) -> ControlFlow<()>,
+    ) {
+        let mut walker = WalkDir::new(root).follow_links(false);
+        if prioritise {
+            walker = walker.sort_by_key(|entry| {
+                let priority = if entry.file_type().is_dir() {
+                    scan_priority::directory_priority(entry.path())
+                } else {
+                    scan_priority::PRIORITY_NORMAL
+                };
+                (priority, entry.file_name().to_os_string())
+            });
+        }
+
+        let walker = walker.into_iter().filter_entry(|entry| {
+            descend(&WalkEntry { path: entry.path(), kind: Self::kind_of(entry), depth: entry.depth() })
+        });
+        for item in walker {
+            let flow = match item {
+                Ok(entry) => visit(Ok(WalkEntry { path: entry.path(), kind: Self::kind_of(&entry), depth: entry.depth() })),
+                Err(e) => {
+                    let path = e.path().map(Path::to_path_buf);
+                    visit(Err(WalkError { path, error: io::Error::from(e) }))
+                }
+            };
+            if flow.is_break() {
+                break;
+            }
+        }
+    }
+}
+
+/// A file system operation a `MemoryFs` can be made to fail
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FsOp {
+    /// Reading the metadata of the entry
+    Stat,
+    /// Listing the directory
+    ReadDir,
+    /// Renaming the entry
+    Rename,
+    /// Removing the entry
+    Remove,
+    /// Moving the entry to the trash
+    Trash,
+}
+
+/// An entry of a `MemoryFs`
+#[derive(Debug, Clone, Copy)]
+struct MemoryNode {
+    /// Whether it is a directory or a file
+    kind: FsEntryKind,
+    /// Size in bytes (0 for directories)
+    len: u64,
+    /// When it was last modified
+    modified: SystemTime,
+}
+
+/// The tree and injected failures of a `MemoryFs`
+#[derive(Debug, Default)]
+struct MemoryState {
+    /// Every entry by path; parents sort before their children
+    nodes: BTreeMap<PathBuf, MemoryNode>,
+    /// Errors returned for an operation on a path
+    failures: HashMap<(PathBuf, FsOp), io::ErrorKind>,
+    /// Paths moved to the trash, in order
+    trashed: Vec<PathBuf>,
+}
+
+/// An in-memory file system for tests, with failures injected per path and operation
+///
+/// Paths are absolute and the root always exists. Adding an entry adds its
+/// missing parent directories. There are no symlinks.
+#[derive(Debug, Default)]
+pub struct MemoryFs {
+    /// The tree, shared with the threads sizing .venv directories
+    state: Mutex<MemoryState>,
+}
+
+impl MemoryFs {
+    /// Create an empty file system
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a directory and its missing parents
+    pub fn add_dir(&self, path: impl AsRef<Path>) {
+        let mut state = self.lock();
+        Self::insert(&mut state, path.as_ref(), FsEntryKind::Directory, 0);
+    }
+
+    /// Add a file of a given size, and its missing parent directories
+    pub fn add_file(&self, path: impl AsRef<Path>, len: u64) {
+        let mut state = self.lock();
+        Self::insert(&mut state, path.as_ref(), FsEntryKind::File, len);
+    }
+
+    /// Add a .venv (`pyvenv.cfg`, `bin` and `lib`) whose files add up to `bytes`
+    pub fn add_venv(&self, path: impl AsRef<Path>, bytes: u64) {
+        let path = path.as_ref();
+        self.add_file(path.join("pyvenv.cfg"), 0);
+        self.add_dir(path.join("bin"));
+        self.add_file(path.join("lib").join("payload"), bytes);
+    }
+
+    /// Set when an entry was last modified
+    pub fn set_modified(&self, path: impl AsRef<Path>, modified: SystemTime) {
+        if let Some(node) = self.lock().nodes.get_mut(path.as_ref()) {
+            node.modified = modified;
+        }
+    }
+
+    /// Make an operation on a path fail with an error of the given kind
+    ///
+    /// The failure follows the entry when a directory above it is renamed.
+    pub fn fail(&self, path: impl AsRef<Path>, op: FsOp, kind: io::ErrorKind) {
+        self.lock().failures.insert((path.as_ref().to_path_buf(), op), kind);
+    }
+
+    /// Check whether an entry exists
+    pub fn contains(&self, path: impl AsRef<Path>) -> bool {
+        self.lock().nodes.contains_key(path.as_ref())
+    }
+
+    /// Get the paths moved to the trash, in order
+    pub fn trashed(&self) -> Vec<PathBuf> {
+        self.lock().trashed.clone()
+    }
+
+    /// Lock the state; a test that panicked while holding it left nothing worth protecting
+    fn lock(&self) -> std::sync::MutexGuard<'_, MemoryState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Insert an entry and its missing parents
+    fn insert(state: &mut MemoryState, path: &Path, kind: FsEntryKind, len: u64) {
+        let modified = SystemTime::now();
+        for ancestor in path.ancestors().skip(1) {
+            if ancestor.parent().is_some() {
+                state.nodes.entry(ancestor.to_path_buf()).or_insert(MemoryNode { kind: FsEntryKind::Directory, len: 0, modified });
+            }
+        }
+        state.nodes.insert(path.to_path_buf(), MemoryNode { kind, len, modified });
+    }
+
+    /// Return the injected error for an operation on a path, if any
+    fn check(state: &MemoryState, path: &Path, op: FsOp) -> io::Result<()> {
+        match state.failures.get(&(path.to_path_buf(), op)) {
+            Some(&kind) => Err(io::Error::new(kind, format!("injected {:?} failure for {}", op, path.display()))),
+            None => Ok(()),
+        }
+    }
+
+    /// Look an entry up, the root being a directory
+    fn node(state: &MemoryState, path: &Path) -> io::Result<MemoryNode> {
+        Self::check(state, path, FsOp::Stat)?;
+        if path.parent().is_none() {
+            return Ok(MemoryNode { kind: FsEntryKind::Directory, len: 0, modified: SystemTime::UNIX_EPOCH });
+        }
+        state.nodes.get(path).copied().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, format!("{} does not exist", path.display()))
+        })
+    }
+
+    /// Remove an entry and everything below it
+    fn remove_tree(state: &mut MemoryState, path: &Path) {
+        state.nodes.retain(|candidate, _| !candidate.starts_with(path));
+    }
+}
+
+impl FsBackend for MemoryFs {
+    fn metadata(&self, path: &Path) -> io::Result<FsMetadata> {
+        let node = Self::node(&self.lock(), path)?;
+        Ok(FsMetadata { kind: node.kind, len: node.len, modified: Some(node.modified), created: Some(node.modified) })
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FsMetadata> {
+        self.metadata(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<FsEntry>> {
+        let state = self.lock();
+        if Self::node(&state, path)?.kind != FsEntryKind::Directory {
+            return Err(io::Error::new(io::ErrorKind::NotADirectory, format!("{} is not a directory", path.display())));
+        }
+        Self::check(&state, path, FsOp::ReadDir)?;
+        Ok(state
+            .nodes
+            .iter()
+            .filter(|(candidate, _)| candidate.parent() == Some(path))
+            .map(|(candidate, node)| FsEntry { path: candidate.clone(), kind: node.kind })
+            .collect())
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        let mut state = self.lock();
+        Self::node(&state, from)?;
+        Self::check(&state, from, FsOp::Rename)?;
+        let moved: Vec<(PathBuf, MemoryNode)> = state
+            .nodes
+            .iter()
+            .filter(|(candidate, _)| candidate.starts_with(from))
+            .map(|(candidate, node)| (to.join(candidate.strip_prefix(from).unwrap_or(Path::new(""))), *node))
+            .collect();
+        Self::remove_tree(&mut state, from);
+        for (path, node) in moved {
+            state.nodes.insert(path.components().collect(), node);
+        }
+
+        // A locked file stays locked when its directory is renamed
+        let failures = std::mem::take(&mut state.failures);
+        state.failures = failures
+            .into_iter()
+            .map(|((path, op), kind)| match path.strip_prefix(from) {
+                Ok(rest) => ((to.join(rest).components().collect(), op), kind),
+                Err(_) => ((path, op), kind),
+            })
+            .collect();
+        Ok(())
+    }
+
+    /// Removes what it can; an entry made to fail stays, with the directories above it
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()> {
+        let mut state = self.lock();
+        Self::node(&state, path)?;
+        let below: Vec<PathBuf> = state.nodes.keys().filter(|candidate| candidate.starts_with(path)).cloned().collect();
+
+        // Children sort after their parents, so going backwards empties each directory before removing it
+        let mut first_error = None;
+        for candidate in below.iter().rev() {
+            if let Err(e) = Self::check(&state, candidate, FsOp::Remove) {
+                debug!("Keeping {}: {}", candidate.display(), e);
+                first_error.get_or_insert(e);
+                continue;
+            }
+            let occupied = state.nodes.keys().any(|other| other.parent() == Some(candidate.as_path()));
+            if !occupied {
+                state.nodes.remove(candidate);
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
+    fn move_to_trash(&self, path: &Path) -> Result<()> {
+        let mut state = self.lock();
+        Self::node(&state, path)?;
+        Self::check(&state, path, FsOp::Trash).map_err(|e| {
+            VenvCleanerError::Io(format!("Failed to move {} to trash: {}", path.display(), e))
+        })?;
+        Self::remove_tree(&mut state, path);
+        state.trashed.push(path.to_path_buf());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_fs_walk_and_size() {
+        let fs = MemoryFs::new();
+        fs.add_venv("/work/app/.venv", 1000);
+        fs.add_file("/work/app/main.py", 10);
+
+        let mut seen = Vec::new();
+        fs.walk(Path::new("/work"), false, &mut |entry| entry.file_name() != "bin", &mut |item| {
+            let entry = item.unwrap();
+            seen.push((entry.path.to_path_buf(), entry.depth));
+            ControlFlow::Continue(())
+        });
+        assert_eq!(seen[0], (PathBuf::from("/work"), 0));
+        assert!(seen.contains(&(PathBuf::from("/work/app/.venv"), 2)));
+        assert!(!seen.iter().any(|(path, _)| path.ends_with("bin")));
+
+        assert_eq!(fs.directory_size(Path::new("/work/app/.venv"), None).unwrap(), (1000, true));
+        let mut files = Vec::new();
+        let usage = fs.directory_usage(Path::new("/work/app/.venv"), None, &mut |path, len| files.push((path.to_path_buf(), len))).unwrap();
+        assert_eq!(usage, DirectoryUsage { bytes: 1000, files: 2, dirs: 2, complete: true });
+        assert_eq!(files.iter().map(|(_, len)| len).sum::<u64>(), 1000);
+        assert!(fs.directory_size(Path::new("/missing"), None).is_err());
+    }
+
+    #[test]
+    fn test_memory_fs_failures() {
+        let fs = MemoryFs::new();
+        fs.add_venv("/work/.venv", 10);
+        fs.add_file("/work/.venv/lib/locked.so", 5);
+
+        fs.fail("/work/.venv/lib", FsOp::ReadDir, io::ErrorKind::PermissionDenied);
+        assert!(!fs.can_delete(Path::new("/work/.venv/lib")).unwrap());
+        assert_eq!(fs.read_dir(Path::new("/work/.venv/lib")).unwrap_err().kind(), io::ErrorKind::PermissionDenied);
+
+        // A removal failing halfway keeps the failing entry and the directories above it
+        fs.fail("/work/.venv/lib/locked.so", FsOp::Remove, io::ErrorKind::PermissionDenied);
+        assert!(fs.remove_dir_all(Path::new("/work/.venv")).is_err());
+        assert!(fs.contains("/work/.venv/lib/locked.so"));
+        assert!(!fs.contains("/work/.venv/bin"));
+        assert!(!fs.contains("/work/.venv/pyvenv.cfg"));
+
+        fs.rename(Path::new("/work/.venv"), Path::new("/work/.old")).unwrap();
+        assert!(fs.contains("/work/.old/lib/locked.so"));
+        fs.move_to_trash(Path::new("/work/.old")).unwrap();
+        assert_eq!(fs.trashed(), vec![PathBuf::from("/work/.old")]);
+        assert!(!fs.contains("/work/.old"));
+    }
+}
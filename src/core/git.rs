@@ -0,0 +1,105 @@
+//! Git activity of the project owning a .venv
+//!
+//! A .venv's own timestamps only change when packages do, so an environment
+//! can look abandoned while its project is committed to every day. This
+//! module asks git for the last commit touching the project directory, which
+//! also works for projects inside a larger repository (monorepos).
+
+use std::path::Path;
+use std::process::Command;
+use chrono::{DateTime, Utc, TimeZone};
+use tracing::debug;
+
+/// Check whether a directory is inside a git work tree
+///
+/// # Arguments
+/// * `dir` - Directory to check
+pub fn is_in_git_repo(dir: &Path) -> bool {
+    dir.ancestors().any(|ancestor| ancestor.join(".git").exists())
+}
+
+/// Get the time of the last commit touching a project directory
+///
+/// # Arguments
+/// * `project_dir` - The project directory (the parent of the .venv)
+///
+/// # Returns
+/// The commit time, or None if the directory is not in a git repository,
+/// has no commits or git is not installed
+pub fn last_commit_time(project_dir: &Path) -> Option<DateTime<Utc>> {
+    if !is_in_git_repo(project_dir) {
+        return None;
+    }
+
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(project_dir)
+        .args(["log", "-1", "--format=%ct", "--", "."])
+        .output()
+        .map_err(|e| debug!("Cannot run git in {}: {}", project_dir.display(), e))
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    parse_commit_timestamp(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Parse the Unix timestamp printed by `git log --format=%ct`
+fn parse_commit_timestamp(output: &str) -> Option<DateTime<Utc>> {
+    let seconds = output.trim().parse::<i64>().ok()?;
+    Utc.timestamp_opt(seconds, 0).single()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) -> bool {
+        Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    #[test]
+    fn test_parse_commit_timestamp() {
+        let parsed = parse_commit_timestamp("1700000000\n").unwrap();
+        assert_eq!(parsed.timestamp(), 1_700_000_000);
+        assert!(parse_commit_timestamp("").is_none());
+        assert!(parse_commit_timestamp("fatal: not a git repository").is_none());
+    }
+
+    #[test]
+    fn test_no_repository() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(!is_in_git_repo(temp_dir.path()));
+        assert!(last_commit_time(temp_dir.path()).is_none());
+    }
+
+    #[test]
+    fn test_last_commit_time_of_subproject() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("services").join("api");
+        fs::create_dir_all(&project).unwrap();
+        if !git(temp_dir.path(), &["init", "-q"]) {
+            // git is not installed; nothing more to check
+            return;
+        }
+        assert!(is_in_git_repo(&project));
+        assert!(last_commit_time(&project).is_none());
+
+        fs::write(project.join("main.py"), "print('hi')\n").unwrap();
+        assert!(git(temp_dir.path(), &["add", "."]));
+        assert!(git(temp_dir.path(), &["commit", "-q", "-m", "init"]));
+
+        let committed = last_commit_time(&project).unwrap();
+        assert!((Utc::now() - committed).num_minutes() < 5);
+    }
+}
@@ -0,0 +1,306 @@
+//! Help and about content shared by the TUI and GUI
+//!
+//! Every help screen, status bar hint and about window draws its text from
+//! the structures here rather than from string literals in the drawing
+//! code. The interfaces then describe shared features in the same words,
+//! stay consistent as features are added to one and not the other, and all
+//! user-facing help strings sit in one place for translation.
+
+/// One line of a help listing: the keys, button or icon, and what it does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HelpEntry {
+    /// Keys, mouse action or icon being described
+    pub keys: &'static str,
+    /// What it does or means
+    pub description: &'static str,
+}
+
+impl HelpEntry {
+    /// Create a help entry
+    pub const fn new(keys: &'static str, description: &'static str) -> Self {
+        Self { keys, description }
+    }
+}
+
+/// A titled group of help entries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HelpSection {
+    /// Heading of the group
+    pub title: &'static str,
+    /// Entries in display order
+    pub entries: &'static [HelpEntry],
+}
+
+/// Name, version and description shown in about windows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AboutInfo {
+    /// Application name
+    pub name: &'static str,
+    /// Version of this build
+    pub version: &'static str,
+    /// Git commit the build was made from, `unknown` outside a checkout
+    pub commit: &'static str,
+    /// Date of that commit (YYYY-MM-DD), `unknown` outside a checkout
+    pub build_date: &'static str,
+    /// License VenvCleaner is distributed under
+    pub license: &'static str,
+    /// One-sentence description of the application
+    pub summary: &'static str,
+}
+
+/// About information of this build
+pub const ABOUT: AboutInfo = AboutInfo {
+    name: "VenvCleaner",
+    version: env!("CARGO_PKG_VERSION"),
+    commit: env!("VENV_CLEANER_COMMIT"),
+    build_date: env!("VENV_CLEANER_BUILD_DATE"),
+    license: env!("CARGO_PKG_LICENSE"),
+    summary: "A multi-mode application to help manage and clean up Python virtual environment folders (.venv) on Mac and Linux.",
+};
+
+/// A third-party crate compiled into this build
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThirdPartyCredit {
+    /// Crate name
+    pub name: &'static str,
+    /// Crate version from `Cargo.lock`
+    pub version: &'static str,
+    /// SPDX license expression, or `unknown` when the build could not read it
+    pub license: &'static str,
+}
+
+/// Crates in `Cargo.lock` with their licenses, generated by `build.rs`
+const THIRD_PARTY_LICENSES: &str = include_str!(concat!(env!("OUT_DIR"), "/third_party_licenses.tsv"));
+
+/// Third-party crates credited in about windows, sorted by name
+///
+/// Lists every package of the lock file, including the optional ones a
+/// build without the `tui` or `gui` feature does not compile in.
+pub fn third_party_credits() -> Vec<ThirdPartyCredit> {
+    parse_credits(THIRD_PARTY_LICENSES)
+}
+
+/// Parse the `name\tversion\tlicense` lines written by `build.rs`
+fn parse_credits(text: &'static str) -> Vec<ThirdPartyCredit> {
+    let mut credits: Vec<ThirdPartyCredit> = text
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            Some(ThirdPartyCredit { name: fields.next()?, version: fields.next()?, license: fields.next()? })
+        })
+        .collect();
+    credits.sort_by(|a, b| a.name.cmp(b.name).then_with(|| a.version.cmp(b.version)));
+    credits
+}
+
+/// What the application does, opening the help screens
+pub const OVERVIEW: &str =
+    "VenvCleaner helps you find, analyze, and clean up Python virtual environment folders (.venv) on your system.";
+
+/// Icons marking the state of a .venv in both lists
+pub const STATUS_ICONS: &[HelpEntry] = &[
+    HelpEntry::new("🟢", "Recently used (<30 days)"),
+    HelpEntry::new("🟡", "Moderately used (30-90 days)"),
+    HelpEntry::new("🔴", "Old (>90 days)"),
+    HelpEntry::new("✎", "Project installed in editable mode (deleting breaks development)"),
+    HelpEntry::new("◆", "Contains user data such as .csv/.ipynb/.zip (deleting asks twice)"),
+    HelpEntry::new("🌱", "Old .venv whose project still has recent git commits"),
+];
+
+/// Marker of selected rows in the TUI, which has no checkboxes
+pub const TUI_SELECTED_ICON: HelpEntry = HelpEntry::new("✓", "Selected for deletion");
+
+/// Key bindings of the TUI, grouped as on its help screen
+pub const TUI_SECTIONS: &[HelpSection] = &[
+    HelpSection {
+        title: "Navigation",
+        entries: &[
+            HelpEntry::new("↑/↓", "Move selection up/down"),
+            HelpEntry::new("Home/End", "Go to first/last item"),
+            HelpEntry::new("PgUp/PgDn", "Page up/down"),
+        ],
+    },
+    HelpSection {
+        title: "Selection",
+        entries: &[
+            HelpEntry::new("Space/Enter", "Toggle selection"),
+            HelpEntry::new("Ctrl+A", "Select all"),
+            HelpEntry::new("Ctrl+D", "Deselect all"),
+        ],
+    },
+    HelpSection {
+        title: "Actions",
+        entries: &[
+            HelpEntry::new("x/Del", "Delete selected items (then p to peek inside first)"),
+            HelpEntry::new("s", "Cycle sort order"),
+            HelpEntry::new("o", "Open folder in file manager"),
+            HelpEntry::new("m", "Slim selected .venv in place (__pycache__, tests, pip cache)"),
+            HelpEntry::new("t", "Open a terminal with the .venv activated"),
+            HelpEntry::new("e", "Open the project in your editor (config `editor`, $EDITOR or VS Code)"),
+            HelpEntry::new("E", "Export the shown list to a .csv, .json, .md, .html or .txt file"),
+            HelpEntry::new("z", "Size the highlighted .venv fully (after --size-timeout)"),
+            HelpEntry::new("D", "Show/hide how much each .venv grew since the last scan, the largest growth highlighted"),
+            HelpEntry::new("g", "Group the list by age (0-30, 31-90, 91-180, >180 days)"),
+            HelpEntry::new("1-4", "Collapse/expand an age group while grouped"),
+            HelpEntry::new("Tab", "Show the directory tree, then move to its venvs, then hide it"),
+            HelpEntry::new("←", "Go up a directory while the tree has the keyboard"),
+            HelpEntry::new("Shift+Tab", "Highlight the next archived .venv"),
+            HelpEntry::new("u", "Restore the highlighted archived .venv"),
+            HelpEntry::new("X", "Delete the highlighted archive (after confirmation)"),
+            HelpEntry::new("r", "Refresh list"),
+        ],
+    },
+    HelpSection {
+        title: "Other",
+        entries: &[
+            HelpEntry::new("h/F1", "Show this help"),
+            HelpEntry::new("i", "Show cleanup recommendations with the commands to run"),
+            HelpEntry::new("L", "Show/hide the log pane (-v/-vv log more)"),
+            HelpEntry::new("!", "Show the notification history (warnings stay in the status bar over newer messages)"),
+            HelpEntry::new("q/Esc", "Quit application"),
+        ],
+    },
+];
+
+/// Status bar hints of the TUI while something is selected
+pub const TUI_HINTS_WITH_SELECTION: &[HelpEntry] = &[
+    HelpEntry::new("h", "Help"),
+    HelpEntry::new("r", "Refresh"),
+    HelpEntry::new("Space", "Toggle"),
+    HelpEntry::new("x", "Delete"),
+    HelpEntry::new("s", "Sort"),
+    HelpEntry::new("o", "Open"),
+    HelpEntry::new("e", "Edit"),
+    HelpEntry::new("m", "Slim"),
+    HelpEntry::new("t", "Shell"),
+    HelpEntry::new("Ctrl+A", "All"),
+    HelpEntry::new("Ctrl+D", "None"),
+    HelpEntry::new("q", "Quit"),
+];
+
+/// Status bar hints of the TUI while nothing is selected
+pub const TUI_HINTS_BROWSING: &[HelpEntry] = &[
+    HelpEntry::new("h", "Help"),
+    HelpEntry::new("r", "Refresh"),
+    HelpEntry::new("Space", "Select"),
+    HelpEntry::new("s", "Sort"),
+    HelpEntry::new("g", "Group"),
+    HelpEntry::new("Tab", "Tree"),
+    HelpEntry::new("o", "Open"),
+    HelpEntry::new("e", "Edit"),
+    HelpEntry::new("t", "Shell"),
+    HelpEntry::new("Ctrl+A", "Select All"),
+    HelpEntry::new("q", "Quit"),
+];
+
+/// Status bar hints of the TUI in a read-only session (`--read-only`)
+pub const TUI_HINTS_READ_ONLY: &[HelpEntry] = &[
+    HelpEntry::new("h", "Help"),
+    HelpEntry::new("r", "Refresh"),
+    HelpEntry::new("s", "Sort"),
+    HelpEntry::new("g", "Group"),
+    HelpEntry::new("Tab", "Tree"),
+    HelpEntry::new("o", "Open"),
+    HelpEntry::new("E", "Export"),
+    HelpEntry::new("t", "Shell"),
+    HelpEntry::new("q", "Quit"),
+];
+
+/// Status bar hints of the TUI in other states
+pub const TUI_HINTS_DEFAULT: &[HelpEntry] = &[
+    HelpEntry::new("h", "Help"),
+    HelpEntry::new("r", "Refresh"),
+    HelpEntry::new("Space", "Select"),
+    HelpEntry::new("x", "Delete"),
+    HelpEntry::new("s", "Sort"),
+    HelpEntry::new("o", "Open"),
+    HelpEntry::new("t", "Shell"),
+    HelpEntry::new("q", "Quit"),
+];
+
+/// How to use the GUI's main window
+pub const GUI_USAGE: &[&str] = &[
+    "Use the table to view all .venv directories found",
+    "Click checkboxes or rows to select directories for deletion",
+    "Use the search box to filter directories",
+    "Sort by different criteria using the dropdown",
+    "Click 'Delete Selected' to remove chosen directories",
+    "Archived .venv directories are listed in the 📦 Archived section, where they can be restored or their archives deleted",
+];
+
+/// Keyboard shortcuts of the GUI
+pub const GUI_KEYBOARD_SHORTCUTS: &[HelpEntry] = &[
+    HelpEntry::new("F1", "Show or hide this list of shortcuts"),
+    HelpEntry::new("F5", "Scan again"),
+    HelpEntry::new("Ctrl+A", "Select all directories"),
+    HelpEntry::new("Delete", "Delete selected directories (after confirmation)"),
+    HelpEntry::new("Esc", "Close the open dialog or window"),
+    HelpEntry::new("Ctrl +/-", "Zoom in/out (also under View → Zoom)"),
+    HelpEntry::new("Ctrl+0", "Reset the zoom"),
+    HelpEntry::new("Ctrl+L", "Show or hide the log panel (-v/-vv log more)"),
+];
+
+/// Mouse interactions of the GUI
+pub const GUI_MOUSE_ACTIONS: &[HelpEntry] = &[
+    HelpEntry::new("Click a row or checkbox", "Select or deselect the directory"),
+    HelpEntry::new("📁", "Open the folder in the file manager"),
+    HelpEntry::new("💻", "Open a terminal with the .venv activated"),
+    HelpEntry::new("📝", "Open the project in your editor (config `editor`, $EDITOR or VS Code)"),
+    HelpEntry::new("Drag a column border", "Resize the column (View → Reset Column Widths restores them)"),
+    HelpEntry::new("🔼/🔽", "Reverse the sort order"),
+];
+
+/// Join hints into a status bar line such as `h:Help r:Refresh`
+///
+/// # Arguments
+/// * `hints` - The hints to show
+///
+/// # Returns
+/// The hints as `keys:description` pairs separated by spaces
+pub fn hint_line(hints: &[HelpEntry]) -> String {
+    hints
+        .iter()
+        .map(|hint| format!("{}:{}", hint.keys, hint.description))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gui_shortcuts_cover_handled_keys() {
+        let keys: Vec<&str> = GUI_KEYBOARD_SHORTCUTS.iter().map(|entry| entry.keys).collect();
+        for key in ["F1", "F5", "Ctrl+A", "Delete", "Esc"] {
+            assert!(keys.contains(&key), "{} is not documented", key);
+        }
+    }
+
+    #[test]
+    fn test_entries_are_complete() {
+        let sections = TUI_SECTIONS.iter().flat_map(|section| section.entries);
+        for entry in STATUS_ICONS.iter().chain(GUI_KEYBOARD_SHORTCUTS).chain(GUI_MOUSE_ACTIONS).chain(sections) {
+            assert!(!entry.keys.is_empty());
+            assert!(!entry.description.is_empty());
+        }
+        assert_eq!(ABOUT.version, env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn test_hint_line() {
+        assert_eq!(hint_line(&TUI_HINTS_DEFAULT[..2]), "h:Help r:Refresh");
+        assert!(hint_line(TUI_HINTS_WITH_SELECTION).ends_with("Ctrl+D:None q:Quit"));
+        assert_eq!(hint_line(&[]), "");
+    }
+
+    #[test]
+    fn test_parse_credits() {
+        let credits = parse_credits("walkdir\t2.4.0\tUnlicense/MIT\nanyhow\t1.0.75\tMIT OR Apache-2.0\n\nbroken\n");
+        assert_eq!(credits.len(), 2);
+        assert_eq!(credits[0], ThirdPartyCredit { name: "anyhow", version: "1.0.75", license: "MIT OR Apache-2.0" });
+        assert_eq!(credits[1].name, "walkdir");
+        assert!(third_party_credits().iter().any(|credit| credit.name == "clap"));
+        assert!(!third_party_credits().iter().any(|credit| credit.name == env!("CARGO_PKG_NAME")));
+    }
+}
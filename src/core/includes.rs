@@ -0,0 +1,200 @@
+//! Subtrees a recursive scan is limited to (`--include`)
+//!
+//! Excludes prune a few directories from an otherwise complete walk; an
+//! include list turns that around when only a few subtrees matter, such as
+//! `--include '~/work/*/backend'`. Each pattern is matched one path
+//! component at a time, so the walk descends only into directories that
+//! can still lead to a match (`~/work`, then `~/work/shop`) and lists only
+//! environments below a directory that matches a whole pattern. `*`, `?`
+//! and `[...]` match within one component and `**` matches any number of
+//! them. Patterns starting with `/` or `~` are absolute; other patterns are
+//! relative to each scanned root.
+
+use std::path::{Component, Path, PathBuf};
+use globset::{Glob, GlobMatcher};
+
+use super::{Result, VenvCleanerError};
+
+/// One component of an include pattern
+#[derive(Debug, Clone)]
+enum Part {
+    /// `**`: any number of directories
+    AnyDepth,
+    /// A pattern for one directory name
+    Name(GlobMatcher),
+}
+
+/// How far a directory gets along a pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Progress {
+    /// The directory cannot lead to a match
+    Off,
+    /// A match may lie below the directory
+    Partial,
+    /// The directory is in a matching subtree
+    Inside,
+}
+
+/// A compiled include pattern
+#[derive(Debug, Clone)]
+struct IncludePattern {
+    /// The pattern as given
+    text: String,
+    /// Whether it is matched from the file system root rather than the scanned root
+    absolute: bool,
+    /// Its components, `~` expanded
+    parts: Vec<Part>,
+}
+
+/// The include patterns of a scan; an empty set includes everything
+#[derive(Debug, Clone, Default)]
+pub struct IncludeSet {
+    /// The compiled patterns
+    patterns: Vec<IncludePattern>,
+}
+
+impl IncludeSet {
+    /// Compile include patterns
+    ///
+    /// # Arguments
+    /// * `patterns` - The patterns, e.g. `~/work/*/backend` or `*/services`
+    ///
+    /// # Returns
+    /// The set, or an error naming the first invalid pattern
+    pub fn new(patterns: &[String]) -> Result<Self> {
+        let patterns = patterns.iter().map(|text| IncludePattern::new(text)).collect::<Result<_>>()?;
+        Ok(Self { patterns })
+    }
+
+    /// Check whether the set limits nothing
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Get the patterns as given
+    pub fn patterns(&self) -> impl Iterator<Item = &str> {
+        self.patterns.iter().map(|pattern| pattern.text.as_str())
+    }
+
+    /// Check whether the walk of a root has to enter a directory
+    ///
+    /// # Arguments
+    /// * `root` - The scanned root
+    /// * `dir` - A directory at or below the root
+    pub fn should_descend(&self, root: &Path, dir: &Path) -> bool {
+        self.best(root, dir) >= Progress::Partial
+    }
+
+    /// Check whether an environment found under a root is in an included subtree
+    ///
+    /// # Arguments
+    /// * `root` - The scanned root
+    /// * `path` - The environment's path
+    pub fn covers(&self, root: &Path, path: &Path) -> bool {
+        self.best(root, path) == Progress::Inside
+    }
+
+    /// Get the furthest any pattern gets along a path
+    fn best(&self, root: &Path, path: &Path) -> Progress {
+        if self.is_empty() {
+            return Progress::Inside;
+        }
+        self.patterns
+            .iter()
+            .map(|pattern| {
+                let names = if pattern.absolute { Some(path) } else { path.strip_prefix(root).ok() };
+                names.map_or(Progress::Off, |names| progress(&pattern.parts, &normal_names(names)))
+            })
+            .max()
+            .unwrap_or(Progress::Off)
+    }
+}
+
+impl IncludePattern {
+    /// Compile one pattern
+    fn new(text: &str) -> Result<Self> {
+        let invalid = |message: String| VenvCleanerError::InvalidArgument(format!("Invalid --include pattern `{}`: {}", text, message));
+        let expanded = match (text.strip_prefix('~'), dirs::home_dir()) {
+            (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => {
+                format!("{}{}", home.display(), rest)
+            }
+            _ => text.to_string(),
+        };
+        let parts = expanded
+            .split('/')
+            .filter(|part| !part.is_empty() && *part != ".")
+            .map(|part| match part {
+                "**" => Ok(Part::AnyDepth),
+                _ => Glob::new(part).map(|glob| Part::Name(glob.compile_matcher())).map_err(|e| invalid(e.to_string())),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if parts.is_empty() {
+            return Err(invalid("it names no directory".to_string()));
+        }
+        Ok(Self { text: text.to_string(), absolute: expanded.starts_with('/'), parts })
+    }
+}
+
+/// Get the directory names of a path, leaving out its root
+fn normal_names(path: &Path) -> Vec<PathBuf> {
+    path.components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(PathBuf::from(name)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Match directory names against pattern parts
+fn progress(parts: &[Part], names: &[PathBuf]) -> Progress {
+    match (parts.first(), names.first()) {
+        (None, _) => Progress::Inside,
+        (Some(_), None) if parts.iter().all(|part| matches!(part, Part::AnyDepth)) => Progress::Inside,
+        (Some(_), None) => Progress::Partial,
+        // `**` matches no directory here, or this one and maybe more
+        (Some(Part::AnyDepth), Some(_)) => progress(&parts[1..], names).max(progress(parts, &names[1..])),
+        (Some(Part::Name(glob)), Some(name)) if glob.is_match(name) => progress(&parts[1..], &names[1..]),
+        (Some(Part::Name(_)), Some(_)) => Progress::Off,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(patterns: &[&str]) -> IncludeSet {
+        IncludeSet::new(&patterns.iter().map(|p| p.to_string()).collect::<Vec<_>>()).unwrap()
+    }
+
+    #[test]
+    fn test_relative_and_absolute_patterns() {
+        let root = Path::new("/home/alice/work");
+        let relative = set(&["*/backend"]);
+        assert!(relative.should_descend(root, root));
+        assert!(relative.should_descend(root, &root.join("shop")));
+        assert!(!relative.should_descend(root, &root.join("shop/frontend")));
+        assert!(relative.covers(root, &root.join("shop/backend/.venv")));
+        assert!(!relative.covers(root, &root.join("shop/.venv")));
+
+        let absolute = set(&["/home/alice/work/*/backend"]);
+        assert!(absolute.should_descend(Path::new("/home"), Path::new("/home/alice")));
+        assert!(!absolute.should_descend(Path::new("/home"), Path::new("/home/bob")));
+        assert!(absolute.covers(Path::new("/home"), Path::new("/home/alice/work/shop/backend/api/.venv")));
+        // A root inside an included subtree is scanned as a whole
+        assert!(absolute.covers(Path::new("/home/alice/work/shop/backend/api"), Path::new("/home/alice/work/shop/backend/api/.venv")));
+    }
+
+    #[test]
+    fn test_any_depth_and_errors() {
+        let root = Path::new("/srv");
+        let deep = set(&["**/services", "tools"]);
+        assert!(deep.should_descend(root, Path::new("/srv/a/b/c")));
+        assert!(deep.covers(root, Path::new("/srv/a/b/services/x/.venv")));
+        assert!(deep.covers(root, Path::new("/srv/tools/.venv")));
+        assert!(!deep.covers(root, Path::new("/srv/a/.venv")));
+        assert!(IncludeSet::default().covers(root, Path::new("/srv/a/.venv")));
+
+        assert!(IncludeSet::new(&["work/[".to_string()]).is_err());
+        assert!(IncludeSet::new(&["/".to_string()]).is_err());
+    }
+}
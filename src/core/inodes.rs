@@ -0,0 +1,180 @@
+//! Inode usage of .venv directories
+//!
+//! A file system can run out of inodes long before it runs out of bytes,
+//! and .venv directories are a common cause: each one holds tens of
+//! thousands of small files. This module estimates how many inodes each
+//! .venv uses (from the file and directory counts made during the scan) and
+//! asks the kernel, through `statvfs`, how many inodes the file systems
+//! holding them have left.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use super::{mounts, VenvInfo};
+
+/// Share of free inodes below which a file system counts as running out
+pub const LOW_FREE_PERCENT: f64 = 10.0;
+
+/// Inode totals of one file system
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InodeUsage {
+    /// Inodes the file system has
+    total: u64,
+    /// Inodes still free for unprivileged users
+    free: u64,
+}
+
+impl InodeUsage {
+    /// Create usage from known totals
+    pub fn new(total: u64, free: u64) -> Self {
+        Self { total, free: free.min(total) }
+    }
+
+    /// Ask the kernel for the inode totals of the file system a path lives on
+    ///
+    /// # Returns
+    /// The usage, or None where it is unknown; file systems that allocate
+    /// inodes dynamically (btrfs, ZFS) report no fixed total and give None too
+    #[cfg(unix)]
+    pub fn of(path: &Path) -> Option<Self> {
+        use std::ffi::CString;
+        use std::os::unix::ffi::OsStrExt;
+
+        let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+        // SAFETY: statvfs is plain old data the kernel fills in
+        let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+        // SAFETY: c_path is NUL-terminated and stat is a valid, writable statvfs
+        if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 || stat.f_files == 0 {
+            return None;
+        }
+        #[allow(clippy::unnecessary_cast)]
+        Some(Self::new(stat.f_files as u64, stat.f_favail as u64))
+    }
+
+    #[cfg(not(unix))]
+    pub fn of(_path: &Path) -> Option<Self> {
+        None
+    }
+
+    /// Get the number of inodes on the file system
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Get the number of free inodes
+    pub fn free(&self) -> u64 {
+        self.free
+    }
+
+    /// Get the share of free inodes, in percent
+    pub fn free_percent(&self) -> f64 {
+        self.free as f64 * 100.0 / self.total.max(1) as f64
+    }
+
+    /// Check whether the file system is running out of inodes
+    pub fn is_low(&self) -> bool {
+        self.free_percent() < LOW_FREE_PERCENT
+    }
+}
+
+/// The .venv directories on one file system and the inodes they use
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilesystemInodes {
+    /// Mount point of the file system, or the first .venv's project where it is unknown
+    pub mount_point: PathBuf,
+    /// Inode totals of the file system, if known
+    pub usage: Option<InodeUsage>,
+    /// Number of .venv directories on it
+    pub venv_count: usize,
+    /// Inodes used by those .venv directories (counted ones only)
+    pub venv_inodes: u64,
+}
+
+impl FilesystemInodes {
+    /// Get the share of the file system's inodes used by .venv directories, in percent
+    pub fn venv_percent(&self) -> Option<f64> {
+        self.usage.map(|usage| self.venv_inodes as f64 * 100.0 / usage.total().max(1) as f64)
+    }
+}
+
+/// Total the inode use of .venv directories per file system
+///
+/// # Arguments
+/// * `venvs` - The scanned .venv directories
+///
+/// # Returns
+/// One entry per file system, in mount point order
+pub fn by_filesystem(venvs: &[VenvInfo]) -> Vec<FilesystemInodes> {
+    let mount_table = std::fs::read_to_string("/proc/self/mounts")
+        .map(|table| mounts::parse_mount_table(&table))
+        .unwrap_or_default();
+
+    let mut groups: BTreeMap<u64, FilesystemInodes> = BTreeMap::new();
+    for venv in venvs {
+        let group = groups.entry(device_of(venv.path())).or_insert_with(|| {
+            let path = venv.path().canonicalize().unwrap_or_else(|_| venv.path().to_path_buf());
+            let mount_point = mounts::find_mount(&mount_table, &path)
+                .map(|entry| entry.mount_point.clone())
+                .unwrap_or_else(|| venv.parent_path().unwrap_or(venv.path()).to_path_buf());
+            FilesystemInodes { mount_point, usage: InodeUsage::of(venv.path()), venv_count: 0, venv_inodes: 0 }
+        });
+        group.venv_count += 1;
+        group.venv_inodes += venv.inode_count().unwrap_or(0);
+    }
+
+    let mut filesystems: Vec<FilesystemInodes> = groups.into_values().collect();
+    filesystems.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+    filesystems
+}
+
+/// Get the device a path lives on, telling file systems apart
+#[cfg(unix)]
+fn device_of(path: &Path) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    std::fs::symlink_metadata(path).map(|metadata| metadata.dev()).unwrap_or(0)
+}
+
+#[cfg(not(unix))]
+fn device_of(_path: &Path) -> u64 {
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ByteSize;
+    use chrono::Utc;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_inode_usage_percentages() {
+        let usage = InodeUsage::new(1000, 50);
+        assert_eq!(usage.free_percent(), 5.0);
+        assert!(usage.is_low());
+        assert!(!InodeUsage::new(1000, 500).is_low());
+        // Free can never exceed the total
+        assert_eq!(InodeUsage::new(10, 20).free(), 10);
+    }
+
+    #[test]
+    fn test_by_filesystem_totals_venvs() {
+        let temp_dir = TempDir::new().unwrap();
+        let venvs: Vec<VenvInfo> = ["a", "b"]
+            .iter()
+            .map(|project| {
+                let path = temp_dir.path().join(project).join(".venv");
+                fs::create_dir_all(&path).unwrap();
+                VenvInfo::new(path, ByteSize::new(0), Utc::now(), Utc::now()).with_item_counts(Some((10, 2)))
+            })
+            .collect();
+
+        let filesystems = by_filesystem(&venvs);
+        assert_eq!(filesystems.len(), 1);
+        assert_eq!(filesystems[0].venv_count, 2);
+        assert_eq!(filesystems[0].venv_inodes, 26);
+        if let Some(usage) = filesystems[0].usage {
+            assert!(usage.total() >= usage.free());
+        }
+    }
+}
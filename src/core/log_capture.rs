@@ -0,0 +1,202 @@
+//! In-app capture of log lines for the TUI and GUI
+//!
+//! The interactive modes own the terminal or window, so log output written
+//! to stderr would either garble the screen or never be seen. The logger
+//! installed for them sends each record over a channel instead, and the
+//! interfaces drain it into a `LogBuffer` that their log panes draw.
+//! `--verbose` chooses how much is captured, as it does for the CLI.
+
+use std::collections::VecDeque;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+use chrono::{DateTime, Utc};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+use super::{timestamps, Result, VenvCleanerError};
+
+/// Number of lines a log pane keeps
+pub const MAX_LOG_LINES: usize = 500;
+
+/// A captured log record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogLine {
+    /// When the record was logged
+    pub timestamp: DateTime<Utc>,
+    /// Its severity
+    pub level: Level,
+    /// The module that logged it
+    pub target: String,
+    /// The message
+    pub message: String,
+}
+
+impl LogLine {
+    /// Format the line as `HH:MM:SS LEVEL message`
+    pub fn formatted(&self) -> String {
+        format!("{} {:<5} {}", timestamps::format_time(&self.timestamp), self.level, self.message)
+    }
+}
+
+/// Get the captured level for a `--verbose` count
+///
+/// # Arguments
+/// * `verbosity` - Number of times `--verbose` was given
+///
+/// # Returns
+/// Warnings only by default, then info, debug and trace
+pub fn level_for_verbosity(verbosity: u8) -> LevelFilter {
+    match verbosity {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Info,
+        2 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
+
+/// Logger that forwards records over a channel
+struct ChannelLogger {
+    /// Where records are sent
+    sender: Mutex<Sender<LogLine>>,
+    /// Most verbose level forwarded
+    level: LevelFilter,
+}
+
+impl Log for ChannelLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        // Below warnings, only VenvCleaner's own records; the UI toolkits are chatty
+        metadata.level() <= self.level
+            && (metadata.level() <= Level::Warn || metadata.target().starts_with("venv_cleaner"))
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let line = LogLine {
+            timestamp: Utc::now(),
+            level: record.level(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+        };
+        if let Ok(sender) = self.sender.lock() {
+            // The pane may be gone while the interface shuts down
+            let _ = sender.send(line);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Install the channel logger as the global logger
+///
+/// # Arguments
+/// * `verbosity` - Number of times `--verbose` was given
+///
+/// # Returns
+/// The receiving end of the captured lines, or an error if a logger is already installed
+pub fn install(verbosity: u8) -> Result<Receiver<LogLine>> {
+    let (sender, receiver) = mpsc::channel();
+    let level = level_for_verbosity(verbosity);
+    log::set_boxed_logger(Box::new(ChannelLogger { sender: Mutex::new(sender), level }))
+        .map_err(|e| VenvCleanerError::Io(format!("Failed to capture log output: {}", e)))?;
+    log::set_max_level(level);
+    Ok(receiver)
+}
+
+/// The most recent captured lines, as shown by a log pane
+#[derive(Debug, Default)]
+pub struct LogBuffer {
+    /// Lines kept, oldest first
+    lines: VecDeque<LogLine>,
+    /// Source of new lines, if logging is captured
+    receiver: Option<Receiver<LogLine>>,
+}
+
+impl LogBuffer {
+    /// Create a buffer fed by a channel
+    pub fn new(receiver: Receiver<LogLine>) -> Self {
+        Self { lines: VecDeque::new(), receiver: Some(receiver) }
+    }
+
+    /// Move newly captured lines into the buffer, dropping the oldest past `MAX_LOG_LINES`
+    ///
+    /// # Returns
+    /// Number of new lines
+    pub fn drain(&mut self) -> usize {
+        let Some(receiver) = &self.receiver else {
+            return 0;
+        };
+        let mut count = 0;
+        for line in receiver.try_iter() {
+            if self.lines.len() == MAX_LOG_LINES {
+                self.lines.pop_front();
+            }
+            self.lines.push_back(line);
+            count += 1;
+        }
+        count
+    }
+
+    /// Get the kept lines, oldest first
+    pub fn lines(&self) -> impl DoubleEndedIterator<Item = &LogLine> + ExactSizeIterator {
+        self.lines.iter()
+    }
+
+    /// Get the number of kept warnings and errors
+    pub fn warning_count(&self) -> usize {
+        self.lines.iter().filter(|line| line.level <= Level::Warn).count()
+    }
+
+    /// Check whether lines are being captured at all
+    pub fn is_capturing(&self) -> bool {
+        self.receiver.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record_through(logger: &ChannelLogger, level: Level, target: &str) {
+        logger.log(&Record::builder().level(level).target(target).args(format_args!("hello")).build());
+    }
+
+    #[test]
+    fn test_level_for_verbosity() {
+        assert_eq!(level_for_verbosity(0), LevelFilter::Warn);
+        assert_eq!(level_for_verbosity(1), LevelFilter::Info);
+        assert_eq!(level_for_verbosity(2), LevelFilter::Debug);
+        assert_eq!(level_for_verbosity(9), LevelFilter::Trace);
+    }
+
+    #[test]
+    fn test_logger_filters_by_level_and_target() {
+        let (sender, receiver) = mpsc::channel();
+        let logger = ChannelLogger { sender: Mutex::new(sender), level: LevelFilter::Info };
+        record_through(&logger, Level::Info, "venv_cleaner::core");
+        record_through(&logger, Level::Debug, "venv_cleaner::core");
+        record_through(&logger, Level::Info, "eframe");
+        record_through(&logger, Level::Warn, "eframe");
+
+        let mut buffer = LogBuffer::new(receiver);
+        assert_eq!(buffer.drain(), 2);
+        let targets: Vec<&str> = buffer.lines().map(|line| line.target.as_str()).collect();
+        assert_eq!(targets, vec!["venv_cleaner::core", "eframe"]);
+        assert_eq!(buffer.warning_count(), 1);
+        assert!(buffer.lines().next().unwrap().formatted().ends_with("INFO  hello"));
+    }
+
+    #[test]
+    fn test_buffer_keeps_the_latest_lines() {
+        let (sender, receiver) = mpsc::channel();
+        let logger = ChannelLogger { sender: Mutex::new(sender), level: LevelFilter::Warn };
+        for _ in 0..MAX_LOG_LINES + 5 {
+            record_through(&logger, Level::Warn, "venv_cleaner");
+        }
+
+        let mut buffer = LogBuffer::new(receiver);
+        assert_eq!(buffer.drain(), MAX_LOG_LINES + 5);
+        assert_eq!(buffer.lines().len(), MAX_LOG_LINES);
+        assert!(!LogBuffer::default().is_capturing());
+    }
+}
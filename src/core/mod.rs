@@ -3,19 +3,143 @@
 //! This module contains the main VenvCleaner struct and core functionality
 //! for finding, analyzing, and managing Python virtual environment folders.
 
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::fs;
-use std::time::SystemTime;
-use walkdir::WalkDir;
-use chrono::{DateTime, Local};
+use std::ops::ControlFlow;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+use chrono::{DateTime, Utc};
 use thiserror::Error;
-use log::{debug, info, warn};
+use tracing::{debug, debug_span, info, info_span, warn, Span};
 
 pub mod venv_info;
+pub mod activity;
+#[cfg(feature = "async")]
+pub mod async_api;
+pub mod archive;
+pub mod audit;
 pub mod file_utils;
+pub mod fs_backend;
+pub mod git;
+pub mod help;
+pub mod includes;
+pub mod inodes;
+pub mod log_capture;
+pub mod artifacts;
+pub mod batch;
+pub mod breakdown;
+pub mod byte_size;
+pub mod cancellation;
+pub mod concurrency;
+pub mod confirmation;
+pub mod config;
+pub mod deletion_eta;
+pub mod detectors;
+pub mod decision_hook;
+pub mod delete_hooks;
+pub mod delete_mode;
+pub mod editor;
+pub mod escalation;
+pub mod export;
+pub mod mounts;
+pub mod offline;
+pub mod ownership;
+pub mod paths;
+pub mod peek;
+pub mod pending_delete;
+pub mod permissions;
+pub mod platform;
+pub mod policy_simulation;
+pub mod progress;
+pub mod project_kind;
+pub mod python_version;
+pub mod recommendations;
+pub mod report_merge;
+pub mod roots;
+pub mod run_lock;
+pub mod run_summary;
+pub mod safety;
+pub mod scan_checkpoint;
+pub mod scan_diff;
+pub mod scan_metadata;
+pub mod scan_priority;
+pub mod scan_stats;
+pub mod shell;
+pub mod site_packages;
+pub mod size_deltas;
+pub mod size_tree;
+pub mod size_history;
+pub mod slim;
+pub mod sorting;
+pub mod stats;
+pub mod summary_cache;
+pub mod telemetry;
+pub mod temp_venvs;
+pub mod timestamps;
+pub mod trash_ledger;
+pub mod user_data;
+pub mod venv_filter;
 
-pub use venv_info::VenvInfo;
+pub use venv_info::{AgeBucket, VenvInfo};
 pub use file_utils::FileUtils;
+pub use fs_backend::{DirectoryUsage, FsBackend, FsEntry, FsEntryKind, FsMetadata, FsOp, MemoryFs, RealFs, WalkEntry, WalkError};
+pub use escalation::EscalationHelper;
+pub use export::{ExportFormat, Reporter};
+pub use mounts::FilesystemInfo;
+pub use offline::{DeletionPlan, OfflineIndex, PlannedDeletion};
+pub use ownership::OwnerFilter;
+pub use activity::ActivityCache;
+#[cfg(feature = "async")]
+pub use async_api::{DeleteTask, ScanTask, TaskHandle};
+pub use audit::{AuditAction, AuditEntry, AuditLog, AuditVerification};
+pub use archive::{ArchiveStub, ArchivedVenv, VenvArchiver};
+pub use artifacts::{ProjectArtifact, ProjectArtifacts};
+pub use batch::{BatchItem, BatchOptions, BatchResult};
+pub use breakdown::{SizeBreakdown, SizeCategory};
+pub use size_deltas::{SizeCache, SizeDeltas};
+pub use size_tree::{SizeNode, SizeNodeKind, SizeTree};
+pub use byte_size::ByteSize;
+pub use cancellation::CancellationToken;
+pub use deletion_eta::DeletionEstimator;
+pub use confirmation::{AutoConfirm, ConfirmationProvider, ConfirmationRequest};
+pub use detectors::Detector;
+#[cfg(feature = "decision-hook")]
+pub use decision_hook::DecisionHook;
+pub use decision_hook::HookDecision;
+pub use delete_hooks::DeleteHooks;
+pub use delete_mode::DeleteMode;
+pub use help::HelpEntry;
+pub use includes::IncludeSet;
+pub use inodes::{FilesystemInodes, InodeUsage};
+pub use log_capture::{LogBuffer, LogLine};
+pub use config::{ColorOverrides, Config, DetectorConfig, PolicyConfig, ScanConfig, TuiConfig};
+pub use slim::{SlimReport, VenvSlimmer};
+pub use sorting::{SortKey, SortOrder};
+pub use peek::{PeekEntry, VenvPeek};
+pub use pending_delete::{BackgroundRemovals, PendingDeletion};
+pub use policy_simulation::{PolicySimulation, SimulationRule, SimulationRules};
+pub use permissions::{PermissionProblem, PermissionReport};
+pub use progress::ProgressEvent;
+pub use project_kind::ProjectKind;
+pub use python_version::PythonVersion;
+pub use recommendations::{Recommendation, RecommendationHistory, RecommendationKind};
+pub use report_merge::{HostSummary, MergedReport, MergedVenv};
+pub use roots::RootSummary;
+pub use run_lock::{LockMode, RunLock};
+pub use run_summary::RunSummary;
+pub use scan_checkpoint::{ScanCheckpoint, ScanCheckpointer};
+pub use scan_diff::{GrownVenv, ScanDiff, ScanSnapshot};
+pub use scan_metadata::ScanMetadata;
+pub use scan_stats::{DirectoryScanStats, ScanTimer};
+pub use size_history::{GrowthProjection, SizeHistory};
+pub use stats::{PackageGroup, PythonVersionGroup, VenvStats};
+pub use summary_cache::ScanSummary;
+pub use telemetry::{CrashReport, CrashReporter};
+pub use trash_ledger::{PurgeReport, TrashLedger, TrashedVenv};
+pub use user_data::UserDataFile;
+pub use venv_filter::VenvFilter;
 
 /// Custom error types for VenvCleaner operations
 #[derive(Error, Debug, Clone)]
@@ -29,6 +153,9 @@ pub enum VenvCleanerError {
     #[error("Permission denied: {path}")]
     PermissionDenied { path: String },
 
+    #[error("Permission denied while removing {path}; what is left was renamed to {}", .marked.display())]
+    PartlyRemoved { path: String, marked: PathBuf },
+
     #[error("Feature not available: {0}")]
     FeatureNotAvailable(String),
 
@@ -41,10 +168,71 @@ pub enum VenvCleanerError {
     #[error("No .venv folders found in the specified directory")]
     NoVenvFound,
 
+    #[error("{path} no longer exists (it was removed outside VenvCleaner)")]
+    VenvVanished { path: String },
+
+    #[error("{path} is on {volume}, which is no longer available (the drive was unplugged or unmounted)")]
+    VolumeUnavailable { path: String, volume: String },
+
+    #[error("Invalid config file {path}: {message}")]
+    ConfigError { path: String, message: String },
+
+    #[error("Audit log {path} is broken at line {line}: {message}")]
+    AuditChainBroken { path: String, line: usize, message: String },
+
+    #[error("Blocked by policy: {0}")]
+    PolicyViolation(String),
+
+    #[error("Hook `{command}` failed: {message}")]
+    HookFailed { command: String, message: String },
+
+    #[error(
+        "Another instance{} is cleaning {root}; pass --wait to wait for it or --force-lock to run anyway",
+        .pid.map(|pid| format!(" (pid {})", pid)).unwrap_or_default()
+    )]
+    AlreadyRunning { root: String, pid: Option<u32> },
+
+    #[error("Refusing to delete {path}: it is inside {location}, which belongs to a system Python")]
+    ProtectedSystemPath { path: String, location: String },
+
+    #[error("Refusing to delete {path}: it is inside the system directory {location}")]
+    SystemDirectory { path: String, location: String },
+
+    #[error("Refusing to delete {path}: it belongs to {owner}; pass --all-users or --user to delete other users' environments")]
+    OwnedByOtherUser { path: String, owner: String },
+
+    #[error("Full Disk Access required to read {path}")]
+    FullDiskAccessRequired { path: String },
+
     #[error("Multiple errors occurred: {0:?}")]
     MultipleErrors(Vec<VenvCleanerError>),
 }
 
+impl VenvCleanerError {
+    /// Check whether the error means the .venv disappeared after the scan
+    pub fn is_vanished(&self) -> bool {
+        matches!(self, VenvCleanerError::VenvVanished { .. })
+    }
+
+    /// Check whether the error means the drive holding the .venv is gone
+    pub fn is_volume_unavailable(&self) -> bool {
+        matches!(self, VenvCleanerError::VolumeUnavailable { .. })
+    }
+
+    /// Check whether the error means the current user may not delete the .venv
+    pub fn is_permission_denied(&self) -> bool {
+        matches!(self, VenvCleanerError::PermissionDenied { .. } | VenvCleanerError::PartlyRemoved { .. })
+    }
+
+    /// Get the directory a deletion denied partway renamed the .venv to, if it did
+    pub fn marked_path(&self) -> Option<&Path> {
+        match self {
+            VenvCleanerError::PartlyRemoved { marked, .. } => Some(marked),
+            _ => None,
+        }
+    }
+}
+
 impl From<std::io::Error> for VenvCleanerError {
     fn from(error: std::io::Error) -> Self {
         VenvCleanerError::Io(error.to_string())
@@ -55,6 +243,7 @@ impl From<std::io::Error> for VenvCleanerError {
 pub type Result<T> = std::result::Result<T, VenvCleanerError>;
 
 /// Main VenvCleaner struct that handles all core operations
+#[derive(Debug, Clone)]
 pub struct VenvCleaner {
     /// The base directory to search from
     base_directory: PathBuf,
@@ -66,6 +255,76 @@ pub struct VenvCleaner {
     dry_run: bool,
     /// Verbosity level (0 = quiet, 1 = normal, 2+ = verbose)
     verbosity: u8,
+    /// Whether to move directories to the system trash instead of deleting them
+    use_trash: bool,
+    /// The deletion mode asked for, which `use_trash` was decided from
+    delete_mode: DeleteMode,
+    /// Whether to walk likely project roots before other directories
+    priority_scan: bool,
+    /// Whether recursive scans skip paths ignored by .gitignore rules
+    respect_gitignore: bool,
+    /// Whether recursive scans skip hidden directories other than `.venv`
+    skip_hidden: bool,
+    /// Whether folders named .venv must look like virtual environments to be found
+    validate: bool,
+    /// Directories recursive scans never enter
+    excludes: Vec<PathBuf>,
+    /// Subtrees recursive scans are limited to, if any
+    includes: IncludeSet,
+    /// System directories below the base directory, which scans never enter either
+    system_excludes: Vec<PathBuf>,
+    /// Environment layouts found besides `.venv`
+    detectors: Vec<Detector>,
+    /// Commands run before and after each deletion
+    delete_hooks: DeleteHooks,
+    /// Archive .venv directories into this directory instead of deleting them
+    archive_dir: Option<PathBuf>,
+    /// Stop sizing a single .venv after this long, reporting a partial size
+    size_timeout: Option<Duration>,
+    /// Number of threads sizing .venv directories during a scan
+    scan_threads: usize,
+    /// Number of .venv directories deleted at once by `delete_venv_directories`
+    delete_jobs: usize,
+    /// The file system the base directory lives on, if known
+    filesystem: Option<FilesystemInfo>,
+    /// Restrictions from the system policy and user config
+    policy: PolicyConfig,
+    /// Further directories scanned after the base directory
+    extra_roots: Vec<PathBuf>,
+    /// System temp directories scanned for abandoned environments of any name
+    temp_roots: Vec<PathBuf>,
+    /// Whether a root that cannot be searched leaves the other roots' results standing
+    partial_roots: bool,
+    /// Roots the last scan could not search, with the reason
+    failed_roots: Arc<Mutex<Vec<(PathBuf, VenvCleanerError)>>>,
+    /// Whose .venv directories scans keep
+    owner_filter: OwnerFilter,
+    /// Age and size criteria scans keep .venv directories by
+    venv_filter: VenvFilter,
+    /// Per-directory timings of the last scan, shared with the per-root copies
+    scan_stats: Arc<Mutex<Vec<DirectoryScanStats>>>,
+    /// Saved scan listed instead of the disk, with deletions only planned
+    offline: Option<OfflineIndex>,
+    /// Directories the last scan could not read, shared with the per-root copies
+    unreadable: Arc<Mutex<Vec<PathBuf>>>,
+    /// Folders named .venv the last scan skipped as not virtual environments, shared likewise
+    invalid_venvs: Arc<Mutex<Vec<PathBuf>>>,
+    /// Archive stub files the last scan walked past, shared likewise
+    archive_stubs: Arc<Mutex<Vec<PathBuf>>>,
+    /// Stops scans and batch deletions early once cancelled, shared with the per-root copies
+    cancellation: CancellationToken,
+    /// Larger .venv directories are only deleted after an interactive confirmation
+    exclude_size_above: Option<ByteSize>,
+    /// Directory recursive scans write their checkpoints to, None for no checkpoints
+    checkpoint_dir: Option<PathBuf>,
+    /// Whether recursive scans resume from their checkpoint
+    resume: bool,
+    /// Whether scans only report .venv directories as found, keeping none of them
+    low_memory: bool,
+    /// The file system scans walk and deletions remove from
+    fs: Arc<dyn FsBackend>,
+    /// Removals of renamed .venv directories running in the background, None to remove inline
+    background_removals: Option<BackgroundRemovals>,
 }
 
 impl VenvCleaner {
@@ -78,6 +337,11 @@ impl VenvCleaner {
     /// * `dry_run` - Whether to perform a dry run without making changes
     /// * `verbosity` - Verbosity level for output
     ///
+    /// Priority scanning is enabled automatically for broad roots such as
+    /// the home directory (see `scan_priority::is_broad_root`). Scan and
+    /// deletion parallelism default to the CPU count, lower on network mounts
+    /// (see the `concurrency` module).
+    ///
     /// # Returns
     /// A new VenvCleaner instance
     pub fn new(
@@ -87,219 +351,2897 @@ impl VenvCleaner {
         dry_run: bool,
         verbosity: u8,
     ) -> Self {
+        let priority_scan = scan_priority::is_broad_root(&base_directory);
+        let system_excludes = safety::system_paths_below(&base_directory);
+        let filesystem = mounts::filesystem_info(&base_directory);
+        let network = filesystem.as_ref().map(FilesystemInfo::is_network).unwrap_or(false);
+        if let Some(filesystem) = &filesystem {
+            debug!("{} is on {}", base_directory.display(), filesystem.describe());
+        }
+
         Self {
             base_directory,
             recursive,
             force_mode,
             dry_run,
             verbosity,
+            use_trash: false,
+            delete_mode: DeleteMode::Permanent,
+            priority_scan,
+            respect_gitignore: false,
+            skip_hidden: false,
+            validate: true,
+            excludes: Vec::new(),
+            includes: IncludeSet::default(),
+            system_excludes,
+            detectors: Vec::new(),
+            delete_hooks: DeleteHooks::default(),
+            archive_dir: None,
+            size_timeout: None,
+            scan_threads: concurrency::default_scan_threads(network),
+            delete_jobs: concurrency::default_delete_jobs(network),
+            filesystem,
+            policy: PolicyConfig::default(),
+            extra_roots: Vec::new(),
+            temp_roots: Vec::new(),
+            partial_roots: false,
+            failed_roots: Arc::default(),
+            owner_filter: OwnerFilter::default(),
+            venv_filter: VenvFilter::default(),
+            scan_stats: Arc::default(),
+            unreadable: Arc::default(),
+            invalid_venvs: Arc::default(),
+            archive_stubs: Arc::default(),
+            offline: None,
+            cancellation: CancellationToken::default(),
+            exclude_size_above: Some(safety::DEFAULT_EXCLUDE_SIZE_ABOVE),
+            checkpoint_dir: None,
+            resume: false,
+            low_memory: false,
+            fs: Arc::new(RealFs),
+            background_removals: None,
         }
     }
 
-    /// Find all .venv directories in the specified path
+    /// Move deleted directories to the system trash instead of removing them
+    ///
+    /// # Arguments
+    /// * `use_trash` - Whether to use the trash
+    ///
+    /// The trash stays on when the policy requires it.
     ///
     /// # Returns
-    /// A vector of VenvInfo structs containing information about found .venv directories
-    pub fn find_venv_directories(&self) -> Result<Vec<VenvInfo>> {
-        info!("Searching for .venv directories in: {}", self.base_directory.display());
+    /// The VenvCleaner with the trash setting applied
+    pub fn with_trash(self, use_trash: bool) -> Self {
+        self.with_delete_mode(if use_trash { DeleteMode::Trash } else { DeleteMode::Permanent })
+    }
 
-        let mut venv_dirs = Vec::new();
-        let mut errors = Vec::new();
+    /// Choose how deleted directories are removed
+    ///
+    /// A new VenvCleaner deletes permanently; the CLI, TUI and GUI start out
+    /// with `DeleteMode::TrashIfAvailable` unless configured otherwise.
+    ///
+    /// # Arguments
+    /// * `mode` - The deletion mode; the trash stays on when the policy requires it
+    ///
+    /// # Returns
+    /// The VenvCleaner with the mode applied
+    pub fn with_delete_mode(mut self, mode: DeleteMode) -> Self {
+        self.delete_mode = mode;
+        self.use_trash = mode.uses_trash(self.filesystem.as_ref()) || self.policy.require_trash;
+        self
+    }
 
-        if self.recursive {
-            // Recursive search using walkdir
-            for entry in WalkDir::new(&self.base_directory)
-                .follow_links(false)
-                .into_iter()
-                .filter_map(|e| e.ok())
-            {
-                if entry.file_type().is_dir() && entry.file_name() == ".venv" {
-                    match self.analyze_venv_directory(entry.path()) {
-                        Ok(venv_info) => {
-                            debug!("Found .venv at: {}", entry.path().display());
-                            venv_dirs.push(venv_info);
-                        }
-                        Err(e) => {
-                            warn!("Error analyzing .venv at {}: {}", entry.path().display(), e);
-                            errors.push(e);
-                        }
-                    }
-                }
-            }
-        } else {
-            // Non-recursive search - only check the base directory
-            let venv_path = self.base_directory.join(".venv");
-            if venv_path.exists() && venv_path.is_dir() {
-                match self.analyze_venv_directory(&venv_path) {
-                    Ok(venv_info) => {
-                        debug!("Found .venv at: {}", venv_path.display());
-                        venv_dirs.push(venv_info);
-                    }
-                    Err(e) => {
-                        warn!("Error analyzing .venv at {}: {}", venv_path.display(), e);
-                        errors.push(e);
-                    }
-                }
-            }
-        }
+    /// Apply deletion restrictions (see `PolicyConfig`)
+    ///
+    /// # Arguments
+    /// * `policy` - Protected paths, mandatory trash and the force-delete size limit
+    ///
+    /// # Returns
+    /// The VenvCleaner with the policy applied
+    pub fn with_policy(mut self, policy: PolicyConfig) -> Self {
+        self.use_trash |= policy.require_trash;
+        self.policy = policy;
+        self
+    }
 
-        if venv_dirs.is_empty() && errors.is_empty() {
-            return Err(VenvCleanerError::NoVenvFound);
-        }
+    /// Walk likely project roots first (or not), overriding the automatic choice
+    ///
+    /// # Arguments
+    /// * `priority_scan` - Whether to order the walk by `scan_priority::directory_priority`
+    ///
+    /// # Returns
+    /// The VenvCleaner with the setting applied
+    pub fn with_priority_scan(mut self, priority_scan: bool) -> Self {
+        self.priority_scan = priority_scan;
+        self
+    }
 
-        if !errors.is_empty() && self.verbosity > 0 {
-            warn!("Encountered {} errors while searching", errors.len());
-        }
+    /// Skip paths ignored by .gitignore, .ignore and global git excludes during recursive scans
+    ///
+    /// # Arguments
+    /// * `respect_gitignore` - Whether to honour ignore rules
+    ///
+    /// # Returns
+    /// The VenvCleaner with the setting applied
+    pub fn with_gitignore(mut self, respect_gitignore: bool) -> Self {
+        self.respect_gitignore = respect_gitignore;
+        self
+    }
 
-        Ok(venv_dirs)
+    /// Skip hidden directories (`.cache`, `.cargo`, `.local`, ...) during recursive scans
+    ///
+    /// `.venv` directories themselves are still found; only the walk into
+    /// other dot-directories below the scanned root is pruned.
+    ///
+    /// # Arguments
+    /// * `skip_hidden` - Whether to skip hidden directories
+    ///
+    /// # Returns
+    /// The VenvCleaner with the setting applied
+    pub fn with_skip_hidden(mut self, skip_hidden: bool) -> Self {
+        self.skip_hidden = skip_hidden;
+        self
     }
 
-    /// Analyze a single .venv directory and create a VenvInfo struct
+    /// Choose whether folders named .venv must look like virtual environments to be found
+    ///
+    /// With validation on (the default) a .venv needs at least two of `bin`,
+    /// `Scripts`, `lib`, `include` and `pyvenv.cfg` (see
+    /// `FileUtils::is_valid_venv_directory`); data folders that merely share
+    /// the name are skipped and listed by `last_invalid_venvs`. `--no-validate`
+    /// turns it off. Layouts of `[[detectors]]` are checked by their own validators.
     ///
     /// # Arguments
-    /// * `path` - Path to the .venv directory
+    /// * `validate` - Whether to validate folders named .venv
     ///
     /// # Returns
-    /// A VenvInfo struct with directory information
-    fn analyze_venv_directory(&self, path: &Path) -> Result<VenvInfo> {
-        let metadata = fs::metadata(path)?;
+    /// The VenvCleaner with the setting applied
+    pub fn with_validation(mut self, validate: bool) -> Self {
+        self.validate = validate;
+        self
+    }
 
-        // Get creation time
-        let created = metadata.created()
-            .unwrap_or_else(|_| SystemTime::now());
+    /// Never walk into these directories during recursive scans
+    ///
+    /// # Arguments
+    /// * `excludes` - Directories pruned from the walk with everything below them
+    ///
+    /// # Returns
+    /// The VenvCleaner with the setting applied
+    pub fn with_excludes(mut self, excludes: Vec<PathBuf>) -> Self {
+        self.excludes = excludes;
+        self
+    }
 
-        // Get last modified time
-        let modified = metadata.modified()
-            .unwrap_or_else(|_| SystemTime::now());
+    /// Limit recursive scans to the subtrees matching include patterns
+    ///
+    /// # Arguments
+    /// * `includes` - The patterns; an empty set scans everything
+    ///
+    /// # Returns
+    /// The VenvCleaner with the setting applied
+    pub fn with_includes(mut self, includes: IncludeSet) -> Self {
+        self.includes = includes;
+        self
+    }
 
-        // Calculate directory size
-        let size = FileUtils::calculate_directory_size(path)?;
+    /// Also find environments of these layouts, besides `.venv` directories
+    ///
+    /// # Arguments
+    /// * `detectors` - The layouts from the config's `[[detectors]]` tables
+    ///
+    /// # Returns
+    /// The VenvCleaner with the setting applied
+    pub fn with_detectors(mut self, detectors: Vec<Detector>) -> Self {
+        self.detectors = detectors;
+        self
+    }
 
-        // Convert system times to DateTime
-        let created_dt: DateTime<Local> = created.into();
-        let modified_dt: DateTime<Local> = modified.into();
+    /// Run these commands before and after each deletion
+    ///
+    /// # Arguments
+    /// * `delete_hooks` - The hooks from the config's `[hooks]` table
+    ///
+    /// # Returns
+    /// The VenvCleaner with the setting applied
+    pub fn with_delete_hooks(mut self, delete_hooks: DeleteHooks) -> Self {
+        self.delete_hooks = delete_hooks;
+        self
+    }
 
-        Ok(VenvInfo::new(
-            path.to_path_buf(),
-            size,
-            created_dt,
-            modified_dt,
-        ))
+    /// Archive .venv directories (into `archive_dir`) instead of deleting them
+    ///
+    /// # Arguments
+    /// * `archive_dir` - Directory for the compressed archives, or None to delete
+    ///
+    /// # Returns
+    /// The VenvCleaner with the setting applied
+    pub fn with_archive_dir(mut self, archive_dir: Option<PathBuf>) -> Self {
+        self.archive_dir = archive_dir;
+        self
     }
 
-    /// Delete a .venv directory
+    /// Limit how long sizing a single .venv may take
+    ///
+    /// On network file systems one huge environment could otherwise stall
+    /// the whole scan. Environments that run out of time are reported with
+    /// a partial size (see `VenvInfo::is_size_partial`) and can be sized
+    /// again with `recalculate_size`.
     ///
     /// # Arguments
-    /// * `venv_info` - Information about the .venv directory to delete
+    /// * `size_timeout` - The per-.venv budget, or None for no limit
     ///
     /// # Returns
-    /// Result indicating success or failure
-    pub fn delete_venv_directory(&self, venv_info: &VenvInfo) -> Result<()> {
-        if self.dry_run {
-            println!("DRY RUN: Would delete: {}", venv_info.path().display());
-            return Ok(());
-        }
+    /// The VenvCleaner with the setting applied
+    pub fn with_size_timeout(mut self, size_timeout: Option<Duration>) -> Self {
+        self.size_timeout = size_timeout;
+        self
+    }
 
-        info!("Deleting .venv directory: {}", venv_info.path().display());
+    /// Get the per-.venv sizing budget, if any
+    pub fn size_timeout(&self) -> Option<Duration> {
+        self.size_timeout
+    }
 
-        // Check if we have permission to delete
-        if !FileUtils::can_delete_directory(venv_info.path())? {
-            return Err(VenvCleanerError::PermissionDenied {
-                path: venv_info.path().display().to_string(),
-            });
+    /// Set how many threads size .venv directories during a scan
+    ///
+    /// # Arguments
+    /// * `scan_threads` - Number of threads (at least 1), or None to keep the default
+    ///
+    /// # Returns
+    /// The VenvCleaner with the setting applied
+    pub fn with_scan_threads(mut self, scan_threads: Option<usize>) -> Self {
+        if let Some(scan_threads) = scan_threads {
+            self.scan_threads = scan_threads.max(1);
         }
+        self
+    }
 
-        // Perform the deletion
-        fs::remove_dir_all(venv_info.path())?;
+    /// Set how many .venv directories are deleted at once
+    ///
+    /// # Arguments
+    /// * `delete_jobs` - Number of parallel deletions (at least 1), or None to keep the default
+    ///
+    /// # Returns
+    /// The VenvCleaner with the setting applied
+    pub fn with_delete_jobs(mut self, delete_jobs: Option<usize>) -> Self {
+        if let Some(delete_jobs) = delete_jobs {
+            self.delete_jobs = delete_jobs.max(1);
+        }
+        self
+    }
 
-        info!("Successfully deleted: {}", venv_info.path().display());
-        Ok(())
+    /// Get the number of threads sizing .venv directories during a scan
+    pub fn scan_threads(&self) -> usize {
+        self.scan_threads
     }
 
-    /// Get the base directory being searched
-    pub fn base_directory(&self) -> &Path {
-        &self.base_directory
+    /// Get the number of .venv directories deleted at once
+    pub fn delete_jobs(&self) -> usize {
+        self.delete_jobs
     }
 
-    /// Check if recursive search is enabled
-    pub fn is_recursive(&self) -> bool {
-        self.recursive
+    /// Also scan these directories after the base directory
+    ///
+    /// # Arguments
+    /// * `extra_roots` - Further directories to search; the base directory is skipped if repeated
+    ///
+    /// # Returns
+    /// The VenvCleaner scanning every root
+    pub fn with_extra_roots(mut self, extra_roots: Vec<PathBuf>) -> Self {
+        let base_directory = self.base_directory.clone();
+        self.extra_roots = extra_roots.into_iter().filter(|root| *root != base_directory).collect();
+        self.extra_roots.dedup();
+        self
     }
 
-    /// Check if force mode is enabled
-    pub fn is_force_mode(&self) -> bool {
-        self.force_mode
+    /// Also scan temp directories for environments CI jobs and tox left behind
+    ///
+    /// Below these roots any directory with a `pyvenv.cfg` counts as an
+    /// environment, the walk is recursive and only environments unused for a
+    /// day are kept (see `temp_venvs`).
+    ///
+    /// # Arguments
+    /// * `temp_roots` - The temp directories, usually `temp_venvs::temp_roots()`; roots already scanned are skipped
+    ///
+    /// # Returns
+    /// The VenvCleaner scanning the temp directories last
+    pub fn with_temp_roots(mut self, temp_roots: Vec<PathBuf>) -> Self {
+        self.temp_roots = temp_roots
+            .into_iter()
+            .filter(|root| *root != self.base_directory && !self.extra_roots.contains(root))
+            .collect();
+        self
     }
 
-    /// Check if this is a dry run
-    pub fn is_dry_run(&self) -> bool {
-        self.dry_run
+    /// Keep scanning the other roots when one of them cannot be searched
+    ///
+    /// # Arguments
+    /// * `partial_roots` - Whether a failed root leaves the other roots' results standing
+    ///
+    /// Without it the first failed root fails the whole scan. With it the
+    /// scan only fails when every root does, and the failed roots are listed
+    /// by `last_failed_roots`.
+    ///
+    /// # Returns
+    /// The VenvCleaner with the setting applied
+    pub fn with_partial_roots(mut self, partial_roots: bool) -> Self {
+        self.partial_roots = partial_roots;
+        self
     }
 
-    /// Get the verbosity level
-    pub fn verbosity(&self) -> u8 {
-        self.verbosity
+    /// Choose whose .venv directories scans keep (the current user's by default)
+    ///
+    /// # Arguments
+    /// * `owner_filter` - Which owners to keep
+    ///
+    /// # Returns
+    /// The VenvCleaner with the filter set
+    pub fn with_owner_filter(mut self, owner_filter: OwnerFilter) -> Self {
+        self.owner_filter = owner_filter;
+        self
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
-    use tempfile::TempDir;
+    /// Get the filter scans apply to owners
+    pub fn owner_filter(&self) -> OwnerFilter {
+        self.owner_filter
+    }
 
-    #[test]
-    fn test_venv_cleaner_creation() {
-        let temp_dir = TempDir::new().unwrap();
-        let cleaner = VenvCleaner::new(
-            temp_dir.path().to_path_buf(),
-            false,
-            false,
-            true,
-            1,
-        );
+    /// Keep only the .venv directories meeting age and size criteria in scans
+    ///
+    /// # Arguments
+    /// * `venv_filter` - The criteria (`--older-than`, `--min-size`)
+    ///
+    /// # Returns
+    /// The VenvCleaner with the filter set
+    pub fn with_venv_filter(mut self, venv_filter: VenvFilter) -> Self {
+        self.venv_filter = venv_filter;
+        self
+    }
 
-        assert_eq!(cleaner.base_directory(), temp_dir.path());
-        assert!(!cleaner.is_recursive());
-        assert!(!cleaner.is_force_mode());
-        assert!(cleaner.is_dry_run());
-        assert_eq!(cleaner.verbosity(), 1);
+    /// Get the age and size criteria scans apply
+    pub fn venv_filter(&self) -> VenvFilter {
+        self.venv_filter
     }
 
-    #[test]
-    fn test_find_venv_directories_empty() {
-        let temp_dir = TempDir::new().unwrap();
-        let cleaner = VenvCleaner::new(
-            temp_dir.path().to_path_buf(),
-            false,
-            false,
-            true,
-            0,
-        );
+    /// List a saved scan instead of walking the disk, and plan deletions instead of performing them
+    ///
+    /// # Arguments
+    /// * `offline` - The saved scan and the plan deletions are added to, or None to work on the disk
+    ///
+    /// # Returns
+    /// The VenvCleaner with the offline index applied
+    pub fn with_offline_index(mut self, offline: Option<OfflineIndex>) -> Self {
+        self.offline = offline;
+        self
+    }
 
-        let result = cleaner.find_venv_directories();
-        assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), VenvCleanerError::NoVenvFound));
+    /// Let a token stop scans and batch deletions part way through
+    ///
+    /// # Arguments
+    /// * `cancellation` - The token; a cancelled scan returns what it found so far
+    ///
+    /// # Returns
+    /// The VenvCleaner with the token set
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = cancellation;
+        self
     }
 
-    #[test]
-    fn test_find_venv_directories_with_venv() {
-        let temp_dir = TempDir::new().unwrap();
-        let venv_path = temp_dir.path().join(".venv");
-        fs::create_dir(&venv_path).unwrap();
+    /// Check whether the scan or deletion in progress was cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
 
-        let cleaner = VenvCleaner::new(
-            temp_dir.path().to_path_buf(),
-            false,
-            false,
-            true,
-            0,
-        );
+    /// Checkpoint recursive scans so an interrupted one can resume
+    ///
+    /// # Arguments
+    /// * `checkpoint_dir` - Where checkpoints are written (see `ScanCheckpoint::default_dir`), None for none
+    /// * `resume` - Whether to skip the directories a checkpoint records as finished
+    ///
+    /// # Returns
+    /// The VenvCleaner with checkpoints set
+    pub fn with_checkpoints(mut self, checkpoint_dir: Option<PathBuf>, resume: bool) -> Self {
+        self.checkpoint_dir = checkpoint_dir;
+        self.resume = resume;
+        self
+    }
 
-        let result = cleaner.find_venv_directories();
-        assert!(result.is_ok());
-        let venv_dirs = result.unwrap();
-        assert_eq!(venv_dirs.len(), 1);
-        assert_eq!(venv_dirs[0].path(), &venv_path);
+    /// Keep scans from holding on to the .venv directories they find
+    ///
+    /// Each .venv is only passed to the `find_venv_directories_with`
+    /// callback, which then returns an empty list, so memory stays flat
+    /// however many are found.
+    ///
+    /// # Arguments
+    /// * `low_memory` - Whether to keep no results
+    ///
+    /// # Returns
+    /// The VenvCleaner with the mode set
+    pub fn with_low_memory(mut self, low_memory: bool) -> Self {
+        self.low_memory = low_memory;
+        self
+    }
+
+    /// Remove deleted .venv directories in the background once they are renamed
+    ///
+    /// Deletions then return as soon as the .venv is renamed to
+    /// `.venv.deleting-<ts>` (see `pending_delete`), and its removal goes on
+    /// while the caller moves to the next one. Errors of those removals are
+    /// only reported by `finish_background_removals`, which must be called
+    /// before exiting; an exit before that leaves the renamed directories
+    /// for the next run to finish.
+    ///
+    /// # Arguments
+    /// * `background` - Whether to remove in the background
+    ///
+    /// # Returns
+    /// The VenvCleaner with the setting applied
+    pub fn with_background_removal(mut self, background: bool) -> Self {
+        self.background_removals = background.then(BackgroundRemovals::default);
+        self
+    }
+
+    /// Wait for the .venv directories still being removed in the background
+    ///
+    /// # Returns
+    /// The .venv path and error of each removal that failed; a permission
+    /// error names what is left (see `VenvCleanerError::marked_path`)
+    pub fn finish_background_removals(&self) -> Vec<(PathBuf, VenvCleanerError)> {
+        self.background_removals.as_ref().map(BackgroundRemovals::wait).unwrap_or_default()
+    }
+
+    /// Walk, size and delete through another file system than the disk
+    ///
+    /// Tests pass a `MemoryFs` to reproduce unreadable directories and
+    /// failed deletions (see the `fs_backend` module).
+    ///
+    /// # Arguments
+    /// * `fs` - The file system
+    ///
+    /// # Returns
+    /// The VenvCleaner with the file system set
+    pub fn with_fs_backend(mut self, fs: Arc<dyn FsBackend>) -> Self {
+        self.fs = fs;
+        self
+    }
+
+    /// Get the file system scans walk and deletions remove from
+    pub fn fs_backend(&self) -> Arc<dyn FsBackend> {
+        Arc::clone(&self.fs)
+    }
+
+    /// Set the size above which a match is never deleted without an interactive confirmation
+    ///
+    /// # Arguments
+    /// * `exclude_size_above` - The size, or None to turn the guard off
+    ///
+    /// # Returns
+    /// The VenvCleaner with the guard set
+    pub fn with_exclude_size_above(mut self, exclude_size_above: Option<ByteSize>) -> Self {
+        self.exclude_size_above = exclude_size_above;
+        self
+    }
+
+    /// Get the size above which a match needs an interactive confirmation, None when the guard is off
+    pub fn exclude_size_above(&self) -> Option<ByteSize> {
+        self.exclude_size_above
+    }
+
+    /// Check whether a .venv is so large it is probably a misdetected data directory
+    pub fn is_size_outlier(&self, venv_info: &VenvInfo) -> bool {
+        self.exclude_size_above.is_some_and(|limit| venv_info.size() > limit)
+    }
+
+    /// Get the saved scan listed instead of the disk, if any
+    pub fn offline_index(&self) -> Option<&OfflineIndex> {
+        self.offline.as_ref()
+    }
+
+    /// Get the per-directory timings of the last scan
+    ///
+    /// # Returns
+    /// One entry per top-level directory below each recursively scanned
+    /// root, slowest first
+    pub fn last_scan_stats(&self) -> Vec<DirectoryScanStats> {
+        let mut stats = self.scan_stats.lock().map(|stats| stats.clone()).unwrap_or_default();
+        stats.sort_by_key(|directory| std::cmp::Reverse(directory.elapsed));
+        stats
+    }
+
+    /// Get the directories the last scan skipped because they could not be read
+    ///
+    /// # Returns
+    /// The unreadable directories, sorted by path
+    pub fn last_unreadable_directories(&self) -> Vec<PathBuf> {
+        let mut unreadable = self.unreadable.lock().map(|unreadable| unreadable.clone()).unwrap_or_default();
+        unreadable.sort();
+        unreadable
+    }
+
+    /// Get the folders named .venv the last scan skipped because they do not look like virtual environments
+    ///
+    /// # Returns
+    /// The skipped folders, sorted by path (none with validation off)
+    pub fn last_invalid_venvs(&self) -> Vec<PathBuf> {
+        let mut invalid = self.invalid_venvs.lock().map(|invalid| invalid.clone()).unwrap_or_default();
+        invalid.sort();
+        invalid
+    }
+
+    /// Get the roots the last scan could not search
+    ///
+    /// # Returns
+    /// Each failed root with the error it failed with, in scan order
+    pub fn last_failed_roots(&self) -> Vec<(PathBuf, VenvCleanerError)> {
+        self.failed_roots.lock().map(|failed| failed.clone()).unwrap_or_default()
+    }
+
+    /// Get every directory scanned, starting with the base directory
+    pub fn roots(&self) -> Vec<&Path> {
+        std::iter::once(self.base_directory.as_path())
+            .chain(self.extra_roots.iter().map(PathBuf::as_path))
+            .chain(self.temp_roots.iter().map(PathBuf::as_path))
+            .collect()
+    }
+
+    /// Find the scan roots that lie inside a virtual environment or `site-packages`
+    ///
+    /// # Returns
+    /// Each such root with the environment it lies in (see `safety::enclosing_environment`)
+    pub fn roots_inside_environments(&self) -> Vec<(PathBuf, PathBuf)> {
+        self.roots()
+            .into_iter()
+            .filter_map(|root| safety::enclosing_environment(root).map(|environment| (root.to_path_buf(), environment)))
+            .collect()
+    }
+
+    /// Create a copy of this cleaner that scans only `root`
+    fn for_root(&self, root: &Path) -> Self {
+        let mut cleaner = self.clone();
+        cleaner.base_directory = root.to_path_buf();
+        cleaner.system_excludes = safety::system_paths_below(root);
+        cleaner.priority_scan = scan_priority::is_broad_root(root);
+        cleaner.filesystem = mounts::filesystem_info(root);
+        cleaner.extra_roots.clear();
+        cleaner.temp_roots.clear();
+        if self.temp_roots.iter().any(|temp_root| temp_root == root) {
+            cleaner.recursive = true;
+            cleaner.detectors.push(temp_venvs::detector());
+            let older_than = cleaner.venv_filter.older_than().map_or(temp_venvs::min_age(), |age| age.max(temp_venvs::min_age()));
+            cleaner.venv_filter = cleaner.venv_filter.with_older_than(Some(older_than));
+        }
+        cleaner
+    }
+
+    /// Find all .venv directories in the specified path
+    ///
+    /// # Returns
+    /// A vector of VenvInfo structs containing information about found .venv directories
+    pub fn find_venv_directories(&self) -> Result<Vec<VenvInfo>> {
+        self.find_venv_directories_with(|_| {})
+    }
+
+    /// Find all .venv directories, reporting each one as soon as it is found
+    ///
+    /// # Arguments
+    /// * `on_found` - Called with each .venv directory as it is discovered
+    ///
+    /// # Returns
+    /// A vector of VenvInfo structs containing information about found .venv directories
+    pub fn find_venv_directories_with<F>(&self, mut on_found: F) -> Result<Vec<VenvInfo>>
+    where
+        F: FnMut(&VenvInfo),
+    {
+        let _span = info_span!("scan", roots = self.roots().len(), offline = self.offline.is_some()).entered();
+        if let Ok(mut stats) = self.scan_stats.lock() {
+            stats.clear();
+        }
+        if let Ok(mut unreadable) = self.unreadable.lock() {
+            unreadable.clear();
+        }
+        if let Ok(mut invalid) = self.invalid_venvs.lock() {
+            invalid.clear();
+        }
+        if let Ok(mut stubs) = self.archive_stubs.lock() {
+            stubs.clear();
+        }
+        if let Ok(mut failed) = self.failed_roots.lock() {
+            failed.clear();
+        }
+        if let Some(offline) = &self.offline {
+            return self.find_in_offline_index(offline, &mut on_found);
+        }
+        // Bind mounts and snapshots reach the same .venv under several paths
+        let mut identities = HashSet::new();
+        if self.roots().len() == 1 && !self.partial_roots {
+            return self.find_in_base_directory(&mut identities, &mut on_found);
+        }
+
+        // Nested roots find the same .venv twice; report and keep it once
+        let mut seen = HashSet::new();
+        let mut venv_dirs = Vec::new();
+        let mut reported = 0;
+        for root in self.roots() {
+            if self.is_cancelled() {
+                break;
+            }
+            let mut report_new = |venv_info: &VenvInfo| {
+                // Low-memory scans return nothing to deduplicate afterwards
+                let new = if self.low_memory {
+                    seen.insert(venv_info.path().to_path_buf())
+                } else {
+                    !seen.contains(venv_info.path())
+                };
+                if new {
+                    reported += 1;
+                    on_found(venv_info);
+                }
+            };
+            // Listed as failed, a missing or unreadable root no longer just looks empty
+            let result = match self.fs.read_dir(root) {
+                Err(e) if self.partial_roots => {
+                    Err(VenvCleanerError::PathError { path: root.display().to_string(), message: e.to_string() })
+                }
+                _ => self.for_root(root).find_in_base_directory(&mut identities, &mut report_new),
+            };
+            match result {
+                Ok(found) => {
+                    for venv_info in found {
+                        if seen.insert(venv_info.path().to_path_buf()) {
+                            venv_dirs.push(venv_info);
+                        }
+                    }
+                }
+                Err(VenvCleanerError::NoVenvFound) => {}
+                Err(e) => {
+                    warn!("Could not search {}: {}", root.display(), e);
+                    if !self.partial_roots {
+                        return Err(e);
+                    }
+                    if let Ok(mut failed) = self.failed_roots.lock() {
+                        failed.push((root.to_path_buf(), e));
+                    }
+                }
+            }
+        }
+
+        if reported == 0 {
+            let failed = self.last_failed_roots();
+            // Only a scan that could search nothing at all fails outright
+            if failed.len() == self.roots().len() {
+                if let Some((_, e)) = failed.into_iter().next() {
+                    return Err(e);
+                }
+            }
+            return Err(VenvCleanerError::NoVenvFound);
+        }
+        Ok(venv_dirs)
+    }
+
+    /// List the .venv directories of a saved scan that match the filters and are not planned yet
+    ///
+    /// The roots are ignored, since they belong to this machine and not the scanned one.
+    fn find_in_offline_index(&self, offline: &OfflineIndex, on_found: &mut dyn FnMut(&VenvInfo)) -> Result<Vec<VenvInfo>> {
+        info!("Listing the saved scan instead of searching the disk");
+        let venv_dirs: Vec<VenvInfo> = offline
+            .remaining()
+            .into_iter()
+            .filter(|venv_info| {
+                self.venv_filter.matches(venv_info)
+                    && !self.is_excluded(venv_info.path())
+                    && !safety::is_system_path(venv_info.path())
+            })
+            .collect();
+        for venv_info in &venv_dirs {
+            on_found(venv_info);
+        }
+        if venv_dirs.is_empty() {
+            return Err(VenvCleanerError::NoVenvFound);
+        }
+        Ok(venv_dirs)
+    }
+
+    /// Find the .venv directories below the base directory only
+    ///
+    /// # Arguments
+    /// * `identities` - The device and inode numbers of the .venv directories found so far
+    /// * `on_found` - Called with each .venv directory as it is discovered
+    fn find_in_base_directory(
+        &self,
+        identities: &mut HashSet<(u64, u64)>,
+        on_found: &mut dyn FnMut(&VenvInfo),
+    ) -> Result<Vec<VenvInfo>> {
+        let _span = info_span!("scan_root", root = %self.base_directory.display()).entered();
+        info!("Searching for .venv directories in: {}", self.base_directory.display());
+
+        let mut venv_dirs = Vec::new();
+        let mut found = 0;
+        let mut errors = Vec::new();
+        let mut privacy_blocked = Vec::new();
+        let mut too_long = Vec::new();
+        let mut unreadable = Vec::new();
+        let mut invalid = Vec::new();
+        let mut stubs = Vec::new();
+        let mut other_owners = 0;
+        let mut unmatched = 0;
+        let mut replicated = 0;
+
+        // An interrupted scan of this root picks up where its checkpoint left off
+        let checkpoint_path = self
+            .checkpoint_dir
+            .as_deref()
+            .filter(|_| self.recursive)
+            .map(|dir| ScanCheckpoint::path_in(dir, &self.base_directory));
+        let resumed = checkpoint_path
+            .as_deref()
+            .filter(|_| self.resume)
+            .and_then(|path| ScanCheckpoint::load(path, &self.base_directory, self.respect_gitignore));
+        let checkpointer = checkpoint_path.map(|path| {
+            let checkpoint = resumed
+                .clone()
+                .unwrap_or_else(|| ScanCheckpoint::new(self.base_directory.clone(), self.respect_gitignore));
+            ScanCheckpointer::new(path, checkpoint, scan_checkpoint::CHECKPOINT_INTERVAL)
+        });
+        let mut timer = ScanTimer::new().with_checkpointer(checkpointer);
+        let resumed_walker;
+        let walker = match &resumed {
+            Some(checkpoint) => {
+                info!(
+                    "Resuming the scan of {}: skipping {} finished directories",
+                    self.base_directory.display(),
+                    checkpoint.completed.len()
+                );
+                let mut walker = self.clone();
+                walker.excludes.extend(checkpoint.completed.keys().cloned());
+                resumed_walker = walker;
+                &resumed_walker
+            }
+            None => self,
+        };
+
+        // Record each .venv as soon as it is analyzed so callers can stream results
+        let mut record = |venv_path: PathBuf, result: Result<VenvInfo>| match result {
+            // Age and size are only known once the .venv is analyzed
+            Ok(venv_info) if !self.venv_filter.matches(&venv_info) => {
+                debug!(
+                    "Skipping .venv not matching --older-than/--min-size: {} ({})",
+                    venv_path.display(),
+                    self.venv_filter.explain(&venv_info).unwrap_or_default()
+                );
+                unmatched += 1;
+            }
+            Ok(venv_info) => {
+                debug!("Found .venv at: {}", venv_path.display());
+                on_found(&venv_info);
+                found += 1;
+                if !self.low_memory {
+                    venv_dirs.push(venv_info);
+                }
+            }
+            Err(e) => {
+                warn!("Error analyzing .venv at {}: {}", venv_path.display(), e);
+                errors.push(e);
+            }
+        };
+
+        // Size the .venv directories on a pool of threads while the walk goes on
+        debug!("Sizing .venv directories with {} threads", self.scan_threads);
+        let (path_tx, path_rx) = mpsc::channel::<PathBuf>();
+        let path_rx = Mutex::new(path_rx);
+        let (done_tx, done_rx) = mpsc::channel();
+        let root_span = Span::current();
+        thread::scope(|scope| {
+            for _ in 0..self.scan_threads {
+                let (path_rx, done_tx, root_span) = (&path_rx, done_tx.clone(), root_span.clone());
+                // Sizing spans nest under the root's scan, whichever thread runs them
+                scope.spawn(move || loop {
+                    let _span = root_span.enter();
+                    let next = path_rx.lock().ok().and_then(|rx| rx.recv().ok());
+                    let Some(venv_path) = next else { break };
+                    // Cancelled scans leave the queued .venv directories unsized
+                    if self.is_cancelled() {
+                        break;
+                    }
+                    let result = self.analyze_venv_directory(&venv_path);
+                    if done_tx.send((venv_path, result)).is_err() {
+                        break;
+                    }
+                });
+            }
+            drop(done_tx);
+
+            let mut visit = |venv_path: &Path| {
+                // Skip other users' environments before paying for sizing them
+                if !self.owner_filter.matches(ownership::path_owner(venv_path)) {
+                    other_owners += 1;
+                    return;
+                }
+                // Data folders merely named .venv are never offered for deletion
+                if self.validate && venv_path.ends_with(".venv") && !FileUtils::is_valid_venv_directory_in(self.fs.as_ref(), venv_path) {
+                    debug!("Skipping folder named .venv that is not a virtual environment: {}", venv_path.display());
+                    invalid.push(venv_path.to_path_buf());
+                    return;
+                }
+                // Counting a .venv once keeps totals right and avoids deleting it twice
+                if let Some(identity) = FileUtils::directory_identity(venv_path) {
+                    if !identities.insert(identity) {
+                        debug!("Skipping .venv already found under another path: {}", venv_path.display());
+                        replicated += 1;
+                        return;
+                    }
+                }
+                let _ = path_tx.send(venv_path.to_path_buf());
+                for (venv_path, result) in done_rx.try_iter() {
+                    record(venv_path, result);
+                }
+            };
+            // The finished directories are not walked again, but their .venv directories are sized afresh
+            for venv_path in resumed.iter().flat_map(ScanCheckpoint::venvs) {
+                if venv_path.is_dir() {
+                    visit(venv_path);
+                }
+            }
+            walker.walk_for_venvs(&mut visit, &mut timer, &mut privacy_blocked, &mut too_long, &mut unreadable, &mut stubs);
+
+            // Closing the queue lets the workers finish once it is drained
+            drop(path_tx);
+            for (venv_path, result) in done_rx {
+                record(venv_path, result);
+            }
+        });
+
+        if !privacy_blocked.is_empty() {
+            warn!(
+                "Skipped {} folders protected by macOS privacy settings (e.g. {})",
+                privacy_blocked.len(),
+                privacy_blocked[0]
+            );
+            if found == 0 {
+                return Err(VenvCleanerError::FullDiskAccessRequired {
+                    path: privacy_blocked.swap_remove(0),
+                });
+            }
+        }
+
+        if !too_long.is_empty() {
+            warn!(
+                "Skipped {} folders whose paths are too long for the operating system (e.g. {})",
+                too_long.len(),
+                FileUtils::truncate_start(&too_long[0], 80)
+            );
+        }
+
+        if other_owners > 0 {
+            info!("Skipped {} .venv directories owned by other users (use --all-users to include them)", other_owners);
+        }
+
+        if unmatched > 0 {
+            info!("Skipped {} .venv directories not matching --older-than/--min-size", unmatched);
+        }
+
+        if replicated > 0 {
+            info!("Skipped {} .venv directories already found under another path (bind mounts or snapshots)", replicated);
+        }
+
+        if let Some(checkpointer) = timer.take_checkpointer() {
+            checkpointer.finish(!self.is_cancelled());
+        }
+        let timings = timer.finish();
+        for directory in &timings {
+            debug!("Scanned {}", directory);
+        }
+        if let Ok(mut stats) = self.scan_stats.lock() {
+            stats.extend(timings);
+        }
+        if !unreadable.is_empty() {
+            debug!("Skipped {} unreadable directories", unreadable.len());
+            if let Ok(mut shared) = self.unreadable.lock() {
+                shared.extend(unreadable);
+            }
+        }
+        if let Ok(mut shared) = self.archive_stubs.lock() {
+            shared.extend(stubs);
+        }
+        if !invalid.is_empty() {
+            info!("Skipped {} folders named .venv that are not virtual environments (--no-validate includes them)", invalid.len());
+            if let Ok(mut shared) = self.invalid_venvs.lock() {
+                shared.extend(invalid);
+            }
+        }
+
+        if found == 0 && errors.is_empty() {
+            return Err(VenvCleanerError::NoVenvFound);
+        }
+
+        if !errors.is_empty() && self.verbosity > 0 {
+            warn!("Encountered {} errors while searching", errors.len());
+        }
+
+        Ok(venv_dirs)
+    }
+
+    /// Walk the base directory, calling `visit` for every .venv directory found
+    ///
+    /// # Arguments
+    /// * `visit` - Called with the path of each .venv directory found
+    /// * `timer` - Times each top-level directory of the walk
+    /// * `privacy_blocked` - Collects folders hidden by macOS privacy settings
+    /// * `too_long` - Collects folders whose paths exceed the OS limits
+    /// * `unreadable` - Collects folders the walk was denied access to
+    /// * `stubs` - Collects the archive stub files walked past
+    fn walk_for_venvs(
+        &self,
+        visit: &mut dyn FnMut(&Path),
+        timer: &mut ScanTimer,
+        privacy_blocked: &mut Vec<String>,
+        too_long: &mut Vec<String>,
+        unreadable: &mut Vec<PathBuf>,
+        stubs: &mut Vec<PathBuf>,
+    ) {
+        let _span = debug_span!("walk", gitignore = self.respect_gitignore).entered();
+        // The walk enters directories leading to an included subtree; only
+        // environments inside one are kept
+        let mut visit_included = |path: &Path| {
+            if !self.recursive || self.includes.covers(&self.base_directory, path) {
+                visit(path);
+            }
+        };
+        let visit: &mut dyn FnMut(&Path) = &mut visit_included;
+        if self.recursive && self.respect_gitignore {
+            self.walk_respecting_gitignore(visit, timer, privacy_blocked, too_long, unreadable, stubs);
+        } else if self.recursive {
+            if self.priority_scan {
+                debug!("Priority scan enabled: walking likely project roots first");
+            }
+
+            // Pruning here keeps the walker out of the skipped trees entirely
+            let mut descend = |entry: &WalkEntry<'_>| self.should_walk(entry);
+            self.fs.walk(&self.base_directory, self.priority_scan, &mut descend, &mut |entry| {
+                if self.is_cancelled() {
+                    debug!("Scan cancelled, stopping the walk");
+                    return ControlFlow::Break(());
+                }
+                let entry = match entry {
+                    Ok(entry) => entry,
+                    Err(WalkError { path, error }) => {
+                        // Remember folders hidden by macOS privacy settings so we can explain them
+                        if let Some(path) = path {
+                            if platform::is_privacy_denied(&error) {
+                                privacy_blocked.push(path.display().to_string());
+                            }
+                            if platform::is_name_too_long(&error) {
+                                too_long.push(path.display().to_string());
+                            }
+                            if is_access_denied(&error) {
+                                unreadable.push(path);
+                            }
+                        }
+                        debug!("Skipping unreadable entry: {}", error);
+                        return ControlFlow::Continue(());
+                    }
+                };
+
+                if entry.depth == 1 && entry.is_dir() {
+                    timer.enter(entry.path);
+                }
+                if entry.kind == FsEntryKind::File && entry.file_name() == archive::STUB_FILE_NAME {
+                    stubs.push(entry.path.to_path_buf());
+                }
+                if entry.is_dir() && (entry.file_name() == ".venv" || self.detected_layout(entry.path).is_some()) {
+                    timer.found_venv(entry.path);
+                    visit(entry.path);
+                }
+                ControlFlow::Continue(())
+            });
+        } else {
+            // Non-recursive search - only check the base directory
+            if archive::has_stub(&self.base_directory) {
+                stubs.push(self.base_directory.join(archive::STUB_FILE_NAME));
+            }
+            let venv_path = self.base_directory.join(".venv");
+            if self.fs.is_dir(&venv_path) {
+                visit(&venv_path);
+            }
+            self.visit_detected_children(&self.base_directory, visit, timer);
+        }
+    }
+
+    /// Find the configured layout a directory is an environment of
+    fn detected_layout(&self, path: &Path) -> Option<&Detector> {
+        let detector = self.detectors.iter().find(|detector| detector.detects(path))?;
+        debug!("Found {} environment at: {}", detector.name(), path.display());
+        Some(detector)
+    }
+
+    /// Visit the children of a directory that are environments of a configured layout
+    fn visit_detected_children(&self, dir: &Path, visit: &mut dyn FnMut(&Path), timer: &mut ScanTimer) {
+        if self.detectors.is_empty() {
+            return;
+        }
+        let Ok(entries) = fs::read_dir(dir) else { return };
+        for entry in entries.flatten() {
+            // .venv children are found already; symlinks are never followed
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            if is_dir && entry.file_name() != ".venv" && self.detected_layout(&entry.path()).is_some() {
+                timer.found_venv(&entry.path());
+                visit(&entry.path());
+            }
+        }
+    }
+
+    /// Check whether a walk of the base directory should enter an entry,
+    /// by the excludes, includes and hidden-directory settings
+    fn should_walk(&self, entry: &WalkEntry<'_>) -> bool {
+        let hidden = self.skip_hidden
+            && entry.depth > 0
+            && entry.is_dir()
+            && is_skipped_hidden(entry.file_name())
+            && !self.detectors.iter().any(|detector| detector.matches_name(entry.file_name()));
+        !hidden && !self.is_excluded(entry.path) && self.includes.should_descend(&self.base_directory, entry.path)
+    }
+
+    /// Check whether a path lies in a directory excluded from scans
+    fn is_excluded(&self, path: &Path) -> bool {
+        self.excludes.iter().chain(&self.system_excludes).any(|exclude| path.starts_with(exclude))
+    }
+
+    /// Walk the tree honouring .gitignore, .ignore and global git excludes
+    ///
+    /// A project's .venv is almost always gitignored itself, so instead of
+    /// visiting .venv folders directly each visited directory is checked for a
+    /// `.venv` child. Everything else that is ignored is pruned from the walk.
+    ///
+    /// # Arguments
+    /// * `visit` - Called with the path of each .venv directory found
+    /// * `timer` - Times each top-level directory of the walk
+    /// * `privacy_blocked` - Collects folders hidden by macOS privacy settings
+    /// * `too_long` - Collects folders whose paths exceed the OS limits
+    /// * `unreadable` - Collects folders the walk was denied access to
+    /// * `stubs` - Collects the archive stub files of the visited directories
+    fn walk_respecting_gitignore(
+        &self,
+        visit: &mut dyn FnMut(&Path),
+        timer: &mut ScanTimer,
+        privacy_blocked: &mut Vec<String>,
+        too_long: &mut Vec<String>,
+        unreadable: &mut Vec<PathBuf>,
+        stubs: &mut Vec<PathBuf>,
+    ) {
+        let mut builder = ignore::WalkBuilder::new(&self.base_directory);
+        builder
+            .hidden(self.skip_hidden)
+            .follow_links(false)
+            .git_ignore(true)
+            .git_global(true)
+            .git_exclude(true)
+            .filter_entry({
+                // .venv folders are reported via their parent; .git never contains projects
+                let excludes: Vec<PathBuf> = self.excludes.iter().chain(&self.system_excludes).cloned().collect();
+                let (includes, root) = (self.includes.clone(), self.base_directory.clone());
+                move |entry| {
+                    entry.file_name() != ".venv"
+                        && entry.file_name() != ".git"
+                        && !excludes.iter().any(|exclude| entry.path().starts_with(exclude))
+                        && includes.should_descend(&root, entry.path())
+                }
+            });
+
+        if self.priority_scan {
+            debug!("Priority scan enabled: walking likely project roots first");
+            builder.sort_by_file_path(|a, b| {
+                let key = |path: &Path| {
+                    let priority = if path.is_dir() {
+                        scan_priority::directory_priority(path)
+                    } else {
+                        scan_priority::PRIORITY_NORMAL
+                    };
+                    (priority, path.file_name().map(|n| n.to_os_string()))
+                };
+                key(a).cmp(&key(b))
+            });
+        }
+
+        for result in builder.build() {
+            if self.is_cancelled() {
+                debug!("Scan cancelled, stopping the walk");
+                break;
+            }
+            let entry = match result {
+                Ok(entry) => entry,
+                Err(e) => {
+                    let path = match &e {
+                        ignore::Error::WithPath { path, .. } => path.display().to_string(),
+                        _ => self.base_directory.display().to_string(),
+                    };
+                    if e.io_error().map(platform::is_privacy_denied).unwrap_or(false) {
+                        privacy_blocked.push(path);
+                    } else if e.io_error().map(platform::is_name_too_long).unwrap_or(false) {
+                        too_long.push(path);
+                    } else if e.io_error().map(is_access_denied).unwrap_or(false) {
+                        unreadable.push(PathBuf::from(path));
+                    }
+                    debug!("Skipping unreadable entry: {}", e);
+                    continue;
+                }
+            };
+
+            if !entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                continue;
+            }
+            if entry.depth() == 1 {
+                timer.enter(entry.path());
+            }
+
+            // Never follow a symlinked .venv out of the tree
+            let candidate = entry.path().join(".venv");
+            if fs::symlink_metadata(&candidate).map(|m| m.is_dir()).unwrap_or(false) {
+                timer.found_venv(&candidate);
+                visit(&candidate);
+            }
+            // Stubs are checked for the same way, as a project may well ignore them
+            if archive::has_stub(entry.path()) {
+                stubs.push(entry.path().join(archive::STUB_FILE_NAME));
+            }
+            self.visit_detected_children(entry.path(), visit, timer);
+        }
+    }
+
+    /// Walk every root for .venv directories without sizing them
+    ///
+    /// Times the walk on its own, so only the excludes and hidden-directory
+    /// settings apply; owners, age and size are not looked at.
+    ///
+    /// # Returns
+    /// The paths of the .venv directories found, in walk order
+    pub fn walk_venv_paths(&self) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        for root in self.roots() {
+            let mut visit = |venv_path: &Path| paths.push(venv_path.to_path_buf());
+            let (mut timer, mut privacy_blocked, mut too_long, mut unreadable, mut stubs) =
+                (ScanTimer::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new());
+            self.for_root(root).walk_for_venvs(&mut visit, &mut timer, &mut privacy_blocked, &mut too_long, &mut unreadable, &mut stubs);
+        }
+        paths
+    }
+
+    /// Walk every root for archive stub files, without looking at .venv directories
+    ///
+    /// Used where no scan runs first; after a scan, `last_archived_venvs`
+    /// has them already.
+    ///
+    /// # Returns
+    /// The stub files found, sorted by path
+    pub fn walk_archive_stubs(&self) -> Vec<PathBuf> {
+        let mut stubs = Vec::new();
+        for root in self.roots() {
+            let (mut timer, mut privacy_blocked, mut too_long, mut unreadable) =
+                (ScanTimer::new(), Vec::new(), Vec::new(), Vec::new());
+            self.for_root(root).walk_for_venvs(&mut |_| {}, &mut timer, &mut privacy_blocked, &mut too_long, &mut unreadable, &mut stubs);
+        }
+        stubs.sort_by(|a, b| sorting::compare_paths(a, b));
+        stubs.dedup();
+        stubs
+    }
+
+    /// Analyze a single .venv directory and create a VenvInfo struct
+    ///
+    /// # Arguments
+    /// * `path` - Path to the .venv directory
+    ///
+    /// # Returns
+    /// A VenvInfo struct with directory information
+    pub fn analyze_venv_directory(&self, path: &Path) -> Result<VenvInfo> {
+        let _span = debug_span!("size_venv", path = %path.display()).entered();
+        let metadata = self.fs.metadata(path)?;
+
+        // Get creation time
+        let created = metadata.created
+            .unwrap_or_else(SystemTime::now);
+
+        // Get last modified time
+        let modified = metadata.modified
+            .unwrap_or_else(SystemTime::now);
+
+        // One walk sizes the directory (within the budget if there is one),
+        // counts its items and looks for user data
+        let mut found_user_data = Vec::new();
+        let usage = self.fs.directory_usage(path, self.size_timeout, &mut |file, size_bytes| {
+            if user_data::is_user_data(path, file, size_bytes, user_data::USER_DATA_THRESHOLD_BYTES) {
+                found_user_data.push(UserDataFile { path: file.to_path_buf(), size_bytes });
+            }
+        })?;
+        let (size, complete) = (usage.bytes, usage.complete);
+        // A directory too slow to size within the budget is not counted at all,
+        // but user data guards deletions, so a cut-short walk looks for it again in full
+        let item_counts = complete.then_some((usage.files, usage.dirs));
+        let found_user_data = if complete {
+            user_data::largest_first(found_user_data)
+        } else {
+            user_data::find_user_data(path, user_data::USER_DATA_THRESHOLD_BYTES)
+        };
+
+        // Convert system times to DateTime
+        let created_dt: DateTime<Utc> = created.into();
+        let modified_dt: DateTime<Utc> = modified.into();
+
+        Ok(VenvInfo::new(
+            path.to_path_buf(),
+            ByteSize::new(size),
+            created_dt,
+            modified_dt,
+        )
+        .with_size_partial(!complete)
+        .with_item_counts(item_counts)
+        .with_filesystem(mounts::filesystem_type(path))
+        .with_removable_volume(mounts::removable_volume(path))
+        .with_owner_uid(ownership::path_owner(path))
+        .with_python_version(PythonVersion::from_venv(path))
+        .with_last_install(site_packages::last_install_time(path))
+        .with_editable_install(site_packages::has_editable_project_install(path))
+        .with_project_kind(ProjectKind::detect(path))
+        .with_user_data(found_user_data))
+    }
+
+    /// Size a .venv directory again without a time limit
+    ///
+    /// Used to retry environments whose size is partial after a timeout.
+    ///
+    /// # Arguments
+    /// * `venv_info` - The .venv directory to size
+    ///
+    /// # Returns
+    /// Fresh information about the directory with its complete size
+    pub fn recalculate_size(&self, venv_info: &VenvInfo) -> Result<VenvInfo> {
+        FileUtils::ensure_exists(venv_info.path())?;
+        let unlimited = self.clone().with_size_timeout(None);
+        // The project's last commit is looked up lazily, so keep whatever was found
+        Ok(unlimited
+            .analyze_venv_directory(venv_info.path())?
+            .with_project_last_commit(venv_info.project_last_commit().copied()))
+    }
+
+    /// Delete a .venv directory
+    ///
+    /// # Arguments
+    /// * `venv_info` - Information about the .venv directory to delete
+    ///
+    /// # Returns
+    /// Result indicating success or failure
+    pub fn delete_venv_directory(&self, venv_info: &VenvInfo) -> Result<()> {
+        let _span = info_span!("delete_venv", path = %venv_info.path().display(), bytes = venv_info.size().bytes()).entered();
+        if let Some(offline) = &self.offline {
+            // The .venv is on another machine; `apply-plan` deletes it there
+            self.check_policy(venv_info)?;
+            if self.dry_run {
+                println!("DRY RUN: Would add to {}: {}", offline.plan().path().display(), venv_info.path().display());
+                return Ok(());
+            }
+            info!("Planning deletion of {} in {}", venv_info.path().display(), offline.plan().path().display());
+            return offline.plan().add(venv_info);
+        }
+
+        // An unplugged drive makes the .venv look vanished, so it is checked first
+        Self::ensure_volume_available(venv_info)?;
+        FileUtils::ensure_exists_in(self.fs.as_ref(), venv_info.path())?;
+        self.check_policy(venv_info)?;
+        self.check_owner(venv_info)?;
+
+        if let Some(archive_dir) = &self.archive_dir {
+            if self.dry_run {
+                println!("DRY RUN: Would archive to {}: {}", archive_dir.display(), venv_info.path().display());
+                return Ok(());
+            }
+
+            if !FileUtils::can_delete_directory(venv_info.path())? {
+                return Err(VenvCleanerError::PermissionDenied {
+                    path: venv_info.path().display().to_string(),
+                });
+            }
+
+            self.delete_hooks.run_pre_delete(venv_info)?;
+            info!("Archiving .venv directory: {}", venv_info.path().display());
+            VenvArchiver::new(archive_dir.clone()).archive(venv_info)?;
+            self.delete_hooks.run_post_delete(venv_info);
+            return self.audit(AuditAction::Archive, venv_info);
+        }
+
+        if self.dry_run {
+            if self.use_trash {
+                println!("DRY RUN: Would move to trash: {}", venv_info.path().display());
+            } else {
+                println!("DRY RUN: Would delete: {}", venv_info.path().display());
+            }
+            return Ok(());
+        }
+
+        self.delete_hooks.run_pre_delete(venv_info)?;
+        info!("Deleting .venv directory: {}", venv_info.path().display());
+        // A drive unplugged halfway through fails with whatever the file system says
+        self.remove_directory(venv_info.path(), true)
+            .map_err(|e| Self::ensure_volume_available(venv_info).err().unwrap_or(e))?;
+        if self.use_trash {
+            self.record_trashed(venv_info);
+        }
+        self.delete_hooks.run_post_delete(venv_info);
+
+        info!("Successfully deleted: {}", venv_info.path().display());
+        self.audit(if self.use_trash { AuditAction::Trash } else { AuditAction::Delete }, venv_info)
+    }
+
+    /// Check that the removable drive a .venv was found on is still mounted
+    ///
+    /// # Arguments
+    /// * `venv_info` - The .venv directory
+    ///
+    /// # Returns
+    /// `VenvCleanerError::VolumeUnavailable` if its drive is gone
+    fn ensure_volume_available(venv_info: &VenvInfo) -> Result<()> {
+        match venv_info.removable_volume() {
+            Some(volume) if !mounts::is_volume_mounted(volume) => Err(VenvCleanerError::VolumeUnavailable {
+                path: venv_info.path().display().to_string(),
+                volume: volume.display().to_string(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Delete a .venv directory as root through `sudo` or `pkexec`
+    ///
+    /// Used after `delete_venv_directory` was denied, and only when the user
+    /// allowed escalation. The policy still applies; the directory is always
+    /// removed permanently, since root's trash or an archive owned by root
+    /// would not be the user's to manage.
+    ///
+    /// # Arguments
+    /// * `venv_info` - Information about the .venv directory to delete
+    /// * `helper` - The program running the removal as root
+    /// * `marked` - What the denied deletion left after renaming the .venv, if it got that far
+    ///   (see `VenvCleanerError::marked_path`)
+    ///
+    /// # Returns
+    /// Ok if the directory was removed, or the error
+    pub fn delete_with_escalation(&self, venv_info: &VenvInfo, helper: EscalationHelper, marked: Option<&Path>) -> Result<()> {
+        let _span = info_span!("delete_venv", path = %venv_info.path().display(), helper = helper.program()).entered();
+        let target = marked.unwrap_or(venv_info.path());
+        FileUtils::ensure_exists(target)?;
+        self.check_policy(venv_info)?;
+        self.check_owner(venv_info)?;
+
+        if self.dry_run {
+            println!("DRY RUN: Would delete with {}: {}", helper.program(), target.display());
+            return Ok(());
+        }
+
+        // A renamed .venv already went through the pre-delete hook
+        if marked.is_none() {
+            self.delete_hooks.run_pre_delete(venv_info)?;
+        }
+        info!("Deleting .venv directory with {}: {}", helper.program(), target.display());
+        helper.remove(target)?;
+        self.delete_hooks.run_post_delete(venv_info);
+        self.audit(AuditAction::Delete, venv_info)
+    }
+
+    /// Record a completed deletion in the policy's audit log, if there is one
+    ///
+    /// The folder is already gone, so a failure is reported as such rather
+    /// than as a failed deletion.
+    fn audit(&self, action: AuditAction, venv_info: &VenvInfo) -> Result<()> {
+        let Some(path) = self.policy.audit_log_path() else {
+            return Ok(());
+        };
+        AuditLog::new(path).append(action, venv_info.path(), venv_info.size().bytes()).map(|_| ()).map_err(|e| {
+            VenvCleanerError::Io(format!(
+                "{} was removed, but recording it in the audit log failed: {}",
+                venv_info.path().display(),
+                e
+            ))
+        })
+    }
+
+    /// Check a deletion against the policy
+    ///
+    /// System directories and system Python locations are refused whatever
+    /// the policy says.
+    ///
+    /// # Returns
+    /// An error if the .venv is protected, or too large to delete without a prompt
+    pub fn check_policy(&self, venv_info: &VenvInfo) -> Result<()> {
+        if let Some(location) = safety::system_location(venv_info.path()) {
+            return Err(VenvCleanerError::SystemDirectory {
+                path: venv_info.path().display().to_string(),
+                location: location.display().to_string(),
+            });
+        }
+        if let Some(location) = safety::protected_python_location(venv_info.path()) {
+            return Err(VenvCleanerError::ProtectedSystemPath {
+                path: venv_info.path().display().to_string(),
+                location: location.display().to_string(),
+            });
+        }
+
+        if let Some(protected) = self.policy.protecting(venv_info.path()) {
+            return Err(VenvCleanerError::PolicyViolation(format!(
+                "{} is inside the protected path {}",
+                venv_info.path().display(),
+                protected.display()
+            )));
+        }
+
+        self.policy.check_force(self.force_mode)?;
+        if self.force_mode && self.is_size_outlier(venv_info) {
+            return Err(VenvCleanerError::PolicyViolation(format!(
+                "{} is {}, too large for a virtual environment; it may be a misdetected data directory, so it is only deleted interactively (see --exclude-size-above)",
+                venv_info.path().display(),
+                venv_info.size_formatted()
+            )));
+        }
+        if self.force_mode {
+            if let Some(limit) = self.policy.max_force_delete_size {
+                if venv_info.size() > limit {
+                    return Err(VenvCleanerError::PolicyViolation(format!(
+                        "{} is {}, larger than the {} allowed without a prompt",
+                        venv_info.path().display(),
+                        venv_info.size_formatted(),
+                        limit
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that a .venv about to be deleted has an owner the scan keeps
+    ///
+    /// Running as root, or with CAP_DAC_OVERRIDE, could delete anyone's
+    /// environment. The owner is looked at again right before deleting, so
+    /// another user's .venv reached any other way (a plan, a changed owner)
+    /// is only deleted with `--all-users`, or `--user` naming that user.
+    ///
+    /// # Returns
+    /// An error naming the owner if the .venv belongs to someone else
+    pub fn check_owner(&self, venv_info: &VenvInfo) -> Result<()> {
+        let owner = ownership::path_owner(venv_info.path());
+        if self.owner_filter.matches(owner) {
+            return Ok(());
+        }
+        Err(VenvCleanerError::OwnedByOtherUser {
+            path: venv_info.path().display().to_string(),
+            owner: ownership::describe_owner(owner),
+        })
+    }
+
+    /// Get the deletion restrictions in effect
+    pub fn policy(&self) -> &PolicyConfig {
+        &self.policy
+    }
+
+    /// Remember a trashed .venv so `trash purge` can remove it later
+    ///
+    /// The folder is already in the trash, so failing to record it is only logged.
+    fn record_trashed(&self, venv_info: &VenvInfo) {
+        let Some(ledger_path) = TrashLedger::default_path() else {
+            return;
+        };
+        let entry = TrashedVenv {
+            path: venv_info.path().to_path_buf(),
+            size_bytes: venv_info.size().bytes(),
+            trashed_at: Utc::now(),
+        };
+        if let Err(e) = TrashLedger::record(&ledger_path, entry) {
+            warn!("Failed to record trashed {}: {}", venv_info.path().display(), e);
+        }
+    }
+
+    /// Delete several .venv directories, up to `delete_jobs` at a time
+    ///
+    /// # Arguments
+    /// * `venvs` - The .venv directories to delete
+    /// * `on_done` - Called on the calling thread as each deletion finishes
+    ///
+    /// # Returns
+    /// The result of each deletion, in the order of `venvs`
+    pub fn delete_venv_directories<F>(&self, venvs: &[VenvInfo], mut on_done: F) -> Vec<Result<()>>
+    where
+        F: FnMut(&VenvInfo, &Result<()>),
+    {
+        self.delete_many(venvs, &BatchOptions::new(), |item| on_done(&item.venv, &item.result))
+            .into_items()
+            .into_iter()
+            .map(|item| item.result)
+            .collect()
+    }
+
+    /// Delete a batch of .venv directories and summarise the outcome
+    ///
+    /// Up to `delete_jobs` directories (or the options' jobs) are deleted at
+    /// once. Once cancelled, the deletions running finish and the rest fail
+    /// with `OperationCancelled`; a batch larger than the policy allows is
+    /// refused as a whole.
+    ///
+    /// # Arguments
+    /// * `venvs` - The .venv directories to delete
+    /// * `options` - How the batch is run
+    /// * `on_done` - Called on the calling thread with each item as it finishes
+    ///
+    /// # Returns
+    /// Every item's outcome and timing, in the order of `venvs`, with the batch totals
+    pub fn delete_many<F>(&self, venvs: &[VenvInfo], options: &BatchOptions, mut on_done: F) -> BatchResult
+    where
+        F: FnMut(&BatchItem),
+    {
+        let jobs = options.jobs().unwrap_or(self.delete_jobs);
+        let cancellation = options.cancellation().unwrap_or(&self.cancellation);
+        let _span = info_span!("delete", count = venvs.len(), jobs).entered();
+        let started_at = Utc::now();
+        let started = Instant::now();
+
+        let mut report = |venv_info: &VenvInfo, result: &Result<()>, elapsed: Duration| {
+            let item = BatchItem { venv: venv_info.clone(), result: result.clone(), elapsed };
+            on_done(&item);
+            item
+        };
+
+        // Too large a batch is refused as a whole rather than cut short at an arbitrary point
+        if let Err(e) = self.policy.check_batch_size(venvs.len()) {
+            warn!("{}", e);
+            let items = venvs.iter().map(|venv_info| report(venv_info, &Err(e.clone()), Duration::ZERO)).collect();
+            return BatchResult::new(items, started_at, started.elapsed());
+        }
+        debug!("Deleting {} .venv directories with {} jobs", venvs.len(), jobs);
+        let batch_span = Span::current();
+        let mut items: Vec<Option<BatchItem>> = venvs.iter().map(|_| None).collect();
+        concurrency::run_parallel(
+            venvs,
+            jobs,
+            |venv_info| {
+                let _span = batch_span.enter();
+                // Deletions already running finish; the rest are not started
+                if cancellation.is_cancelled() {
+                    return (Err(VenvCleanerError::OperationCancelled), Duration::ZERO);
+                }
+                let started = Instant::now();
+                (self.delete_venv_directory(venv_info), started.elapsed())
+            },
+            |index, (result, elapsed)| items[index] = Some(report(&venvs[index], result, *elapsed)),
+        );
+
+        // Every item was reported once
+        BatchResult::new(items.into_iter().flatten().collect(), started_at, started.elapsed())
+    }
+
+    /// Get the questions deleting a .venv needs answered, in the order they are asked
+    ///
+    /// # Arguments
+    /// * `venv_info` - The .venv to delete
+    pub fn deletion_requests<'a>(&self, venv_info: &'a VenvInfo) -> Vec<ConfirmationRequest<'a>> {
+        let mut requests = vec![ConfirmationRequest::Delete(venv_info)];
+        if venv_info.has_user_data() {
+            requests.push(ConfirmationRequest::DeleteUserData(venv_info));
+        }
+        if self.is_size_outlier(venv_info) {
+            requests.push(ConfirmationRequest::DeleteOversized(venv_info));
+        }
+        requests
+    }
+
+    /// Confirm .venv directories with a provider, then delete the confirmed ones
+    ///
+    /// Every question is asked before anything is deleted, so an approval
+    /// flow sees the whole batch; the confirmed directories are then deleted
+    /// as by `delete_venv_directories`.
+    ///
+    /// # Arguments
+    /// * `venvs` - The .venv directories to delete
+    /// * `provider` - Answers the questions of `deletion_requests`
+    /// * `on_done` - Called with each .venv and its result as soon as it is settled
+    ///
+    /// # Returns
+    /// Per .venv, in the order given: true if deleted, false if a question
+    /// was declined, or why deleting failed; an error from the provider stops
+    /// the batch before anything is deleted
+    pub fn delete_confirmed<F>(
+        &self,
+        venvs: &[VenvInfo],
+        provider: &dyn ConfirmationProvider,
+        mut on_done: F,
+    ) -> Result<Vec<Result<bool>>>
+    where
+        F: FnMut(&VenvInfo, &Result<bool>),
+    {
+        let mut confirmed = Vec::with_capacity(venvs.len());
+        for venv_info in venvs {
+            let mut declined = None;
+            for request in self.deletion_requests(venv_info) {
+                if !provider.confirm(&request)? {
+                    declined = Some(request.declined_reason());
+                    break;
+                }
+            }
+            if let Some(reason) = declined {
+                info!("Keeping {}: {}", venv_info.path().display(), reason);
+            }
+            confirmed.push(declined.is_none());
+        }
+
+        for (venv_info, _) in venvs.iter().zip(&confirmed).filter(|(_, confirmed)| !**confirmed) {
+            on_done(venv_info, &Ok(false));
+        }
+        let to_delete: Vec<VenvInfo> = venvs
+            .iter()
+            .zip(&confirmed)
+            .filter(|(_, confirmed)| **confirmed)
+            .map(|(venv_info, _)| venv_info.clone())
+            .collect();
+        let mut deleted = self
+            .delete_venv_directories(&to_delete, |venv_info, result| on_done(venv_info, &result.clone().map(|()| true)))
+            .into_iter();
+        Ok(confirmed
+            .into_iter()
+            .map(|confirmed| {
+                if confirmed {
+                    deleted.next().map_or(Ok(false), |result| result.map(|()| true))
+                } else {
+                    Ok(false)
+                }
+            })
+            .collect())
+    }
+
+    /// Remove a directory permanently, or move it to the trash if enabled
+    ///
+    /// # Arguments
+    /// * `path` - The directory
+    /// * `in_background` - Whether the removal may go on after returning (see `with_background_removal`)
+    fn remove_directory(&self, path: &Path, in_background: bool) -> Result<()> {
+        // Check if we have permission to delete
+        if !self.fs.can_delete(path)? {
+            return Err(VenvCleanerError::PermissionDenied {
+                path: path.display().to_string(),
+            });
+        }
+
+        if self.use_trash {
+            return self.fs.move_to_trash(path);
+        }
+
+        // Renaming first means an interruption leaves a marked directory, not a broken .venv
+        let marked = pending_delete::mark_for_deletion_in(self.fs.as_ref(), path)?;
+        match self.background_removals.as_ref().filter(|_| in_background) {
+            Some(removals) => {
+                removals.spawn(self.fs_backend(), path.to_path_buf(), marked);
+                Ok(())
+            }
+            None => pending_delete::remove_marked_in(self.fs.as_ref(), path, &marked),
+        }
+    }
+
+    /// Find deletions interrupted after their .venv was renamed
+    ///
+    /// The roots are walked as a scan walks them, so excluded and system
+    /// directories are left alone.
+    ///
+    /// # Returns
+    /// The pending deletions in the search scope, sorted by path
+    pub fn find_pending_deletions(&self) -> Vec<PendingDeletion> {
+        if self.offline.is_some() {
+            return Vec::new();
+        }
+        let mut pending: Vec<PendingDeletion> = self
+            .roots()
+            .into_iter()
+            .flat_map(|root| self.for_root(root).walk_for_pending())
+            .collect();
+        pending.sort_by(|a, b| sorting::compare_paths(a.path(), b.path()));
+        pending.dedup_by(|a, b| a.path() == b.path());
+        pending
+    }
+
+    /// Walk the base directory for directories renamed for deletion
+    ///
+    /// Marked directories and .venv directories are not walked into; without
+    /// recursion only the children of the base directory are looked at.
+    fn walk_for_pending(&self) -> Vec<PendingDeletion> {
+        let mut pending = Vec::new();
+        let mut descend = |entry: &WalkEntry<'_>| {
+            if entry.depth == 0 {
+                return true;
+            }
+            if !entry.is_dir() || self.is_excluded(entry.path) {
+                return false;
+            }
+            if pending_delete::is_marked_name(entry.file_name()) {
+                if !self.recursive || self.includes.covers(&self.base_directory, entry.path) {
+                    pending.extend(PendingDeletion::from_path(entry.path));
+                }
+                return false;
+            }
+            self.recursive && entry.file_name() != ".venv" && entry.file_name() != ".git" && self.should_walk(entry)
+        };
+        self.fs.walk(&self.base_directory, false, &mut descend, &mut |entry| {
+            if let Err(WalkError { error, .. }) = entry {
+                debug!("Skipping unreadable entry: {}", error);
+            }
+            ControlFlow::Continue(())
+        });
+        pending
+    }
+
+    /// Finish an interrupted deletion, removing what is left
+    ///
+    /// Goes through the same policy, owner and hook checks as a deletion and
+    /// is recorded in the audit log under the original path.
+    ///
+    /// # Arguments
+    /// * `pending` - The interrupted deletion
+    ///
+    /// # Returns
+    /// The number of bytes freed
+    pub fn finish_pending_deletion(&self, pending: &PendingDeletion) -> Result<u64> {
+        let _span = info_span!("finish_pending", path = %pending.path().display()).entered();
+        FileUtils::ensure_exists_in(self.fs.as_ref(), pending.path())?;
+        let size = ByteSize::new(pending.size_bytes());
+        let venv_info = VenvInfo::new(pending.venv_path(), size, Utc::now(), Utc::now());
+        self.check_policy(&venv_info)?;
+        // The original path is gone, so the owner is read from the marked directory
+        self.check_owner(&VenvInfo::new(pending.path().to_path_buf(), size, Utc::now(), Utc::now()))?;
+
+        if self.dry_run {
+            println!("DRY RUN: Would finish deleting: {}", pending.path().display());
+            return Ok(pending.size_bytes());
+        }
+
+        self.delete_hooks.run_pre_delete(&venv_info)?;
+        info!("Finishing the deletion of {}", pending.path().display());
+        pending_delete::remove_marked_in(self.fs.as_ref(), venv_info.path(), pending.path())?;
+        self.delete_hooks.run_post_delete(&venv_info);
+        self.audit(AuditAction::Delete, &venv_info)?;
+        Ok(pending.size_bytes())
+    }
+
+    /// Undo an interrupted deletion, renaming the directory back to its original name
+    ///
+    /// # Arguments
+    /// * `pending` - The interrupted deletion
+    ///
+    /// # Returns
+    /// The restored .venv path
+    pub fn restore_pending_deletion(&self, pending: &PendingDeletion) -> Result<PathBuf> {
+        if self.dry_run {
+            println!("DRY RUN: Would restore: {}", pending.venv_path().display());
+            return Ok(pending.venv_path());
+        }
+
+        pending.restore()
+    }
+
+    /// Find the build artifacts (`build/`, `dist/`, `*.egg-info`, `.tox`) of
+    /// the project that owns a .venv directory
+    ///
+    /// # Arguments
+    /// * `venv_info` - Information about the .venv whose project should be inspected
+    ///
+    /// # Returns
+    /// The artifacts found next to the .venv, or an empty list if it has no
+    /// parent or the run archives or plans rather than deletes
+    pub fn find_project_artifacts(&self, venv_info: &VenvInfo) -> Result<Vec<ProjectArtifact>> {
+        if self.archive_dir.is_some() || self.offline.is_some() {
+            return Ok(Vec::new());
+        }
+        match venv_info.parent_path() {
+            Some(project_dir) => ProjectArtifacts::find(project_dir),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Get the archived .venv directories whose stub files the last scan walked past
+    ///
+    /// # Returns
+    /// The archived environments, sorted by path; unreadable stubs are skipped
+    pub fn last_archived_venvs(&self) -> Vec<ArchivedVenv> {
+        let mut stubs = self.archive_stubs.lock().map(|stubs| stubs.clone()).unwrap_or_default();
+        stubs.sort_by(|a, b| sorting::compare_paths(a, b));
+        stubs.dedup();
+        archive::load_archived(&stubs)
+    }
+
+    /// Restore an archived .venv directory in place
+    ///
+    /// # Arguments
+    /// * `archived` - The archived environment to restore
+    ///
+    /// # Returns
+    /// The path of the restored .venv directory
+    pub fn restore_archived_venv(&self, archived: &ArchivedVenv) -> Result<PathBuf> {
+        if self.dry_run {
+            println!("DRY RUN: Would restore: {}", archived.stub().venv_path().display());
+            return Ok(archived.stub().venv_path().to_path_buf());
+        }
+
+        VenvArchiver::unarchive(archived.stub_path())
+    }
+
+    /// Delete the archive (and stub file) of an archived .venv directory
+    ///
+    /// # Arguments
+    /// * `archived` - The archived environment to discard
+    ///
+    /// # Returns
+    /// The number of bytes freed
+    pub fn delete_archived_venv(&self, archived: &ArchivedVenv) -> Result<u64> {
+        if self.dry_run {
+            println!("DRY RUN: Would delete archive: {}", archived.stub().archive_path().display());
+            return Ok(archived.compressed_size().unwrap_or(0));
+        }
+
+        VenvArchiver::delete_archive(archived.stub_path())
+    }
+
+    /// Slim a .venv directory in place, keeping the environment usable
+    ///
+    /// Honours dry-run mode: nothing is removed, but the report still lists
+    /// what would be and how many bytes that would reclaim.
+    ///
+    /// # Arguments
+    /// * `venv_info` - Information about the .venv directory to slim
+    /// * `strip_symbols` - Whether to also strip debug symbols from shared libraries
+    ///
+    /// # Returns
+    /// A report of the removed directories and bytes saved
+    pub fn slim_venv_directory(&self, venv_info: &VenvInfo, strip_symbols: bool) -> Result<SlimReport> {
+        if !self.dry_run && !FileUtils::can_delete_directory(venv_info.path())? {
+            return Err(VenvCleanerError::PermissionDenied {
+                path: venv_info.path().display().to_string(),
+            });
+        }
+
+        VenvSlimmer::new(self.dry_run)
+            .with_strip_symbols(strip_symbols)
+            .slim(venv_info.path())
+    }
+
+    /// Delete a build artifact of a project as part of a deep clean
+    ///
+    /// The artifact goes through the policy, owner, hook and audit steps of a
+    /// .venv deletion, and goes to the trash when a .venv would.
+    ///
+    /// # Arguments
+    /// * `artifact` - The artifact to delete (as returned by `find_project_artifacts`)
+    ///
+    /// # Returns
+    /// The number of bytes freed
+    pub fn delete_project_artifact(&self, artifact: &ProjectArtifact) -> Result<u64> {
+        let _span = info_span!("delete_artifact", path = %artifact.path().display()).entered();
+        if self.archive_dir.is_some() || self.offline.is_some() {
+            return Err(VenvCleanerError::InvalidArgument(format!(
+                "{} is a build artifact; archiving and offline plans only handle .venv directories",
+                artifact.path().display()
+            )));
+        }
+        let artifact_info = VenvInfo::new(artifact.path().to_path_buf(), ByteSize::new(artifact.size_bytes()), Utc::now(), Utc::now());
+        FileUtils::ensure_exists_in(self.fs.as_ref(), artifact.path())?;
+        self.check_policy(&artifact_info)?;
+        self.check_owner(&artifact_info)?;
+
+        if self.dry_run {
+            println!("DRY RUN: Would delete: {}", artifact.path().display());
+            return Ok(artifact.size_bytes());
+        }
+
+        self.delete_hooks.run_pre_delete(&artifact_info)?;
+        info!("Deleting build artifact: {}", artifact.path().display());
+        self.remove_directory(artifact.path(), false)?;
+        if self.use_trash {
+            self.record_trashed(&artifact_info);
+        }
+        self.delete_hooks.run_post_delete(&artifact_info);
+        self.audit(if self.use_trash { AuditAction::Trash } else { AuditAction::Delete }, &artifact_info)?;
+        Ok(artifact.size_bytes())
+    }
+
+    /// Get the base directory being searched
+    pub fn base_directory(&self) -> &Path {
+        &self.base_directory
+    }
+
+    /// Check if recursive search is enabled
+    pub fn is_recursive(&self) -> bool {
+        self.recursive
+    }
+
+    /// Check if deleted directories are moved to the trash
+    pub fn is_using_trash(&self) -> bool {
+        self.use_trash
+    }
+
+    /// Get the deletion mode asked for (see `is_using_trash` for the outcome)
+    pub fn delete_mode(&self) -> DeleteMode {
+        self.delete_mode
+    }
+
+    /// Describe where deleted directories go, for headers and confirmation dialogs
+    ///
+    /// # Returns
+    /// E.g. "Trash: deleted folders can be restored" or, when the trash
+    /// was asked for but there is none, why deletions are permanent
+    pub fn delete_mode_description(&self) -> String {
+        if self.use_trash {
+            return "Trash: deleted folders can be restored".to_string();
+        }
+        match (self.delete_mode, &self.filesystem) {
+            (DeleteMode::Permanent, _) => "Permanent delete: deleted folders cannot be restored".to_string(),
+            (_, Some(filesystem)) if !filesystem.supports_trash() => format!(
+                "Permanent delete: {} is on a {} network share without a trash",
+                self.base_directory.display(),
+                filesystem.fs_type
+            ),
+            _ => "Permanent delete: this system has no trash".to_string(),
+        }
+    }
+
+    /// Get the file system the base directory lives on, if known
+    pub fn filesystem(&self) -> Option<&FilesystemInfo> {
+        self.filesystem.as_ref()
+    }
+
+    /// Explain why the trash is unlikely to work for the base directory
+    ///
+    /// # Returns
+    /// A warning when the trash is enabled on a file system without one
+    pub fn trash_warning(&self) -> Option<String> {
+        let filesystem = self.filesystem.as_ref()?;
+        (self.use_trash && !self.dry_run && !filesystem.supports_trash()).then(|| format!(
+            "{} is on a {} network share, which usually has no trash; moving folders may fail or copy them across the network",
+            self.base_directory.display(),
+            filesystem.fs_type
+        ))
+    }
+
+    /// Check if likely project roots are walked first
+    pub fn is_priority_scan(&self) -> bool {
+        self.priority_scan
+    }
+
+    /// Get the archive directory, if .venv directories are archived instead of deleted
+    pub fn archive_dir(&self) -> Option<&Path> {
+        self.archive_dir.as_deref()
+    }
+
+    /// Check if recursive scans honour .gitignore rules
+    pub fn is_respecting_gitignore(&self) -> bool {
+        self.respect_gitignore
+    }
+
+    /// Check if recursive scans skip hidden directories
+    pub fn is_skipping_hidden(&self) -> bool {
+        self.skip_hidden
+    }
+
+    /// Check if folders named .venv must look like virtual environments to be found
+    pub fn is_validating(&self) -> bool {
+        self.validate
+    }
+
+    /// Get the directories recursive scans never enter
+    pub fn excludes(&self) -> &[PathBuf] {
+        &self.excludes
+    }
+
+    /// Get the subtrees recursive scans are limited to
+    pub fn includes(&self) -> &IncludeSet {
+        &self.includes
+    }
+
+    /// Get the environment layouts found besides `.venv`
+    pub fn detectors(&self) -> &[Detector] {
+        &self.detectors
+    }
+
+    /// Get the commands run before and after each deletion
+    pub fn delete_hooks(&self) -> &DeleteHooks {
+        &self.delete_hooks
+    }
+
+    /// Check if force mode is enabled
+    pub fn is_force_mode(&self) -> bool {
+        self.force_mode
+    }
+
+    /// Check if this is a dry run
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Get the verbosity level
+    pub fn verbosity(&self) -> u8 {
+        self.verbosity
+    }
+}
+
+/// Check whether an error means a folder may not be read, macOS privacy settings aside
+fn is_access_denied(error: &std::io::Error) -> bool {
+    error.kind() == std::io::ErrorKind::PermissionDenied && !platform::is_privacy_denied(error)
+}
+
+/// Check whether a directory name is hidden and not one a scan looks for
+fn is_skipped_hidden(name: &std::ffi::OsStr) -> bool {
+    name.to_string_lossy().starts_with('.') && name != ".venv"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Create a .venv with the layout `FileUtils::is_valid_venv_directory` expects
+    fn create_venv(venv_path: &Path) {
+        fs::create_dir_all(venv_path.join("bin")).unwrap();
+        fs::write(venv_path.join("pyvenv.cfg"), "home = /usr/bin\n").unwrap();
+    }
+
+    #[test]
+    fn test_venv_cleaner_creation() {
+        let temp_dir = TempDir::new().unwrap();
+        let cleaner = VenvCleaner::new(
+            temp_dir.path().to_path_buf(),
+            false,
+            false,
+            true,
+            1,
+        );
+
+        assert_eq!(cleaner.base_directory(), temp_dir.path());
+        assert!(!cleaner.is_recursive());
+        assert!(!cleaner.is_force_mode());
+        assert!(cleaner.is_dry_run());
+        assert_eq!(cleaner.verbosity(), 1);
+    }
+
+    #[test]
+    fn test_find_venv_directories_empty() {
+        let temp_dir = TempDir::new().unwrap();
+        let cleaner = VenvCleaner::new(
+            temp_dir.path().to_path_buf(),
+            false,
+            false,
+            true,
+            0,
+        );
+
+        let result = cleaner.find_venv_directories();
+        assert!(result.is_err());
+        assert!(matches!(result.unwrap_err(), VenvCleanerError::NoVenvFound));
+    }
+
+    #[test]
+    fn test_find_venv_directories_with_venv() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv_path = temp_dir.path().join(".venv");
+        create_venv(&venv_path);
+
+        let cleaner = VenvCleaner::new(
+            temp_dir.path().to_path_buf(),
+            false,
+            false,
+            true,
+            0,
+        );
+
+        let result = cleaner.find_venv_directories();
+        assert!(result.is_ok());
+        let venv_dirs = result.unwrap();
+        assert_eq!(venv_dirs.len(), 1);
+        assert_eq!(venv_dirs[0].path(), &venv_path);
+    }
+
+    #[test]
+    fn test_delete_project_artifacts() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv_path = temp_dir.path().join(".venv");
+        create_venv(&venv_path);
+        fs::create_dir(temp_dir.path().join("dist")).unwrap();
+        fs::write(temp_dir.path().join("dist").join("pkg.whl"), "wheel").unwrap();
+
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), false, true, false, 0);
+        let venv_dirs = cleaner.find_venv_directories().unwrap();
+        let artifacts = cleaner.find_project_artifacts(&venv_dirs[0]).unwrap();
+        assert_eq!(artifacts.len(), 1);
+
+        let freed = cleaner.delete_project_artifact(&artifacts[0]).unwrap();
+        assert_eq!(freed, 5);
+        assert!(!temp_dir.path().join("dist").exists());
+        assert!(venv_path.exists());
+    }
+
+    #[test]
+    fn test_project_artifacts_follow_the_policy() {
+        let temp_dir = TempDir::new().unwrap();
+        create_venv(&temp_dir.path().join("proj").join(".venv"));
+        fs::create_dir_all(temp_dir.path().join("proj").join("build")).unwrap();
+        let audit_path = temp_dir.path().join("audit.log");
+
+        let protected = PolicyConfig { protected_paths: vec![temp_dir.path().join("proj")], ..PolicyConfig::default() };
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, false, false, 0).with_policy(protected);
+        let venv_dirs = cleaner.find_venv_directories().unwrap();
+        let artifacts = cleaner.find_project_artifacts(&venv_dirs[0]).unwrap();
+        let error = cleaner.delete_project_artifact(&artifacts[0]).unwrap_err();
+        assert!(matches!(error, VenvCleanerError::PolicyViolation(_)));
+
+        let archiving = VenvCleaner::new(temp_dir.path().to_path_buf(), true, false, false, 0)
+            .with_archive_dir(Some(temp_dir.path().join("archive")));
+        assert!(archiving.find_project_artifacts(&venv_dirs[0]).unwrap().is_empty());
+        assert!(archiving.delete_project_artifact(&artifacts[0]).is_err());
+
+        let policy = PolicyConfig { audit_log: Some(audit_path.clone()), ..PolicyConfig::default() };
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, false, false, 0).with_policy(policy);
+        cleaner.delete_project_artifact(&artifacts[0]).unwrap();
+        assert!(!temp_dir.path().join("proj").join("build").exists());
+        assert_eq!(AuditLog::new(audit_path).verify().unwrap().valid_entries, 1);
+    }
+
+    #[test]
+    fn test_policy_blocks_deletions() {
+        let temp_dir = TempDir::new().unwrap();
+        for project in ["shared", "big"] {
+            create_venv(&temp_dir.path().join(project).join(".venv"));
+            fs::write(temp_dir.path().join(project).join(".venv").join("lib.so"), vec![0u8; 2048]).unwrap();
+        }
+
+        let policy = PolicyConfig {
+            protected_paths: vec![temp_dir.path().join("shared")],
+            require_trash: true,
+            max_force_delete_size: Some(ByteSize::new(ByteSize::KB)),
+            ..PolicyConfig::default()
+        };
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, true, true, 0)
+            .with_policy(policy)
+            .with_trash(false);
+        assert!(cleaner.is_using_trash());
+
+        for venv_info in cleaner.find_venv_directories().unwrap() {
+            let error = cleaner.delete_venv_directory(&venv_info).unwrap_err();
+            assert!(matches!(error, VenvCleanerError::PolicyViolation(_)));
+            let expected = if venv_info.path().starts_with(temp_dir.path().join("shared")) { "protected" } else { "larger" };
+            assert!(error.to_string().contains(expected));
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_policy_refuses_system_directories() {
+        let cleaner = VenvCleaner::new(PathBuf::from("/"), true, false, false, 0);
+        let venv_info = VenvInfo::new(PathBuf::from("/etc/app/.venv"), ByteSize::new(0), Utc::now(), Utc::now());
+        let error = cleaner.check_policy(&venv_info).unwrap_err();
+        assert!(matches!(error, VenvCleanerError::SystemDirectory { ref location, .. } if location == "/etc"));
+    }
+
+    #[test]
+    fn test_force_mode_keeps_size_outliers() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv_path = temp_dir.path().join("data").join(".venv");
+        create_venv(&venv_path);
+
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, true, false, 0).with_exclude_size_above(Some(ByteSize::new(1)));
+        let venv_info = cleaner.find_venv_directories().unwrap().remove(0);
+        assert!(cleaner.is_size_outlier(&venv_info));
+        let error = cleaner.delete_venv_directory(&venv_info).unwrap_err();
+        assert!(error.to_string().contains("misdetected data directory"));
+        assert!(venv_path.exists());
+
+        // Interactive runs confirm outliers themselves, so core leaves them to the prompt
+        let interactive = VenvCleaner::new(temp_dir.path().to_path_buf(), true, false, false, 0).with_exclude_size_above(Some(ByteSize::new(1)));
+        assert!(interactive.check_policy(&venv_info).is_ok());
+        assert!(!cleaner.clone().with_exclude_size_above(None).is_size_outlier(&venv_info));
+    }
+
+    #[test]
+    fn test_delete_many_summarises_the_batch() {
+        let temp_dir = TempDir::new().unwrap();
+        for project in ["a", "b", "c"] {
+            create_venv(&temp_dir.path().join(project).join(".venv"));
+        }
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, true, false, 0).with_delete_jobs(Some(2));
+        let mut venv_dirs = cleaner.find_venv_directories().unwrap();
+        venv_dirs.sort_by(|a, b| a.path().cmp(b.path()));
+        fs::remove_dir_all(venv_dirs[2].path()).unwrap();
+
+        let mut reported = 0;
+        let batch = cleaner.delete_many(&venv_dirs, &BatchOptions::new(), |_| reported += 1);
+        assert_eq!(reported, 3);
+        assert_eq!((batch.deleted(), batch.vanished(), batch.failed(), batch.cancelled()), (2, 1, 0, 0));
+        assert_eq!(batch.freed_bytes(), (venv_dirs[0].size() + venv_dirs[1].size()).bytes());
+        assert_eq!(batch.items()[0].venv.path(), venv_dirs[0].path());
+        assert!(batch.items().iter().all(|item| item.elapsed <= batch.elapsed()));
+
+        // A cancelled token of the batch's own leaves everything alone
+        create_venv(venv_dirs[0].path());
+        let token = CancellationToken::new();
+        token.cancel();
+        let batch = cleaner.delete_many(&venv_dirs[..1], &BatchOptions::new().with_cancellation(token).with_jobs(Some(1)), |_| {});
+        assert_eq!((batch.cancelled(), batch.attempted().count()), (1, 0));
+        assert!(venv_dirs[0].path().exists());
+    }
+
+    #[test]
+    fn test_policy_caps_batch_size() {
+        let temp_dir = TempDir::new().unwrap();
+        for project in ["a", "b", "c"] {
+            create_venv(&temp_dir.path().join(project).join(".venv"));
+        }
+
+        let policy = PolicyConfig { max_batch_size: Some(2), ..PolicyConfig::default() };
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, false, false, 0).with_policy(policy);
+        let venv_dirs = cleaner.find_venv_directories().unwrap();
+
+        let mut reported = 0;
+        let results = cleaner.delete_venv_directories(&venv_dirs, |_, _| reported += 1);
+        assert_eq!(reported, 3);
+        assert!(results.iter().all(|result| matches!(result, Err(VenvCleanerError::PolicyViolation(_)))));
+        assert!(venv_dirs.iter().all(|venv_info| venv_info.path().exists()));
+
+        assert!(cleaner.delete_venv_directories(&venv_dirs[..2], |_, _| {}).iter().all(Result::is_ok));
+    }
+
+    #[test]
+    fn test_deletions_are_audited() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("proj");
+        create_venv(&project.join(".venv"));
+        let audit_path = temp_dir.path().join("audit.log");
+
+        let policy = PolicyConfig { audit_log: Some(audit_path.clone()), ..PolicyConfig::default() };
+        let cleaner = VenvCleaner::new(project, false, true, false, 0).with_policy(policy);
+        let venv_dirs = cleaner.find_venv_directories().unwrap();
+        cleaner.delete_venv_directory(&venv_dirs[0]).unwrap();
+
+        let log = AuditLog::new(audit_path);
+        assert_eq!(log.verify().unwrap().valid_entries, 1);
+        assert!(fs::read_to_string(log.path()).unwrap().contains("\"action\":\"delete\""));
+    }
+
+    #[test]
+    fn test_find_pending_deletions_honours_the_scan_scope() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut marked = Vec::new();
+        for project in ["app", "scratch/old", "app/.tox"] {
+            let venv_path = temp_dir.path().join(project).join(".venv");
+            create_venv(&venv_path);
+            marked.push(pending_delete::mark_for_deletion(&venv_path).unwrap());
+        }
+
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, false, false, 0)
+            .with_excludes(vec![temp_dir.path().join("scratch")]);
+        let found: Vec<PathBuf> = cleaner.find_pending_deletions().iter().map(|p| p.path().to_path_buf()).collect();
+        assert_eq!(found, vec![marked[2].clone(), marked[0].clone()]);
+
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, false, false, 0).with_skip_hidden(true);
+        assert!(!cleaner.find_pending_deletions().iter().any(|p| p.path() == marked[2]));
+
+        // Without recursion only the base directory's own children count
+        assert!(VenvCleaner::new(temp_dir.path().to_path_buf(), false, false, false, 0).find_pending_deletions().is_empty());
+        let cleaner = VenvCleaner::new(temp_dir.path().join("app"), false, false, false, 0);
+        assert_eq!(cleaner.find_pending_deletions().len(), 1);
+    }
+
+    #[test]
+    fn test_finish_pending_deletion_is_checked_and_audited() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv_path = temp_dir.path().join("proj").join(".venv");
+        create_venv(&venv_path);
+        let marked = pending_delete::mark_for_deletion(&venv_path).unwrap();
+        let audit_path = temp_dir.path().join("audit.log");
+
+        let protected = PolicyConfig { protected_paths: vec![temp_dir.path().join("proj")], ..PolicyConfig::default() };
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, false, false, 0).with_policy(protected);
+        let pending = cleaner.find_pending_deletions();
+        let error = cleaner.finish_pending_deletion(&pending[0]).unwrap_err();
+        assert!(matches!(error, VenvCleanerError::PolicyViolation(_)));
+        assert!(marked.exists());
+
+        let policy = PolicyConfig { audit_log: Some(audit_path.clone()), ..PolicyConfig::default() };
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, false, false, 0).with_policy(policy);
+        assert!(cleaner.finish_pending_deletion(&pending[0]).unwrap() > 0);
+        assert!(!marked.exists());
+        let log = fs::read_to_string(audit_path).unwrap();
+        assert!(log.contains("\"action\":\"delete\"") && log.contains("proj"));
+    }
+
+    #[test]
+    fn test_delete_vanished_venv() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("proj");
+        create_venv(&project.join(".venv"));
+
+        let cleaner = VenvCleaner::new(project, false, true, false, 0);
+        let venv_dirs = cleaner.find_venv_directories().unwrap();
+        fs::remove_dir_all(venv_dirs[0].path()).unwrap();
+
+        let error = cleaner.delete_venv_directory(&venv_dirs[0]).unwrap_err();
+        assert!(error.is_vanished());
+        assert!(error.to_string().contains("no longer exists"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_delete_on_unplugged_volume() {
+        let temp_dir = TempDir::new().unwrap();
+        let drive = temp_dir.path().join("USB");
+        let project = drive.join("proj");
+        create_venv(&project.join(".venv"));
+
+        // The drive's mount point is left behind as a plain directory
+        let cleaner = VenvCleaner::new(project, false, true, false, 0);
+        let venv_info = cleaner.find_venv_directories().unwrap().remove(0).with_removable_volume(Some(drive.clone()));
+        let error = cleaner.delete_venv_directory(&venv_info).unwrap_err();
+        assert!(error.is_volume_unavailable());
+        assert!(error.to_string().contains("no longer available"));
+        assert!(venv_info.path().exists());
+
+        // And once it is removed too, the drive is still blamed rather than the .venv
+        fs::remove_dir_all(&drive).unwrap();
+        assert!(cleaner.delete_venv_directory(&venv_info).unwrap_err().is_volume_unavailable());
+    }
+
+    #[test]
+    fn test_delete_confirmed_asks_the_provider() {
+        let temp_dir = TempDir::new().unwrap();
+        for project in ["drop", "keep"] {
+            create_venv(&temp_dir.path().join(project).join(".venv"));
+        }
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, true, false, 0);
+        let mut venv_dirs = cleaner.find_venv_directories().unwrap();
+        venv_dirs.sort_by(|a, b| a.path().cmp(b.path()));
+
+        // A provider failing stops the batch before anything is deleted
+        let failing = |_: &ConfirmationRequest<'_>| -> Result<bool> { Err(VenvCleanerError::OperationCancelled) };
+        assert!(cleaner.delete_confirmed(&venv_dirs, &failing, |_, _| {}).is_err());
+        assert!(venv_dirs.iter().all(|venv| venv.path().exists()));
+
+        let provider = |request: &ConfirmationRequest<'_>| -> Result<bool> {
+            Ok(!request.venv().path().starts_with(temp_dir.path().join("keep")))
+        };
+        let mut settled = Vec::new();
+        let results = cleaner
+            .delete_confirmed(&venv_dirs, &provider, |venv, result| settled.push((venv.path().to_path_buf(), result.is_ok())))
+            .unwrap();
+        assert_eq!(results.into_iter().map(|result| result.unwrap()).collect::<Vec<_>>(), vec![true, false]);
+        assert_eq!(settled.len(), 2);
+        assert!(!venv_dirs[0].path().exists());
+        assert!(venv_dirs[1].path().exists());
+    }
+
+    #[test]
+    fn test_with_trash() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv_path = temp_dir.path().join(".venv");
+        create_venv(&venv_path);
+
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), false, true, true, 0);
+        assert!(!cleaner.is_using_trash());
+
+        // Dry runs never touch the trash either
+        let cleaner = cleaner.with_trash(true);
+        assert!(cleaner.is_using_trash());
+        let venv_dirs = cleaner.find_venv_directories().unwrap();
+        cleaner.delete_venv_directory(&venv_dirs[0]).unwrap();
+        assert!(venv_path.exists());
+        assert_eq!(cleaner.trash_warning(), None);
+
+        // Each .venv records the file system it was found on
+        let base_fs = cleaner.filesystem().map(|filesystem| filesystem.fs_type.as_str());
+        assert_eq!(venv_dirs[0].filesystem(), base_fs);
+
+        // The frontends' default uses the trash only where there is one, and says which it is
+        let cleaner = cleaner.with_delete_mode(DeleteMode::TrashIfAvailable);
+        assert_eq!(cleaner.delete_mode(), DeleteMode::TrashIfAvailable);
+        assert_eq!(cleaner.is_using_trash(), DeleteMode::TrashIfAvailable.uses_trash(cleaner.filesystem()));
+        assert_eq!(cleaner.delete_mode_description().starts_with("Trash"), cleaner.is_using_trash());
+        let cleaner = cleaner.with_trash(false);
+        assert_eq!(cleaner.delete_mode_description(), "Permanent delete: deleted folders cannot be restored");
+    }
+
+    #[test]
+    fn test_priority_scan_visits_projects_first() {
+        let temp_dir = TempDir::new().unwrap();
+        // "Downloads" sorts before "zproject" alphabetically but is low priority
+        create_venv(&temp_dir.path().join("Downloads").join("old").join(".venv"));
+        create_venv(&temp_dir.path().join("zproject").join(".venv"));
+        fs::write(temp_dir.path().join("zproject").join("pyproject.toml"), "").unwrap();
+
+        // One sizing thread keeps results in walk order
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, false, false, 0)
+            .with_priority_scan(true)
+            .with_scan_threads(Some(1));
+        let mut order = Vec::new();
+        let venvs = cleaner
+            .find_venv_directories_with(|venv| order.push(venv.location()))
+            .unwrap();
+
+        assert_eq!(venvs.len(), 2);
+        assert!(order[0].ends_with("zproject"));
+        assert!(order[1].contains("Downloads"));
+    }
+
+    #[test]
+    fn test_validation_skips_folders_merely_named_venv() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = temp_dir.path().join("app").join(".venv");
+        create_venv(&venv);
+        let data = temp_dir.path().join("data").join(".venv");
+        fs::create_dir_all(&data).unwrap();
+        fs::write(data.join("samples.csv"), "a,b\n").unwrap();
+
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, false, true, 0);
+        let venvs = cleaner.find_venv_directories().unwrap();
+        assert_eq!(venvs.len(), 1);
+        assert_eq!(venvs[0].path(), venv);
+        assert_eq!(cleaner.last_invalid_venvs(), vec![data]);
+
+        let cleaner = cleaner.with_validation(false);
+        assert_eq!(cleaner.find_venv_directories().unwrap().len(), 2);
+        assert!(cleaner.last_invalid_venvs().is_empty());
+    }
+
+    #[test]
+    fn test_gitignore_pruning() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("project");
+        // ignore only applies .gitignore files inside git repositories
+        fs::create_dir_all(project.join(".git")).unwrap();
+        fs::write(project.join(".gitignore"), ".venv/\nvendor/\n").unwrap();
+        create_venv(&project.join(".venv"));
+        create_venv(&project.join("vendor").join("lib").join(".venv"));
+
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, false, false, 0);
+        assert_eq!(cleaner.find_venv_directories().unwrap().len(), 2);
+
+        // The project's own (ignored) .venv is still found; the one in vendor/ is pruned
+        let cleaner = cleaner.with_gitignore(true);
+        let venvs = cleaner.find_venv_directories().unwrap();
+        assert_eq!(venvs.len(), 1);
+        assert_eq!(venvs[0].path(), project.join(".venv"));
+    }
+
+    #[test]
+    fn test_analyze_sizes_counts_and_finds_user_data_in_one_walk() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv_path = temp_dir.path().join("project").join(".venv");
+        create_venv(&venv_path);
+        let site_packages = venv_path.join("lib").join("site-packages");
+        fs::create_dir_all(&site_packages).unwrap();
+        let big = vec![0u8; user_data::USER_DATA_THRESHOLD_BYTES as usize];
+        fs::write(venv_path.join("results.csv"), &big).unwrap();
+        fs::write(site_packages.join("iris.csv"), &big).unwrap();
+
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, false, true, 0);
+        let venv_info = cleaner.analyze_venv_directory(&venv_path).unwrap();
+        assert_eq!(venv_info.size().bytes(), 2 * big.len() as u64 + 16);
+        assert_eq!((venv_info.file_count(), venv_info.dir_count()), (Some(3), Some(3)));
+        assert_eq!(venv_info.user_data().len(), 1);
+        assert_eq!(venv_info.user_data()[0].path, venv_path.join("results.csv"));
+    }
+
+    #[test]
+    fn test_delete_archives_when_configured() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv_path = temp_dir.path().join("project").join(".venv");
+        fs::create_dir_all(venv_path.join("bin")).unwrap();
+
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, true, false, 0)
+            .with_archive_dir(Some(temp_dir.path().join("archives")));
+        let venv_info = cleaner.analyze_venv_directory(&venv_path).unwrap();
+        cleaner.delete_venv_directory(&venv_info).unwrap();
+
+        assert!(!venv_path.exists());
+        assert!(archive::has_stub(&temp_dir.path().join("project")));
+
+        assert!(matches!(cleaner.find_venv_directories(), Err(VenvCleanerError::NoVenvFound)));
+        let archived = cleaner.last_archived_venvs();
+        assert_eq!(archived.len(), 1);
+        assert_eq!(cleaner.walk_archive_stubs(), vec![archived[0].stub_path().to_path_buf()]);
+        assert_eq!(cleaner.restore_archived_venv(&archived[0]).unwrap(), venv_path);
+        assert!(venv_path.join("bin").is_dir());
+        let _ = cleaner.find_venv_directories();
+        assert!(cleaner.last_archived_venvs().is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_find_venv_in_non_utf8_directory() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join(OsStr::from_bytes(b"caf\xe9"));
+        let venv_path = project.join(".venv");
+        if fs::create_dir_all(venv_path.join("bin")).is_err() {
+            // Some file systems (e.g. on macOS) only accept UTF-8 names
+            return;
+        }
+        fs::write(venv_path.join("pyvenv.cfg"), "home = /usr/bin\n").unwrap();
+
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, true, false, 0);
+        let venv_dirs = cleaner.find_venv_directories().unwrap();
+        assert_eq!(venv_dirs.len(), 1);
+        assert_eq!(venv_dirs[0].path(), &venv_path);
+        assert_eq!(venv_dirs[0].project_name(), Some("caf\u{fffd}".to_string()));
+
+        // Deleting works on the exact OS path, not its lossy form
+        cleaner.delete_venv_directory(&venv_dirs[0]).unwrap();
+        assert!(!venv_path.exists());
+        assert!(project.is_dir());
+    }
+
+    #[test]
+    fn test_size_timeout_reports_partial_size() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv_path = temp_dir.path().join(".venv");
+        create_venv(&venv_path);
+        fs::write(venv_path.join("pyvenv.cfg"), "version = 3.11.4").unwrap();
+
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), false, false, true, 0)
+            .with_size_timeout(Some(Duration::ZERO));
+        let partial = cleaner.find_venv_directories().unwrap().remove(0);
+        assert!(partial.is_size_partial());
+
+        let complete = cleaner.recalculate_size(&partial).unwrap();
+        assert!(!complete.is_size_partial());
+        assert_eq!(complete.size(), ByteSize::new(16));
+    }
+
+    #[test]
+    fn test_parallel_scan_and_delete() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv_paths: Vec<PathBuf> = (0..6).map(|i| temp_dir.path().join(format!("p{}", i)).join(".venv")).collect();
+        for path in &venv_paths {
+            create_venv(path);
+        }
+
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, true, false, 0)
+            .with_scan_threads(Some(4))
+            .with_delete_jobs(Some(3));
+        assert_eq!((cleaner.scan_threads(), cleaner.delete_jobs()), (4, 3));
+
+        let mut streamed = 0;
+        let mut venvs = cleaner.find_venv_directories_with(|_| streamed += 1).unwrap();
+        assert_eq!((venvs.len(), streamed), (6, 6));
+
+        venvs.sort_by(|a, b| a.compare_by_path(b));
+        let mut finished = 0;
+        let results = cleaner.delete_venv_directories(&venvs, |_, _| finished += 1);
+        assert_eq!(finished, 6);
+        assert!(results.iter().all(|r| r.is_ok()));
+        assert!(venv_paths.iter().all(|path| !path.exists()));
+    }
+
+    #[test]
+    fn test_multiple_roots() {
+        let temp_dir = TempDir::new().unwrap();
+        let work = temp_dir.path().join("work");
+        let src = temp_dir.path().join("src");
+        for venv in [work.join("a").join(".venv"), work.join("nested").join("b").join(".venv"), src.join("c").join(".venv")] {
+            create_venv(&venv);
+        }
+
+        // The nested root overlaps the first one; its .venv is reported once
+        let cleaner = VenvCleaner::new(work.clone(), true, false, true, 0)
+            .with_extra_roots(vec![work.clone(), src.clone(), work.join("nested")]);
+        assert_eq!(cleaner.roots().len(), 3);
+
+        let mut streamed = 0;
+        let venvs = cleaner.find_venv_directories_with(|_| streamed += 1).unwrap();
+        assert_eq!((venvs.len(), streamed), (3, 3));
+        assert!(venvs.iter().any(|v| v.path().starts_with(&src)));
+
+        let empty = temp_dir.path().join("empty");
+        fs::create_dir_all(&empty).unwrap();
+        let cleaner = VenvCleaner::new(empty.clone(), true, false, true, 0).with_extra_roots(vec![empty]);
+        assert!(matches!(cleaner.find_venv_directories(), Err(VenvCleanerError::NoVenvFound)));
+    }
+
+    #[test]
+    fn test_temp_roots_find_abandoned_environments() {
+        let temp_dir = TempDir::new().unwrap();
+        let work = temp_dir.path().join("work");
+        let tmp = temp_dir.path().join("tmp");
+        create_venv(&work.join(".venv"));
+        // tox and CI environments are named anything, and hidden ones count too
+        let abandoned = tmp.join("tox-1").join(".tox").join("py311");
+        let fresh = tmp.join("tmpk3j1").join("venv");
+        create_venv(&abandoned);
+        create_venv(&fresh);
+        let two_days_ago = std::time::SystemTime::now() - Duration::from_secs(2 * 24 * 3600);
+        fs::File::open(&abandoned).unwrap().set_modified(two_days_ago).unwrap();
+
+        // Temp roots are walked recursively even when the other roots are not
+        let cleaner = VenvCleaner::new(work.clone(), false, false, true, 0).with_temp_roots(vec![tmp.clone(), work]);
+        assert_eq!(cleaner.roots().len(), 2);
+        let venvs = cleaner.find_venv_directories().unwrap();
+        let paths: Vec<&Path> = venvs.iter().map(VenvInfo::path).collect();
+        assert_eq!(paths.len(), 2);
+        assert!(paths.contains(&abandoned.as_path()));
+        assert!(!paths.contains(&fresh.as_path()));
+    }
+
+    #[test]
+    fn test_failed_root_keeps_other_results() {
+        let temp_dir = TempDir::new().unwrap();
+        let work = temp_dir.path().join("work");
+        let missing = temp_dir.path().join("unmounted");
+        create_venv(&work.join("a").join(".venv"));
+
+        let cleaner = VenvCleaner::new(work.clone(), true, false, true, 0).with_extra_roots(vec![missing.clone()]);
+        assert!(cleaner.find_venv_directories().is_ok());
+        assert!(cleaner.last_failed_roots().is_empty());
+
+        let cleaner = cleaner.with_partial_roots(true);
+        assert_eq!(cleaner.find_venv_directories().unwrap().len(), 1);
+        let failed = cleaner.last_failed_roots();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].0, missing);
+        assert!(matches!(failed[0].1, VenvCleanerError::PathError { .. }));
+
+        // With every root failing there are no results to show
+        let cleaner = VenvCleaner::new(missing.clone(), true, false, true, 0)
+            .with_extra_roots(vec![temp_dir.path().join("gone")])
+            .with_partial_roots(true);
+        assert!(matches!(cleaner.find_venv_directories(), Err(VenvCleanerError::PathError { .. })));
+        assert_eq!(cleaner.last_failed_roots().len(), 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_replicated_venvs_are_found_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let work = temp_dir.path().join("work");
+        create_venv(&work.join("a").join(".venv"));
+        fs::write(work.join("a").join(".venv").join("lib.so"), vec![0u8; 512]).unwrap();
+
+        // A second path to the same directories, as a bind mount would give
+        let mirror = temp_dir.path().join("mirror");
+        std::os::unix::fs::symlink(&work, &mirror).unwrap();
+
+        let cleaner = VenvCleaner::new(work.clone(), true, false, true, 0).with_extra_roots(vec![mirror]);
+        let venvs = cleaner.find_venv_directories().unwrap();
+        assert_eq!(venvs.len(), 1);
+        assert!(venvs[0].path().starts_with(&work));
+    }
+
+    #[test]
+    fn test_owner_filter_skips_other_users() {
+        let temp_dir = TempDir::new().unwrap();
+        create_venv(&temp_dir.path().join("a").join(".venv"));
+
+        let venvs = VenvCleaner::new(temp_dir.path().to_path_buf(), true, false, true, 0).find_venv_directories().unwrap();
+        assert_eq!(venvs[0].owner_uid(), ownership::current_uid());
+
+        // Only meaningful where ownership is recorded
+        if let Some(uid) = ownership::current_uid() {
+            let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, false, true, 0)
+                .with_owner_filter(OwnerFilter::User(uid.wrapping_add(1)));
+            assert!(matches!(cleaner.find_venv_directories(), Err(VenvCleanerError::NoVenvFound)));
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_other_users_venvs_need_all_users_to_delete() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv_path = temp_dir.path().join("a").join(".venv");
+        fs::create_dir_all(&venv_path).unwrap();
+        let venv_info = VenvInfo::new(venv_path.clone(), ByteSize::new(0), Utc::now(), Utc::now());
+
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, true, false, 0);
+        assert!(cleaner.check_owner(&venv_info).is_ok());
+
+        // Handing the .venv to another user takes root
+        if ownership::is_root() {
+            std::os::unix::fs::chown(&venv_path, Some(65534), Some(65534)).unwrap();
+            let error = cleaner.delete_venv_directory(&venv_info).unwrap_err();
+            assert!(matches!(error, VenvCleanerError::OwnedByOtherUser { .. }));
+            assert!(venv_path.exists());
+
+            assert!(cleaner.clone().with_owner_filter(OwnerFilter::User(65533)).check_owner(&venv_info).is_err());
+            assert!(cleaner.clone().with_owner_filter(OwnerFilter::User(65534)).check_owner(&venv_info).is_ok());
+            cleaner.with_owner_filter(OwnerFilter::AllUsers).delete_venv_directory(&venv_info).unwrap();
+            assert!(!venv_path.exists());
+        }
+    }
+
+    #[test]
+    fn test_skip_hidden_prunes_dot_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        create_venv(&temp_dir.path().join("a").join(".venv"));
+        create_venv(&temp_dir.path().join(".cache").join("b").join(".venv"));
+
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, false, true, 0);
+        assert_eq!(cleaner.find_venv_directories().unwrap().len(), 2);
+
+        for respect_gitignore in [false, true] {
+            let venvs = cleaner.clone()
+                .with_skip_hidden(true)
+                .with_gitignore(respect_gitignore)
+                .find_venv_directories()
+                .unwrap();
+            assert_eq!(venvs.len(), 1);
+            assert!(venvs[0].path().ends_with(Path::new("a").join(".venv")));
+        }
+    }
+
+    #[test]
+    fn test_scan_stats_per_top_level_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        create_venv(&temp_dir.path().join("work").join("a").join(".venv"));
+        create_venv(&temp_dir.path().join("work").join("b").join(".venv"));
+        fs::create_dir_all(temp_dir.path().join("Downloads").join("files")).unwrap();
+
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, false, true, 0);
+        for respect_gitignore in [false, true] {
+            let cleaner = cleaner.clone().with_gitignore(respect_gitignore);
+            cleaner.find_venv_directories().unwrap();
+            let stats = cleaner.last_scan_stats();
+            assert_eq!(stats.len(), 2);
+            let venvs_in = |name: &str| stats.iter().find(|s| s.path.ends_with(name)).unwrap().venvs;
+            assert_eq!(venvs_in("work"), 2);
+            assert_eq!(venvs_in("Downloads"), 0);
+        }
+    }
+
+    #[test]
+    fn test_low_memory_scan_only_reports() {
+        let temp_dir = TempDir::new().unwrap();
+        create_venv(&temp_dir.path().join("work").join("a").join(".venv"));
+        create_venv(&temp_dir.path().join("work").join("b").join(".venv"));
+
+        // The nested root finds both again, but they are reported once
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, false, true, 0)
+            .with_extra_roots(vec![temp_dir.path().join("work")])
+            .with_low_memory(true);
+        let mut reported = Vec::new();
+        let kept = cleaner.find_venv_directories_with(|venv_info| reported.push(venv_info.path().to_path_buf())).unwrap();
+        assert!(kept.is_empty());
+        assert_eq!(reported.len(), 2);
+
+        fs::remove_dir_all(temp_dir.path().join("work")).unwrap();
+        assert!(matches!(cleaner.find_venv_directories(), Err(VenvCleanerError::NoVenvFound)));
+    }
+
+    #[test]
+    fn test_resumed_scan_skips_finished_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        let (root, checkpoints) = (temp_dir.path().join("share"), temp_dir.path().join("checkpoints"));
+        create_venv(&root.join("alice").join("app").join(".venv"));
+        create_venv(&root.join("alice").join("tool").join(".venv"));
+        create_venv(&root.join("bob").join("app").join(".venv"));
+
+        // The interrupted scan had finished alice's tree and found one .venv there
+        let mut checkpoint = ScanCheckpoint::new(root.clone(), false);
+        checkpoint.completed.insert(root.join("alice"), vec![root.join("alice").join("app").join(".venv")]);
+        let checkpoint_path = ScanCheckpoint::path_in(&checkpoints, &root);
+        checkpoint.save(&checkpoint_path).unwrap();
+
+        let cleaner = VenvCleaner::new(root.clone(), true, false, true, 0).with_checkpoints(Some(checkpoints.clone()), true);
+        let mut found: Vec<PathBuf> = cleaner.find_venv_directories().unwrap().iter().map(|v| v.path().to_path_buf()).collect();
+        found.sort();
+        assert_eq!(found, vec![root.join("alice").join("app").join(".venv"), root.join("bob").join("app").join(".venv")]);
+        // A completed scan leaves no checkpoint behind
+        assert!(!checkpoint_path.exists());
+
+        let cleaner = cleaner.with_checkpoints(Some(checkpoints), false);
+        assert_eq!(cleaner.find_venv_directories().unwrap().len(), 3);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unreadable_directories_are_collected() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        create_venv(&temp_dir.path().join("work").join(".venv"));
+        let locked = temp_dir.path().join("locked");
+        fs::create_dir_all(&locked).unwrap();
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+
+        let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, false, true, 0);
+        for respect_gitignore in [false, true] {
+            let cleaner = cleaner.clone().with_gitignore(respect_gitignore);
+            cleaner.find_venv_directories().unwrap();
+            // Root reads every directory regardless of its permissions
+            let expected = if ownership::is_root() { Vec::new() } else { vec![locked.clone()] };
+            assert_eq!(cleaner.last_unreadable_directories(), expected);
+        }
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[test]
+    fn test_excludes_prune_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        create_venv(&temp_dir.path().join("work").join(".venv"));
+        create_venv(&temp_dir.path().join("scratch").join("a").join(".venv"));
+
+        for respect_gitignore in [false, true] {
+            let venvs = VenvCleaner::new(temp_dir.path().to_path_buf(), true, false, true, 0)
+                .with_gitignore(respect_gitignore)
+                .with_excludes(vec![temp_dir.path().join("scratch")])
+                .find_venv_directories()
+                .unwrap();
+            assert_eq!(venvs.len(), 1);
+            assert!(venvs[0].path().starts_with(temp_dir.path().join("work")));
+        }
+    }
+
+    #[test]
+    fn test_includes_limit_the_walk() {
+        let temp_dir = TempDir::new().unwrap();
+        create_venv(&temp_dir.path().join("shop").join("backend").join("api").join(".venv"));
+        create_venv(&temp_dir.path().join("shop").join("frontend").join(".venv"));
+        create_venv(&temp_dir.path().join("shop").join(".venv"));
+        create_venv(&temp_dir.path().join(".venv"));
+
+        let includes = IncludeSet::new(&["*/backend".to_string()]).unwrap();
+        for respect_gitignore in [false, true] {
+            let venvs = VenvCleaner::new(temp_dir.path().to_path_buf(), true, false, true, 0)
+                .with_gitignore(respect_gitignore)
+                .with_includes(includes.clone())
+                .find_venv_directories()
+                .unwrap();
+            assert_eq!(venvs.len(), 1, "gitignore {}", respect_gitignore);
+            assert!(venvs[0].path().ends_with("shop/backend/api/.venv"));
+        }
+    }
+
+    #[test]
+    fn test_offline_index_plans_instead_of_deleting() {
+        let temp_dir = TempDir::new().unwrap();
+        // A real .venv at the indexed path must survive
+        let venv_path = temp_dir.path().join("app").join(".venv");
+        fs::create_dir_all(&venv_path).unwrap();
+        let index = temp_dir.path().join("index.json");
+        fs::write(&index, format!("[{{\"path\":{:?},\"size_bytes\":5}}]", venv_path.display().to_string())).unwrap();
+        let plan = temp_dir.path().join("plan.json");
+
+        let cleaner = VenvCleaner::new(PathBuf::from("/nonexistent"), true, false, false, 0)
+            .with_offline_index(Some(OfflineIndex::load(&index, plan.clone()).unwrap()));
+        let venvs = cleaner.find_venv_directories().unwrap();
+        assert_eq!(venvs.len(), 1);
+
+        cleaner.delete_venv_directory(&venvs[0]).unwrap();
+        assert!(venv_path.exists());
+        assert_eq!(DeletionPlan::load(&plan).unwrap()[0].path, venv_path);
+        assert!(matches!(cleaner.find_venv_directories(), Err(VenvCleanerError::NoVenvFound)));
+    }
+
+    #[test]
+    fn test_detectors_find_custom_layouts() {
+        let temp_dir = TempDir::new().unwrap();
+        create_venv(&temp_dir.path().join("a").join(".venv"));
+        let custom = temp_dir.path().join("b").join(".pyenv-local");
+        fs::create_dir_all(&custom).unwrap();
+        fs::write(custom.join("pyvenv.cfg"), "home = /usr/bin\n").unwrap();
+        // Same name, but without the marker file
+        fs::create_dir_all(temp_dir.path().join("c").join(".pyenv-local")).unwrap();
+
+        let detector = Detector::from_config(&DetectorConfig {
+            name: "pyenv-local".to_string(),
+            patterns: vec![".pyenv-local".to_string()],
+            markers: vec!["pyvenv.cfg".to_string()],
+            ..DetectorConfig::default()
+        })
+        .unwrap();
+        for respect_gitignore in [false, true] {
+            let mut venvs = VenvCleaner::new(temp_dir.path().to_path_buf(), true, false, true, 0)
+                .with_gitignore(respect_gitignore)
+                .with_skip_hidden(true)
+                .with_detectors(vec![detector.clone()])
+                .find_venv_directories()
+                .unwrap();
+            venvs.sort_by(|a, b| a.path().cmp(b.path()));
+            let paths: Vec<&Path> = venvs.iter().map(|venv| venv.path()).collect();
+            assert_eq!(paths, vec![temp_dir.path().join("a").join(".venv").as_path(), custom.as_path()]);
+        }
     }
 }
@@ -0,0 +1,434 @@
+//! File system detection for VenvCleaner
+//!
+//! Network file systems (NFS, SMB/CIFS, sshfs, ...) are slow to walk and
+//! easy to overload with parallel requests, so scans and deletions on them
+//! run with less parallelism. They usually have no usable trash either, and
+//! like `noatime` mounts they do not keep reliable access times, which is why
+//! VenvCleaner judges age by modification times only.
+//!
+//! This module finds the file system a path lives on from the kernel's mount
+//! table (`/proc/self/mounts` on Linux), falling back to `statfs` where there
+//! is no mount table, as on macOS and the BSDs.
+//!
+//! Removable drives are recognised too, by the flag Linux keeps for the
+//! disk in sysfs or by being mounted where desktops put them (`/media`,
+//! `/run/media`, `/Volumes`), so that a .venv whose drive was unplugged
+//! since the scan can be reported as such rather than as a bare IO error.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File system types served over the network
+pub const NETWORK_FILESYSTEMS: &[&str] = &[
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smb3",
+    "smbfs",
+    "afpfs",
+    "9p",
+    "ceph",
+    "glusterfs",
+    "lustre",
+    "afs",
+    "davfs",
+    "fuse.sshfs",
+    "fuse.glusterfs",
+    "fuse.rclone",
+];
+
+/// Directories below which desktops mount removable drives
+pub const REMOVABLE_MOUNT_ROOTS: &[&str] = &["/media", "/run/media", "/Volumes"];
+
+/// One line of the mount table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MountEntry {
+    /// Device or source mounted, e.g. "/dev/sdb1" or "server:/export"
+    pub device: String,
+    /// Directory the file system is mounted on
+    pub mount_point: PathBuf,
+    /// File system type, e.g. "ext4" or "nfs4"
+    pub fs_type: String,
+    /// Mount options, e.g. "rw" or "noatime"
+    pub options: Vec<String>,
+}
+
+/// The file system a path lives on and how VenvCleaner treats it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilesystemInfo {
+    /// File system type, e.g. "ext4", "apfs" or "nfs4"
+    pub fs_type: String,
+    /// Whether the file system is mounted with `noatime`
+    pub noatime: bool,
+}
+
+impl FilesystemInfo {
+    /// Create a FilesystemInfo for a file system type
+    pub fn new(fs_type: impl Into<String>) -> Self {
+        Self { fs_type: fs_type.into(), noatime: false }
+    }
+
+    /// Check whether the file system is served over the network
+    pub fn is_network(&self) -> bool {
+        is_network_filesystem(&self.fs_type)
+    }
+
+    /// Check whether folders on this file system can be moved to the trash
+    ///
+    /// Trash implementations either have no trash directory on network
+    /// shares or fall back to copying the folder home over the network.
+    pub fn supports_trash(&self) -> bool {
+        !self.is_network()
+    }
+
+    /// Check whether access times on this file system can be trusted
+    pub fn tracks_access_times(&self) -> bool {
+        !self.noatime && !self.is_network()
+    }
+
+    /// Describe the file system and the strategy used for it
+    ///
+    /// # Returns
+    /// E.g. "ext4" or "nfs4 (network: reduced parallelism, no trash, access times ignored)"
+    pub fn describe(&self) -> String {
+        let mut notes = Vec::new();
+        if self.is_network() {
+            notes.push("network: reduced parallelism");
+        }
+        if !self.supports_trash() {
+            notes.push("no trash");
+        }
+        if !self.tracks_access_times() {
+            notes.push("access times ignored");
+        }
+
+        if notes.is_empty() {
+            self.fs_type.clone()
+        } else {
+            format!("{} ({})", self.fs_type, notes.join(", "))
+        }
+    }
+}
+
+/// Parse a mount table in the `/proc/self/mounts` format
+///
+/// Each line holds the device, mount point and type separated by spaces;
+/// spaces inside paths are escaped as `\040`.
+///
+/// # Arguments
+/// * `text` - Contents of the mount table
+pub fn parse_mount_table(text: &str) -> Vec<MountEntry> {
+    text.lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let device = unescape_mount_field(fields.next()?);
+            let mount_point = unescape_mount_field(fields.next()?);
+            let fs_type = fields.next()?.to_string();
+            let options = fields.next().unwrap_or_default().split(',').map(str::to_string).collect();
+            Some(MountEntry { device, mount_point: PathBuf::from(mount_point), fs_type, options })
+        })
+        .collect()
+}
+
+/// Decode the octal escapes (`\040` for a space) used by the mount table
+fn unescape_mount_field(field: &str) -> String {
+    let mut result = String::with_capacity(field.len());
+    let mut rest = field;
+    while let Some(index) = rest.find('\\') {
+        result.push_str(&rest[..index]);
+        let code = rest.get(index + 1..index + 4).and_then(|digits| u8::from_str_radix(digits, 8).ok());
+        match code {
+            Some(byte) => {
+                result.push(byte as char);
+                rest = &rest[index + 4..];
+            }
+            None => {
+                result.push('\\');
+                rest = &rest[index + 1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Find the mount a path lives on: the entry with the longest matching mount point
+///
+/// # Arguments
+/// * `mounts` - The mount table
+/// * `path` - An absolute path
+pub fn find_mount<'a>(mounts: &'a [MountEntry], path: &Path) -> Option<&'a MountEntry> {
+    mounts
+        .iter()
+        .filter(|entry| path.starts_with(&entry.mount_point))
+        .max_by_key(|entry| entry.mount_point.components().count())
+}
+
+impl MountEntry {
+    /// Check whether the entry is a removable drive, such as a USB stick
+    pub fn is_removable(&self) -> bool {
+        is_removable_mount_point(&self.mount_point) || is_removable_device(&self.device)
+    }
+}
+
+/// Check whether a directory is a drive mounted where desktops put removable ones
+///
+/// # Arguments
+/// * `mount_point` - The directory, e.g. `/media/alice/USB`
+pub fn is_removable_mount_point(mount_point: &Path) -> bool {
+    REMOVABLE_MOUNT_ROOTS
+        .iter()
+        .any(|root| mount_point.starts_with(root) && mount_point != Path::new(root))
+}
+
+/// Check whether sysfs marks the disk of a device as removable
+#[cfg(target_os = "linux")]
+fn is_removable_device(device: &str) -> bool {
+    let Some(name) = device.strip_prefix("/dev/") else {
+        return false;
+    };
+    let Ok(dir) = fs::canonicalize(Path::new("/sys/class/block").join(name)) else {
+        return false;
+    };
+    // A partition's flag is kept by the disk it belongs to
+    let disk = if dir.join("partition").exists() { dir.parent().unwrap_or(&dir) } else { &dir };
+    fs::read_to_string(disk.join("removable")).map(|flag| flag.trim() == "1").unwrap_or(false)
+}
+
+/// Check whether sysfs marks the disk of a device as removable
+#[cfg(not(target_os = "linux"))]
+fn is_removable_device(_device: &str) -> bool {
+    false
+}
+
+/// Find the removable drive a path lives on
+///
+/// # Returns
+/// The drive's mount point, or None if the path is not on a removable drive
+pub fn removable_volume(path: &Path) -> Option<PathBuf> {
+    let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+
+    if let Ok(table) = fs::read_to_string("/proc/self/mounts") {
+        let mounts = parse_mount_table(&table);
+        return find_mount(&mounts, &path)
+            .filter(|entry| entry.is_removable())
+            .map(|entry| entry.mount_point.clone());
+    }
+
+    // Without a mount table, the directory below /Volumes (or /media) stands for the drive
+    path.ancestors().find(|ancestor| {
+        ancestor.parent().is_some_and(|parent| REMOVABLE_MOUNT_ROOTS.iter().any(|root| parent == Path::new(root)))
+    }).map(Path::to_path_buf)
+}
+
+/// Check whether a drive is still mounted at a mount point
+///
+/// Once a drive is unplugged its mount point is removed, or left behind as
+/// an empty directory of the file system it was mounted on.
+///
+/// # Arguments
+/// * `mount_point` - Where the drive was mounted
+#[cfg(unix)]
+pub fn is_volume_mounted(mount_point: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    let Ok(volume) = fs::metadata(mount_point) else {
+        return false;
+    };
+    match mount_point.parent().map(fs::metadata) {
+        Some(Ok(parent)) => parent.dev() != volume.dev(),
+        _ => true,
+    }
+}
+
+/// Check whether a drive is still mounted at a mount point
+///
+/// # Arguments
+/// * `mount_point` - Where the drive was mounted
+#[cfg(not(unix))]
+pub fn is_volume_mounted(mount_point: &Path) -> bool {
+    mount_point.exists()
+}
+
+/// Find the file system a path lives on
+///
+/// # Returns
+/// The file system, or None when neither the mount table nor `statfs` knows it
+pub fn filesystem_info(path: &Path) -> Option<FilesystemInfo> {
+    let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+
+    if let Ok(table) = fs::read_to_string("/proc/self/mounts") {
+        let mounts = parse_mount_table(&table);
+        if let Some(entry) = find_mount(&mounts, &path) {
+            return Some(FilesystemInfo {
+                fs_type: entry.fs_type.clone(),
+                noatime: entry.options.iter().any(|option| option == "noatime"),
+            });
+        }
+    }
+
+    statfs_type(&path).map(FilesystemInfo::new)
+}
+
+/// Get the type of the file system a path lives on
+///
+/// # Returns
+/// The file system type, or None when it cannot be determined
+pub fn filesystem_type(path: &Path) -> Option<String> {
+    filesystem_info(path).map(|info| info.fs_type)
+}
+
+/// Ask the kernel for a path's file system type name
+#[cfg(any(target_os = "macos", target_os = "freebsd", target_os = "openbsd"))]
+fn statfs_type(path: &Path) -> Option<String> {
+    use std::ffi::{CStr, CString};
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+    // SAFETY: c_path is NUL-terminated and stat is a valid, writable statfs
+    if unsafe { libc::statfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    // SAFETY: the kernel NUL-terminates f_fstypename
+    let name = unsafe { CStr::from_ptr(stat.f_fstypename.as_ptr()) };
+    Some(name.to_string_lossy().into_owned())
+}
+
+/// Ask the kernel for a path's file system type, mapping its magic number to a name
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn statfs_type(path: &Path) -> Option<String> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+    // SAFETY: c_path is NUL-terminated and stat is a valid, writable statfs
+    if unsafe { libc::statfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    #[allow(clippy::unnecessary_cast)]
+    linux_fs_name(stat.f_type as u64).map(str::to_string)
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "freebsd",
+    target_os = "openbsd"
+)))]
+fn statfs_type(_path: &Path) -> Option<String> {
+    None
+}
+
+/// Map a Linux `statfs` magic number to the file system name used in the mount table
+#[cfg_attr(not(any(target_os = "linux", target_os = "android")), allow(dead_code))]
+fn linux_fs_name(magic: u64) -> Option<&'static str> {
+    Some(match magic {
+        0xEF53 => "ext4",
+        0x9123_683E => "btrfs",
+        0x5846_5342 => "xfs",
+        0x2FC1_2FC1 => "zfs",
+        0x0102_1994 => "tmpfs",
+        0x794C_7630 => "overlay",
+        0x6969 => "nfs",
+        0xFF53_4D42 => "cifs",
+        0xFE53_4D42 => "smb3",
+        0x517B => "smbfs",
+        0x0102_1997 => "9p",
+        0x00C3_6400 => "ceph",
+        0x0BD0_0BD0 => "lustre",
+        0x6573_5546 => "fuse",
+        _ => return None,
+    })
+}
+
+/// Check whether a file system type is served over the network
+pub fn is_network_filesystem(fs_type: &str) -> bool {
+    NETWORK_FILESYSTEMS.contains(&fs_type)
+}
+
+/// Check whether a path lives on a network file system
+pub fn is_network_path(path: &Path) -> bool {
+    filesystem_info(path).map(|info| info.is_network()).unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TABLE: &str = "\
+/dev/sda2 / ext4 rw,noatime 0 0
+proc /proc proc rw,nosuid 0 0
+server:/export/home /home nfs4 rw,relatime,vers=4.2 0 0
+//nas/Team\\040Share /mnt/team\\040share cifs rw 0 0
+";
+
+    #[test]
+    fn test_parse_mount_table() {
+        let mounts = parse_mount_table(TABLE);
+        assert_eq!(mounts.len(), 4);
+        assert_eq!(mounts[2].mount_point, PathBuf::from("/home"));
+        assert_eq!(mounts[2].fs_type, "nfs4");
+        assert!(mounts[0].options.contains(&"noatime".to_string()));
+        assert_eq!(mounts[3].mount_point, PathBuf::from("/mnt/team share"));
+    }
+
+    #[test]
+    fn test_find_mount_uses_longest_prefix() {
+        let mounts = parse_mount_table(TABLE);
+        let fs_type = |path: &str| find_mount(&mounts, Path::new(path)).map(|m| m.fs_type.as_str());
+        assert_eq!(fs_type("/home/alice/project/.venv"), Some("nfs4"));
+        assert_eq!(fs_type("/homework"), Some("ext4"));
+        assert_eq!(fs_type("/mnt/team share/x"), Some("cifs"));
+    }
+
+    #[test]
+    fn test_removable_mounts() {
+        let mounts = parse_mount_table(&format!("{}/dev/sdb1 /run/media/alice/USB\\040STICK vfat rw 0 0\n", TABLE));
+        let usb = find_mount(&mounts, Path::new("/run/media/alice/USB STICK/proj/.venv")).unwrap();
+        assert_eq!((usb.device.as_str(), usb.is_removable()), ("/dev/sdb1", true));
+        assert!(!find_mount(&mounts, Path::new("/home/alice/proj/.venv")).unwrap().is_removable());
+        assert!(is_removable_mount_point(Path::new("/Volumes/Backup")));
+        assert!(!is_removable_mount_point(Path::new("/media")));
+
+        // A plain directory is not a mount point, and a missing one is not mounted
+        #[cfg(unix)]
+        {
+            let temp_dir = tempfile::TempDir::new().unwrap();
+            assert!(!is_volume_mounted(temp_dir.path()));
+            assert!(!is_volume_mounted(&temp_dir.path().join("gone")));
+            assert!(is_volume_mounted(Path::new("/")));
+        }
+    }
+
+    #[test]
+    fn test_is_network_filesystem() {
+        assert!(is_network_filesystem("nfs4"));
+        assert!(is_network_filesystem("fuse.sshfs"));
+        assert!(!is_network_filesystem("ext4"));
+        assert!(!is_network_filesystem("apfs"));
+    }
+
+    #[test]
+    fn test_filesystem_strategy() {
+        let local = FilesystemInfo::new("apfs");
+        assert!(local.supports_trash());
+        assert!(local.tracks_access_times());
+        assert_eq!(local.describe(), "apfs");
+
+        let noatime = FilesystemInfo { fs_type: "ext4".to_string(), noatime: true };
+        assert!(noatime.supports_trash());
+        assert_eq!(noatime.describe(), "ext4 (access times ignored)");
+
+        let nfs = FilesystemInfo::new("nfs4");
+        assert!(!nfs.supports_trash());
+        assert_eq!(nfs.describe(), "nfs4 (network: reduced parallelism, no trash, access times ignored)");
+        assert_eq!(linux_fs_name(0x6969), Some("nfs"));
+
+        // Whatever the platform, the temporary directory's file system is known or None
+        let current = filesystem_info(&std::env::temp_dir());
+        assert!(current.map(|info| !info.fs_type.is_empty()).unwrap_or(true));
+    }
+}
@@ -0,0 +1,210 @@
+//! Browsing a saved scan and planning deletions offline
+//!
+//! `--from-index index.json` loads a scan captured on another machine with
+//! `--query --progress json` (or an exported JSON list) instead of walking
+//! the disk, so deletions can be reviewed in the TUI or GUI away from that
+//! machine. Deleting then only records the .venv in a plan file, which
+//! `venv_cleaner apply-plan plan.json` carries out later on the machine itself.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{ByteSize, MergedReport, PythonVersion, Result, VenvCleanerError, VenvInfo};
+
+/// A .venv recorded in a deletion plan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedDeletion {
+    /// Path to the .venv directory on the scanned machine
+    pub path: PathBuf,
+    /// Its size in bytes when the index was captured
+    pub size_bytes: u64,
+}
+
+/// The contents of a plan file
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PlanFile {
+    /// The index the plan was made from
+    index: Option<PathBuf>,
+    /// When the plan was last changed
+    updated: Option<DateTime<Utc>>,
+    /// The .venv directories to delete, by path
+    venvs: Vec<PlannedDeletion>,
+}
+
+/// A plan file deletions are added to, shared between copies of the cleaner
+#[derive(Debug, Clone)]
+pub struct DeletionPlan {
+    /// The plan file, rewritten after every change
+    path: PathBuf,
+    /// The index the plan is made from
+    index: Option<PathBuf>,
+    /// The planned deletions, by path
+    venvs: Arc<Mutex<BTreeMap<PathBuf, u64>>>,
+}
+
+impl DeletionPlan {
+    /// Load a plan file
+    ///
+    /// # Arguments
+    /// * `path` - The plan file written while browsing offline
+    ///
+    /// # Returns
+    /// The planned deletions, sorted by path
+    pub fn load(path: &Path) -> Result<Vec<PlannedDeletion>> {
+        let text = fs::read_to_string(path).map_err(|e| VenvCleanerError::PathError {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        })?;
+        let plan: PlanFile = serde_json::from_str(&text).map_err(|e| VenvCleanerError::PathError {
+            path: path.display().to_string(),
+            message: format!("not a deletion plan: {}", e),
+        })?;
+        let mut venvs = plan.venvs;
+        venvs.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(venvs)
+    }
+
+    /// Open a plan file to add to, keeping what it already plans
+    ///
+    /// # Arguments
+    /// * `path` - The plan file; it is created on the first deletion
+    /// * `index` - The index the plan is made from
+    pub fn open(path: PathBuf, index: Option<PathBuf>) -> Result<Self> {
+        let existing = if path.exists() { Self::load(&path)? } else { Vec::new() };
+        let venvs = existing.into_iter().map(|planned| (planned.path, planned.size_bytes)).collect();
+        Ok(Self { path, index, venvs: Arc::new(Mutex::new(venvs)) })
+    }
+
+    /// Get the plan file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Check whether a .venv is already planned for deletion
+    pub fn contains(&self, path: &Path) -> bool {
+        self.venvs.lock().map(|venvs| venvs.contains_key(path)).unwrap_or(false)
+    }
+
+    /// Get the planned deletions, sorted by path
+    pub fn entries(&self) -> Vec<PlannedDeletion> {
+        self.venvs
+            .lock()
+            .map(|venvs| {
+                venvs.iter().map(|(path, &size_bytes)| PlannedDeletion { path: path.clone(), size_bytes }).collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Plan a .venv for deletion and save the plan file
+    ///
+    /// # Arguments
+    /// * `venv_info` - The .venv to delete later
+    pub fn add(&self, venv_info: &VenvInfo) -> Result<()> {
+        let mut venvs = self
+            .venvs
+            .lock()
+            .map_err(|_| VenvCleanerError::Io("The deletion plan is unavailable".to_string()))?;
+        venvs.insert(venv_info.path().to_path_buf(), venv_info.size().bytes());
+
+        let file = PlanFile {
+            index: self.index.clone(),
+            updated: Some(Utc::now()),
+            venvs: venvs.iter().map(|(path, &size_bytes)| PlannedDeletion { path: path.clone(), size_bytes }).collect(),
+        };
+        let json = serde_json::to_string_pretty(&file).map_err(|e| VenvCleanerError::Io(e.to_string()))?;
+        fs::write(&self.path, json + "\n").map_err(|e| VenvCleanerError::PathError {
+            path: self.path.display().to_string(),
+            message: e.to_string(),
+        })
+    }
+}
+
+/// A saved scan browsed in place of the disk, and the plan deletions go to
+#[derive(Debug, Clone)]
+pub struct OfflineIndex {
+    /// The .venv directories of the saved scan
+    venvs: Vec<VenvInfo>,
+    /// Where deletions are recorded
+    plan: DeletionPlan,
+}
+
+impl OfflineIndex {
+    /// Load a saved scan
+    ///
+    /// # Arguments
+    /// * `index` - A scan saved with `--query --progress json`, or an exported JSON list
+    /// * `plan` - The plan file deletions are added to
+    ///
+    /// # Returns
+    /// The index, or an error if either file cannot be read
+    pub fn load(index: &Path, plan: PathBuf) -> Result<Self> {
+        let mut report = MergedReport::new();
+        report.add_file(index)?;
+        let venvs = report
+            .venvs()
+            .iter()
+            .map(|venv| {
+                // The index has no creation time; the modification time stands in
+                let modified = venv.last_modified.unwrap_or_else(Utc::now);
+                VenvInfo::new(venv.path.clone(), ByteSize::new(venv.size_bytes), modified, modified)
+                    .with_python_version(venv.python_version.as_deref().and_then(PythonVersion::parse))
+            })
+            .collect();
+        Ok(Self { venvs, plan: DeletionPlan::open(plan, Some(index.to_path_buf()))? })
+    }
+
+    /// Get the .venv directories of the saved scan not planned for deletion yet
+    pub fn remaining(&self) -> Vec<VenvInfo> {
+        self.venvs.iter().filter(|venv| !self.plan.contains(venv.path())).cloned().collect()
+    }
+
+    /// Get the plan deletions are added to
+    pub fn plan(&self) -> &DeletionPlan {
+        &self.plan
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_planned_venvs_leave_the_index() {
+        let temp_dir = TempDir::new().unwrap();
+        let index = temp_dir.path().join("index.json");
+        let plan_path = temp_dir.path().join("plan.json");
+        fs::write(
+            &index,
+            "{\"event\":\"venv_found\",\"path\":\"/srv/a/.venv\",\"size_bytes\":10,\"python_version\":\"3.11.4\"}\n\
+             {\"event\":\"venv_found\",\"path\":\"/srv/b/.venv\",\"size_bytes\":20}\n",
+        )
+        .unwrap();
+
+        let offline = OfflineIndex::load(&index, plan_path.clone()).unwrap();
+        let remaining = offline.remaining();
+        assert_eq!(remaining.len(), 2);
+        assert_eq!(remaining[0].python_version(), PythonVersion::parse("3.11.4"));
+
+        offline.plan().add(&remaining[1]).unwrap();
+        assert_eq!(offline.remaining().len(), 1);
+        assert_eq!(DeletionPlan::load(&plan_path).unwrap(), vec![PlannedDeletion { path: "/srv/b/.venv".into(), size_bytes: 20 }]);
+
+        // Reopening keeps what was planned before
+        let reopened = OfflineIndex::load(&index, plan_path).unwrap();
+        assert_eq!(reopened.remaining().len(), 1);
+    }
+
+    #[test]
+    fn test_load_rejects_other_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let not_a_plan = temp_dir.path().join("plan.json");
+        fs::write(&not_a_plan, "[1, 2]").unwrap();
+        assert!(matches!(DeletionPlan::load(&not_a_plan), Err(VenvCleanerError::PathError { .. })));
+        assert!(DeletionPlan::load(&temp_dir.path().join("missing.json")).is_err());
+    }
+}
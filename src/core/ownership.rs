@@ -0,0 +1,197 @@
+//! Ownership of .venv directories on multi-user systems
+//!
+//! On shared servers a recursive scan of `/home` or `/srv` finds every
+//! user's environments. Only the current user's are listed by default
+//! (`--mine`); `--all-users` lists everyone's and `--user NAME` one other
+//! user's, so an administrator can clean up what a departed user left behind.
+//! Owners are user ids, resolved to names for display.
+
+use std::fs;
+use std::path::Path;
+
+use super::{Result, VenvCleanerError};
+
+/// Which owners' .venv directories a scan keeps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OwnerFilter {
+    /// Only those owned by the current user
+    #[default]
+    Mine,
+    /// Those of every user
+    AllUsers,
+    /// Only those owned by this user id
+    User(u32),
+}
+
+impl OwnerFilter {
+    /// Check whether a .venv with this owner is kept
+    ///
+    /// # Arguments
+    /// * `owner_uid` - The owner's user id, None where ownership is unknown
+    ///
+    /// # Returns
+    /// True when the owner matches (unknown owners always do)
+    pub fn matches(self, owner_uid: Option<u32>) -> bool {
+        let Some(owner_uid) = owner_uid else {
+            return true;
+        };
+        match self {
+            OwnerFilter::Mine => current_uid().map(|uid| uid == owner_uid).unwrap_or(true),
+            OwnerFilter::AllUsers => true,
+            OwnerFilter::User(uid) => uid == owner_uid,
+        }
+    }
+
+    /// Check whether .venv directories of other users can be kept
+    pub fn includes_others(self) -> bool {
+        self != OwnerFilter::Mine
+    }
+}
+
+/// Get the owner of a file or directory, without following symlinks
+///
+/// # Returns
+/// The owner's user id, or None if it cannot be read or the platform has none
+pub fn path_owner(path: &Path) -> Option<u32> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        fs::symlink_metadata(path).ok().map(|metadata| metadata.uid())
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = fs::symlink_metadata(path);
+        None
+    }
+}
+
+/// Get the effective user id of this process, None where the platform has none
+pub fn current_uid() -> Option<u32> {
+    #[cfg(unix)]
+    {
+        // SAFETY: geteuid has no preconditions
+        Some(unsafe { libc::geteuid() })
+    }
+    #[cfg(not(unix))]
+    {
+        None
+    }
+}
+
+/// Check whether VenvCleaner runs as root, when the owner column is shown
+pub fn is_root() -> bool {
+    current_uid() == Some(0)
+}
+
+/// Get the login name of a user id
+///
+/// # Returns
+/// The name from the user database, or None for unknown ids
+#[cfg(unix)]
+pub fn user_name(uid: u32) -> Option<String> {
+    use std::ffi::CStr;
+
+    let mut buffer = vec![0 as libc::c_char; 4096];
+    // SAFETY: passwd is plain old data that getpwuid_r fills in
+    let mut passwd: libc::passwd = unsafe { std::mem::zeroed() };
+    let mut result: *mut libc::passwd = std::ptr::null_mut();
+    // SAFETY: every pointer is valid and the buffer length is correct
+    let status = unsafe { libc::getpwuid_r(uid, &mut passwd, buffer.as_mut_ptr(), buffer.len(), &mut result) };
+    if status != 0 || result.is_null() {
+        return None;
+    }
+    // SAFETY: on success pw_name points to a NUL-terminated string inside buffer
+    let name = unsafe { CStr::from_ptr(passwd.pw_name) };
+    Some(name.to_string_lossy().into_owned())
+}
+
+#[cfg(not(unix))]
+pub fn user_name(_uid: u32) -> Option<String> {
+    None
+}
+
+/// Look up the user id of a login name, accepting numeric ids as well
+///
+/// # Arguments
+/// * `name` - The login name or user id given to `--user`
+///
+/// # Returns
+/// The user id, or an error for unknown users
+pub fn resolve_user(name: &str) -> Result<u32> {
+    if let Ok(uid) = name.parse::<u32>() {
+        return Ok(uid);
+    }
+    lookup_user(name).ok_or_else(|| VenvCleanerError::InvalidArgument(format!("Unknown user: {}", name)))
+}
+
+#[cfg(unix)]
+fn lookup_user(name: &str) -> Option<u32> {
+    use std::ffi::CString;
+
+    let c_name = CString::new(name).ok()?;
+    let mut buffer = vec![0 as libc::c_char; 4096];
+    // SAFETY: passwd is plain old data that getpwnam_r fills in
+    let mut passwd: libc::passwd = unsafe { std::mem::zeroed() };
+    let mut result: *mut libc::passwd = std::ptr::null_mut();
+    // SAFETY: every pointer is valid and the buffer length is correct
+    let status = unsafe {
+        libc::getpwnam_r(c_name.as_ptr(), &mut passwd, buffer.as_mut_ptr(), buffer.len(), &mut result)
+    };
+    (status == 0 && !result.is_null()).then_some(passwd.pw_uid)
+}
+
+#[cfg(not(unix))]
+fn lookup_user(_name: &str) -> Option<u32> {
+    None
+}
+
+/// Describe an owner for display: the login name, else the numeric id
+///
+/// # Arguments
+/// * `owner_uid` - The owner's user id, if known
+///
+/// # Returns
+/// The name, the id as a number, or "-" when unknown
+pub fn describe_owner(owner_uid: Option<u32>) -> String {
+    match owner_uid {
+        Some(uid) => user_name(uid).unwrap_or_else(|| uid.to_string()),
+        None => "-".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_filter_matches() {
+        let me = current_uid();
+        assert!(OwnerFilter::Mine.matches(me));
+        assert!(OwnerFilter::Mine.matches(None));
+        assert!(OwnerFilter::AllUsers.matches(Some(4242)));
+        assert!(OwnerFilter::User(4242).matches(Some(4242)));
+        assert!(!OwnerFilter::User(4242).matches(Some(4243)));
+        if me.is_some() && me != Some(4242) {
+            assert!(!OwnerFilter::Mine.matches(Some(4242)));
+        }
+        assert!(!OwnerFilter::Mine.includes_others());
+    }
+
+    #[test]
+    fn test_new_directories_are_mine() {
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(path_owner(temp_dir.path()), current_uid());
+        assert_eq!(path_owner(&temp_dir.path().join("missing")), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_and_describe_users() {
+        assert_eq!(resolve_user("1234").unwrap(), 1234);
+        assert_eq!(resolve_user("root").unwrap(), 0);
+        assert_eq!(describe_owner(Some(0)), "root");
+        assert!(resolve_user("no-such-user-venv-cleaner").is_err());
+        assert_eq!(describe_owner(None), "-");
+    }
+}
@@ -0,0 +1,131 @@
+//! Where VenvCleaner keeps its own files
+//!
+//! Every file the tool writes for itself lives in one of four directories,
+//! each following the platform's conventions: the XDG base directories on
+//! Linux (`$XDG_CONFIG_HOME`, `$XDG_CACHE_HOME`, `$XDG_DATA_HOME`,
+//! `$XDG_STATE_HOME`), `~/Library/...` on macOS and the known folders on
+//! Windows. Setting `VENV_CLEANER_HOME` moves all four below one directory
+//! instead, for portable installs, tests and sandboxes.
+//!
+//! | Kind   | Holds                                                           | Linux default                      |
+//! |--------|-----------------------------------------------------------------|------------------------------------|
+//! | config | `config.toml`                                                   | `~/.config/venv_cleaner`           |
+//! | cache  | project activity, recommendation and size history, scan summary | `~/.cache/venv_cleaner`            |
+//! | data   | archives, trash ledger, locks, crash reports                    | `~/.local/share/venv_cleaner`      |
+//! | logs   | log files                                                       | `~/.local/state/venv_cleaner/logs` |
+
+use std::path::{Path, PathBuf};
+
+/// Environment variable moving every directory below one root
+pub const HOME_ENV: &str = "VENV_CLEANER_HOME";
+
+/// Name of the application's directory inside the platform directories
+const APP_DIR_NAME: &str = "venv_cleaner";
+
+/// A kind of file the tool keeps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathKind {
+    /// Settings written by the user
+    Config,
+    /// Files that can be rebuilt at any time
+    Cache,
+    /// Files worth keeping, such as archives and the trash ledger
+    Data,
+    /// Log files
+    Logs,
+}
+
+impl PathKind {
+    /// Get the name of the kind's directory below `VENV_CLEANER_HOME`
+    fn dir_name(self) -> &'static str {
+        match self {
+            PathKind::Config => "config",
+            PathKind::Cache => "cache",
+            PathKind::Data => "data",
+            PathKind::Logs => "logs",
+        }
+    }
+
+    /// Get the platform's directory for the kind's files
+    fn platform_dir(self) -> Option<PathBuf> {
+        match self {
+            PathKind::Config => dirs::config_dir().map(|dir| dir.join(APP_DIR_NAME)),
+            PathKind::Cache => dirs::cache_dir().map(|dir| dir.join(APP_DIR_NAME)),
+            PathKind::Data => dirs::data_local_dir().map(|dir| dir.join(APP_DIR_NAME)),
+            // macOS keeps logs apart in ~/Library/Logs; elsewhere they are state
+            // ($XDG_STATE_HOME), or data where the platform has no state directory
+            PathKind::Logs if cfg!(target_os = "macos") => {
+                dirs::home_dir().map(|home| home.join("Library").join("Logs").join(APP_DIR_NAME))
+            }
+            PathKind::Logs => dirs::state_dir()
+                .or_else(dirs::data_local_dir)
+                .map(|dir| dir.join(APP_DIR_NAME).join("logs")),
+        }
+    }
+}
+
+/// Get the directory holding one kind of the tool's files
+///
+/// # Arguments
+/// * `kind` - The kind of file
+///
+/// # Returns
+/// The directory, or None if the platform has no home directory to put it in
+pub fn dir(kind: PathKind) -> Option<PathBuf> {
+    resolve(kind, std::env::var_os(HOME_ENV).map(PathBuf::from).as_deref())
+}
+
+/// Get the directory for a kind of file, given the value of `VENV_CLEANER_HOME`
+fn resolve(kind: PathKind, home_override: Option<&Path>) -> Option<PathBuf> {
+    match home_override.filter(|home| !home.as_os_str().is_empty()) {
+        Some(home) => Some(home.join(kind.dir_name())),
+        None => kind.platform_dir(),
+    }
+}
+
+/// Get the configuration directory
+pub fn config_dir() -> Option<PathBuf> {
+    dir(PathKind::Config)
+}
+
+/// Get the cache directory
+pub fn cache_dir() -> Option<PathBuf> {
+    dir(PathKind::Cache)
+}
+
+/// Get the data directory
+pub fn data_dir() -> Option<PathBuf> {
+    dir(PathKind::Data)
+}
+
+/// Get the log directory
+pub fn log_dir() -> Option<PathBuf> {
+    dir(PathKind::Logs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_override_puts_every_kind_below_one_root() {
+        let home = Path::new("/opt/venv_cleaner_home");
+        assert_eq!(resolve(PathKind::Config, Some(home)), Some(home.join("config")));
+        assert_eq!(resolve(PathKind::Cache, Some(home)), Some(home.join("cache")));
+        assert_eq!(resolve(PathKind::Data, Some(home)), Some(home.join("data")));
+        assert_eq!(resolve(PathKind::Logs, Some(home)), Some(home.join("logs")));
+    }
+
+    #[test]
+    fn test_platform_dirs_end_in_app_dir() {
+        // An empty override is ignored, like an unset one
+        for kind in [PathKind::Config, PathKind::Cache, PathKind::Data] {
+            if let Some(dir) = resolve(kind, Some(Path::new(""))) {
+                assert!(dir.ends_with(APP_DIR_NAME));
+            }
+        }
+        if let Some(dir) = resolve(PathKind::Logs, None) {
+            assert!(dir.components().any(|part| part.as_os_str() == APP_DIR_NAME));
+        }
+    }
+}
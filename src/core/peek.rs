@@ -0,0 +1,168 @@
+//! Preview of a .venv's contents before deleting it
+//!
+//! A .venv occasionally ends up holding more than installed packages:
+//! notebooks, datasets or exports that someone saved next to `bin/` and
+//! `lib/`. The peek lists the top-level entries of the environment and its
+//! largest packages so that anything unexpected stands out before it is
+//! wiped along with the rest.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::file_utils::FileUtils;
+use super::site_packages;
+use super::Result;
+
+/// Number of packages listed by default
+pub const DEFAULT_PACKAGE_LIMIT: usize = 10;
+
+/// One entry of a .venv's contents
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeekEntry {
+    /// File or directory name
+    pub name: String,
+    /// Whether the entry is a directory
+    pub is_dir: bool,
+    /// Size in bytes (recursive for directories)
+    pub size_bytes: u64,
+}
+
+impl PeekEntry {
+    /// Get the name with a trailing `/` for directories
+    pub fn display_name(&self) -> String {
+        if self.is_dir {
+            format!("{}/", self.name)
+        } else {
+            self.name.clone()
+        }
+    }
+}
+
+/// Top-level contents and largest packages of a .venv
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VenvPeek {
+    /// Path to the .venv directory
+    pub path: PathBuf,
+    /// Top-level entries, largest first
+    pub entries: Vec<PeekEntry>,
+    /// Largest packages in site-packages, largest first
+    pub packages: Vec<PeekEntry>,
+}
+
+impl VenvPeek {
+    /// Look inside a .venv directory
+    ///
+    /// # Arguments
+    /// * `venv_path` - Path to the .venv directory
+    /// * `package_limit` - Maximum number of packages to list
+    ///
+    /// # Returns
+    /// The peek, or an error if the directory cannot be read
+    pub fn of(venv_path: &Path, package_limit: usize) -> Result<Self> {
+        FileUtils::ensure_exists(venv_path)?;
+        let mut entries = sized_entries(venv_path, |_| true)?;
+        sort_largest_first(&mut entries);
+
+        let mut packages = Vec::new();
+        for site_packages in site_packages::site_packages_dirs(venv_path) {
+            packages.extend(sized_entries(&site_packages, is_package_entry).unwrap_or_default());
+        }
+        sort_largest_first(&mut packages);
+        packages.truncate(package_limit);
+
+        Ok(Self {
+            path: venv_path.to_path_buf(),
+            entries,
+            packages,
+        })
+    }
+}
+
+/// Check whether a site-packages entry is a package rather than metadata
+fn is_package_entry(name: &str) -> bool {
+    !(name.ends_with(".dist-info")
+        || name.ends_with(".egg-info")
+        || name.ends_with(".pth")
+        || name == "__pycache__")
+}
+
+/// List the entries of a directory that pass `keep`, with their sizes
+fn sized_entries(dir: &Path, keep: fn(&str) -> bool) -> Result<Vec<PeekEntry>> {
+    let mut entries = Vec::new();
+
+    for entry in fs::read_dir(dir)?.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !keep(&name) {
+            continue;
+        }
+        let file_type = match entry.file_type() {
+            Ok(file_type) => file_type,
+            Err(_) => continue,
+        };
+        let is_dir = file_type.is_dir();
+        let size_bytes = if is_dir {
+            FileUtils::calculate_directory_size(&entry.path()).unwrap_or(0)
+        } else {
+            entry.metadata().map(|m| m.len()).unwrap_or(0)
+        };
+        entries.push(PeekEntry { name, is_dir, size_bytes });
+    }
+
+    Ok(entries)
+}
+
+/// Sort entries by size, largest first, then by name
+fn sort_largest_first(entries: &mut [PeekEntry]) {
+    entries.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then_with(|| a.name.cmp(&b.name)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_venv(temp_dir: &TempDir) -> PathBuf {
+        let venv = temp_dir.path().join(".venv");
+        let site_packages = venv.join("lib").join("python3.11").join("site-packages");
+        fs::create_dir_all(site_packages.join("numpy")).unwrap();
+        fs::create_dir_all(site_packages.join("six-1.0.dist-info")).unwrap();
+        fs::create_dir_all(venv.join("bin")).unwrap();
+        fs::write(site_packages.join("numpy").join("core.so"), vec![0u8; 4000]).unwrap();
+        fs::write(site_packages.join("six.py"), vec![0u8; 100]).unwrap();
+        fs::write(site_packages.join("six-1.0.dist-info").join("RECORD"), vec![0u8; 5000]).unwrap();
+        fs::write(venv.join("pyvenv.cfg"), "home = /usr/bin\n").unwrap();
+        fs::write(venv.join("results.csv"), vec![0u8; 20000]).unwrap();
+        venv
+    }
+
+    #[test]
+    fn test_top_level_entries_largest_first() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = create_venv(&temp_dir);
+
+        let peek = VenvPeek::of(&venv, DEFAULT_PACKAGE_LIMIT).unwrap();
+        let names: Vec<String> = peek.entries.iter().map(PeekEntry::display_name).collect();
+        assert_eq!(names, vec!["results.csv", "lib/", "pyvenv.cfg", "bin/"]);
+        assert_eq!(peek.entries[0].size_bytes, 20000);
+    }
+
+    #[test]
+    fn test_packages_skip_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = create_venv(&temp_dir);
+
+        let peek = VenvPeek::of(&venv, DEFAULT_PACKAGE_LIMIT).unwrap();
+        let names: Vec<&str> = peek.packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["numpy", "six.py"]);
+
+        let limited = VenvPeek::of(&venv, 1).unwrap();
+        assert_eq!(limited.packages.len(), 1);
+    }
+
+    #[test]
+    fn test_missing_venv_is_an_error() {
+        let temp_dir = TempDir::new().unwrap();
+        let error = VenvPeek::of(&temp_dir.path().join("missing"), DEFAULT_PACKAGE_LIMIT).unwrap_err();
+        assert!(error.is_vanished());
+    }
+}
@@ -0,0 +1,338 @@
+//! Two-phase deletion of .venv directories
+//!
+//! Removing a large .venv takes a while, and an interrupted `remove_dir_all`
+//! leaves a half-deleted environment that still looks like a .venv but no
+//! longer works. Deletions therefore first rename the directory to
+//! `.venv.deleting-<unix time>`, which is atomic and instant, and only then
+//! remove it, inline or on a background thread (`BackgroundRemovals`).
+//! Environments with another name (detector layouts such as `.pyenv-local`,
+//! tox environments such as `py311`) keep it after a dot, as in
+//! `.venv.deleting-<unix time>.py311`. After an interruption the clearly
+//! marked directory is all that is left; the next run finds it and offers to
+//! finish the deletion or to restore the directory to its original name.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use chrono::{DateTime, Utc, TimeZone};
+
+use super::{FileUtils, FsBackend, RealFs, Result, VenvCleanerError};
+
+/// Name prefix of a .venv whose deletion has started
+pub const PENDING_PREFIX: &str = ".venv.deleting-";
+
+/// Name of the environments whose marked name records none
+const DEFAULT_NAME: &str = ".venv";
+
+/// A .venv directory renamed for deletion but not yet removed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingDeletion {
+    /// The renamed directory
+    path: PathBuf,
+    /// When the deletion started, from the directory name
+    started: Option<DateTime<Utc>>,
+    /// The name the directory had before, from the directory name
+    original_name: String,
+    /// Size of what is left, in bytes
+    size_bytes: u64,
+}
+
+impl PendingDeletion {
+    /// Recognise a renamed directory by its name
+    ///
+    /// # Arguments
+    /// * `path` - A directory that may be a pending deletion
+    ///
+    /// # Returns
+    /// The pending deletion, or None if the name does not carry the prefix
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?;
+        let marker = name.strip_prefix(PENDING_PREFIX)?;
+        // The stamp is digits and dashes, so the first dot starts the original name
+        let (stamp, original_name) = marker.split_once('.').unwrap_or((marker, DEFAULT_NAME));
+        let seconds = stamp.split('-').next().and_then(|s| s.parse::<i64>().ok());
+        Some(Self {
+            path: path.to_path_buf(),
+            started: seconds.and_then(|s| Utc.timestamp_opt(s, 0).single()),
+            original_name: original_name.to_string(),
+            size_bytes: FileUtils::calculate_directory_size(path).unwrap_or(0),
+        })
+    }
+
+    /// Get the renamed directory
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Get the path the directory had before the deletion started
+    pub fn venv_path(&self) -> PathBuf {
+        self.path.with_file_name(&self.original_name)
+    }
+
+    /// Get when the deletion started, if the name records it
+    pub fn started(&self) -> Option<&DateTime<Utc>> {
+        self.started.as_ref()
+    }
+
+    /// Get the size of what is left
+    pub fn size_bytes(&self) -> u64 {
+        self.size_bytes
+    }
+
+    /// Remove what is left of the directory
+    pub fn finish(&self) -> Result<()> {
+        fs::remove_dir_all(&self.path)?;
+        Ok(())
+    }
+
+    /// Rename the directory back to its original name
+    ///
+    /// Files removed before the interruption are gone, so the environment
+    /// may need reinstalling; the project keeps its .venv location though.
+    ///
+    /// # Returns
+    /// The restored path, or an error if a new environment already exists there
+    pub fn restore(&self) -> Result<PathBuf> {
+        let venv_path = self.venv_path();
+        if fs::symlink_metadata(&venv_path).is_ok() {
+            return Err(VenvCleanerError::PathError {
+                path: venv_path.display().to_string(),
+                message: format!("A {} already exists here; remove or rename it first", self.original_name),
+            });
+        }
+        fs::rename(&self.path, &venv_path)?;
+        Ok(venv_path)
+    }
+}
+
+/// Rename a .venv for deletion, the first phase of a two-phase delete
+///
+/// # Arguments
+/// * `venv_path` - Path to the .venv directory
+///
+/// # Returns
+/// The new path of the directory, to be removed next
+pub fn mark_for_deletion(venv_path: &Path) -> Result<PathBuf> {
+    mark_for_deletion_in(&RealFs, venv_path)
+}
+
+/// Rename a .venv for deletion on a given file system
+///
+/// # Arguments
+/// * `fs` - The file system the .venv is on
+/// * `venv_path` - Path to the environment directory, named `.venv` or otherwise
+///
+/// # Returns
+/// The new path of the directory, to be removed next
+pub fn mark_for_deletion_in(fs: &dyn FsBackend, venv_path: &Path) -> Result<PathBuf> {
+    let stamp = Utc::now().timestamp();
+    // Other names are kept after the stamp so a restore can give them back
+    let original = match venv_path.file_name().map(|name| name.to_string_lossy()) {
+        Some(name) if name != DEFAULT_NAME => format!(".{}", name),
+        _ => String::new(),
+    };
+    let mut marked = venv_path.with_file_name(format!("{}{}{}", PENDING_PREFIX, stamp, original));
+    // An earlier interrupted deletion may have used the same second
+    let mut attempt = 1;
+    while fs.symlink_metadata(&marked).is_ok() {
+        marked = venv_path.with_file_name(format!("{}{}-{}{}", PENDING_PREFIX, stamp, attempt, original));
+        attempt += 1;
+    }
+
+    match fs.rename(venv_path, &marked) {
+        Ok(()) => Ok(marked),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            FileUtils::ensure_exists_in(fs, venv_path)?;
+            Err(e.into())
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => Err(VenvCleanerError::PermissionDenied {
+            path: venv_path.display().to_string(),
+        }),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Remove a directory renamed for deletion, the second phase of a two-phase delete
+///
+/// # Arguments
+/// * `fs` - The file system the directory is on
+/// * `venv_path` - The path the directory had before it was renamed
+/// * `marked` - The renamed directory
+///
+/// # Returns
+/// An error naming what is left if a file could not be removed
+pub fn remove_marked_in(fs: &dyn FsBackend, venv_path: &Path, marked: &Path) -> Result<()> {
+    match fs.remove_dir_all(marked) {
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => Err(VenvCleanerError::PartlyRemoved {
+            path: venv_path.display().to_string(),
+            marked: marked.to_path_buf(),
+        }),
+        result => Ok(result?),
+    }
+}
+
+/// Check whether a directory name marks a deletion in progress
+pub fn is_marked_name(name: &std::ffi::OsStr) -> bool {
+    name.to_str().is_some_and(|name| name.starts_with(PENDING_PREFIX))
+}
+
+/// The .venv path of a removal running in the background, and the thread removing it
+type Removal = (PathBuf, JoinHandle<Result<()>>);
+
+/// Second phases of two-phase deletes running on background threads
+///
+/// Once renamed, a .venv is out of its project's way, so removing it need not
+/// hold up the next prompt or deletion. Clones share the running removals.
+#[derive(Debug, Clone, Default)]
+pub struct BackgroundRemovals {
+    /// The removals not waited for yet
+    running: Arc<Mutex<Vec<Removal>>>,
+}
+
+impl BackgroundRemovals {
+    /// Start removing a renamed directory on a thread of its own
+    ///
+    /// # Arguments
+    /// * `fs` - The file system the directory is on
+    /// * `venv_path` - The path the directory had before it was renamed
+    /// * `marked` - The renamed directory
+    pub fn spawn(&self, fs: Arc<dyn FsBackend>, venv_path: PathBuf, marked: PathBuf) {
+        let path = venv_path.clone();
+        let thread = thread::spawn(move || remove_marked_in(fs.as_ref(), &path, &marked));
+        self.running.lock().unwrap_or_else(|e| e.into_inner()).push((venv_path, thread));
+    }
+
+    /// Get the number of removals not waited for yet
+    pub fn len(&self) -> usize {
+        self.running.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Check whether every removal has been waited for
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Wait for the running removals to finish
+    ///
+    /// # Returns
+    /// The .venv path and error of each removal that failed
+    pub fn wait(&self) -> Vec<(PathBuf, VenvCleanerError)> {
+        let running = std::mem::take(&mut *self.running.lock().unwrap_or_else(|e| e.into_inner()));
+        running
+            .into_iter()
+            .filter_map(|(venv_path, thread)| {
+                let result = thread
+                    .join()
+                    .unwrap_or_else(|_| Err(VenvCleanerError::Io(format!("Removing {} panicked", venv_path.display()))));
+                result.err().map(|e| (venv_path, e))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_venv(project: &Path) -> PathBuf {
+        let venv = project.join(".venv");
+        fs::create_dir_all(venv.join("bin")).unwrap();
+        fs::write(venv.join("pyvenv.cfg"), "home = /usr/bin\n").unwrap();
+        venv
+    }
+
+    #[test]
+    fn test_mark_then_finish() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = create_venv(&temp_dir.path().join("app"));
+
+        let marked = mark_for_deletion(&venv).unwrap();
+        assert!(!venv.exists());
+        assert!(marked.file_name().unwrap().to_str().unwrap().starts_with(PENDING_PREFIX));
+
+        let pending = PendingDeletion::from_path(&marked).unwrap();
+        assert_eq!(pending.venv_path(), venv);
+        assert!(pending.started().is_some());
+        assert!(pending.size_bytes() > 0);
+
+        pending.finish().unwrap();
+        assert!(!marked.exists());
+    }
+
+    #[test]
+    fn test_restore_renames_back() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("app");
+        let venv = create_venv(&project);
+        let pending = PendingDeletion::from_path(&mark_for_deletion(&venv).unwrap()).unwrap();
+
+        create_venv(&project);
+        assert!(pending.restore().is_err());
+        fs::remove_dir_all(&venv).unwrap();
+        assert_eq!(pending.restore().unwrap(), venv);
+        assert!(venv.join("pyvenv.cfg").is_file());
+    }
+
+    #[test]
+    fn test_background_removal_reports_what_is_left() {
+        use crate::core::{FsOp, MemoryFs};
+
+        let fs = Arc::new(MemoryFs::new());
+        fs.add_venv("/memory/app/.venv", 100);
+        fs.add_venv("/memory/locked/.venv", 100);
+        fs.add_file("/memory/locked/.venv/lib/busy.so", 10);
+        fs.fail("/memory/locked/.venv/lib/busy.so", FsOp::Remove, std::io::ErrorKind::PermissionDenied);
+
+        let removals = BackgroundRemovals::default();
+        for project in ["/memory/app", "/memory/locked"] {
+            let venv = Path::new(project).join(".venv");
+            let marked = mark_for_deletion_in(fs.as_ref(), &venv).unwrap();
+            removals.spawn(fs.clone(), venv, marked);
+        }
+        assert_eq!(removals.clone().len(), 2);
+
+        let failed = removals.wait();
+        assert!(removals.is_empty());
+        assert!(fs.read_dir(Path::new("/memory/app")).unwrap().is_empty());
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].0, Path::new("/memory/locked/.venv"));
+        let marked = failed[0].1.marked_path().unwrap();
+        assert!(failed[0].1.is_permission_denied());
+        assert!(is_marked_name(marked.file_name().unwrap()));
+        assert!(fs.contains(marked.join("lib").join("busy.so")));
+    }
+
+    #[test]
+    fn test_restore_keeps_other_names() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("app");
+        let local = project.join(".pyenv-local");
+        let tox = project.join(".tox").join("py311");
+        fs::create_dir_all(local.join("bin")).unwrap();
+        fs::create_dir_all(tox.join("bin")).unwrap();
+
+        let marked = [mark_for_deletion(&local).unwrap(), mark_for_deletion(&tox).unwrap()];
+        assert!(marked[1].file_name().unwrap().to_str().unwrap().ends_with(".py311"));
+
+        let restored: Vec<PathBuf> = marked.iter().map(|p| PendingDeletion::from_path(p).unwrap().restore().unwrap()).collect();
+        assert_eq!(restored, vec![local.clone(), tox.clone()]);
+        assert!(local.join("bin").is_dir() && tox.join("bin").is_dir());
+
+        // The same second twice still keeps the name
+        let first = mark_for_deletion(&local).unwrap();
+        fs::create_dir_all(local.join("bin")).unwrap();
+        let second = mark_for_deletion(&local).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(PendingDeletion::from_path(&second).unwrap().venv_path(), local);
+    }
+
+    #[test]
+    fn test_mark_missing_venv_reports_vanished() {
+        let temp_dir = TempDir::new().unwrap();
+        let error = mark_for_deletion(&temp_dir.path().join(".venv")).unwrap_err();
+        assert!(error.is_vanished());
+        assert!(PendingDeletion::from_path(Path::new("/p/.venv")).is_none());
+    }
+}
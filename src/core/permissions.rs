@@ -0,0 +1,262 @@
+//! Pre-check of whether .venv directories could be deleted
+//!
+//! On shared machines environments are often created by another user, with
+//! sudo, or inside a directory the current user cannot write. Removing such
+//! a .venv fails part-way, leaving a broken environment behind. This module
+//! walks a .venv without changing anything and lists what would stop the
+//! deletion, so ownership can be fixed before the actual cleanup runs.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Most problems listed per .venv; a root-owned tree would otherwise list every directory
+pub const MAX_PROBLEMS: usize = 20;
+
+/// Something that would make deleting a .venv fail
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PermissionProblem {
+    /// The directory holding the .venv cannot be written, so the .venv cannot be unlinked
+    ParentNotWritable(PathBuf),
+    /// The parent is sticky (like /tmp) and the .venv belongs to another user
+    StickyParent(PathBuf),
+    /// A directory cannot be written, so its entries cannot be removed
+    DirectoryNotWritable(PathBuf),
+    /// A directory cannot be read, so its contents cannot be listed for removal
+    DirectoryNotReadable(PathBuf),
+    /// A file or directory has the immutable or append-only attribute
+    Immutable(PathBuf),
+}
+
+impl PermissionProblem {
+    /// Get the path the problem is about
+    pub fn path(&self) -> &Path {
+        match self {
+            PermissionProblem::ParentNotWritable(path)
+            | PermissionProblem::StickyParent(path)
+            | PermissionProblem::DirectoryNotWritable(path)
+            | PermissionProblem::DirectoryNotReadable(path)
+            | PermissionProblem::Immutable(path) => path,
+        }
+    }
+
+    /// Describe the problem and how it is usually fixed
+    pub fn describe(&self) -> String {
+        match self {
+            PermissionProblem::ParentNotWritable(path) => {
+                format!("{} is not writable (the .venv cannot be removed from it)", path.display())
+            }
+            PermissionProblem::StickyParent(path) => {
+                format!("{} is sticky and the .venv belongs to another user", path.display())
+            }
+            PermissionProblem::DirectoryNotWritable(path) => {
+                format!("{} is not writable (check its owner)", path.display())
+            }
+            PermissionProblem::DirectoryNotReadable(path) => {
+                format!("{} cannot be read", path.display())
+            }
+            PermissionProblem::Immutable(path) => {
+                format!("{} is immutable or append-only (see chattr/chflags)", path.display())
+            }
+        }
+    }
+}
+
+/// Outcome of checking one .venv
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermissionReport {
+    /// The .venv directory checked
+    pub venv_path: PathBuf,
+    /// The problems found, at most `MAX_PROBLEMS`
+    pub problems: Vec<PermissionProblem>,
+    /// Whether more problems were found than listed
+    pub truncated: bool,
+}
+
+impl PermissionReport {
+    /// Check whether a .venv could be deleted by the current user
+    ///
+    /// # Arguments
+    /// * `venv_path` - Path to the .venv directory
+    ///
+    /// # Returns
+    /// The problems that would stop the deletion (none when it would succeed)
+    pub fn check(venv_path: &Path) -> Self {
+        let mut problems = Vec::new();
+
+        if let Some(parent) = venv_path.parent() {
+            if !is_writable_dir(parent) {
+                problems.push(PermissionProblem::ParentNotWritable(parent.to_path_buf()));
+            } else if blocked_by_sticky_bit(parent, venv_path) {
+                problems.push(PermissionProblem::StickyParent(parent.to_path_buf()));
+            }
+        }
+
+        for entry in WalkDir::new(venv_path).follow_links(false) {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    if let Some(path) = e.path() {
+                        problems.push(PermissionProblem::DirectoryNotReadable(path.to_path_buf()));
+                    }
+                    continue;
+                }
+            };
+            let path = entry.path();
+            let file_type = entry.file_type();
+
+            if file_type.is_dir() && !is_writable_dir(path) {
+                problems.push(PermissionProblem::DirectoryNotWritable(path.to_path_buf()));
+            }
+            if (file_type.is_dir() || file_type.is_file()) && is_immutable(path) {
+                problems.push(PermissionProblem::Immutable(path.to_path_buf()));
+            }
+        }
+
+        Self::from_problems(venv_path, problems)
+    }
+
+    /// Build a report, keeping the first `MAX_PROBLEMS` problems
+    fn from_problems(venv_path: &Path, mut problems: Vec<PermissionProblem>) -> Self {
+        let truncated = problems.len() > MAX_PROBLEMS;
+        problems.truncate(MAX_PROBLEMS);
+        Self { venv_path: venv_path.to_path_buf(), problems, truncated }
+    }
+
+    /// Check whether the deletion would succeed
+    pub fn is_deletable(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Check whether the current user may add and remove entries of a directory
+///
+/// Uses access(2), which honours group membership and root.
+#[cfg(unix)]
+fn is_writable_dir(path: &Path) -> bool {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let Ok(c_path) = CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+    // SAFETY: c_path is a valid NUL-terminated string
+    unsafe { libc::access(c_path.as_ptr(), libc::W_OK | libc::X_OK) == 0 }
+}
+
+#[cfg(not(unix))]
+fn is_writable_dir(path: &Path) -> bool {
+    fs::metadata(path).map(|m| !m.permissions().readonly()).unwrap_or(false)
+}
+
+/// Check whether a sticky parent keeps the current user from removing the .venv
+#[cfg(unix)]
+fn blocked_by_sticky_bit(parent: &Path, venv_path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    /// `S_ISVTX`, whose libc type differs between platforms
+    const STICKY_BIT: u32 = 0o1000;
+
+    let (Ok(parent_meta), Ok(venv_meta)) = (fs::metadata(parent), fs::symlink_metadata(venv_path)) else {
+        return false;
+    };
+    if parent_meta.mode() & STICKY_BIT == 0 {
+        return false;
+    }
+    // SAFETY: geteuid has no preconditions
+    let uid = unsafe { libc::geteuid() };
+    uid != 0 && uid != venv_meta.uid() && uid != parent_meta.uid()
+}
+
+#[cfg(not(unix))]
+fn blocked_by_sticky_bit(_parent: &Path, _venv_path: &Path) -> bool {
+    false
+}
+
+/// Check the immutable and append-only attributes set with `chattr`
+#[cfg(target_os = "linux")]
+fn is_immutable(path: &Path) -> bool {
+    use std::os::unix::fs::OpenOptionsExt;
+    use std::os::unix::io::AsRawFd;
+
+    /// `FS_IMMUTABLE_FL` and `FS_APPEND_FL` from linux/fs.h
+    const IMMUTABLE_FLAGS: libc::c_int = 0x10 | 0x20;
+
+    // Non-blocking so a stray FIFO cannot hang the check
+    let Ok(file) = fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_NONBLOCK | libc::O_NOFOLLOW)
+        .open(path)
+    else {
+        return false;
+    };
+    let mut flags: libc::c_int = 0;
+    // SAFETY: the descriptor is open for the duration of the call and flags is writable
+    let result = unsafe { libc::ioctl(file.as_raw_fd(), libc::FS_IOC_GETFLAGS, &mut flags) };
+    result == 0 && flags & IMMUTABLE_FLAGS != 0
+}
+
+/// Check the immutable and append-only flags set with `chflags`
+#[cfg(target_os = "macos")]
+fn is_immutable(path: &Path) -> bool {
+    use std::os::macos::fs::MetadataExt;
+
+    let flags = libc::UF_IMMUTABLE | libc::UF_APPEND | libc::SF_IMMUTABLE | libc::SF_APPEND;
+    fs::symlink_metadata(path).map(|m| m.st_flags() & flags != 0).unwrap_or(false)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn is_immutable(_path: &Path) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_writable_venv_is_deletable() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = temp_dir.path().join(".venv");
+        fs::create_dir_all(venv.join("lib")).unwrap();
+        fs::write(venv.join("pyvenv.cfg"), "home = /usr/bin\n").unwrap();
+
+        let report = PermissionReport::check(&venv);
+        assert!(report.is_deletable(), "{:?}", report.problems);
+        assert!(!report.truncated);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_read_only_directory_is_reported() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let venv = temp_dir.path().join(".venv");
+        let lib = venv.join("lib");
+        fs::create_dir_all(&lib).unwrap();
+        fs::set_permissions(&lib, fs::Permissions::from_mode(0o555)).unwrap();
+
+        let report = PermissionReport::check(&venv);
+        let running_as_root = unsafe { libc::geteuid() } == 0;
+        fs::set_permissions(&lib, fs::Permissions::from_mode(0o755)).unwrap();
+
+        // Root may write anywhere, so only check the report for other users
+        if !running_as_root {
+            assert_eq!(report.problems, vec![PermissionProblem::DirectoryNotWritable(lib.clone())]);
+            assert!(report.problems[0].describe().contains("not writable"));
+        }
+    }
+
+    #[test]
+    fn test_problems_are_capped() {
+        let problems: Vec<PermissionProblem> = (0..MAX_PROBLEMS + 3)
+            .map(|i| PermissionProblem::Immutable(PathBuf::from(format!("/v/{}", i))))
+            .collect();
+        let report = PermissionReport::from_problems(Path::new("/v"), problems);
+        assert!(report.truncated);
+        assert_eq!(report.problems.len(), MAX_PROBLEMS);
+        assert_eq!(report.problems[0].path(), Path::new("/v/0"));
+    }
+}
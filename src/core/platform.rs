@@ -0,0 +1,168 @@
+//! Platform-specific behaviour for VenvCleaner
+//!
+//! This module hides the differences between macOS and Linux that matter for
+//! cleanup: detecting macOS privacy (TCC) denials such as scanning ~/Documents
+//! without Full Disk Access, the guidance shown to users when that happens,
+//! and moving folders to the system trash instead of deleting them (and
+//! purging them from it again).
+
+use std::io;
+use std::path::Path;
+use tracing::debug;
+
+use super::{Result, VenvCleanerError};
+
+/// Steps shown to macOS users when a scan is blocked by privacy settings
+pub const FULL_DISK_ACCESS_GUIDANCE: &str = "macOS blocked access to this folder. Open System Settings > Privacy & Security > Full Disk Access, enable your terminal (or VenvCleaner), then restart it and scan again.";
+
+/// Check whether an IO error is a macOS privacy (TCC) denial
+///
+/// TCC denials surface as `EPERM` ("Operation not permitted") rather than
+/// the usual `EACCES`, which is what distinguishes them from plain
+/// permission problems. Always false on other platforms.
+///
+/// # Arguments
+/// * `error` - The IO error to inspect
+pub fn is_privacy_denied(error: &io::Error) -> bool {
+    if cfg!(target_os = "macos") {
+        // EPERM is 1 on every Unix
+        error.raw_os_error() == Some(1)
+    } else {
+        false
+    }
+}
+
+/// Check whether an IO error means a path is too long for the OS
+///
+/// Deeply nested trees can exceed `PATH_MAX` (or a single name can exceed
+/// 255 bytes); such entries cannot be opened and are skipped by scans.
+///
+/// # Arguments
+/// * `error` - The IO error to inspect
+pub fn is_name_too_long(error: &io::Error) -> bool {
+    error.kind() == io::ErrorKind::InvalidFilename
+}
+
+/// Get actionable guidance for an error, if there is any
+///
+/// # Arguments
+/// * `error` - The error to describe
+///
+/// # Returns
+/// A short instruction for the user, or None if the error needs no guidance
+pub fn error_guidance(error: &VenvCleanerError) -> Option<&'static str> {
+    match error {
+        VenvCleanerError::FullDiskAccessRequired { .. } => Some(FULL_DISK_ACCESS_GUIDANCE),
+        _ => None,
+    }
+}
+
+/// Format an error for display, appending guidance when available
+///
+/// # Arguments
+/// * `error` - The error to describe
+///
+/// # Returns
+/// The error message, followed by a blank line and the guidance if any
+pub fn describe_error(error: &VenvCleanerError) -> String {
+    match error_guidance(error) {
+        Some(guidance) => format!("{}\n\n{}", error, guidance),
+        None => error.to_string(),
+    }
+}
+
+/// Move a file or directory to the system trash
+///
+/// On macOS this uses `NSFileManager` directly rather than asking Finder,
+/// which needs no extra Automation permission and works for quarantined
+/// files. On Linux the freedesktop.org trash specification is used.
+///
+/// # Arguments
+/// * `path` - The path to move to the trash
+pub fn move_to_trash(path: &Path) -> Result<()> {
+    debug!("Moving to trash: {}", path.display());
+
+    #[allow(unused_mut)]
+    let mut context = trash::TrashContext::default();
+
+    #[cfg(target_os = "macos")]
+    {
+        use trash::macos::{DeleteMethod, TrashContextExtMacos};
+        context.set_delete_method(DeleteMethod::NsFileManager);
+    }
+
+    context.delete(path).map_err(|e| VenvCleanerError::Io(format!(
+        "Failed to move {} to trash: {}",
+        path.display(),
+        e
+    )))
+}
+
+/// List the items in the system trash
+///
+/// Only Linux (freedesktop.org trash) and Windows allow inspecting the trash.
+#[cfg(any(target_os = "windows", all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))))]
+pub fn list_trash() -> Result<Vec<trash::TrashItem>> {
+    trash::os_limited::list().map_err(|e| VenvCleanerError::Io(format!("Failed to list the trash: {}", e)))
+}
+
+/// List the items in the system trash
+#[cfg(not(any(target_os = "windows", all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android")))))]
+pub fn list_trash() -> Result<Vec<trash::TrashItem>> {
+    Err(VenvCleanerError::FeatureNotAvailable(
+        "Purging the trash is not supported on this platform; empty it from the file manager".to_string(),
+    ))
+}
+
+/// Permanently remove items from the system trash
+///
+/// # Arguments
+/// * `items` - Items returned by `list_trash`
+#[cfg(any(target_os = "windows", all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))))]
+pub fn purge_from_trash(items: Vec<trash::TrashItem>) -> Result<()> {
+    debug!("Purging {} items from the trash", items.len());
+    trash::os_limited::purge_all(items).map_err(|e| VenvCleanerError::Io(format!("Failed to purge the trash: {}", e)))
+}
+
+/// Permanently remove items from the system trash
+#[cfg(not(any(target_os = "windows", all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android")))))]
+pub fn purge_from_trash(_items: Vec<trash::TrashItem>) -> Result<()> {
+    Err(VenvCleanerError::FeatureNotAvailable(
+        "Purging the trash is not supported on this platform".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_privacy_denied() {
+        let eperm = io::Error::from_raw_os_error(1);
+        assert_eq!(is_privacy_denied(&eperm), cfg!(target_os = "macos"));
+
+        let not_found = io::Error::new(io::ErrorKind::NotFound, "missing");
+        assert!(!is_privacy_denied(&not_found));
+        assert!(!is_name_too_long(&not_found));
+
+        // ENAMETOOLONG is 36 on Linux and 63 on macOS
+        #[cfg(target_os = "linux")]
+        assert!(is_name_too_long(&io::Error::from_raw_os_error(36)));
+    }
+
+    #[test]
+    fn test_describe_error_with_guidance() {
+        let error = VenvCleanerError::FullDiskAccessRequired { path: "/Users/me/Documents".to_string() };
+        let description = describe_error(&error);
+        assert!(description.contains("/Users/me/Documents"));
+        assert!(description.contains("Full Disk Access"));
+        assert!(description.contains("System Settings"));
+    }
+
+    #[test]
+    fn test_describe_error_without_guidance() {
+        let error = VenvCleanerError::NoVenvFound;
+        assert!(error_guidance(&error).is_none());
+        assert_eq!(describe_error(&error), error.to_string());
+    }
+}
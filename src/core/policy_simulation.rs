@@ -0,0 +1,205 @@
+//! Trying cleanup rules out before automating them
+//!
+//! An unattended `--force` run applies several rules in turn: the age and
+//! size criteria pick the candidates, then system Python locations, the
+//! policy's protected paths, `--exclude-size-above`, the force-delete size
+//! limit, user data and the batch size limit each keep some of them. A
+//! simulation runs the same rules over a scan or a saved report without
+//! deleting anything and tallies what each rule kept, so thresholds can be
+//! tuned before the cron job or service is switched on.
+
+use std::path::Path;
+
+use super::{safety, ByteSize, PolicyConfig, VenvFilter, VenvInfo};
+
+/// The rules of an unattended run, in the order they are applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SimulationRule {
+    /// The policy disables `--force`
+    ForceDisabled,
+    /// `--older-than`/`--min-size` are not met
+    CriteriaNotMet,
+    /// The .venv lies in a system Python location
+    SystemPython,
+    /// The .venv lies in one of the policy's protected paths
+    ProtectedPath,
+    /// The .venv is larger than `--exclude-size-above`
+    SizeOutlier,
+    /// The .venv is larger than the policy's `max_force_delete_size`
+    ForceSizeLimit,
+    /// The .venv holds user data and `--include-user-data` is not given
+    UserData,
+    /// The policy's `max_batch_size` deletions were reached
+    BatchLimit,
+}
+
+impl SimulationRule {
+    /// Every rule, in the order they are applied
+    pub const ALL: [SimulationRule; 8] = [
+        SimulationRule::ForceDisabled,
+        SimulationRule::CriteriaNotMet,
+        SimulationRule::SystemPython,
+        SimulationRule::ProtectedPath,
+        SimulationRule::SizeOutlier,
+        SimulationRule::ForceSizeLimit,
+        SimulationRule::UserData,
+        SimulationRule::BatchLimit,
+    ];
+
+    /// Describe what the rule keeps
+    pub fn describe(self) -> &'static str {
+        match self {
+            SimulationRule::ForceDisabled => "--force is disabled by the policy",
+            SimulationRule::CriteriaNotMet => "not matching --older-than/--min-size",
+            SimulationRule::SystemPython => "in a system Python location",
+            SimulationRule::ProtectedPath => "in a protected path of the policy",
+            SimulationRule::SizeOutlier => "larger than --exclude-size-above",
+            SimulationRule::ForceSizeLimit => "larger than the policy's max_force_delete_size",
+            SimulationRule::UserData => "contains user data (--include-user-data deletes it)",
+            SimulationRule::BatchLimit => "beyond the policy's max_batch_size",
+        }
+    }
+}
+
+/// The settings an unattended run would apply
+#[derive(Debug, Clone, Default)]
+pub struct SimulationRules {
+    /// Age and size criteria picking the candidates
+    pub filter: VenvFilter,
+    /// Restrictions from the system policy and user config
+    pub policy: PolicyConfig,
+    /// Size above which a match is never deleted unattended
+    pub exclude_size_above: Option<ByteSize>,
+    /// Whether .venv directories holding user data are deleted too
+    pub include_user_data: bool,
+}
+
+/// What an unattended run would do with each .venv of a scan
+#[derive(Debug, Clone, Default)]
+pub struct PolicySimulation {
+    /// The .venv directories that would be deleted, in scan order
+    deleted: Vec<VenvInfo>,
+    /// The .venv directories that would be kept, with the first rule keeping each
+    kept: Vec<(VenvInfo, SimulationRule)>,
+}
+
+impl PolicySimulation {
+    /// Apply the rules to the .venv directories of a scan, in order
+    ///
+    /// # Arguments
+    /// * `venvs` - Every .venv found, before `--older-than`/`--min-size`
+    /// * `rules` - The settings the unattended run would use
+    ///
+    /// # Returns
+    /// The simulation
+    pub fn run(venvs: &[VenvInfo], rules: &SimulationRules) -> Self {
+        let force_limit = rules.policy.max_force_delete_size;
+        let mut simulation = Self::default();
+        for venv in venvs {
+            let rule = if rules.policy.disable_force {
+                Some(SimulationRule::ForceDisabled)
+            } else if !rules.filter.matches(venv) {
+                Some(SimulationRule::CriteriaNotMet)
+            } else if safety::protected_python_location(venv.path()).is_some() {
+                Some(SimulationRule::SystemPython)
+            } else if rules.policy.protecting(venv.path()).is_some() {
+                Some(SimulationRule::ProtectedPath)
+            } else if rules.exclude_size_above.is_some_and(|limit| venv.size() > limit) {
+                Some(SimulationRule::SizeOutlier)
+            } else if force_limit.is_some_and(|limit| venv.size() > limit) {
+                Some(SimulationRule::ForceSizeLimit)
+            } else if venv.has_user_data() && !rules.include_user_data {
+                Some(SimulationRule::UserData)
+            } else if rules.policy.max_batch_size.is_some_and(|limit| simulation.deleted.len() >= limit) {
+                Some(SimulationRule::BatchLimit)
+            } else {
+                None
+            };
+            match rule {
+                Some(rule) => simulation.kept.push((venv.clone(), rule)),
+                None => simulation.deleted.push(venv.clone()),
+            }
+        }
+        simulation
+    }
+
+    /// Get the .venv directories that would be deleted
+    pub fn deleted(&self) -> &[VenvInfo] {
+        &self.deleted
+    }
+
+    /// Get the bytes the deletions would free
+    pub fn deleted_bytes(&self) -> u64 {
+        self.deleted.iter().map(|venv| venv.size().bytes()).sum()
+    }
+
+    /// Get the .venv directories a rule would keep
+    pub fn kept_by(&self, rule: SimulationRule) -> Vec<&VenvInfo> {
+        self.kept.iter().filter(|(_, kept_by)| *kept_by == rule).map(|(venv, _)| venv).collect()
+    }
+
+    /// Get the rule that would keep a .venv, None if it would be deleted or is not in the scan
+    pub fn rule_for(&self, path: &Path) -> Option<SimulationRule> {
+        self.kept.iter().find(|(venv, _)| venv.path() == path).map(|(_, rule)| *rule)
+    }
+
+    /// Get the number of .venv directories kept and the bytes they hold
+    pub fn kept_totals(&self) -> (usize, u64) {
+        (self.kept.len(), self.kept.iter().map(|(venv, _)| venv.size().bytes()).sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use std::path::PathBuf;
+
+    fn venv(path: &str, size: u64, age_days: i64) -> VenvInfo {
+        let last_used = Utc::now() - Duration::days(age_days);
+        VenvInfo::new(PathBuf::from(path), ByteSize::new(size), last_used, last_used)
+    }
+
+    #[test]
+    fn test_first_matching_rule_keeps() {
+        let venvs = vec![
+            venv("/p/recent/.venv", 500, 5),
+            venv("/p/old/.venv", 500, 200),
+            venv("/p/shared/old/.venv", 500, 200),
+            venv("/p/huge/.venv", 5000, 200),
+            venv("/p/older/.venv", 700, 300),
+            venv("/p/oldest/.venv", 900, 400),
+        ];
+        let rules = SimulationRules {
+            filter: VenvFilter::default().with_older_than(Some(Duration::days(90))),
+            policy: PolicyConfig {
+                protected_paths: vec![PathBuf::from("/p/shared")],
+                max_batch_size: Some(2),
+                ..PolicyConfig::default()
+            },
+            exclude_size_above: Some(ByteSize::new(1000)),
+            include_user_data: false,
+        };
+        let simulation = PolicySimulation::run(&venvs, &rules);
+
+        let deleted: Vec<&Path> = simulation.deleted().iter().map(VenvInfo::path).collect();
+        assert_eq!(deleted, vec![Path::new("/p/old/.venv"), Path::new("/p/older/.venv")]);
+        assert_eq!(simulation.deleted_bytes(), 1200);
+        assert_eq!(simulation.rule_for(Path::new("/p/recent/.venv")), Some(SimulationRule::CriteriaNotMet));
+        assert_eq!(simulation.rule_for(Path::new("/p/shared/old/.venv")), Some(SimulationRule::ProtectedPath));
+        assert_eq!(simulation.rule_for(Path::new("/p/huge/.venv")), Some(SimulationRule::SizeOutlier));
+        assert_eq!(simulation.kept_by(SimulationRule::BatchLimit).len(), 1);
+        assert_eq!(simulation.kept_totals(), (4, 6900));
+    }
+
+    #[test]
+    fn test_disabled_force_keeps_everything() {
+        let rules = SimulationRules {
+            policy: PolicyConfig { disable_force: true, ..PolicyConfig::default() },
+            ..SimulationRules::default()
+        };
+        let simulation = PolicySimulation::run(&[venv("/p/old/.venv", 1, 400)], &rules);
+        assert!(simulation.deleted().is_empty());
+        assert_eq!(simulation.kept_by(SimulationRule::ForceDisabled).len(), 1);
+    }
+}
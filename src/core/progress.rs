@@ -0,0 +1,160 @@
+//! Machine-readable progress events
+//!
+//! With `--progress json` the CLI writes one JSON object per line to stdout
+//! describing what it is doing, so wrapper GUIs and scripts can embed
+//! venv_cleaner as a backend. Every event carries an `event` field naming its
+//! kind; events are emitted in a deterministic order (venvs sorted by path).
+
+use serde::Serialize;
+
+use super::{timestamps, ScanMetadata, VenvInfo};
+
+/// A single progress event of the line-delimited JSON protocol
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ProgressEvent {
+    /// Scanning has started
+    ScanStarted {
+        /// Directory being searched
+        root: String,
+        /// Whether the search is recursive
+        recursive: bool,
+        /// VenvCleaner version scanning
+        version: String,
+        /// Name of the machine scanned
+        host: String,
+        /// When the scan started (RFC 3339)
+        started_at: String,
+    },
+    /// A .venv directory was found
+    VenvFound {
+        /// Full path to the .venv directory
+        path: String,
+        /// Size of the directory in bytes
+        size_bytes: u64,
+        /// Whether sizing timed out, making `size_bytes` a lower bound
+        size_partial: bool,
+        /// Last modification time (RFC 3339)
+        last_modified: String,
+        /// Python version from pyvenv.cfg, if known
+        python_version: Option<String>,
+        /// Whether the parent project is installed in editable mode
+        editable_install: bool,
+        /// Bytes of files that look like user data (zero if none)
+        user_data_bytes: u64,
+    },
+    /// Deleting a .venv directory finished (successfully or not)
+    DeleteDone {
+        /// Full path to the .venv directory
+        path: String,
+        /// Bytes freed (zero on failure)
+        freed_bytes: u64,
+        /// Whether this was a dry run (nothing was actually removed)
+        dry_run: bool,
+        /// Error message if the deletion failed
+        error: Option<String>,
+        /// Estimated seconds until the remaining deletions finish, if measured
+        eta_seconds: Option<u64>,
+    },
+    /// The run is complete
+    Summary {
+        /// Number of .venv directories found
+        found: usize,
+        /// Number of .venv directories deleted
+        deleted: usize,
+        /// Total bytes freed
+        freed_bytes: u64,
+        /// Number of deletions that failed
+        errors: usize,
+        /// When the run finished (RFC 3339)
+        finished_at: String,
+        /// Seconds from the start of the scan to the end of the run
+        duration_seconds: i64,
+    },
+}
+
+impl ProgressEvent {
+    /// Create a `scan_started` event for a scan
+    ///
+    /// Only the first root is named; the metadata of reports lists them all.
+    pub fn scan_started(metadata: &ScanMetadata) -> Self {
+        ProgressEvent::ScanStarted {
+            root: metadata.roots.first().map(|root| root.display().to_string()).unwrap_or_default(),
+            recursive: metadata.recursive,
+            version: metadata.version.clone(),
+            host: metadata.host.clone(),
+            started_at: timestamps::to_rfc3339(&metadata.started),
+        }
+    }
+
+    /// Create a `venv_found` event for a discovered .venv directory
+    pub fn venv_found(venv_info: &VenvInfo) -> Self {
+        ProgressEvent::VenvFound {
+            path: venv_info.path().display().to_string(),
+            size_bytes: venv_info.size().bytes(),
+            size_partial: venv_info.is_size_partial(),
+            last_modified: timestamps::to_rfc3339(venv_info.last_modified()),
+            python_version: venv_info.python_version().map(|v| v.to_string()),
+            editable_install: venv_info.has_editable_install(),
+            user_data_bytes: venv_info.user_data_bytes(),
+        }
+    }
+
+    /// Serialize the event as a single line of JSON (without the newline)
+    pub fn to_json_line(&self) -> String {
+        // Serializing plain strings and numbers cannot fail
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ByteSize;
+    use chrono::{TimeZone, Utc};
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn test_scan_started_json() {
+        let started = Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap();
+        let mut metadata = ScanMetadata::new(&[Path::new("/home/user")], true, started);
+        metadata.host = "build-01".to_string();
+        metadata.version = "1.2.3".to_string();
+        // The timezone is a process-wide setting, so compare against whatever it formats to
+        assert_eq!(
+            ProgressEvent::scan_started(&metadata).to_json_line(),
+            format!(
+                r#"{{"event":"scan_started","root":"/home/user","recursive":true,"version":"1.2.3","host":"build-01","started_at":"{}"}}"#,
+                timestamps::to_rfc3339(&started)
+            )
+        );
+    }
+
+    #[test]
+    fn test_venv_found_json() {
+        let venv = VenvInfo::new(PathBuf::from("/p/.venv"), ByteSize::new(42), Utc::now(), Utc::now());
+        let json: serde_json::Value = serde_json::from_str(&ProgressEvent::venv_found(&venv).to_json_line()).unwrap();
+
+        assert_eq!(json["event"], "venv_found");
+        assert_eq!(json["path"], "/p/.venv");
+        assert_eq!(json["size_bytes"], 42);
+        assert!(json["python_version"].is_null());
+        assert_eq!(json["editable_install"], false);
+        assert_eq!(json["user_data_bytes"], 0);
+    }
+
+    #[test]
+    fn test_summary_json_is_single_line() {
+        let event = ProgressEvent::Summary {
+            found: 3,
+            deleted: 2,
+            freed_bytes: 1024,
+            errors: 1,
+            finished_at: timestamps::to_rfc3339(&Utc::now()),
+            duration_seconds: 4,
+        };
+        let line = event.to_json_line();
+        assert!(!line.contains('\n'));
+        assert!(line.starts_with(r#"{"event":"summary""#));
+    }
+}
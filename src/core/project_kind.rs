@@ -0,0 +1,112 @@
+//! Recognising what kind of project a .venv belongs to
+//!
+//! The files next to a .venv say what it is for: `manage.py` marks a Django
+//! site, a `notebooks` folder or `*.ipynb` files Jupyter work, and torch,
+//! tensorflow or jax in site-packages a machine-learning experiment, whose
+//! environment easily holds gigabytes of CUDA libraries. Listings show the
+//! kind as a small badge, so the heavyweight experiments stand out.
+
+use std::fs;
+use std::path::Path;
+
+use super::site_packages;
+
+/// Packages that make a .venv a machine-learning environment
+pub const ML_PACKAGES: &[&str] = &["torch", "tensorflow", "jax", "keras"];
+
+/// Folder names holding a project's notebooks
+pub const NOTEBOOK_DIRS: &[&str] = &["notebooks", "notebook"];
+
+/// Kinds of projects recognised by their markers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProjectKind {
+    /// A deep learning framework is installed
+    MachineLearning,
+    /// The project has a Django `manage.py`
+    Django,
+    /// The project keeps Jupyter notebooks
+    Notebook,
+}
+
+impl ProjectKind {
+    /// Recognise the kind of the project owning a .venv
+    ///
+    /// Installed ML frameworks win over the project's markers, since they
+    /// decide how large the environment is.
+    ///
+    /// # Arguments
+    /// * `venv_path` - Path to the .venv directory
+    ///
+    /// # Returns
+    /// The kind, or None if no marker was found
+    pub fn detect(venv_path: &Path) -> Option<Self> {
+        let has_ml_package = site_packages::site_packages_dirs(venv_path)
+            .iter()
+            .any(|site_packages| ML_PACKAGES.iter().any(|package| site_packages.join(package).is_dir()));
+        if has_ml_package {
+            return Some(ProjectKind::MachineLearning);
+        }
+
+        let project = venv_path.parent()?;
+        if project.join("manage.py").is_file() {
+            return Some(ProjectKind::Django);
+        }
+        let has_notebooks = NOTEBOOK_DIRS.iter().any(|dir| project.join(dir).is_dir())
+            || fs::read_dir(project).is_ok_and(|entries| {
+                entries
+                    .flatten()
+                    .any(|entry| entry.path().extension().is_some_and(|extension| extension == "ipynb"))
+            });
+        has_notebooks.then_some(ProjectKind::Notebook)
+    }
+
+    /// Get the short badge shown in listings
+    pub fn badge(self) -> &'static str {
+        match self {
+            ProjectKind::MachineLearning => "ML",
+            ProjectKind::Django => "Django",
+            ProjectKind::Notebook => "Notebook",
+        }
+    }
+
+    /// Get what the badge means, for tooltips
+    pub fn description(self) -> &'static str {
+        match self {
+            ProjectKind::MachineLearning => "Machine-learning environment: torch, tensorflow, jax or keras is installed",
+            ProjectKind::Django => "Django project: it has a manage.py",
+            ProjectKind::Notebook => "Notebook project: it keeps Jupyter notebooks",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_project_kinds() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = temp_dir.path().join(".venv");
+        fs::create_dir_all(&venv).unwrap();
+        assert_eq!(ProjectKind::detect(&venv), None);
+
+        fs::write(temp_dir.path().join("analysis.ipynb"), "{}").unwrap();
+        assert_eq!(ProjectKind::detect(&venv), Some(ProjectKind::Notebook));
+
+        fs::write(temp_dir.path().join("manage.py"), "").unwrap();
+        assert_eq!(ProjectKind::detect(&venv), Some(ProjectKind::Django));
+
+        // An installed framework wins over the project's markers
+        fs::create_dir_all(venv.join("lib").join("python3.12").join("site-packages").join("torch")).unwrap();
+        assert_eq!(ProjectKind::detect(&venv), Some(ProjectKind::MachineLearning));
+        assert_eq!(ProjectKind::MachineLearning.badge(), "ML");
+    }
+
+    #[test]
+    fn test_notebooks_folder() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("notebooks")).unwrap();
+        assert_eq!(ProjectKind::detect(&temp_dir.path().join(".venv")), Some(ProjectKind::Notebook));
+    }
+}
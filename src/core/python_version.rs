@@ -0,0 +1,187 @@
+//! Python interpreter version detection for .venv directories
+//!
+//! Virtual environments created by `venv`, `virtualenv` and `uv` record the
+//! interpreter they were built with in `pyvenv.cfg`. This module parses that
+//! file and knows which CPython release lines have reached end-of-life.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use chrono::NaiveDate;
+use tracing::debug;
+
+/// End-of-life dates for CPython release lines as (major, minor, year, month, day)
+const EOL_DATES: &[(u32, u32, i32, u32, u32)] = &[
+    (2, 7, 2020, 1, 1),
+    (3, 5, 2020, 9, 30),
+    (3, 6, 2021, 12, 23),
+    (3, 7, 2023, 6, 27),
+    (3, 8, 2024, 10, 7),
+    (3, 9, 2025, 10, 31),
+    (3, 10, 2026, 10, 31),
+    (3, 11, 2027, 10, 31),
+    (3, 12, 2028, 10, 31),
+    (3, 13, 2029, 10, 31),
+];
+
+/// A Python interpreter version as recorded in pyvenv.cfg
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PythonVersion {
+    /// Major version number (e.g. 3)
+    major: u32,
+    /// Minor version number (e.g. 11)
+    minor: u32,
+    /// Patch version number, if recorded
+    patch: Option<u32>,
+}
+
+impl PythonVersion {
+    /// Create a new PythonVersion instance
+    pub fn new(major: u32, minor: u32, patch: Option<u32>) -> Self {
+        Self { major, minor, patch }
+    }
+
+    /// Parse a version string such as `3.11.4`, `3.12` or `3.13.0rc1`
+    ///
+    /// # Returns
+    /// The parsed version, or None if the string has no major.minor pair
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.trim().split('.');
+        let major = parts.next()?.trim().parse().ok()?;
+        let minor = parts.next()?.trim().parse().ok()?;
+
+        // Patch releases may carry pre-release suffixes like "0rc1"
+        let patch = parts.next().and_then(|p| {
+            let digits: String = p.chars().take_while(|c| c.is_ascii_digit()).collect();
+            digits.parse().ok()
+        });
+
+        Some(Self::new(major, minor, patch))
+    }
+
+    /// Read the interpreter version from a .venv directory's pyvenv.cfg
+    ///
+    /// # Arguments
+    /// * `venv_path` - Path to the .venv directory
+    ///
+    /// # Returns
+    /// The version, or None if pyvenv.cfg is missing or has no version key
+    pub fn from_venv(venv_path: &Path) -> Option<Self> {
+        let contents = fs::read_to_string(venv_path.join("pyvenv.cfg")).ok()?;
+        let version = Self::from_pyvenv_cfg(&contents);
+        if version.is_none() {
+            debug!("No Python version found in {}/pyvenv.cfg", venv_path.display());
+        }
+        version
+    }
+
+    /// Extract the interpreter version from the contents of a pyvenv.cfg file
+    ///
+    /// `venv` writes a `version` key while `uv` and `virtualenv` write
+    /// `version_info`; either is accepted.
+    pub fn from_pyvenv_cfg(contents: &str) -> Option<Self> {
+        contents.lines().find_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            match key.trim() {
+                "version" | "version_info" => Self::parse(value),
+                _ => None,
+            }
+        })
+    }
+
+    /// Get the major version number
+    pub fn major(&self) -> u32 {
+        self.major
+    }
+
+    /// Get the minor version number
+    pub fn minor(&self) -> u32 {
+        self.minor
+    }
+
+    /// Get the release line as a "major.minor" string (e.g. "3.11")
+    pub fn release_line(&self) -> String {
+        format!("{}.{}", self.major, self.minor)
+    }
+
+    /// Get the end-of-life date of this release line, if known
+    pub fn eol_date(&self) -> Option<NaiveDate> {
+        EOL_DATES
+            .iter()
+            .find(|(major, minor, ..)| *major == self.major && *minor == self.minor)
+            .and_then(|&(_, _, y, m, d)| NaiveDate::from_ymd_opt(y, m, d))
+    }
+
+    /// Check whether this release line is past end-of-life on the given date
+    ///
+    /// Release lines older than anything in the table (e.g. Python 2.6) count
+    /// as end-of-life; unknown newer lines do not.
+    pub fn is_eol_on(&self, date: NaiveDate) -> bool {
+        match self.eol_date() {
+            Some(eol) => date >= eol,
+            None => {
+                let oldest = EOL_DATES[0];
+                (self.major, self.minor) < (oldest.0, oldest.1)
+            }
+        }
+    }
+
+    /// Check whether this release line is past end-of-life today
+    pub fn is_eol(&self) -> bool {
+        self.is_eol_on(chrono::Utc::now().date_naive())
+    }
+}
+
+impl fmt::Display for PythonVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.patch {
+            Some(patch) => write!(f, "{}.{}.{}", self.major, self.minor, patch),
+            None => write!(f, "{}.{}", self.major, self.minor),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_versions() {
+        assert_eq!(PythonVersion::parse("3.11.4"), Some(PythonVersion::new(3, 11, Some(4))));
+        assert_eq!(PythonVersion::parse("3.12"), Some(PythonVersion::new(3, 12, None)));
+        assert_eq!(PythonVersion::parse(" 3.13.0rc1 "), Some(PythonVersion::new(3, 13, Some(0))));
+        assert_eq!(PythonVersion::parse("3"), None);
+        assert_eq!(PythonVersion::parse("abc"), None);
+    }
+
+    #[test]
+    fn test_from_pyvenv_cfg() {
+        let venv_cfg = "home = /usr/bin\ninclude-system-site-packages = false\nversion = 3.10.12\n";
+        assert_eq!(PythonVersion::from_pyvenv_cfg(venv_cfg).unwrap().to_string(), "3.10.12");
+
+        let uv_cfg = "home = /opt/python/bin\nimplementation = CPython\nuv = 0.4.0\nversion_info = 3.12.5\n";
+        assert_eq!(PythonVersion::from_pyvenv_cfg(uv_cfg).unwrap().release_line(), "3.12");
+
+        assert!(PythonVersion::from_pyvenv_cfg("home = /usr/bin\n").is_none());
+    }
+
+    #[test]
+    fn test_from_venv() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(PythonVersion::from_venv(temp_dir.path()).is_none());
+
+        fs::write(temp_dir.path().join("pyvenv.cfg"), "version = 3.8.18\n").unwrap();
+        assert_eq!(PythonVersion::from_venv(temp_dir.path()), Some(PythonVersion::new(3, 8, Some(18))));
+    }
+
+    #[test]
+    fn test_is_eol_on() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        assert!(PythonVersion::new(3, 7, None).is_eol_on(date));
+        assert!(PythonVersion::new(3, 8, Some(10)).is_eol_on(date));
+        assert!(!PythonVersion::new(3, 9, None).is_eol_on(date));
+        assert!(PythonVersion::new(2, 6, None).is_eol_on(date));
+        assert!(!PythonVersion::new(3, 99, None).is_eol_on(date));
+    }
+}
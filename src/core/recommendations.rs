@@ -0,0 +1,537 @@
+//! Cleanup recommendations for a set of .venv directories
+//!
+//! Beyond "this one is old", a scan can tell which environments no longer
+//! work (their interpreter is gone), which run an end-of-life Python, which
+//! are copies of each other (same Python, same packages), how much
+//! regenerable cache the rest carry, and which heavy packages are installed
+//! over and over where uv's cache would keep a single hardlinked copy. Each recommendation names the .venv
+//! directories it covers, the space acting on it reclaims and the exact
+//! command to run, so the CLI, TUI and GUI can all show the same advice.
+//!
+//! A .venv is put in at most one category that deletes it, so acting on
+//! every recommendation never deletes anything twice. Printing the same
+//! advice on every `--query` of a cron job is noise, so the CLI keeps a
+//! small history and repeats an unchanged recommendation only once a day.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{paths, shell, site_packages, slim, FileUtils, Result, VenvCleanerError, VenvInfo, VenvStats};
+
+/// Size above which a .venv counts as large
+pub const LARGE_VENV_BYTES: u64 = 500 * 1024 * 1024;
+
+/// Regenerable cache, summed over all .venv directories, worth a slim pass
+pub const CACHE_THRESHOLD_BYTES: u64 = 50 * 1024 * 1024;
+
+/// Average size of one copy of a package worth sharing between environments
+pub const SHARED_PACKAGE_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Number of environments a heavy package must be installed in to be worth sharing
+pub const SHARED_PACKAGE_MIN_VENVS: usize = 3;
+
+/// Documentation of uv's cache, which hardlinks packages into environments
+pub const UV_CACHE_DOCS: &str = "https://docs.astral.sh/uv/concepts/cache/";
+
+/// Packages named in the title of a shared-packages recommendation
+const NAMED_PACKAGES: usize = 3;
+
+/// How long an unchanged recommendation stays quiet after being shown
+pub const COOLDOWN_HOURS: i64 = 24;
+
+/// What a recommendation is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecommendationKind {
+    /// Environments whose interpreter or pyvenv.cfg is gone
+    Broken,
+    /// Environments not used for more than 90 days
+    Old,
+    /// Environments on a Python release past its end of life
+    EolPython,
+    /// Environments with the same Python and packages as a more recently used one
+    Duplicates,
+    /// Regenerable caches (`__pycache__`, bundled tests, pip caches) inside environments
+    Caches,
+    /// Environments over 500 MB
+    Large,
+    /// Heavy packages installed separately in many environments
+    SharedPackages,
+}
+
+impl RecommendationKind {
+    /// Every kind, in the order recommendations are listed
+    pub const ALL: [RecommendationKind; 7] = [
+        RecommendationKind::Broken,
+        RecommendationKind::Old,
+        RecommendationKind::EolPython,
+        RecommendationKind::Duplicates,
+        RecommendationKind::Caches,
+        RecommendationKind::Large,
+        RecommendationKind::SharedPackages,
+    ];
+
+    /// Get the icon shown before the recommendation
+    pub fn icon(self) -> &'static str {
+        match self {
+            RecommendationKind::Broken => "💔",
+            RecommendationKind::Old => "🧹",
+            RecommendationKind::EolPython => "⏳",
+            RecommendationKind::Duplicates => "👯",
+            RecommendationKind::Caches => "✂️",
+            RecommendationKind::Large => "📦",
+            RecommendationKind::SharedPackages => "🔗",
+        }
+    }
+
+    /// Check whether acting on the recommendation deletes the environments
+    pub fn deletes(self) -> bool {
+        !matches!(self, RecommendationKind::Caches | RecommendationKind::Large | RecommendationKind::SharedPackages)
+    }
+
+    /// Check whether the recommendation is advice only, with nothing VenvCleaner can act on
+    pub fn is_advice(self) -> bool {
+        matches!(self, RecommendationKind::Large | RecommendationKind::SharedPackages)
+    }
+
+    /// Get the documentation explaining the recommendation, if any
+    pub fn doc_link(self) -> Option<&'static str> {
+        match self {
+            RecommendationKind::SharedPackages => Some(UV_CACHE_DOCS),
+            _ => None,
+        }
+    }
+}
+
+/// One piece of advice with the command that acts on it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recommendation {
+    /// What the recommendation is about
+    kind: RecommendationKind,
+    /// The .venv directories it covers
+    venvs: Vec<PathBuf>,
+    /// Bytes acting on it would reclaim (for `Large`, the size of the directories)
+    bytes: u64,
+    /// The shell command that acts on it
+    command: String,
+    /// The packages it is about, heaviest first (for `SharedPackages`)
+    packages: Vec<String>,
+}
+
+impl Recommendation {
+    /// Create a recommendation
+    pub fn new(kind: RecommendationKind, venvs: Vec<PathBuf>, bytes: u64, command: String) -> Self {
+        Self { kind, venvs, bytes, command, packages: Vec::new() }
+    }
+
+    /// Name the packages the recommendation is about
+    pub fn with_packages(mut self, packages: Vec<String>) -> Self {
+        self.packages = packages;
+        self
+    }
+
+    /// Get the packages the recommendation is about
+    pub fn packages(&self) -> &[String] {
+        &self.packages
+    }
+
+    /// Get what the recommendation is about
+    pub fn kind(&self) -> RecommendationKind {
+        self.kind
+    }
+
+    /// Get the .venv directories it covers
+    pub fn venvs(&self) -> &[PathBuf] {
+        &self.venvs
+    }
+
+    /// Get the bytes acting on it would reclaim
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Get the shell command that acts on it
+    pub fn command(&self) -> &str {
+        &self.command
+    }
+
+    /// Describe the recommendation in one sentence, without its size
+    pub fn title(&self) -> String {
+        let count = self.venvs.len();
+        match self.kind {
+            RecommendationKind::Broken => format!("{} broken .venv directories (interpreter or pyvenv.cfg missing) can only be recreated", count),
+            RecommendationKind::Old => format!("{} old .venv directories (>90 days) could be cleaned up", count),
+            RecommendationKind::EolPython => format!("{} .venv directories use an end-of-life Python and should be recreated", count),
+            RecommendationKind::Duplicates => format!("{} .venv directories duplicate another environment's Python and packages", count),
+            RecommendationKind::Caches => format!("{} .venv directories hold regenerable caches that slimming removes", count),
+            RecommendationKind::Large => format!("{} large .venv directories (>500MB) are taking significant space", count),
+            RecommendationKind::SharedPackages => {
+                let mut names = self.packages.iter().take(NAMED_PACKAGES).cloned().collect::<Vec<_>>().join(", ");
+                if self.packages.len() > NAMED_PACKAGES {
+                    names.push_str(&format!(" and {} more", self.packages.len() - NAMED_PACKAGES));
+                }
+                format!("{} .venv directories install their own copies of {}; uv's hardlinked cache would keep one", count, names)
+            }
+        }
+    }
+
+    /// Get a fingerprint of what the recommendation covers, to notice when it changes
+    fn signature(&self) -> String {
+        format!("{}:{}", self.venvs.len(), self.bytes)
+    }
+}
+
+/// Work out every recommendation for a scan
+///
+/// Looking for caches walks each environment again, so this is done once
+/// per scan rather than on every redraw.
+///
+/// # Arguments
+/// * `venvs` - The scanned .venv directories
+/// * `roots` - The directories that were scanned, for commands covering a whole root
+///
+/// # Returns
+/// The recommendations that apply, in `RecommendationKind::ALL` order
+pub fn recommend(venvs: &[VenvInfo], roots: &[&Path]) -> Vec<Recommendation> {
+    let mut recommendations = Vec::new();
+    let mut claimed: HashSet<&Path> = HashSet::new();
+
+    let broken: Vec<&VenvInfo> = venvs.iter().filter(|v| is_broken(v.path())).collect();
+    push_deletion(&mut recommendations, &mut claimed, RecommendationKind::Broken, broken);
+
+    let old: Vec<&VenvInfo> = venvs.iter().filter(|v| v.is_old()).collect();
+    push_deletion(&mut recommendations, &mut claimed, RecommendationKind::Old, old);
+
+    let eol: Vec<&VenvInfo> = venvs.iter().filter(|v| v.python_version().is_some_and(|p| p.is_eol())).collect();
+    push_deletion(&mut recommendations, &mut claimed, RecommendationKind::EolPython, eol);
+
+    let duplicates = find_duplicates(venvs.iter().filter(|v| !claimed.contains(v.path())));
+    push_deletion(&mut recommendations, &mut claimed, RecommendationKind::Duplicates, duplicates);
+
+    // Slimming keeps the environment, so it only concerns the ones not recommended for deletion
+    let mut cached = Vec::new();
+    let mut cache_bytes = 0;
+    for venv in venvs.iter().filter(|v| !claimed.contains(v.path())) {
+        let bytes: u64 = slim::slim_candidates(venv.path())
+            .iter()
+            .filter_map(|dir| FileUtils::calculate_directory_size(dir).ok())
+            .sum();
+        if bytes > 0 {
+            cached.push(venv.path().to_path_buf());
+            cache_bytes += bytes;
+        }
+    }
+    if cache_bytes >= CACHE_THRESHOLD_BYTES {
+        let command = roots
+            .iter()
+            .map(|root| format!("venv_cleaner slim -r {}", shell::shell_quote(&root.display().to_string())))
+            .collect::<Vec<_>>()
+            .join(" && ");
+        recommendations.push(Recommendation::new(RecommendationKind::Caches, cached, cache_bytes, command));
+    }
+
+    let large: Vec<&VenvInfo> = venvs.iter().filter(|v| v.size().bytes() > LARGE_VENV_BYTES).collect();
+    if !large.is_empty() {
+        let command = roots
+            .iter()
+            .map(|root| format!("venv_cleaner stats --breakdown -r {}", shell::shell_quote(&root.display().to_string())))
+            .collect::<Vec<_>>()
+            .join(" && ");
+        recommendations.push(Recommendation::new(
+            RecommendationKind::Large,
+            large.iter().map(|v| v.path().to_path_buf()).collect(),
+            large.iter().map(|v| v.size().bytes()).sum(),
+            command,
+        ));
+    }
+
+    if let Some(shared) = shared_packages(venvs.iter().filter(|v| !claimed.contains(v.path())), roots) {
+        recommendations.push(shared);
+    }
+
+    recommendations
+}
+
+/// Recommend sharing the heavy packages installed in many of the environments
+///
+/// The savings count every copy but the first of each package, which is
+/// what installing through uv's cache, hardlinking one copy into every
+/// environment, would free.
+fn shared_packages<'a>(venvs: impl Iterator<Item = &'a VenvInfo>, roots: &[&Path]) -> Option<Recommendation> {
+    let venvs: Vec<VenvInfo> = venvs.cloned().collect();
+    let shared: Vec<_> = VenvStats::by_package(&venvs)
+        .into_iter()
+        .filter(|group| {
+            group.venv_count() >= SHARED_PACKAGE_MIN_VENVS
+                && group.total_bytes() / group.venv_count() as u64 >= SHARED_PACKAGE_BYTES
+        })
+        .collect();
+    if shared.is_empty() {
+        return None;
+    }
+
+    let savings = shared
+        .iter()
+        .map(|group| group.total_bytes() - group.total_bytes() / group.venv_count() as u64)
+        .sum();
+    let mut covered: Vec<PathBuf> = shared.iter().flat_map(|group| group.venvs().iter().cloned()).collect();
+    covered.sort();
+    covered.dedup();
+    let command = roots
+        .iter()
+        .map(|root| format!("venv_cleaner stats --by-package -r {}", shell::shell_quote(&root.display().to_string())))
+        .collect::<Vec<_>>()
+        .join(" && ");
+    let packages = shared.iter().map(|group| group.name().to_string()).collect();
+    Some(Recommendation::new(RecommendationKind::SharedPackages, covered, savings, command).with_packages(packages))
+}
+
+/// Add a deleting recommendation for the environments no earlier one claimed
+fn push_deletion<'a>(
+    recommendations: &mut Vec<Recommendation>,
+    claimed: &mut HashSet<&'a Path>,
+    kind: RecommendationKind,
+    venvs: Vec<&'a VenvInfo>,
+) {
+    let venvs: Vec<&VenvInfo> = venvs.into_iter().filter(|v| claimed.insert(v.path())).collect();
+    if venvs.is_empty() {
+        return;
+    }
+    recommendations.push(Recommendation::new(
+        kind,
+        venvs.iter().map(|v| v.path().to_path_buf()).collect(),
+        venvs.iter().map(|v| v.size().bytes()).sum(),
+        deletion_command(&venvs),
+    ));
+}
+
+/// Build the command deleting exactly these environments: each project scanned on its own
+fn deletion_command(venvs: &[&VenvInfo]) -> String {
+    let projects: Vec<String> = venvs
+        .iter()
+        .map(|v| shell::shell_quote(&v.location()))
+        .collect();
+    format!("venv_cleaner -f {}", projects.join(" "))
+}
+
+/// Check whether a .venv can no longer run: no pyvenv.cfg, or no interpreter that resolves
+///
+/// A dangling `bin/python` symlink, left when the Python it was created
+/// from is uninstalled or upgraded, fails to resolve and counts as missing.
+pub fn is_broken(venv_path: &Path) -> bool {
+    let interpreters = [
+        venv_path.join("bin").join("python"),
+        venv_path.join("bin").join("python3"),
+        venv_path.join("Scripts").join("python.exe"),
+    ];
+    !venv_path.join("pyvenv.cfg").is_file() || !interpreters.iter().any(|python| python.is_file())
+}
+
+/// Find the environments duplicating a more recently used one
+///
+/// Two environments are duplicates when they have the same Python release
+/// line and exactly the same installed distributions (name and version, as
+/// recorded by their `*.dist-info` folders). Of each group the most recently
+/// used environment is kept.
+fn find_duplicates<'a>(venvs: impl Iterator<Item = &'a VenvInfo>) -> Vec<&'a VenvInfo> {
+    let mut groups: BTreeMap<(String, Vec<String>), Vec<&VenvInfo>> = BTreeMap::new();
+    for venv in venvs {
+        let Some(python) = venv.python_version() else {
+            continue;
+        };
+        let mut packages: Vec<String> = site_packages::dist_info_dirs(venv.path())
+            .iter()
+            .filter_map(|dir| dir.file_name().map(|name| name.to_string_lossy().to_lowercase()))
+            .collect();
+        if packages.is_empty() {
+            continue;
+        }
+        packages.sort();
+        groups.entry((python.release_line(), packages)).or_default().push(venv);
+    }
+
+    let mut duplicates = Vec::new();
+    for mut group in groups.into_values().filter(|group| group.len() > 1) {
+        group.sort_by(|a, b| a.compare_by_last_modified(b));
+        duplicates.extend(group.into_iter().skip(1));
+    }
+    duplicates.sort_by(|a, b| a.compare_by_path(b));
+    duplicates
+}
+
+/// When each kind of recommendation was last shown, to repeat unchanged ones only once a day
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecommendationHistory {
+    /// Where the history is saved, None for an in-memory history
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    /// The last showing of each kind
+    shown: BTreeMap<RecommendationKind, ShownRecommendation>,
+}
+
+/// The last time a kind of recommendation was shown, and what it covered then
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ShownRecommendation {
+    /// When it was shown
+    at: DateTime<Utc>,
+    /// What it covered (see `Recommendation::signature`)
+    signature: String,
+}
+
+impl RecommendationHistory {
+    /// Get the default location of the history file
+    pub fn default_path() -> Option<PathBuf> {
+        paths::cache_dir().map(|dir| dir.join("recommendations.json"))
+    }
+
+    /// Load the history from a file; a missing or unreadable file gives an empty history
+    ///
+    /// # Arguments
+    /// * `path` - Path to the history file
+    pub fn load(path: PathBuf) -> Self {
+        let history: Self = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        Self { path: Some(path), ..history }
+    }
+
+    /// Check whether a recommendation was shown unchanged within the cooldown
+    ///
+    /// # Arguments
+    /// * `recommendation` - The recommendation about to be shown
+    /// * `now` - The current time
+    pub fn is_quiet(&self, recommendation: &Recommendation, now: DateTime<Utc>) -> bool {
+        self.shown.get(&recommendation.kind()).is_some_and(|shown| {
+            shown.signature == recommendation.signature() && now - shown.at < Duration::hours(COOLDOWN_HOURS)
+        })
+    }
+
+    /// Record that recommendations were shown
+    ///
+    /// # Arguments
+    /// * `recommendations` - The recommendations shown
+    /// * `now` - The current time
+    pub fn mark_shown<'a>(&mut self, recommendations: impl IntoIterator<Item = &'a Recommendation>, now: DateTime<Utc>) {
+        for recommendation in recommendations {
+            self.shown.insert(
+                recommendation.kind(),
+                ShownRecommendation { at: now, signature: recommendation.signature() },
+            );
+        }
+    }
+
+    /// Write the history back to disk (a no-op for in-memory histories)
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| VenvCleanerError::Io(format!("Failed to encode recommendation history: {}", e)))?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ByteSize;
+    use tempfile::TempDir;
+
+    /// Create a working .venv with a Python version, packages and a regular interpreter file
+    fn create_venv(project: &Path, python: &str, packages: &[&str], age_days: i64) -> VenvInfo {
+        let venv = project.join(".venv");
+        let site_packages = venv.join("lib").join(format!("python{}", python)).join("site-packages");
+        fs::create_dir_all(&site_packages).unwrap();
+        fs::create_dir_all(venv.join("bin")).unwrap();
+        fs::write(venv.join("bin").join("python"), "").unwrap();
+        fs::write(venv.join("pyvenv.cfg"), format!("version = {}.0\n", python)).unwrap();
+        for package in packages {
+            fs::create_dir_all(site_packages.join(format!("{}.dist-info", package))).unwrap();
+        }
+
+        let last_used = Utc::now() - chrono::Duration::days(age_days);
+        VenvInfo::new(venv.clone(), ByteSize::new(1000), last_used, last_used)
+            .with_python_version(super::super::PythonVersion::from_venv(&venv))
+    }
+
+    #[test]
+    fn test_each_venv_is_recommended_for_deletion_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let broken_and_old = create_venv(&root.join("broken"), "3.11", &["six-1.16.0"], 200);
+        fs::remove_file(broken_and_old.path().join("bin").join("python")).unwrap();
+        let old = create_venv(&root.join("old"), "3.12", &["requests-2.31.0"], 120);
+        let eol = create_venv(&root.join("legacy"), "3.7", &["flask-2.0.0"], 5);
+        let kept = create_venv(&root.join("app"), "3.12", &["numpy-1.26.0"], 1);
+        let copy = create_venv(&root.join("app-copy"), "3.12", &["numpy-1.26.0"], 10);
+
+        let venvs = vec![broken_and_old.clone(), old.clone(), eol.clone(), kept, copy.clone()];
+        let recommendations = recommend(&venvs, &[root]);
+        let kinds: Vec<RecommendationKind> = recommendations.iter().map(Recommendation::kind).collect();
+        assert_eq!(
+            kinds,
+            vec![RecommendationKind::Broken, RecommendationKind::Old, RecommendationKind::EolPython, RecommendationKind::Duplicates]
+        );
+        assert_eq!(recommendations[0].venvs(), [broken_and_old.path().to_path_buf()]);
+        assert_eq!(recommendations[1].venvs(), [old.path().to_path_buf()]);
+        assert_eq!(recommendations[2].venvs(), [eol.path().to_path_buf()]);
+        assert_eq!(recommendations[3].venvs(), [copy.path().to_path_buf()]);
+        assert_eq!(
+            recommendations[3].command(),
+            format!("venv_cleaner -f {}", shell::shell_quote(&copy.location()))
+        );
+        assert_eq!(recommendations[1].bytes(), 1000);
+    }
+
+    #[test]
+    fn test_heavy_packages_in_many_venvs_are_worth_sharing() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let mut venvs = Vec::new();
+        for project in ["a", "b", "c"] {
+            let venv = create_venv(&root.join(project), "3.12", &[], 1);
+            let torch = venv.path().join("lib").join("python3.12").join("site-packages").join("torch");
+            fs::create_dir_all(&torch).unwrap();
+            // Sparse files reach the threshold without writing 100 MB each
+            fs::File::create(torch.join("libtorch.so")).unwrap().set_len(SHARED_PACKAGE_BYTES).unwrap();
+            venvs.push(venv);
+        }
+
+        let shared = shared_packages(venvs.iter(), &[root]).unwrap();
+        assert_eq!(shared.packages(), ["torch"]);
+        assert_eq!(shared.venvs().len(), 3);
+        assert_eq!(shared.bytes(), 2 * SHARED_PACKAGE_BYTES);
+        assert_eq!(shared.kind().doc_link(), Some(UV_CACHE_DOCS));
+        assert!(shared.title().contains("torch"));
+
+        // Two copies are not worth a recommendation yet
+        assert!(shared_packages(venvs.iter().take(2), &[root]).is_none());
+    }
+
+    #[test]
+    fn test_history_quiets_unchanged_recommendations_for_a_day() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("recommendations.json");
+        let now = Utc::now();
+        let recommendation = Recommendation::new(RecommendationKind::Old, vec![PathBuf::from("/p/.venv")], 10, String::new());
+
+        let mut history = RecommendationHistory::load(path.clone());
+        assert!(!history.is_quiet(&recommendation, now));
+        history.mark_shown([&recommendation], now);
+        history.save().unwrap();
+
+        let history = RecommendationHistory::load(path);
+        assert!(history.is_quiet(&recommendation, now + Duration::hours(1)));
+        assert!(!history.is_quiet(&recommendation, now + Duration::hours(COOLDOWN_HOURS)));
+        let grown = Recommendation::new(RecommendationKind::Old, vec![PathBuf::from("/p/.venv")], 20, String::new());
+        assert!(!history.is_quiet(&grown, now + Duration::hours(1)));
+    }
+}
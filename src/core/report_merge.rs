@@ -0,0 +1,328 @@
+//! Combining scan reports from several machines
+//!
+//! The quarterly team-wide disk review needs one view of every machine. Each
+//! machine saves a report, either with `--query --progress json` or with the
+//! GUI/TUI export to JSON, and `merge` reads them all. Reports name the host
+//! they were made on (in the `scan` block of exports, or the `scan_started`
+//! event); older reports without one are attributed to their file name
+//! without the extension, so save them as `build-01.json`, `laptop-ana.json`
+//! and so on.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+
+use super::{timestamps, ByteSize, Result, VenvCleanerError};
+
+/// A .venv listed in one machine's report
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergedVenv {
+    /// The machine the report came from
+    pub host: String,
+    /// Path to the .venv directory on that machine
+    pub path: PathBuf,
+    /// Size of the directory in bytes
+    pub size_bytes: u64,
+    /// Last modification time, if the report has it
+    pub last_modified: Option<DateTime<Utc>>,
+    /// Python version from pyvenv.cfg, if known
+    pub python_version: Option<String>,
+}
+
+impl MergedVenv {
+    /// Check whether the .venv was unused for over 90 days when the report was saved
+    ///
+    /// A .venv without a modification time never counts as old.
+    pub fn is_old(&self) -> bool {
+        self.last_modified.is_some_and(|modified| modified < Utc::now() - Duration::days(90))
+    }
+}
+
+/// Totals of the .venv directories of one machine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostSummary {
+    /// The machine
+    pub host: String,
+    /// Number of .venv directories reported
+    pub count: usize,
+    /// Their total size in bytes
+    pub total_bytes: u64,
+    /// Size of those not used for over 90 days
+    pub reclaimable_bytes: u64,
+}
+
+/// The fields read from a `venv_found` or `scan_started` event or an exported row
+#[derive(Deserialize)]
+struct ReportRow {
+    event: Option<String>,
+    host: Option<String>,
+    path: Option<PathBuf>,
+    size_bytes: Option<u64>,
+    last_modified: Option<String>,
+    python_version: Option<String>,
+}
+
+/// An exported JSON report: its rows and the scan they came from
+#[derive(Deserialize)]
+struct ExportedReport {
+    venvs: Vec<ReportRow>,
+    scan: Option<ExportedScan>,
+}
+
+/// The fields read from the `scan` block of an export
+#[derive(Deserialize)]
+struct ExportedScan {
+    host: Option<String>,
+}
+
+/// The .venv directories of several machines' reports
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MergedReport {
+    /// Every .venv, in the order the reports were added
+    venvs: Vec<MergedVenv>,
+}
+
+impl MergedReport {
+    /// Create an empty report
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add one machine's report (see `parse_report` for the formats read)
+    ///
+    /// # Arguments
+    /// * `host` - The machine the report came from, unless the report names one
+    /// * `text` - The report's contents
+    ///
+    /// # Returns
+    /// The number of .venv directories added, or an error if the report is not valid JSON
+    pub fn add_report(&mut self, host: &str, text: &str) -> Result<usize> {
+        let venvs = parse_report(host, text)?;
+        let added = venvs.len();
+        self.venvs.extend(venvs);
+        Ok(added)
+    }
+
+    /// Add a report file, named after the machine it came from
+    ///
+    /// # Arguments
+    /// * `path` - The saved report; its file name without the extension is the host
+    ///   unless the report names one
+    ///
+    /// # Returns
+    /// The number of .venv directories added
+    pub fn add_file(&mut self, path: &Path) -> Result<usize> {
+        let path_error = |message: String| VenvCleanerError::PathError {
+            path: path.display().to_string(),
+            message,
+        };
+        let host = host_of(path);
+        let text = fs::read_to_string(path).map_err(|e| path_error(e.to_string()))?;
+        self.add_report(&host, &text).map_err(|e| path_error(e.to_string()))
+    }
+
+    /// Get every .venv of the merged reports
+    pub fn venvs(&self) -> &[MergedVenv] {
+        &self.venvs
+    }
+
+    /// Total the .venv directories of each machine
+    ///
+    /// # Returns
+    /// One summary per machine, sorted by host
+    pub fn host_summaries(&self) -> Vec<HostSummary> {
+        let mut summaries: BTreeMap<&str, HostSummary> = BTreeMap::new();
+        for venv in &self.venvs {
+            let summary = summaries.entry(&venv.host).or_insert_with(|| HostSummary {
+                host: venv.host.clone(),
+                count: 0,
+                total_bytes: 0,
+                reclaimable_bytes: 0,
+            });
+            summary.count += 1;
+            summary.total_bytes += venv.size_bytes;
+            if venv.is_old() {
+                summary.reclaimable_bytes += venv.size_bytes;
+            }
+        }
+        summaries.into_values().collect()
+    }
+
+    /// Render the merged report as a standalone HTML page
+    ///
+    /// The page has a table of per-machine totals and one of every .venv,
+    /// largest first, with a host column.
+    pub fn to_html(&self) -> String {
+        let mut html = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>VenvCleaner report</title>\n\
+             <style>body{font-family:sans-serif}table{border-collapse:collapse;margin-bottom:2em}\
+             th,td{border:1px solid #ccc;padding:4px 8px;text-align:left}td.num{text-align:right}</style>\n\
+             </head>\n<body>\n<h1>VenvCleaner report</h1>\n",
+        );
+
+        html.push_str("<h2>Machines</h2>\n<table>\n<tr><th>Host</th><th>.venv</th><th>Size</th><th>Reclaimable (&gt;90 days)</th></tr>\n");
+        let summaries = self.host_summaries();
+        for summary in &summaries {
+            html.push_str(&format!(
+                "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>\n",
+                escape_html(&summary.host),
+                summary.count,
+                ByteSize::new(summary.total_bytes),
+                ByteSize::new(summary.reclaimable_bytes)
+            ));
+        }
+        html.push_str(&format!(
+            "<tr><th>Total</th><th class=\"num\">{}</th><th class=\"num\">{}</th><th class=\"num\">{}</th></tr>\n</table>\n",
+            self.venvs.len(),
+            summaries.iter().map(|summary| ByteSize::new(summary.total_bytes)).sum::<ByteSize>(),
+            summaries.iter().map(|summary| ByteSize::new(summary.reclaimable_bytes)).sum::<ByteSize>()
+        ));
+
+        html.push_str("<h2>Environments</h2>\n<table>\n<tr><th>Host</th><th>Path</th><th>Size</th><th>Last modified</th><th>Python</th></tr>\n");
+        let mut venvs: Vec<&MergedVenv> = self.venvs.iter().collect();
+        venvs.sort_by_key(|venv| std::cmp::Reverse(venv.size_bytes));
+        for venv in venvs {
+            html.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td class=\"num\">{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape_html(&venv.host),
+                escape_html(&venv.path.display().to_string()),
+                ByteSize::new(venv.size_bytes),
+                venv.last_modified.map(|modified| timestamps::format_date(&modified)).unwrap_or_default(),
+                escape_html(venv.python_version.as_deref().unwrap_or(""))
+            ));
+        }
+        html.push_str("</table>\n</body>\n</html>\n");
+        html
+    }
+}
+
+/// Read the .venv directories of a saved report
+///
+/// Exports (a JSON object with `venvs` and `scan`, or the plain array of
+/// older versions, as written by `--output json` and the GUI/TUI export) and
+/// `--progress json` output (one event per line, of which only `venv_found`
+/// and `scan_started` events are used) are both accepted, so `merge` and
+/// `diff` read the same reports.
+///
+/// # Arguments
+/// * `host` - The machine the report came from, unless the report names one
+/// * `text` - The report's contents
+///
+/// # Returns
+/// The .venv directories listed, or an error if the report is not valid JSON
+pub(crate) fn parse_report(host: &str, text: &str) -> Result<Vec<MergedVenv>> {
+    let mut host = host.to_string();
+    let rows: Vec<ReportRow> = if text.trim_start().starts_with('[') {
+        serde_json::from_str(text)
+            .map_err(|e| VenvCleanerError::InvalidArgument(format!("Not an exported list: {}", e)))?
+    } else if let Ok(report) = serde_json::from_str::<ExportedReport>(text) {
+        if let Some(named) = report.scan.and_then(|scan| scan.host) {
+            host = named;
+        }
+        report.venvs
+    } else {
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str(line).map_err(|e| {
+                    VenvCleanerError::InvalidArgument(format!("Line {} is not a progress event: {}", index + 1, e))
+                })
+            })
+            .collect::<Result<_>>()?
+    };
+
+    let mut venvs = Vec::new();
+    for row in rows {
+        match row.event.as_deref() {
+            Some("scan_started") => {
+                if let Some(named) = row.host {
+                    host = named;
+                }
+                continue;
+            }
+            Some("venv_found") | None => {}
+            Some(_) => continue,
+        }
+        let Some(path) = row.path else { continue };
+        venvs.push(MergedVenv {
+            host: host.clone(),
+            path,
+            size_bytes: row.size_bytes.unwrap_or(0),
+            last_modified: row
+                .last_modified
+                .and_then(|modified| DateTime::parse_from_rfc3339(&modified).ok())
+                .map(|modified| modified.with_timezone(&Utc)),
+            python_version: row.python_version,
+        });
+    }
+    Ok(venvs)
+}
+
+/// Get the host a report file belongs to from its name
+fn host_of(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+/// Escape text for use in HTML
+pub(super) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_merge_progress_json_and_exports() {
+        let old = (Utc::now() - Duration::days(200)).to_rfc3339();
+        let mut report = MergedReport::new();
+
+        let progress = format!(
+            "{{\"event\":\"scan_started\",\"root\":\"/home\",\"recursive\":true}}\n\
+             {{\"event\":\"venv_found\",\"path\":\"/home/a/.venv\",\"size_bytes\":100,\"last_modified\":\"{}\"}}\n",
+            old
+        );
+        assert_eq!(report.add_report("build-01", &progress).unwrap(), 1);
+        let export = "[{\"path\":\"/p/b/.venv\",\"size_bytes\":40},{\"path\":\"/p/c/.venv\",\"size_bytes\":2}]";
+        assert_eq!(report.add_report("laptop", export).unwrap(), 2);
+        assert!(report.add_report("bad", "not json").is_err());
+
+        let summaries = report.host_summaries();
+        assert_eq!(summaries.len(), 2);
+        assert_eq!((summaries[0].host.as_str(), summaries[0].reclaimable_bytes), ("build-01", 100));
+        assert_eq!((summaries[1].count, summaries[1].total_bytes, summaries[1].reclaimable_bytes), (2, 42, 0));
+    }
+
+    #[test]
+    fn test_host_from_file_name_and_html() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("gpu<box>.json");
+        fs::write(&file, "{\"event\":\"venv_found\",\"path\":\"/srv/x/.venv\",\"size_bytes\":7}\n").unwrap();
+
+        let mut report = MergedReport::new();
+        report.add_file(&file).unwrap();
+        assert_eq!(report.venvs()[0].host, "gpu<box>");
+
+        let html = report.to_html();
+        assert!(html.contains("<td>gpu&lt;box&gt;</td><td>/srv/x/.venv</td><td class=\"num\">7 bytes</td>"));
+        assert!(matches!(report.add_file(&temp_dir.path().join("missing.json")), Err(VenvCleanerError::PathError { .. })));
+    }
+
+    #[test]
+    fn test_reports_naming_their_host() {
+        let mut report = MergedReport::new();
+        let export = "{\"venvs\": [{\"path\": \"/p/a/.venv\", \"size_bytes\": 5}], \"scan\": {\"host\": \"build-07\"}}";
+        assert_eq!(report.add_report("export", export).unwrap(), 1);
+        let progress = "{\"event\":\"scan_started\",\"root\":\"/p\",\"recursive\":true,\"host\":\"laptop\"}\n\
+                        {\"event\":\"venv_found\",\"path\":\"/p/b/.venv\",\"size_bytes\":3}\n";
+        assert_eq!(report.add_report("progress", progress).unwrap(), 1);
+        let hosts: Vec<&str> = report.venvs().iter().map(|venv| venv.host.as_str()).collect();
+        assert_eq!(hosts, ["build-07", "laptop"]);
+    }
+}
@@ -0,0 +1,117 @@
+//! Per-root totals for scans of several directories
+//!
+//! A scan can cover several roots at once (`venv_cleaner ~/work ~/src`).
+//! This module attributes each .venv to the root it was found under and
+//! totals the count, size and reclaimable space of every root, where
+//! reclaimable means unused for over 90 days, as in the recommendations.
+
+use std::path::{Path, PathBuf};
+
+use super::VenvInfo;
+
+/// Totals of the .venv directories found under one root
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RootSummary {
+    /// The scanned directory
+    pub root: PathBuf,
+    /// Number of .venv directories found under it
+    pub count: usize,
+    /// Their total size in bytes
+    pub total_bytes: u64,
+    /// Size of those not used for over 90 days
+    pub reclaimable_bytes: u64,
+}
+
+impl RootSummary {
+    /// Total the .venv directories of each root
+    ///
+    /// # Arguments
+    /// * `roots` - The scanned directories, in scan order
+    /// * `venvs` - The .venv directories found
+    ///
+    /// # Returns
+    /// One summary per root, in the order given (roots without any .venv included)
+    pub fn for_roots(roots: &[&Path], venvs: &[VenvInfo]) -> Vec<Self> {
+        let mut summaries: Vec<Self> = roots
+            .iter()
+            .map(|root| Self {
+                root: root.to_path_buf(),
+                count: 0,
+                total_bytes: 0,
+                reclaimable_bytes: 0,
+            })
+            .collect();
+
+        for venv in venvs {
+            let Some(index) = root_index(roots, venv.path()) else {
+                continue;
+            };
+            let summary = &mut summaries[index];
+            summary.count += 1;
+            summary.total_bytes += venv.size().bytes();
+            if venv.is_old() {
+                summary.reclaimable_bytes += venv.size().bytes();
+            }
+        }
+
+        summaries
+    }
+}
+
+/// Find the root a path was found under
+///
+/// When roots are nested, the innermost one wins.
+///
+/// # Arguments
+/// * `roots` - The scanned directories
+/// * `path` - A path below one of them
+///
+/// # Returns
+/// The position of the root in `roots`, if any contains the path
+pub fn root_index(roots: &[&Path], path: &Path) -> Option<usize> {
+    roots
+        .iter()
+        .enumerate()
+        .filter(|(_, root)| path.starts_with(root))
+        .max_by_key(|(_, root)| root.components().count())
+        .map(|(index, _)| index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ByteSize;
+    use chrono::{Duration, Utc};
+
+    fn venv(path: &str, size: u64, days_old: i64) -> VenvInfo {
+        let when = Utc::now() - Duration::days(days_old);
+        VenvInfo::new(PathBuf::from(path), ByteSize::new(size), when, when)
+    }
+
+    #[test]
+    fn test_root_index_prefers_innermost_root() {
+        let roots = [Path::new("/work"), Path::new("/work/clientA"), Path::new("/src")];
+        assert_eq!(root_index(&roots, Path::new("/work/tools/.venv")), Some(0));
+        assert_eq!(root_index(&roots, Path::new("/work/clientA/api/.venv")), Some(1));
+        assert_eq!(root_index(&roots, Path::new("/src/lib/.venv")), Some(2));
+        assert_eq!(root_index(&roots, Path::new("/elsewhere/.venv")), None);
+        // Component-wise matching: /workshop is not below /work
+        assert_eq!(root_index(&roots, Path::new("/workshop/.venv")), None);
+    }
+
+    #[test]
+    fn test_for_roots_totals_each_root() {
+        let roots = [Path::new("/work"), Path::new("/src"), Path::new("/empty")];
+        let venvs = vec![
+            venv("/work/a/.venv", 100, 200),
+            venv("/work/b/.venv", 50, 1),
+            venv("/src/c/.venv", 30, 120),
+        ];
+
+        let summaries = RootSummary::for_roots(&roots, &venvs);
+        assert_eq!(summaries.len(), 3);
+        assert_eq!((summaries[0].count, summaries[0].total_bytes, summaries[0].reclaimable_bytes), (2, 150, 100));
+        assert_eq!((summaries[1].count, summaries[1].total_bytes, summaries[1].reclaimable_bytes), (1, 30, 30));
+        assert_eq!(summaries[2].count, 0);
+    }
+}
@@ -0,0 +1,293 @@
+//! Per-root locks keeping concurrent cleanups apart
+//!
+//! A cron job and an interactive run cleaning the same tree would race on
+//! the same deletions, each reporting the other's removals as failures. A
+//! cleanup therefore holds an advisory lock per scanned root, a file named
+//! after the root's path in VenvCleaner's data directory, and a shared lock
+//! on each of the root's ancestors, so cleanups of nested roots (`~` and
+//! `~/work`) exclude each other too. The locks are `flock`s on those files,
+//! so the kernel releases them when a run exits or crashes and a stale lock
+//! file never blocks anyone. A root's file records the holder's pid for the
+//! "another instance is cleaning" message.
+
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use sha2::{Digest, Sha256};
+
+use super::{paths, Result, VenvCleanerError};
+
+/// Directory holding the lock files
+pub fn default_lock_dir() -> PathBuf {
+    paths::data_dir()
+        .unwrap_or_else(|| PathBuf::from("venv_cleaner"))
+        .join("locks")
+}
+
+/// How to behave when another instance holds a lock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockMode {
+    /// Fail with `VenvCleanerError::AlreadyRunning`
+    FailFast,
+    /// Block until the other instance finishes (`--wait`)
+    Wait,
+}
+
+/// A held lock on one root, released when dropped
+#[derive(Debug)]
+pub struct RunLock {
+    /// The locked root
+    root: PathBuf,
+    /// Shared locks on the root's ancestors (those no earlier lock of the run
+    /// holds), then the exclusive lock on the root; closing them releases them
+    files: Vec<File>,
+}
+
+impl RunLock {
+    /// Lock a root against other VenvCleaner instances
+    ///
+    /// # Arguments
+    /// * `root` - The scanned directory
+    /// * `lock_dir` - Where lock files live (see `default_lock_dir`)
+    /// * `mode` - Whether to fail or wait when it is already locked
+    ///
+    /// # Returns
+    /// The held lock, or `AlreadyRunning` naming the other instance
+    pub fn acquire(root: &Path, lock_dir: &Path, mode: LockMode) -> Result<Self> {
+        Self::acquire_tree(root, &canonical(root), lock_dir, mode, &mut HashSet::new())
+    }
+
+    /// Lock several roots, in a fixed order so waiting instances cannot deadlock
+    ///
+    /// Roots are taken in the order of their canonical paths, which puts
+    /// every ancestor before its descendants; a root inside another one of
+    /// the list is covered by the outer root's lock and not locked again.
+    ///
+    /// # Arguments
+    /// * `roots` - The scanned directories
+    /// * `lock_dir` - Where lock files live
+    /// * `mode` - Whether to fail or wait when one is already locked
+    ///
+    /// # Returns
+    /// The held locks, or the first `AlreadyRunning` error (no lock is kept then)
+    pub fn acquire_all(roots: &[&Path], lock_dir: &Path, mode: LockMode) -> Result<Vec<Self>> {
+        let mut keyed: Vec<(PathBuf, &Path)> = roots.iter().map(|root| (canonical(root), *root)).collect();
+        keyed.sort_by(|a, b| a.0.cmp(&b.0));
+        keyed.dedup_by(|a, b| a.0 == b.0);
+
+        let mut locked = HashSet::new();
+        let mut covered: Vec<PathBuf> = Vec::new();
+        let mut locks = Vec::new();
+        for (key, root) in keyed {
+            if covered.iter().any(|outer| key.starts_with(outer)) {
+                continue;
+            }
+            locks.push(Self::acquire_tree(root, &key, lock_dir, mode, &mut locked)?);
+            covered.push(key);
+        }
+        Ok(locks)
+    }
+
+    /// Lock a root exclusively after its ancestors shared
+    ///
+    /// A run cleaning `~` then excludes one cleaning `~/work` (which holds
+    /// `~` shared) and the other way round, while runs cleaning `~/work` and
+    /// `~/other` share their ancestors' locks and go on side by side.
+    ///
+    /// # Arguments
+    /// * `root` - The scanned directory, as given
+    /// * `key` - Its canonical path
+    /// * `lock_dir` - Where lock files live
+    /// * `mode` - Whether to fail or wait when it is already locked
+    /// * `locked` - Ancestors the run already holds shared, extended with the new ones
+    fn acquire_tree(root: &Path, key: &Path, lock_dir: &Path, mode: LockMode, locked: &mut HashSet<PathBuf>) -> Result<Self> {
+        fs::create_dir_all(lock_dir)?;
+        let mut files = Vec::new();
+
+        let mut ancestors: Vec<&Path> = key.ancestors().skip(1).collect();
+        ancestors.reverse();
+        for ancestor in ancestors {
+            if !locked.insert(ancestor.to_path_buf()) {
+                continue;
+            }
+            let mut file = open_lock_file(ancestor, lock_dir)?;
+            if !try_lock_shared(&file, mode)? {
+                return Err(already_running(ancestor, &mut file));
+            }
+            files.push(file);
+        }
+
+        let mut file = open_lock_file(key, lock_dir)?;
+        if !try_lock(&file, mode)? {
+            return Err(already_running(root, &mut file));
+        }
+
+        // Only the exclusive holder writes, so the pid read by others is whole
+        file.set_len(0)?;
+        file.rewind()?;
+        write!(file, "{}", std::process::id())?;
+        files.push(file);
+        Ok(Self { root: root.to_path_buf(), files })
+    }
+
+    /// Get the locked root
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl Drop for RunLock {
+    fn drop(&mut self) {
+        // A run waiting on an ancestor's shared lock must not report this pid afterwards
+        if let Some(file) = self.files.last() {
+            let _ = file.set_len(0);
+        }
+    }
+}
+
+/// Get the path a root is locked under, so any spelling of it matches
+fn canonical(root: &Path) -> PathBuf {
+    root.canonicalize().unwrap_or_else(|_| root.to_path_buf())
+}
+
+/// Get the lock file of a canonical path: a hash of it, so any path length fits
+fn lock_path(key: &Path, lock_dir: &Path) -> PathBuf {
+    let mut hasher = Sha256::new();
+    hasher.update(key.as_os_str().as_encoded_bytes());
+    let digest: String = hasher.finalize().iter().take(8).map(|byte| format!("{:02x}", byte)).collect();
+    lock_dir.join(format!("{}.lock", digest))
+}
+
+/// Open the lock file of a canonical path without truncating the holder's pid
+fn open_lock_file(key: &Path, lock_dir: &Path) -> Result<File> {
+    Ok(OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(lock_path(key, lock_dir))?)
+}
+
+/// Build the error naming the instance holding a lock file
+fn already_running(root: &Path, file: &mut File) -> VenvCleanerError {
+    let mut holder = String::new();
+    let _ = file.read_to_string(&mut holder);
+    VenvCleanerError::AlreadyRunning {
+        root: root.display().to_string(),
+        pid: holder.trim().parse().ok(),
+    }
+}
+
+/// Take an exclusive lock on the file
+///
+/// # Returns
+/// False if another process holds it and `mode` is `FailFast`
+pub(crate) fn try_lock(file: &File, mode: LockMode) -> Result<bool> {
+    lock_file(file, mode, true)
+}
+
+/// Take a shared lock on the file, as held on the ancestors of a locked root
+///
+/// # Returns
+/// False if another process holds it exclusively and `mode` is `FailFast`
+fn try_lock_shared(file: &File, mode: LockMode) -> Result<bool> {
+    lock_file(file, mode, false)
+}
+
+/// `flock` the file exclusively or shared
+#[cfg(unix)]
+fn lock_file(file: &File, mode: LockMode, exclusive: bool) -> Result<bool> {
+    use std::os::unix::io::AsRawFd;
+
+    let kind = if exclusive { libc::LOCK_EX } else { libc::LOCK_SH };
+    let operation = match mode {
+        LockMode::FailFast => kind | libc::LOCK_NB,
+        LockMode::Wait => kind,
+    };
+    loop {
+        // SAFETY: the descriptor stays open for the duration of the call
+        if unsafe { libc::flock(file.as_raw_fd(), operation) } == 0 {
+            return Ok(true);
+        }
+        let error = std::io::Error::last_os_error();
+        match error.kind() {
+            std::io::ErrorKind::Interrupted => continue,
+            std::io::ErrorKind::WouldBlock => return Ok(false),
+            _ => return Err(error.into()),
+        }
+    }
+}
+
+/// Platforms without `flock` run unlocked
+#[cfg(not(unix))]
+fn lock_file(_file: &File, _mode: LockMode, _exclusive: bool) -> Result<bool> {
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_second_lock_names_the_holder() {
+        let temp_dir = TempDir::new().unwrap();
+        let (root, lock_dir) = (temp_dir.path().join("home"), temp_dir.path().join("locks"));
+        fs::create_dir(&root).unwrap();
+
+        let lock = RunLock::acquire(&root, &lock_dir, LockMode::FailFast).unwrap();
+        assert_eq!(lock.root(), root);
+        // A differently spelled path to the same root shares the lock
+        let spelled = root.join("..").join("home");
+        match RunLock::acquire(&spelled, &lock_dir, LockMode::FailFast) {
+            Err(VenvCleanerError::AlreadyRunning { pid, .. }) => assert_eq!(pid, Some(std::process::id())),
+            other => panic!("expected AlreadyRunning, got {:?}", other),
+        }
+
+        drop(lock);
+        assert!(RunLock::acquire(&root, &lock_dir, LockMode::FailFast).is_ok());
+    }
+
+    #[test]
+    fn test_acquire_all_locks_each_root_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let (a, b) = (temp_dir.path().join("a"), temp_dir.path().join("b"));
+        fs::create_dir(&a).unwrap();
+        fs::create_dir(&b).unwrap();
+        let lock_dir = temp_dir.path().join("locks");
+
+        let locks = RunLock::acquire_all(&[&b, &a, &b], &lock_dir, LockMode::FailFast).unwrap();
+        let roots: Vec<&Path> = locks.iter().map(RunLock::root).collect();
+        assert_eq!(roots, vec![a.as_path(), b.as_path()]);
+        assert_ne!(lock_path(&canonical(&a), &lock_dir), lock_path(&canonical(&b), &lock_dir));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_nested_roots_exclude_each_other() {
+        let temp_dir = TempDir::new().unwrap();
+        let (home, work, other) = (temp_dir.path().join("home"), temp_dir.path().join("home/work"), temp_dir.path().join("home/other"));
+        fs::create_dir_all(&work).unwrap();
+        fs::create_dir_all(&other).unwrap();
+        let lock_dir = temp_dir.path().join("locks");
+
+        // Cleaning inside a locked root, or around one, waits for it
+        let lock = RunLock::acquire(&home, &lock_dir, LockMode::FailFast).unwrap();
+        assert!(matches!(RunLock::acquire(&work, &lock_dir, LockMode::FailFast), Err(VenvCleanerError::AlreadyRunning { .. })));
+        drop(lock);
+        let lock = RunLock::acquire(&work, &lock_dir, LockMode::FailFast).unwrap();
+        assert!(matches!(RunLock::acquire(&home, &lock_dir, LockMode::FailFast), Err(VenvCleanerError::AlreadyRunning { .. })));
+
+        // Siblings share their ancestors
+        let sibling = RunLock::acquire(&other, &lock_dir, LockMode::FailFast).unwrap();
+        drop((lock, sibling));
+
+        // One run listing both keeps only the outer root, found by its canonical path
+        let spelled = work.join("..").join("..").join("home");
+        let locks = RunLock::acquire_all(&[&work, &spelled], &lock_dir, LockMode::FailFast).unwrap();
+        let roots: Vec<&Path> = locks.iter().map(RunLock::root).collect();
+        assert_eq!(roots, vec![spelled.as_path()]);
+    }
+}
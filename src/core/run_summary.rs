@@ -0,0 +1,210 @@
+//! Summary of a run written as JSON for auditing
+//!
+//! Nightly cron cleanups usually run with `--force` and their output lands
+//! in a mail nobody reads. With `--summary-json PATH` the CLI writes the
+//! final counts, bytes freed and every failed path to a JSON file instead,
+//! whatever the output mode, so runs on many machines can be collected and
+//! audited centrally. The file is replaced atomically, so a collector never
+//! reads half of it.
+//!
+//! Each .venv the run deleted or kept is listed with the reason, such as
+//! "age 142d >= 120d AND size 2.10 GB >= 1.00 GB (--force)", so automation
+//! that deleted too much or too little can be debugged from the file alone.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::{scan_metadata, timestamps, Result, VenvCleanerError};
+
+/// A .venv directory that could not be deleted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SummaryError {
+    /// Full path to the .venv directory
+    pub path: String,
+    /// Why it was not deleted
+    pub error: String,
+}
+
+/// What a run did with one .venv directory it found, and why
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SummaryDecision {
+    /// Full path to the .venv directory
+    pub path: String,
+    /// `deleted` or `kept`
+    pub action: String,
+    /// The rule or answer that decided it
+    pub reason: String,
+}
+
+/// The outcome of one run
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RunSummary {
+    /// VenvCleaner version that made the run
+    version: String,
+    /// Name of the machine the run was on
+    host: String,
+    /// When the run started (RFC 3339)
+    started_at: String,
+    /// When the summary was completed (RFC 3339)
+    finished_at: String,
+    /// Directories searched
+    roots: Vec<String>,
+    /// Whether the run only listed .venv directories
+    query: bool,
+    /// Whether deletions were only simulated
+    dry_run: bool,
+    /// Number of .venv directories found
+    found: usize,
+    /// Number of .venv directories deleted
+    deleted: usize,
+    /// Total bytes freed
+    freed_bytes: u64,
+    /// The deletions that failed, with their paths
+    errors: Vec<SummaryError>,
+    /// Why each .venv was deleted or kept
+    decisions: Vec<SummaryDecision>,
+}
+
+impl RunSummary {
+    /// Start the summary of a run
+    ///
+    /// # Arguments
+    /// * `started` - When the run started
+    /// * `roots` - The directories searched
+    /// * `query` - Whether the run only lists .venv directories
+    /// * `dry_run` - Whether deletions are only simulated
+    pub fn new(started: DateTime<Utc>, roots: &[&Path], query: bool, dry_run: bool) -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            host: scan_metadata::host_name(),
+            started_at: timestamps::to_rfc3339(&started),
+            finished_at: timestamps::to_rfc3339(&started),
+            roots: roots.iter().map(|root| root.display().to_string()).collect(),
+            query,
+            dry_run,
+            found: 0,
+            deleted: 0,
+            freed_bytes: 0,
+            errors: Vec::new(),
+            decisions: Vec::new(),
+        }
+    }
+
+    /// Set the number of .venv directories found
+    pub fn with_found(mut self, found: usize) -> Self {
+        self.found = found;
+        self
+    }
+
+    /// Count a deleted .venv
+    ///
+    /// # Arguments
+    /// * `freed_bytes` - Bytes its deletion freed
+    pub fn record_deleted(&mut self, freed_bytes: u64) {
+        self.deleted += 1;
+        self.freed_bytes += freed_bytes;
+    }
+
+    /// Record a .venv that could not be deleted
+    pub fn record_error(&mut self, path: impl Into<String>, error: &VenvCleanerError) {
+        self.errors.push(SummaryError { path: path.into(), error: error.to_string() });
+    }
+
+    /// Record why a .venv was deleted or kept
+    ///
+    /// # Arguments
+    /// * `path` - Full path to the .venv directory
+    /// * `deleted` - Whether it was deleted
+    /// * `reason` - The rule or answer that decided it
+    pub fn record_decision(&mut self, path: impl Into<String>, deleted: bool, reason: impl Into<String>) {
+        let action = if deleted { "deleted" } else { "kept" };
+        self.decisions.push(SummaryDecision { path: path.into(), action: action.to_string(), reason: reason.into() });
+    }
+
+    /// Get why each .venv was deleted or kept
+    pub fn decisions(&self) -> &[SummaryDecision] {
+        &self.decisions
+    }
+
+    /// Get the number of .venv directories deleted
+    pub fn deleted(&self) -> usize {
+        self.deleted
+    }
+
+    /// Get the total bytes freed
+    pub fn freed_bytes(&self) -> u64 {
+        self.freed_bytes
+    }
+
+    /// Get the deletions that failed
+    pub fn errors(&self) -> &[SummaryError] {
+        &self.errors
+    }
+
+    /// Write the summary, stamped with the current time, replacing the file atomically
+    ///
+    /// # Arguments
+    /// * `path` - The file to write
+    pub fn write_to(&self, path: &Path) -> Result<()> {
+        let mut summary = self.clone();
+        summary.finished_at = timestamps::to_rfc3339(&Utc::now());
+        let contents = serde_json::to_string_pretty(&summary)
+            .map_err(|e| VenvCleanerError::Io(format!("Failed to encode run summary: {}", e)))?;
+
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let mut partial = path.as_os_str().to_owned();
+        partial.push(".tmp");
+        let partial = PathBuf::from(partial);
+        fs::write(&partial, contents + "\n")?;
+        fs::rename(&partial, path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_record_and_write() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut summary = RunSummary::new(Utc::now(), &[Path::new("/srv/projects")], false, false).with_found(3);
+        summary.record_deleted(1024);
+        summary.record_deleted(2048);
+        summary.record_decision("/srv/projects/old/.venv", true, "age 142d >= 120d (--force)");
+        summary.record_error("/srv/projects/locked/.venv", &VenvCleanerError::PermissionDenied {
+            path: "/srv/projects/locked/.venv".to_string(),
+        });
+        assert_eq!(summary.deleted(), 2);
+
+        let path = temp_dir.path().join("audit").join("summary.json");
+        summary.write_to(&path).unwrap();
+        let written: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written["found"], 3);
+        assert_eq!(written["deleted"], 2);
+        assert_eq!(written["freed_bytes"], 3072);
+        assert_eq!(written["roots"][0], "/srv/projects");
+        assert_eq!(written["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(written["errors"][0]["path"], "/srv/projects/locked/.venv");
+        assert_eq!(written["decisions"][0]["action"], "deleted");
+        assert_eq!(written["decisions"][0]["reason"], "age 142d >= 120d (--force)");
+        assert!(!temp_dir.path().join("audit").join("summary.json.tmp").exists());
+    }
+
+    #[test]
+    fn test_write_replaces_previous_summary() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("summary.json");
+        fs::write(&path, "old").unwrap();
+
+        RunSummary::new(Utc::now(), &[], true, true).write_to(&path).unwrap();
+        let written: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written["query"], true);
+        assert_eq!(written["errors"].as_array().unwrap().len(), 0);
+    }
+}
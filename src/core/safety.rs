@@ -0,0 +1,244 @@
+//! Guards against cleaning the operating system by mistake
+//!
+//! A recursive force run as root over `/` or `/home` touches every user's
+//! work without a single prompt, so it needs `--i-know-what-im-doing`.
+//! Whoever runs VenvCleaner, the system directories `/usr` and `/etc` are
+//! never searched: environments there belong to the distribution.
+//!
+//! Deletion itself refuses the locations of system and framework Pythons,
+//! such as `/usr/lib/python3*` or macOS's `Python.framework`, even when a
+//! path there is handed over directly or looks like a virtual environment.
+//!
+//! A scan started from inside a virtual environment or `site-packages`, say
+//! from a shell left in `.venv/lib/python3.12`, finds the environments that
+//! packages bundle as test data. Such roots are pointed out before scanning.
+//!
+//! No virtual environment grows to hundreds of gigabytes; a match that large
+//! is almost surely a data folder that happens to look like one. Matches above
+//! `--exclude-size-above` (200 GB unless set) are never deleted automatically,
+//! only after a person has confirmed them.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+
+use super::ByteSize;
+
+/// Directories never searched for .venv directories
+pub const SYSTEM_PATHS: &[&str] = &["/usr", "/etc"];
+
+/// Roots a recursive force run as root must be confirmed for
+pub const DANGEROUS_ROOTS: &[&str] = &["/", "/home"];
+
+/// Size above which a match is taken for a misdetected data directory, unless `--exclude-size-above` says otherwise
+pub const DEFAULT_EXCLUDE_SIZE_ABOVE: ByteSize = ByteSize::new(200 * ByteSize::GB);
+
+/// Locations of system Pythons that are never deleted, as globs
+pub const PROTECTED_PYTHON_PATHS: &[&str] = &[
+    "/usr/lib/python*",
+    "/usr/lib64/python*",
+    "/usr/local/lib/python*",
+    "/usr/share/python*",
+    "/usr/bin",
+    "/usr/local/bin",
+    "/Library/Frameworks/Python.framework",
+    "/System/Library/Frameworks/Python.framework",
+    "/Library/Developer/CommandLineTools/Library/Frameworks/Python3.framework",
+    "/Applications/Xcode.app/Contents/Developer/Library/Frameworks/Python3.framework",
+    "/opt/homebrew/Frameworks/Python.framework",
+    "/opt/homebrew/Cellar/python*",
+    "/usr/local/Cellar/python*",
+    "/usr/local/Frameworks/Python.framework",
+];
+
+/// The compiled protected Python locations
+static PROTECTED_GLOBS: OnceLock<GlobSet> = OnceLock::new();
+
+/// Compile the protected Python locations, with `*` staying within one path component
+fn protected_globs() -> &'static GlobSet {
+    PROTECTED_GLOBS.get_or_init(|| {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in PROTECTED_PYTHON_PATHS {
+            if let Ok(glob) = GlobBuilder::new(pattern).literal_separator(true).build() {
+                builder.add(glob);
+            }
+        }
+        builder.build().unwrap_or_else(|_| GlobSet::empty())
+    })
+}
+
+/// Find the protected Python location a path is in
+///
+/// Both the path as given and, if it exists, its resolved form are checked,
+/// so a symlink into a system Python is caught too.
+///
+/// # Arguments
+/// * `path` - The directory about to be deleted
+///
+/// # Returns
+/// The protected location containing the path, or None if it may be deleted
+pub fn protected_python_location(path: &Path) -> Option<PathBuf> {
+    let resolved = fs::canonicalize(path).ok();
+    std::iter::once(path)
+        .chain(resolved.as_deref())
+        .flat_map(Path::ancestors)
+        .find(|ancestor| protected_globs().is_match(ancestor))
+        .map(Path::to_path_buf)
+}
+
+/// Check whether a path is inside a system directory
+///
+/// The check is on the path as given: scans do not follow symlinks, so it
+/// is cheap enough to run for every directory walked.
+///
+/// # Arguments
+/// * `path` - The path to check
+pub fn is_system_path(path: &Path) -> bool {
+    SYSTEM_PATHS.iter().any(|system| path.starts_with(system))
+}
+
+/// Find the system directory a path is in
+///
+/// Unlike `is_system_path`, the resolved form of the path is checked as
+/// well, so a symlink into a system directory is caught before deleting.
+///
+/// # Arguments
+/// * `path` - The directory about to be deleted
+///
+/// # Returns
+/// The system directory containing the path, or None if it is outside them all
+pub fn system_location(path: &Path) -> Option<PathBuf> {
+    let resolved = fs::canonicalize(path).ok();
+    std::iter::once(path)
+        .chain(resolved.as_deref())
+        .find_map(|candidate| SYSTEM_PATHS.iter().find(|system| candidate.starts_with(system)))
+        .map(PathBuf::from)
+}
+
+/// Find the system directories a scan of a root would walk into
+///
+/// The directories are returned as paths below the root as given, so they
+/// can be compared with the walked paths even for relative roots.
+///
+/// # Arguments
+/// * `root` - The directory to be scanned
+///
+/// # Returns
+/// The system directories below the root, or the root itself when it lies inside one
+pub fn system_paths_below(root: &Path) -> Vec<PathBuf> {
+    let Ok(canonical) = fs::canonicalize(root) else {
+        return Vec::new();
+    };
+    if is_system_path(&canonical) {
+        return vec![root.to_path_buf()];
+    }
+    SYSTEM_PATHS
+        .iter()
+        .filter_map(|system| Path::new(system).strip_prefix(&canonical).ok())
+        .map(|relative| root.join(relative))
+        .collect()
+}
+
+/// Find the virtual environment or `site-packages` directory a scan root lies in
+///
+/// # Arguments
+/// * `root` - The directory to be scanned
+///
+/// # Returns
+/// The innermost directory with a `pyvenv.cfg` or named `site-packages`
+/// containing the root (or the root itself), or None
+pub fn enclosing_environment(root: &Path) -> Option<PathBuf> {
+    let canonical = fs::canonicalize(root).ok()?;
+    canonical
+        .ancestors()
+        .find(|ancestor| {
+            ancestor.file_name().is_some_and(|name| name == "site-packages") || ancestor.join("pyvenv.cfg").is_file()
+        })
+        .map(Path::to_path_buf)
+}
+
+/// Find the scan roots a recursive force run as root must be confirmed for
+///
+/// # Arguments
+/// * `roots` - The directories to be scanned
+///
+/// # Returns
+/// The roots that are `/` or `/home` once resolved
+pub fn dangerous_roots(roots: &[&Path]) -> Vec<PathBuf> {
+    roots
+        .iter()
+        .map(|root| fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf()))
+        .filter(|root| DANGEROUS_ROOTS.iter().any(|dangerous| root == Path::new(dangerous)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_paths() {
+        assert!(is_system_path(Path::new("/usr/lib/python3/dist-packages/.venv")));
+        assert!(is_system_path(Path::new("/etc")));
+        assert!(!is_system_path(Path::new("/usrlocal/.venv")));
+        assert!(!is_system_path(Path::new("/home/ana/app/.venv")));
+        assert_eq!(system_location(Path::new("/etc/app/.venv")), Some(PathBuf::from("/etc")));
+        assert_eq!(system_location(Path::new("/home/ana/app/.venv")), None);
+    }
+
+    #[test]
+    fn test_system_paths_below_root() {
+        assert_eq!(system_paths_below(Path::new("/")), vec![PathBuf::from("/usr"), PathBuf::from("/etc")]);
+        assert_eq!(system_paths_below(Path::new("/usr/lib")), vec![PathBuf::from("/usr/lib")]);
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        assert!(system_paths_below(temp_dir.path()).is_empty());
+    }
+
+    #[test]
+    fn test_protected_python_locations() {
+        assert_eq!(
+            protected_python_location(Path::new("/usr/lib/python3.11/venv/.venv")),
+            Some(PathBuf::from("/usr/lib/python3.11"))
+        );
+        assert_eq!(
+            protected_python_location(Path::new("/Library/Frameworks/Python.framework/Versions/3.12")),
+            Some(PathBuf::from("/Library/Frameworks/Python.framework"))
+        );
+        assert!(protected_python_location(Path::new("/usr/lib/pythonic-app/.venv")).is_some());
+        assert!(protected_python_location(Path::new("/usr/lib/x/python3/.venv")).is_none());
+        assert!(protected_python_location(Path::new("/home/ana/app/.venv")).is_none());
+
+        // A symlink into a protected location is caught by its target
+        #[cfg(unix)]
+        if Path::new("/usr/bin").exists() {
+            let temp_dir = tempfile::TempDir::new().unwrap();
+            let link = temp_dir.path().join("bin");
+            std::os::unix::fs::symlink("/usr/bin", &link).unwrap();
+            assert_eq!(protected_python_location(&link), Some(PathBuf::from("/usr/bin")));
+        }
+    }
+
+    #[test]
+    fn test_enclosing_environment() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let venv = temp_dir.path().join("app").join(".venv");
+        let packages = venv.join("lib").join("python3.12").join("site-packages");
+        fs::create_dir_all(packages.join("numpy")).unwrap();
+        fs::write(venv.join("pyvenv.cfg"), "home = /usr/bin\n").unwrap();
+
+        let venv = fs::canonicalize(venv).unwrap();
+        assert_eq!(enclosing_environment(&venv.join("lib")), Some(venv.clone()));
+        assert_eq!(enclosing_environment(&venv.join("lib/python3.12/site-packages/numpy")), Some(venv.join("lib/python3.12/site-packages")));
+        assert_eq!(enclosing_environment(&venv), Some(venv));
+        assert_eq!(enclosing_environment(&temp_dir.path().join("app")), None);
+    }
+
+    #[test]
+    fn test_dangerous_roots() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let roots = [Path::new("/"), temp_dir.path()];
+        assert_eq!(dangerous_roots(&roots), vec![PathBuf::from("/")]);
+        assert!(dangerous_roots(&[temp_dir.path()]).is_empty());
+    }
+}
@@ -0,0 +1,238 @@
+//! Checkpoints letting an interrupted scan resume
+//!
+//! Scanning a large network share can take hours. While a recursive scan
+//! walks, the top-level directories it has finished are written to a
+//! checkpoint file under the user cache directory every minute, together
+//! with the .venv directories found in them. If the scan is interrupted,
+//! `--resume` skips the finished directories and only sizes the .venv
+//! directories recorded for them again, walking the rest of the tree as
+//! usual. A scan that completes removes its checkpoint.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use super::{paths, Result, VenvCleanerError};
+
+/// How often a scan writes its checkpoint
+pub const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Checkpoints older than this, in days, are not resumed from
+pub const MAX_CHECKPOINT_AGE_DAYS: i64 = 7;
+
+/// The progress of a scan of one root
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanCheckpoint {
+    /// The scanned root
+    pub root: PathBuf,
+    /// Whether the walk honoured .gitignore rules, which changes what it finds
+    pub respect_gitignore: bool,
+    /// When the checkpoint was written
+    pub updated: DateTime<Utc>,
+    /// Finished top-level directories, with the .venv directories found in each
+    pub completed: BTreeMap<PathBuf, Vec<PathBuf>>,
+}
+
+impl ScanCheckpoint {
+    /// Create an empty checkpoint for a root
+    ///
+    /// # Arguments
+    /// * `root` - The scanned root
+    /// * `respect_gitignore` - Whether the walk honours .gitignore rules
+    pub fn new(root: PathBuf, respect_gitignore: bool) -> Self {
+        Self { root, respect_gitignore, updated: Utc::now(), completed: BTreeMap::new() }
+    }
+
+    /// Get the checkpoint file of a root in a directory
+    ///
+    /// # Arguments
+    /// * `dir` - The directory holding checkpoints
+    /// * `root` - The scanned root, turned into the file name
+    pub fn path_in(dir: &Path, root: &Path) -> PathBuf {
+        let name: String = root
+            .to_string_lossy()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '.' { c } else { '_' })
+            .collect();
+        dir.join(format!("{}.json", name.trim_start_matches('_')))
+    }
+
+    /// Get the default directory holding checkpoints
+    pub fn default_dir() -> Option<PathBuf> {
+        paths::cache_dir().map(|dir| dir.join("checkpoints"))
+    }
+
+    /// Load the checkpoint of a root, if one can be resumed from
+    ///
+    /// A checkpoint of another root or walk mode, or one older than
+    /// `MAX_CHECKPOINT_AGE_DAYS`, is ignored.
+    ///
+    /// # Arguments
+    /// * `path` - The checkpoint file
+    /// * `root` - The root being scanned
+    /// * `respect_gitignore` - Whether the walk honours .gitignore rules
+    pub fn load(path: &Path, root: &Path, respect_gitignore: bool) -> Option<Self> {
+        let contents = fs::read_to_string(path).ok()?;
+        let checkpoint: Self = match serde_json::from_str(&contents) {
+            Ok(checkpoint) => checkpoint,
+            Err(e) => {
+                warn!("Ignoring unreadable scan checkpoint {}: {}", path.display(), e);
+                return None;
+            }
+        };
+        if checkpoint.root != root || checkpoint.respect_gitignore != respect_gitignore {
+            info!("Not resuming: the checkpoint {} is of a different scan", path.display());
+            return None;
+        }
+        if Utc::now() - checkpoint.updated > chrono::Duration::days(MAX_CHECKPOINT_AGE_DAYS) {
+            info!("Not resuming: the checkpoint {} is older than {} days", path.display(), MAX_CHECKPOINT_AGE_DAYS);
+            return None;
+        }
+        Some(checkpoint)
+    }
+
+    /// Get the .venv directories found in the finished directories
+    pub fn venvs(&self) -> impl Iterator<Item = &PathBuf> {
+        self.completed.values().flatten()
+    }
+
+    /// Write the checkpoint, creating its directory if needed
+    ///
+    /// # Arguments
+    /// * `path` - The checkpoint file
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| VenvCleanerError::Io(e.to_string()))?;
+        // Written aside first, so an interruption mid-write keeps the previous checkpoint
+        let partial = path.with_extension("json.partial");
+        fs::write(&partial, json + "\n")?;
+        fs::rename(&partial, path)?;
+        Ok(())
+    }
+}
+
+/// Records a walk's finished top-level directories and writes them out periodically
+#[derive(Debug)]
+pub struct ScanCheckpointer {
+    /// The checkpoint file
+    path: PathBuf,
+    /// The progress so far, including any resumed checkpoint
+    checkpoint: ScanCheckpoint,
+    /// The top-level directory being walked, with the .venv directories found in it
+    current: Option<(PathBuf, Vec<PathBuf>)>,
+    /// When the checkpoint was last written, or the walk started
+    last_saved: Instant,
+    /// How often the checkpoint is written
+    interval: Duration,
+}
+
+impl ScanCheckpointer {
+    /// Start recording a walk
+    ///
+    /// # Arguments
+    /// * `path` - The checkpoint file
+    /// * `checkpoint` - The resumed checkpoint, or an empty one
+    /// * `interval` - How often the checkpoint is written
+    pub fn new(path: PathBuf, checkpoint: ScanCheckpoint, interval: Duration) -> Self {
+        Self { path, checkpoint, current: None, last_saved: Instant::now(), interval }
+    }
+
+    /// Note that the walk entered a top-level directory, finishing the previous one
+    ///
+    /// # Arguments
+    /// * `dir` - The directory entered
+    pub fn enter(&mut self, dir: &Path) {
+        if let Some((finished, venvs)) = self.current.replace((dir.to_path_buf(), Vec::new())) {
+            self.checkpoint.completed.insert(finished, venvs);
+        }
+        if self.last_saved.elapsed() >= self.interval {
+            self.save();
+        }
+    }
+
+    /// Note a .venv found in the current top-level directory
+    ///
+    /// A .venv directly in the root belongs to no top-level directory and is
+    /// found again on resume anyway.
+    pub fn found_venv(&mut self, venv_path: &Path) {
+        if let Some((_, venvs)) = &mut self.current {
+            venvs.push(venv_path.to_path_buf());
+        }
+    }
+
+    /// Write the finished directories now
+    pub fn save(&mut self) {
+        self.checkpoint.updated = Utc::now();
+        match self.checkpoint.save(&self.path) {
+            Ok(()) => debug!(
+                "Checkpointed {} finished directories to {}",
+                self.checkpoint.completed.len(),
+                self.path.display()
+            ),
+            Err(e) => warn!("Could not write the scan checkpoint {}: {}", self.path.display(), e),
+        }
+        self.last_saved = Instant::now();
+    }
+
+    /// End the walk, removing the checkpoint if it ran to completion
+    ///
+    /// # Arguments
+    /// * `completed` - Whether the walk finished, rather than being cancelled
+    pub fn finish(mut self, completed: bool) {
+        if !completed {
+            self.save();
+        } else if self.path.exists() {
+            if let Err(e) = fs::remove_file(&self.path) {
+                warn!("Could not remove the scan checkpoint {}: {}", self.path.display(), e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_checkpointer_records_finished_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = PathBuf::from("/srv/share");
+        let path = ScanCheckpoint::path_in(temp_dir.path(), &root);
+        assert!(path.ends_with("srv_share.json"));
+
+        let mut checkpointer = ScanCheckpointer::new(path.clone(), ScanCheckpoint::new(root.clone(), false), Duration::ZERO);
+        checkpointer.enter(&root.join("alice"));
+        checkpointer.found_venv(&root.join("alice/app/.venv"));
+        checkpointer.enter(&root.join("bob"));
+        checkpointer.found_venv(&root.join("bob/.venv"));
+        checkpointer.finish(false);
+
+        // Only the directories the walk moved past are finished
+        let checkpoint = ScanCheckpoint::load(&path, &root, false).unwrap();
+        assert_eq!(checkpoint.completed.keys().collect::<Vec<_>>(), vec![&root.join("alice")]);
+        assert_eq!(checkpoint.venvs().collect::<Vec<_>>(), vec![&root.join("alice/app/.venv")]);
+        assert!(ScanCheckpoint::load(&path, &root, true).is_none());
+        assert!(ScanCheckpoint::load(&path, Path::new("/srv/other"), false).is_none());
+
+        ScanCheckpointer::new(path.clone(), checkpoint, CHECKPOINT_INTERVAL).finish(true);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_stale_checkpoint_is_ignored() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = PathBuf::from("/srv/share");
+        let path = ScanCheckpoint::path_in(temp_dir.path(), &root);
+        let mut checkpoint = ScanCheckpoint::new(root.clone(), false);
+        checkpoint.updated = Utc::now() - chrono::Duration::days(MAX_CHECKPOINT_AGE_DAYS + 1);
+        checkpoint.save(&path).unwrap();
+        assert!(ScanCheckpoint::load(&path, &root, false).is_none());
+    }
+}
@@ -0,0 +1,210 @@
+//! Comparison of two saved scans
+//!
+//! A scan is saved as any report `merge` reads: `--query --progress json`
+//! output or a JSON export such as `--output json`. Comparing a scan taken
+//! before a cleanup campaign with one taken afterwards shows which
+//! environments were removed, which are new and which have grown, so the
+//! effect of the campaign can be verified.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{report_merge, Result, VenvCleanerError};
+
+/// The .venv directories of one saved scan, by path
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScanSnapshot {
+    /// Size in bytes of each .venv found
+    venvs: BTreeMap<PathBuf, u64>,
+}
+
+impl ScanSnapshot {
+    /// Parse a saved scan, in any format `merge` reads
+    ///
+    /// # Arguments
+    /// * `text` - The saved output
+    ///
+    /// # Returns
+    /// The snapshot, or an error if the scan is not valid JSON
+    pub fn from_report(text: &str) -> Result<Self> {
+        let venvs = report_merge::parse_report("", text)?
+            .into_iter()
+            .map(|venv| (venv.path, venv.size_bytes))
+            .collect();
+        Ok(Self { venvs })
+    }
+
+    /// Load a snapshot from a saved scan
+    ///
+    /// # Arguments
+    /// * `path` - The saved scan
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path).map_err(|e| VenvCleanerError::PathError {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        })?;
+        Self::from_report(&text).map_err(|e| VenvCleanerError::PathError {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        })
+    }
+
+    /// Get the number of .venv directories in the snapshot
+    pub fn len(&self) -> usize {
+        self.venvs.len()
+    }
+
+    /// Check whether the snapshot holds no .venv directories
+    pub fn is_empty(&self) -> bool {
+        self.venvs.is_empty()
+    }
+
+    /// Get the total size of the .venv directories in the snapshot
+    pub fn total_bytes(&self) -> u64 {
+        self.venvs.values().sum()
+    }
+}
+
+/// A .venv present in both scans that got bigger
+#[derive(Debug, Clone, PartialEq)]
+pub struct GrownVenv {
+    /// Path to the .venv directory
+    pub path: PathBuf,
+    /// Size in the earlier scan
+    pub before_bytes: u64,
+    /// Size in the later scan
+    pub after_bytes: u64,
+}
+
+impl GrownVenv {
+    /// Get how many bytes the .venv grew by
+    pub fn growth_bytes(&self) -> u64 {
+        self.after_bytes - self.before_bytes
+    }
+}
+
+/// Differences between two scans, each list sorted by path
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScanDiff {
+    /// .venv directories only in the later scan, with their size
+    pub added: Vec<(PathBuf, u64)>,
+    /// .venv directories only in the earlier scan, with their size
+    pub removed: Vec<(PathBuf, u64)>,
+    /// .venv directories in both scans that got bigger
+    pub grown: Vec<GrownVenv>,
+}
+
+impl ScanDiff {
+    /// Compare two scans
+    ///
+    /// # Arguments
+    /// * `before` - The earlier scan
+    /// * `after` - The later scan
+    pub fn between(before: &ScanSnapshot, after: &ScanSnapshot) -> Self {
+        let mut diff = Self::default();
+
+        for (path, &size) in &before.venvs {
+            match after.venvs.get(path) {
+                None => diff.removed.push((path.clone(), size)),
+                Some(&after_size) if after_size > size => diff.grown.push(GrownVenv {
+                    path: path.clone(),
+                    before_bytes: size,
+                    after_bytes: after_size,
+                }),
+                Some(_) => {}
+            }
+        }
+        for (path, &size) in &after.venvs {
+            if !before.venvs.contains_key(path) {
+                diff.added.push((path.clone(), size));
+            }
+        }
+
+        diff
+    }
+
+    /// Check whether the scans found the same .venv directories with no growth
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.grown.is_empty()
+    }
+
+    /// Get the space held by the removed .venv directories
+    pub fn removed_bytes(&self) -> u64 {
+        self.removed.iter().map(|(_, size)| size).sum()
+    }
+
+    /// Get the space held by the added .venv directories
+    pub fn added_bytes(&self) -> u64 {
+        self.added.iter().map(|(_, size)| size).sum()
+    }
+
+    /// Get the total growth of the .venv directories present in both scans
+    pub fn grown_bytes(&self) -> u64 {
+        self.grown.iter().map(GrownVenv::growth_bytes).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const BEFORE: &str = r#"{"event":"scan_started","root":"/work","recursive":true}
+{"event":"venv_found","path":"/work/a/.venv","size_bytes":100,"size_partial":false,"last_modified":"2024-01-01T00:00:00+00:00","python_version":null,"editable_install":false}
+{"event":"venv_found","path":"/work/b/.venv","size_bytes":200,"size_partial":false,"last_modified":"2024-01-01T00:00:00+00:00","python_version":"3.11","editable_install":false}
+{"event":"venv_found","path":"/work/c/.venv","size_bytes":300,"size_partial":false,"last_modified":"2024-01-01T00:00:00+00:00","python_version":null,"editable_install":false}
+{"event":"summary","found":3,"deleted":0,"freed_bytes":0,"errors":0}
+"#;
+
+    const AFTER: &str = r#"
+{"event":"venv_found","path":"/work/b/.venv","size_bytes":250}
+{"event":"venv_found","path":"/work/c/.venv","size_bytes":280}
+{"event":"venv_found","path":"/work/d/.venv","size_bytes":50}
+"#;
+
+    #[test]
+    fn test_from_report() {
+        let snapshot = ScanSnapshot::from_report(BEFORE).unwrap();
+        assert_eq!(snapshot.len(), 3);
+        assert_eq!(snapshot.total_bytes(), 600);
+
+        let error = ScanSnapshot::from_report("{\"event\":\"venv_found\"}\nnot json").unwrap_err();
+        assert!(error.to_string().contains("Line 2"));
+    }
+
+    #[test]
+    fn test_from_exported_report() {
+        let export = r#"{"scan":{"host":"laptop"},"venvs":[{"path":"/work/b/.venv","size_bytes":250}]}"#;
+        let snapshot = ScanSnapshot::from_report(export).unwrap();
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot.total_bytes(), 250);
+        assert_eq!(ScanSnapshot::from_report(r#"[{"path":"/work/a/.venv","size_bytes":100}]"#).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_diff_between_scans() {
+        let before = ScanSnapshot::from_report(BEFORE).unwrap();
+        let after = ScanSnapshot::from_report(AFTER).unwrap();
+        let diff = ScanDiff::between(&before, &after);
+
+        assert_eq!(diff.removed, vec![(PathBuf::from("/work/a/.venv"), 100)]);
+        assert_eq!(diff.added, vec![(PathBuf::from("/work/d/.venv"), 50)]);
+        assert_eq!(diff.grown.len(), 1);
+        assert_eq!(diff.grown[0].path, PathBuf::from("/work/b/.venv"));
+        assert_eq!(diff.grown_bytes(), 50);
+        assert!(ScanDiff::between(&before, &before).is_empty());
+    }
+
+    #[test]
+    fn test_load_reports_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let saved = temp_dir.path().join("scan.json");
+        fs::write(&saved, AFTER).unwrap();
+        assert_eq!(ScanSnapshot::load(&saved).unwrap().len(), 3);
+
+        let missing = temp_dir.path().join("missing.json");
+        let error = ScanSnapshot::load(&missing).unwrap_err();
+        assert!(error.to_string().contains("missing.json"));
+    }
+}
@@ -0,0 +1,156 @@
+//! What a report was made from
+//!
+//! Reports get archived and compared months later, by which time nobody
+//! remembers which machine, directory or release produced them. Every
+//! report format therefore ends in the scan's metadata: the VenvCleaner
+//! version, the host name, the roots searched, whether the search was
+//! recursive, and when the scan started and finished. The metadata goes
+//! after the rows because the finish time is only known once the last row
+//! is written, which keeps streamed reports (`--low-memory`) identical to
+//! rendered ones.
+
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::timestamps;
+
+/// The scan a report lists the results of
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanMetadata {
+    /// VenvCleaner version that made the scan
+    pub version: String,
+    /// Name of the machine scanned
+    pub host: String,
+    /// Directories searched
+    pub roots: Vec<PathBuf>,
+    /// Whether the search was recursive
+    pub recursive: bool,
+    /// When the scan started; report ages are counted from here
+    pub started: DateTime<Utc>,
+    /// When the scan finished
+    pub finished: DateTime<Utc>,
+}
+
+/// The metadata as written into JSON reports
+#[derive(Serialize)]
+struct MetadataJson<'a> {
+    version: &'a str,
+    host: &'a str,
+    roots: Vec<String>,
+    recursive: bool,
+    started_at: String,
+    finished_at: String,
+    duration_seconds: i64,
+}
+
+impl ScanMetadata {
+    /// Describe a scan of this machine by this build, finishing when it starts
+    ///
+    /// # Arguments
+    /// * `roots` - The directories searched
+    /// * `recursive` - Whether the search is recursive
+    /// * `started` - When the scan started
+    pub fn new(roots: &[&Path], recursive: bool, started: DateTime<Utc>) -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            host: host_name(),
+            roots: roots.iter().map(|root| root.to_path_buf()).collect(),
+            recursive,
+            started,
+            finished: started,
+        }
+    }
+
+    /// Set when the scan finished
+    pub fn with_finished(mut self, finished: DateTime<Utc>) -> Self {
+        self.finished = finished;
+        self
+    }
+
+    /// Get how long the scan took, in whole seconds
+    pub fn duration_seconds(&self) -> i64 {
+        (self.finished - self.started).num_seconds().max(0)
+    }
+
+    /// Get the metadata as labelled values, in the order every text format lists them
+    pub fn fields(&self) -> [(&'static str, String); 7] {
+        [
+            ("version", self.version.clone()),
+            ("host", self.host.clone()),
+            ("roots", self.roots.iter().map(|root| root.display().to_string()).collect::<Vec<_>>().join(", ")),
+            ("recursive", self.recursive.to_string()),
+            ("started_at", timestamps::to_rfc3339(&self.started)),
+            ("finished_at", timestamps::to_rfc3339(&self.finished)),
+            ("duration_seconds", self.duration_seconds().to_string()),
+        ]
+    }
+
+    /// Serialize the metadata as a pretty-printed JSON object
+    pub fn to_json_pretty(&self) -> String {
+        let json = MetadataJson {
+            version: &self.version,
+            host: &self.host,
+            roots: self.roots.iter().map(|root| root.display().to_string()).collect(),
+            recursive: self.recursive,
+            started_at: timestamps::to_rfc3339(&self.started),
+            finished_at: timestamps::to_rfc3339(&self.finished),
+            duration_seconds: self.duration_seconds(),
+        };
+        // Plain strings, numbers and booleans always serialize
+        serde_json::to_string_pretty(&json).unwrap_or_default()
+    }
+}
+
+/// Get the name of this machine, or `unknown` if it has none
+pub fn host_name() -> String {
+    system_host_name()
+        .or_else(|| std::env::var("COMPUTERNAME").ok())
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Ask the system for the host name
+#[cfg(unix)]
+fn system_host_name() -> Option<String> {
+    let mut buffer = [0u8; 256];
+    // SAFETY: the buffer is writable for its whole length
+    if unsafe { libc::gethostname(buffer.as_mut_ptr().cast(), buffer.len()) } != 0 {
+        return None;
+    }
+    let end = buffer.iter().position(|&byte| byte == 0).unwrap_or(buffer.len());
+    Some(String::from_utf8_lossy(&buffer[..end]).into_owned())
+}
+
+/// Ask the system for the host name
+#[cfg(not(unix))]
+fn system_host_name() -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn test_fields_and_duration() {
+        let started = Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap();
+        let metadata = ScanMetadata::new(&[Path::new("/a"), Path::new("/b")], true, started)
+            .with_finished(started + Duration::seconds(95));
+        assert_eq!(metadata.version, env!("CARGO_PKG_VERSION"));
+        assert!(!metadata.host.is_empty());
+        assert_eq!(metadata.duration_seconds(), 95);
+
+        let fields = metadata.fields();
+        assert_eq!(fields[2], ("roots", "/a, /b".to_string()));
+        // The timezone is a process-wide setting, so compare against whatever it formats to
+        assert_eq!(fields[4], ("started_at", timestamps::to_rfc3339(&started)));
+
+        let json: serde_json::Value = serde_json::from_str(&metadata.to_json_pretty()).unwrap();
+        assert_eq!(json["roots"][1], "/b");
+        assert_eq!(json["duration_seconds"], 95);
+        assert_eq!(ScanMetadata::new(&[], false, started).with_finished(started - Duration::seconds(5)).duration_seconds(), 0);
+    }
+}
@@ -0,0 +1,112 @@
+//! Scan ordering heuristics for broad searches
+//!
+//! When the search starts at a very broad root such as the home directory, the
+//! order in which sub-directories are visited decides how quickly the first
+//! useful results appear. This module ranks directories so that likely project
+//! roots (containing `.git`, `pyproject.toml`, ...) are walked first and bulky
+//! media or cache folders (Photos, Downloads, Library, ...) last.
+
+use std::path::Path;
+
+/// Files or folders whose presence marks a directory as a likely project root
+pub const PROJECT_MARKERS: &[&str] = &[
+    ".venv",
+    ".git",
+    "pyproject.toml",
+    "setup.py",
+    "setup.cfg",
+    "requirements.txt",
+    "Pipfile",
+];
+
+/// Directory names that rarely contain Python projects and are walked last
+pub const LOW_PRIORITY_DIRS: &[&str] = &[
+    "Applications",
+    "Downloads",
+    "Library",
+    "Movies",
+    "Music",
+    "Photos",
+    "Pictures",
+    "Videos",
+    "node_modules",
+    ".cache",
+    ".Trash",
+];
+
+/// Priority for directories that look like project roots
+pub const PRIORITY_PROJECT: u8 = 0;
+
+/// Priority for ordinary directories
+pub const PRIORITY_NORMAL: u8 = 1;
+
+/// Priority for directories that are unlikely to contain projects
+pub const PRIORITY_LOW: u8 = 2;
+
+/// Rank a directory for scan ordering (lower values are walked first)
+///
+/// # Arguments
+/// * `path` - Full path to the directory
+///
+/// # Returns
+/// One of `PRIORITY_PROJECT`, `PRIORITY_NORMAL` or `PRIORITY_LOW`
+pub fn directory_priority(path: &Path) -> u8 {
+    let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+
+    if LOW_PRIORITY_DIRS.iter().any(|low| *low == name) {
+        PRIORITY_LOW
+    } else if PROJECT_MARKERS.iter().any(|marker| path.join(marker).exists()) {
+        PRIORITY_PROJECT
+    } else {
+        PRIORITY_NORMAL
+    }
+}
+
+/// Check whether a search root is broad enough to benefit from priority ordering
+///
+/// Ranking costs a few extra `stat` calls per directory, so it is only worth
+/// it for the home directory or one of its ancestors (e.g. `/Users`).
+///
+/// # Arguments
+/// * `base_directory` - The directory the search starts from
+pub fn is_broad_root(base_directory: &Path) -> bool {
+    match dirs::home_dir() {
+        Some(home) => home.starts_with(base_directory),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_directory_priority() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let project = temp_dir.path().join("webapp");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(project.join("pyproject.toml"), "").unwrap();
+        assert_eq!(directory_priority(&project), PRIORITY_PROJECT);
+
+        let plain = temp_dir.path().join("notes");
+        fs::create_dir_all(&plain).unwrap();
+        assert_eq!(directory_priority(&plain), PRIORITY_NORMAL);
+
+        // Low-priority names win even if they contain project markers
+        let photos = temp_dir.path().join("Photos");
+        fs::create_dir_all(photos.join(".git")).unwrap();
+        assert_eq!(directory_priority(&photos), PRIORITY_LOW);
+    }
+
+    #[test]
+    fn test_is_broad_root() {
+        if let Some(home) = dirs::home_dir() {
+            assert!(is_broad_root(&home));
+            assert!(is_broad_root(Path::new("/")));
+            assert!(!is_broad_root(&home.join("definitely-not-a-real-project-dir")));
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! Per-directory timing of a scan
+//!
+//! A slow scan of a broad root is usually down to one or two huge trees
+//! (`~/Downloads`, a photo library, a mounted share) that hold no projects.
+//! The walk is timed per top-level directory below each root so `-vv` can
+//! show where the time went, e.g. `~/work: 1.2s, 14 venvs` next to
+//! `~/Downloads: 8.9s, 0 venvs`, and the user knows what to exclude.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use super::{FileUtils, ScanCheckpointer};
+
+/// Time spent walking one top-level directory and the .venv directories found there
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectoryScanStats {
+    /// The top-level directory below the scan root
+    pub path: PathBuf,
+    /// Wall-clock time spent walking it
+    pub elapsed: Duration,
+    /// Number of .venv directories found inside it
+    pub venvs: usize,
+}
+
+impl fmt::Display for DirectoryScanStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {:.1}s, {} venv{}",
+            FileUtils::home_relative(&self.path),
+            self.elapsed.as_secs_f64(),
+            self.venvs,
+            if self.venvs == 1 { "" } else { "s" }
+        )
+    }
+}
+
+/// Splits a pre-order walk into sections, one per top-level directory
+#[derive(Debug, Default)]
+pub struct ScanTimer {
+    /// Finished and current sections, in walk order
+    sections: Vec<DirectoryScanStats>,
+    /// When the current section started
+    started: Option<Instant>,
+    /// Records finished sections so an interrupted scan can resume
+    checkpointer: Option<ScanCheckpointer>,
+}
+
+impl ScanTimer {
+    /// Create a timer with no sections yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checkpoint the sections as they finish
+    ///
+    /// # Arguments
+    /// * `checkpointer` - Records the sections, or None for no checkpoints
+    pub fn with_checkpointer(mut self, checkpointer: Option<ScanCheckpointer>) -> Self {
+        self.checkpointer = checkpointer;
+        self
+    }
+
+    /// Take the checkpointer back once the walk is over
+    pub fn take_checkpointer(&mut self) -> Option<ScanCheckpointer> {
+        self.checkpointer.take()
+    }
+
+    /// Start timing a top-level directory, closing the previous one
+    ///
+    /// # Arguments
+    /// * `path` - The directory the walk just entered
+    pub fn enter(&mut self, path: &Path) {
+        self.close();
+        self.sections.push(DirectoryScanStats {
+            path: path.to_path_buf(),
+            elapsed: Duration::ZERO,
+            venvs: 0,
+        });
+        self.started = Some(Instant::now());
+        if let Some(checkpointer) = &mut self.checkpointer {
+            checkpointer.enter(path);
+        }
+    }
+
+    /// Count a .venv found in the current directory
+    ///
+    /// A .venv directly in the scan root belongs to no section and is not counted.
+    ///
+    /// # Arguments
+    /// * `venv_path` - The .venv found
+    pub fn found_venv(&mut self, venv_path: &Path) {
+        if let Some(current) = self.sections.last_mut() {
+            current.venvs += 1;
+        }
+        if let Some(checkpointer) = &mut self.checkpointer {
+            checkpointer.found_venv(venv_path);
+        }
+    }
+
+    /// Stop timing the current section
+    fn close(&mut self) {
+        if let (Some(started), Some(current)) = (self.started.take(), self.sections.last_mut()) {
+            current.elapsed = started.elapsed();
+        }
+    }
+
+    /// Finish the walk
+    ///
+    /// # Returns
+    /// The statistics of every directory walked, slowest first
+    pub fn finish(mut self) -> Vec<DirectoryScanStats> {
+        self.close();
+        self.sections.sort_by_key(|section| std::cmp::Reverse(section.elapsed));
+        self.sections
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timer_attributes_venvs_to_current_directory() {
+        let mut timer = ScanTimer::new();
+        // A .venv in the root itself comes before any section
+        timer.found_venv(Path::new("/root/.venv"));
+        timer.enter(Path::new("/root/work"));
+        timer.found_venv(Path::new("/root/work/a/.venv"));
+        timer.found_venv(Path::new("/root/work/b/.venv"));
+        timer.enter(Path::new("/root/Downloads"));
+
+        let stats = timer.finish();
+        assert_eq!(stats.len(), 2);
+        let work = stats.iter().find(|s| s.path.ends_with("work")).unwrap();
+        let downloads = stats.iter().find(|s| s.path.ends_with("Downloads")).unwrap();
+        assert_eq!(work.venvs, 2);
+        assert_eq!(downloads.venvs, 0);
+    }
+
+    #[test]
+    fn test_stats_sorted_slowest_first_and_displayed() {
+        let mut timer = ScanTimer::new();
+        timer.enter(Path::new("/fast"));
+        timer.enter(Path::new("/slow"));
+        timer.found_venv(Path::new("/slow/.venv"));
+        std::thread::sleep(Duration::from_millis(20));
+
+        let stats = timer.finish();
+        assert_eq!(stats[0].path, PathBuf::from("/slow"));
+        assert!(stats[0].to_string().ends_with("s, 1 venv"));
+        assert!(stats[1].to_string().ends_with("s, 0 venvs"));
+    }
+}
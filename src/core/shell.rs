@@ -0,0 +1,195 @@
+//! Opening a terminal with a .venv activated
+//!
+//! Before deciding the fate of an old environment it is often useful to check
+//! whether it still works. This module launches a new terminal window in the
+//! project directory with the environment's activation script sourced, using
+//! Terminal.app on macOS, `cmd` on Windows and the first terminal emulator
+//! found on `PATH` (or `$TERMINAL`) on Linux.
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::debug;
+
+use super::{FileUtils, Result, VenvCleanerError};
+
+/// Terminal emulators tried on Linux when `$TERMINAL` is not set, in order
+pub const LINUX_TERMINALS: &[&str] = &[
+    "x-terminal-emulator",
+    "gnome-terminal",
+    "konsole",
+    "xfce4-terminal",
+    "alacritty",
+    "kitty",
+    "xterm",
+];
+
+/// Find the activation script of a .venv directory
+///
+/// # Arguments
+/// * `venv_path` - Path to the .venv directory
+///
+/// # Returns
+/// `bin/activate` (or `Scripts\activate.bat` on Windows) if it exists
+pub fn activation_script(venv_path: &Path) -> Option<PathBuf> {
+    let script = if cfg!(target_os = "windows") {
+        venv_path.join("Scripts").join("activate.bat")
+    } else {
+        venv_path.join("bin").join("activate")
+    };
+
+    script.is_file().then_some(script)
+}
+
+/// Quote a string for safe use in a POSIX shell command line
+pub fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Build the POSIX shell snippet that activates a .venv and starts a shell
+///
+/// The activation script only exports variables into the shell that sources
+/// it, so the snippet sources it and then replaces itself with the user's
+/// interactive `$SHELL`, which inherits `VIRTUAL_ENV` and the adjusted `PATH`.
+///
+/// # Arguments
+/// * `project_dir` - Directory the shell starts in
+/// * `script` - Path to the `bin/activate` script
+pub fn activation_command(project_dir: &Path, script: &Path) -> String {
+    format!(
+        "cd {} && . {} && exec \"${{SHELL:-/bin/sh}}\" -i",
+        shell_quote(&project_dir.display().to_string()),
+        shell_quote(&script.display().to_string()),
+    )
+}
+
+/// Open a new terminal window with the given .venv activated
+///
+/// # Arguments
+/// * `venv_path` - Path to the .venv directory
+///
+/// # Returns
+/// Ok once the terminal has been launched (it is not waited for)
+pub fn open_activated_shell(venv_path: &Path) -> Result<()> {
+    FileUtils::ensure_exists(venv_path)?;
+    let script = activation_script(venv_path).ok_or_else(|| VenvCleanerError::PathError {
+        path: venv_path.display().to_string(),
+        message: "No activation script found (the environment may be broken)".to_string(),
+    })?;
+    let project_dir = venv_path.parent().unwrap_or(venv_path);
+
+    let mut command = terminal_command(project_dir, &script)?;
+    command.current_dir(project_dir);
+    debug!("Opening activated shell: {:?}", command);
+
+    command.spawn().map_err(|e| VenvCleanerError::Io(format!("Failed to open terminal: {}", e)))?;
+    Ok(())
+}
+
+/// Build the platform command that opens a terminal running the activation
+#[cfg(target_os = "macos")]
+fn terminal_command(project_dir: &Path, script: &Path) -> Result<Command> {
+    // AppleScript string literals escape backslashes and double quotes
+    let snippet = activation_command(project_dir, script).replace('\\', "\\\\").replace('"', "\\\"");
+
+    let mut command = Command::new("osascript");
+    command
+        .arg("-e")
+        .arg(format!("tell application \"Terminal\" to do script \"{}\"", snippet))
+        .arg("-e")
+        .arg("tell application \"Terminal\" to activate");
+    Ok(command)
+}
+
+/// Build the platform command that opens a terminal running the activation
+#[cfg(target_os = "windows")]
+fn terminal_command(_project_dir: &Path, script: &Path) -> Result<Command> {
+    let mut command = Command::new("cmd");
+    command.args(["/C", "start", "cmd", "/K"]).arg(script);
+    Ok(command)
+}
+
+/// Build the platform command that opens a terminal running the activation
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn terminal_command(project_dir: &Path, script: &Path) -> Result<Command> {
+    let terminal = find_linux_terminal().ok_or_else(|| {
+        VenvCleanerError::FeatureNotAvailable(
+            "No terminal emulator found; set $TERMINAL to your terminal's command".to_string(),
+        )
+    })?;
+    let snippet = activation_command(project_dir, script);
+    let name = Path::new(&terminal)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let mut command = Command::new(&terminal);
+    match name.as_str() {
+        "gnome-terminal" => command.arg("--"),
+        "xfce4-terminal" => command.arg("-x"),
+        "kitty" => &mut command,
+        _ => command.arg("-e"),
+    };
+    command.args(["sh", "-c", &snippet]);
+    Ok(command)
+}
+
+/// Find the terminal emulator to use on Linux
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn find_linux_terminal() -> Option<String> {
+    if let Ok(terminal) = env::var("TERMINAL") {
+        if !terminal.trim().is_empty() {
+            return Some(terminal);
+        }
+    }
+
+    LINUX_TERMINALS
+        .iter()
+        .find(|name| is_on_path(name))
+        .map(|name| name.to_string())
+}
+
+/// Check whether an executable with the given name exists on `PATH`
+pub(crate) fn is_on_path(name: &str) -> bool {
+    env::var_os("PATH")
+        .map(|paths| env::split_paths(&paths).any(|dir| dir.join(name).is_file()))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("/home/me/proj"), "'/home/me/proj'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
+
+    #[test]
+    fn test_activation_command() {
+        let command = activation_command(Path::new("/tmp/my proj"), Path::new("/tmp/my proj/.venv/bin/activate"));
+        assert!(command.starts_with("cd '/tmp/my proj' && . '/tmp/my proj/.venv/bin/activate'"));
+        assert!(command.ends_with("-i"));
+    }
+
+    #[test]
+    fn test_activation_script_required() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = temp_dir.path().join(".venv");
+        fs::create_dir_all(&venv).unwrap();
+        assert!(activation_script(&venv).is_none());
+        assert!(open_activated_shell(&venv).is_err());
+
+        let script = if cfg!(target_os = "windows") {
+            venv.join("Scripts").join("activate.bat")
+        } else {
+            venv.join("bin").join("activate")
+        };
+        fs::create_dir_all(script.parent().unwrap()).unwrap();
+        fs::write(&script, "").unwrap();
+        assert_eq!(activation_script(&venv), Some(script));
+    }
+}
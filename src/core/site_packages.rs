@@ -0,0 +1,287 @@
+//! Inspection of a .venv's installed packages
+//!
+//! This module locates the `site-packages` directories of a virtual
+//! environment and reads pip's install records (`*.dist-info` folders) from
+//! them. The newest `RECORD`/`METADATA`/`INSTALLER` timestamp is the moment of
+//! the last `pip install`, which says more about whether an environment is
+//! still in use than the .venv directory's own modification time.
+//!
+//! It also detects editable (development) installs of the project that owns
+//! the .venv, recorded either in a `direct_url.json` with `"editable": true`
+//! or as a plain path line in a `.pth` file. Deleting such an environment
+//! breaks an active development setup rather than just a cached one.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use chrono::{DateTime, Utc};
+
+/// Files pip writes into every `*.dist-info` folder when installing a package
+pub const INSTALL_RECORD_FILES: &[&str] = &["RECORD", "METADATA", "INSTALLER"];
+
+/// Find the site-packages directories of a .venv
+///
+/// Covers the POSIX layout (`lib/pythonX.Y/site-packages`, also under
+/// `lib64`) and the Windows layout (`Lib/site-packages`).
+///
+/// # Arguments
+/// * `venv_path` - Path to the .venv directory
+///
+/// # Returns
+/// The existing site-packages directories, sorted by path
+pub fn site_packages_dirs(venv_path: &Path) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    for lib in ["lib", "lib64"] {
+        if let Ok(entries) = fs::read_dir(venv_path.join(lib)) {
+            for entry in entries.flatten() {
+                let site_packages = entry.path().join("site-packages");
+                let is_python_dir = entry.file_name().to_string_lossy().starts_with("python");
+                if is_python_dir && site_packages.is_dir() && !dirs.contains(&site_packages) {
+                    dirs.push(site_packages);
+                }
+            }
+        }
+    }
+
+    let windows_site_packages = venv_path.join("Lib").join("site-packages");
+    if windows_site_packages.is_dir() && !dirs.contains(&windows_site_packages) {
+        dirs.push(windows_site_packages);
+    }
+
+    dirs.sort();
+    dirs
+}
+
+/// Find the `*.dist-info` folders of all packages installed in a .venv
+///
+/// # Arguments
+/// * `venv_path` - Path to the .venv directory
+pub fn dist_info_dirs(venv_path: &Path) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    for site_packages in site_packages_dirs(venv_path) {
+        if let Ok(entries) = fs::read_dir(&site_packages) {
+            dirs.extend(
+                entries
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| path.is_dir() && path.extension().map(|e| e == "dist-info").unwrap_or(false)),
+            );
+        }
+    }
+
+    dirs.sort();
+    dirs
+}
+
+/// Determine when a package was last installed into a .venv
+///
+/// # Arguments
+/// * `venv_path` - Path to the .venv directory
+///
+/// # Returns
+/// The newest install-record timestamp, or None if no package records exist
+pub fn last_install_time(venv_path: &Path) -> Option<DateTime<Utc>> {
+    dist_info_dirs(venv_path)
+        .iter()
+        .flat_map(|dist_info| INSTALL_RECORD_FILES.iter().map(move |name| dist_info.join(name)))
+        .filter_map(|record| fs::metadata(record).and_then(|m| m.modified()).ok())
+        .max()
+        .map(|time: SystemTime| time.into())
+}
+
+/// Check whether a .venv holds an editable install of its parent project
+///
+/// # Arguments
+/// * `venv_path` - Path to the .venv directory
+///
+/// # Returns
+/// True if any editable install or `.pth` path points into the project
+pub fn has_editable_project_install(venv_path: &Path) -> bool {
+    match venv_path.parent() {
+        Some(project) => !editable_install_paths(venv_path, project).is_empty(),
+        None => false,
+    }
+}
+
+/// Find the editable install targets inside a project directory
+///
+/// # Arguments
+/// * `venv_path` - Path to the .venv directory
+/// * `project` - The project directory to look for references to
+///
+/// # Returns
+/// The referenced paths that lie inside `project` (but not inside the .venv)
+pub fn editable_install_paths(venv_path: &Path, project: &Path) -> Vec<PathBuf> {
+    let project = fs::canonicalize(project).unwrap_or_else(|_| project.to_path_buf());
+    let venv = fs::canonicalize(venv_path).unwrap_or_else(|_| venv_path.to_path_buf());
+    let mut targets = Vec::new();
+
+    for dist_info in dist_info_dirs(venv_path) {
+        if let Some(target) = editable_direct_url(&dist_info.join("direct_url.json")) {
+            targets.push(target);
+        }
+    }
+
+    for site_packages in site_packages_dirs(venv_path) {
+        if let Ok(entries) = fs::read_dir(&site_packages) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.extension().map(|e| e == "pth").unwrap_or(false) {
+                    targets.extend(pth_paths(&path));
+                }
+            }
+        }
+    }
+
+    let mut inside: Vec<PathBuf> = targets
+        .into_iter()
+        .map(|target| fs::canonicalize(&target).unwrap_or(target))
+        .filter(|target| target.starts_with(&project) && !target.starts_with(&venv))
+        .collect();
+    inside.sort();
+    inside.dedup();
+    inside
+}
+
+/// Read the target directory of an editable install from `direct_url.json`
+fn editable_direct_url(direct_url: &Path) -> Option<PathBuf> {
+    let content = fs::read_to_string(direct_url).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+
+    let editable = json["dir_info"]["editable"].as_bool().unwrap_or(false);
+    let url = json["url"].as_str()?;
+    if !editable {
+        return None;
+    }
+
+    url.strip_prefix("file://").map(|path| PathBuf::from(percent_decode(path)))
+}
+
+/// Read the plain directory entries of a `.pth` file (import lines are skipped)
+fn pth_paths(pth: &Path) -> Vec<PathBuf> {
+    fs::read_to_string(pth)
+        .map(|content| {
+            content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with("import"))
+                .map(PathBuf::from)
+                .filter(|path| path.is_absolute())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Decode `%XX` escapes in a file URL path
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(byte) = hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn create_dist_info(site_packages: &Path, name: &str, age_days: u64) {
+        let dist_info = site_packages.join(format!("{}.dist-info", name));
+        fs::create_dir_all(&dist_info).unwrap();
+        let when = SystemTime::now() - Duration::from_secs(age_days * 24 * 60 * 60);
+        for file in INSTALL_RECORD_FILES {
+            let path = dist_info.join(file);
+            fs::write(&path, "").unwrap();
+            File::options().write(true).open(&path).unwrap().set_modified(when).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_site_packages_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = temp_dir.path().join(".venv");
+        fs::create_dir_all(venv.join("lib").join("python3.11").join("site-packages")).unwrap();
+        fs::create_dir_all(venv.join("lib").join("not-python")).unwrap();
+
+        let dirs = site_packages_dirs(&venv);
+        assert_eq!(dirs, vec![venv.join("lib").join("python3.11").join("site-packages")]);
+    }
+
+    #[test]
+    fn test_last_install_time_uses_newest_record() {
+        let temp_dir = TempDir::new().unwrap();
+        let site_packages = temp_dir.path().join(".venv").join("lib").join("python3.12").join("site-packages");
+        create_dist_info(&site_packages, "requests-2.31.0", 40);
+        create_dist_info(&site_packages, "urllib3-2.2.1", 10);
+        fs::create_dir_all(site_packages.join("requests")).unwrap();
+
+        assert_eq!(dist_info_dirs(&temp_dir.path().join(".venv")).len(), 2);
+
+        let last_install = last_install_time(&temp_dir.path().join(".venv")).unwrap();
+        let age = (Utc::now() - last_install).num_days();
+        assert_eq!(age, 10);
+    }
+
+    #[test]
+    fn test_last_install_time_without_packages() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("bin")).unwrap();
+        assert!(last_install_time(temp_dir.path()).is_none());
+    }
+
+    #[test]
+    fn test_editable_install_detection() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("my project");
+        let venv = project.join(".venv");
+        let site_packages = venv.join("lib").join("python3.12").join("site-packages");
+        fs::create_dir_all(project.join("src")).unwrap();
+        create_dist_info(&site_packages, "requests-2.31.0", 1);
+        assert!(!has_editable_project_install(&venv));
+
+        // A plain .pth path elsewhere does not count
+        fs::write(site_packages.join("other.pth"), "/definitely/elsewhere\nimport sys\n").unwrap();
+        assert!(!has_editable_project_install(&venv));
+
+        create_dist_info(&site_packages, "myproject-0.1.0", 1);
+        let url = format!("file://{}", project.display()).replace(' ', "%20");
+        fs::write(
+            site_packages.join("myproject-0.1.0.dist-info").join("direct_url.json"),
+            format!(r#"{{"url": "{}", "dir_info": {{"editable": true}}}}"#, url),
+        )
+        .unwrap();
+        assert!(has_editable_project_install(&venv));
+    }
+
+    #[test]
+    fn test_editable_pth_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("proj");
+        let venv = project.join(".venv");
+        let site_packages = venv.join("lib").join("python3.11").join("site-packages");
+        fs::create_dir_all(&site_packages).unwrap();
+        fs::create_dir_all(project.join("src")).unwrap();
+        fs::write(site_packages.join("__editable__.proj.pth"), format!("{}\n", project.join("src").display())).unwrap();
+
+        let paths = editable_install_paths(&venv, &project);
+        assert_eq!(paths, vec![fs::canonicalize(project.join("src")).unwrap()]);
+    }
+}
@@ -0,0 +1,213 @@
+//! How much each .venv grew or shrank since the previous scan
+//!
+//! Listings record each .venv's size in a small JSON cache under the user
+//! cache directory, and compare it with the size recorded by the scan
+//! before. The "Δ since last scan" column of the CLI query mode, the TUI and
+//! the GUI shows the difference, and the environments that grew the most are
+//! highlighted, as they are often the ones worth looking into first (a
+//! stray `pip install`, a model cached inside the .venv). Sizes cut short
+//! by `--size-timeout` are not recorded, and entries not seen for 90 days
+//! are dropped.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use super::{paths, ByteSize, Result, VenvCleanerError, VenvInfo};
+
+/// Number of environments highlighted as having grown the most
+pub const TOP_GROWTH: usize = 3;
+
+/// How long an entry is kept after its .venv was last seen, in days
+pub const ENTRY_TTL_DAYS: i64 = 90;
+
+/// A .venv's size as recorded by a scan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct RecordedSize {
+    /// The size in bytes
+    bytes: u64,
+    /// When the scan measured it
+    at: DateTime<Utc>,
+}
+
+/// On-disk record of each .venv's size at the last scan, keyed by path
+#[derive(Debug)]
+pub struct SizeCache {
+    /// Where the cache is stored (None keeps it in memory only)
+    path: Option<PathBuf>,
+    /// Recorded sizes by .venv path
+    entries: HashMap<PathBuf, RecordedSize>,
+}
+
+impl SizeCache {
+    /// Get the default location of the cache file
+    pub fn default_path() -> Option<PathBuf> {
+        paths::cache_dir().map(|dir| dir.join("sizes.json"))
+    }
+
+    /// Load the cache from its default location
+    pub fn load_default() -> Self {
+        match Self::default_path() {
+            Some(path) => Self::load(path),
+            None => Self::in_memory(),
+        }
+    }
+
+    /// Load the cache from a file; a missing or unreadable file gives an empty cache
+    ///
+    /// # Arguments
+    /// * `path` - Path to the cache file
+    pub fn load(path: PathBuf) -> Self {
+        let entries = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        Self { path: Some(path), entries }
+    }
+
+    /// Create a cache that is never written to disk
+    pub fn in_memory() -> Self {
+        Self { path: None, entries: HashMap::new() }
+    }
+
+    /// Compare the sizes a scan found with the recorded ones, then record the new sizes
+    ///
+    /// # Arguments
+    /// * `venvs` - The .venv directories the scan found
+    ///
+    /// # Returns
+    /// The change of every .venv recorded before
+    pub fn compare_and_record(&mut self, venvs: &[VenvInfo]) -> SizeDeltas {
+        let now = Utc::now();
+        let mut deltas = HashMap::new();
+        for venv in venvs.iter().filter(|venv| !venv.is_size_partial()) {
+            if let Some(previous) = self.entries.get(venv.path()) {
+                deltas.insert(venv.path().to_path_buf(), venv.size().bytes() as i64 - previous.bytes as i64);
+            }
+            self.entries.insert(venv.path().to_path_buf(), RecordedSize { bytes: venv.size().bytes(), at: now });
+        }
+        self.entries.retain(|_, recorded| now - recorded.at < Duration::days(ENTRY_TTL_DAYS));
+        SizeDeltas::new(deltas)
+    }
+
+    /// Write the cache back to disk
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = serde_json::to_string(&self.entries)
+            .map_err(|e| VenvCleanerError::Io(format!("Failed to encode size cache: {}", e)))?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+}
+
+/// The change in size of each .venv since the previous scan
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SizeDeltas {
+    /// Bytes gained (or lost, when negative) by .venv path
+    deltas: HashMap<PathBuf, i64>,
+    /// The environments that grew the most
+    top_growth: HashSet<PathBuf>,
+}
+
+impl SizeDeltas {
+    /// Create the deltas of a scan, picking the environments that grew the most
+    ///
+    /// # Arguments
+    /// * `deltas` - Bytes gained (or lost, when negative) by .venv path
+    pub fn new(deltas: HashMap<PathBuf, i64>) -> Self {
+        let mut growth: Vec<(&PathBuf, i64)> = deltas.iter().filter(|(_, delta)| **delta > 0).map(|(path, delta)| (path, *delta)).collect();
+        growth.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        let top_growth = growth.into_iter().take(TOP_GROWTH).map(|(path, _)| path.clone()).collect();
+        Self { deltas, top_growth }
+    }
+
+    /// Get the change of a .venv, or None if the previous scan did not record it
+    pub fn get(&self, path: &Path) -> Option<i64> {
+        self.deltas.get(path).copied()
+    }
+
+    /// Check whether a .venv is one of those that grew the most
+    pub fn is_top_growth(&self, path: &Path) -> bool {
+        self.top_growth.contains(path)
+    }
+
+    /// Format a change for the column, e.g. `+1.50 GB`, `-12.00 MB` or `0`
+    pub fn format(delta: i64) -> String {
+        let size = ByteSize::new(delta.unsigned_abs());
+        match delta {
+            0 => "0".to_string(),
+            d if d > 0 => format!("+{}", size),
+            _ => format!("-{}", size),
+        }
+    }
+
+    /// Format the change of a .venv for the column, `new` if the previous scan did not record it
+    pub fn describe(&self, path: &Path) -> String {
+        self.get(path).map(Self::format).unwrap_or_else(|| "new".to_string())
+    }
+}
+
+/// Compare a finished scan with the sizes recorded before and record its own
+///
+/// # Arguments
+/// * `venvs` - The .venv directories the scan found
+///
+/// # Returns
+/// The change of every .venv recorded before
+pub fn record_scan(venvs: &[VenvInfo]) -> SizeDeltas {
+    let mut cache = SizeCache::load_default();
+    let deltas = cache.compare_and_record(venvs);
+    debug!("Recorded the sizes of {} .venv directories", venvs.len());
+    if let Err(e) = cache.save() {
+        warn!("Failed to save the size cache: {}", e);
+    }
+    deltas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn venv(name: &str, bytes: u64) -> VenvInfo {
+        VenvInfo::new(PathBuf::from(format!("/work/{}/.venv", name)), ByteSize::new(bytes), Utc::now(), Utc::now())
+    }
+
+    #[test]
+    fn test_compare_and_record() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("sizes.json");
+
+        let mut cache = SizeCache::load(path.clone());
+        let first = cache.compare_and_record(&[venv("a", 100), venv("b", 500)]);
+        assert_eq!(first, SizeDeltas::default());
+        cache.save().unwrap();
+
+        let mut cache = SizeCache::load(path);
+        let deltas = cache.compare_and_record(&[venv("a", 400), venv("b", 200), venv("c", 50)]);
+        assert_eq!(deltas.get(Path::new("/work/a/.venv")), Some(300));
+        assert_eq!(deltas.get(Path::new("/work/b/.venv")), Some(-300));
+        assert_eq!(deltas.describe(Path::new("/work/c/.venv")), "new");
+        assert!(deltas.is_top_growth(Path::new("/work/a/.venv")));
+        assert!(!deltas.is_top_growth(Path::new("/work/b/.venv")));
+    }
+
+    #[test]
+    fn test_top_growth_and_format() {
+        let deltas = SizeDeltas::new((1..=5).map(|i| (PathBuf::from(format!("/v{}", i)), i * 1024)).collect());
+        let top: Vec<bool> = (1..=5).map(|i| deltas.is_top_growth(Path::new(&format!("/v{}", i)))).collect();
+        assert_eq!(top, vec![false, false, true, true, true]);
+
+        assert_eq!(SizeDeltas::format(1536), "+1.50 KB");
+        assert_eq!(SizeDeltas::format(-(12 * ByteSize::MB as i64)), "-12.00 MB");
+        assert_eq!(SizeDeltas::format(0), "0");
+    }
+}
@@ -0,0 +1,248 @@
+//! History of .venv sizes and when the disk fills up
+//!
+//! Every `stats` run and GUI scan adds a sample to a small history in the
+//! cache directory: the combined size of the .venv directories found below
+//! the scanned roots, and the space left on the file system holding them.
+//! With samples from more than a day apart the growth rate of the
+//! environments gives a projection such as "at this rate, full in ~31 days",
+//! which tends to get people to clean up sooner than any total does.
+//!
+//! One sample is kept per roots and day, so repeated scans refresh the day's
+//! numbers instead of skewing the rate, and only the last year is kept.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{paths, Result, VenvCleanerError};
+
+/// Samples kept per set of roots, one per day
+pub const MAX_SAMPLES: usize = 365;
+
+/// How far back the growth rate looks
+pub const GROWTH_WINDOW_DAYS: i64 = 30;
+
+/// Shortest span of samples a growth rate is worked out from
+pub const MIN_SPAN_HOURS: i64 = 24;
+
+/// One measurement of the .venv directories below a set of roots
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SizeSample {
+    /// When the scan finished
+    pub at: DateTime<Utc>,
+    /// Combined size of the .venv directories found
+    pub venv_bytes: u64,
+    /// Space left on the file system holding the roots
+    pub free_bytes: u64,
+}
+
+/// How fast the .venv directories grow and when the disk is full at that rate
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GrowthProjection {
+    /// Bytes the .venv directories grew per day, negative when they shrank
+    pub bytes_per_day: f64,
+    /// The days the rate was measured over
+    pub span_days: f64,
+    /// Space left on the file system at the latest sample
+    pub free_bytes: u64,
+}
+
+impl GrowthProjection {
+    /// Get the days until the file system is full, None if the environments are not growing
+    pub fn days_until_full(&self) -> Option<f64> {
+        (self.bytes_per_day > 0.0).then(|| self.free_bytes as f64 / self.bytes_per_day)
+    }
+
+    /// Describe when the disk fills up, e.g. "at this rate, full in ~31 days"
+    pub fn summary(&self) -> String {
+        match self.days_until_full() {
+            Some(days) if days < 1.0 => "at this rate, full within a day".to_string(),
+            Some(days) if days > 3650.0 => "at this rate, not full for more than ten years".to_string(),
+            Some(days) => format!("at this rate, full in ~{:.0} days", days),
+            None => "not growing, so never full at this rate".to_string(),
+        }
+    }
+}
+
+/// Size samples of every set of roots scanned
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SizeHistory {
+    /// Where the history is saved, None for an in-memory history
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    /// Samples by roots, oldest first
+    samples: BTreeMap<String, Vec<SizeSample>>,
+}
+
+impl SizeHistory {
+    /// Get the default location of the history file
+    pub fn default_path() -> Option<PathBuf> {
+        paths::cache_dir().map(|dir| dir.join("size_history.json"))
+    }
+
+    /// Load the history from a file; a missing or unreadable file gives an empty history
+    ///
+    /// # Arguments
+    /// * `path` - Path to the history file
+    pub fn load(path: PathBuf) -> Self {
+        let history: Self = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        Self { path: Some(path), ..history }
+    }
+
+    /// Get the key samples of a set of roots are kept under
+    fn key(roots: &[&Path]) -> String {
+        roots.iter().map(|root| root.display().to_string()).collect::<Vec<_>>().join("\n")
+    }
+
+    /// Add a sample for a set of roots, replacing one taken earlier the same day
+    ///
+    /// # Arguments
+    /// * `roots` - The roots that were scanned
+    /// * `sample` - The measurement
+    pub fn record(&mut self, roots: &[&Path], sample: SizeSample) {
+        let samples = self.samples.entry(Self::key(roots)).or_default();
+        if samples.last().is_some_and(|last| last.at.date_naive() == sample.at.date_naive()) {
+            samples.pop();
+        }
+        samples.push(sample);
+        if samples.len() > MAX_SAMPLES {
+            samples.drain(..samples.len() - MAX_SAMPLES);
+        }
+    }
+
+    /// Work out the growth of the .venv directories below a set of roots
+    ///
+    /// The rate runs from the oldest sample of the last 30 days to the newest.
+    ///
+    /// # Arguments
+    /// * `roots` - The roots that were scanned
+    ///
+    /// # Returns
+    /// The projection, or None until the samples span at least a day
+    pub fn projection(&self, roots: &[&Path]) -> Option<GrowthProjection> {
+        let samples = self.samples.get(&Self::key(roots))?;
+        let latest = samples.last()?;
+        let window_start = latest.at - Duration::days(GROWTH_WINDOW_DAYS);
+        let oldest = samples.iter().find(|sample| sample.at >= window_start)?;
+
+        let span = latest.at - oldest.at;
+        if span < Duration::hours(MIN_SPAN_HOURS) {
+            return None;
+        }
+        let span_days = span.num_seconds() as f64 / 86_400.0;
+        Some(GrowthProjection {
+            bytes_per_day: (latest.venv_bytes as f64 - oldest.venv_bytes as f64) / span_days,
+            span_days,
+            free_bytes: latest.free_bytes,
+        })
+    }
+
+    /// Write the history back to disk (a no-op for in-memory histories)
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| VenvCleanerError::Io(format!("Failed to encode size history: {}", e)))?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+}
+
+/// Ask the kernel how much space is left for unprivileged users on the file system a path lives on
+///
+/// # Returns
+/// The free bytes, or None where they are unknown
+#[cfg(unix)]
+pub fn free_bytes(path: &Path) -> Option<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    // SAFETY: statvfs is plain old data the kernel fills in
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: c_path is NUL-terminated and stat is a valid, writable statvfs
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    #[allow(clippy::unnecessary_cast)]
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(not(unix))]
+pub fn free_bytes(_path: &Path) -> Option<u64> {
+    None
+}
+
+/// Add a sample for a finished scan to the saved history and project its growth
+///
+/// # Arguments
+/// * `roots` - The roots that were scanned
+/// * `venv_bytes` - Combined size of the .venv directories found
+///
+/// # Returns
+/// The projection, or None without free-space figures or enough history
+pub fn record_scan(roots: &[&Path], venv_bytes: u64) -> Option<GrowthProjection> {
+    let free_bytes = free_bytes(roots.first()?)?;
+    let mut history = SizeHistory::load(SizeHistory::default_path()?);
+    history.record(roots, SizeSample { at: Utc::now(), venv_bytes, free_bytes });
+    if let Err(e) = history.save() {
+        tracing::warn!("Failed to save size history: {}", e);
+    }
+    history.projection(roots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    fn sample(day: u32, venv_bytes: u64, free_bytes: u64) -> SizeSample {
+        SizeSample { at: Utc.with_ymd_and_hms(2025, 3, day, 12, 0, 0).unwrap(), venv_bytes, free_bytes }
+    }
+
+    #[test]
+    fn test_projection_from_growth_rate() {
+        let root = Path::new("/home/user/projects");
+        let mut history = SizeHistory::default();
+        history.record(&[root], sample(1, 10 * GB, 40 * GB));
+        assert_eq!(history.projection(&[root]), None);
+
+        // A second scan the same day replaces the first
+        history.record(&[root], sample(1, 10 * GB, 40 * GB));
+        history.record(&[root], sample(11, 20 * GB, 30 * GB));
+        let projection = history.projection(&[root]).unwrap();
+        assert_eq!(projection.bytes_per_day, GB as f64);
+        assert_eq!(projection.days_until_full(), Some(30.0));
+        assert_eq!(projection.summary(), "at this rate, full in ~30 days");
+        assert_eq!(history.samples.values().next().unwrap().len(), 2);
+
+        history.record(&[root], sample(12, 5 * GB, 45 * GB));
+        assert_eq!(history.projection(&[root]).unwrap().days_until_full(), None);
+        assert_eq!(history.projection(&[Path::new("/elsewhere")]), None);
+    }
+
+    #[test]
+    fn test_history_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("size_history.json");
+        let root = temp_dir.path();
+
+        let mut history = SizeHistory::load(path.clone());
+        history.record(&[root], sample(1, GB, GB));
+        history.save().unwrap();
+        assert_eq!(SizeHistory::load(path).samples, history.samples);
+        assert!(free_bytes(root).is_some());
+    }
+}
@@ -0,0 +1,296 @@
+//! Drill-down of where the space inside one .venv goes
+//!
+//! `venv_cleaner explain` shows a .venv as a tree of its largest
+//! directories and files a few levels deep, so it is plain whether the
+//! space sits in one heavy package, in caches and bundled tests a slim pass
+//! would reclaim, or in native libraries. A directory holding a single
+//! entry is folded into it, so `lib/python3.12/site-packages` takes one
+//! level and the packages show up right below it. Each level keeps its
+//! largest entries and adds the rest up into one line.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+use super::{site_packages, slim, Result, SizeCategory, VenvCleanerError};
+
+/// What an entry of the tree is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeNodeKind {
+    /// A plain directory
+    Directory,
+    /// A package directory in site-packages
+    Package,
+    /// A directory a slim pass removes (`__pycache__`, bundled tests, pip caches)
+    Slimmable,
+    /// A native extension module or shared library
+    SharedLibrary,
+    /// Any other file
+    File,
+}
+
+impl SizeNodeKind {
+    /// Get the label shown next to the entry, if any
+    pub fn label(self) -> Option<&'static str> {
+        match self {
+            SizeNodeKind::Package => Some("package"),
+            SizeNodeKind::Slimmable => Some("slimmable"),
+            SizeNodeKind::SharedLibrary => Some("shared library"),
+            SizeNodeKind::Directory | SizeNodeKind::File => None,
+        }
+    }
+}
+
+/// One directory or file of the tree
+#[derive(Debug, Clone)]
+pub struct SizeNode {
+    /// The name shown, with folded directories joined by `/`
+    name: String,
+    /// The path of the entry (the innermost one when folded)
+    path: PathBuf,
+    /// The size of the entry and everything below it
+    bytes: u64,
+    /// What the entry is
+    kind: SizeNodeKind,
+    /// The largest entries inside, largest first, empty below the depth shown
+    children: Vec<SizeNode>,
+    /// The number of entries inside left out as smaller than those kept
+    hidden: usize,
+    /// The combined size of the entries left out
+    hidden_bytes: u64,
+}
+
+impl SizeNode {
+    /// Get the name shown
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the path of the entry
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Get the size in bytes
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Get what the entry is
+    pub fn kind(&self) -> SizeNodeKind {
+        self.kind
+    }
+
+    /// Get the largest entries inside, largest first
+    pub fn children(&self) -> &[SizeNode] {
+        &self.children
+    }
+
+    /// Get the number and combined size of the entries inside left out
+    pub fn hidden(&self) -> (usize, u64) {
+        (self.hidden, self.hidden_bytes)
+    }
+}
+
+/// The largest contributors to one .venv's size
+#[derive(Debug, Clone)]
+pub struct SizeTree {
+    /// The .venv itself
+    root: SizeNode,
+    /// The largest shared libraries anywhere in the .venv, largest first
+    shared_libraries: Vec<(PathBuf, u64)>,
+    /// Bytes a slim pass would reclaim
+    slimmable: u64,
+}
+
+impl SizeTree {
+    /// Levels shown below the .venv unless asked otherwise
+    pub const DEFAULT_DEPTH: usize = 3;
+    /// Entries kept per directory, and shared libraries listed
+    pub const TOP_ENTRIES: usize = 5;
+
+    /// Measure a .venv
+    ///
+    /// # Arguments
+    /// * `venv_path` - Path to the .venv directory
+    /// * `depth` - Levels shown below the .venv
+    ///
+    /// # Returns
+    /// The tree, or an error if the .venv cannot be read; unreadable entries inside are skipped
+    pub fn build(venv_path: &Path, depth: usize) -> Result<Self> {
+        if !venv_path.is_dir() {
+            return Err(VenvCleanerError::PathError {
+                path: venv_path.display().to_string(),
+                message: "not a directory".to_string(),
+            });
+        }
+
+        let mut walker = Walker {
+            venv: venv_path.to_path_buf(),
+            slim_roots: slim::slim_candidates(venv_path).into_iter().collect(),
+            site_packages: site_packages::site_packages_dirs(venv_path).into_iter().collect(),
+            shared_libraries: Vec::new(),
+            slimmable: 0,
+        };
+        let root = walker.directory(venv_path, venv_path.display().to_string(), depth, false);
+
+        let mut shared_libraries = walker.shared_libraries;
+        shared_libraries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        shared_libraries.truncate(Self::TOP_ENTRIES);
+
+        Ok(Self { root, shared_libraries, slimmable: walker.slimmable })
+    }
+
+    /// Get the .venv with its largest entries
+    pub fn root(&self) -> &SizeNode {
+        &self.root
+    }
+
+    /// Get the largest shared libraries anywhere in the .venv, largest first
+    pub fn shared_libraries(&self) -> &[(PathBuf, u64)] {
+        &self.shared_libraries
+    }
+
+    /// Get the bytes a slim pass would reclaim
+    pub fn slimmable_bytes(&self) -> u64 {
+        self.slimmable
+    }
+}
+
+/// Measures a .venv entry by entry, collecting what the tree reports besides sizes
+struct Walker {
+    /// The .venv, which is never folded into its only entry
+    venv: PathBuf,
+    /// Directories a slim pass removes
+    slim_roots: HashSet<PathBuf>,
+    /// The .venv's site-packages directories
+    site_packages: HashSet<PathBuf>,
+    /// Every shared library found, with its size
+    shared_libraries: Vec<(PathBuf, u64)>,
+    /// Bytes inside the slim roots
+    slimmable: u64,
+}
+
+impl Walker {
+    /// Measure a directory, keeping its largest entries for `depth` more levels
+    fn directory(&mut self, path: &Path, name: String, depth: usize, in_slim_root: bool) -> SizeNode {
+        let is_slim_root = !in_slim_root && self.slim_roots.contains(path);
+        let is_package = path.parent().is_some_and(|parent| self.site_packages.contains(parent))
+            && !entry_name(path).ends_with(".dist-info");
+        let mut entries: Vec<PathBuf> = match fs::read_dir(path) {
+            Ok(entries) => entries.filter_map(|entry| entry.ok().map(|entry| entry.path())).collect(),
+            Err(e) => {
+                warn!("Skipping unreadable directory {}: {}", path.display(), e);
+                Vec::new()
+            }
+        };
+        entries.sort();
+
+        // Fold a directory holding only a directory into it, keeping the depth
+        if let [only] = entries.as_slice() {
+            if only.is_dir() && !only.is_symlink() && path != self.venv && !is_slim_root && !is_package && !self.site_packages.contains(path) {
+                let name = format!("{}/{}", name, entry_name(only));
+                return self.directory(&only.clone(), name, depth, in_slim_root);
+            }
+        }
+
+        let mut children: Vec<SizeNode> = entries
+            .iter()
+            .filter_map(|entry| self.entry(entry, depth.saturating_sub(1), in_slim_root || is_slim_root))
+            .collect();
+        let bytes = children.iter().map(|child| child.bytes).sum();
+        if is_slim_root {
+            self.slimmable += bytes;
+        }
+
+        children.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+        let (hidden, hidden_bytes) = if depth == 0 {
+            let hidden = (children.len(), bytes);
+            children.clear();
+            hidden
+        } else {
+            let left_out = children.split_off(children.len().min(SizeTree::TOP_ENTRIES));
+            (left_out.len(), left_out.iter().map(|child| child.bytes).sum())
+        };
+
+        let kind = if is_slim_root {
+            SizeNodeKind::Slimmable
+        } else if is_package {
+            SizeNodeKind::Package
+        } else {
+            SizeNodeKind::Directory
+        };
+
+        SizeNode { name, path: path.to_path_buf(), bytes, kind, children, hidden, hidden_bytes }
+    }
+
+    /// Measure one entry of a directory; symlinks are left out so `lib64` is not counted twice
+    fn entry(&mut self, path: &Path, depth: usize, in_slim_root: bool) -> Option<SizeNode> {
+        let metadata = match fs::symlink_metadata(path) {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                warn!("Skipping unreadable entry {}: {}", path.display(), e);
+                return None;
+            }
+        };
+        if metadata.is_dir() {
+            return Some(self.directory(path, entry_name(path), depth, in_slim_root));
+        }
+        if !metadata.is_file() {
+            return None;
+        }
+
+        let bytes = metadata.len();
+        let kind = if SizeCategory::of(path) == SizeCategory::SharedLibraries {
+            self.shared_libraries.push((path.to_path_buf(), bytes));
+            SizeNodeKind::SharedLibrary
+        } else {
+            SizeNodeKind::File
+        };
+        Some(SizeNode { name: entry_name(path), path: path.to_path_buf(), bytes, kind, children: Vec::new(), hidden: 0, hidden_bytes: 0 })
+    }
+}
+
+/// Get the last component of a path for display
+fn entry_name(path: &Path) -> String {
+    path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_tree_folds_and_ranks() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = temp_dir.path().join(".venv");
+        let site = venv.join("lib").join("python3.12").join("site-packages");
+        fs::create_dir_all(site.join("torch").join("lib")).unwrap();
+        fs::create_dir_all(site.join("torch").join("__pycache__")).unwrap();
+        fs::create_dir_all(site.join("six")).unwrap();
+        fs::create_dir_all(venv.join("bin")).unwrap();
+        fs::write(site.join("torch").join("lib").join("libtorch.so"), vec![0u8; 4000]).unwrap();
+        fs::write(site.join("torch").join("__pycache__").join("a.pyc"), vec![0u8; 300]).unwrap();
+        fs::write(site.join("six").join("six.py"), vec![0u8; 100]).unwrap();
+        fs::write(venv.join("bin").join("python"), vec![0u8; 10]).unwrap();
+
+        let tree = SizeTree::build(&venv, 3).unwrap();
+        let root = tree.root();
+        assert_eq!(root.bytes(), 4410);
+        assert_eq!(root.children()[0].name(), "lib/python3.12/site-packages");
+
+        let torch = &root.children()[0].children()[0];
+        assert_eq!((torch.name(), torch.kind()), ("torch", SizeNodeKind::Package));
+        assert_eq!(torch.children()[0].name(), "lib");
+        assert_eq!(torch.children()[1].kind(), SizeNodeKind::Slimmable);
+        // The fourth level is only summed up
+        assert_eq!(torch.children()[0].children().len(), 0);
+        assert_eq!(torch.children()[0].hidden(), (1, 4000));
+
+        assert_eq!(tree.shared_libraries()[0].1, 4000);
+        assert_eq!(tree.slimmable_bytes(), 300);
+        assert!(SizeTree::build(&temp_dir.path().join("missing"), 3).is_err());
+    }
+}
@@ -0,0 +1,309 @@
+//! In-place slimming of .venv directories
+//!
+//! Slimming reclaims space without deleting the environment: compiled
+//! `__pycache__` folders are regenerated by Python on demand, test suites
+//! bundled inside installed packages are never imported at runtime, pip
+//! download caches inside the venv are pure cache, and `*.dist-info` folders
+//! whose RECORD lists no surviving files are leftovers of interrupted
+//! uninstalls. The environment keeps working after a slim pass, just with a
+//! cold cache. Stripping debug symbols from native libraries is opt-in.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use walkdir::WalkDir;
+use tracing::{debug, warn};
+
+use super::{site_packages, FileUtils, Result, SizeCategory};
+
+/// Directory names of test suites bundled inside installed packages
+pub const SLIM_TEST_DIRS: &[&str] = &["tests", "test"];
+
+/// Locations of pip download caches inside a .venv (relative to the .venv)
+pub const PIP_CACHE_DIRS: &[&str] = &[".cache/pip", "pip-cache"];
+
+/// Outcome of slimming a single .venv directory
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SlimReport {
+    /// Directories that were (or in a dry run, would be) removed
+    removed: Vec<PathBuf>,
+    /// Bytes reclaimed (or in a dry run, reclaimable)
+    bytes_saved: u64,
+    /// Directories that could not be removed, with the reason
+    failed: Vec<(PathBuf, String)>,
+    /// Number of shared libraries whose debug symbols were stripped
+    stripped: usize,
+}
+
+impl SlimReport {
+    /// Get the directories that were removed
+    pub fn removed(&self) -> &[PathBuf] {
+        &self.removed
+    }
+
+    /// Get the number of bytes reclaimed
+    pub fn bytes_saved(&self) -> u64 {
+        self.bytes_saved
+    }
+
+    /// Get the directories that could not be removed
+    pub fn failed(&self) -> &[(PathBuf, String)] {
+        &self.failed
+    }
+
+    /// Get the number of shared libraries that were stripped
+    pub fn stripped(&self) -> usize {
+        self.stripped
+    }
+}
+
+/// Find the directories a slim pass removes from a .venv
+///
+/// Every `__pycache__` folder is a candidate, as are `tests`/`test` folders
+/// nested inside an installed package, pip caches and orphaned `*.dist-info`
+/// folders. Top-level `test` packages directly in site-packages are kept,
+/// since that name is occasionally a real dependency.
+///
+/// # Arguments
+/// * `venv_path` - Path to the .venv directory
+///
+/// # Returns
+/// The candidate directories, outermost only, sorted by path
+pub fn slim_candidates(venv_path: &Path) -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+    let mut walker = WalkDir::new(venv_path).follow_links(false).into_iter();
+
+    while let Some(entry) = walker.next() {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                debug!("Skipping unreadable entry: {}", e);
+                continue;
+            }
+        };
+
+        if entry.file_type().is_dir() && is_slim_candidate(entry.path()) {
+            candidates.push(entry.path().to_path_buf());
+            walker.skip_current_dir();
+        }
+    }
+
+    candidates.extend(
+        PIP_CACHE_DIRS
+            .iter()
+            .map(|dir| venv_path.join(dir))
+            .filter(|dir| dir.is_dir()),
+    );
+    candidates.extend(site_packages::dist_info_dirs(venv_path).into_iter().filter(|d| is_orphaned_dist_info(d)));
+
+    candidates.sort();
+    candidates
+}
+
+/// Check whether a `*.dist-info` folder has outlived the package it describes
+///
+/// Interrupted uninstalls can remove a package's files but leave its
+/// metadata behind, making pip believe it is still installed. A folder is
+/// orphaned when its RECORD lists files outside itself and none of them exist.
+///
+/// # Arguments
+/// * `dist_info` - Path to the `*.dist-info` folder
+pub fn is_orphaned_dist_info(dist_info: &Path) -> bool {
+    let Some(site_packages) = dist_info.parent() else {
+        return false;
+    };
+    let Ok(record) = fs::read_to_string(dist_info.join("RECORD")) else {
+        return false;
+    };
+
+    let mut installed_files = record
+        .lines()
+        .filter_map(|line| line.split(',').next())
+        .filter(|path| !path.trim().is_empty())
+        .map(|path| site_packages.join(path))
+        .filter(|path| !path.starts_with(dist_info))
+        .peekable();
+
+    installed_files.peek().is_some() && installed_files.all(|path| !path.exists())
+}
+
+/// Check whether a single directory is removed by a slim pass
+fn is_slim_candidate(path: &Path) -> bool {
+    let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    if name == "__pycache__" {
+        return true;
+    }
+
+    let inside_site_packages = path.ancestors().skip(2).any(|a| a.file_name().map(|n| n == "site-packages").unwrap_or(false));
+    SLIM_TEST_DIRS.iter().any(|dir| *dir == name) && inside_site_packages
+}
+
+/// Removes regenerable and unused content from .venv directories
+#[derive(Debug, Clone, Default)]
+pub struct VenvSlimmer {
+    /// Whether to only report what would be removed
+    dry_run: bool,
+    /// Whether to strip debug symbols from native shared libraries
+    strip_symbols: bool,
+}
+
+impl VenvSlimmer {
+    /// Create a new VenvSlimmer
+    ///
+    /// # Arguments
+    /// * `dry_run` - Whether to only report what would be removed
+    pub fn new(dry_run: bool) -> Self {
+        Self { dry_run, strip_symbols: false }
+    }
+
+    /// Also strip debug symbols from native shared libraries (`strip` must be installed)
+    pub fn with_strip_symbols(mut self, strip_symbols: bool) -> Self {
+        self.strip_symbols = strip_symbols;
+        self
+    }
+
+    /// Slim a single .venv directory
+    ///
+    /// # Arguments
+    /// * `venv_path` - Path to the .venv directory
+    ///
+    /// # Returns
+    /// A report of the removed directories and bytes saved
+    pub fn slim(&self, venv_path: &Path) -> Result<SlimReport> {
+        let mut report = SlimReport::default();
+
+        for candidate in slim_candidates(venv_path) {
+            let size = FileUtils::calculate_directory_size(&candidate)?;
+
+            if !self.dry_run {
+                if let Err(e) = fs::remove_dir_all(&candidate) {
+                    warn!("Failed to remove {}: {}", candidate.display(), e);
+                    report.failed.push((candidate, e.to_string()));
+                    continue;
+                }
+            }
+
+            debug!("Slimmed {} ({} bytes)", candidate.display(), size);
+            report.bytes_saved += size;
+            report.removed.push(candidate);
+        }
+
+        // Stripping cannot be previewed without copying every library, so dry runs skip it
+        if self.strip_symbols && !self.dry_run {
+            self.strip_shared_libraries(venv_path, &mut report);
+        }
+
+        Ok(report)
+    }
+
+    /// Strip debug symbols from every native shared library in a .venv
+    fn strip_shared_libraries(&self, venv_path: &Path, report: &mut SlimReport) {
+        let libraries = WalkDir::new(venv_path)
+            .follow_links(false)
+            .into_iter()
+            .flatten()
+            .filter(|entry| entry.file_type().is_file())
+            .filter(|entry| SizeCategory::of(entry.path()) == SizeCategory::SharedLibraries);
+
+        for entry in libraries {
+            let path = entry.path();
+            let before = entry.metadata().map(|m| m.len()).unwrap_or(0);
+
+            // macOS strip has no --strip-debug; -S removes the debugging symbol table entries
+            let flag = if cfg!(target_os = "macos") { "-S" } else { "--strip-debug" };
+            match Command::new("strip").arg(flag).arg(path).output() {
+                Ok(output) if output.status.success() => {
+                    let after = fs::metadata(path).map(|m| m.len()).unwrap_or(before);
+                    report.bytes_saved += before.saturating_sub(after);
+                    report.stripped += 1;
+                }
+                Ok(output) => {
+                    let error = String::from_utf8_lossy(&output.stderr).trim().to_string();
+                    debug!("strip failed for {}: {}", path.display(), error);
+                    report.failed.push((path.to_path_buf(), error));
+                }
+                Err(e) => {
+                    // No strip binary: report once and stop trying
+                    warn!("Cannot run strip: {}", e);
+                    report.failed.push((path.to_path_buf(), format!("Cannot run strip: {}", e)));
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_venv(root: &Path) -> PathBuf {
+        let site_packages = root.join(".venv").join("lib").join("python3.11").join("site-packages");
+        fs::create_dir_all(site_packages.join("pkg").join("__pycache__")).unwrap();
+        fs::create_dir_all(site_packages.join("pkg").join("tests").join("__pycache__")).unwrap();
+        fs::create_dir_all(site_packages.join("test")).unwrap();
+        fs::write(site_packages.join("pkg").join("__init__.py"), "").unwrap();
+        fs::write(site_packages.join("pkg").join("__pycache__").join("__init__.pyc"), vec![0u8; 100]).unwrap();
+        fs::write(site_packages.join("pkg").join("tests").join("test_a.py"), vec![0u8; 50]).unwrap();
+        root.join(".venv")
+    }
+
+    #[test]
+    fn test_slim_candidates() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = create_venv(temp_dir.path());
+        let pkg = venv.join("lib").join("python3.11").join("site-packages").join("pkg");
+
+        // The top-level `test` package is kept and nested caches are not listed twice
+        assert_eq!(slim_candidates(&venv), vec![pkg.join("__pycache__"), pkg.join("tests")]);
+    }
+
+    #[test]
+    fn test_slim_removes_candidates() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = create_venv(temp_dir.path());
+        let pkg = venv.join("lib").join("python3.11").join("site-packages").join("pkg");
+
+        let report = VenvSlimmer::new(false).slim(&venv).unwrap();
+        assert_eq!(report.bytes_saved(), 150);
+        assert_eq!(report.removed().len(), 2);
+        assert!(report.failed().is_empty());
+        assert!(!pkg.join("__pycache__").exists());
+        assert!(pkg.join("__init__.py").exists());
+    }
+
+    #[test]
+    fn test_slim_dry_run_keeps_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = create_venv(temp_dir.path());
+
+        let report = VenvSlimmer::new(true).slim(&venv).unwrap();
+        assert_eq!(report.bytes_saved(), 150);
+        assert!(slim_candidates(&venv).len() == 2);
+    }
+
+    #[test]
+    fn test_orphans_and_pip_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = create_venv(temp_dir.path());
+        let site_packages = venv.join("lib").join("python3.11").join("site-packages");
+
+        // pkg still exists, gone does not
+        let live = site_packages.join("pkg-1.0.dist-info");
+        fs::create_dir_all(&live).unwrap();
+        fs::write(live.join("RECORD"), "pkg/__init__.py,sha256=x,0\npkg-1.0.dist-info/RECORD,,\n").unwrap();
+        let orphan = site_packages.join("gone-2.0.dist-info");
+        fs::create_dir_all(&orphan).unwrap();
+        fs::write(orphan.join("RECORD"), "gone/__init__.py,sha256=x,0\ngone-2.0.dist-info/RECORD,,\n").unwrap();
+        fs::create_dir_all(venv.join(".cache").join("pip").join("http")).unwrap();
+
+        assert!(!is_orphaned_dist_info(&live));
+        assert!(is_orphaned_dist_info(&orphan));
+
+        let candidates = slim_candidates(&venv);
+        assert!(candidates.contains(&orphan));
+        assert!(candidates.contains(&venv.join(".cache").join("pip")));
+        assert!(!candidates.contains(&live));
+    }
+}
@@ -0,0 +1,254 @@
+//! Shared ordering of .venv lists
+//!
+//! The CLI, TUI and GUI sort by the same keys. Each sort is a chain of
+//! comparators: the chosen key, in the chosen direction, then the path, so
+//! .venv directories of equal size or date keep their places between
+//! refreshes instead of swapping with every rescan.
+//!
+//! Paths are compared component by component in natural order: runs of
+//! digits compare as numbers, so `project2` comes before `project10`. The
+//! comparison goes by Unicode code point rather than the locale's collation,
+//! so a list sorts the same on every machine. Case matters unless
+//! `set_ignore_case` turns it off (`--sort-ignore-case` or the
+//! `sort_ignore_case` config key); paths equal but for case or leading zeros
+//! still get a fixed order.
+
+use std::cmp::Ordering;
+use std::iter::Peekable;
+use std::path::Path;
+use std::str::Chars;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+
+use super::VenvInfo;
+
+/// Whether path comparisons ignore case
+static IGNORE_CASE: AtomicBool = AtomicBool::new(false);
+
+/// Choose whether path sorts ignore case for the rest of the run
+///
+/// # Arguments
+/// * `ignore_case` - Whether `Project` and `project` sort together
+pub fn set_ignore_case(ignore_case: bool) {
+    IGNORE_CASE.store(ignore_case, AtomicOrdering::Relaxed);
+}
+
+/// Check whether path sorts ignore case
+pub fn ignores_case() -> bool {
+    IGNORE_CASE.load(AtomicOrdering::Relaxed)
+}
+
+/// Compare two paths in natural order
+///
+/// # Arguments
+/// * `a` - The first path
+/// * `b` - The second path
+///
+/// # Returns
+/// The order of the paths, a parent always before its children
+pub fn compare_paths(a: &Path, b: &Path) -> Ordering {
+    compare_paths_with(a, b, ignores_case())
+}
+
+/// Compare two paths in natural order, ignoring case or not
+fn compare_paths_with(a: &Path, b: &Path, ignore_case: bool) -> Ordering {
+    let mut a_components = a.components();
+    let mut b_components = b.components();
+    loop {
+        match (a_components.next(), b_components.next()) {
+            (Some(a_component), Some(b_component)) => {
+                let ordering = natural_compare(
+                    &a_component.as_os_str().to_string_lossy(),
+                    &b_component.as_os_str().to_string_lossy(),
+                    ignore_case,
+                );
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            // Equal but for case or leading zeros: fall back to the raw paths
+            (None, None) => return a.cmp(b),
+        }
+    }
+}
+
+/// Compare two names, digit runs by their numeric value
+fn natural_compare(a: &str, b: &str, ignore_case: bool) -> Ordering {
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+    loop {
+        let ordering = match (a_chars.peek().copied(), b_chars.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a_char), Some(b_char)) if a_char.is_ascii_digit() && b_char.is_ascii_digit() => {
+                compare_numbers(&take_digits(&mut a_chars), &take_digits(&mut b_chars))
+            }
+            (Some(a_char), Some(b_char)) => {
+                a_chars.next();
+                b_chars.next();
+                if ignore_case {
+                    a_char.to_lowercase().cmp(b_char.to_lowercase())
+                } else {
+                    a_char.cmp(&b_char)
+                }
+            }
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+/// Consume a run of ASCII digits
+fn take_digits(chars: &mut Peekable<Chars>) -> String {
+    let mut digits = String::new();
+    while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+        digits.push(digit);
+    }
+    digits
+}
+
+/// Compare two digit runs by value, however long they are
+fn compare_numbers(a: &str, b: &str) -> Ordering {
+    let a = a.trim_start_matches('0');
+    let b = b.trim_start_matches('0');
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+/// Keys .venv lists are sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    /// By path, in natural order (see `compare_paths`)
+    Path,
+    /// Largest first
+    Size,
+    /// Most files first, uncounted last
+    Files,
+    /// Newest first
+    Created,
+    /// Most recently used first
+    LastModified,
+}
+
+impl SortKey {
+    /// Compare two .venv directories by this key alone, in its natural order
+    pub fn compare(self, a: &VenvInfo, b: &VenvInfo) -> Ordering {
+        match self {
+            SortKey::Path => a.compare_by_path(b),
+            SortKey::Size => a.compare_by_size(b),
+            SortKey::Files => a.compare_by_file_count(b),
+            SortKey::Created => a.compare_by_created(b),
+            SortKey::LastModified => a.compare_by_last_modified(b),
+        }
+    }
+}
+
+/// A sort key, its direction, and the path breaking ties
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortOrder {
+    /// The key compared first
+    key: SortKey,
+    /// Whether the key's natural order is reversed
+    reverse: bool,
+}
+
+impl SortOrder {
+    /// Create an order sorting by a key in its natural order
+    ///
+    /// # Arguments
+    /// * `key` - The key compared first
+    pub fn new(key: SortKey) -> Self {
+        Self { key, reverse: false }
+    }
+
+    /// Reverse the key's order; ties stay in path order either way
+    pub fn with_reverse(mut self, reverse: bool) -> Self {
+        self.reverse = reverse;
+        self
+    }
+
+    /// Get the comparators applied in turn: the key, then the path unless the key is the path
+    pub fn chain(self) -> Vec<SortKey> {
+        if self.key == SortKey::Path {
+            vec![SortKey::Path]
+        } else {
+            vec![self.key, SortKey::Path]
+        }
+    }
+
+    /// Compare two .venv directories through the chain
+    pub fn compare(self, a: &VenvInfo, b: &VenvInfo) -> Ordering {
+        let primary = self.key.compare(a, b);
+        let primary = if self.reverse { primary.reverse() } else { primary };
+        self.chain().into_iter().skip(1).fold(primary, |ordering, key| ordering.then_with(|| key.compare(a, b)))
+    }
+
+    /// Sort a list of .venv directories in this order
+    pub fn sort(self, venvs: &mut [VenvInfo]) {
+        venvs.sort_by(|a, b| self.compare(a, b));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ByteSize;
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    fn venv(path: &str, size_bytes: u64) -> VenvInfo {
+        let now = Utc::now();
+        VenvInfo::new(PathBuf::from(path), ByteSize::new(size_bytes), now, now)
+    }
+
+    fn paths(venvs: &[VenvInfo]) -> Vec<String> {
+        venvs.iter().map(|venv| venv.path().display().to_string()).collect()
+    }
+
+    #[test]
+    fn test_ties_are_broken_by_path() {
+        let mut venvs = vec![venv("/c/.venv", 10), venv("/a/.venv", 10), venv("/b/.venv", 20)];
+        SortOrder::new(SortKey::Size).sort(&mut venvs);
+        assert_eq!(paths(&venvs), vec!["/b/.venv", "/a/.venv", "/c/.venv"]);
+
+        // Reversing flips the sizes but not the order of equal ones
+        SortOrder::new(SortKey::Size).with_reverse(true).sort(&mut venvs);
+        assert_eq!(paths(&venvs), vec!["/a/.venv", "/c/.venv", "/b/.venv"]);
+
+        SortOrder::new(SortKey::Path).with_reverse(true).sort(&mut venvs);
+        assert_eq!(paths(&venvs), vec!["/c/.venv", "/b/.venv", "/a/.venv"]);
+    }
+
+    #[test]
+    fn test_paths_sort_naturally() {
+        let mut venvs = vec![
+            venv("/work/project10/.venv", 1),
+            venv("/work/project2/.venv", 1),
+            venv("/work/Project3/.venv", 1),
+            venv("/work/project2-old/.venv", 1),
+            venv("/work/project02/.venv", 1),
+        ];
+        SortOrder::new(SortKey::Path).sort(&mut venvs);
+        assert_eq!(
+            paths(&venvs),
+            vec!["/work/Project3/.venv", "/work/project02/.venv", "/work/project2/.venv", "/work/project2-old/.venv", "/work/project10/.venv"]
+        );
+
+        let ignoring_case = |a: &str, b: &str| compare_paths_with(Path::new(a), Path::new(b), true);
+        assert_eq!(ignoring_case("/work/Project3", "/work/project10"), Ordering::Less);
+        assert_eq!(ignoring_case("/work/B", "/work/a"), Ordering::Greater);
+        // Equal but for case, the order is still fixed
+        assert_eq!(ignoring_case("/work/A", "/work/a"), Ordering::Less);
+        // A parent comes before its children, whatever the separator sorts like
+        assert_eq!(ignoring_case("/work/a/.venv", "/work/a-b/.venv"), Ordering::Less);
+        assert_eq!(compare_numbers("99999999999999999999999", "100000000000000000000000"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_chain() {
+        assert_eq!(SortOrder::new(SortKey::Created).chain(), vec![SortKey::Created, SortKey::Path]);
+        assert_eq!(SortOrder::new(SortKey::Path).with_reverse(true).chain(), vec![SortKey::Path]);
+    }
+}
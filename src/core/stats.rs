@@ -0,0 +1,271 @@
+//! Aggregate statistics over discovered .venv directories
+//!
+//! This module summarises a set of VenvInfo instances, for example grouping
+//! them by the Python release line they were built with so that environments
+//! tied to end-of-life interpreters stand out, or adding up each installed
+//! package across all of them, so a framework copied into a dozen projects
+//! makes the case for a shared environment.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use chrono::NaiveDate;
+
+use super::{PythonVersion, VenvInfo, VenvPeek};
+
+/// Totals for all .venv directories tied to a single Python release line
+#[derive(Debug, Clone, PartialEq)]
+pub struct PythonVersionGroup {
+    /// The release line (major.minor), or None when pyvenv.cfg had no version
+    version: Option<PythonVersion>,
+    /// Number of .venv directories in this group
+    count: usize,
+    /// Combined size of the group in bytes
+    total_bytes: u64,
+    /// Whether the release line is past end-of-life
+    eol: bool,
+}
+
+impl PythonVersionGroup {
+    /// Get the release line, or None for environments of unknown version
+    pub fn version(&self) -> Option<PythonVersion> {
+        self.version
+    }
+
+    /// Get a display label for the release line (e.g. "3.11" or "unknown")
+    pub fn label(&self) -> String {
+        self.version
+            .map(|v| v.release_line())
+            .unwrap_or_else(|| "unknown".to_string())
+    }
+
+    /// Get the number of .venv directories in this group
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Get the combined size of the group in bytes
+    pub fn total_bytes(&self) -> u64 {
+        self.total_bytes
+    }
+
+    /// Check whether the release line is past end-of-life
+    pub fn is_eol(&self) -> bool {
+        self.eol
+    }
+}
+
+/// Totals for one package across all .venv directories it is installed in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageGroup {
+    /// The package's directory or module name in site-packages
+    name: String,
+    /// The .venv directories holding the package, sorted by path
+    venvs: Vec<PathBuf>,
+    /// Combined size of all its copies in bytes
+    total_bytes: u64,
+}
+
+impl PackageGroup {
+    /// Get the package name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the number of .venv directories holding the package
+    pub fn venv_count(&self) -> usize {
+        self.venvs.len()
+    }
+
+    /// Get the .venv directories holding the package
+    pub fn venvs(&self) -> &[PathBuf] {
+        &self.venvs
+    }
+
+    /// Get the combined size of all its copies in bytes
+    pub fn total_bytes(&self) -> u64 {
+        self.total_bytes
+    }
+}
+
+/// Summary statistics for a set of .venv directories
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VenvStats {
+    /// Number of .venv directories
+    total_count: usize,
+    /// Combined size in bytes
+    total_bytes: u64,
+}
+
+impl VenvStats {
+    /// Calculate summary statistics for a set of .venv directories
+    pub fn from_venvs(venvs: &[VenvInfo]) -> Self {
+        Self {
+            total_count: venvs.len(),
+            total_bytes: venvs.iter().map(|v| v.size().bytes()).sum(),
+        }
+    }
+
+    /// Get the number of .venv directories
+    pub fn total_count(&self) -> usize {
+        self.total_count
+    }
+
+    /// Get the combined size in bytes
+    pub fn total_bytes(&self) -> u64 {
+        self.total_bytes
+    }
+
+    /// Group .venv directories by Python release line
+    ///
+    /// # Arguments
+    /// * `venvs` - The .venv directories to group
+    /// * `today` - The date used to decide end-of-life status
+    ///
+    /// # Returns
+    /// One group per release line, oldest first, with unknown versions last
+    pub fn by_python_version(venvs: &[VenvInfo], today: NaiveDate) -> Vec<PythonVersionGroup> {
+        // BTreeMap keeps the groups ordered; None sorts first so it is moved afterwards
+        let mut groups: BTreeMap<Option<(u32, u32)>, (usize, u64)> = BTreeMap::new();
+
+        for venv in venvs {
+            let key = venv.python_version().map(|v| (v.major(), v.minor()));
+            let entry = groups.entry(key).or_insert((0, 0));
+            entry.0 += 1;
+            entry.1 += venv.size().bytes();
+        }
+
+        let mut result: Vec<PythonVersionGroup> = groups
+            .into_iter()
+            .map(|(key, (count, total_bytes))| {
+                let version = key.map(|(major, minor)| PythonVersion::new(major, minor, None));
+                PythonVersionGroup {
+                    version,
+                    count,
+                    total_bytes,
+                    eol: version.map(|v| v.is_eol_on(today)).unwrap_or(false),
+                }
+            })
+            .collect();
+
+        if result.first().map(|g| g.version.is_none()).unwrap_or(false) {
+            let unknown = result.remove(0);
+            result.push(unknown);
+        }
+
+        result
+    }
+
+    /// Add up the installed packages of all .venv directories
+    ///
+    /// # Arguments
+    /// * `venvs` - The .venv directories to look inside
+    ///
+    /// # Returns
+    /// One group per package name, largest combined size first
+    pub fn by_package(venvs: &[VenvInfo]) -> Vec<PackageGroup> {
+        let peeks: Vec<VenvPeek> = venvs
+            .iter()
+            .filter_map(|venv| VenvPeek::of(venv.path(), usize::MAX).ok())
+            .collect();
+        Self::group_packages(&peeks)
+    }
+
+    /// Add up the packages listed in a set of peeks
+    fn group_packages(peeks: &[VenvPeek]) -> Vec<PackageGroup> {
+        let mut groups: HashMap<&str, (Vec<PathBuf>, u64)> = HashMap::new();
+        for peek in peeks {
+            for package in &peek.packages {
+                let entry = groups.entry(package.name.as_str()).or_default();
+                // `lib64` is usually a symlink to `lib`, listing the same copy twice
+                if entry.0.last() == Some(&peek.path) {
+                    continue;
+                }
+                entry.0.push(peek.path.clone());
+                entry.1 += package.size_bytes;
+            }
+        }
+
+        let mut result: Vec<PackageGroup> = groups
+            .into_iter()
+            .map(|(name, (mut venvs, total_bytes))| {
+                venvs.sort();
+                PackageGroup { name: name.to_string(), venvs, total_bytes }
+            })
+            .collect();
+        result.sort_by(|a, b| b.total_bytes.cmp(&a.total_bytes).then_with(|| a.name.cmp(&b.name)));
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ByteSize;
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    fn venv(path: &str, size: u64, version: Option<&str>) -> VenvInfo {
+        let now = Utc::now();
+        VenvInfo::new(PathBuf::from(path), ByteSize::new(size), now, now)
+            .with_python_version(version.and_then(PythonVersion::parse))
+    }
+
+    #[test]
+    fn test_venv_stats_totals() {
+        let venvs = vec![venv("/a/.venv", 100, None), venv("/b/.venv", 250, Some("3.12.1"))];
+        let stats = VenvStats::from_venvs(&venvs);
+        assert_eq!(stats.total_count(), 2);
+        assert_eq!(stats.total_bytes(), 350);
+    }
+
+    #[test]
+    fn test_by_python_version() {
+        let venvs = vec![
+            venv("/a/.venv", 100, Some("3.12.1")),
+            venv("/b/.venv", 200, Some("3.7.9")),
+            venv("/c/.venv", 300, None),
+            venv("/d/.venv", 400, Some("3.12.4")),
+        ];
+        let today = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let groups = VenvStats::by_python_version(&venvs, today);
+
+        let labels: Vec<String> = groups.iter().map(|g| g.label()).collect();
+        assert_eq!(labels, vec!["3.7", "3.12", "unknown"]);
+
+        assert_eq!(groups[0].count(), 1);
+        assert!(groups[0].is_eol());
+        assert_eq!(groups[1].count(), 2);
+        assert_eq!(groups[1].total_bytes(), 500);
+        assert!(!groups[1].is_eol());
+        assert!(!groups[2].is_eol());
+    }
+
+    #[test]
+    fn test_by_python_version_empty() {
+        let today = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        assert!(VenvStats::by_python_version(&[], today).is_empty());
+    }
+
+    #[test]
+    fn test_by_package() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut venvs = Vec::new();
+        for (project, torch_size) in [("a", 3000), ("b", 5000)] {
+            let venv_path = temp_dir.path().join(project).join(".venv");
+            let site_packages = venv_path.join("lib").join("python3.12").join("site-packages");
+            std::fs::create_dir_all(site_packages.join("torch")).unwrap();
+            std::fs::create_dir_all(site_packages.join("torch-2.3.0.dist-info")).unwrap();
+            std::fs::write(site_packages.join("torch").join("lib.so"), vec![0u8; torch_size]).unwrap();
+            std::fs::write(site_packages.join(format!("{}_only.py", project)), "x").unwrap();
+            venvs.push(venv(venv_path.to_str().unwrap(), 0, None));
+        }
+
+        let groups = VenvStats::by_package(&venvs);
+        assert_eq!(groups[0].name(), "torch");
+        assert_eq!(groups[0].venv_count(), 2);
+        assert_eq!(groups[0].total_bytes(), 8000);
+        // dist-info folders are metadata, not packages
+        assert_eq!(groups.len(), 3);
+        assert!(groups[1..].iter().all(|group| group.venv_count() == 1));
+    }
+}
@@ -0,0 +1,161 @@
+//! Summary of the last full scan, for shell prompts
+//!
+//! `venv_cleaner prompt-summary` runs on every prompt, so it never scans:
+//! it only reads this small JSON file from the cache directory. Unfiltered
+//! scans of the CLI query mode, `stats`, `refresh-cache` and the GUI
+//! overwrite it with the number and size of the .venv directories found,
+//! and how much of that is unused for over 90 days. The file is replaced in one
+//! rename, so a prompt drawn during a scan reads the old summary or the new
+//! one, never half of each.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use super::{paths, ByteSize, Result, VenvCleanerError, VenvInfo};
+
+/// Template used when `prompt-summary` is given no `--format`
+pub const DEFAULT_FORMAT: &str = "venvs: {count} / {size}";
+
+/// What the last full scan found
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScanSummary {
+    /// When the scan finished
+    pub at: DateTime<Utc>,
+    /// The roots that were scanned
+    pub roots: Vec<PathBuf>,
+    /// Number of .venv directories found
+    pub venv_count: usize,
+    /// Combined size of the .venv directories found
+    pub venv_bytes: u64,
+    /// Combined size of those unused for over 90 days
+    pub old_bytes: u64,
+}
+
+impl ScanSummary {
+    /// Summarize the .venv directories a scan found
+    ///
+    /// # Arguments
+    /// * `roots` - The roots that were scanned
+    /// * `venvs` - The .venv directories found
+    /// * `at` - When the scan finished
+    pub fn of(roots: &[&Path], venvs: &[VenvInfo], at: DateTime<Utc>) -> Self {
+        Self {
+            at,
+            roots: roots.iter().map(|root| root.to_path_buf()).collect(),
+            venv_count: venvs.len(),
+            venv_bytes: venvs.iter().map(|venv| venv.size().bytes()).sum(),
+            old_bytes: venvs.iter().filter(|venv| venv.is_old()).map(|venv| venv.size().bytes()).sum(),
+        }
+    }
+
+    /// Get the default location of the summary file
+    pub fn default_path() -> Option<PathBuf> {
+        paths::cache_dir().map(|dir| dir.join("summary.json"))
+    }
+
+    /// Load a summary; a missing or unreadable file gives None
+    ///
+    /// # Arguments
+    /// * `path` - Path to the summary file
+    pub fn load(path: &Path) -> Option<Self> {
+        let contents = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    /// Replace the summary file with this summary
+    ///
+    /// # Arguments
+    /// * `path` - Path to the summary file
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| VenvCleanerError::Io(format!("Failed to encode scan summary: {}", e)))?;
+        // Prompts read the file at any moment, so it is swapped in whole
+        let partial = path.with_extension("json.tmp");
+        fs::write(&partial, contents)?;
+        fs::rename(&partial, path)?;
+        Ok(())
+    }
+
+    /// Fill in a template such as `"venvs: {count} / {size}"`
+    ///
+    /// `{count}` is the number of .venv directories, `{size}` their combined
+    /// size, `{old}` the size of those unused for over 90 days and `{age}`
+    /// how long ago the scan finished, e.g. `3h`.
+    ///
+    /// # Arguments
+    /// * `template` - The text with placeholders
+    /// * `now` - The time `{age}` is counted to
+    pub fn render(&self, template: &str, now: DateTime<Utc>) -> String {
+        template
+            .replace("{count}", &self.venv_count.to_string())
+            .replace("{size}", &ByteSize::new(self.venv_bytes).compact())
+            .replace("{old}", &ByteSize::new(self.old_bytes).compact())
+            .replace("{age}", &compact_age(now - self.at))
+    }
+}
+
+/// Format a time span in its largest whole unit, e.g. `45m`, `3h` or `2d`
+fn compact_age(age: chrono::Duration) -> String {
+    let minutes = age.num_minutes().max(0);
+    if minutes < 60 {
+        format!("{}m", minutes)
+    } else if minutes < 24 * 60 {
+        format!("{}h", minutes / 60)
+    } else {
+        format!("{}d", minutes / (24 * 60))
+    }
+}
+
+/// Save the summary of a finished, unfiltered scan as the one prompts show
+///
+/// # Arguments
+/// * `roots` - The roots that were scanned
+/// * `venvs` - The .venv directories found
+pub fn record_scan(roots: &[&Path], venvs: &[VenvInfo]) {
+    let Some(path) = ScanSummary::default_path() else {
+        return;
+    };
+    debug!("Saving the scan summary to {}", path.display());
+    if let Err(e) = ScanSummary::of(roots, venvs, Utc::now()).save(&path) {
+        warn!("Failed to save the scan summary: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use tempfile::TempDir;
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    #[test]
+    fn test_summary_round_trip_and_render() {
+        // Whether a .venv is old is judged against the clock
+        let at = Utc::now();
+        let venvs = vec![
+            VenvInfo::new(PathBuf::from("/w/a/.venv"), ByteSize::new(50 * GB), at - Duration::days(200), at - Duration::days(120)),
+            VenvInfo::new(PathBuf::from("/w/b/.venv"), ByteSize::new(2 * GB), at - Duration::days(3), at - Duration::days(1)),
+        ];
+        let summary = ScanSummary::of(&[Path::new("/w")], &venvs, at);
+        assert_eq!((summary.venv_count, summary.venv_bytes, summary.old_bytes), (2, 52 * GB, 50 * GB));
+
+        assert_eq!(summary.render(DEFAULT_FORMAT, at), "venvs: 2 / 52.0GB");
+        assert_eq!(summary.render("{old} old, {age} ago", at + Duration::hours(5)), "50.0GB old, 5h ago");
+        assert_eq!(summary.render("{age}", at + Duration::days(3)), "3d");
+        assert_eq!(ByteSize::new(512).compact(), "512B");
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("cache").join("summary.json");
+        assert_eq!(ScanSummary::load(&path), None);
+        summary.save(&path).unwrap();
+        assert_eq!(ScanSummary::load(&path), Some(summary));
+        assert!(!path.with_extension("json.tmp").exists());
+    }
+}
@@ -0,0 +1,277 @@
+//! Opt-in crash and error reports
+//!
+//! When enabled with `crash_reports = true` in the `[telemetry]` table of the
+//! config file, panics and unexpected I/O failures are written as small JSON
+//! reports to `<data dir>/venv_cleaner/crash-reports`. Reports are anonymous:
+//! the home directory and user name are replaced in every message, and no
+//! host or environment details beyond the OS and architecture are recorded.
+//! With `submit_url` set, each report is also POSTed there using `curl`.
+//! Nothing is recorded or sent unless the user opts in.
+
+use std::fs;
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use super::{paths, timestamps, Result, VenvCleanerError};
+
+/// Name of the directory reports are written to inside the data directory
+pub const REPORT_DIR_NAME: &str = "crash-reports";
+
+/// Crash reporting settings read from the `[telemetry]` table
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TelemetryConfig {
+    /// Whether to write crash and error reports (off unless set)
+    pub crash_reports: bool,
+    /// Where reports are submitted with an HTTP POST; local only when unset
+    pub submit_url: Option<String>,
+}
+
+/// A single anonymized crash or error report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrashReport {
+    /// VenvCleaner version
+    pub version: String,
+    /// Operating system (e.g. "linux", "macos")
+    pub os: String,
+    /// CPU architecture
+    pub arch: String,
+    /// When the failure happened (RFC 3339)
+    pub timestamp: String,
+    /// "panic" or "error"
+    pub kind: String,
+    /// The panic or error message
+    pub message: String,
+    /// Source location of a panic
+    pub location: Option<String>,
+    /// Backtrace of a panic
+    pub backtrace: Option<String>,
+}
+
+impl CrashReport {
+    /// Create a report with the environment fields filled in
+    fn new(kind: &str, message: &str) -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            timestamp: timestamps::to_rfc3339(&Utc::now()),
+            kind: kind.to_string(),
+            message: anonymize(message),
+            location: None,
+            backtrace: None,
+        }
+    }
+
+    /// Create a report for a panic
+    pub fn from_panic(info: &PanicHookInfo<'_>) -> Self {
+        let payload = info.payload();
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Box<dyn Any>".to_string());
+
+        let mut report = Self::new("panic", &message);
+        report.location = info.location().map(|l| anonymize(&format!("{}:{}:{}", l.file(), l.line(), l.column())));
+        report.backtrace = Some(anonymize(&std::backtrace::Backtrace::force_capture().to_string()));
+        report
+    }
+
+    /// Create a report for an error returned to the user
+    pub fn from_error(error: &VenvCleanerError) -> Self {
+        Self::new("error", &error.to_string())
+    }
+}
+
+/// Check whether an error is worth reporting
+///
+/// Only failures of the file system are; usage errors such as bad
+/// arguments, invalid config files or an empty scan are not.
+pub fn is_reportable(error: &VenvCleanerError) -> bool {
+    match error {
+        VenvCleanerError::Io(_)
+        | VenvCleanerError::PathError { .. }
+        | VenvCleanerError::PermissionDenied { .. }
+        | VenvCleanerError::PartlyRemoved { .. } => true,
+        VenvCleanerError::MultipleErrors(errors) => errors.iter().any(is_reportable),
+        _ => false,
+    }
+}
+
+/// Replace the home directory and user name in a message
+///
+/// # Arguments
+/// * `text` - The message to anonymize
+pub fn anonymize(text: &str) -> String {
+    let mut text = text.to_string();
+    if let Some(home) = dirs::home_dir() {
+        let home = home.display().to_string();
+        if home.len() > 1 {
+            text = text.replace(&home, "~");
+        }
+    }
+    for var in ["USER", "USERNAME"] {
+        if let Ok(user) = std::env::var(var) {
+            if user.len() > 2 {
+                text = text.replace(&user, "<user>");
+            }
+        }
+    }
+    text
+}
+
+/// Writes (and optionally submits) crash reports
+#[derive(Debug, Clone)]
+pub struct CrashReporter {
+    /// Directory reports are written to
+    report_dir: PathBuf,
+    /// Where reports are submitted, if anywhere
+    submit_url: Option<String>,
+}
+
+impl CrashReporter {
+    /// Create a reporter writing to a specific directory
+    ///
+    /// # Arguments
+    /// * `report_dir` - Directory reports are written to
+    pub fn new(report_dir: PathBuf) -> Self {
+        Self { report_dir, submit_url: None }
+    }
+
+    /// Also submit every report to this URL
+    pub fn with_submit_url(mut self, submit_url: Option<String>) -> Self {
+        self.submit_url = submit_url;
+        self
+    }
+
+    /// Create the reporter described by the config file
+    ///
+    /// # Returns
+    /// The reporter, or None when crash reports are not enabled
+    pub fn from_config(config: &TelemetryConfig) -> Option<Self> {
+        if !config.crash_reports {
+            return None;
+        }
+        let report_dir = Self::default_report_dir()?;
+        Some(Self::new(report_dir).with_submit_url(config.submit_url.clone()))
+    }
+
+    /// Get the default report directory
+    pub fn default_report_dir() -> Option<PathBuf> {
+        paths::data_dir().map(|dir| dir.join(REPORT_DIR_NAME))
+    }
+
+    /// Get the directory reports are written to
+    pub fn report_dir(&self) -> &Path {
+        &self.report_dir
+    }
+
+    /// Write a report and submit it if configured
+    ///
+    /// Failures are logged, never propagated: reporting must not turn one
+    /// failure into two.
+    ///
+    /// # Returns
+    /// The path of the written report
+    pub fn record(&self, report: &CrashReport) -> Option<PathBuf> {
+        let path = match self.write(report) {
+            Ok(path) => path,
+            Err(e) => {
+                warn!("Failed to write crash report: {}", e);
+                return None;
+            }
+        };
+        if let Some(url) = &self.submit_url {
+            if let Err(e) = submit(&path, url) {
+                warn!("Failed to submit crash report: {}", e);
+            }
+        }
+        Some(path)
+    }
+
+    /// Write a report to the report directory
+    ///
+    /// # Returns
+    /// The path of the new report file
+    pub fn write(&self, report: &CrashReport) -> Result<PathBuf> {
+        fs::create_dir_all(&self.report_dir)?;
+        let contents = serde_json::to_string_pretty(report)
+            .map_err(|e| VenvCleanerError::Io(format!("Failed to encode crash report: {}", e)))?;
+
+        let stamp = Utc::now().format("%Y%m%d-%H%M%S%.3f");
+        let path = self.report_dir.join(format!("{}-{}-{}.json", report.kind, stamp, std::process::id()));
+        fs::write(&path, contents)?;
+        debug!("Wrote crash report {}", path.display());
+        Ok(path)
+    }
+
+    /// Record every panic, then run the previously installed panic hook
+    pub fn install_panic_hook(&self) {
+        let reporter = self.clone();
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if let Some(path) = reporter.record(&CrashReport::from_panic(info)) {
+                eprintln!("A crash report was written to {}", path.display());
+            }
+            previous(info);
+        }));
+    }
+}
+
+/// POST a report file to a URL using curl
+fn submit(path: &Path, url: &str) -> Result<()> {
+    let output = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--max-time", "10"])
+        .args(["-H", "Content-Type: application/json", "--data-binary"])
+        .arg(format!("@{}", path.display()))
+        .arg(url)
+        .output()
+        .map_err(|e| VenvCleanerError::Io(format!("Cannot run curl: {}", e)))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(VenvCleanerError::Io(String::from_utf8_lossy(&output.stderr).trim().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_disabled_by_default() {
+        assert!(CrashReporter::from_config(&TelemetryConfig::default()).is_none());
+    }
+
+    #[test]
+    fn test_write_anonymized_report() {
+        let temp_dir = TempDir::new().unwrap();
+        let reporter = CrashReporter::new(temp_dir.path().join(REPORT_DIR_NAME));
+        let home = dirs::home_dir().unwrap().join("projects").join(".venv");
+        let error = VenvCleanerError::PermissionDenied { path: home.display().to_string() };
+
+        let path = reporter.write(&CrashReport::from_error(&error)).unwrap();
+        let report: CrashReport = serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(report.kind, "error");
+        assert_eq!(report.os, std::env::consts::OS);
+        assert!(report.message.contains("~/projects/.venv"), "{}", report.message);
+    }
+
+    #[test]
+    fn test_is_reportable() {
+        assert!(is_reportable(&VenvCleanerError::Io("Input/output error".to_string())));
+        assert!(!is_reportable(&VenvCleanerError::NoVenvFound));
+        assert!(!is_reportable(&VenvCleanerError::InvalidArgument("--x".to_string())));
+        assert!(is_reportable(&VenvCleanerError::MultipleErrors(vec![
+            VenvCleanerError::OperationCancelled,
+            VenvCleanerError::PermissionDenied { path: "/x".to_string() },
+        ])));
+    }
+}
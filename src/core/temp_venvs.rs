@@ -0,0 +1,73 @@
+//! Abandoned environments in the system temp directories (`--scan-temp`)
+//!
+//! CI runners and tox create throwaway environments under `/tmp` or
+//! `$TMPDIR` (`/tmp/tox-1234/.tox/py311`, `/tmp/tmpk3j1/venv`) and leave
+//! them behind when a job is killed or a runner is reused. They are rarely
+//! called `.venv`, so below a temp root any directory holding a
+//! `pyvenv.cfg` counts as an environment, hidden ones included. A job may
+//! still be using a fresh one, so only environments unused for a day are
+//! listed there.
+
+use std::path::PathBuf;
+use chrono::Duration;
+
+use super::config::DetectorConfig;
+use super::Detector;
+
+/// How long an environment in a temp directory has to be unused before it is listed
+pub fn min_age() -> Duration {
+    Duration::days(1)
+}
+
+/// Get the system temp directories: `$TMPDIR` (or the platform's default) and `/tmp`
+///
+/// # Returns
+/// The directories that exist, each once
+pub fn temp_roots() -> Vec<PathBuf> {
+    let mut roots = vec![std::env::temp_dir()];
+    if cfg!(unix) {
+        roots.push(PathBuf::from("/tmp"));
+    }
+
+    let mut seen = Vec::new();
+    roots.retain(|root| {
+        // `$TMPDIR` is often `/tmp/` or a symlink to it
+        let identity = root.canonicalize().ok();
+        let new = identity.is_some() && !seen.contains(&identity);
+        seen.push(identity);
+        new
+    });
+    roots
+}
+
+/// Get the layout environments below a temp root are recognised by
+pub fn detector() -> Detector {
+    Detector::from_config(&DetectorConfig {
+        name: "temp".to_string(),
+        patterns: vec!["*".to_string()],
+        markers: vec!["pyvenv.cfg".to_string()],
+        validators: Vec::new(),
+    })
+    .expect("the built-in temp detector is valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detector_and_roots() {
+        let temp_dir = TempDir::new().unwrap();
+        let env = temp_dir.path().join("tox-1").join(".tox").join("py311");
+        fs::create_dir_all(&env).unwrap();
+        assert!(!detector().detects(&env));
+        fs::write(env.join("pyvenv.cfg"), "home = /usr/bin\n").unwrap();
+        assert!(detector().detects(&env));
+
+        let roots = temp_roots();
+        assert!(roots.iter().all(|root| root.is_dir()));
+        assert!(!roots.is_empty());
+    }
+}
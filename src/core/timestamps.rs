@@ -0,0 +1,154 @@
+//! Showing timestamps in local time or UTC
+//!
+//! Every timestamp VenvCleaner records, from a .venv's modification time to
+//! the entries of the trash ledger, is kept as `DateTime<Utc>`, so ages and
+//! comparisons do not depend on the machine's timezone. Only output converts:
+//! to local time by default, or not at all with `--utc`, so JSON reports made
+//! on servers in different timezones diff cleanly.
+//!
+//! Dates are shown as ISO-8601 (`2025-03-01`) unless the config's
+//! `date_format` sets another strftime pattern, such as `%d/%m/%Y`; times of
+//! day keep `%H:%M:%S`. Reports written as RFC 3339 never follow it.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+
+use super::{Result, VenvCleanerError};
+
+/// Date format used unless the config sets one (ISO-8601)
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Format of the time of day shown after dates
+const TIME_FORMAT: &str = "%H:%M:%S";
+
+/// Whether output shows UTC instead of local time
+static SHOW_UTC: AtomicBool = AtomicBool::new(false);
+
+/// The configured date format, empty for the default
+static DATE_FORMAT: RwLock<String> = RwLock::new(String::new());
+
+/// Choose the timezone of all output for the rest of the run
+///
+/// # Arguments
+/// * `utc` - Whether to show UTC (`--utc`) instead of local time
+pub fn set_show_utc(utc: bool) {
+    SHOW_UTC.store(utc, Ordering::Relaxed);
+}
+
+/// Check whether output shows UTC instead of local time
+pub fn shows_utc() -> bool {
+    SHOW_UTC.load(Ordering::Relaxed)
+}
+
+/// Check that a date format is a valid strftime pattern
+///
+/// # Arguments
+/// * `format` - The pattern, e.g. `%d/%m/%Y`
+///
+/// # Returns
+/// Ok, or an error naming the pattern
+pub fn validate_date_format(format: &str) -> Result<()> {
+    if format.is_empty() || StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+        return Err(VenvCleanerError::InvalidArgument(format!(
+            "Invalid date format '{}': use strftime fields such as %Y, %m and %d",
+            format
+        )));
+    }
+    Ok(())
+}
+
+/// Choose the date format of all output for the rest of the run
+///
+/// # Arguments
+/// * `format` - A strftime pattern, or None for ISO-8601
+///
+/// # Returns
+/// Ok, or an error if the pattern is invalid (the format is then left unchanged)
+pub fn set_date_format(format: Option<&str>) -> Result<()> {
+    if let Some(format) = format {
+        validate_date_format(format)?;
+    }
+    if let Ok(mut current) = DATE_FORMAT.write() {
+        *current = format.unwrap_or_default().to_string();
+    }
+    Ok(())
+}
+
+/// Get the date format in use
+pub fn date_format() -> String {
+    match DATE_FORMAT.read() {
+        Ok(format) if !format.is_empty() => format.clone(),
+        _ => DEFAULT_DATE_FORMAT.to_string(),
+    }
+}
+
+/// Format the date of a timestamp for display
+pub fn format_date(time: &DateTime<Utc>) -> String {
+    format_in(time, &date_format(), shows_utc())
+}
+
+/// Format the date and time of day of a timestamp for display
+pub fn format_date_time(time: &DateTime<Utc>) -> String {
+    format_in(time, &format!("{} {}", date_format(), TIME_FORMAT), shows_utc())
+}
+
+/// Format the time of day of a timestamp for display
+pub fn format_time(time: &DateTime<Utc>) -> String {
+    format_in(time, TIME_FORMAT, shows_utc())
+}
+
+/// Format a timestamp as RFC 3339 for reports, in the chosen timezone
+///
+/// UTC timestamps end in `Z`; local ones carry the machine's offset.
+pub fn to_rfc3339(time: &DateTime<Utc>) -> String {
+    rfc3339_in(time, shows_utc())
+}
+
+/// Format a timestamp in UTC or local time
+fn format_in(time: &DateTime<Utc>, pattern: &str, utc: bool) -> String {
+    if utc {
+        time.format(pattern).to_string()
+    } else {
+        time.with_timezone(&Local).format(pattern).to_string()
+    }
+}
+
+/// Format a timestamp as RFC 3339 in UTC or local time
+fn rfc3339_in(time: &DateTime<Utc>, utc: bool) -> String {
+    if utc {
+        time.to_rfc3339_opts(SecondsFormat::Secs, true)
+    } else {
+        time.with_timezone(&Local).to_rfc3339_opts(SecondsFormat::Secs, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_utc_output_is_the_same_everywhere() {
+        let time = Utc.with_ymd_and_hms(2025, 3, 1, 23, 30, 0).unwrap();
+        assert_eq!(format_in(&time, "%Y-%m-%d %H:%M", true), "2025-03-01 23:30");
+        assert_eq!(rfc3339_in(&time, true), "2025-03-01T23:30:00Z");
+    }
+
+    #[test]
+    fn test_local_output_is_the_same_instant() {
+        let time = Utc.with_ymd_and_hms(2025, 3, 1, 23, 30, 0).unwrap();
+        let local = rfc3339_in(&time, false);
+        assert_eq!(DateTime::parse_from_rfc3339(&local).unwrap(), time);
+        assert_eq!(format_in(&time, "%H:%M", false), time.with_timezone(&Local).format("%H:%M").to_string());
+    }
+
+    #[test]
+    fn test_validate_date_format() {
+        assert!(validate_date_format(DEFAULT_DATE_FORMAT).is_ok());
+        assert!(validate_date_format("%d/%m/%Y").is_ok());
+        assert!(matches!(validate_date_format("%Q"), Err(VenvCleanerError::InvalidArgument(_))));
+        assert!(validate_date_format("").is_err());
+    }
+}
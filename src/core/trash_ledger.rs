@@ -0,0 +1,232 @@
+//! Record of the .venv directories VenvCleaner moved to the trash
+//!
+//! `--trash` only relocates the disk usage until the trash is emptied, and
+//! emptying the whole trash is often not an option. Every .venv trashed by
+//! VenvCleaner is therefore recorded in a small JSON ledger, so that
+//! `trash purge --older-than 30d` can remove exactly those items from the
+//! trash and leave everything else in it alone.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use super::{paths, platform, Result, VenvCleanerError};
+
+/// Largest difference, in seconds, between the time recorded in the ledger
+/// and the deletion time kept by the trash for them to be the same item
+pub const MATCH_WINDOW_SECS: i64 = 120;
+
+/// Serialises ledger updates from parallel deletions
+static LEDGER_LOCK: Mutex<()> = Mutex::new(());
+
+/// A .venv directory moved to the trash by VenvCleaner
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrashedVenv {
+    /// Where the directory was before it was trashed
+    pub path: PathBuf,
+    /// Size of the directory when it was trashed
+    pub size_bytes: u64,
+    /// When it was trashed
+    pub trashed_at: DateTime<Utc>,
+}
+
+impl TrashedVenv {
+    /// Check whether an item in the trash is this .venv
+    ///
+    /// # Arguments
+    /// * `original_path` - Where the trashed item came from
+    /// * `time_deleted` - When the trash says it was deleted, as a Unix timestamp
+    pub fn is_same_item(&self, original_path: &Path, time_deleted: i64) -> bool {
+        original_path == self.path
+            && (time_deleted - self.trashed_at.timestamp()).abs() <= MATCH_WINDOW_SECS
+    }
+}
+
+/// Outcome of purging old entries from the trash
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PurgeReport {
+    /// Entries removed from the trash (or that would be, in a dry run)
+    pub purged: Vec<TrashedVenv>,
+    /// Entries no longer in the trash (restored or emptied by other means)
+    pub gone: Vec<TrashedVenv>,
+}
+
+impl PurgeReport {
+    /// Get the space freed by the purged entries
+    pub fn freed_bytes(&self) -> u64 {
+        self.purged.iter().map(|entry| entry.size_bytes).sum()
+    }
+}
+
+/// On-disk ledger of the .venv directories VenvCleaner trashed
+#[derive(Debug)]
+pub struct TrashLedger {
+    /// Where the ledger is stored
+    path: PathBuf,
+    /// Recorded entries, oldest first
+    entries: Vec<TrashedVenv>,
+}
+
+impl TrashLedger {
+    /// Get the default location of the ledger file
+    pub fn default_path() -> Option<PathBuf> {
+        paths::data_dir().map(|dir| dir.join("trashed.json"))
+    }
+
+    /// Load the ledger from a file; a missing or unreadable file gives an empty ledger
+    ///
+    /// # Arguments
+    /// * `path` - Path to the ledger file
+    pub fn load(path: PathBuf) -> Self {
+        let entries = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        Self { path, entries }
+    }
+
+    /// Get the recorded entries, oldest first
+    pub fn entries(&self) -> &[TrashedVenv] {
+        &self.entries
+    }
+
+    /// Get the total size of the recorded entries
+    pub fn total_bytes(&self) -> u64 {
+        self.entries.iter().map(|entry| entry.size_bytes).sum()
+    }
+
+    /// Get the entries trashed longer ago than `older_than`
+    ///
+    /// # Arguments
+    /// * `older_than` - Minimum time since the entry was trashed
+    pub fn older_than(&self, older_than: Duration) -> Vec<&TrashedVenv> {
+        let cutoff = Utc::now() - older_than;
+        self.entries.iter().filter(|entry| entry.trashed_at < cutoff).collect()
+    }
+
+    /// Record a trashed .venv in the ledger at `path` and save it
+    ///
+    /// # Arguments
+    /// * `path` - Path to the ledger file
+    /// * `entry` - The trashed .venv
+    pub fn record(path: &Path, entry: TrashedVenv) -> Result<()> {
+        let _guard = LEDGER_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        debug!("Recording trashed .venv: {}", entry.path.display());
+
+        let mut ledger = Self::load(path.to_path_buf());
+        ledger.entries.push(entry);
+        ledger.save()
+    }
+
+    /// Write the ledger back to disk
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = serde_json::to_string_pretty(&self.entries)
+            .map_err(|e| VenvCleanerError::Io(format!("Failed to encode trash ledger: {}", e)))?;
+        fs::write(&self.path, contents)?;
+        Ok(())
+    }
+
+    /// Remove the entries trashed longer ago than `older_than` from the trash
+    ///
+    /// Entries that are no longer in the trash are dropped from the ledger.
+    /// Nothing else in the trash is touched.
+    ///
+    /// # Arguments
+    /// * `older_than` - Minimum time since the entry was trashed
+    /// * `dry_run` - Only report what would be purged
+    ///
+    /// # Returns
+    /// What was purged and which entries had already left the trash
+    pub fn purge(&mut self, older_than: Duration, dry_run: bool) -> Result<PurgeReport> {
+        let expired: Vec<TrashedVenv> = self.older_than(older_than).into_iter().cloned().collect();
+        if expired.is_empty() {
+            return Ok(PurgeReport::default());
+        }
+
+        let mut trash_items = platform::list_trash()?;
+        let mut to_purge = Vec::new();
+        let mut report = PurgeReport::default();
+
+        for entry in expired {
+            let position = trash_items
+                .iter()
+                .position(|item| entry.is_same_item(&item.original_path(), item.time_deleted));
+            match position {
+                Some(index) => {
+                    to_purge.push(trash_items.swap_remove(index));
+                    report.purged.push(entry);
+                }
+                None => report.gone.push(entry),
+            }
+        }
+
+        if dry_run {
+            return Ok(report);
+        }
+
+        if !to_purge.is_empty() {
+            platform::purge_from_trash(to_purge)?;
+        }
+        self.entries.retain(|entry| !report.purged.contains(entry) && !report.gone.contains(entry));
+        self.save()?;
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(path: &str, days_ago: i64) -> TrashedVenv {
+        TrashedVenv {
+            path: PathBuf::from(path),
+            size_bytes: 1024,
+            trashed_at: Utc::now() - Duration::days(days_ago),
+        }
+    }
+
+    #[test]
+    fn test_record_and_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("nested").join("trashed.json");
+
+        TrashLedger::record(&path, entry("/work/a/.venv", 40)).unwrap();
+        TrashLedger::record(&path, entry("/work/b/.venv", 2)).unwrap();
+
+        let ledger = TrashLedger::load(path);
+        assert_eq!(ledger.entries().len(), 2);
+        assert_eq!(ledger.total_bytes(), 2048);
+        assert_eq!(ledger.entries()[0].path, PathBuf::from("/work/a/.venv"));
+    }
+
+    #[test]
+    fn test_older_than() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("trashed.json");
+        TrashLedger::record(&path, entry("/work/a/.venv", 40)).unwrap();
+        TrashLedger::record(&path, entry("/work/b/.venv", 2)).unwrap();
+
+        let ledger = TrashLedger::load(path);
+        let old = ledger.older_than(Duration::days(30));
+        assert_eq!(old.len(), 1);
+        assert_eq!(old[0].path, PathBuf::from("/work/a/.venv"));
+        assert!(ledger.older_than(Duration::days(60)).is_empty());
+    }
+
+    #[test]
+    fn test_is_same_item() {
+        let trashed = entry("/work/a/.venv", 0);
+        let when = trashed.trashed_at.timestamp();
+
+        assert!(trashed.is_same_item(Path::new("/work/a/.venv"), when + 1));
+        assert!(!trashed.is_same_item(Path::new("/work/a/.venv"), when + MATCH_WINDOW_SECS + 1));
+        assert!(!trashed.is_same_item(Path::new("/work/b/.venv"), when));
+    }
+}
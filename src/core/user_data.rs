@@ -0,0 +1,129 @@
+//! Detection of user files stored inside a .venv
+//!
+//! Notebooks, datasets and archives sometimes get saved into a virtual
+//! environment by accident, and deleting the environment then loses work
+//! that cannot be reinstalled. This module flags large files with typical
+//! data extensions found outside `site-packages` (packages legitimately
+//! ship test data there), so that the UIs can ask for extra confirmation.
+
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Extensions of files that are usually user data rather than part of an environment
+pub const USER_DATA_EXTENSIONS: &[&str] = &[
+    "csv", "tsv", "ipynb", "zip", "tar", "gz", "parquet", "xlsx", "xls", "h5", "hdf5", "pkl", "npy", "npz", "sqlite", "db",
+];
+
+/// Size from which a file with a data extension counts as user data
+pub const USER_DATA_THRESHOLD_BYTES: u64 = 1024 * 1024;
+
+/// A file inside a .venv that looks like user data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserDataFile {
+    /// Path to the file
+    pub path: PathBuf,
+    /// Size of the file in bytes
+    pub size_bytes: u64,
+}
+
+/// Find the files inside a .venv that look like user data
+///
+/// # Arguments
+/// * `venv_path` - Path to the .venv directory
+/// * `threshold_bytes` - Minimum size of a flagged file
+///
+/// # Returns
+/// The flagged files, largest first
+pub fn find_user_data(venv_path: &Path, threshold_bytes: u64) -> Vec<UserDataFile> {
+    let files = WalkDir::new(venv_path)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|entry| entry.file_name() != "site-packages")
+        .flatten()
+        .filter(|entry| entry.file_type().is_file() && has_data_extension(entry.path()))
+        .filter_map(|entry| {
+            let size_bytes = entry.metadata().ok()?.len();
+            (size_bytes >= threshold_bytes).then(|| UserDataFile {
+                path: entry.into_path(),
+                size_bytes,
+            })
+        })
+        .collect();
+
+    largest_first(files)
+}
+
+/// Check whether a file met while walking a .venv looks like user data
+///
+/// For walks that visit every file anyway, such as sizing the .venv.
+///
+/// # Arguments
+/// * `venv_path` - Path to the .venv directory
+/// * `path` - Path to the file
+/// * `size_bytes` - Size of the file
+/// * `threshold_bytes` - Minimum size of a flagged file
+pub fn is_user_data(venv_path: &Path, path: &Path, size_bytes: u64, threshold_bytes: u64) -> bool {
+    let in_site_packages = path
+        .strip_prefix(venv_path)
+        .map(|relative| relative.components().any(|component| component.as_os_str() == "site-packages"))
+        .unwrap_or(false);
+    size_bytes >= threshold_bytes && !in_site_packages && has_data_extension(path)
+}
+
+/// Sort flagged files largest first, as `find_user_data` returns them
+pub fn largest_first(mut files: Vec<UserDataFile>) -> Vec<UserDataFile> {
+    files.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then_with(|| a.path.cmp(&b.path)));
+    files
+}
+
+/// Check whether a file has one of the user data extensions
+fn has_data_extension(path: &Path) -> bool {
+    path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .map(|e| USER_DATA_EXTENSIONS.contains(&e.as_str()))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_finds_large_data_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = temp_dir.path().join(".venv");
+        fs::create_dir_all(venv.join("notebooks")).unwrap();
+        fs::write(venv.join("results.CSV"), vec![0u8; 300]).unwrap();
+        fs::write(venv.join("notebooks").join("analysis.ipynb"), vec![0u8; 200]).unwrap();
+        fs::write(venv.join("small.zip"), vec![0u8; 10]).unwrap();
+        fs::write(venv.join("pyvenv.cfg"), vec![0u8; 500]).unwrap();
+
+        let files = find_user_data(&venv, 100);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, venv.join("results.CSV"));
+        assert_eq!(files[1].size_bytes, 200);
+    }
+
+    #[test]
+    fn test_ignores_site_packages() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = temp_dir.path().join(".venv");
+        let site_packages = venv.join("lib").join("python3.11").join("site-packages").join("sklearn");
+        fs::create_dir_all(&site_packages).unwrap();
+        fs::write(site_packages.join("iris.csv"), vec![0u8; 300]).unwrap();
+
+        assert!(find_user_data(&venv, 100).is_empty());
+        assert!(find_user_data(&temp_dir.path().join("missing"), 100).is_empty());
+    }
+
+    #[test]
+    fn test_is_user_data() {
+        let venv = Path::new("/p/.venv");
+        assert!(is_user_data(venv, &venv.join("data").join("big.parquet"), 300, 100));
+        assert!(!is_user_data(venv, &venv.join("data").join("small.parquet"), 10, 100));
+        assert!(!is_user_data(venv, &venv.join("bin").join("python"), 300, 100));
+        assert!(!is_user_data(venv, &venv.join("lib").join("site-packages").join("iris.csv"), 300, 100));
+    }
+}
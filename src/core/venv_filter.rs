@@ -0,0 +1,132 @@
+//! Age and size criteria for the .venv directories a run works on
+//!
+//! `--older-than 90d` and `--min-size 200MB` narrow a scan to the
+//! environments a cleanup policy is about. The CLI then lists or cleans only
+//! those; the TUI and GUI start with just those listed and already selected,
+//! so a policy-based cleanup becomes "review, then confirm".
+//!
+//! Automated runs say why each environment was picked, e.g.
+//! "age 142d >= 120d AND size 2.10 GB >= 1.00 GB", in the log and the run
+//! summary, so a surprising deletion can be traced back to its rule.
+
+use chrono::{Duration, Utc};
+
+use super::{ByteSize, VenvInfo};
+
+/// Criteria a .venv has to meet to be kept by a scan; the default keeps everything
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct VenvFilter {
+    /// Minimum time since the .venv was last used
+    older_than: Option<Duration>,
+    /// Minimum size
+    min_size: Option<ByteSize>,
+}
+
+impl VenvFilter {
+    /// Keep only .venv directories unused for at least this long
+    pub fn with_older_than(mut self, older_than: Option<Duration>) -> Self {
+        self.older_than = older_than;
+        self
+    }
+
+    /// Keep only .venv directories of at least this size
+    pub fn with_min_size(mut self, min_size: Option<ByteSize>) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Get the minimum time since last use, if set
+    pub fn older_than(&self) -> Option<Duration> {
+        self.older_than
+    }
+
+    /// Check whether any criterion is set
+    pub fn is_active(&self) -> bool {
+        self.older_than.is_some() || self.min_size.is_some()
+    }
+
+    /// Check whether a .venv meets every criterion
+    ///
+    /// # Arguments
+    /// * `venv` - The scanned .venv
+    pub fn matches(&self, venv: &VenvInfo) -> bool {
+        let old_enough = self
+            .older_than
+            .is_none_or(|older_than| *venv.last_modified() <= Utc::now() - older_than);
+        let large_enough = self.min_size.is_none_or(|min_size| venv.size() >= min_size);
+        old_enough && large_enough
+    }
+
+    /// Describe how a .venv measures up to each criterion, e.g. "age 142d >= 120d AND size 2.10 GB >= 1.00 GB"
+    ///
+    /// # Arguments
+    /// * `venv` - The scanned .venv
+    ///
+    /// # Returns
+    /// The comparisons, a failing one with `<`, or None if no criterion is set
+    pub fn explain(&self, venv: &VenvInfo) -> Option<String> {
+        let mut parts = Vec::new();
+        if let Some(older_than) = self.older_than {
+            let age = Utc::now() - *venv.last_modified();
+            let operator = if age >= older_than { ">=" } else { "<" };
+            // Hour criteria such as 12h would read 0d >= 0d in days
+            let (age, limit) = if older_than.num_hours() % 24 == 0 {
+                (format!("{}d", age.num_days()), format!("{}d", older_than.num_days()))
+            } else {
+                (format!("{}h", age.num_hours()), format!("{}h", older_than.num_hours()))
+            };
+            parts.push(format!("age {} {} {}", age, operator, limit));
+        }
+        if let Some(min_size) = self.min_size {
+            let operator = if venv.size() >= min_size { ">=" } else { "<" };
+            parts.push(format!("size {} {} {}", venv.size_formatted(), operator, min_size));
+        }
+        (!parts.is_empty()).then(|| parts.join(" AND "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn venv(size: u64, age_days: i64) -> VenvInfo {
+        let last_used = Utc::now() - Duration::days(age_days);
+        VenvInfo::new(PathBuf::from("/p/.venv"), ByteSize::new(size), last_used, last_used)
+    }
+
+    #[test]
+    fn test_default_filter_keeps_everything() {
+        let filter = VenvFilter::default();
+        assert!(!filter.is_active());
+        assert!(filter.matches(&venv(0, 0)));
+    }
+
+    #[test]
+    fn test_filter_requires_every_criterion() {
+        let filter = VenvFilter::default()
+            .with_older_than(Some(Duration::days(90)))
+            .with_min_size(Some(ByteSize::new(200)));
+        assert!(filter.is_active());
+        assert!(filter.matches(&venv(200, 120)));
+        assert!(!filter.matches(&venv(100, 120)));
+        assert!(!filter.matches(&venv(500, 30)));
+    }
+
+    #[test]
+    fn test_explain_names_each_comparison() {
+        assert_eq!(VenvFilter::default().explain(&venv(0, 0)), None);
+
+        let filter = VenvFilter::default()
+            .with_older_than(Some(Duration::days(120)))
+            .with_min_size(Some(ByteSize::new(ByteSize::GB)));
+        assert_eq!(
+            filter.explain(&venv(2 * 1024 * 1024 * 1024, 142)).as_deref(),
+            Some("age 142d >= 120d AND size 2.00 GB >= 1.00 GB")
+        );
+        assert_eq!(filter.explain(&venv(512, 30)).as_deref(), Some("age 30d < 120d AND size 512 bytes < 1.00 GB"));
+
+        let hours = VenvFilter::default().with_older_than(Some(Duration::hours(12)));
+        assert_eq!(hours.explain(&venv(0, 1)).as_deref(), Some("age 24h >= 12h"));
+    }
+}
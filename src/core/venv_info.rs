@@ -1,23 +1,101 @@
 //! VenvInfo module for storing and managing information about .venv directories
 //!
 //! This module contains the VenvInfo struct which holds all relevant information
-//! about a Python virtual environment directory, including path, size, and timestamps.
+//! about a Python virtual environment directory, including path, size, and timestamps,
+//! and the AgeBucket ranges used to group environments by how long ago they were used.
 
 use std::path::{Path, PathBuf};
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Utc};
 use std::fmt;
 
+use super::{timestamps, ByteSize, FileUtils, ProjectKind, PythonVersion};
+use super::user_data::UserDataFile;
+
+/// Ranges of days since a .venv was last used
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AgeBucket {
+    /// Used in the last 30 days
+    Recent,
+    /// Last used 31 to 90 days ago
+    Aging,
+    /// Last used 91 to 180 days ago
+    Stale,
+    /// Not used for more than 180 days
+    Abandoned,
+}
+
+impl AgeBucket {
+    /// All buckets, newest first
+    pub const ALL: [AgeBucket; 4] = [
+        AgeBucket::Recent,
+        AgeBucket::Aging,
+        AgeBucket::Stale,
+        AgeBucket::Abandoned,
+    ];
+
+    /// Get the bucket for an age in days
+    ///
+    /// # Arguments
+    /// * `days` - Days since last use (negative ages, from clock skew, count as recent)
+    pub fn from_days(days: i64) -> Self {
+        match days {
+            i64::MIN..=30 => AgeBucket::Recent,
+            31..=90 => AgeBucket::Aging,
+            91..=180 => AgeBucket::Stale,
+            _ => AgeBucket::Abandoned,
+        }
+    }
+
+    /// Get the display label of this bucket
+    pub fn label(self) -> &'static str {
+        match self {
+            AgeBucket::Recent => "0–30 days",
+            AgeBucket::Aging => "31–90 days",
+            AgeBucket::Stale => "91–180 days",
+            AgeBucket::Abandoned => ">180 days",
+        }
+    }
+}
+
+impl fmt::Display for AgeBucket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
 /// Information about a Python virtual environment directory
 #[derive(Debug, Clone, PartialEq)]
 pub struct VenvInfo {
     /// Full path to the .venv directory
     path: PathBuf,
-    /// Size of the directory in bytes
-    size_bytes: u64,
+    /// Size of the directory
+    size: ByteSize,
+    /// Whether sizing stopped early, making `size` a lower bound
+    size_partial: bool,
     /// When the directory was created
-    created: DateTime<Local>,
+    created: DateTime<Utc>,
     /// When the directory was last modified (last used)
-    last_modified: DateTime<Local>,
+    last_modified: DateTime<Utc>,
+    /// Python interpreter version recorded in pyvenv.cfg, if any
+    python_version: Option<PythonVersion>,
+    /// When a package was last installed by pip, if any package records exist
+    last_install: Option<DateTime<Utc>>,
+    /// Whether the .venv holds an editable install of its parent project
+    editable_install: bool,
+    /// Kind of the parent project, if recognised by its markers
+    project_kind: Option<ProjectKind>,
+    /// When the parent project last had a git commit, once looked up (scans leave it unset)
+    project_last_commit: Option<DateTime<Utc>>,
+    /// Type of the file system the directory lives on, e.g. "ext4" or "nfs4"
+    filesystem: Option<String>,
+    /// Mount point of the removable drive the directory lives on, if any
+    removable_volume: Option<PathBuf>,
+    /// Large files that look like user data rather than part of the environment
+    user_data: Vec<UserDataFile>,
+    /// User id of the directory's owner, where the platform records one
+    owner_uid: Option<u32>,
+    /// Number of files and subdirectories, if they were counted
+    item_counts: Option<(usize, usize)>,
 }
 
 impl VenvInfo {
@@ -25,7 +103,7 @@ impl VenvInfo {
     ///
     /// # Arguments
     /// * `path` - Full path to the .venv directory
-    /// * `size_bytes` - Size of the directory in bytes
+    /// * `size` - Size of the directory
     /// * `created` - Creation timestamp
     /// * `last_modified` - Last modification timestamp
     ///
@@ -33,18 +111,179 @@ impl VenvInfo {
     /// A new VenvInfo instance
     pub fn new(
         path: PathBuf,
-        size_bytes: u64,
-        created: DateTime<Local>,
-        last_modified: DateTime<Local>,
+        size: ByteSize,
+        created: DateTime<Utc>,
+        last_modified: DateTime<Utc>,
     ) -> Self {
         Self {
             path,
-            size_bytes,
+            size,
+            size_partial: false,
             created,
             last_modified,
+            python_version: None,
+            last_install: None,
+            editable_install: false,
+            project_kind: None,
+            project_last_commit: None,
+            filesystem: None,
+            removable_volume: None,
+            user_data: Vec::new(),
+            owner_uid: None,
+            item_counts: None,
         }
     }
 
+    /// Mark the size as a lower bound because sizing stopped early
+    ///
+    /// # Arguments
+    /// * `size_partial` - Whether not every file was counted
+    ///
+    /// # Returns
+    /// The VenvInfo with the flag set
+    pub fn with_size_partial(mut self, size_partial: bool) -> Self {
+        self.size_partial = size_partial;
+        self
+    }
+
+    /// Attach the Python interpreter version this .venv was built with
+    ///
+    /// # Arguments
+    /// * `python_version` - The version read from pyvenv.cfg, if any
+    ///
+    /// # Returns
+    /// The VenvInfo with the version set
+    pub fn with_python_version(mut self, python_version: Option<PythonVersion>) -> Self {
+        self.python_version = python_version;
+        self
+    }
+
+    /// Attach the time a package was last installed into this .venv
+    ///
+    /// # Arguments
+    /// * `last_install` - The newest pip install-record timestamp, if any
+    ///
+    /// # Returns
+    /// The VenvInfo with the install time set
+    pub fn with_last_install(mut self, last_install: Option<DateTime<Utc>>) -> Self {
+        self.last_install = last_install;
+        self
+    }
+
+    /// Mark whether this .venv holds an editable install of its parent project
+    ///
+    /// # Arguments
+    /// * `editable_install` - Whether an editable install points into the project
+    ///
+    /// # Returns
+    /// The VenvInfo with the flag set
+    pub fn with_editable_install(mut self, editable_install: bool) -> Self {
+        self.editable_install = editable_install;
+        self
+    }
+
+    /// Attach the kind of the parent project
+    ///
+    /// # Arguments
+    /// * `project_kind` - The kind found by `ProjectKind::detect`, if any
+    ///
+    /// # Returns
+    /// The VenvInfo with the kind set
+    pub fn with_project_kind(mut self, project_kind: Option<ProjectKind>) -> Self {
+        self.project_kind = project_kind;
+        self
+    }
+
+    /// Attach the type of the file system this .venv lives on
+    ///
+    /// # Arguments
+    /// * `filesystem` - The file system type, if known
+    ///
+    /// # Returns
+    /// The VenvInfo with the file system set
+    pub fn with_filesystem(mut self, filesystem: Option<String>) -> Self {
+        self.filesystem = filesystem;
+        self
+    }
+
+    /// Attach the removable drive this .venv lives on
+    ///
+    /// # Arguments
+    /// * `removable_volume` - The drive's mount point, if the .venv is on one
+    ///
+    /// # Returns
+    /// The VenvInfo with the drive set
+    pub fn with_removable_volume(mut self, removable_volume: Option<PathBuf>) -> Self {
+        self.removable_volume = removable_volume;
+        self
+    }
+
+    /// Attach the owner of this .venv
+    ///
+    /// # Arguments
+    /// * `owner_uid` - The owner's user id, if known
+    ///
+    /// # Returns
+    /// The VenvInfo with the owner set
+    pub fn with_owner_uid(mut self, owner_uid: Option<u32>) -> Self {
+        self.owner_uid = owner_uid;
+        self
+    }
+
+    /// Get the user id of this .venv's owner, if known
+    pub fn owner_uid(&self) -> Option<u32> {
+        self.owner_uid
+    }
+
+    /// Attach the number of files and subdirectories in this .venv
+    ///
+    /// # Arguments
+    /// * `item_counts` - The (file, directory) counts found while sizing the .venv, if counted
+    ///
+    /// # Returns
+    /// The VenvInfo with the counts set
+    pub fn with_item_counts(mut self, item_counts: Option<(usize, usize)>) -> Self {
+        self.item_counts = item_counts;
+        self
+    }
+
+    /// Get the number of files in this .venv, if counted
+    pub fn file_count(&self) -> Option<usize> {
+        self.item_counts.map(|(files, _)| files)
+    }
+
+    /// Get the number of subdirectories in this .venv, if counted
+    pub fn dir_count(&self) -> Option<usize> {
+        self.item_counts.map(|(_, dirs)| dirs)
+    }
+
+    /// Get the file count formatted with thousands separators
+    ///
+    /// # Returns
+    /// The count, such as "215,307", or "-" if the files were not counted
+    pub fn file_count_formatted(&self) -> String {
+        self.file_count()
+            .map(|files| FileUtils::format_count(files as u64))
+            .unwrap_or_else(|| "-".to_string())
+    }
+
+    /// Get the number of inodes this .venv uses: its files and directories, itself included
+    ///
+    /// Symlinks and hard links are not told apart, so this is an estimate.
+    pub fn inode_count(&self) -> Option<u64> {
+        self.item_counts.map(|(files, dirs)| (files + dirs + 1) as u64)
+    }
+
+    /// Get the type of the file system this .venv lives on, if known
+    pub fn filesystem(&self) -> Option<&str> {
+        self.filesystem.as_deref()
+    }
+
+    /// Get the mount point of the removable drive this .venv lives on, if any
+    pub fn removable_volume(&self) -> Option<&Path> {
+        self.removable_volume.as_deref()
+    }
+
     /// Get the path to the .venv directory
     pub fn path(&self) -> &Path {
         &self.path
@@ -55,79 +294,183 @@ impl VenvInfo {
         self.path.parent()
     }
 
-    /// Get the size in bytes
-    pub fn size_bytes(&self) -> u64 {
-        self.size_bytes
+    /// Get the size
+    pub fn size(&self) -> ByteSize {
+        self.size
+    }
+
+    /// Check whether the size is only a lower bound (sizing timed out)
+    pub fn is_size_partial(&self) -> bool {
+        self.size_partial
     }
 
     /// Get the size formatted as a human-readable string
     ///
     /// # Returns
-    /// Size formatted as MB or GB depending on the size
+    /// Size formatted as MB or GB depending on the size, prefixed with `≥`
+    /// when the size is partial
     pub fn size_formatted(&self) -> String {
-        const KB: u64 = 1024;
-        const MB: u64 = KB * 1024;
-        const GB: u64 = MB * 1024;
-
-        if self.size_bytes >= GB {
-            format!("{:.2} GB", self.size_bytes as f64 / GB as f64)
-        } else if self.size_bytes >= MB {
-            format!("{:.2} MB", self.size_bytes as f64 / MB as f64)
-        } else if self.size_bytes >= KB {
-            format!("{:.2} KB", self.size_bytes as f64 / KB as f64)
+        let size = self.size().to_string();
+
+        if self.size_partial {
+            format!("≥ {}", size)
         } else {
-            format!("{} bytes", self.size_bytes)
+            size
         }
     }
 
+    /// Get the Python interpreter version, if it could be determined
+    pub fn python_version(&self) -> Option<PythonVersion> {
+        self.python_version
+    }
+
     /// Get the creation timestamp
-    pub fn created(&self) -> &DateTime<Local> {
+    pub fn created(&self) -> &DateTime<Utc> {
         &self.created
     }
 
     /// Get the creation date formatted as a string
     pub fn created_formatted(&self) -> String {
-        self.created.format("%Y-%m-%d %H:%M:%S").to_string()
+        timestamps::format_date_time(&self.created)
     }
 
     /// Get the last modified timestamp
-    pub fn last_modified(&self) -> &DateTime<Local> {
+    pub fn last_modified(&self) -> &DateTime<Utc> {
         &self.last_modified
     }
 
     /// Get the last modified date formatted as a string
     pub fn last_modified_formatted(&self) -> String {
-        self.last_modified.format("%Y-%m-%d %H:%M:%S").to_string()
+        timestamps::format_date_time(&self.last_modified)
+    }
+
+    /// Get the time a package was last installed by pip, if known
+    pub fn last_install(&self) -> Option<&DateTime<Utc>> {
+        self.last_install.as_ref()
+    }
+
+    /// Get the last pip install date formatted as a string ("-" if unknown)
+    pub fn last_install_formatted(&self) -> String {
+        self.last_install
+            .map(|t| timestamps::format_date_time(&t))
+            .unwrap_or_else(|| "-".to_string())
+    }
+
+    /// Attach the files inside this .venv that look like user data
+    ///
+    /// # Arguments
+    /// * `user_data` - The flagged files, largest first
+    ///
+    /// # Returns
+    /// The VenvInfo with the files set
+    pub fn with_user_data(mut self, user_data: Vec<UserDataFile>) -> Self {
+        self.user_data = user_data;
+        self
+    }
+
+    /// Get the files inside this .venv that look like user data
+    pub fn user_data(&self) -> &[UserDataFile] {
+        &self.user_data
+    }
+
+    /// Check if the .venv contains files that look like user data
+    ///
+    /// Such files cannot be reinstalled, so deleting the .venv needs
+    /// extra confirmation.
+    pub fn has_user_data(&self) -> bool {
+        !self.user_data.is_empty()
+    }
+
+    /// Get the total size of the files that look like user data
+    pub fn user_data_bytes(&self) -> u64 {
+        self.user_data.iter().map(|file| file.size_bytes).sum()
+    }
+
+    /// Check if the project is installed in editable (development) mode
+    ///
+    /// Deleting such a .venv breaks an active development setup, so callers
+    /// should flag it prominently.
+    pub fn has_editable_install(&self) -> bool {
+        self.editable_install
+    }
+
+    /// Get the kind of the parent project, if recognised
+    pub fn project_kind(&self) -> Option<ProjectKind> {
+        self.project_kind
+    }
+
+    /// Attach the time of the parent project's last git commit
+    ///
+    /// # Arguments
+    /// * `project_last_commit` - The last commit touching the project directory, if any
+    ///
+    /// # Returns
+    /// The VenvInfo with the commit time set
+    pub fn with_project_last_commit(mut self, project_last_commit: Option<DateTime<Utc>>) -> Self {
+        self.project_last_commit = project_last_commit;
+        self
+    }
+
+    /// Get the time of the parent project's last git commit, if known
+    pub fn project_last_commit(&self) -> Option<&DateTime<Utc>> {
+        self.project_last_commit.as_ref()
+    }
+
+    /// Get the parent project's last commit date formatted as a string ("-" if unknown)
+    pub fn project_last_commit_formatted(&self) -> String {
+        self.project_last_commit
+            .map(|t| timestamps::format_date(&t))
+            .unwrap_or_else(|| "-".to_string())
+    }
+
+    /// Check if the .venv looks old while its project still gets commits
+    ///
+    /// Such environments are usually still needed: the packages simply have
+    /// not changed for a while.
+    pub fn is_project_active(&self) -> bool {
+        let ninety_days_ago = Utc::now() - chrono::Duration::days(90);
+        self.is_old() && self.project_last_commit.map(|t| t > ninety_days_ago).unwrap_or(false)
     }
 
     /// Get the project name (directory name containing the .venv)
     pub fn project_name(&self) -> Option<String> {
         self.parent_path()
             .and_then(|p| p.file_name())
-            .and_then(|name| name.to_str())
-            .map(|s| s.to_string())
+            .map(|name| name.to_string_lossy().to_string())
     }
 
     /// Check if this .venv was recently used (within the last 30 days)
     pub fn is_recently_used(&self) -> bool {
-        let now = Local::now();
+        let now = Utc::now();
         let thirty_days_ago = now - chrono::Duration::days(30);
         self.last_modified > thirty_days_ago
     }
 
     /// Check if this .venv is old (not modified in the last 90 days)
     pub fn is_old(&self) -> bool {
-        let now = Local::now();
+        let now = Utc::now();
         let ninety_days_ago = now - chrono::Duration::days(90);
         self.last_modified < ninety_days_ago
     }
 
     /// Get age in days since last modification
     pub fn age_in_days(&self) -> i64 {
-        let now = Local::now();
+        self.age_in_days_at(Utc::now())
+    }
+
+    /// Get age in days since last modification, as of a given time
+    ///
+    /// # Arguments
+    /// * `now` - The time the age is measured at, e.g. when a report was started
+    pub fn age_in_days_at(&self, now: DateTime<Utc>) -> i64 {
         (now - self.last_modified).num_days()
     }
 
+    /// Get the age bucket this .venv falls into
+    pub fn age_bucket(&self) -> AgeBucket {
+        AgeBucket::from_days(self.age_in_days())
+    }
+
     /// Get a summary string for display purposes
     pub fn summary(&self) -> String {
         format!(
@@ -164,7 +507,12 @@ impl fmt::Display for VenvInfo {
 impl VenvInfo {
     /// Compare by size (largest first)
     pub fn compare_by_size(&self, other: &Self) -> std::cmp::Ordering {
-        other.size_bytes.cmp(&self.size_bytes)
+        other.size.cmp(&self.size)
+    }
+
+    /// Compare by file count (most files first, uncounted last)
+    pub fn compare_by_file_count(&self, other: &Self) -> std::cmp::Ordering {
+        other.file_count().cmp(&self.file_count())
     }
 
     /// Compare by creation date (newest first)
@@ -177,9 +525,9 @@ impl VenvInfo {
         other.last_modified.cmp(&self.last_modified)
     }
 
-    /// Compare by path (alphabetical)
+    /// Compare by path, in natural order (see `sorting::compare_paths`)
     pub fn compare_by_path(&self, other: &Self) -> std::cmp::Ordering {
-        self.path.cmp(&other.path)
+        super::sorting::compare_paths(&self.path, &other.path)
     }
 }
 
@@ -191,17 +539,17 @@ mod tests {
     fn create_test_venv_info() -> VenvInfo {
         let path = PathBuf::from("/home/user/project/.venv");
         let size = 1024 * 1024 * 100; // 100 MB
-        let created = Local::now() - chrono::Duration::days(10);
-        let modified = Local::now() - chrono::Duration::days(5);
+        let created = Utc::now() - chrono::Duration::days(10);
+        let modified = Utc::now() - chrono::Duration::days(5);
 
-        VenvInfo::new(path, size, created, modified)
+        VenvInfo::new(path, ByteSize::new(size), created, modified)
     }
 
     #[test]
     fn test_venv_info_creation() {
         let venv_info = create_test_venv_info();
         assert_eq!(venv_info.path(), Path::new("/home/user/project/.venv"));
-        assert_eq!(venv_info.size_bytes(), 1024 * 1024 * 100);
+        assert_eq!(venv_info.size(), ByteSize::new(1024 * 1024 * 100));
     }
 
     #[test]
@@ -215,8 +563,8 @@ mod tests {
     fn test_size_formatting_gb() {
         let path = PathBuf::from("/test/.venv");
         let size = 1024 * 1024 * 1024 * 2; // 2 GB
-        let now = Local::now();
-        let venv_info = VenvInfo::new(path, size, now, now);
+        let now = Utc::now();
+        let venv_info = VenvInfo::new(path, ByteSize::new(size), now, now);
 
         let formatted = venv_info.size_formatted();
         assert!(formatted.contains("GB"));
@@ -226,8 +574,8 @@ mod tests {
     fn test_size_formatting_kb() {
         let path = PathBuf::from("/test/.venv");
         let size = 1024 * 500; // 500 KB
-        let now = Local::now();
-        let venv_info = VenvInfo::new(path, size, now, now);
+        let now = Utc::now();
+        let venv_info = VenvInfo::new(path, ByteSize::new(size), now, now);
 
         let formatted = venv_info.size_formatted();
         assert!(formatted.contains("KB"));
@@ -237,13 +585,23 @@ mod tests {
     fn test_size_formatting_bytes() {
         let path = PathBuf::from("/test/.venv");
         let size = 512; // 512 bytes
-        let now = Local::now();
-        let venv_info = VenvInfo::new(path, size, now, now);
+        let now = Utc::now();
+        let venv_info = VenvInfo::new(path, ByteSize::new(size), now, now);
 
         let formatted = venv_info.size_formatted();
         assert!(formatted.contains("bytes"));
     }
 
+    #[test]
+    fn test_size_formatting_partial() {
+        let venv_info = create_test_venv_info();
+        assert!(!venv_info.is_size_partial());
+
+        let venv_info = venv_info.with_size_partial(true);
+        assert!(venv_info.is_size_partial());
+        assert_eq!(venv_info.size_formatted(), "≥ 100.00 MB");
+    }
+
     #[test]
     fn test_project_name() {
         let venv_info = create_test_venv_info();
@@ -253,9 +611,9 @@ mod tests {
     #[test]
     fn test_is_recently_used() {
         let path = PathBuf::from("/test/.venv");
-        let now = Local::now();
+        let now = Utc::now();
         let recent = now - chrono::Duration::days(10);
-        let venv_info = VenvInfo::new(path, 1024, now, recent);
+        let venv_info = VenvInfo::new(path, ByteSize::new(1024), now, recent);
 
         assert!(venv_info.is_recently_used());
     }
@@ -263,9 +621,9 @@ mod tests {
     #[test]
     fn test_is_old() {
         let path = PathBuf::from("/test/.venv");
-        let now = Local::now();
+        let now = Utc::now();
         let old = now - chrono::Duration::days(100);
-        let venv_info = VenvInfo::new(path, 1024, now, old);
+        let venv_info = VenvInfo::new(path, ByteSize::new(1024), now, old);
 
         assert!(venv_info.is_old());
     }
@@ -273,13 +631,28 @@ mod tests {
     #[test]
     fn test_age_calculation() {
         let path = PathBuf::from("/test/.venv");
-        let now = Local::now();
+        let now = Utc::now();
         let modified = now - chrono::Duration::days(15);
-        let venv_info = VenvInfo::new(path, 1024, now, modified);
+        let venv_info = VenvInfo::new(path, ByteSize::new(1024), now, modified);
 
         assert_eq!(venv_info.age_in_days(), 15);
     }
 
+    #[test]
+    fn test_age_bucket() {
+        assert_eq!(AgeBucket::from_days(0), AgeBucket::Recent);
+        assert_eq!(AgeBucket::from_days(30), AgeBucket::Recent);
+        assert_eq!(AgeBucket::from_days(31), AgeBucket::Aging);
+        assert_eq!(AgeBucket::from_days(90), AgeBucket::Aging);
+        assert_eq!(AgeBucket::from_days(180), AgeBucket::Stale);
+        assert_eq!(AgeBucket::from_days(181), AgeBucket::Abandoned);
+
+        let old = Utc::now() - chrono::Duration::days(120);
+        let venv_info = VenvInfo::new(PathBuf::from("/p/.venv"), ByteSize::new(0), old, old);
+        assert_eq!(venv_info.age_bucket(), AgeBucket::Stale);
+        assert_eq!(venv_info.age_bucket().label(), "91–180 days");
+    }
+
     #[test]
     fn test_display_formatting() {
         let venv_info = create_test_venv_info();
@@ -288,6 +661,79 @@ mod tests {
         assert!(display_str.contains("/home/user/project/.venv"));
     }
 
+    #[test]
+    fn test_python_version() {
+        let venv_info = create_test_venv_info();
+        assert!(venv_info.python_version().is_none());
+
+        let venv_info = venv_info.with_python_version(PythonVersion::parse("3.11.4"));
+        assert_eq!(venv_info.python_version().unwrap().release_line(), "3.11");
+    }
+
+    #[test]
+    fn test_last_install() {
+        let venv_info = create_test_venv_info();
+        assert!(venv_info.last_install().is_none());
+        assert_eq!(venv_info.last_install_formatted(), "-");
+
+        let installed = Utc::now() - chrono::Duration::days(3);
+        let venv_info = venv_info.with_last_install(Some(installed));
+        assert_eq!(venv_info.last_install(), Some(&installed));
+        assert_eq!(venv_info.last_install_formatted(), timestamps::format_date_time(&installed));
+    }
+
+    #[test]
+    fn test_project_last_commit() {
+        let old = Utc::now() - chrono::Duration::days(200);
+        let venv_info = VenvInfo::new(PathBuf::from("/home/me/proj/.venv"), ByteSize::new(10), old, old);
+        assert_eq!(venv_info.project_last_commit_formatted(), "-");
+        assert!(!venv_info.is_project_active());
+
+        let committed = Utc::now() - chrono::Duration::days(3);
+        let venv_info = venv_info.with_project_last_commit(Some(committed));
+        assert_eq!(venv_info.project_last_commit(), Some(&committed));
+        assert_eq!(venv_info.project_last_commit_formatted(), timestamps::format_date(&committed));
+        assert!(venv_info.is_project_active());
+    }
+
+    #[test]
+    fn test_editable_install_flag() {
+        let venv_info = create_test_venv_info();
+        assert!(!venv_info.has_editable_install());
+        assert!(venv_info.with_editable_install(true).has_editable_install());
+    }
+
+    #[test]
+    fn test_user_data() {
+        let venv_info = create_test_venv_info();
+        assert!(!venv_info.has_user_data());
+
+        let venv_info = venv_info.with_user_data(vec![
+            UserDataFile { path: PathBuf::from("/home/user/project/.venv/data.csv"), size_bytes: 3000 },
+            UserDataFile { path: PathBuf::from("/home/user/project/.venv/notes.ipynb"), size_bytes: 2000 },
+        ]);
+        assert!(venv_info.has_user_data());
+        assert_eq!(venv_info.user_data().len(), 2);
+        assert_eq!(venv_info.user_data_bytes(), 5000);
+    }
+
+    #[test]
+    fn test_item_counts() {
+        let uncounted = create_test_venv_info();
+        assert_eq!(uncounted.file_count(), None);
+        assert_eq!(uncounted.file_count_formatted(), "-");
+
+        let counted = uncounted.clone().with_item_counts(Some((215307, 9120)));
+        assert_eq!(counted.file_count(), Some(215307));
+        assert_eq!(counted.dir_count(), Some(9120));
+        assert_eq!(counted.file_count_formatted(), "215,307");
+        assert_eq!(counted.inode_count(), Some(224428));
+        assert_eq!(uncounted.inode_count(), None);
+
+        // Most files first, uncounted last
+        assert_eq!(counted.compare_by_file_count(&uncounted), std::cmp::Ordering::Less);
+    }
+
     #[test]
     fn test_location() {
         let venv_info = create_test_venv_info();
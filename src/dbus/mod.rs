@@ -0,0 +1,326 @@
+//! D-Bus service for VenvCleaner (Linux only)
+//!
+//! This module exposes the `org.ncca.VenvCleaner` interface on the session bus
+//! at `/org/ncca/VenvCleaner`, so desktop applets and GNOME extensions can
+//! integrate .venv cleanup. Methods:
+//!
+//! * `Scan(root: s, recursive: b) -> u` - scan (empty root = default directory)
+//! * `ListVenvs() -> a(stxs)` - path, size in bytes, last modified (unix), Python version
+//! * `Delete(paths: as) -> a(sbs)` - path, success, error message
+//!
+//! Progress is reported with the `ScanStarted`, `VenvFound`, `ScanFinished`
+//! and `DeleteDone` signals. Scans run on a blocking thread pool, so the bus
+//! stays responsive and `VenvFound` is sent as each .venv is found.
+
+use std::path::PathBuf;
+use std::sync::mpsc;
+use tracing::{debug, info};
+use zbus::export::futures_util::StreamExt;
+use zbus::object_server::SignalContext;
+
+use crate::core::{DeleteHooks, DeleteMode, FileUtils, PolicyConfig, VenvCleaner, VenvCleanerError, VenvInfo, Result};
+
+/// Well-known bus name of the service
+pub const BUS_NAME: &str = "org.ncca.VenvCleaner";
+
+/// Object path the interface is served at
+pub const OBJECT_PATH: &str = "/org/ncca/VenvCleaner";
+
+/// A .venv directory as returned by `ListVenvs`
+type VenvTuple = (String, u64, i64, String);
+
+/// Per-path outcome as returned by `Delete`
+type DeleteTuple = (String, bool, String);
+
+/// The D-Bus service state, holding the results of the last scan
+pub struct DbusService {
+    /// Default directory for scans
+    base_directory: PathBuf,
+    /// Default recursion setting
+    recursive: bool,
+    /// Whether deletions are simulated
+    dry_run: bool,
+    /// Where deleted directories go
+    delete_mode: DeleteMode,
+    /// Verbosity level passed to the core
+    verbosity: u8,
+    /// Deletion restrictions from the system policy and user config
+    policy: PolicyConfig,
+    /// Commands run before and after each deletion
+    delete_hooks: DeleteHooks,
+    /// .venv directories found by the last scan
+    venvs: Vec<VenvInfo>,
+}
+
+impl DbusService {
+    /// Create a new DbusService instance
+    ///
+    /// # Arguments
+    /// * `base_directory` - Default directory for scans
+    /// * `recursive` - Default recursion setting for scans
+    /// * `dry_run` - Whether deletions are simulated
+    /// * `verbosity` - Verbosity level
+    pub fn new(base_directory: PathBuf, recursive: bool, dry_run: bool, verbosity: u8) -> Self {
+        Self {
+            base_directory,
+            recursive,
+            dry_run,
+            delete_mode: DeleteMode::Permanent,
+            verbosity,
+            policy: PolicyConfig::default(),
+            delete_hooks: DeleteHooks::default(),
+            venvs: Vec::new(),
+        }
+    }
+
+    /// Set where deleted directories go (see `cli::resolve_delete_mode`)
+    pub fn with_delete_mode(mut self, delete_mode: DeleteMode) -> Self {
+        self.delete_mode = delete_mode;
+        self
+    }
+
+    /// Apply deletion restrictions to every scan and deletion
+    pub fn with_policy(mut self, policy: PolicyConfig) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Run these commands before and after each deletion
+    pub fn with_delete_hooks(mut self, delete_hooks: DeleteHooks) -> Self {
+        self.delete_hooks = delete_hooks;
+        self
+    }
+
+    /// Register the service on the session bus and serve requests forever
+    pub fn run(self) -> Result<()> {
+        info!("Starting D-Bus service {} at {}", BUS_NAME, OBJECT_PATH);
+
+        let _connection = zbus::blocking::connection::Builder::session()
+            .and_then(|builder| builder.name(BUS_NAME))
+            .and_then(|builder| builder.serve_at(OBJECT_PATH, self))
+            .and_then(|builder| builder.build())
+            .map_err(|e| VenvCleanerError::Io(format!("Failed to start D-Bus service: {}", e)))?;
+
+        // Requests are handled on zbus' own executor; keep the connection alive
+        loop {
+            std::thread::park();
+        }
+    }
+
+    /// Create the VenvCleaner for a scan (empty root = default directory)
+    fn scan_cleaner(&self, root: &str, recursive: bool) -> Result<VenvCleaner> {
+        let base_directory = if root.is_empty() {
+            self.base_directory.clone()
+        } else {
+            PathBuf::from(root)
+        };
+
+        if !base_directory.is_dir() {
+            return Err(VenvCleanerError::PathError {
+                path: base_directory.display().to_string(),
+                message: "Path is not a directory".to_string(),
+            });
+        }
+
+        Ok(self.cleaner(base_directory, recursive))
+    }
+
+    /// Remember the results of a scan
+    fn store_scan(&mut self, result: Result<Vec<VenvInfo>>) -> Result<&[VenvInfo]> {
+        self.venvs = match result {
+            Ok(venvs) => venvs,
+            Err(VenvCleanerError::NoVenvFound) => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        self.venvs.sort_by(|a, b| a.compare_by_path(b));
+
+        Ok(&self.venvs)
+    }
+
+    /// Delete one path from the last scan, returning the freed bytes
+    ///
+    /// Paths that were not found by the last scan are refused, so callers can
+    /// never remove arbitrary directories through the bus.
+    fn delete_venv(&mut self, path: &str) -> Result<u64> {
+        let index = FileUtils::position_of_path(self.venvs.iter().map(VenvInfo::path), path)
+            .ok_or_else(|| VenvCleanerError::InvalidArgument(format!("{} was not found by the last scan", path)))?;
+
+        let size = self.venvs[index].size().bytes();
+        if !self.dry_run {
+            let cleaner = self.cleaner(self.base_directory.clone(), self.recursive);
+            cleaner.delete_venv_directory(&self.venvs[index])?;
+            self.venvs.remove(index);
+        }
+
+        Ok(size)
+    }
+
+    /// Convert a VenvInfo into the `ListVenvs` tuple representation
+    fn venv_tuple(venv_info: &VenvInfo) -> VenvTuple {
+        (
+            venv_info.path().display().to_string(),
+            venv_info.size().bytes(),
+            venv_info.last_modified().timestamp(),
+            venv_info.python_version().map(|v| v.to_string()).unwrap_or_default(),
+        )
+    }
+
+    /// Create a VenvCleaner for the given search settings
+    fn cleaner(&self, base_directory: PathBuf, recursive: bool) -> VenvCleaner {
+        // Bus clients confirm deletions themselves, so the core runs in force mode
+        VenvCleaner::new(base_directory, recursive, true, self.dry_run, self.verbosity)
+            .with_policy(self.policy.clone())
+            .with_delete_hooks(self.delete_hooks.clone())
+            .with_delete_mode(self.delete_mode)
+    }
+}
+
+#[zbus::interface(name = "org.ncca.VenvCleaner")]
+impl DbusService {
+    /// Scan for .venv directories, returning how many were found
+    async fn scan(
+        &mut self,
+        root: String,
+        recursive: bool,
+        #[zbus(signal_context)] ctxt: SignalContext<'_>,
+    ) -> zbus::fdo::Result<u32> {
+        debug!("D-Bus Scan({}, {})", root, recursive);
+        let failed = |e: VenvCleanerError| zbus::fdo::Error::Failed(e.to_string());
+        let cleaner = self.scan_cleaner(&root, recursive).map_err(failed)?;
+        Self::scan_started(&ctxt, &root).await?;
+
+        // The walk blocks, so it runs on the blocking pool and reports each .venv back here
+        let (found_tx, found_rx) = mpsc::channel();
+        let scan = blocking::unblock(move || {
+            cleaner.find_venv_directories_with(|venv_info| {
+                let _ = found_tx.send((venv_info.path().display().to_string(), venv_info.size().bytes()));
+            })
+        });
+        let mut found = blocking::Unblock::new(found_rx.into_iter());
+        while let Some((path, size)) = found.next().await {
+            Self::venv_found(&ctxt, &path, size).await?;
+        }
+
+        let venvs = self.store_scan(scan.await).map_err(failed)?;
+        let count = venvs.len() as u32;
+        let total: u64 = venvs.iter().map(|venv_info| venv_info.size().bytes()).sum();
+        Self::scan_finished(&ctxt, count, total).await?;
+
+        Ok(count)
+    }
+
+    /// List the .venv directories found by the last scan
+    fn list_venvs(&self) -> Vec<VenvTuple> {
+        self.venvs.iter().map(Self::venv_tuple).collect()
+    }
+
+    /// Delete .venv directories found by the last scan
+    async fn delete(
+        &mut self,
+        paths: Vec<String>,
+        #[zbus(signal_context)] ctxt: SignalContext<'_>,
+    ) -> zbus::fdo::Result<Vec<DeleteTuple>> {
+        let mut results = Vec::with_capacity(paths.len());
+
+        for path in paths {
+            let (freed, error) = match self.delete_venv(&path) {
+                Ok(freed) => (freed, String::new()),
+                Err(e) => (0, e.to_string()),
+            };
+            Self::delete_done(&ctxt, &path, freed, &error).await?;
+            results.push((path, error.is_empty(), error));
+        }
+
+        Ok(results)
+    }
+
+    /// Whether deletions are simulated
+    #[zbus(property)]
+    fn dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Emitted when a scan starts
+    #[zbus(signal)]
+    async fn scan_started(ctxt: &SignalContext<'_>, root: &str) -> zbus::Result<()>;
+
+    /// Emitted for each .venv directory found by a scan
+    #[zbus(signal)]
+    async fn venv_found(ctxt: &SignalContext<'_>, path: &str, size_bytes: u64) -> zbus::Result<()>;
+
+    /// Emitted when a scan finishes
+    #[zbus(signal)]
+    async fn scan_finished(ctxt: &SignalContext<'_>, count: u32, total_bytes: u64) -> zbus::Result<()>;
+
+    /// Emitted after each deletion attempt (empty error on success)
+    #[zbus(signal)]
+    async fn delete_done(ctxt: &SignalContext<'_>, path: &str, freed_bytes: u64, error: &str) -> zbus::Result<()>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    /// Scan the service's default directory as the `Scan` method does, without the signals
+    fn scan(service: &mut DbusService) -> usize {
+        let result = service.scan_cleaner("", true).unwrap().find_venv_directories();
+        service.store_scan(result).unwrap().len()
+    }
+
+    /// Create a .venv that passes validation in a project below the temp dir
+    fn create_venv(temp_dir: &TempDir) -> PathBuf {
+        let venv = temp_dir.path().join("proj").join(".venv");
+        fs::create_dir_all(venv.join("bin")).unwrap();
+        fs::write(venv.join("pyvenv.cfg"), "version = 3.11.2\n").unwrap();
+        venv
+    }
+
+    #[test]
+    fn test_scan_and_list() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = create_venv(&temp_dir);
+
+        let mut service = DbusService::new(temp_dir.path().to_path_buf(), true, false, 0);
+        assert_eq!(scan(&mut service), 1);
+
+        let listed = service.list_venvs();
+        assert_eq!(listed[0].0, venv.display().to_string());
+        assert_eq!(listed[0].3, "3.11.2");
+    }
+
+    #[test]
+    fn test_delete_requires_scanned_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = create_venv(&temp_dir);
+
+        let mut service = DbusService::new(temp_dir.path().to_path_buf(), true, false, 0);
+        assert!(service.delete_venv(&venv.display().to_string()).is_err());
+
+        scan(&mut service);
+        assert!(service.delete_venv(&venv.display().to_string()).is_ok());
+        assert!(!venv.exists());
+        assert!(service.list_venvs().is_empty());
+    }
+
+    #[test]
+    fn test_delete_mode_reaches_the_cleaner() {
+        let service = DbusService::new(PathBuf::from("."), true, false, 0);
+        assert_eq!(service.cleaner(PathBuf::from("."), true).delete_mode(), DeleteMode::Permanent);
+        let service = service.with_delete_mode(DeleteMode::TrashIfAvailable);
+        assert_eq!(service.cleaner(PathBuf::from("."), true).delete_mode(), DeleteMode::TrashIfAvailable);
+    }
+
+    #[test]
+    fn test_dry_run_keeps_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = create_venv(&temp_dir);
+
+        let mut service = DbusService::new(temp_dir.path().to_path_buf(), true, true, 0);
+        scan(&mut service);
+        assert!(service.delete_venv(&venv.display().to_string()).is_ok());
+        assert!(venv.exists());
+    }
+}
@@ -0,0 +1,223 @@
+//! Synthetic .venv tree generation for tests and benchmarks
+//!
+//! This module fabricates realistic-looking project trees, each with a .venv
+//! folder containing `bin/`, `lib/pythonX.Y/site-packages/`, `include/` and a
+//! `pyvenv.cfg`, padded to a configurable size and back-dated to a spread of
+//! ages. It backs the integration tests and the dev-only
+//! `--generate-fixture DIR` flag used for scanner and UI performance checks.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tracing::debug;
+
+use crate::core::{FileUtils, Result, VenvCleanerError};
+
+/// Python versions cycled through when writing pyvenv.cfg files
+const FIXTURE_PYTHON_VERSIONS: &[&str] = &["3.8.18", "3.9.19", "3.10.14", "3.11.9", "3.12.4"];
+
+/// Description of the synthetic tree to generate
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixtureSpec {
+    /// Number of projects (and therefore .venv folders) to create
+    count: usize,
+    /// Approximate size of each .venv folder in bytes
+    venv_size: u64,
+    /// Ages are spread evenly between 0 and this many days
+    max_age_days: u64,
+    /// Number of group directories the projects are distributed across
+    groups: usize,
+}
+
+impl FixtureSpec {
+    /// Create a new FixtureSpec for the given number of projects
+    ///
+    /// Defaults to 64 KB per .venv, ages up to 180 days and 4 groups.
+    ///
+    /// # Arguments
+    /// * `count` - Number of projects to create
+    pub fn new(count: usize) -> Self {
+        Self {
+            count,
+            venv_size: 64 * 1024,
+            max_age_days: 180,
+            groups: 4,
+        }
+    }
+
+    /// Set the approximate size of each .venv folder in bytes
+    pub fn with_venv_size(mut self, venv_size: u64) -> Self {
+        self.venv_size = venv_size;
+        self
+    }
+
+    /// Set the maximum age (in days) of the generated .venv folders
+    pub fn with_max_age_days(mut self, max_age_days: u64) -> Self {
+        self.max_age_days = max_age_days;
+        self
+    }
+
+    /// Set the number of group directories projects are spread across
+    pub fn with_groups(mut self, groups: usize) -> Self {
+        self.groups = groups.max(1);
+        self
+    }
+
+    /// Get the number of projects to create
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Get the approximate size of each .venv folder in bytes
+    pub fn venv_size(&self) -> u64 {
+        self.venv_size
+    }
+
+    /// Get the maximum age in days
+    pub fn max_age_days(&self) -> u64 {
+        self.max_age_days
+    }
+
+    /// Age in days of the project with the given index
+    fn age_for_index(&self, index: usize) -> u64 {
+        if self.count <= 1 {
+            return 0;
+        }
+        self.max_age_days * index as u64 / (self.count as u64 - 1)
+    }
+}
+
+/// Generator for synthetic .venv trees
+pub struct FixtureGenerator;
+
+impl FixtureGenerator {
+    /// Generate a synthetic tree under `root`
+    ///
+    /// The layout is `root/group-<g>/project-<n>/.venv`, so both recursive
+    /// and non-recursive scans have something to find.
+    ///
+    /// # Arguments
+    /// * `root` - Directory to create the tree in (created if missing)
+    /// * `spec` - Description of the tree
+    ///
+    /// # Returns
+    /// The paths of the generated .venv folders, in creation order
+    pub fn generate(root: &Path, spec: &FixtureSpec) -> Result<Vec<PathBuf>> {
+        fs::create_dir_all(root)?;
+
+        let mut venvs = Vec::with_capacity(spec.count);
+        for index in 0..spec.count {
+            let project = root
+                .join(format!("group-{}", index % spec.groups))
+                .join(format!("project-{:04}", index));
+            let version = FIXTURE_PYTHON_VERSIONS[index % FIXTURE_PYTHON_VERSIONS.len()];
+
+            let venv = Self::create_venv(&project, version, spec.venv_size)?;
+            Self::set_age(&venv, spec.age_for_index(index))?;
+            debug!("Generated fixture venv: {}", venv.display());
+            venvs.push(venv);
+        }
+
+        Ok(venvs)
+    }
+
+    /// Create a single .venv folder inside `project`
+    ///
+    /// # Arguments
+    /// * `project` - The project directory (created if missing)
+    /// * `python_version` - Version written to pyvenv.cfg (e.g. "3.11.9")
+    /// * `size` - Approximate total size of the .venv in bytes
+    ///
+    /// # Returns
+    /// The path to the created .venv folder
+    pub fn create_venv(project: &Path, python_version: &str, size: u64) -> Result<PathBuf> {
+        let venv = project.join(".venv");
+        let release_line: String = python_version.split('.').take(2).collect::<Vec<_>>().join(".");
+        let site_packages = venv.join("lib").join(format!("python{}", release_line)).join("site-packages");
+
+        fs::create_dir_all(venv.join("bin"))?;
+        fs::create_dir_all(venv.join("include"))?;
+        fs::create_dir_all(site_packages.join("fixturepkg"))?;
+
+        fs::write(
+            venv.join("pyvenv.cfg"),
+            format!("home = /usr/bin\ninclude-system-site-packages = false\nversion = {}\n", python_version),
+        )?;
+        fs::write(venv.join("bin").join("activate"), "# fixture activate script\n")?;
+        fs::write(site_packages.join("fixturepkg").join("__init__.py"), "")?;
+
+        // Pad the package payload so the whole .venv is roughly the requested size
+        let written = FileUtils::calculate_directory_size(&venv)?;
+        let mut payload = File::create(site_packages.join("fixturepkg").join("payload.bin"))?;
+        Self::write_padding(&mut payload, size.saturating_sub(written))?;
+
+        Ok(venv)
+    }
+
+    /// Back-date a .venv folder's modification time by `days`
+    fn set_age(venv: &Path, days: u64) -> Result<()> {
+        let when = SystemTime::now()
+            .checked_sub(Duration::from_secs(days * 24 * 60 * 60))
+            .ok_or_else(|| VenvCleanerError::InvalidArgument(format!("Age out of range: {} days", days)))?;
+
+        // The scanner reads the .venv directory's own mtime as "last used"
+        File::open(venv)?.set_modified(when)?;
+        Ok(())
+    }
+
+    /// Write `bytes` zero bytes to a file in fixed-size chunks
+    fn write_padding(file: &mut File, mut bytes: u64) -> Result<()> {
+        const CHUNK: usize = 64 * 1024;
+        let zeros = [0u8; CHUNK];
+
+        while bytes > 0 {
+            let len = bytes.min(CHUNK as u64) as usize;
+            file.write_all(&zeros[..len])?;
+            bytes -= len as u64;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::PythonVersion;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_fixture_spec_ages() {
+        let spec = FixtureSpec::new(5).with_max_age_days(100);
+        assert_eq!(spec.age_for_index(0), 0);
+        assert_eq!(spec.age_for_index(2), 50);
+        assert_eq!(spec.age_for_index(4), 100);
+        assert_eq!(FixtureSpec::new(1).age_for_index(0), 0);
+    }
+
+    #[test]
+    fn test_create_venv_layout_and_size() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv = FixtureGenerator::create_venv(&temp_dir.path().join("proj"), "3.11.9", 10_000).unwrap();
+
+        assert!(FileUtils::is_valid_venv_directory(&venv));
+        assert_eq!(PythonVersion::from_venv(&venv).unwrap().release_line(), "3.11");
+        assert_eq!(FileUtils::calculate_directory_size(&venv).unwrap(), 10_000);
+    }
+
+    #[test]
+    fn test_generate_tree() {
+        let temp_dir = TempDir::new().unwrap();
+        let spec = FixtureSpec::new(6).with_groups(2).with_venv_size(4096).with_max_age_days(200);
+        let venvs = FixtureGenerator::generate(temp_dir.path(), &spec).unwrap();
+
+        assert_eq!(venvs.len(), 6);
+        assert!(temp_dir.path().join("group-1").join("project-0005").join(".venv").is_dir());
+
+        // The last project is the oldest
+        let modified = fs::metadata(&venvs[5]).unwrap().modified().unwrap();
+        let age = SystemTime::now().duration_since(modified).unwrap();
+        assert!(age >= Duration::from_secs(199 * 24 * 60 * 60));
+    }
+}
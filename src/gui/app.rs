@@ -5,16 +5,45 @@
 //! It implements the eframe::App trait for the main GUI loop.
 
 use std::collections::HashSet;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::thread;
-use std::time::Instant;
+use std::time::{Duration, Instant};
+use chrono::Utc;
 
 use eframe::egui::{self, *};
-use log::{debug, info, warn};
+use tracing::{debug, info, warn};
 
-use crate::core::{VenvCleaner, VenvInfo, Result};
+use crate::core::{deletion_eta, editor, export, help, paths, peek, recommendations, shell, size_deltas, size_history, sorting, summary_cache, timestamps, ActivityCache, AgeBucket, ArchivedVenv, BatchOptions, BatchResult, ByteSize, CancellationToken, DeleteMode, DeletionEstimator, ExportFormat, FileUtils, GrowthProjection, LogBuffer, Recommendation, ScanMetadata, SizeDeltas, SortOrder, VenvCleaner, VenvCleanerError, VenvInfo, VenvPeek};
 use super::{GuiAppState, GuiSortBy, GuiEvent, utils};
+use super::components::{
+    ColumnWidths, ConfirmationDialog, DialogResult, ProgressIndicator, StatusCard, Toolbar, ToolbarAction, VenvTable, VenvTableAction,
+};
+use super::theme::{ColorSchemes, Density};
+
+/// Zoom levels offered in the View menu
+const ZOOM_LEVELS: [f32; 6] = [1.0, 1.25, 1.5, 1.75, 2.0, 2.5];
+
+/// A second scan shown beside the main list, to compare two roots
+struct ComparePane {
+    /// The root it scans
+    root: PathBuf,
+    /// The .venv directories found below it, None while scanning
+    venvs: Option<Vec<VenvInfo>>,
+    /// Selected rows
+    selected: HashSet<usize>,
+    /// Column widths of its table
+    column_widths: ColumnWidths,
+    /// Why the last scan failed, if it did
+    error: Option<String>,
+}
+
+impl ComparePane {
+    /// Create a pane waiting for the scan of a root
+    fn new(root: PathBuf, column_widths: ColumnWidths) -> Self {
+        Self { root, venvs: None, selected: HashSet::new(), column_widths, error: None }
+    }
+}
 
 /// Main GUI application state
 pub struct GuiApp {
@@ -47,12 +76,28 @@ pub struct GuiApp {
     event_sender: Option<Sender<GuiEvent>>,
     /// Deletion progress
     deletion_progress: f32,
+    /// Estimated time left of the running deletion
+    deletion_eta: Option<Duration>,
     /// Whether to show confirmation dialog
     show_confirmation_dialog: bool,
+    /// Contents of the selected .venv directories, shown while confirming their deletion
+    peeks: Option<Vec<VenvPeek>>,
+    /// Whether losing the user data in the selected .venv directories was accepted
+    user_data_confirmed: bool,
+    /// Whether the deletion being confirmed moves the .venv directories to the trash
+    delete_to_trash: bool,
+    /// Files in the .venv directories of the deletion being confirmed
+    deletion_file_count: u64,
     /// Whether to show help window
     show_help: bool,
+    /// Whether to show the keyboard shortcuts overlay
+    show_shortcuts: bool,
     /// Whether to show about window
     show_about: bool,
+    /// Whether the next scan's results start selected (`--older-than`/`--min-size` at startup)
+    preselect: bool,
+    /// Cleanup recommendations worked out when the window was opened, while it is shown
+    recommendations: Option<Vec<Recommendation>>,
     /// Whether to show folder selection dialog
     show_folder_dialog: bool,
     /// New directory path from folder dialog
@@ -65,6 +110,55 @@ pub struct GuiApp {
     main_window_size: Vec2,
     /// Table scroll position
     table_scroll: f32,
+    /// Archived .venv directories (stub files) found by the last scan
+    archived: Vec<ArchivedVenv>,
+    /// Roots the last scan could not search, with why, each offered for a retry
+    failed_roots: Vec<(PathBuf, String)>,
+    /// Failed roots being searched again
+    retrying_roots: HashSet<PathBuf>,
+    /// Archived .venv whose archive is awaiting deletion confirmation
+    pending_archive_deletion: Option<ArchivedVenv>,
+    /// Whether the table is split into collapsible age bucket sections
+    group_by_age: bool,
+    /// Whether the table has a file count column
+    show_file_counts: bool,
+    /// Whether the table has a column with the change in size since the previous scan
+    show_size_delta: bool,
+    /// How much each .venv grew or shrank since the previous scan
+    size_deltas: SizeDeltas,
+    /// UI zoom factor (1.0 = native size), persisted across sessions
+    zoom: f32,
+    /// Whether to use the high-contrast theme, persisted across sessions
+    high_contrast: bool,
+    /// How tightly table rows and text are packed, persisted across sessions
+    density: Density,
+    /// Theme variant and density currently applied to the egui context
+    applied_style: Option<(bool, Density)>,
+    /// When the scan in progress was started
+    scan_started: Option<Instant>,
+    /// How long the last completed scan took
+    last_scan_duration: Option<Duration>,
+    /// The last scan, described at the end of exports
+    scan_metadata: Option<ScanMetadata>,
+    /// When the disk fills up at the growth rate of the size history
+    growth: Option<GrowthProjection>,
+    /// Table column widths, adjusted by dragging and persisted across sessions
+    column_widths: ColumnWidths,
+    /// Editor command template from the config file
+    editor: Option<String>,
+    /// Recent log lines for the log panel
+    logs: LogBuffer,
+    /// Whether the log panel is shown
+    show_log: bool,
+    /// A second root shown side by side, while comparing
+    compare: Option<ComparePane>,
+    /// Stops the scan or deletion in progress, None when neither can be cancelled
+    cancellation: Option<CancellationToken>,
+    /// How a cancelled deletion ended, shown once the list has been refreshed
+    cancel_notice: Option<String>,
+    /// The system tray icon, with the actions chosen from its menu
+    #[cfg(feature = "tray")]
+    tray: Option<(super::tray::Tray, Receiver<super::tray::TrayAction>)>,
 }
 
 impl GuiApp {
@@ -75,6 +169,7 @@ impl GuiApp {
         recursive: bool,
     ) -> Self {
         let (sender, receiver) = mpsc::channel();
+        let preselect = cleaner.venv_filter().is_active();
 
         let mut app = Self {
             state: GuiAppState::Loading,
@@ -92,15 +187,48 @@ impl GuiApp {
             event_receiver: Some(receiver),
             event_sender: Some(sender),
             deletion_progress: 0.0,
+            deletion_eta: None,
             show_confirmation_dialog: false,
+            peeks: None,
+            user_data_confirmed: false,
+            delete_to_trash: false,
+            deletion_file_count: 0,
             show_help: false,
+            show_shortcuts: false,
             show_about: false,
+            preselect,
+            recommendations: None,
             show_folder_dialog: false,
             pending_directory: None,
             search_filter: String::new(),
             dark_theme: false,
             main_window_size: Vec2::new(1200.0, 800.0),
             table_scroll: 0.0,
+            archived: Vec::new(),
+            failed_roots: Vec::new(),
+            retrying_roots: HashSet::new(),
+            pending_archive_deletion: None,
+            group_by_age: false,
+            show_file_counts: false,
+            show_size_delta: false,
+            size_deltas: SizeDeltas::default(),
+            zoom: 1.0,
+            high_contrast: false,
+            density: Density::default(),
+            applied_style: None,
+            scan_started: None,
+            scan_metadata: None,
+            last_scan_duration: None,
+            growth: None,
+            column_widths: ColumnWidths::default(),
+            editor: None,
+            logs: LogBuffer::default(),
+            show_log: false,
+            compare: None,
+            cancellation: None,
+            cancel_notice: None,
+            #[cfg(feature = "tray")]
+            tray: None,
         };
 
         // Start loading .venv directories immediately
@@ -108,38 +236,291 @@ impl GuiApp {
         app
     }
 
+    /// Open projects with this editor command template (`{dir}` is the project directory)
+    pub fn with_editor(mut self, editor: Option<String>) -> Self {
+        self.editor = editor;
+        self
+    }
+
+    /// Show these captured log lines in the log panel
+    pub fn with_logs(mut self, logs: LogBuffer) -> Self {
+        self.logs = logs;
+        self
+    }
+
+    /// Put an icon in the system tray, showing the usage of each scan
+    ///
+    /// # Arguments
+    /// * `ctx` - The GUI's context, woken up when a tray menu entry is chosen
+    #[cfg(feature = "tray")]
+    pub fn with_tray(mut self, ctx: &Context) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        match super::tray::Tray::start(sender, ctx.clone()) {
+            Ok(tray) => {
+                if self.state == GuiAppState::Browsing {
+                    tray.set_usage(&self.venvs);
+                }
+                self.tray = Some((tray, receiver));
+            }
+            Err(e) => {
+                warn!("System tray unavailable: {}", e);
+                self.status = format!("⚠️ System tray unavailable: {}", e);
+            }
+        }
+        self
+    }
+
+    /// Restore the view settings saved by a previous session
+    ///
+    /// # Arguments
+    /// * `storage` - eframe's persistent storage, if available
+    pub fn with_storage(mut self, storage: Option<&dyn eframe::Storage>) -> Self {
+        if let Some(storage) = storage {
+            if let Some(zoom) = eframe::get_value::<f32>(storage, "zoom") {
+                self.zoom = zoom.clamp(ZOOM_LEVELS[0], ZOOM_LEVELS[ZOOM_LEVELS.len() - 1]);
+            }
+            if let Some(high_contrast) = eframe::get_value(storage, "high_contrast") {
+                self.high_contrast = high_contrast;
+            }
+            if let Some(density) = eframe::get_value(storage, "density") {
+                self.density = density;
+            }
+            if let Some(column_widths) = eframe::get_value(storage, "column_widths") {
+                self.column_widths = column_widths;
+            }
+        }
+        self
+    }
+
+    /// Apply the zoom factor, theme variant and density to the egui context
+    fn apply_view_settings(&mut self, ctx: &Context) {
+        // The saved zoom is applied on the first frame; afterwards egui's own
+        // Ctrl +/- shortcuts can change it too, so read it back for saving
+        let first_frame = self.applied_style.is_none();
+        if first_frame {
+            ctx.set_zoom_factor(self.zoom);
+        } else {
+            self.zoom = ctx.zoom_factor();
+        }
+
+        let wanted = (self.high_contrast, self.density);
+        if self.applied_style != Some(wanted) {
+            if self.high_contrast {
+                ColorSchemes::high_contrast().with_density(self.density).apply_to_ctx(ctx);
+            } else {
+                let mut style = Style::default();
+                self.density.apply_to_style(&mut style);
+                ctx.set_style(style);
+            }
+            self.applied_style = Some(wanted);
+        }
+    }
+
+    /// Build the cleaner scanning a root with the filters of this session
+    fn scan_cleaner(&self, root: PathBuf) -> VenvCleaner {
+        VenvCleaner::new(root, self.is_recursive, false, false, 0)
+            .with_owner_filter(self.cleaner.owner_filter())
+            .with_venv_filter(self.cleaner.venv_filter())
+            .with_skip_hidden(self.cleaner.is_skipping_hidden())
+            .with_validation(self.cleaner.is_validating())
+            .with_excludes(self.cleaner.excludes().to_vec())
+            .with_includes(self.cleaner.includes().clone())
+            .with_detectors(self.cleaner.detectors().to_vec())
+            .with_offline_index(self.cleaner.offline_index().cloned())
+            .with_fs_backend(self.cleaner.fs_backend())
+    }
+
     /// Start loading .venv directories in background
     fn start_loading_venvs(&mut self) {
         if let Some(sender) = &self.event_sender {
-            let cleaner = VenvCleaner::new(
-                self.current_directory.clone(),
-                self.is_recursive,
-                false,
-                false,
-                0,
-            );
+            let cancellation = CancellationToken::new();
+            let mut cleaner = self.scan_cleaner(self.current_directory.clone());
+            // The roots given at startup are listed together until another folder is chosen
+            if self.current_directory == self.cleaner.base_directory() {
+                cleaner = cleaner.with_extra_roots(self.cleaner.roots().into_iter().skip(1).map(Path::to_path_buf).collect());
+            }
+            // A root that cannot be searched must not hide the others' results
+            let cleaner = cleaner.with_partial_roots(true).with_cancellation(cancellation.clone());
             let sender_clone = sender.clone();
 
+            self.cancellation = Some(cancellation);
             self.state = GuiAppState::Loading;
+            self.scan_started = Some(Instant::now());
+            self.scan_metadata = Some(ScanMetadata::new(&cleaner.roots(), self.is_recursive, Utc::now()));
             let search_mode = if self.is_recursive { "recursively" } else { "in current directory" };
             self.status = format!("🔍 Scanning for .venv directories {}...", search_mode);
 
             thread::spawn(move || {
                 debug!("Starting .venv loading task in background");
-                match cleaner.find_venv_directories() {
+                let result = cleaner.find_venv_directories();
+                let archived = cleaner.last_archived_venvs();
+                let has_archived = !archived.is_empty();
+                let _ = sender_clone.send(GuiEvent::ArchivedLoaded(archived));
+                let failed = cleaner
+                    .last_failed_roots()
+                    .into_iter()
+                    .map(|(root, e)| (root, crate::core::platform::describe_error(&e)))
+                    .collect();
+                let _ = sender_clone.send(GuiEvent::RootsFailed(failed));
+                match result {
+                    // Archived environments alone still make the list worth showing, as does a cancelled scan
+                    Err(VenvCleanerError::NoVenvFound) if has_archived || cleaner.is_cancelled() => {
+                        let _ = sender_clone.send(GuiEvent::VenvsLoaded(Vec::new()));
+                    }
                     Ok(venvs) => {
                         debug!("Found {} .venv directories", venvs.len());
+                        // Every row shows its project's last commit; asking git is slow, so go through the cache
+                        let venvs = if cleaner.offline_index().is_some() {
+                            venvs
+                        } else {
+                            let mut activity = ActivityCache::load_default();
+                            let venvs = activity.with_last_commits(venvs);
+                            if let Err(e) = activity.save() {
+                                warn!("Failed to save activity cache: {}", e);
+                            }
+                            venvs
+                        };
                         let _ = sender_clone.send(GuiEvent::VenvsLoaded(venvs));
                     }
                     Err(e) => {
                         warn!("Error loading .venv directories: {}", e);
-                        let _ = sender_clone.send(GuiEvent::LoadError(e.to_string()));
+                        let _ = sender_clone.send(GuiEvent::LoadError(crate::core::platform::describe_error(&e)));
                     }
                 }
             });
         }
     }
 
+    /// Scan a second root in the background and show it beside the main list
+    fn start_compare_scan(&mut self, root: PathBuf) {
+        let Some(sender) = self.event_sender.clone() else {
+            return;
+        };
+        let cleaner = self.scan_cleaner(root.clone());
+        self.status = format!("🔍 Scanning {} for comparison...", root.display());
+        self.compare = Some(ComparePane::new(root.clone(), self.column_widths));
+
+        thread::spawn(move || {
+            let result = match cleaner.find_venv_directories() {
+                Ok(venvs) => Ok(venvs),
+                Err(VenvCleanerError::NoVenvFound) => Ok(Vec::new()),
+                Err(e) => Err(crate::core::platform::describe_error(&e)),
+            };
+            let _ = sender.send(GuiEvent::CompareLoaded { root, result });
+        });
+    }
+
+    /// Search a root the last scan failed on again, adding what it finds to the list
+    fn start_root_retry(&mut self, root: PathBuf) {
+        let Some(sender) = self.event_sender.clone() else {
+            return;
+        };
+        let cleaner = self.scan_cleaner(root.clone()).with_partial_roots(true);
+        self.status = format!("🔍 Retrying {}...", root.display());
+        self.retrying_roots.insert(root.clone());
+
+        thread::spawn(move || {
+            let result = match cleaner.find_venv_directories() {
+                Ok(venvs) => Ok(venvs),
+                Err(VenvCleanerError::NoVenvFound) => Ok(Vec::new()),
+                Err(e) => Err(crate::core::platform::describe_error(&e)),
+            };
+            let _ = sender.send(GuiEvent::RootRetried { root, result });
+        });
+    }
+
+    /// Pick a directory and compare it with the main list
+    fn open_compare_pane(&mut self) {
+        if let Some(root) = rfd::FileDialog::new().set_directory(&self.current_directory).pick_folder() {
+            self.start_compare_scan(root);
+        }
+    }
+
+    /// Move the rows selected in the comparison pane to the main list, selected
+    ///
+    /// Deleting happens from the main list, so this gathers one batch from both roots.
+    fn move_compare_selection_to_main(&mut self) {
+        let Some(compare) = &mut self.compare else {
+            return;
+        };
+        let Some(venvs) = &mut compare.venvs else {
+            return;
+        };
+        let moved = utils::move_selected((venvs, &mut compare.selected), (&mut self.venvs, &mut self.selected_venvs));
+        self.status = format!("⬅ Moved {} directories from {} to the main list", moved, compare.root.display());
+    }
+
+    /// Move the rows selected in the main list to the comparison pane, selected
+    fn move_main_selection_to_compare(&mut self) {
+        let Some(compare) = &mut self.compare else {
+            return;
+        };
+        let Some(venvs) = &mut compare.venvs else {
+            return;
+        };
+        let moved = utils::move_selected((&mut self.venvs, &mut self.selected_venvs), (venvs, &mut compare.selected));
+        self.status = format!("➡ Moved {} directories to the comparison of {}", moved, compare.root.display());
+    }
+
+    /// Ask for confirmation before deleting the selected .venv directories
+    ///
+    /// Whether they go to the trash starts out as configured and can be
+    /// switched in the dialog for this deletion only.
+    fn request_deletion(&mut self) {
+        if let Err(e) = self.cleaner.policy().check_batch_size(self.selected_venvs.len()) {
+            self.status = format!("🔒 {}", e);
+            return;
+        }
+        self.delete_to_trash = self.cleaner.is_using_trash();
+        // Counted once here rather than on every frame of the dialog
+        let selected: Vec<VenvInfo> = self.selected_venvs.iter().filter_map(|&i| self.venvs.get(i)).cloned().collect();
+        self.deletion_file_count = deletion_eta::file_count(&selected);
+        self.show_confirmation_dialog = true;
+    }
+
+    /// Carry out the actions chosen from the tray menu
+    #[cfg(feature = "tray")]
+    fn handle_tray_actions(&mut self, ctx: &Context) {
+        use super::tray::TrayAction;
+
+        let Some((_, receiver)) = &self.tray else {
+            return;
+        };
+        let actions: Vec<TrayAction> = receiver.try_iter().collect();
+        for action in actions {
+            match action {
+                TrayAction::Open => Self::show_window(ctx),
+                TrayAction::ScanNow => {
+                    if self.state != GuiAppState::Deleting {
+                        self.start_loading_venvs();
+                    }
+                }
+                TrayAction::CleanOld => {
+                    // Deletions still go through the confirmation dialog, so the window is brought up
+                    Self::show_window(ctx);
+                    if self.state != GuiAppState::Browsing {
+                        self.status = "⏳ Wait for the scan to finish before cleaning old .venv directories".to_string();
+                        continue;
+                    }
+                    self.selected_venvs = self.venvs.iter().enumerate().filter(|(_, v)| v.is_old()).map(|(i, _)| i).collect();
+                    if self.selected_venvs.is_empty() {
+                        self.status = "✅ No .venv directories unused for over 90 days".to_string();
+                    } else {
+                        self.request_deletion();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Show, restore and focus the main window
+    #[cfg(feature = "tray")]
+    fn show_window(ctx: &Context) {
+        ctx.send_viewport_cmd(ViewportCommand::Visible(true));
+        ctx.send_viewport_cmd(ViewportCommand::Minimized(false));
+        ctx.send_viewport_cmd(ViewportCommand::Focus);
+    }
+
     /// Start deletion of selected .venv directories
     fn start_deletion(&mut self) {
         if let Some(sender) = &self.event_sender {
@@ -159,28 +540,218 @@ impl GuiApp {
                 false,
                 false,
                 0,
-            );
+            )
+            .with_policy(self.cleaner.policy().clone())
+            .with_delete_hooks(self.cleaner.delete_hooks().clone())
+            .with_trash(self.delete_to_trash)
+            .with_archive_dir(self.cleaner.archive_dir().map(Path::to_path_buf))
+            .with_offline_index(self.cleaner.offline_index().cloned())
+            .with_fs_backend(self.cleaner.fs_backend());
+            let cancellation = CancellationToken::new();
             let sender_clone = sender.clone();
 
+            self.cancellation = Some(cancellation.clone());
             self.state = GuiAppState::Deleting;
             self.deletion_progress = 0.0;
+            self.deletion_eta = None;
             self.status = format!("Deleting {} directories...", selected_venvs.len());
 
             thread::spawn(move || {
                 debug!("Starting deletion task for {} directories", selected_venvs.len());
-                let mut results = Vec::new();
-
-                for venv in selected_venvs {
-                    let result = cleaner.delete_venv_directory(&venv);
-                    results.push((venv, result));
-                }
+                let mut estimator = DeletionEstimator::for_venvs(&selected_venvs);
+                let total = selected_venvs.len();
+                let mut completed = 0;
+
+                // The directories being removed are finished before stopping
+                let options = BatchOptions::new().with_cancellation(cancellation);
+                let batch = cleaner.delete_many(&selected_venvs, &options, |item| {
+                    completed += 1;
+                    estimator.record(item.venv.path());
+                    let _ = sender_clone.send(GuiEvent::DeletionProgress {
+                        completed,
+                        total,
+                        eta: estimator.remaining(),
+                    });
+                });
 
                 debug!("Deletion task completed");
-                let _ = sender_clone.send(GuiEvent::DeletionComplete(results));
+                let _ = sender_clone.send(GuiEvent::DeletionComplete(batch));
+            });
+        }
+    }
+
+    /// Start slimming the selected .venv directories in place
+    fn start_slimming(&mut self) {
+        if let Some(sender) = &self.event_sender {
+            let selected_venvs: Vec<VenvInfo> = self.selected_venvs
+                .iter()
+                .filter_map(|&i| self.venvs.get(i))
+                .cloned()
+                .collect();
+
+            if selected_venvs.is_empty() {
+                return;
+            }
+
+            let cleaner = self.cleaner.clone();
+            let sender_clone = sender.clone();
+
+            self.state = GuiAppState::Loading;
+            self.status = format!("✂️ Slimming {} directories...", selected_venvs.len());
+
+            thread::spawn(move || {
+                debug!("Starting slim task for {} directories", selected_venvs.len());
+                let results = selected_venvs
+                    .into_iter()
+                    .map(|venv| {
+                        let result = cleaner.slim_venv_directory(&venv, false);
+                        (venv, result)
+                    })
+                    .collect();
+
+                let _ = sender_clone.send(GuiEvent::SlimComplete(results));
             });
         }
     }
 
+    /// Restore an archived .venv directory in the background
+    fn start_restore(&mut self, archived: ArchivedVenv) {
+        if let Some(sender) = &self.event_sender {
+            let cleaner = self.cleaner.clone();
+            let sender_clone = sender.clone();
+
+            self.state = GuiAppState::Loading;
+            self.status = format!("📦 Restoring {}...", archived.location());
+
+            thread::spawn(move || {
+                let message = match cleaner.restore_archived_venv(&archived) {
+                    Ok(venv_path) => format!("📦 Restored {}", venv_path.display()),
+                    Err(e) => format!("❌ Could not restore {}: {}", archived.location(), e),
+                };
+                let _ = sender_clone.send(GuiEvent::ArchiveActionComplete(message));
+            });
+        }
+    }
+
+    /// Delete the archive of an archived .venv directory
+    fn delete_archive(&mut self, archived: ArchivedVenv) {
+        self.status = match self.cleaner.delete_archived_venv(&archived) {
+            Ok(freed) => format!("🗑️ Deleted archive of {}, freed {}", archived.location(), ByteSize::new(freed)),
+            Err(e) => format!("❌ Could not delete archive of {}: {}", archived.location(), e),
+        };
+        self.archived.retain(|a| a.stub_path() != archived.stub_path());
+    }
+
+    /// Draw the roots the last scan could not search, with why and a retry button each
+    fn draw_failed_roots_section(&mut self, ui: &mut Ui) {
+        if self.failed_roots.is_empty() {
+            return;
+        }
+
+        let mut retry = None;
+        CollapsingHeader::new(
+            RichText::new(format!("⚠️ {} roots could not be searched", self.failed_roots.len())).color(Color32::from_rgb(255, 193, 7)),
+        )
+        .id_source("failed_roots_section")
+        .default_open(false)
+        .show(ui, |ui| {
+            for (root, error) in &self.failed_roots {
+                ui.horizontal(|ui| {
+                    let retrying = self.retrying_roots.contains(root);
+                    let label = if retrying { "🔍 Retrying..." } else { "🔄 Retry" };
+                    if ui.add_enabled(!retrying, egui::Button::new(label).small()).on_hover_text("Search this root again").clicked() {
+                        retry = Some(root.clone());
+                    }
+                    ui.label(RichText::new(root.display().to_string()).strong());
+                });
+                ui.indent(root, |ui| {
+                    ui.colored_label(Color32::from_rgb(220, 53, 69), error);
+                });
+            }
+        });
+        ui.separator();
+
+        if let Some(root) = retry {
+            self.start_root_retry(root);
+        }
+    }
+
+    /// Draw the archived .venv directories with restore and delete actions
+    fn draw_archived_section(&mut self, ui: &mut Ui) {
+        if self.archived.is_empty() {
+            return;
+        }
+
+        let compressed_total: u64 = self.archived.iter().filter_map(|a| a.compressed_size()).sum();
+        let mut restore = None;
+        let mut delete = None;
+
+        CollapsingHeader::new(format!("📦 Archived ({}, {} on disk)", self.archived.len(), ByteSize::new(compressed_total)))
+            .id_source("archived_section")
+            .default_open(true)
+            .show(ui, |ui| {
+                for archived in &self.archived {
+                    ui.horizontal(|ui| {
+                        if ui.small_button("♻️ Restore").on_hover_text("Unpack the archive back into place").clicked() {
+                            restore = Some(archived.clone());
+                        }
+                        if ui.small_button("🗑️ Delete archive").on_hover_text("Discard the archive; the .venv cannot be restored afterwards").clicked() {
+                            delete = Some(archived.clone());
+                        }
+                        ui.label(archived.location());
+                        match archived.compressed_size() {
+                            Some(size) => ui.label(RichText::new(ByteSize::new(size).to_string()).strong()),
+                            None => ui.colored_label(Color32::RED, "archive missing"),
+                        };
+                        ui.label(
+                            RichText::new(format!(
+                                "was {}, archived {}",
+                                ByteSize::new(archived.stub().original_size()),
+                                timestamps::format_date(archived.stub().archived_at())
+                            ))
+                            .weak(),
+                        );
+                    });
+                }
+            });
+        ui.separator();
+
+        if let Some(archived) = restore {
+            self.start_restore(archived);
+        }
+        if delete.is_some() {
+            self.pending_archive_deletion = delete;
+        }
+    }
+
+    /// Draw the confirmation dialog for deleting an archive
+    fn draw_archive_deletion_dialog(&mut self, ctx: &Context) {
+        let Some(archived) = self.pending_archive_deletion.clone() else {
+            return;
+        };
+
+        let message = format!(
+            "Delete the archive of {}?\nArchive size: {}",
+            archived.location(),
+            ByteSize::new(archived.compressed_size().unwrap_or(0))
+        );
+
+        let result = ConfirmationDialog::new("Confirm Archive Deletion", &message)
+            .buttons("🗑️ Delete archive", "❌ Cancel")
+            .warning("⚠️ The .venv can no longer be restored afterwards!")
+            .danger()
+            .show(ctx);
+
+        match result {
+            DialogResult::Confirm => {
+                self.pending_archive_deletion = None;
+                self.delete_archive(archived);
+            }
+            DialogResult::Cancel => self.pending_archive_deletion = None,
+            DialogResult::None | DialogResult::Alternative => {}
+        }
+    }
+
     /// Handle background events
     fn handle_events(&mut self) {
         let mut events = Vec::new();
@@ -193,39 +764,165 @@ impl GuiApp {
         for event in events {
             match event {
                 GuiEvent::VenvsLoaded(venvs) => {
+                    let cancelled = self.cancellation.take().is_some_and(|token| token.is_cancelled());
+                    self.last_scan_duration = self.scan_started.take().map(|started| started.elapsed());
+                    self.scan_metadata = self.scan_metadata.take().map(|metadata| metadata.with_finished(Utc::now()));
                     self.venvs = venvs;
                     self.sort_venvs();
                     self.state = GuiAppState::Browsing;
                     self.selected_venvs.clear();
+                    #[cfg(feature = "tray")]
+                    if let Some((tray, _)) = &self.tray {
+                        tray.set_usage(&self.venvs);
+                    }
 
-                    if self.venvs.is_empty() {
+                    // Filtered, saved and cancelled scans do not measure the disk
+                    if !cancelled && !self.cleaner.venv_filter().is_active() && self.cleaner.offline_index().is_none() {
+                        let total: u64 = self.venvs.iter().map(|v| v.size().bytes()).sum();
+                        self.growth = size_history::record_scan(&self.cleaner.roots(), total);
+                        summary_cache::record_scan(&self.cleaner.roots(), &self.venvs);
+                    }
+                    if !cancelled && self.cleaner.offline_index().is_none() {
+                        self.size_deltas = size_deltas::record_scan(&self.venvs);
+                    }
+
+                    if cancelled {
+                        self.preselect = false;
+                        self.status = format!(
+                            "⏹ Scan cancelled: showing the {} .venv directories found so far. Refresh to scan everything.",
+                            self.venvs.len()
+                        );
+                    } else if self.venvs.is_empty() {
                         self.status = "No .venv directories found. Try changing the search directory or enabling recursive search.".to_string();
+                    } else if std::mem::take(&mut self.preselect) {
+                        // Only the startup scan: later refreshes keep the user's own selection
+                        self.selected_venvs = (0..self.venvs.len()).collect();
+                        self.status = format!(
+                            "Selected the {} .venv directories matching --older-than/--min-size. Review them, then delete.",
+                            self.venvs.len()
+                        );
+                    } else if let Some((root, environment)) = self.cleaner.roots_inside_environments().first() {
+                        self.status = format!(
+                            "Found {} .venv directories. ⚠️ {} is inside {}; they may be test data of its packages, check before deleting.",
+                            self.venvs.len(),
+                            root.display(),
+                            environment.display()
+                        );
                     } else {
                         self.status = format!("Found {} .venv directories. Select directories to delete or use the search filter.", self.venvs.len());
                     }
+                    if !self.failed_roots.is_empty() {
+                        self.status = format!(
+                            "⚠️ {} of {} roots could not be searched, see the warnings above the list. {}",
+                            self.failed_roots.len(),
+                            self.scan_metadata.as_ref().map(|metadata| metadata.roots.len()).unwrap_or(self.failed_roots.len()),
+                            self.status
+                        );
+                    }
+                    if let Some(notice) = self.cancel_notice.take() {
+                        self.status = format!("{} {}", notice, self.status);
+                    }
+                }
+                GuiEvent::RootsFailed(failed) => {
+                    self.failed_roots = failed;
+                    self.retrying_roots.clear();
+                }
+                GuiEvent::RootRetried { root, result } => {
+                    // A full rescan since the retry started has the final word on this root
+                    if !self.retrying_roots.remove(&root) {
+                        continue;
+                    }
+                    match result {
+                        Ok(venvs) => {
+                            self.failed_roots.retain(|(failed, _)| *failed != root);
+                            let added = self.merge_venvs(venvs);
+                            self.status = format!("✅ {} can be searched again: added {} .venv directories", root.display(), added);
+                        }
+                        Err(error) => {
+                            if let Some(entry) = self.failed_roots.iter_mut().find(|(failed, _)| *failed == root) {
+                                entry.1 = error;
+                            }
+                            self.status = format!("⚠️ {} still cannot be searched", root.display());
+                        }
+                    }
                 }
                 GuiEvent::LoadError(error) => {
+                    self.cancellation = None;
                     self.error_message = error;
                     self.state = GuiAppState::Error;
                 }
-                GuiEvent::DeletionComplete(results) => {
-                    self.handle_deletion_results(results);
+                GuiEvent::DeletionProgress { completed, total, eta } => {
+                    self.deletion_progress = completed as f32 / total.max(1) as f32;
+                    self.deletion_eta = eta;
+                }
+                GuiEvent::DeletionComplete(batch) => {
+                    self.cancellation = None;
+                    self.handle_deletion_results(&batch);
+                    if batch.cancelled() > 0 {
+                        self.cancel_notice = Some(format!(
+                            "⏹ Deletion cancelled after {} of {} directories; the rest were left untouched.",
+                            batch.attempted().count(),
+                            batch.total()
+                        ));
+                    }
                     // Refresh the list after deletion
                     self.start_loading_venvs();
                 }
+                GuiEvent::ArchivedLoaded(archived) => {
+                    self.archived = archived;
+                }
+                GuiEvent::CompareLoaded { root, result } => {
+                    // A pane closed or pointed elsewhere since keeps its own scan
+                    let Some(compare) = self.compare.as_mut().filter(|compare| compare.root == root) else {
+                        continue;
+                    };
+                    match result {
+                        Ok(mut venvs) => {
+                            SortOrder::new(self.sort_by.key()).with_reverse(self.reverse_sort).sort(&mut venvs);
+                            self.status = format!("Found {} .venv directories in {} for comparison", venvs.len(), root.display());
+                            compare.venvs = Some(venvs);
+                            compare.error = None;
+                        }
+                        Err(error) => {
+                            compare.venvs = Some(Vec::new());
+                            compare.error = Some(error);
+                        }
+                    }
+                }
+                GuiEvent::ArchiveActionComplete(message) => {
+                    // The restored .venv joins the list; the status is kept until the scan completes
+                    self.start_loading_venvs();
+                    self.status = message;
+                }
+                GuiEvent::SlimComplete(results) => {
+                    let saved: u64 = results.iter().filter_map(|(_, r)| r.as_ref().ok()).map(|r| r.bytes_saved()).sum();
+                    let failed = results.iter().filter(|(_, r)| r.as_ref().map(|r| !r.failed().is_empty()).unwrap_or(true)).count();
+
+                    // Sizes changed, so rescan; the status is kept until the scan completes
+                    self.start_loading_venvs();
+                    self.status = if failed == 0 {
+                        format!("✂️ Slimmed {} directories, reclaimed {}", results.len(), ByteSize::new(saved))
+                    } else {
+                        format!("⚠️ Slimmed {} directories, reclaimed {} ({} had errors)", results.len(), ByteSize::new(saved), failed)
+                    };
+                }
             }
         }
     }
 
     /// Handle deletion results
-    fn handle_deletion_results(&mut self, results: Vec<(VenvInfo, Result<()>)>) {
-        let successful = results.iter().filter(|(_, r)| r.is_ok()).count();
-        let failed = results.len() - successful;
+    fn handle_deletion_results(&mut self, batch: &BatchResult) {
+        let (successful, vanished, failed) = (batch.deleted(), batch.vanished(), batch.failed());
 
+        let already_gone = if vanished > 0 {
+            format!(" {} had already been removed elsewhere.", vanished)
+        } else {
+            String::new()
+        };
         if failed == 0 {
-            self.status = format!("✅ Successfully deleted {} directories. List will refresh automatically.", successful);
+            self.status = format!("✅ Successfully deleted {} directories.{} List will refresh automatically.", successful, already_gone);
         } else {
-            self.status = format!("⚠️ Deleted {} directories, {} failed. Check permissions for failed items.", successful, failed);
+            self.status = format!("⚠️ Deleted {} directories, {} failed.{} Check permissions for failed items.", successful, failed, already_gone);
         }
 
         self.selected_venvs.clear();
@@ -233,46 +930,83 @@ impl GuiApp {
         self.state = GuiAppState::Loading; // Will transition to Browsing when refresh completes
     }
 
+    /// Report a failed action on a .venv
+    ///
+    /// A .venv removed outside VenvCleaner since the scan is dropped from
+    /// the table with a notice, so the totals no longer count it.
+    fn report_failure(&mut self, action: &str, error: VenvCleanerError) {
+        if let VenvCleanerError::VenvVanished { path } = &error {
+            if let Some(position) = self.venvs.iter().position(|v| v.path() == Path::new(path)) {
+                self.venvs.remove(position);
+                self.selected_venvs = self
+                    .selected_venvs
+                    .iter()
+                    .filter(|&&i| i != position)
+                    .map(|&i| if i > position { i - 1 } else { i })
+                    .collect();
+                self.status = format!("⚠️ {} no longer exists (removed outside VenvCleaner) and was dropped from the list", path);
+                return;
+            }
+        }
+        self.status = format!("Could not {}: {}", action, error);
+    }
+
     /// Sort the current list of venvs
+    /// Add .venv directories to the list, keeping the selection
+    ///
+    /// # Returns
+    /// How many were not listed yet
+    fn merge_venvs(&mut self, venvs: Vec<VenvInfo>) -> usize {
+        let selected: HashSet<PathBuf> =
+            self.selected_venvs.iter().filter_map(|&i| self.venvs.get(i)).map(|v| v.path().to_path_buf()).collect();
+        let listed: HashSet<PathBuf> = self.venvs.iter().map(|v| v.path().to_path_buf()).collect();
+        let before = self.venvs.len();
+        self.venvs.extend(venvs.into_iter().filter(|v| !listed.contains(v.path())));
+        self.sort_venvs();
+        self.selected_venvs = self.venvs.iter().enumerate().filter(|(_, v)| selected.contains(v.path())).map(|(i, _)| i).collect();
+        self.venvs.len() - before
+    }
+
     fn sort_venvs(&mut self) {
-        match self.sort_by {
-            GuiSortBy::Path => {
-                self.venvs.sort_by(|a, b| {
-                    if self.reverse_sort {
-                        b.path().cmp(a.path())
-                    } else {
-                        a.path().cmp(b.path())
-                    }
-                });
-            }
-            GuiSortBy::Size => {
-                self.venvs.sort_by(|a, b| {
-                    if self.reverse_sort {
-                        a.size_bytes().cmp(&b.size_bytes())
-                    } else {
-                        b.size_bytes().cmp(&a.size_bytes())
-                    }
-                });
-            }
-            GuiSortBy::Created => {
-                self.venvs.sort_by(|a, b| {
-                    if self.reverse_sort {
-                        a.created().cmp(b.created())
-                    } else {
-                        b.created().cmp(a.created())
-                    }
-                });
-            }
-            GuiSortBy::LastModified => {
-                self.venvs.sort_by(|a, b| {
-                    if self.reverse_sort {
-                        a.last_modified().cmp(b.last_modified())
-                    } else {
-                        b.last_modified().cmp(a.last_modified())
-                    }
-                });
-            }
+        let order = SortOrder::new(self.sort_by.key()).with_reverse(self.reverse_sort);
+        order.sort(&mut self.venvs);
+        if let Some(venvs) = self.compare.as_mut().and_then(|compare| compare.venvs.as_mut()) {
+            order.sort(venvs);
+        }
+    }
+
+    /// Write the rows shown, in the order shown, to a report file chosen by the user
+    fn export_view(&mut self) {
+        let Some(mut path) = rfd::FileDialog::new()
+            .set_directory(&self.current_directory)
+            .set_file_name("venvs.csv")
+            .add_filter("CSV", &["csv"])
+            .add_filter("JSON", &["json"])
+            .add_filter("Markdown", &["md"])
+            .add_filter("HTML", &["html"])
+            .add_filter("Text table", &["txt"])
+            .save_file()
+        else {
+            return;
+        };
+        if ExportFormat::from_path(&path).is_none() {
+            path.set_extension(ExportFormat::Csv.extension());
         }
+
+        let mut shown: Vec<&VenvInfo> = self.get_filtered_venvs().into_iter().map(|(_, venv)| venv).collect();
+        if self.group_by_age {
+            // Grouped tables show one section per age bucket, newest first
+            shown.sort_by_key(|venv| AgeBucket::ALL.iter().position(|&bucket| bucket == venv.age_bucket()));
+        }
+        let count = shown.len();
+        let metadata = self
+            .scan_metadata
+            .clone()
+            .unwrap_or_else(|| ScanMetadata::new(&[self.current_directory.as_path()], self.is_recursive, Utc::now()));
+        self.status = match export::write_file(&path, &shown, &metadata) {
+            Ok(_) => format!("📤 Exported {} .venv directories to {}", count, path.display()),
+            Err(e) => format!("❌ Export failed: {}", e),
+        };
     }
 
     /// Get filtered venvs based on search filter
@@ -284,9 +1018,7 @@ impl GuiApp {
                 .iter()
                 .enumerate()
                 .filter(|(_, venv)| {
-                    let search_lower = self.search_filter.to_lowercase();
-                    venv.location().to_lowercase().contains(&search_lower) ||
-                    venv.path().display().to_string().to_lowercase().contains(&search_lower)
+                    FileUtils::path_matches_search(venv.path(), &self.search_filter)
                 })
                 .collect()
         }
@@ -313,6 +1045,18 @@ impl GuiApp {
                     self.show_folder_dialog = true;
                     ui.close_menu();
                 }
+                if ui.button("📤 Export...").clicked() {
+                    self.export_view();
+                    ui.close_menu();
+                }
+                if ui.button("🔀 Compare With Directory...").clicked() {
+                    self.open_compare_pane();
+                    ui.close_menu();
+                }
+                if self.compare.is_some() && ui.button("Close Comparison").clicked() {
+                    self.compare = None;
+                    ui.close_menu();
+                }
                 ui.separator();
                 if ui.button("❌ Quit").clicked() {
                     ui.ctx().send_viewport_cmd(ViewportCommand::Close);
@@ -333,10 +1077,14 @@ impl GuiApp {
                 ui.separator();
                 if ui.button("🗑️ Delete Selected").clicked() {
                     if !self.selected_venvs.is_empty() {
-                        self.show_confirmation_dialog = true;
+                        self.request_deletion();
                     }
                     ui.close_menu();
                 }
+                if ui.button("✂️ Slim Selected").clicked() {
+                    self.start_slimming();
+                    ui.close_menu();
+                }
             });
 
             ui.menu_button("View", |ui| {
@@ -351,6 +1099,11 @@ impl GuiApp {
                         self.sort_venvs();
                         ui.close_menu();
                     }
+                    if ui.selectable_label(matches!(self.sort_by, GuiSortBy::Files), "Files").clicked() {
+                        self.sort_by = GuiSortBy::Files;
+                        self.sort_venvs();
+                        ui.close_menu();
+                    }
                     if ui.selectable_label(matches!(self.sort_by, GuiSortBy::Created), "Created").clicked() {
                         self.sort_by = GuiSortBy::Created;
                         self.sort_venvs();
@@ -366,6 +1119,37 @@ impl GuiApp {
                 if ui.checkbox(&mut self.reverse_sort, "Reverse Sort").clicked() {
                     self.sort_venvs();
                 }
+
+                ui.separator();
+                ui.menu_button(format!("Zoom: {:.0}%", self.zoom * 100.0), |ui| {
+                    for level in ZOOM_LEVELS {
+                        let selected = (self.zoom - level).abs() < 0.01;
+                        if ui.selectable_label(selected, format!("{:.0}%", level * 100.0)).clicked() {
+                            self.zoom = level;
+                            ui.ctx().set_zoom_factor(level);
+                            ui.close_menu();
+                        }
+                    }
+                });
+                if ui.button("💡 Recommendations").clicked() {
+                    self.recommendations = Some(recommendations::recommend(&self.venvs, &self.cleaner.roots()));
+                    ui.close_menu();
+                }
+                ui.separator();
+                ui.checkbox(&mut self.high_contrast, "High Contrast");
+                ui.menu_button(format!("Density: {}", self.density.label()), |ui| {
+                    for density in Density::ALL {
+                        if ui.selectable_label(self.density == density, density.label()).clicked() {
+                            self.density = density;
+                            ui.close_menu();
+                        }
+                    }
+                });
+                ui.checkbox(&mut self.show_log, "Log Panel (Ctrl+L)");
+                if ui.button("Reset Column Widths").clicked() {
+                    self.column_widths = ColumnWidths::default();
+                    ui.close_menu();
+                }
             });
 
             ui.menu_button("Help", |ui| {
@@ -373,6 +1157,10 @@ impl GuiApp {
                     self.show_help = true;
                     ui.close_menu();
                 }
+                if ui.button("⌨ Keyboard Shortcuts (F1)").clicked() {
+                    self.show_shortcuts = true;
+                    ui.close_menu();
+                }
                 ui.separator();
                 if ui.button("ℹ️ About").clicked() {
                     self.show_about = true;
@@ -385,9 +1173,47 @@ impl GuiApp {
     /// Draw the toolbar
     fn draw_toolbar(&mut self, ui: &mut Ui) {
         ui.horizontal(|ui| {
-            // Refresh button
-            if ui.button("🔄 Refresh").clicked() {
-                self.start_loading_venvs();
+            let delete_label = format!("Delete Selected ({})", self.selected_venvs.len());
+            let has_selection = !self.selected_venvs.is_empty();
+            // Greyed out, with the reason, while the selection is larger than the policy allows
+            let batch_error = self.cleaner.policy().check_batch_size(self.selected_venvs.len()).err().map(|e| format!("🔒 {}", e));
+            let delete_tooltip = batch_error.as_deref().unwrap_or("Delete the selected directories (Delete)");
+            let clicked = Toolbar::new()
+                .action(ToolbarAction::button("Refresh").icon("🔄").tooltip("Scan again (F5)"))
+                .action(ToolbarAction::separator())
+                .action(ToolbarAction::button("Select All").tooltip("Select every directory (Ctrl+A)"))
+                .action(ToolbarAction::button("Select None"))
+                .action(ToolbarAction::separator())
+                .action(
+                    ToolbarAction::button(&delete_label)
+                        .icon("🗑️")
+                        .enabled(has_selection && batch_error.is_none())
+                        .tooltip(delete_tooltip),
+                )
+                .action(
+                    ToolbarAction::button("Slim")
+                        .icon("✂️")
+                        .enabled(has_selection)
+                        .tooltip("Reclaim space without deleting: removes __pycache__, bundled tests, pip caches and orphaned metadata"),
+                )
+                .show(ui);
+
+            if let [refresh, _, select_all, select_none, _, delete, slim] = clicked[..] {
+                if refresh {
+                    self.start_loading_venvs();
+                }
+                if select_all {
+                    self.selected_venvs = (0..self.venvs.len()).collect();
+                }
+                if select_none {
+                    self.selected_venvs.clear();
+                }
+                if delete {
+                    self.request_deletion();
+                }
+                if slim {
+                    self.start_slimming();
+                }
             }
 
             ui.separator();
@@ -399,6 +1225,7 @@ impl GuiApp {
                 .show_ui(ui, |ui| {
                     ui.selectable_value(&mut self.sort_by, GuiSortBy::Path, "Path");
                     ui.selectable_value(&mut self.sort_by, GuiSortBy::Size, "Size");
+                    ui.selectable_value(&mut self.sort_by, GuiSortBy::Files, "Files");
                     ui.selectable_value(&mut self.sort_by, GuiSortBy::Created, "Created");
                     ui.selectable_value(&mut self.sort_by, GuiSortBy::LastModified, "Last Used");
                 });
@@ -408,32 +1235,28 @@ impl GuiApp {
                 self.sort_venvs();
             }
 
-            ui.separator();
-
-            // Selection controls
-            if ui.button("Select All").clicked() {
-                if !self.venvs.is_empty() {
-                    self.selected_venvs = (0..self.venvs.len()).collect();
-                }
-            }
-
-            if ui.button("Select None").clicked() {
-                self.selected_venvs.clear();
-            }
-
-            ui.separator();
-
-            // Delete button
-            ui.add_enabled_ui(!self.selected_venvs.is_empty(), |ui| {
-                if ui.button(format!("🗑️ Delete Selected ({})", self.selected_venvs.len())).clicked() {
-                    self.show_confirmation_dialog = true;
-                }
-            });
+            ui.checkbox(&mut self.group_by_age, "Group by age")
+                .on_hover_text("Split the table into collapsible 0–30, 31–90, 91–180 and >180 day sections");
+            ui.checkbox(&mut self.show_file_counts, "File counts")
+                .on_hover_text("Show how many files each .venv holds; many small files are slow to delete and use up inodes");
+            ui.checkbox(&mut self.show_size_delta, "Δ since last scan")
+                .on_hover_text("Show how much each .venv grew or shrank since the previous scan; the largest growth is highlighted");
 
             ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
                 // Search filter
                 ui.add_sized([200.0, 20.0], TextEdit::singleline(&mut self.search_filter).hint_text("Search directories..."));
                 ui.label("🔍");
+                ui.separator();
+
+                // Where deletions go stays in view, so a permanent delete never comes as a surprise
+                let (badge, color, description) = if let Some(dir) = self.cleaner.archive_dir() {
+                    ("📦 Archive", Color32::from_rgb(23, 162, 184), format!("Deleted folders are compressed into {}", dir.display()))
+                } else if self.cleaner.is_using_trash() {
+                    ("♻️ Trash", Color32::from_rgb(40, 167, 69), self.cleaner.delete_mode_description())
+                } else {
+                    ("🔥 Permanent delete", Color32::from_rgb(220, 53, 69), self.cleaner.delete_mode_description())
+                };
+                ui.label(RichText::new(badge).color(color).strong()).on_hover_text(description);
             });
         });
     }
@@ -470,205 +1293,193 @@ impl GuiApp {
 
             ui.add_space(40.0);
             ui.label("Please wait while scanning directories...");
+            self.draw_cancel_button(ui, "Stop the scan and list the .venv directories found so far");
+        });
+    }
+
+    /// Draw the dashboard strip of status cards above the table
+    fn draw_dashboard(&self, ui: &mut Ui) {
+        let total_size: ByteSize = self.venvs.iter().map(VenvInfo::size).sum();
+        let reclaimable: ByteSize = self.venvs.iter().filter(|v| v.is_old()).map(VenvInfo::size).sum();
+        let scan_duration = match self.last_scan_duration {
+            Some(duration) if duration.as_secs() >= 1 => format!("{:.1} s", duration.as_secs_f64()),
+            Some(duration) => format!("{} ms", duration.as_millis()),
+            None => "—".to_string(),
+        };
+
+        ui.horizontal(|ui| {
+            StatusCard::new("Virtual environments", self.venvs.len().to_string(), "📁", Color32::from_rgb(70, 130, 200))
+                .show(ui);
+            StatusCard::new("Total size", total_size.to_string(), "💾", utils::get_size_color(total_size.bytes()))
+                .show(ui);
+            StatusCard::new("Reclaimable (>90 days)", reclaimable.to_string(), "🧹", Color32::from_rgb(255, 165, 0))
+                .show(ui)
+                .on_hover_text("Size of the .venv directories not used in the last 90 days");
+            StatusCard::new("Last scan", scan_duration, "⏱", Color32::GRAY)
+                .show(ui);
+
+            let (full_in, color) = match self.growth.and_then(|growth| growth.days_until_full()) {
+                Some(days) if days < 90.0 => (format!("~{:.0} days", days), Color32::from_rgb(220, 53, 69)),
+                Some(days) if days <= 3650.0 => (format!("~{:.0} days", days), Color32::GRAY),
+                _ => ("—".to_string(), Color32::GRAY),
+            };
+            let hover = match self.growth {
+                Some(growth) => format!(
+                    "{} free; the .venv directories changed by {}{}/day over {:.0} days, {}",
+                    ByteSize::new(growth.free_bytes),
+                    if growth.bytes_per_day < 0.0 { "-" } else { "+" },
+                    ByteSize::new(growth.bytes_per_day.abs() as u64),
+                    growth.span_days,
+                    growth.summary()
+                ),
+                None => "Scan again on another day to see when the disk fills up".to_string(),
+            };
+            StatusCard::new("Disk full in", full_in, "📈", color)
+                .show(ui)
+                .on_hover_text(hover);
         });
     }
 
     /// Draw the .venv directory list
     fn draw_venv_list(&mut self, ui: &mut Ui) {
-        // Collect filtered data first to avoid borrowing issues
-        let filtered_indices: Vec<usize> = if self.search_filter.is_empty() {
-            (0..self.venvs.len()).collect()
-        } else {
-            let search_lower = self.search_filter.to_lowercase();
-            self.venvs
-                .iter()
-                .enumerate()
-                .filter(|(_, venv)| {
-                    venv.location().to_lowercase().contains(&search_lower) ||
-                    venv.path().display().to_string().to_lowercase().contains(&search_lower)
-                })
-                .map(|(i, _)| i)
-                .collect()
-        };
+        let filtered_count = self.get_filtered_venvs().len();
+
+        self.draw_dashboard(ui);
+        ui.add_space(4.0);
 
         // Summary info
         ui.horizontal(|ui| {
             ui.label(format!("Found {} directories", self.venvs.len()));
             if !self.search_filter.is_empty() {
-                ui.label(format!("(showing {} filtered)", filtered_indices.len()));
+                ui.label(format!("(showing {} filtered)", filtered_count));
             }
             if !self.selected_venvs.is_empty() {
-                let total_size: u64 = self.selected_venvs
+                let total_size: ByteSize = self.selected_venvs
                     .iter()
                     .filter_map(|&i| self.venvs.get(i))
-                    .map(|v| v.size_bytes())
+                    .map(|v| v.size())
                     .sum();
                 ui.label(format!("| Selected: {} ({} total)",
                     self.selected_venvs.len(),
-                    utils::format_size(total_size)));
+                    total_size));
             }
         });
 
         ui.separator();
 
-        // Table header
-        ScrollArea::vertical()
-            .id_source("venv_table")
-            .show(ui, |ui| {
+        self.draw_failed_roots_section(ui);
+        self.draw_archived_section(ui);
+
+        let action = VenvTable::new(&self.venvs, &mut self.selected_venvs, &self.search_filter)
+            .group_by_age(self.group_by_age)
+            .file_counts(self.show_file_counts || self.sort_by == GuiSortBy::Files)
+            .size_deltas(self.show_size_delta.then_some(&self.size_deltas))
+            .column_widths(&mut self.column_widths)
+            .row_height(self.density.row_height())
+            .show(ui);
+
+        match action {
+            Some(VenvTableAction::OpenShell(index)) => {
+                if let Some(venv) = self.venvs.get(index) {
+                    match shell::open_activated_shell(venv.path()) {
+                        Ok(()) => self.status = format!("Opened a terminal for {}", venv.location()),
+                        Err(e) => self.report_failure("open shell", e),
+                    }
+                }
+            }
+            Some(VenvTableAction::OpenEditor(index)) => {
+                if let Some(venv) = self.venvs.get(index) {
+                    match editor::open_project(venv.path(), self.editor.as_deref()) {
+                        Ok(()) => self.status = format!("Opened {} in the editor", venv.location()),
+                        Err(e) => self.report_failure("open editor", e),
+                    }
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// Draw the comparison pane beside the main list, while comparing two roots
+    fn draw_compare_pane(&mut self, ctx: &Context) {
+        let main_selected = self.selected_venvs.len();
+        let main_total: ByteSize = self.venvs.iter().map(VenvInfo::size).sum();
+        let group_by_age = self.group_by_age;
+        let file_counts = self.show_file_counts || self.sort_by == GuiSortBy::Files;
+        let row_height = self.density.row_height();
+        let Some(compare) = &mut self.compare else {
+            return;
+        };
+
+        let mut close = false;
+        let mut rescan = false;
+        let mut to_main = false;
+        let mut to_compare = false;
+        SidePanel::right("compare_pane")
+            .resizable(true)
+            .default_width(ctx.screen_rect().width() / 2.0)
+            .show(ctx, |ui| {
                 ui.horizontal(|ui| {
-                    ui.allocate_ui_with_layout(
-                        Vec2::new(ui.available_width(), ui.spacing().button_padding.y * 2.0 + 14.0),
-                        Layout::left_to_right(Align::Center),
-                        |ui| {
-                            // Column headers
-                            if ui.selectable_label(false, "Select").clicked() {
-                                // Toggle all selection
-                                if self.selected_venvs.len() == filtered_indices.len() {
-                                    self.selected_venvs.clear();
-                                } else {
-                                    self.selected_venvs = filtered_indices.iter().cloned().collect();
-                                }
-                            }
-                            ui.separator();
-
-                            ui.allocate_ui_with_layout(
-                                Vec2::new(40.0, 20.0),
-                                Layout::left_to_right(Align::Center),
-                                |ui| { ui.label("Age"); },
-                            );
-                            ui.separator();
-
-                            ui.allocate_ui_with_layout(
-                                Vec2::new(400.0, 20.0),
-                                Layout::left_to_right(Align::Center),
-                                |ui| { ui.label("Location"); },
-                            );
-                            ui.separator();
-
-                            ui.allocate_ui_with_layout(
-                                Vec2::new(100.0, 20.0),
-                                Layout::left_to_right(Align::Center),
-                                |ui| { ui.label("Size"); },
-                            );
-                            ui.separator();
-
-                            ui.allocate_ui_with_layout(
-                                Vec2::new(150.0, 20.0),
-                                Layout::left_to_right(Align::Center),
-                                |ui| { ui.label("Last Used"); },
-                            );
-                            ui.separator();
-
-                            ui.allocate_ui_with_layout(
-                                Vec2::new(100.0, 20.0),
-                                Layout::left_to_right(Align::Center),
-                                |ui| { ui.label("Actions"); },
-                            );
-                        },
-                    );
+                    ui.heading(format!("🔀 {}", compare.root.display()));
+                    ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                        close = ui.small_button("✖").on_hover_text("Close the comparison").clicked();
+                        rescan = ui.small_button("🔄").on_hover_text("Scan this directory again").clicked();
+                    });
                 });
 
-                ui.separator();
+                let Some(venvs) = &compare.venvs else {
+                    ui.label("🔍 Scanning...");
+                    return;
+                };
+                if let Some(error) = &compare.error {
+                    ui.colored_label(Color32::from_rgb(220, 53, 69), format!("❌ {}", error));
+                }
 
-                // Table rows
-                for &original_index in &filtered_indices {
-                    if let Some(venv) = self.venvs.get(original_index) {
-                        let is_selected = self.selected_venvs.contains(&original_index);
-
-                        let row_response = ui.horizontal(|ui| {
-                            // Selection checkbox
-                            let mut selected = is_selected;
-                            if ui.checkbox(&mut selected, "").clicked() {
-                                if selected {
-                                    self.selected_venvs.insert(original_index);
-                                } else {
-                                    self.selected_venvs.remove(&original_index);
-                                }
-                            }
-                            ui.separator();
-
-                            // Age indicator
-                            let age_days = venv.age_in_days();
-                            ui.allocate_ui_with_layout(
-                                Vec2::new(40.0, 20.0),
-                                Layout::left_to_right(Align::Center),
-                                |ui| {
-                                    ui.colored_label(
-                                        utils::get_age_color(age_days),
-                                        format!("{} {}d", utils::get_age_indicator(age_days), age_days)
-                                    );
-                                },
-                            );
-                            ui.separator();
-
-                            // Location
-                            ui.allocate_ui_with_layout(
-                                Vec2::new(400.0, 20.0),
-                                Layout::left_to_right(Align::Center),
-                                |ui| {
-                                    ui.label(utils::format_path_for_display(&venv.location(), 60));
-                                },
-                            );
-                            ui.separator();
-
-                            // Size
-                            ui.allocate_ui_with_layout(
-                                Vec2::new(100.0, 20.0),
-                                Layout::left_to_right(Align::Center),
-                                |ui| {
-                                    ui.colored_label(
-                                        utils::get_size_color(venv.size_bytes()),
-                                        utils::format_size(venv.size_bytes())
-                                    );
-                                },
-                            );
-                            ui.separator();
-
-                            // Last used
-                            ui.allocate_ui_with_layout(
-                                Vec2::new(150.0, 20.0),
-                                Layout::left_to_right(Align::Center),
-                                |ui| {
-                                    ui.label(venv.last_modified_formatted());
-                                },
-                            );
-                            ui.separator();
-
-                            // Actions
-                            ui.allocate_ui_with_layout(
-                                Vec2::new(100.0, 20.0),
-                                Layout::left_to_right(Align::Center),
-                                |ui| {
-                                    if ui.small_button("📁 Open").clicked() {
-                                        if let Some(parent) = venv.parent_path() {
-                                            let _ = open::that(parent);
-                                        }
-                                    }
-                                },
-                            );
-                        });
+                let total: ByteSize = venvs.iter().map(VenvInfo::size).sum();
+                let reclaimable: ByteSize = venvs.iter().filter(|v| v.is_old()).map(VenvInfo::size).sum();
+                let difference = if total >= main_total {
+                    format!("{} more than the main list", total - main_total)
+                } else {
+                    format!("{} less than the main list", main_total - total)
+                };
+                ui.label(format!(
+                    "{} directories, {} ({}), {} reclaimable (>90 days)",
+                    venvs.len(),
+                    total,
+                    difference,
+                    reclaimable
+                ));
 
-                        // Row selection on click
-                        if row_response.response.clicked() {
-                            if self.selected_venvs.contains(&original_index) {
-                                self.selected_venvs.remove(&original_index);
-                            } else {
-                                self.selected_venvs.insert(original_index);
-                            }
-                        }
+                ui.horizontal(|ui| {
+                    to_main = ui
+                        .add_enabled(!compare.selected.is_empty(), Button::new(format!("⬅ Move Selected to Main ({})", compare.selected.len())))
+                        .on_hover_text("Deleting happens from the main list; move rows there to delete them with its selection")
+                        .clicked();
+                    to_compare = ui
+                        .add_enabled(main_selected > 0, Button::new(format!("➡ Move Main Selection Here ({})", main_selected)))
+                        .clicked();
+                });
+                ui.separator();
 
-                        // Highlight selected rows
-                        if is_selected {
-                            let rect = row_response.response.rect;
-                            ui.painter().rect_filled(
-                                rect,
-                                Rounding::same(2.0),
-                                Color32::from_rgba_unmultiplied(100, 150, 255, 30)
-                            );
-                        }
-                    }
-                }
+                ui.push_id("compare_table", |ui| {
+                    VenvTable::new(venvs, &mut compare.selected, &self.search_filter)
+                        .group_by_age(group_by_age)
+                        .file_counts(file_counts)
+                        .column_widths(&mut compare.column_widths)
+                        .row_height(row_height)
+                        .show(ui);
+                });
             });
+
+        if close {
+            self.compare = None;
+        } else if rescan {
+            let root = compare.root.clone();
+            self.start_compare_scan(root);
+        } else if to_main {
+            self.move_compare_selection_to_main();
+        } else if to_compare {
+            self.move_main_selection_to_compare();
+        }
     }
 
     /// Draw deletion progress
@@ -681,15 +1492,32 @@ impl GuiApp {
             ui.label(&self.status);
             ui.add_space(20.0);
 
-            let progress_bar = ProgressBar::new(self.deletion_progress)
-                .text(format!("{:.0}%", self.deletion_progress * 100.0));
-            ui.add(progress_bar);
+            ProgressIndicator::new(self.deletion_progress)
+                .text(deletion_eta::format_eta(self.deletion_eta))
+                .show(ui);
 
             ui.add_space(20.0);
             ui.label("Please wait...");
+            self.draw_cancel_button(ui, "Stop after the directory being deleted; the rest are left untouched");
         });
     }
 
+    /// Draw the Cancel button of a scan or deletion that can be stopped
+    ///
+    /// The state returns to Browsing once the background thread has stopped.
+    fn draw_cancel_button(&mut self, ui: &mut Ui, tooltip: &str) {
+        let Some(cancellation) = &self.cancellation else {
+            return;
+        };
+        ui.add_space(20.0);
+        if cancellation.is_cancelled() {
+            ui.add_enabled(false, Button::new("⏹ Cancelling..."));
+        } else if ui.button("⏹ Cancel").on_hover_text(tooltip).clicked() {
+            cancellation.cancel();
+            self.status = "⏹ Cancelling, finishing the current step...".to_string();
+        }
+    }
+
     /// Draw error screen
     fn draw_error_screen(&mut self, ui: &mut Ui) {
         ui.vertical_centered(|ui| {
@@ -726,44 +1554,149 @@ impl GuiApp {
             return;
         }
 
-        Window::new("Confirm Deletion")
-            .collapsible(false)
-            .resizable(false)
-            .anchor(Align2::CENTER_CENTER, Vec2::ZERO)
-            .show(ctx, |ui| {
-                ui.vertical_centered(|ui| {
-                    ui.add_space(10.0);
-                    ui.heading("⚠️ Confirm Deletion");
-                    ui.add_space(20.0);
+        let selected: Vec<&VenvInfo> = self.selected_venvs
+            .iter()
+            .filter_map(|&i| self.venvs.get(i))
+            .collect();
+        let total_size: ByteSize = selected.iter().map(|v| v.size()).sum();
+        let with_user_data: Vec<&&VenvInfo> = selected.iter().filter(|v| v.has_user_data()).collect();
+        let action = if self.delete_to_trash { "move to the Trash" } else { "permanently delete" };
+        let mut message = format!(
+            "You are about to {} {} .venv directories.\n\nTotal size to be freed: {} ({})",
+            action,
+            self.selected_venvs.len(),
+            total_size,
+            deletion_eta::describe_file_count(self.deletion_file_count)
+        );
+        // The trash was asked for but there is none here
+        if !self.delete_to_trash && !self.cleaner.is_using_trash() && self.cleaner.delete_mode() != DeleteMode::Permanent {
+            message.push_str(&format!("\n\n{}.", self.cleaner.delete_mode_description()));
+        }
+        if !with_user_data.is_empty() {
+            message.push_str(&format!(
+                "\n\n◆ {} of them contain user data ({}) that cannot be reinstalled.",
+                with_user_data.len(),
+                ByteSize::new(with_user_data.iter().map(|v| v.user_data_bytes()).sum())
+            ));
+        }
 
-                    let selected_venvs: Vec<&VenvInfo> = self.selected_venvs
-                        .iter()
-                        .filter_map(|&i| self.venvs.get(i))
-                        .collect();
+        // User data has to be accepted with a first click before anything is deleted
+        let needs_user_data_confirmation = !with_user_data.is_empty() && !self.user_data_confirmed;
+        let (confirm_text, warning) = if needs_user_data_confirmation {
+            ("◆ Accept losing user data", "⚠️ Peek inside first: the user data is deleted too!")
+        } else if self.delete_to_trash {
+            ("♻️ Move to Trash", "♻️ Recoverable: they can be restored from the Trash")
+        } else {
+            ("🗑️ Delete", "⚠️ This action cannot be undone!")
+        };
 
-                    let total_size: u64 = selected_venvs.iter().map(|v| v.size_bytes()).sum();
+        let peek_text = if self.peeks.is_some() { "🙈 Hide contents" } else { "🔍 Peek inside" };
+        let mut dialog = ConfirmationDialog::new("⚠️ Confirm Deletion", &message)
+            .buttons(confirm_text, "❌ Cancel")
+            .warning(warning)
+            .alternative(peek_text);
+        if !self.delete_to_trash {
+            dialog = dialog.danger();
+        }
+        // The policy can require the trash; then the checkbox is shown greyed out
+        dialog = dialog.checkbox("Move to the Trash instead of deleting permanently", &mut self.delete_to_trash);
+        if self.cleaner.policy().require_trash {
+            dialog = dialog.checkbox_locked("🔒 Required by the system policy");
+        }
+        let result = dialog.show(ctx);
 
-                    ui.label(format!("You are about to permanently delete {} .venv directories.", selected_venvs.len()));
-                    ui.add_space(10.0);
-                    ui.label(format!("Total size to be freed: {}", utils::format_size(total_size)));
-                    ui.add_space(10.0);
-                    ui.colored_label(Color32::RED, "⚠️ This action cannot be undone!");
-                    ui.add_space(20.0);
+        match result {
+            DialogResult::Confirm if needs_user_data_confirmation => {
+                self.user_data_confirmed = true;
+            }
+            DialogResult::Confirm => {
+                self.show_confirmation_dialog = false;
+                self.peeks = None;
+                self.user_data_confirmed = false;
+                self.start_deletion();
+            }
+            DialogResult::Cancel => {
+                self.show_confirmation_dialog = false;
+                self.peeks = None;
+                self.user_data_confirmed = false;
+            }
+            DialogResult::Alternative => {
+                self.peeks = match self.peeks {
+                    Some(_) => None,
+                    None => Some(self.peek_selected()),
+                };
+            }
+            DialogResult::None => {}
+        }
 
-                    ui.horizontal(|ui| {
-                        if ui.button("❌ Cancel").clicked() {
-                            self.show_confirmation_dialog = false;
-                        }
+        self.draw_peek_window(ctx);
+    }
 
-                        ui.add_space(20.0);
+    /// Read the contents of the selected .venv directories, sorted by path
+    fn peek_selected(&self) -> Vec<VenvPeek> {
+        let mut paths: Vec<&Path> = self.selected_venvs
+            .iter()
+            .filter_map(|&i| self.venvs.get(i))
+            .map(|v| v.path())
+            .collect();
+        paths.sort_by(|a, b| sorting::compare_paths(a, b));
+
+        paths
+            .into_iter()
+            .filter_map(|path| match VenvPeek::of(path, peek::DEFAULT_PACKAGE_LIMIT) {
+                Ok(peek) => Some(peek),
+                Err(e) => {
+                    warn!("Could not look inside {}: {}", path.display(), e);
+                    None
+                }
+            })
+            .collect()
+    }
 
-                        if ui.button("🗑️ Delete").clicked() {
-                            self.show_confirmation_dialog = false;
-                            self.start_deletion();
-                        }
-                    });
+    /// Draw the contents of the selected .venv directories next to the confirmation dialog
+    fn draw_peek_window(&self, ctx: &Context) {
+        let Some(peeks) = &self.peeks else {
+            return;
+        };
 
-                    ui.add_space(10.0);
+        Window::new("🔍 Peek inside")
+            .collapsible(false)
+            .anchor(Align2::RIGHT_CENTER, Vec2::new(-20.0, 0.0))
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                ScrollArea::vertical().max_height(500.0).show(ui, |ui| {
+                    for (index, peek) in peeks.iter().enumerate() {
+                        CollapsingHeader::new(peek.path.display().to_string())
+                            .default_open(index == 0)
+                            .show(ui, |ui| {
+                                ui.strong("Contents");
+                                Grid::new(("peek_entries", index)).striped(true).show(ui, |ui| {
+                                    for entry in &peek.entries {
+                                        // Loose files at the top of a .venv are the usual sign of stored data
+                                        if entry.is_dir || entry.name == "pyvenv.cfg" {
+                                            ui.label(entry.display_name());
+                                        } else {
+                                            ui.colored_label(Color32::from_rgb(255, 193, 7), entry.display_name());
+                                        }
+                                        ui.label(ByteSize::new(entry.size_bytes).to_string());
+                                        ui.end_row();
+                                    }
+                                });
+
+                                ui.add_space(6.0);
+                                ui.strong("Largest packages");
+                                if peek.packages.is_empty() {
+                                    ui.weak("No site-packages found");
+                                }
+                                Grid::new(("peek_packages", index)).striped(true).show(ui, |ui| {
+                                    for package in &peek.packages {
+                                        ui.label(package.display_name());
+                                        ui.label(ByteSize::new(package.size_bytes).to_string());
+                                        ui.end_row();
+                                    }
+                                });
+                            });
+                    }
                 });
             });
     }
@@ -780,41 +1713,35 @@ impl GuiApp {
             .default_size([600.0, 400.0])
             .show(ctx, |ui| {
                 ScrollArea::vertical().show(ui, |ui| {
-                    ui.heading("VenvCleaner GUI Help");
+                    ui.heading(format!("{} GUI Help", help::ABOUT.name));
                     ui.separator();
 
                     ui.heading("Overview");
-                    ui.label("VenvCleaner helps you find, analyze, and clean up Python virtual environment folders (.venv) on your system.");
+                    ui.label(help::OVERVIEW);
                     ui.add_space(10.0);
 
                     ui.heading("Usage");
-                    ui.label("• Use the table to view all .venv directories found");
-                    ui.label("• Click checkboxes or rows to select directories for deletion");
-                    ui.label("• Use the search box to filter directories");
-                    ui.label("• Sort by different criteria using the dropdown");
-                    ui.label("• Click 'Delete Selected' to remove chosen directories");
+                    for line in help::GUI_USAGE {
+                        ui.label(format!("• {}", line));
+                    }
                     ui.add_space(10.0);
 
                     ui.heading("Color Coding");
-                    ui.horizontal(|ui| {
-                        ui.colored_label(Color32::from_rgb(100, 255, 100), "🟢");
-                        ui.label("Recently used (<30 days)");
-                    });
-                    ui.horizontal(|ui| {
-                        ui.colored_label(Color32::from_rgb(255, 255, 100), "🟡");
-                        ui.label("Moderately used (30-90 days)");
-                    });
-                    ui.horizontal(|ui| {
-                        ui.colored_label(Color32::from_rgb(255, 100, 100), "🔴");
-                        ui.label("Old (>90 days)");
-                    });
+                    for entry in help::STATUS_ICONS {
+                        ui.horizontal(|ui| {
+                            ui.colored_label(status_icon_color(entry.keys), entry.keys);
+                            ui.label(entry.description);
+                        });
+                    }
                     ui.add_space(10.0);
 
-                    ui.heading("Keyboard Shortcuts");
-                    ui.label("• Ctrl+A: Select all directories");
-                    ui.label("• Delete: Delete selected directories");
-                    ui.label("• F5: Refresh list");
-                    ui.add_space(10.0);
+                    for (heading, entries) in [("Keyboard Shortcuts", help::GUI_KEYBOARD_SHORTCUTS), ("Mouse", help::GUI_MOUSE_ACTIONS)] {
+                        ui.heading(heading);
+                        for entry in entries {
+                            ui.label(format!("• {}: {}", entry.keys, entry.description));
+                        }
+                        ui.add_space(10.0);
+                    }
 
                     if ui.button("Close").clicked() {
                         self.show_help = false;
@@ -823,29 +1750,160 @@ impl GuiApp {
             });
     }
 
+    /// Draw the recent log lines in a panel along the bottom of the window
+    fn draw_log_panel(&mut self, ctx: &Context) {
+        if !self.show_log {
+            return;
+        }
+
+        TopBottomPanel::bottom("log_panel")
+            .resizable(true)
+            .default_height(150.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.strong(format!("📜 Log ({} warnings)", self.logs.warning_count()));
+                    if !self.logs.is_capturing() {
+                        ui.weak("log capture is not active");
+                    }
+                    ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                        if ui.small_button("✖").on_hover_text("Hide the log panel (Ctrl+L)").clicked() {
+                            self.show_log = false;
+                        }
+                    });
+                });
+                ui.separator();
+                ScrollArea::vertical().stick_to_bottom(true).auto_shrink([false, false]).show(ui, |ui| {
+                    for line in self.logs.lines() {
+                        let color = match line.level {
+                            log::Level::Error => Color32::from_rgb(220, 53, 69),
+                            log::Level::Warn => Color32::from_rgb(255, 193, 7),
+                            _ => ui.visuals().weak_text_color(),
+                        };
+                        ui.label(RichText::new(line.formatted()).monospace().color(color));
+                    }
+                });
+            });
+    }
+
+    /// Draw the keyboard shortcuts overlay
+    fn draw_shortcuts_window(&mut self, ctx: &Context) {
+        if !self.show_shortcuts {
+            return;
+        }
+
+        let mut open = true;
+        Window::new("⌨ Keyboard Shortcuts")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, Vec2::ZERO)
+            .show(ctx, |ui| {
+                for (heading, entries) in [("Keyboard", help::GUI_KEYBOARD_SHORTCUTS), ("Mouse", help::GUI_MOUSE_ACTIONS)] {
+                    ui.heading(heading);
+                    Grid::new(heading).num_columns(2).spacing([20.0, 4.0]).striped(true).show(ui, |ui| {
+                        for entry in entries {
+                            ui.strong(entry.keys);
+                            ui.label(entry.description);
+                            ui.end_row();
+                        }
+                    });
+                    ui.add_space(10.0);
+                }
+                ui.label(RichText::new("Press F1 or Esc to close").weak());
+            });
+        self.show_shortcuts &= open;
+    }
+
+    /// Draw the cleanup recommendations window
+    fn draw_recommendations_window(&mut self, ctx: &Context) {
+        let Some(recommendations) = &self.recommendations else {
+            return;
+        };
+
+        let mut open = true;
+        Window::new("💡 Recommendations")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .anchor(Align2::CENTER_CENTER, Vec2::ZERO)
+            .show(ctx, |ui| {
+                if recommendations.is_empty() {
+                    ui.label("✅ Nothing to recommend: no broken, old, end-of-life or duplicate .venv directories");
+                }
+                for recommendation in recommendations {
+                    ui.horizontal(|ui| {
+                        ui.strong(format!("{} {}", recommendation.kind().icon(), recommendation.title()));
+                        ui.label(ByteSize::new(recommendation.bytes()).to_string());
+                    });
+                    ui.horizontal(|ui| {
+                        ui.monospace(recommendation.command());
+                        if ui.small_button("📋 Copy").on_hover_text("Copy the command").clicked() {
+                            ui.output_mut(|output| output.copied_text = recommendation.command().to_string());
+                        }
+                    });
+                    if let Some(link) = recommendation.kind().doc_link() {
+                        ui.hyperlink(link);
+                    }
+                    ui.add_space(8.0);
+                }
+            });
+        if !open {
+            self.recommendations = None;
+        }
+    }
+
     /// Draw about window
     fn draw_about_window(&mut self, ctx: &Context) {
         if !self.show_about {
             return;
         }
 
-        Window::new("About VenvCleaner")
+        Window::new(format!("About {}", help::ABOUT.name))
             .collapsible(false)
             .resizable(false)
             .anchor(Align2::CENTER_CENTER, Vec2::ZERO)
             .show(ctx, |ui| {
                 ui.vertical_centered(|ui| {
                     ui.add_space(10.0);
-                    ui.heading("VenvCleaner");
+                    ui.heading(help::ABOUT.name);
                     ui.add_space(10.0);
-                    ui.label("Version 0.1.0");
+                    ui.label(format!("Version {}", help::ABOUT.version));
+                    ui.label(
+                        RichText::new(format!("Commit {} ({})", help::ABOUT.commit, help::ABOUT.build_date))
+                            .small()
+                            .weak(),
+                    );
                     ui.add_space(20.0);
-                    ui.label("A multi-mode application to help manage and clean up");
-                    ui.label("Python virtual environment folders (.venv) on Mac and Linux.");
+                    ui.add(Label::new(help::ABOUT.summary).wrap(true));
+                    ui.add_space(10.0);
+                    ui.label(format!("Licensed under the {} license", help::ABOUT.license));
                     ui.add_space(20.0);
                     ui.label("Built with Rust and egui");
-                    ui.add_space(20.0);
+                    ui.add_space(10.0);
+                });
 
+                let credits = help::third_party_credits();
+                CollapsingHeader::new(format!("Third-party crates ({})", credits.len()))
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                            Grid::new("third_party_credits").striped(true).show(ui, |ui| {
+                                for credit in &credits {
+                                    ui.label(credit.name);
+                                    ui.label(RichText::new(credit.version).weak());
+                                    ui.label(credit.license);
+                                    ui.end_row();
+                                }
+                            });
+                        });
+                    });
+
+                ui.add_space(10.0);
+                ui.vertical_centered(|ui| {
+                    if ui.button("📂 Open config folder").on_hover_text("Open the folder holding config.toml").clicked() {
+                        self.open_config_folder();
+                    }
+                    ui.add_space(10.0);
                     if ui.button("Close").clicked() {
                         self.show_about = false;
                     }
@@ -854,6 +1912,22 @@ impl GuiApp {
             });
     }
 
+    /// Open the configuration folder in the file manager, creating it first if needed
+    fn open_config_folder(&mut self) {
+        let Some(dir) = paths::config_dir() else {
+            self.status = "This platform has no configuration directory".to_string();
+            return;
+        };
+        let result = std::fs::create_dir_all(&dir).and_then(|()| open::that(&dir));
+        self.status = match result {
+            Ok(()) => format!("Opened {}", dir.display()),
+            Err(e) => {
+                warn!("Failed to open config folder {}: {}", dir.display(), e);
+                format!("Could not open {}: {}", dir.display(), e)
+            }
+        };
+    }
+
     /// Draw folder selection dialog
     fn draw_folder_dialog(&mut self, ctx: &Context) {
         if !self.show_folder_dialog {
@@ -976,11 +2050,28 @@ impl GuiApp {
     }
 }
 
+/// Get the colour a status icon is drawn in within the help window
+fn status_icon_color(icon: &str) -> Color32 {
+    match icon {
+        "🟢" => Color32::from_rgb(100, 255, 100),
+        "🟡" => Color32::from_rgb(255, 255, 100),
+        "🔴" => Color32::from_rgb(255, 100, 100),
+        "✎" => Color32::from_rgb(200, 120, 255),
+        "◆" => Color32::from_rgb(220, 53, 69),
+        _ => Color32::GRAY,
+    }
+}
+
 impl eframe::App for GuiApp {
     /// Update the application
     fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
+        // Zoom and theme first, so this frame is laid out with them
+        self.apply_view_settings(ctx);
+
         // Handle background events
         self.handle_events();
+        #[cfg(feature = "tray")]
+        self.handle_tray_actions(ctx);
 
         // Update animations
         self.update_animation();
@@ -992,6 +2083,12 @@ impl eframe::App for GuiApp {
 
         // Handle keyboard shortcuts
         ctx.input(|i| {
+            if i.key_pressed(Key::F1) {
+                self.show_shortcuts = !self.show_shortcuts;
+            }
+            if i.modifiers.ctrl && i.key_pressed(Key::L) {
+                self.show_log = !self.show_log;
+            }
             if i.key_pressed(Key::F5) {
                 self.start_loading_venvs();
             }
@@ -1001,15 +2098,24 @@ impl eframe::App for GuiApp {
                 }
             }
             if i.key_pressed(Key::Delete) && !self.selected_venvs.is_empty() {
-                self.show_confirmation_dialog = true;
+                self.request_deletion();
             }
             if i.key_pressed(Key::Escape) {
                 self.show_confirmation_dialog = false;
+                self.pending_archive_deletion = None;
                 self.show_help = false;
+                self.show_shortcuts = false;
                 self.show_about = false;
             }
         });
 
+        // New log lines only arrive when the log panel is redrawn
+        if self.logs.drain() > 0 && self.show_log {
+            ctx.request_repaint();
+        }
+        self.draw_log_panel(ctx);
+        self.draw_compare_pane(ctx);
+
         // Main UI
         CentralPanel::default().show(ctx, |ui| {
             ui.vertical(|ui| {
@@ -1032,7 +2138,10 @@ impl eframe::App for GuiApp {
 
         // Modal dialogs
         self.draw_confirmation_dialog(ctx);
+        self.draw_archive_deletion_dialog(ctx);
         self.draw_help_window(ctx);
+        self.draw_shortcuts_window(ctx);
+        self.draw_recommendations_window(ctx);
         self.draw_about_window(ctx);
         self.draw_folder_dialog(ctx);
     }
@@ -1042,6 +2151,10 @@ impl eframe::App for GuiApp {
         eframe::set_value(storage, "search_filter", &self.search_filter);
         eframe::set_value(storage, "sort_by", &(self.sort_by as u8));
         eframe::set_value(storage, "reverse_sort", &self.reverse_sort);
+        eframe::set_value(storage, "zoom", &self.zoom);
+        eframe::set_value(storage, "high_contrast", &self.high_contrast);
+        eframe::set_value(storage, "density", &self.density);
+        eframe::set_value(storage, "column_widths", &self.column_widths);
     }
 
     /// Auto-save interval
@@ -4,7 +4,8 @@
 //! including custom widgets, dialogs, and specialized controls.
 
 use eframe::egui::{self, *};
-use crate::core::VenvInfo;
+use crate::core::{AgeBucket, ByteSize, FileUtils, SizeDeltas, VenvInfo};
+use super::theme::Density;
 use super::utils;
 
 /// A custom table widget for displaying .venv directories
@@ -12,6 +13,95 @@ pub struct VenvTable<'a> {
     venvs: &'a [VenvInfo],
     selected: &'a mut std::collections::HashSet<usize>,
     search_filter: &'a str,
+    group_by_age: bool,
+    file_counts: bool,
+    size_deltas: Option<&'a SizeDeltas>,
+    widths: Option<&'a mut ColumnWidths>,
+    row_height: f32,
+}
+
+/// An action requested from a table row that the table cannot perform itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VenvTableAction {
+    /// Open a terminal with the .venv at this index activated
+    OpenShell(usize),
+    /// Open the project owning the .venv at this index in the editor
+    OpenEditor(usize),
+}
+
+/// User-adjustable widths of the resizable .venv table columns
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ColumnWidths {
+    pub age: f32,
+    pub location: f32,
+    pub size: f32,
+    pub files: f32,
+    pub size_delta: f32,
+    pub last_used: f32,
+}
+
+impl ColumnWidths {
+    /// Narrowest width a column can be dragged to
+    pub const MIN_WIDTH: f32 = 40.0;
+    /// Width of the fixed selection checkbox column
+    const CHECKBOX: f32 = 30.0;
+    /// Width of the fixed actions column
+    const ACTIONS: f32 = 100.0;
+
+    /// Grow or shrink a column by a drag distance, keeping it at least MIN_WIDTH wide
+    pub fn resize(width: &mut f32, delta: f32) {
+        *width = (*width + delta).max(Self::MIN_WIDTH);
+    }
+}
+
+impl Default for ColumnWidths {
+    fn default() -> Self {
+        Self {
+            age: 60.0,
+            location: 400.0,
+            size: 100.0,
+            files: 90.0,
+            size_delta: 100.0,
+            last_used: 170.0,
+        }
+    }
+}
+
+/// Lay out a table cell of exactly the given width
+fn cell<R>(ui: &mut Ui, width: f32, height: f32, add_contents: impl FnOnce(&mut Ui) -> R) -> R {
+    ui.allocate_ui_with_layout(Vec2::new(width, height), Layout::left_to_right(Align::Center), |ui| {
+        ui.set_min_width(width);
+        ui.set_max_width(width);
+        add_contents(ui)
+    })
+    .inner
+}
+
+/// Draw the vertical line between two columns
+///
+/// In the header the line is a drag handle; dragging it returns the
+/// horizontal distance moved this frame.
+fn column_separator(ui: &mut Ui, height: f32, resizable: bool) -> f32 {
+    let sense = if resizable { Sense::drag() } else { Sense::hover() };
+    let (rect, response) = ui.allocate_exact_size(Vec2::new(6.0, height), sense);
+
+    let active = resizable && (response.hovered() || response.dragged());
+    let stroke = if active {
+        ui.visuals().widgets.active.fg_stroke
+    } else {
+        ui.visuals().widgets.noninteractive.bg_stroke
+    };
+    ui.painter().vline(rect.center().x, rect.y_range(), stroke);
+
+    if active {
+        ui.ctx().set_cursor_icon(CursorIcon::ResizeColumn);
+    }
+    if resizable && response.dragged() {
+        response.drag_delta().x
+    } else {
+        0.0
+    }
 }
 
 impl<'a> VenvTable<'a> {
@@ -25,231 +115,309 @@ impl<'a> VenvTable<'a> {
             venvs,
             selected,
             search_filter,
+            group_by_age: false,
+            file_counts: false,
+            size_deltas: None,
+            widths: None,
+            row_height: Density::default().row_height(),
         }
     }
 
-    /// Show the table
-    pub fn show(self, ui: &mut Ui) {
-        let available_width = ui.available_width();
-
-        // Calculate column widths
-        let checkbox_width = 30.0;
-        let age_width = 60.0;
-        let size_width = 100.0;
-        let date_width = 150.0;
-        let actions_width = 80.0;
-        let location_width = available_width - checkbox_width - age_width - size_width - date_width - actions_width - 50.0;
-        let row_height = 24.0;
-
-        ScrollArea::vertical()
-            .id_source("venv_table_scroll")
-            .show(ui, |ui| {
-                // Table header
-                ui.horizontal(|ui| {
-                    ui.allocate_ui_with_layout(
-                        Vec2::new(checkbox_width, row_height),
-                        Layout::left_to_right(Align::Center),
-                        |ui| {
-                            let all_selected = self.selected.len() == self.venvs.len() && !self.venvs.is_empty();
-                            let mut select_all = all_selected;
-                            if ui.checkbox(&mut select_all, "").changed() {
-                                if select_all {
-                                    *self.selected = (0..self.venvs.len()).collect();
-                                } else {
-                                    self.selected.clear();
-                                }
-                            }
-                        },
-                    );
-
-                    ui.separator();
-
-                    ui.allocate_ui_with_layout(
-                        Vec2::new(age_width, row_height),
-                        Layout::left_to_right(Align::Center),
-                        |ui| {
-                            ui.strong("Age");
-                        },
-                    );
-
-                    ui.separator();
-
-                    ui.allocate_ui_with_layout(
-                        Vec2::new(location_width, row_height),
-                        Layout::left_to_right(Align::Center),
-                        |ui| {
-                            ui.strong("Location");
-                        },
-                    );
-
-                    ui.separator();
-
-                    ui.allocate_ui_with_layout(
-                        Vec2::new(size_width, row_height),
-                        Layout::left_to_right(Align::Center),
-                        |ui| {
-                            ui.strong("Size");
-                        },
-                    );
-
-                    ui.separator();
-
-                    ui.allocate_ui_with_layout(
-                        Vec2::new(date_width, row_height),
-                        Layout::left_to_right(Align::Center),
-                        |ui| {
-                            ui.strong("Last Used");
-                        },
-                    );
-
-                    ui.separator();
-
-                    ui.allocate_ui_with_layout(
-                        Vec2::new(actions_width, row_height),
-                        Layout::left_to_right(Align::Center),
-                        |ui| {
-                            ui.strong("Actions");
-                        },
-                    );
-                });
+    /// Split the rows into one collapsible section per age bucket
+    pub fn group_by_age(mut self, group_by_age: bool) -> Self {
+        self.group_by_age = group_by_age;
+        self
+    }
 
-                ui.separator();
+    /// Add a column with the number of files in each .venv
+    pub fn file_counts(mut self, file_counts: bool) -> Self {
+        self.file_counts = file_counts;
+        self
+    }
 
-                // Filter venvs based on search
-                let filtered_venvs: Vec<(usize, &VenvInfo)> = self.venvs
-                    .iter()
-                    .enumerate()
-                    .filter(|(_, venv)| {
-                        if self.search_filter.is_empty() {
-                            true
-                        } else {
-                            let search_lower = self.search_filter.to_lowercase();
-                            venv.location().to_lowercase().contains(&search_lower) ||
-                            venv.path().display().to_string().to_lowercase().contains(&search_lower)
-                        }
-                    })
-                    .collect();
-
-                // Table rows
-                for (original_index, venv) in filtered_venvs {
-                    let is_selected = self.selected.contains(&original_index);
-
-                    let row_response = ui.horizontal(|ui| {
-                        // Selection checkbox
-                        ui.allocate_ui_with_layout(
-                            Vec2::new(checkbox_width, row_height),
-                            Layout::left_to_right(Align::Center),
-                            |ui| {
-                                let mut selected = is_selected;
-                                if ui.checkbox(&mut selected, "").clicked() {
-                                    if selected {
-                                        self.selected.insert(original_index);
-                                    } else {
-                                        self.selected.remove(&original_index);
-                                    }
-                                }
-                            },
-                        );
+    /// Add a column with how much each .venv grew or shrank since the previous scan
+    pub fn size_deltas(mut self, size_deltas: Option<&'a SizeDeltas>) -> Self {
+        self.size_deltas = size_deltas;
+        self
+    }
 
-                        ui.separator();
+    /// Use (and update, when the user drags a column separator) these column widths
+    pub fn column_widths(mut self, widths: &'a mut ColumnWidths) -> Self {
+        self.widths = Some(widths);
+        self
+    }
 
-                        // Age indicator
-                        ui.allocate_ui_with_layout(
-                            Vec2::new(age_width, row_height),
-                            Layout::left_to_right(Align::Center),
-                            |ui| {
-                                let age_days = venv.age_in_days();
-                                ui.colored_label(
-                                    utils::get_age_color(age_days),
-                                    format!("{} {}d", utils::get_age_indicator(age_days), age_days)
-                                );
-                            },
-                        );
+    /// Lay the rows out this tall (see `Density::row_height`)
+    pub fn row_height(mut self, row_height: f32) -> Self {
+        self.row_height = row_height;
+        self
+    }
 
-                        ui.separator();
+    /// Get the indices of the .venv directories matching the search filter
+    pub fn filtered_indices(&self) -> Vec<usize> {
+        self.venvs
+            .iter()
+            .enumerate()
+            .filter(|(_, venv)| FileUtils::path_matches_search(venv.path(), self.search_filter))
+            .map(|(i, _)| i)
+            .collect()
+    }
 
-                        // Location
-                        ui.allocate_ui_with_layout(
-                            Vec2::new(location_width, row_height),
-                            Layout::left_to_right(Align::Center),
-                            |ui| {
-                                ui.label(utils::format_path_for_display(&venv.location(), 60));
-                            },
-                        );
+    /// Check whether every visible row is selected
+    pub fn all_selected(&self) -> bool {
+        let visible = self.filtered_indices();
+        !visible.is_empty() && visible.iter().all(|i| self.selected.contains(i))
+    }
 
-                        ui.separator();
+    /// Select or deselect every visible row, leaving hidden rows untouched
+    pub fn set_all_selected(&mut self, select: bool) {
+        for index in self.filtered_indices() {
+            if select {
+                self.selected.insert(index);
+            } else {
+                self.selected.remove(&index);
+            }
+        }
+    }
 
-                        // Size
-                        ui.allocate_ui_with_layout(
-                            Vec2::new(size_width, row_height),
-                            Layout::left_to_right(Align::Center),
-                            |ui| {
-                                ui.colored_label(
-                                    utils::get_size_color(venv.size_bytes()),
-                                    utils::format_size(venv.size_bytes())
-                                );
-                            },
-                        );
+    /// Show the table
+    ///
+    /// # Returns
+    /// The action requested from a row this frame, if any
+    pub fn show(mut self, ui: &mut Ui) -> Option<VenvTableAction> {
+        let mut default_widths = ColumnWidths::default();
+        let widths = self.widths.take().unwrap_or(&mut default_widths);
+        let row_height = self.row_height;
+        let visible = self.filtered_indices();
+        let mut action = None;
+
+        ScrollArea::both()
+            .id_source("venv_table_scroll")
+            .show(ui, |ui| {
+                // Table header; the lines after Age, Location, Size, Files, Δ Last Scan and Last Used are drag handles
+                ui.horizontal(|ui| {
+                    cell(ui, ColumnWidths::CHECKBOX, row_height, |ui| {
+                        let mut select_all = self.all_selected();
+                        if ui.checkbox(&mut select_all, "").on_hover_text("Select or deselect all shown directories").changed() {
+                            self.set_all_selected(select_all);
+                        }
+                    });
+                    column_separator(ui, row_height, false);
+
+                    let mut columns = vec![
+                        ("Age", &mut widths.age),
+                        ("Location", &mut widths.location),
+                        ("Size", &mut widths.size),
+                    ];
+                    if self.file_counts {
+                        columns.push(("Files", &mut widths.files));
+                    }
+                    if self.size_deltas.is_some() {
+                        columns.push(("Δ Last Scan", &mut widths.size_delta));
+                    }
+                    columns.push(("Last Used", &mut widths.last_used));
+                    for (title, width) in columns {
+                        cell(ui, *width, row_height, |ui| ui.strong(title));
+                        let delta = column_separator(ui, row_height, true);
+                        ColumnWidths::resize(width, delta);
+                    }
 
-                        ui.separator();
+                    cell(ui, ColumnWidths::ACTIONS, row_height, |ui| ui.strong("Actions"));
+                });
 
-                        // Last used
-                        ui.allocate_ui_with_layout(
-                            Vec2::new(date_width, row_height),
-                            Layout::left_to_right(Align::Center),
-                            |ui| {
-                                ui.label(venv.last_modified_formatted());
-                            },
-                        );
+                ui.separator();
 
-                        ui.separator();
+                // Table rows, optionally in one collapsible section per age bucket
+                if self.group_by_age {
+                    for bucket in AgeBucket::ALL {
+                        let members: Vec<usize> = visible
+                            .iter()
+                            .copied()
+                            .filter(|&i| self.venvs[i].age_bucket() == bucket)
+                            .collect();
+                        if members.is_empty() {
+                            continue;
+                        }
 
-                        // Actions
-                        ui.allocate_ui_with_layout(
-                            Vec2::new(actions_width, row_height),
-                            Layout::left_to_right(Align::Center),
-                            |ui| {
-                                if ui.small_button("📁").on_hover_text("Open folder").clicked() {
-                                    if let Some(parent) = venv.parent_path() {
-                                        let _ = open::that(parent);
-                                    }
+                        let total: ByteSize = members.iter().map(|&i| self.venvs[i].size()).sum();
+                        CollapsingHeader::new(format!("Last used {} — {} directories, {}", bucket.label(), members.len(), total))
+                            .id_source(("age_bucket", bucket))
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                for &index in &members {
+                                    action = self.show_row(ui, index, widths, row_height).or(action);
                                 }
-                            },
-                        );
-                    });
-
-                    // Row selection on click
-                    if row_response.response.clicked() {
-                        if self.selected.contains(&original_index) {
-                            self.selected.remove(&original_index);
-                        } else {
-                            self.selected.insert(original_index);
-                        }
+                            });
                     }
-
-                    // Highlight selected rows
-                    if is_selected {
-                        let rect = row_response.response.rect;
-                        ui.painter().rect_filled(
-                            rect,
-                            Rounding::same(2.0),
-                            Color32::from_rgba_unmultiplied(100, 150, 255, 30)
-                        );
+                } else {
+                    for &index in &visible {
+                        action = self.show_row(ui, index, widths, row_height).or(action);
                     }
+                }
+
+                ui.allocate_space(Vec2::new(ui.available_width(), 10.0));
+            });
+
+        action
+    }
 
-                    // Hover effect
-                    if row_response.response.hovered() && !is_selected {
-                        let rect = row_response.response.rect;
-                        ui.painter().rect_filled(
-                            rect,
-                            Rounding::same(2.0),
-                            Color32::from_rgba_unmultiplied(100, 150, 255, 15)
-                        );
+    /// Show a single row of the table
+    fn show_row(&mut self, ui: &mut Ui, index: usize, widths: &ColumnWidths, row_height: f32) -> Option<VenvTableAction> {
+        let venv = &self.venvs[index];
+        let is_selected = self.selected.contains(&index);
+        let mut action = None;
+
+        let row_response = ui.horizontal(|ui| {
+            // Selection checkbox
+            cell(ui, ColumnWidths::CHECKBOX, row_height, |ui| {
+                let mut selected = is_selected;
+                if ui.checkbox(&mut selected, "").clicked() {
+                    if selected {
+                        self.selected.insert(index);
+                    } else {
+                        self.selected.remove(&index);
                     }
                 }
+            });
+            column_separator(ui, row_height, false);
+
+            // Age indicator
+            cell(ui, widths.age, row_height, |ui| {
+                let age_days = venv.age_in_days();
+                ui.colored_label(
+                    utils::get_age_color(age_days),
+                    format!("{} {}d", utils::get_age_indicator(age_days), age_days)
+                )
+                .on_hover_text(format!("Last used {} days ago", age_days));
+            });
+            column_separator(ui, row_height, false);
+
+            // Location, truncated to the column width
+            cell(ui, widths.location, row_height, |ui| {
+                if venv.has_user_data() {
+                    ui.colored_label(Color32::from_rgb(220, 53, 69), "◆")
+                        .on_hover_text(format!(
+                            "Contains user data ({} file(s), {}) that cannot be reinstalled; deleting asks twice",
+                            venv.user_data().len(),
+                            ByteSize::new(venv.user_data_bytes())
+                        ));
+                }
+                if venv.has_editable_install() {
+                    ui.colored_label(Color32::from_rgb(200, 120, 255), "✎")
+                        .on_hover_text("The project is installed in editable mode in this .venv; deleting it breaks the development setup");
+                }
+                if let Some(kind) = venv.project_kind() {
+                    ui.small(RichText::new(kind.badge()).color(Color32::from_rgb(23, 162, 184)))
+                        .on_hover_text(kind.description());
+                }
+                if let Some(volume) = venv.removable_volume() {
+                    ui.colored_label(Color32::from_rgb(255, 193, 7), "⏏")
+                        .on_hover_text(format!("On the removable drive at {}; deleting fails once it is unplugged", volume.display()));
+                }
+                ui.add(Label::new(venv.location()).truncate(true))
+                    .on_hover_text(venv.path().display().to_string());
+            });
+            column_separator(ui, row_height, false);
+
+            // Size
+            cell(ui, widths.size, row_height, |ui| {
+                ui.colored_label(
+                    utils::get_size_color(venv.size().bytes()),
+                    venv.size().to_string()
+                )
+                .on_hover_text(format!("{} bytes", venv.size().bytes()));
+            });
+            column_separator(ui, row_height, false);
+
+            // File count
+            if self.file_counts {
+                cell(ui, widths.files, row_height, |ui| {
+                    ui.label(venv.file_count_formatted()).on_hover_text(match venv.dir_count() {
+                        Some(dirs) => format!("{} files in {} directories", venv.file_count_formatted(), dirs),
+                        None => "Not counted: sizing stopped early".to_string(),
+                    });
+                });
+                column_separator(ui, row_height, false);
+            }
 
-                ui.allocate_space(Vec2::new(ui.available_width(), 10.0));
+            // Change in size since the previous scan
+            if let Some(deltas) = self.size_deltas {
+                cell(ui, widths.size_delta, row_height, |ui| {
+                    let color = match deltas.get(venv.path()) {
+                        Some(_) if deltas.is_top_growth(venv.path()) => Color32::from_rgb(220, 53, 69),
+                        Some(delta) if delta > 0 => Color32::from_rgb(255, 193, 7),
+                        Some(delta) if delta < 0 => Color32::from_rgb(40, 167, 69),
+                        _ => ui.visuals().weak_text_color(),
+                    };
+                    ui.colored_label(color, deltas.describe(venv.path())).on_hover_text(match deltas.get(venv.path()) {
+                        Some(delta) => format!("{:+} bytes since the previous scan", delta),
+                        None => "Not seen by the previous scan".to_string(),
+                    });
+                });
+                column_separator(ui, row_height, false);
+            }
+
+            // Last used
+            cell(ui, widths.last_used, row_height, |ui| {
+                ui.label(venv.last_modified_formatted())
+                    .on_hover_text(format!("Created {}", venv.created_formatted()));
+                if venv.project_last_commit().is_some() {
+                    let marker = if venv.is_project_active() { "🌱" } else { "⎇" };
+                    ui.label(marker).on_hover_text(format!(
+                        "Last git commit in the project: {}{}",
+                        venv.project_last_commit_formatted(),
+                        if venv.is_project_active() { " (still active)" } else { "" }
+                    ));
+                }
             });
+            column_separator(ui, row_height, false);
+
+            // Actions
+            cell(ui, ColumnWidths::ACTIONS, row_height, |ui| {
+                if ui.small_button("📁").on_hover_text("Open folder").clicked() {
+                    if let Some(parent) = venv.parent_path() {
+                        let _ = open::that(parent);
+                    }
+                }
+                if ui.small_button("💻").on_hover_text("Open a terminal with this .venv activated").clicked() {
+                    action = Some(VenvTableAction::OpenShell(index));
+                }
+                if ui.small_button("📝").on_hover_text("Open the project in your editor").clicked() {
+                    action = Some(VenvTableAction::OpenEditor(index));
+                }
+            });
+        });
+
+        // Row selection on click
+        if row_response.response.clicked() {
+            if self.selected.contains(&index) {
+                self.selected.remove(&index);
+            } else {
+                self.selected.insert(index);
+            }
+        }
+
+        // Highlight selected rows
+        if is_selected {
+            let rect = row_response.response.rect;
+            ui.painter().rect_filled(
+                rect,
+                Rounding::same(2.0),
+                Color32::from_rgba_unmultiplied(100, 150, 255, 30)
+            );
+        }
+
+        // Hover effect
+        if row_response.response.hovered() && !is_selected {
+            let rect = row_response.response.rect;
+            ui.painter().rect_filled(
+                rect,
+                Rounding::same(2.0),
+                Color32::from_rgba_unmultiplied(100, 150, 255, 15)
+            );
+        }
+
+        action
     }
 }
 
@@ -300,6 +468,10 @@ pub struct ConfirmationDialog<'a> {
     confirm_text: &'a str,
     cancel_text: &'a str,
     danger: bool,
+    warning: Option<&'a str>,
+    alternative: Option<&'a str>,
+    checkbox: Option<(&'a str, &'a mut bool)>,
+    checkbox_locked: Option<&'a str>,
 }
 
 impl<'a> ConfirmationDialog<'a> {
@@ -311,6 +483,10 @@ impl<'a> ConfirmationDialog<'a> {
             confirm_text: "OK",
             cancel_text: "Cancel",
             danger: false,
+            warning: None,
+            alternative: None,
+            checkbox: None,
+            checkbox_locked: None,
         }
     }
 
@@ -327,8 +503,32 @@ impl<'a> ConfirmationDialog<'a> {
         self
     }
 
+    /// Show a warning line in red below the message
+    pub fn warning(mut self, warning: &'a str) -> Self {
+        self.warning = Some(warning);
+        self
+    }
+
+    /// Add a third button between cancel and confirm that keeps the dialog open
+    pub fn alternative(mut self, text: &'a str) -> Self {
+        self.alternative = Some(text);
+        self
+    }
+
+    /// Add a checkbox above the buttons that toggles `value` in place
+    pub fn checkbox(mut self, label: &'a str, value: &'a mut bool) -> Self {
+        self.checkbox = Some((label, value));
+        self
+    }
+
+    /// Grey out the checkbox, showing `reason` on hover, e.g. when the policy decides it
+    pub fn checkbox_locked(mut self, reason: &'a str) -> Self {
+        self.checkbox_locked = Some(reason);
+        self
+    }
+
     /// Show the dialog and return the user's choice
-    pub fn show(self, ctx: &Context) -> DialogResult {
+    pub fn show(mut self, ctx: &Context) -> DialogResult {
         let mut result = DialogResult::None;
 
         Window::new(self.title)
@@ -339,6 +539,17 @@ impl<'a> ConfirmationDialog<'a> {
                 ui.vertical_centered(|ui| {
                     ui.add_space(10.0);
                     ui.label(self.message);
+                    if let Some(warning) = self.warning {
+                        ui.add_space(10.0);
+                        ui.colored_label(Color32::RED, warning);
+                    }
+                    if let Some((label, value)) = self.checkbox.as_mut() {
+                        ui.add_space(10.0);
+                        let checkbox = ui.add_enabled(self.checkbox_locked.is_none(), egui::Checkbox::new(value, *label));
+                        if let Some(reason) = self.checkbox_locked {
+                            checkbox.on_disabled_hover_text(reason);
+                        }
+                    }
                     ui.add_space(20.0);
 
                     ui.horizontal(|ui| {
@@ -348,6 +559,13 @@ impl<'a> ConfirmationDialog<'a> {
 
                         ui.add_space(20.0);
 
+                        if let Some(alternative) = self.alternative {
+                            if ui.button(alternative).clicked() {
+                                result = DialogResult::Alternative;
+                            }
+                            ui.add_space(20.0);
+                        }
+
                         let confirm_button = if self.danger {
                             Button::new(self.confirm_text).fill(Color32::from_rgb(220, 53, 69))
                         } else {
@@ -373,6 +591,7 @@ pub enum DialogResult {
     None,
     Confirm,
     Cancel,
+    Alternative,
 }
 
 /// A progress indicator component
@@ -494,14 +713,18 @@ impl<'a> Toolbar<'a> {
         ui.horizontal(|ui| {
             for action in self.actions {
                 let clicked = match action {
-                    ToolbarAction::Button { text, icon, enabled, .. } => {
+                    ToolbarAction::Button { text, icon, enabled, tooltip } => {
                         let button_text = if let Some(icon) = icon {
                             format!("{} {}", icon, text)
                         } else {
                             text.to_string()
                         };
 
-                        ui.add_enabled(enabled, Button::new(button_text)).clicked()
+                        let response = ui.add_enabled(enabled, Button::new(button_text));
+                        match tooltip {
+                            Some(tooltip) => response.on_hover_text(tooltip).on_disabled_hover_text(tooltip).clicked(),
+                            None => response.clicked(),
+                        }
                     }
                     ToolbarAction::Separator => {
                         ui.separator();
@@ -579,18 +802,82 @@ mod tests {
     use super::*;
     use crate::core::VenvInfo;
     use std::path::PathBuf;
-    use chrono::Local;
+    use chrono::Utc;
 
     fn create_test_venv(path: &str, size: u64) -> VenvInfo {
-        let now = Local::now();
+        let now = Utc::now();
         VenvInfo::new(
             PathBuf::from(path),
-            size,
+            ByteSize::new(size),
             now,
             now,
         )
     }
 
+    #[test]
+    fn test_venv_table_filter_and_select_all() {
+        let venvs = vec![
+            create_test_venv("/home/user/webapp/.venv", 100),
+            create_test_venv("/home/user/data-analysis/.venv", 200),
+            create_test_venv("/home/user/webapp-old/.venv", 300),
+        ];
+        let mut selected = std::collections::HashSet::from([1]);
+
+        let mut table = VenvTable::new(&venvs, &mut selected, "WEBAPP");
+        assert_eq!(table.filtered_indices(), vec![0, 2]);
+        assert!(!table.all_selected());
+
+        // Select-all only touches the rows the filter shows
+        table.set_all_selected(true);
+        assert!(table.all_selected());
+        table.set_all_selected(false);
+        assert_eq!(selected, std::collections::HashSet::from([1]));
+    }
+
+    #[test]
+    fn test_venv_table_renders_headless() {
+        let venvs = vec![create_test_venv("/home/user/webapp/.venv", 100)];
+        let mut selected = std::collections::HashSet::new();
+        let mut widths = ColumnWidths::default();
+        let deltas = crate::core::SizeDeltas::default();
+        let ctx = Context::default();
+
+        for (group_by_age, file_counts) in [(false, false), (true, true)] {
+            let _ = ctx.run(RawInput::default(), |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    let action = VenvTable::new(&venvs, &mut selected, "")
+                        .group_by_age(group_by_age)
+                        .file_counts(file_counts)
+                        .size_deltas(file_counts.then_some(&deltas))
+                        .column_widths(&mut widths)
+                        .show(ui);
+                    assert_eq!(action, None);
+                });
+            });
+        }
+        assert!(selected.is_empty());
+        assert_eq!(widths, ColumnWidths::default());
+    }
+
+    #[test]
+    fn test_column_widths_resize_and_persist() {
+        let mut widths = ColumnWidths::default();
+        ColumnWidths::resize(&mut widths.location, 250.0);
+        assert_eq!(widths.location, 650.0);
+
+        // Columns cannot be dragged narrower than the minimum
+        ColumnWidths::resize(&mut widths.size, -500.0);
+        assert_eq!(widths.size, ColumnWidths::MIN_WIDTH);
+
+        let saved = serde_json::to_string(&widths).unwrap();
+        assert_eq!(serde_json::from_str::<ColumnWidths>(&saved).unwrap(), widths);
+
+        // Widths saved before a column existed still load
+        let old: ColumnWidths = serde_json::from_str(r#"{"age":60.0,"location":300.0,"size":100.0,"last_used":170.0}"#).unwrap();
+        assert_eq!(old.location, 300.0);
+        assert_eq!(old.files, ColumnWidths::default().files);
+    }
+
     #[test]
     fn test_status_card_creation() {
         let card = StatusCard::new("Test", "123", "🔍", Color32::BLUE);
@@ -611,6 +898,11 @@ mod tests {
         assert_eq!(dialog.confirm_text, "Yes");
         assert_eq!(dialog.cancel_text, "No");
         assert!(dialog.danger);
+        assert_eq!(dialog.warning, None);
+        assert_eq!(dialog.alternative, None);
+        let dialog = dialog.warning("Careful").alternative("Peek");
+        assert_eq!(dialog.warning, Some("Careful"));
+        assert_eq!(dialog.alternative, Some("Peek"));
     }
 
     #[test]
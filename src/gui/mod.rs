@@ -5,18 +5,20 @@
 //! sorting options, and interactive deletion capabilities similar to the TUI version.
 
 use std::path::PathBuf;
-use std::sync::mpsc;
+use std::sync::{mpsc, Arc};
 use std::thread;
 use std::time::{Duration, Instant};
 
 use eframe::egui::{self, *};
-use log::{debug, info, warn};
+use tracing::{debug, info, warn};
 
-use crate::core::{VenvCleaner, VenvCleanerError, VenvInfo, Result};
+use crate::core::{ArchivedVenv, BatchResult, Config, DeleteMode, Detector, FileUtils, FsBackend, IncludeSet, LogBuffer, LogLine, OfflineIndex, OwnerFilter, SlimReport, SortKey, VenvCleaner, VenvCleanerError, VenvFilter, VenvInfo, Result};
 
 pub mod app;
 pub mod components;
 pub mod theme;
+#[cfg(feature = "tray")]
+pub mod tray;
 
 pub use app::GuiApp;
 pub use theme::Theme;
@@ -31,6 +33,13 @@ pub struct GuiMode {
     recursive: bool,
     /// Verbosity level
     verbosity: u8,
+    /// Editor command template from the config file
+    editor: Option<String>,
+    /// Captured log lines for the log panel
+    log_receiver: Option<mpsc::Receiver<LogLine>>,
+    /// Whether to put an icon in the system tray
+    #[cfg(feature = "tray")]
+    tray: bool,
 }
 
 /// Application states for the GUI
@@ -58,6 +67,8 @@ pub enum GuiSortBy {
     Path,
     /// Sort by size (largest first)
     Size,
+    /// Sort by file count (most files first)
+    Files,
     /// Sort by creation date (newest first)
     Created,
     /// Sort by last modified date (most recent first)
@@ -69,7 +80,8 @@ impl GuiSortBy {
     pub fn next(self) -> Self {
         match self {
             GuiSortBy::Path => GuiSortBy::Size,
-            GuiSortBy::Size => GuiSortBy::Created,
+            GuiSortBy::Size => GuiSortBy::Files,
+            GuiSortBy::Files => GuiSortBy::Created,
             GuiSortBy::Created => GuiSortBy::LastModified,
             GuiSortBy::LastModified => GuiSortBy::Path,
         }
@@ -80,10 +92,22 @@ impl GuiSortBy {
         match self {
             GuiSortBy::Path => "Path",
             GuiSortBy::Size => "Size",
+            GuiSortBy::Files => "Files",
             GuiSortBy::Created => "Created",
             GuiSortBy::LastModified => "Last Used",
         }
     }
+
+    /// Get the shared sort key this option sorts by
+    pub fn key(self) -> SortKey {
+        match self {
+            GuiSortBy::Path => SortKey::Path,
+            GuiSortBy::Size => SortKey::Size,
+            GuiSortBy::Files => SortKey::Files,
+            GuiSortBy::Created => SortKey::Created,
+            GuiSortBy::LastModified => SortKey::LastModified,
+        }
+    }
 }
 
 /// Background task events
@@ -93,8 +117,22 @@ pub enum GuiEvent {
     VenvsLoaded(Vec<VenvInfo>),
     /// Error occurred while loading .venv directories
     LoadError(String),
+    /// A .venv of a deletion batch was finished, with the estimated time left
+    DeletionProgress { completed: usize, total: usize, eta: Option<Duration> },
     /// Deletion operation completed
-    DeletionComplete(Vec<(VenvInfo, Result<()>)>),
+    DeletionComplete(BatchResult),
+    /// Slimming operation completed
+    SlimComplete(Vec<(VenvInfo, Result<SlimReport>)>),
+    /// Archived .venv directories (stub files) have been loaded
+    ArchivedLoaded(Vec<ArchivedVenv>),
+    /// Restoring an archive or deleting it completed, with a status message
+    ArchiveActionComplete(String),
+    /// Roots the scan could not search, with why; sent before the scan's results
+    RootsFailed(Vec<(PathBuf, String)>),
+    /// The scan retrying a failed root completed
+    RootRetried { root: PathBuf, result: std::result::Result<Vec<VenvInfo>, String> },
+    /// The scan of the comparison pane's root completed
+    CompareLoaded { root: PathBuf, result: std::result::Result<Vec<VenvInfo>, String> },
 }
 
 impl GuiMode {
@@ -107,22 +145,120 @@ impl GuiMode {
         info!("Creating GUI mode");
 
         // Create VenvCleaner instance
+        let config = Config::load()?;
         let cleaner = VenvCleaner::new(
             base_directory.clone(),
             recursive,
             false, // force_mode = false for GUI
             false, // dry_run = false (we handle this in GUI)
             verbosity,
-        );
+        )
+        .with_policy(config.policy)
+        .with_delete_hooks(config.hooks);
 
         Ok(Self {
             cleaner,
             base_directory,
             recursive,
             verbosity,
+            editor: None,
+            log_receiver: None,
+            #[cfg(feature = "tray")]
+            tray: false,
         })
     }
 
+    /// Also scan these directories, listed together with the base directory
+    pub fn with_extra_roots(mut self, extra_roots: Vec<PathBuf>) -> Self {
+        self.cleaner = self.cleaner.with_extra_roots(extra_roots);
+        self
+    }
+
+    /// Choose whether deleted directories go to the system trash (see `DeleteMode`)
+    pub fn with_delete_mode(mut self, mode: DeleteMode) -> Self {
+        self.cleaner = self.cleaner.with_delete_mode(mode);
+        self
+    }
+
+    /// Archive deleted directories into the given directory instead of removing them
+    pub fn with_archive_dir(mut self, archive_dir: Option<PathBuf>) -> Self {
+        self.cleaner = self.cleaner.with_archive_dir(archive_dir);
+        self
+    }
+
+    /// Choose whose .venv directories are listed (the current user's by default)
+    pub fn with_owner_filter(mut self, owner_filter: OwnerFilter) -> Self {
+        self.cleaner = self.cleaner.with_owner_filter(owner_filter);
+        self
+    }
+
+    /// List only the .venv directories meeting age and size criteria, starting with them selected
+    pub fn with_venv_filter(mut self, venv_filter: VenvFilter) -> Self {
+        self.cleaner = self.cleaner.with_venv_filter(venv_filter);
+        self
+    }
+
+    /// Skip hidden directories other than `.venv` during recursive scans
+    pub fn with_skip_hidden(mut self, skip_hidden: bool) -> Self {
+        self.cleaner = self.cleaner.with_skip_hidden(skip_hidden);
+        self
+    }
+
+    /// Choose whether folders named .venv must look like virtual environments to be listed
+    pub fn with_validation(mut self, validate: bool) -> Self {
+        self.cleaner = self.cleaner.with_validation(validate);
+        self
+    }
+
+    /// Never walk into these directories during recursive scans
+    pub fn with_excludes(mut self, excludes: Vec<PathBuf>) -> Self {
+        self.cleaner = self.cleaner.with_excludes(excludes);
+        self
+    }
+
+    /// Limit recursive scans to the subtrees matching these patterns
+    pub fn with_includes(mut self, includes: IncludeSet) -> Self {
+        self.cleaner = self.cleaner.with_includes(includes);
+        self
+    }
+
+    /// Also find environments of these layouts, besides `.venv` directories
+    pub fn with_detectors(mut self, detectors: Vec<Detector>) -> Self {
+        self.cleaner = self.cleaner.with_detectors(detectors);
+        self
+    }
+
+    /// Browse a saved scan instead of the disk, adding deletions to its plan
+    pub fn with_offline_index(mut self, offline: Option<OfflineIndex>) -> Self {
+        self.cleaner = self.cleaner.with_offline_index(offline);
+        self
+    }
+
+    /// Scan and delete through another file system than the disk, e.g. a `MemoryFs` in tests
+    pub fn with_fs_backend(mut self, fs: Arc<dyn FsBackend>) -> Self {
+        self.cleaner = self.cleaner.with_fs_backend(fs);
+        self
+    }
+
+    /// Open projects with this editor command template (`{dir}` is the project directory)
+    pub fn with_editor(mut self, editor: Option<String>) -> Self {
+        self.editor = editor;
+        self
+    }
+
+    /// Show the log lines captured from this channel in the log panel
+    pub fn with_log_receiver(mut self, receiver: mpsc::Receiver<LogLine>) -> Self {
+        self.log_receiver = Some(receiver);
+        self
+    }
+
+    /// Put an icon showing the .venv disk usage in the system tray, with scan and clean entries
+    #[cfg(feature = "tray")]
+    pub fn with_tray(mut self, tray: bool) -> Self {
+        self.tray = tray;
+        self
+    }
+
     /// Run the GUI application
     pub fn run(self) -> Result<()> {
         info!("Starting GUI mode");
@@ -141,13 +277,24 @@ impl GuiMode {
         };
 
         // Create the GUI app
-        let gui_app = GuiApp::new(self.cleaner, self.base_directory, self.recursive);
+        let logs = self.log_receiver.map(LogBuffer::new).unwrap_or_default();
+        let gui_app = GuiApp::new(self.cleaner, self.base_directory, self.recursive)
+            .with_editor(self.editor)
+            .with_logs(logs);
+        #[cfg(feature = "tray")]
+        let tray = self.tray;
 
         // Run the application
         eframe::run_native(
             "VenvCleaner",
             options,
-            Box::new(|_cc| Box::new(gui_app)),
+            Box::new(move |cc| {
+                let gui_app = gui_app.with_storage(cc.storage);
+                // The tray needs the context to wake the GUI up on its actions
+                #[cfg(feature = "tray")]
+                let gui_app = if tray { gui_app.with_tray(&cc.egui_ctx) } else { gui_app };
+                Box::new(gui_app)
+            }),
         )
         .map_err(|e| VenvCleanerError::Io(format!("Failed to run GUI: {}", e)))?;
 
@@ -159,30 +306,9 @@ impl GuiMode {
 pub mod utils {
     use super::*;
 
-    /// Format file size for display
-    pub fn format_size(bytes: u64) -> String {
-        const KB: u64 = 1024;
-        const MB: u64 = KB * 1024;
-        const GB: u64 = MB * 1024;
-
-        if bytes >= GB {
-            format!("{:.2} GB", bytes as f64 / GB as f64)
-        } else if bytes >= MB {
-            format!("{:.2} MB", bytes as f64 / MB as f64)
-        } else if bytes >= KB {
-            format!("{:.2} KB", bytes as f64 / KB as f64)
-        } else {
-            format!("{} bytes", bytes)
-        }
-    }
-
     /// Format a file path for display, truncating if necessary
     pub fn format_path_for_display(path: &str, max_length: usize) -> String {
-        if path.len() <= max_length {
-            path.to_string()
-        } else {
-            format!("...{}", &path[path.len().saturating_sub(max_length - 3)..])
-        }
+        FileUtils::truncate_start(path, max_length)
     }
 
     /// Get color for size based on magnitude
@@ -210,6 +336,43 @@ pub mod utils {
         }
     }
 
+    /// Move the selected rows of one list to another, where they stay selected
+    ///
+    /// Rows already in the other list are only selected there, not added twice.
+    ///
+    /// # Arguments
+    /// * `from` - The list the rows are taken from, with its selection
+    /// * `to` - The list the rows are moved to, with its selection
+    ///
+    /// # Returns
+    /// The number of rows moved
+    pub fn move_selected(
+        from: (&mut Vec<VenvInfo>, &mut std::collections::HashSet<usize>),
+        to: (&mut Vec<VenvInfo>, &mut std::collections::HashSet<usize>),
+    ) -> usize {
+        let (from_venvs, from_selected) = from;
+        let (to_venvs, to_selected) = to;
+        let mut indices: Vec<usize> = from_selected.drain().filter(|&i| i < from_venvs.len()).collect();
+        indices.sort_unstable();
+
+        // Removed from the back so the remaining indices stay valid
+        let mut moved: Vec<VenvInfo> = indices.iter().rev().map(|&i| from_venvs.remove(i)).collect();
+        moved.reverse();
+        let count = moved.len();
+        for venv in moved {
+            match to_venvs.iter().position(|existing| existing.path() == venv.path()) {
+                Some(index) => {
+                    to_selected.insert(index);
+                }
+                None => {
+                    to_venvs.push(venv);
+                    to_selected.insert(to_venvs.len() - 1);
+                }
+            }
+        }
+        count
+    }
+
     /// Get age indicator emoji
     pub fn get_age_indicator(days: i64) -> &'static str {
         if days <= 30 {
@@ -230,7 +393,8 @@ mod tests {
     #[test]
     fn test_gui_sort_by_cycle() {
         assert_eq!(GuiSortBy::Path.next(), GuiSortBy::Size);
-        assert_eq!(GuiSortBy::Size.next(), GuiSortBy::Created);
+        assert_eq!(GuiSortBy::Size.next(), GuiSortBy::Files);
+        assert_eq!(GuiSortBy::Files.next(), GuiSortBy::Created);
         assert_eq!(GuiSortBy::Created.next(), GuiSortBy::LastModified);
         assert_eq!(GuiSortBy::LastModified.next(), GuiSortBy::Path);
     }
@@ -239,6 +403,7 @@ mod tests {
     fn test_gui_sort_by_display_names() {
         assert_eq!(GuiSortBy::Path.display_name(), "Path");
         assert_eq!(GuiSortBy::Size.display_name(), "Size");
+        assert_eq!(GuiSortBy::Files.display_name(), "Files");
         assert_eq!(GuiSortBy::Created.display_name(), "Created");
         assert_eq!(GuiSortBy::LastModified.display_name(), "Last Used");
     }
@@ -255,13 +420,24 @@ mod tests {
     }
 
     #[test]
-    fn test_utils_format_size() {
-        use utils::format_size;
-
-        assert_eq!(format_size(500), "500 bytes");
-        assert_eq!(format_size(1536), "1.50 KB");
-        assert_eq!(format_size(1024 * 1024 * 2), "2.00 MB");
-        assert_eq!(format_size(1024 * 1024 * 1024), "1.00 GB");
+    fn test_utils_move_selected() {
+        use crate::core::ByteSize;
+        use chrono::Utc;
+        use std::collections::HashSet;
+
+        let venv = |path: &str| VenvInfo::new(PathBuf::from(path), ByteSize::new(1), Utc::now(), Utc::now());
+        let mut home = vec![venv("/home/a/.venv"), venv("/home/b/.venv"), venv("/home/c/.venv")];
+        let mut home_selected: HashSet<usize> = [0, 2].into_iter().collect();
+        let mut drive = vec![venv("/home/c/.venv")];
+        let mut drive_selected = HashSet::new();
+
+        let moved = utils::move_selected((&mut home, &mut home_selected), (&mut drive, &mut drive_selected));
+        assert_eq!(moved, 2);
+        assert_eq!(home.len(), 1);
+        assert!(home_selected.is_empty());
+        let paths: Vec<_> = drive.iter().map(|v| v.path().display().to_string()).collect();
+        assert_eq!(paths, vec!["/home/c/.venv", "/home/a/.venv"]);
+        assert_eq!(drive_selected, [0, 1].into_iter().collect());
     }
 
     #[test]
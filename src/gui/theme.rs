@@ -3,7 +3,7 @@
 //! This module provides consistent theming and styling for the VenvCleaner GUI,
 //! including color schemes, fonts, and layout constants.
 
-use eframe::egui::{Color32, FontId, Rounding, Stroke, Style, Visuals};
+use eframe::egui::{Color32, FontId, Rounding, Stroke, Style, TextStyle, Visuals};
 
 /// Theme configuration for the GUI application
 #[derive(Debug, Clone)]
@@ -175,6 +175,22 @@ impl Theme {
         }
     }
 
+    /// Size the rows, fonts and spacing for a density
+    ///
+    /// # Arguments
+    /// * `density` - How tightly rows are packed
+    pub fn with_density(mut self, density: Density) -> Self {
+        let scale = density.font_scale();
+        self.font_small *= scale;
+        self.font_normal *= scale;
+        self.font_large *= scale;
+        self.font_heading *= scale;
+        self.row_height = density.row_height();
+        self.spacing *= density.spacing_scale();
+        self.padding *= density.spacing_scale();
+        self
+    }
+
     /// Apply this theme to the egui context
     pub fn apply_to_ctx(&self, ctx: &eframe::egui::Context) {
         let mut style = Style::default();
@@ -205,6 +221,17 @@ impl Theme {
         style.visuals.widgets.hovered.rounding = self.rounding;
         style.visuals.widgets.active.rounding = self.rounding;
 
+        // Set borders (separators use the non-interactive stroke)
+        style.visuals.widgets.noninteractive.bg_stroke = self.separator_stroke();
+        style.visuals.widgets.inactive.bg_stroke = self.border_stroke();
+        style.visuals.window_stroke = self.border_stroke();
+
+        // Set fonts
+        style.text_styles.insert(TextStyle::Small, self.font_id(FontSize::Small));
+        style.text_styles.insert(TextStyle::Body, self.font_id(FontSize::Normal));
+        style.text_styles.insert(TextStyle::Button, self.font_id(FontSize::Normal));
+        style.text_styles.insert(TextStyle::Heading, self.font_id(FontSize::Heading));
+
         // Set spacing
         style.spacing.item_spacing = eframe::egui::Vec2::splat(self.spacing);
         style.spacing.button_padding = eframe::egui::Vec2::new(self.padding, self.padding * 0.5);
@@ -259,6 +286,62 @@ impl Theme {
     }
 }
 
+/// How tightly the table rows and text are packed, chosen in View → Density
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Density {
+    /// Short rows and smaller text, to review hundreds of .venv directories on a laptop screen
+    Compact,
+    /// The default sizes
+    #[default]
+    Comfortable,
+}
+
+impl Density {
+    /// Every density, in the order the View menu lists them
+    pub const ALL: [Density; 2] = [Density::Compact, Density::Comfortable];
+
+    /// Get the name shown in the View menu
+    pub fn label(self) -> &'static str {
+        match self {
+            Density::Compact => "Compact",
+            Density::Comfortable => "Comfortable",
+        }
+    }
+
+    /// Get the height of a table row in points
+    pub fn row_height(self) -> f32 {
+        match self {
+            Density::Compact => 18.0,
+            Density::Comfortable => Theme::default().row_height,
+        }
+    }
+
+    /// Get the factor font sizes are multiplied by
+    pub fn font_scale(self) -> f32 {
+        match self {
+            Density::Compact => 0.85,
+            Density::Comfortable => 1.0,
+        }
+    }
+
+    /// Get the factor spacing between widgets is multiplied by
+    pub fn spacing_scale(self) -> f32 {
+        match self {
+            Density::Compact => 0.5,
+            Density::Comfortable => 1.0,
+        }
+    }
+
+    /// Scale the text and spacing of an egui style to this density
+    pub fn apply_to_style(self, style: &mut Style) {
+        for font in style.text_styles.values_mut() {
+            font.size *= self.font_scale();
+        }
+        style.spacing.item_spacing *= self.spacing_scale();
+        style.spacing.button_padding *= self.spacing_scale();
+    }
+}
+
 /// Font size categories
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FontSize {
@@ -305,6 +388,55 @@ impl ColorSchemes {
             ..Default::default()
         }
     }
+
+    /// High-contrast theme for low-vision users
+    ///
+    /// White text on black with bright, saturated status colors, thicker
+    /// strokes and larger fonts.
+    pub fn high_contrast() -> Theme {
+        Theme {
+            primary: Color32::from_rgb(255, 255, 0),
+            secondary: Color32::from_rgb(0, 255, 255),
+            accent: Color32::from_rgb(255, 255, 0),
+
+            success: Color32::from_rgb(0, 255, 0),
+            warning: Color32::from_rgb(255, 255, 0),
+            error: Color32::from_rgb(255, 80, 80),
+            info: Color32::from_rgb(0, 255, 255),
+
+            background: Color32::BLACK,
+            surface: Color32::BLACK,
+            panel: Color32::from_rgb(20, 20, 20),
+
+            text_primary: Color32::WHITE,
+            text_secondary: Color32::WHITE,
+            text_muted: Color32::from_rgb(220, 220, 220),
+
+            selection: Color32::from_rgb(0, 70, 160),
+            highlight: Color32::from_rgb(120, 90, 0),
+            hover: Color32::from_rgb(60, 60, 60),
+
+            border: Color32::WHITE,
+            separator: Color32::WHITE,
+
+            age_recent: Color32::from_rgb(0, 255, 0),
+            age_moderate: Color32::from_rgb(255, 255, 0),
+            age_old: Color32::from_rgb(255, 80, 80),
+
+            size_small: Color32::WHITE,
+            size_medium: Color32::from_rgb(255, 255, 0),
+            size_large: Color32::from_rgb(255, 80, 80),
+
+            font_small: 13.0,
+            font_normal: 15.0,
+            font_large: 18.0,
+            font_heading: 22.0,
+
+            stroke_width: 2.0,
+
+            ..Default::default()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -391,4 +523,33 @@ mod tests {
         assert_ne!(blue.primary, purple.primary);
         assert_ne!(green.primary, purple.primary);
     }
+
+    #[test]
+    fn test_high_contrast_theme() {
+        let theme = ColorSchemes::high_contrast();
+
+        // Pure white on pure black, with heavier strokes than the default
+        assert_eq!(theme.background, Color32::BLACK);
+        assert_eq!(theme.text_primary, Color32::WHITE);
+        assert_eq!(theme.border, Color32::WHITE);
+        assert!(theme.stroke_width > Theme::default().stroke_width);
+        assert!(theme.font_normal > Theme::default().font_normal);
+    }
+
+    #[test]
+    fn test_density() {
+        let comfortable = Theme::default().with_density(Density::Comfortable);
+        assert_eq!(comfortable.row_height, Theme::default().row_height);
+        assert_eq!(comfortable.font_normal, Theme::default().font_normal);
+
+        let compact = ColorSchemes::high_contrast().with_density(Density::Compact);
+        assert!(compact.row_height < comfortable.row_height);
+        assert!(compact.font_normal < ColorSchemes::high_contrast().font_normal);
+
+        let mut style = Style::default();
+        let body = style.text_styles[&TextStyle::Body].size;
+        Density::Compact.apply_to_style(&mut style);
+        assert!(style.text_styles[&TextStyle::Body].size < body);
+        assert_eq!(serde_json::to_string(&Density::Compact).unwrap(), "\"Compact\"");
+    }
 }
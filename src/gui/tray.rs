@@ -0,0 +1,214 @@
+//! System tray icon for the GUI (feature `tray`)
+//!
+//! With `--tray` the GUI puts an icon in the system tray whose tooltip shows
+//! how much disk the .venv directories of the last scan take and how much of
+//! it is reclaimable. Its menu opens the window, rescans, or selects the
+//! environments unused for over 90 days and asks to delete them; nothing is
+//! deleted without the usual confirmation dialog.
+//!
+//! On Linux the icon is an AppIndicator living in a GTK main loop, which runs
+//! on a thread of its own beside eframe's; elsewhere it is created on the
+//! GUI thread. Either way its actions reach the app through a channel.
+
+use std::sync::mpsc::Sender;
+
+use eframe::egui::Context;
+use tray_icon::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tray_icon::{ClickType, Icon, TrayIcon, TrayIconBuilder, TrayIconEvent};
+
+use crate::core::{ByteSize, VenvInfo};
+
+/// What the tray menu asked the GUI to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayAction {
+    /// Show and focus the window
+    Open,
+    /// Scan the roots again
+    ScanNow,
+    /// Select the environments unused for over 90 days and confirm their deletion
+    CleanOld,
+}
+
+impl TrayAction {
+    /// Every action, in menu order
+    const ALL: [TrayAction; 3] = [TrayAction::Open, TrayAction::ScanNow, TrayAction::CleanOld];
+
+    /// Menu item id of this action
+    pub fn id(self) -> &'static str {
+        match self {
+            TrayAction::Open => "open",
+            TrayAction::ScanNow => "scan-now",
+            TrayAction::CleanOld => "clean-old",
+        }
+    }
+
+    /// Menu item label of this action
+    pub fn label(self) -> &'static str {
+        match self {
+            TrayAction::Open => "Open VenvCleaner",
+            TrayAction::ScanNow => "Scan now",
+            TrayAction::CleanOld => "Clean old (>90d)",
+        }
+    }
+
+    /// Find the action of a menu item id
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.id() == id)
+    }
+}
+
+/// Describe the disk usage of scanned environments for the tray tooltip
+///
+/// # Arguments
+/// * `venvs` - The environments of the last scan
+///
+/// # Returns
+/// Their count and total size, and the size of those unused for over 90 days
+pub fn usage_text(venvs: &[VenvInfo]) -> String {
+    let total: ByteSize = venvs.iter().map(VenvInfo::size).sum();
+    let reclaimable: ByteSize = venvs.iter().filter(|v| v.is_old()).map(VenvInfo::size).sum();
+    format!("VenvCleaner: {} in {} .venv, {} reclaimable", total, venvs.len(), reclaimable)
+}
+
+/// The tray icon of a running GUI
+pub struct Tray {
+    /// Usage texts for the GTK thread owning the icon
+    #[cfg(target_os = "linux")]
+    usage: Sender<String>,
+    /// The icon, owned by the GUI thread
+    #[cfg(not(target_os = "linux"))]
+    icon: TrayIcon,
+}
+
+impl Tray {
+    /// Put the icon in the system tray
+    ///
+    /// # Arguments
+    /// * `actions` - Receives the action of each menu item chosen
+    /// * `ctx` - The GUI's context, repainted so it handles the action at once
+    ///
+    /// # Returns
+    /// The tray, or why no icon could be created (e.g. no tray on the desktop)
+    pub fn start(actions: Sender<TrayAction>, ctx: Context) -> Result<Self, String> {
+        forward_events(actions, ctx);
+
+        #[cfg(target_os = "linux")]
+        {
+            let (usage, texts) = std::sync::mpsc::channel::<String>();
+            let (started, result) = std::sync::mpsc::channel();
+            std::thread::spawn(move || {
+                if let Err(e) = gtk::init() {
+                    let _ = started.send(Err(format!("Failed to start GTK: {}", e)));
+                    return;
+                }
+                let icon = match build_icon() {
+                    Ok(icon) => icon,
+                    Err(e) => {
+                        let _ = started.send(Err(e));
+                        return;
+                    }
+                };
+                let _ = started.send(Ok(()));
+                // AppIndicators show no tooltip, so the usage goes in the title next to the icon
+                gtk::glib::timeout_add_local(std::time::Duration::from_millis(500), move || {
+                    if let Some(text) = texts.try_iter().last() {
+                        icon.set_title(Some(&text));
+                    }
+                    gtk::glib::Continue(true)
+                });
+                gtk::main();
+            });
+            result
+                .recv()
+                .map_err(|_| "The tray thread stopped".to_string())?
+                .map(|()| Self { usage })
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            build_icon().map(|icon| Self { icon })
+        }
+    }
+
+    /// Show the usage of the last scan on the icon
+    ///
+    /// # Arguments
+    /// * `venvs` - The environments of the last scan
+    pub fn set_usage(&self, venvs: &[VenvInfo]) {
+        let text = usage_text(venvs);
+        #[cfg(target_os = "linux")]
+        {
+            let _ = self.usage.send(text);
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = self.icon.set_tooltip(Some(text));
+        }
+    }
+}
+
+/// Create the icon with its menu
+fn build_icon() -> Result<TrayIcon, String> {
+    let menu = Menu::new();
+    let [open, scan_now, clean_old] = TrayAction::ALL.map(|action| MenuItem::with_id(action.id(), action.label(), true, None));
+    menu.append_items(&[&open, &PredefinedMenuItem::separator(), &scan_now, &clean_old])
+        .map_err(|e| format!("Failed to create the tray menu: {}", e))?;
+
+    TrayIconBuilder::new()
+        .with_menu(Box::new(menu))
+        .with_tooltip("VenvCleaner: scanning...")
+        .with_icon(icon_image()?)
+        .build()
+        .map_err(|e| format!("Failed to create the tray icon: {}", e))
+}
+
+/// Send the menu choices and icon clicks to the GUI
+fn forward_events(actions: Sender<TrayAction>, ctx: Context) {
+    let menu_actions = actions.clone();
+    let menu_ctx = ctx.clone();
+    MenuEvent::set_event_handler(Some(move |event: MenuEvent| {
+        if let Some(action) = TrayAction::from_id(event.id().as_ref()) {
+            let _ = menu_actions.send(action);
+            menu_ctx.request_repaint();
+        }
+    }));
+    // A left click opens the window where the platform reports clicks (not Linux)
+    TrayIconEvent::set_event_handler(Some(move |event: TrayIconEvent| {
+        if event.click_type == ClickType::Left {
+            let _ = actions.send(TrayAction::Open);
+            ctx.request_repaint();
+        }
+    }));
+}
+
+/// Draw the 32x32 icon: a green disc, the color the GUI uses for reclaimed space
+fn icon_image() -> Result<Icon, String> {
+    const SIZE: u32 = 32;
+    let center = (SIZE as f32 - 1.0) / 2.0;
+    let mut rgba = Vec::with_capacity((SIZE * SIZE * 4) as usize);
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let distance = ((x as f32 - center).powi(2) + (y as f32 - center).powi(2)).sqrt();
+            let alpha = if distance <= center { 255 } else { 0 };
+            rgba.extend_from_slice(&[40, 167, 69, alpha]);
+        }
+    }
+    Icon::from_rgba(rgba, SIZE, SIZE).map_err(|e| format!("Failed to create the tray icon: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_action_ids_round_trip() {
+        for action in TrayAction::ALL {
+            assert_eq!(TrayAction::from_id(action.id()), Some(action));
+        }
+        assert_eq!(TrayAction::from_id("quit"), None);
+    }
+
+    #[test]
+    fn test_usage_text_of_empty_scan() {
+        assert_eq!(usage_text(&[]), "VenvCleaner: 0 bytes in 0 .venv, 0 bytes reclaimable");
+    }
+}
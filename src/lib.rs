@@ -0,0 +1,18 @@
+//! VenvCleaner library
+//!
+//! This crate contains everything behind the `venv_cleaner` binary: the core
+//! scanning and deletion logic, the CLI/TUI/GUI front-ends, and fixture
+//! generation used by the integration tests and benchmarks.
+//!
+//! Author: VenvCleaner Team
+//! License: MIT
+
+pub mod cli;
+pub mod core;
+pub mod fixtures;
+#[cfg(feature = "tui")]
+pub mod tui;
+#[cfg(feature = "gui")]
+pub mod gui;
+#[cfg(all(feature = "dbus", target_os = "linux"))]
+pub mod dbus;
@@ -10,52 +10,81 @@
 
 use clap::{Arg, Command, ArgMatches};
 use std::process;
-use log::{info, error};
+use std::sync::mpsc::Receiver;
+use tracing::{info, error, warn};
 
-mod cli;
-mod core;
+use venv_cleaner::cli::{AdviseCommand, ApplyPlanCommand, AuditCommand, BenchCommand, CliMode, DiffCommand, ExplainCommand, InfoCommand, MergeCommand, Pager, PolicyCommand, PromptSummaryCommand, RefreshCacheCommand, RobotMode, SlimCommand, StatsCommand, TrashCommand, UnarchiveCommand};
+use venv_cleaner::core::{log_capture, sorting, telemetry, timestamps, Config, LogLine, CrashReport, CrashReporter, VenvCleanerError};
 #[cfg(feature = "tui")]
-mod tui;
+use venv_cleaner::tui::{Theme, TuiMode};
 #[cfg(feature = "gui")]
-mod gui;
-
-use cli::CliMode;
-use core::VenvCleanerError;
-#[cfg(feature = "tui")]
-use tui::TuiMode;
-#[cfg(feature = "gui")]
-use gui::GuiMode;
+use venv_cleaner::gui::GuiMode;
 
 /// Main entry point for the VenvCleaner application
 fn main() {
-    // Initialize logger
-    env_logger::init();
+    // Parse command line arguments
+    let matches = build_cli().get_matches();
+
+    // The TUI and GUI own the screen, so their log lines go to an in-app pane
+    let log_receiver = if captures_logs(&matches) {
+        log_capture::install(matches.get_count("verbose")).ok()
+    } else {
+        env_logger::init();
+        None
+    };
+
+    // Dropping the guard writes out the trace, so it has to happen before exiting
+    let trace_guard = matches.get_one::<String>("trace-output").map(|path| install_trace_output(path));
 
     info!("Starting VenvCleaner application");
 
-    // Parse command line arguments
-    let matches = build_cli().get_matches();
+    // Timestamps are kept in UTC; only what is printed follows --utc
+    timestamps::set_show_utc(matches.get_flag("utc"));
+
+    // Crash reports are opt-in; an unreadable config is reported later by the mode that needs it
+    let config = Config::load().ok();
+    let crash_reporter = config.as_ref().and_then(|config| CrashReporter::from_config(&config.telemetry));
+    if let Some(config) = &config {
+        // Already validated when the config was loaded
+        if let Err(e) = timestamps::set_date_format(config.date_format.as_deref()) {
+            warn!("Ignoring date_format: {}", e);
+        }
+    }
+    sorting::set_ignore_case(matches.get_flag("sort-ignore-case") || config.as_ref().is_some_and(|config| config.sort_ignore_case));
+    if let Some(reporter) = &crash_reporter {
+        reporter.install_panic_hook();
+    }
+
+    // Dropping the pager waits for it, so errors are printed after it has quit
+    let pager = if matches.get_flag("pager") { start_pager(&matches) } else { None };
 
     // Execute the application based on the mode selected
-    if let Err(e) = run_application(&matches) {
+    let result = run_application(&matches, log_receiver);
+    drop(pager);
+    drop(trace_guard);
+    if let Err(e) = result {
         error!("Application error: {}", e);
-        eprintln!("Error: {}", e);
+        if let Some(reporter) = crash_reporter.as_ref().filter(|_| telemetry::is_reportable(&e)) {
+            reporter.record(&CrashReport::from_error(&e));
+        }
+        eprintln!("Error: {}", venv_cleaner::core::platform::describe_error(&e));
         process::exit(1);
     }
 }
 
 /// Build the command line interface structure
 fn build_cli() -> Command {
-    Command::new("venv_cleaner")
+    let cmd = Command::new("venv_cleaner")
         .version(env!("CARGO_PKG_VERSION"))
         .author(env!("CARGO_PKG_AUTHORS"))
         .about("A multi-mode application to help manage and clean up .venv folders on Mac and Linux")
         .long_about("VenvCleaner helps you find, analyze, and clean up Python virtual environment folders (.venv) on your system. It supports three modes: CLI for command-line operations, TUI for interactive terminal interface, and GUI for graphical interface.")
         .arg(
             Arg::new("directory")
-                .help("Directory to search for .venv folders")
+                .help("Directories to search for .venv folders (several give per-root subtotals)")
                 .value_name("DIR")
                 .index(1)
+                .num_args(1..)
                 .required(false)
         )
         .arg(
@@ -91,14 +120,28 @@ fn build_cli() -> Command {
                 .long("tui")
                 .help("Launch in Terminal User Interface mode")
                 .action(clap::ArgAction::SetTrue)
-                .conflicts_with_all(&["gui", "query", "force"])
+                .conflicts_with_all(["gui", "query", "force"])
+        )
+        .arg(
+            Arg::new("read-only")
+                .long("read-only")
+                .help("Browse the TUI without any key that deletes, slims or restores, e.g. on machines where changes need approval")
+                .action(clap::ArgAction::SetTrue)
+                .requires("tui")
         )
         .arg(
             Arg::new("gui")
                 .long("gui")
                 .help("Launch in Graphical User Interface mode")
                 .action(clap::ArgAction::SetTrue)
-                .conflicts_with_all(&["tui", "query", "force"])
+                .conflicts_with_all(["tui", "query", "force"])
+        )
+        .arg(
+            Arg::new("tray")
+                .long("tray")
+                .help("Also show the .venv disk usage in the system tray, with Scan now and Clean old entries (tray feature)")
+                .action(clap::ArgAction::SetTrue)
+                .requires("gui")
         )
         .arg(
             Arg::new("verbose")
@@ -106,6 +149,35 @@ fn build_cli() -> Command {
                 .long("verbose")
                 .help("Enable verbose output")
                 .action(clap::ArgAction::Count)
+                .global(true)
+        )
+        .arg(
+            Arg::new("trace-output")
+                .long("trace-output")
+                .value_name("FILE")
+                .help("Write spans of the scan, sizing and deletions to FILE as a Chrome trace (open in chrome://tracing or Perfetto)")
+                .global(true)
+        )
+        .arg(
+            Arg::new("utc")
+                .long("utc")
+                .help("Show times in UTC instead of local time, e.g. in JSON reports compared across machines")
+                .action(clap::ArgAction::SetTrue)
+                .global(true)
+        )
+        .arg(
+            Arg::new("sort-ignore-case")
+                .long("sort-ignore-case")
+                .help("Ignore case when sorting paths, so Project3 sorts between project2 and project10")
+                .action(clap::ArgAction::SetTrue)
+                .global(true)
+        )
+        .arg(
+            Arg::new("pager")
+                .long("pager")
+                .help("Page output through $PAGER (default: less), keeping colours; only for runs that neither ask nor delete")
+                .action(clap::ArgAction::SetTrue)
+                .global(true)
         )
         .arg(
             Arg::new("dry-run")
@@ -113,10 +185,830 @@ fn build_cli() -> Command {
                 .help("Show what would be deleted without actually deleting")
                 .action(clap::ArgAction::SetTrue)
         )
+        .arg(
+            Arg::new("wait")
+                .long("wait")
+                .help("Wait for another instance cleaning the same directory instead of stopping")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with("force-lock")
+        )
+        .arg(
+            Arg::new("force-lock")
+                .long("force-lock")
+                .help("Clean even if another instance is cleaning the same directory")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("allow-escalation")
+                .long("allow-escalation")
+                .help("When a deletion is denied, let administrators retry it as root with sudo or pkexec (asks first)")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("check-permissions")
+                .long("check-permissions")
+                .help("Report whether each .venv found could be deleted, without deleting anything")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("mine")
+                .long("mine")
+                .help("Only list .venv folders owned by the current user (default)")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with_all(["all-users", "user"])
+        )
+        .arg(
+            Arg::new("all-users")
+                .long("all-users")
+                .help("List .venv folders of every user, e.g. when run as root on a shared server; needed to delete them too")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with("user")
+        )
+        .arg(
+            Arg::new("user")
+                .long("user")
+                .value_name("NAME")
+                .help("Only list .venv folders owned by this user (login name or uid)")
+        )
+        .arg(
+            Arg::new("older-than")
+                .long("older-than")
+                .value_name("AGE")
+                .help("Only include .venv folders unused for at least AGE (e.g. 90d, 2w, 12h); the TUI and GUI start with them selected")
+        )
+        .arg(
+            Arg::new("min-size")
+                .long("min-size")
+                .value_name("SIZE")
+                .help("Only include .venv folders of at least SIZE (e.g. 200MB); the TUI and GUI start with them selected")
+        )
+        .arg(
+            Arg::new("exclude-size-above")
+                .long("exclude-size-above")
+                .value_name("SIZE")
+                .help("Never delete .venv folders larger than SIZE without an interactive confirmation, as they are likely misdetected data (default 200GB, 'none' to turn off)")
+        )
+        .arg(
+            Arg::new("trash")
+                .long("trash")
+                .help("Move .venv folders to the system trash, even where it is unlikely to work (default: the trash when there is one)")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("permanent")
+                .long("permanent")
+                .help("Delete .venv folders permanently instead of moving them to the trash")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with("trash")
+        )
+        .arg(
+            Arg::new("i-know-what-im-doing")
+                .long("i-know-what-im-doing")
+                .help("Allow a recursive force run as root over / or /home, or a force run from inside a virtual environment")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("confirm-timeout")
+                .long("confirm-timeout")
+                .value_name("DURATION")
+                .help("Give up on each prompt after this long (e.g. 30s, 5m) and take --default-answer, so unattended runs cannot hang")
+        )
+        .arg(
+            Arg::new("default-answer")
+                .long("default-answer")
+                .value_name("ANSWER")
+                .help("Answer taken when a prompt times out, as if typed (default: n)")
+                .requires("confirm-timeout")
+        )
+        .arg(
+            Arg::new("from-index")
+                .long("from-index")
+                .value_name("FILE")
+                .help("List a scan saved with --query --progress json (or exported to JSON) instead of the disk; deletions are only added to a plan")
+                .conflicts_with_all(["robot", "dbus"])
+        )
+        .arg(
+            Arg::new("plan")
+                .long("plan")
+                .value_name("FILE")
+                .help("Plan file --from-index adds deletions to, for `apply-plan` on the scanned machine (default: venv_cleaner-plan.json)")
+                .requires("from-index")
+        )
+        .arg(
+            Arg::new("archive")
+                .long("archive")
+                .help("Compress .venv folders into tar.zst archives and leave a stub marker instead of deleting them")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with("trash")
+        )
+        .arg(
+            Arg::new("archive-dir")
+                .long("archive-dir")
+                .value_name("DIR")
+                .help("Directory archives are written to (implies --archive)")
+                .conflicts_with("trash")
+        )
+        .arg(
+            Arg::new("respect-gitignore")
+                .long("respect-gitignore")
+                .help("Skip directories ignored by .gitignore rules and global git excludes when scanning")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("profile")
+                .long("profile")
+                .value_name("NAME")
+                .help("Apply the [profiles.NAME] preset of the config: its roots, excludes, detectors and thresholds")
+        )
+        .arg(
+            Arg::new("skip-hidden")
+                .long("skip-hidden")
+                .help("Do not descend into hidden directories such as .cache, .cargo or .local when scanning")
+                .action(clap::ArgAction::SetTrue)
+                .overrides_with("include-hidden")
+        )
+        .arg(
+            Arg::new("scan-temp")
+                .long("scan-temp")
+                .help("Also scan /tmp and $TMPDIR for environments of any name left behind by CI jobs and tox, unused for over a day")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("include")
+                .long("include")
+                .value_name("GLOB")
+                .help("Only scan subtrees matching GLOB, e.g. '~/work/*/backend' or '*/services' (relative to each root; ** matches any depth). Repeatable")
+                .action(clap::ArgAction::Append)
+        )
+        .arg(
+            Arg::new("include-hidden")
+                .long("include-hidden")
+                .help("Descend into hidden directories when scanning (the default; overrides skip_hidden in the config)")
+                .action(clap::ArgAction::SetTrue)
+                .overrides_with("skip-hidden")
+        )
+        .arg(
+            Arg::new("no-validate")
+                .long("no-validate")
+                .help("Also list folders named .venv that do not look like virtual environments (no two of bin, Scripts, lib, include, pyvenv.cfg)")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("last-install")
+                .long("last-install")
+                .help("Show when pip last installed a package into each .venv (query mode column)")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("activity")
+                .long("activity")
+                .help("Show each project's last activity: the newer of its last commit and newest source file (query mode column, cached for a day)")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("size-delta")
+                .long("size-delta")
+                .help("Show how much each .venv grew or shrank since the previous scan, highlighting the largest growth (query mode column)")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("size-timeout")
+                .long("size-timeout")
+                .value_name("SECONDS")
+                .help("Stop sizing a single .venv after this many seconds and report a partial size (0 = no limit)")
+                .value_parser(clap::value_parser!(u64))
+        )
+        .arg(
+            Arg::new("scan-threads")
+                .long("scan-threads")
+                .value_name("N")
+                .help("Threads sizing .venv folders during a scan (default: CPU count up to 8, 2 on network mounts)")
+                .value_parser(clap::value_parser!(usize))
+        )
+        .arg(
+            Arg::new("delete-jobs")
+                .long("delete-jobs")
+                .value_name("N")
+                .help("Folders deleted at once by batch deletions (default: CPU count up to 4, 1 on network mounts)")
+                .value_parser(clap::value_parser!(usize))
+        )
+        .arg(
+            Arg::new("deep-clean")
+                .long("deep-clean")
+                .help("Also offer to delete each project's build/, dist/, *.egg-info and .tox (asks per item)")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("include-artifacts")
+                .long("include-artifacts")
+                .help("Let --force and a decision hook delete the build artifacts found by --deep-clean without asking")
+                .action(clap::ArgAction::SetTrue)
+                .requires("deep-clean")
+        )
+        .arg(
+            Arg::new("include-user-data")
+                .long("include-user-data")
+                .help("Let --force and --progress json delete .venv folders holding data files (.csv, .ipynb, .zip, ...) without asking")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("group-by")
+                .long("group-by")
+                .value_name("KEY")
+                .help("Group the query mode table: none, or age for 0-30/31-90/91-180/>180 day sections")
+                .value_parser(["none", "age"])
+                .default_value("none")
+        )
+        .arg(
+            Arg::new("sort")
+                .long("sort")
+                .value_name("KEY")
+                .help("Sort the query mode table: size (largest first), files (most files first), last-used, created (newest first) or location")
+                .value_parser(["size", "files", "last-used", "created", "location"])
+                .default_value("size")
+        )
+        .arg(
+            Arg::new("watch")
+                .long("watch")
+                .value_name("INTERVAL")
+                .help("With --query, rescan and redraw the table every INTERVAL (e.g. 30s, 5m) to watch space come back; type s to sort by the next column, r to reverse, q to quit")
+        )
+        .arg(
+            Arg::new("file-count")
+                .long("file-count")
+                .help("Show how many files each .venv holds (query mode column); many small files are slow to delete and use up inodes")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("inodes")
+                .long("inodes")
+                .help("Show the inodes each .venv uses (query mode column) and how many the file systems holding them have left")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("summary-json")
+                .long("summary-json")
+                .value_name("PATH")
+                .help("Write the final summary (counts, freed bytes, failed paths) as JSON to PATH, for auditing cron runs")
+        )
+        .arg(
+            Arg::new("progress")
+                .long("progress")
+                .value_name("FORMAT")
+                .help("Progress output format: human, or json for line-delimited events (needs --query or --force)")
+                .value_parser(["human", "json"])
+                .default_value("human")
+        )
+        .arg(
+            Arg::new("low-memory")
+                .long("low-memory")
+                .help("With --query, stream rows to --output as they are found and print the totals, keeping no scan results or caches (for constrained CI containers)")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("resume")
+                .long("resume")
+                .help("Resume an interrupted recursive scan from its last checkpoint instead of starting over")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .value_name("FORMAT")
+                .help("Print the --query listing as a table, json, csv, markdown or html report instead of the colored table")
+                .value_parser(["table", "json", "csv", "markdown", "html"])
+        )
+        .arg(
+            Arg::new("robot")
+                .long("robot")
+                .help("Read JSON commands on stdin and write JSON responses (for editor plugins)")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with_all(["tui", "gui", "query", "force"])
+        )
+        .arg(
+            Arg::new("dbus")
+                .long("dbus")
+                .help("Serve the org.ncca.VenvCleaner interface on the D-Bus session bus (Linux)")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with_all(["tui", "gui", "query", "force", "robot"])
+        )
+        .subcommand(
+            Command::new("bench")
+                .about("Time the scanner's walk and sizing over a directory several times (for comparing scanner changes)")
+                .hide(true)
+                .arg(
+                    Arg::new("directory")
+                        .help("Directory to scan recursively")
+                        .value_name("DIR")
+                        .index(1)
+                )
+                .arg(
+                    Arg::new("runs")
+                        .long("runs")
+                        .value_name("N")
+                        .help("Number of scans to time (default 5)")
+                        .value_parser(clap::value_parser!(usize))
+                )
+                .arg(
+                    Arg::new("scan-threads")
+                        .long("scan-threads")
+                        .value_name("N")
+                        .help("Threads sizing .venv folders (default: CPU count up to 8, 2 on network mounts)")
+                        .value_parser(clap::value_parser!(usize))
+                )
+        )
+        .subcommand(
+            Command::new("stats")
+                .about("Show aggregate statistics about .venv folders")
+                .arg(
+                    Arg::new("directory")
+                        .help("Directory to search for .venv folders")
+                        .value_name("DIR")
+                        .index(1)
+                )
+                .arg(
+                    Arg::new("recursive")
+                        .short('r')
+                        .long("recursive")
+                        .help("Recursively search from the specified directory")
+                        .action(clap::ArgAction::SetTrue)
+                )
+                .arg(
+                    Arg::new("by-python")
+                        .long("by-python")
+                        .help("Group .venv folders by Python version and flag end-of-life releases")
+                        .action(clap::ArgAction::SetTrue)
+                )
+                .arg(
+                    Arg::new("breakdown")
+                        .long("breakdown")
+                        .help("Break the size down into bytecode, shared libraries, sources and data")
+                        .action(clap::ArgAction::SetTrue)
+                )
+                .arg(
+                    Arg::new("by-package")
+                        .long("by-package")
+                        .help("Add up the heaviest packages across all .venv folders")
+                        .action(clap::ArgAction::SetTrue)
+                )
+        )
+        .subcommand(
+            Command::new("slim")
+                .about("Reclaim space from .venv folders without deleting them (__pycache__, tests, pip cache, orphaned metadata)")
+                .arg(
+                    Arg::new("directory")
+                        .help("Directory to search for .venv folders")
+                        .value_name("DIR")
+                        .index(1)
+                )
+                .arg(
+                    Arg::new("recursive")
+                        .short('r')
+                        .long("recursive")
+                        .help("Recursively search from the specified directory")
+                        .action(clap::ArgAction::SetTrue)
+                )
+                .arg(
+                    Arg::new("dry-run")
+                        .long("dry-run")
+                        .help("Show how much would be reclaimed without removing anything")
+                        .action(clap::ArgAction::SetTrue)
+                )
+                .arg(
+                    Arg::new("strip")
+                        .long("strip")
+                        .help("Also strip debug symbols from native shared libraries (requires `strip`)")
+                        .action(clap::ArgAction::SetTrue)
+                )
+        )
+        .subcommand(
+            Command::new("explain")
+                .about("Show where the space inside one .venv goes: its largest packages, caches and shared libraries")
+                .arg(
+                    Arg::new("path")
+                        .help("The .venv, or a project directory containing one")
+                        .value_name("PATH")
+                        .required(true)
+                        .index(1)
+                )
+                .arg(
+                    Arg::new("depth")
+                        .long("depth")
+                        .value_name("LEVELS")
+                        .help("Levels of the tree shown below the .venv (default: 3)")
+                        .value_parser(clap::value_parser!(usize))
+                )
+        )
+        .subcommand(
+            Command::new("advise")
+                .about("Walk through the cleanup recommendations one category at a time and carry out the approved ones")
+                .arg(
+                    Arg::new("directory")
+                        .help("Directory to search for .venv folders")
+                        .value_name("DIR")
+                        .index(1)
+                )
+                .arg(
+                    Arg::new("recursive")
+                        .short('r')
+                        .long("recursive")
+                        .help("Recursively search from the specified directory")
+                        .action(clap::ArgAction::SetTrue)
+                )
+                .arg(
+                    Arg::new("dry-run")
+                        .long("dry-run")
+                        .help("Show what the approved actions would reclaim without changing anything")
+                        .action(clap::ArgAction::SetTrue)
+                )
+        )
+        .subcommand(
+            Command::new("policy")
+                .about("Try cleanup rules out before automating them")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("simulate")
+                        .about("Show what an unattended --force run would delete and which rule keeps the rest, without deleting anything")
+                        .arg(
+                            Arg::new("directory")
+                                .help("Directory to search for .venv folders")
+                                .value_name("DIR")
+                                .index(1)
+                        )
+                        .arg(
+                            Arg::new("recursive")
+                                .short('r')
+                                .long("recursive")
+                                .help("Recursively search from the specified directory")
+                                .action(clap::ArgAction::SetTrue)
+                        )
+                        .arg(
+                            Arg::new("older-than")
+                                .long("older-than")
+                                .value_name("AGE")
+                                .help("Only delete .venv folders not used for AGE (e.g. 90d, 12w, 36h)")
+                        )
+                        .arg(
+                            Arg::new("min-size")
+                                .long("min-size")
+                                .value_name("SIZE")
+                                .help("Only delete .venv folders of at least SIZE (e.g. 200MB, 1GB)")
+                        )
+                        .arg(
+                            Arg::new("exclude-size-above")
+                                .long("exclude-size-above")
+                                .value_name("SIZE")
+                                .help("Keep .venv folders larger than SIZE (default 200GB, 'none' to turn off)")
+                        )
+                        .arg(
+                            Arg::new("include-user-data")
+                                .long("include-user-data")
+                                .help("Also delete .venv folders holding user data")
+                                .action(clap::ArgAction::SetTrue)
+                        )
+                        .arg(
+                            Arg::new("from-index")
+                                .long("from-index")
+                                .value_name("FILE")
+                                .help("Simulate over a report saved with --query --progress json instead of scanning")
+                        )
+                )
+        )
+        .subcommand(
+            Command::new("info")
+                .about("Show build features, config and cache locations and detected platform integrations")
+        )
+        .subcommand(
+            Command::new("prompt-summary")
+                .about("Print the cached summary of the last full scan on one line, for a shell prompt; never scans")
+                .arg(
+                    Arg::new("format")
+                        .long("format")
+                        .value_name("TEMPLATE")
+                        .help("Line to print, with {count}, {size}, {old} (unused over 90 days) and {age} filled in (default: \"venvs: {count} / {size}\")")
+                )
+                .arg(
+                    Arg::new("max-age")
+                        .long("max-age")
+                        .value_name("AGE")
+                        .help("Print nothing if the last full scan is older than this, e.g. 12h or 7d")
+                )
+        )
+        .subcommand(
+            Command::new("refresh-cache")
+                .about("Rescan the configured roots silently and refresh the cached summary, size history and project activity (for cron)")
+                .arg(
+                    Arg::new("directory")
+                        .help("Directories to scan instead of the configured roots")
+                        .value_name("DIR")
+                        .num_args(1..)
+                        .index(1)
+                )
+                .arg(
+                    Arg::new("profile")
+                        .long("profile")
+                        .value_name("NAME")
+                        .help("Apply the [profiles.NAME] preset of the config: its roots, excludes and detectors")
+                )
+        )
+        .subcommand(
+            Command::new("unarchive")
+                .about("Restore .venv folders archived with --archive (or discard their archives)")
+                .arg(
+                    Arg::new("paths")
+                        .help("Stub files (.venv.archived) or project directories to restore")
+                        .value_name("PATH")
+                        .num_args(0..)
+                )
+                .arg(
+                    Arg::new("recursive")
+                        .short('r')
+                        .long("recursive")
+                        .help("Recursively search the given directories for archived .venv folders")
+                        .action(clap::ArgAction::SetTrue)
+                )
+                .arg(
+                    Arg::new("dry-run")
+                        .long("dry-run")
+                        .help("Show what would be restored without extracting anything")
+                        .action(clap::ArgAction::SetTrue)
+                )
+                .arg(
+                    Arg::new("delete")
+                        .long("delete")
+                        .help("Delete the archives (and stubs) instead of restoring them")
+                        .action(clap::ArgAction::SetTrue)
+                )
+        )
+        .subcommand(
+            Command::new("diff")
+                .about("Compare two scans saved with --query --progress json or --output json (removed, added and grown .venv folders)")
+                .arg(
+                    Arg::new("before")
+                        .long("before")
+                        .value_name("FILE")
+                        .help("Scan taken before the cleanup")
+                        .required(true)
+                )
+                .arg(
+                    Arg::new("after")
+                        .long("after")
+                        .value_name("FILE")
+                        .help("Scan taken after the cleanup")
+                        .required(true)
+                )
+        )
+        .subcommand(
+            Command::new("merge")
+                .about("Combine the JSON scan reports of several machines into per-host totals (hosts are named after the report files)")
+                .arg(
+                    Arg::new("reports")
+                        .help("Reports saved with --query --progress json or exported to JSON, e.g. build-01.json")
+                        .value_name("REPORT")
+                        .num_args(1..)
+                        .required(true)
+                )
+                .arg(
+                    Arg::new("html")
+                        .long("html")
+                        .value_name("FILE")
+                        .help("Also write an HTML report listing every .venv with its host")
+                )
+        )
+        .subcommand(
+            Command::new("apply-plan")
+                .about("Delete the .venv folders of a plan made with --from-index, on the machine that was scanned")
+                .arg(
+                    Arg::new("plan")
+                        .help("The plan file")
+                        .value_name("PLAN")
+                        .index(1)
+                        .required(true)
+                )
+                .arg(
+                    Arg::new("force")
+                        .short('f')
+                        .long("force")
+                        .help("Delete without asking for confirmation")
+                        .action(clap::ArgAction::SetTrue)
+                )
+                .arg(
+                    Arg::new("dry-run")
+                        .long("dry-run")
+                        .help("Show what would be deleted without deleting anything")
+                        .action(clap::ArgAction::SetTrue)
+                )
+                .arg(
+                    Arg::new("trash")
+                        .long("trash")
+                        .help("Move the .venv folders to the system trash, even where it is unlikely to work (default: the trash when there is one)")
+                        .action(clap::ArgAction::SetTrue)
+                )
+                .arg(
+                    Arg::new("permanent")
+                        .long("permanent")
+                        .help("Delete the .venv folders permanently instead of moving them to the trash")
+                        .action(clap::ArgAction::SetTrue)
+                        .conflicts_with("trash")
+                )
+                .arg(
+                    Arg::new("all-users")
+                        .long("all-users")
+                        .help("Also delete planned .venv folders owned by other users")
+                        .action(clap::ArgAction::SetTrue)
+                )
+                .arg(
+                    Arg::new("include-user-data")
+                        .long("include-user-data")
+                        .help("Delete planned .venv folders holding data files without asking for each")
+                        .action(clap::ArgAction::SetTrue)
+                )
+        )
+        .subcommand(
+            Command::new("audit")
+                .about("Check the hash-chained deletion audit log enabled by the policy's audit_log")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("verify")
+                        .about("Verify that no audit log entry was edited, removed or reordered")
+                        .arg(
+                            Arg::new("log")
+                                .long("log")
+                                .value_name("FILE")
+                                .help("Audit log to check (default: the audit_log of the policy)")
+                        )
+                )
+        )
+        .subcommand(
+            Command::new("trash")
+                .about("List or purge the .venv folders VenvCleaner moved to the trash with --trash")
+                .subcommand(Command::new("list").about("List the trashed .venv folders and the space they hold"))
+                .subcommand(
+                    Command::new("purge")
+                        .about("Permanently remove trashed .venv folders, leaving the rest of the trash alone")
+                        .arg(
+                            Arg::new("older-than")
+                                .long("older-than")
+                                .value_name("AGE")
+                                .help("Only purge folders trashed longer ago than this, e.g. 12h, 30d or 2w (default: 30d)")
+                        )
+                        .arg(
+                            Arg::new("dry-run")
+                                .long("dry-run")
+                                .help("Show what would be purged without removing anything")
+                                .action(clap::ArgAction::SetTrue)
+                        )
+                )
+        );
+
+    add_dev_args(cmd)
+}
+
+/// Add hidden developer-only arguments (debug builds only)
+#[cfg(debug_assertions)]
+fn add_dev_args(cmd: Command) -> Command {
+    cmd
+        .arg(
+            Arg::new("generate-fixture")
+                .long("generate-fixture")
+                .value_name("DIR")
+                .help("Generate a synthetic tree of .venv folders for testing (dev only)")
+                .hide(true)
+        )
+        .arg(
+            Arg::new("fixture-count")
+                .long("fixture-count")
+                .value_name("N")
+                .help("Number of .venv folders to generate (default 100)")
+                .value_parser(clap::value_parser!(usize))
+                .requires("generate-fixture")
+                .hide(true)
+        )
+        .arg(
+            Arg::new("fixture-size")
+                .long("fixture-size")
+                .value_name("SIZE")
+                .help("Size of each generated .venv, e.g. 64K or 10M (default 64K)")
+                .requires("generate-fixture")
+                .hide(true)
+        )
+        .arg(
+            Arg::new("fixture-max-age")
+                .long("fixture-max-age")
+                .value_name("DAYS")
+                .help("Spread last-used times from now back to DAYS ago (default 180)")
+                .value_parser(clap::value_parser!(u64))
+                .requires("generate-fixture")
+                .hide(true)
+        )
+}
+
+/// Release builds have no developer-only arguments
+#[cfg(not(debug_assertions))]
+fn add_dev_args(cmd: Command) -> Command {
+    cmd
 }
 
 /// Run the application based on the parsed command line arguments
-fn run_application(matches: &ArgMatches) -> Result<(), VenvCleanerError> {
+fn run_application(matches: &ArgMatches, log_receiver: Option<Receiver<LogLine>>) -> Result<(), VenvCleanerError> {
+    // Subcommands run independently of the interactive modes
+    if let Some(("stats", stats_matches)) = matches.subcommand() {
+        return StatsCommand::new(stats_matches)?.execute();
+    }
+
+    if let Some(("bench", bench_matches)) = matches.subcommand() {
+        return BenchCommand::new(bench_matches)?.execute().map(|_| ());
+    }
+
+    if let Some(("slim", slim_matches)) = matches.subcommand() {
+        return SlimCommand::new(slim_matches)?.execute().map(|_| ());
+    }
+
+    if let Some(("explain", explain_matches)) = matches.subcommand() {
+        return ExplainCommand::new(explain_matches)?.execute().map(|_| ());
+    }
+
+    if let Some(("advise", advise_matches)) = matches.subcommand() {
+        return AdviseCommand::new(advise_matches)?.execute().map(|_| ());
+    }
+
+    if let Some(("policy", policy_matches)) = matches.subcommand() {
+        if let Some(("simulate", simulate_matches)) = policy_matches.subcommand() {
+            return PolicyCommand::new(simulate_matches)?.execute();
+        }
+    }
+
+    if let Some(("info", _)) = matches.subcommand() {
+        return InfoCommand::new().execute();
+    }
+
+    if let Some(("prompt-summary", prompt_matches)) = matches.subcommand() {
+        return PromptSummaryCommand::new(prompt_matches)?.execute();
+    }
+
+    if let Some(("refresh-cache", refresh_matches)) = matches.subcommand() {
+        return RefreshCacheCommand::new(refresh_matches)?.execute();
+    }
+
+    if let Some(("unarchive", unarchive_matches)) = matches.subcommand() {
+        return UnarchiveCommand::new(unarchive_matches)?.execute().map(|_| ());
+    }
+
+    if let Some(("diff", diff_matches)) = matches.subcommand() {
+        return DiffCommand::new(diff_matches)?.execute().map(|_| ());
+    }
+
+    if let Some(("apply-plan", apply_matches)) = matches.subcommand() {
+        return ApplyPlanCommand::new(apply_matches)?.execute().map(|_| ());
+    }
+
+    if let Some(("merge", merge_matches)) = matches.subcommand() {
+        return MergeCommand::new(merge_matches)?.execute().map(|_| ());
+    }
+
+    if let Some(("audit", audit_matches)) = matches.subcommand() {
+        if let Some(("verify", verify_matches)) = audit_matches.subcommand() {
+            return AuditCommand::new(verify_matches)?.execute().map(|_| ());
+        }
+    }
+
+    if let Some(("trash", trash_matches)) = matches.subcommand() {
+        return TrashCommand::new(trash_matches)?.execute();
+    }
+
+    if matches.get_flag("robot") {
+        let stdin = std::io::stdin();
+        return RobotMode::new(matches)?.run(stdin.lock(), std::io::stdout());
+    }
+
+    if matches.get_flag("dbus") {
+        #[cfg(all(feature = "dbus", target_os = "linux"))]
+        {
+            let base_directory = venv_cleaner::cli::resolve_base_directory(matches)?;
+            let config = Config::load()?;
+            let service = venv_cleaner::dbus::DbusService::new(
+                base_directory,
+                !matches.get_flag("no-recursive"),
+                matches.get_flag("dry-run"),
+                matches.get_count("verbose"),
+            )
+            .with_delete_mode(venv_cleaner::cli::resolve_delete_mode(matches, config.delete_mode.as_deref())?)
+            .with_policy(config.policy)
+            .with_delete_hooks(config.hooks);
+            return service.run();
+        }
+        #[cfg(not(all(feature = "dbus", target_os = "linux")))]
+        {
+            eprintln!("D-Bus support is not available in this build. Please rebuild with --features dbus on Linux");
+            return Err(VenvCleanerError::FeatureNotAvailable("D-Bus".to_string()));
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    if matches.get_one::<String>("generate-fixture").is_some() {
+        return venv_cleaner::cli::FixtureCommand::new(matches)?.execute();
+    }
+
     // Determine the operating mode
     let mode = determine_mode(matches);
 
@@ -131,28 +1023,48 @@ fn run_application(matches: &ArgMatches) -> Result<(), VenvCleanerError> {
             #[cfg(feature = "tui")]
             {
                 // Extract TUI-specific arguments
-                let base_directory = if let Some(dir) = matches.get_one::<String>("directory") {
-                    std::path::PathBuf::from(dir)
-                } else {
-                    std::env::current_dir()?
-                };
+                let config = Config::load()?.with_profile(matches.get_one::<String>("profile").map(String::as_str))?;
+                let (base_directory, extra_roots) = venv_cleaner::cli::resolve_scan_roots(matches, &config.scan)?;
 
                 // TUI mode defaults to recursive unless explicitly disabled
-                let recursive = if matches.get_flag("no-recursive") {
-                    false
-                } else if matches.get_flag("recursive") {
-                    true
-                } else {
-                    true // Default to recursive for TUI mode
-                };
+                let recursive = !matches.get_flag("no-recursive");
                 let verbosity = matches.get_count("verbose");
 
                 // Create and run TUI mode
-                let mut tui_mode = TuiMode::new(base_directory, recursive, verbosity)?;
+                // The theme is checked before the terminal switches to raw mode
+                let theme = Theme::from_config(&config.tui)?;
+                let mut tui_mode = TuiMode::new(base_directory, recursive, verbosity)?
+                    .with_read_only(matches.get_flag("read-only"))
+                    .with_extra_roots(extra_roots)
+                    .with_policy(config.policy.clone())
+                    .with_delete_hooks(config.hooks.clone())
+                    .with_delete_mode(venv_cleaner::cli::resolve_delete_mode(matches, config.delete_mode.as_deref())?)
+                    .with_archive_dir(venv_cleaner::cli::resolve_archive_dir(matches))
+                    .with_size_timeout(venv_cleaner::cli::resolve_size_timeout(matches))
+                    .with_concurrency(
+                        matches.get_one::<usize>("scan-threads").copied(),
+                        matches.get_one::<usize>("delete-jobs").copied(),
+                    )
+                    .with_owner_filter(venv_cleaner::cli::resolve_owner_filter(matches)?)
+                    .with_venv_filter(venv_cleaner::cli::resolve_venv_filter(matches, &config.scan)?)
+                    .with_skip_hidden(venv_cleaner::cli::resolve_skip_hidden(matches, &config.scan))
+                    .with_validation(!matches.get_flag("no-validate"))
+                    .with_excludes(config.scan.exclude_paths())
+                    .with_includes(venv_cleaner::cli::resolve_includes(matches)?)
+                    .with_detectors(venv_cleaner::core::Detector::from_configs(&config.detectors)?)
+                    .with_offline_index(venv_cleaner::cli::resolve_offline_index(matches)?)
+                    .with_editor(config.editor)
+                    .with_theme(theme)
+                    .with_glyphs(venv_cleaner::tui::Glyphs::from_config(config.tui.glyphs.as_deref())?)
+                    .with_terminal_progress(config.tui.terminal_progress.unwrap_or(true));
+                if let Some(receiver) = log_receiver {
+                    tui_mode = tui_mode.with_log_receiver(receiver);
+                }
                 tui_mode.run()
             }
             #[cfg(not(feature = "tui"))]
             {
+                let _ = log_receiver;
                 eprintln!("TUI mode is not available in this build. Please rebuild with --features tui");
                 Err(VenvCleanerError::FeatureNotAvailable("TUI".to_string()))
             }
@@ -161,11 +1073,8 @@ fn run_application(matches: &ArgMatches) -> Result<(), VenvCleanerError> {
             #[cfg(feature = "gui")]
             {
                 // Extract GUI-specific arguments
-                let base_directory = if let Some(dir) = matches.get_one::<String>("directory") {
-                    std::path::PathBuf::from(dir)
-                } else {
-                    std::env::current_dir()?
-                };
+                let config = Config::load()?.with_profile(matches.get_one::<String>("profile").map(String::as_str))?;
+                let (base_directory, extra_roots) = venv_cleaner::cli::resolve_scan_roots(matches, &config.scan)?;
 
                 // GUI mode defaults to recursive unless explicitly disabled
                 let recursive = if matches.get_flag("no-recursive") {
@@ -178,11 +1087,36 @@ fn run_application(matches: &ArgMatches) -> Result<(), VenvCleanerError> {
                 let verbosity = matches.get_count("verbose");
 
                 // Create and run GUI mode
-                let gui_mode = GuiMode::new(base_directory, recursive, verbosity)?;
+                let mut gui_mode = GuiMode::new(base_directory, recursive, verbosity)?
+                    .with_extra_roots(extra_roots)
+                    .with_delete_mode(venv_cleaner::cli::resolve_delete_mode(matches, config.delete_mode.as_deref())?)
+                    .with_archive_dir(venv_cleaner::cli::resolve_archive_dir(matches))
+                    .with_owner_filter(venv_cleaner::cli::resolve_owner_filter(matches)?)
+                    .with_venv_filter(venv_cleaner::cli::resolve_venv_filter(matches, &config.scan)?)
+                    .with_skip_hidden(venv_cleaner::cli::resolve_skip_hidden(matches, &config.scan))
+                    .with_validation(!matches.get_flag("no-validate"))
+                    .with_excludes(config.scan.exclude_paths())
+                    .with_includes(venv_cleaner::cli::resolve_includes(matches)?)
+                    .with_detectors(venv_cleaner::core::Detector::from_configs(&config.detectors)?)
+                    .with_offline_index(venv_cleaner::cli::resolve_offline_index(matches)?)
+                    .with_editor(config.editor);
+                if let Some(receiver) = log_receiver {
+                    gui_mode = gui_mode.with_log_receiver(receiver);
+                }
+                #[cfg(feature = "tray")]
+                {
+                    gui_mode = gui_mode.with_tray(matches.get_flag("tray"));
+                }
+                #[cfg(not(feature = "tray"))]
+                if matches.get_flag("tray") {
+                    eprintln!("The system tray is not available in this build. Please rebuild with --features tray");
+                    return Err(VenvCleanerError::FeatureNotAvailable("System tray".to_string()));
+                }
                 gui_mode.run()
             }
             #[cfg(not(feature = "gui"))]
             {
+                let _ = log_receiver;
                 eprintln!("GUI mode is not available in this build. Please rebuild with --features gui");
                 Err(VenvCleanerError::FeatureNotAvailable("GUI".to_string()))
             }
@@ -201,6 +1135,61 @@ fn determine_mode(matches: &ArgMatches) -> AppMode {
     }
 }
 
+/// Record tracing spans in a Chrome trace file until the returned guard is dropped
+///
+/// Events keep going to the logger as well, so `-v` output is unchanged.
+fn install_trace_output(path: &str) -> tracing_chrome::FlushGuard {
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let (layer, guard) = tracing_chrome::ChromeLayerBuilder::new().file(path).include_args(true).build();
+    if tracing::subscriber::set_global_default(tracing_subscriber::registry().with(layer)).is_err() {
+        eprintln!("Could not record a trace: a tracing subscriber is already installed");
+    }
+    guard
+}
+
+/// Start paging stdout for `--pager`, if the run only prints
+fn start_pager(matches: &ArgMatches) -> Option<Pager> {
+    if !only_prints(matches) {
+        eprintln!("Not paging: --pager only applies to runs that neither ask for confirmation nor delete");
+        return None;
+    }
+    Pager::start().unwrap_or_else(|e| {
+        warn!("Not paging: {}", e);
+        None
+    })
+}
+
+/// Check whether the run only prints, so that its output can go through a pager
+///
+/// Prompts would be hidden behind the pager, and quitting it early ends the
+/// run, which must never happen halfway through a deletion.
+fn only_prints(matches: &ArgMatches) -> bool {
+    match matches.subcommand() {
+        Some(("stats" | "bench" | "explain" | "advise" | "info" | "diff" | "merge" | "audit", _)) => true,
+        Some(("slim" | "unarchive" | "apply-plan", sub_matches)) => sub_matches.get_flag("dry-run"),
+        Some(("trash", trash_matches)) => match trash_matches.subcommand() {
+            Some(("purge", purge_matches)) => purge_matches.get_flag("dry-run"),
+            _ => true,
+        },
+        Some(_) => false,
+        None => {
+            !matches.get_flag("robot")
+                && !matches.get_flag("dbus")
+                && determine_mode(matches) == AppMode::Cli
+                && (matches.get_flag("query") || matches.get_flag("dry-run") || matches.get_flag("check-permissions"))
+        }
+    }
+}
+
+/// Check whether the run ends up in the TUI or GUI, whose log lines are shown in the app
+fn captures_logs(matches: &ArgMatches) -> bool {
+    matches.subcommand().is_none()
+        && !matches.get_flag("robot")
+        && !matches.get_flag("dbus")
+        && determine_mode(matches) != AppMode::Cli
+}
+
 /// Application operating modes
 #[derive(Debug, Clone, PartialEq)]
 enum AppMode {
@@ -222,21 +1211,128 @@ mod tests {
         assert_eq!(cmd.get_name(), "venv_cleaner");
     }
 
+    #[test]
+    fn test_multiple_directories_parsing() {
+        let matches = build_cli()
+            .try_get_matches_from(["venv_cleaner", "-r", "-q", "work", "src"])
+            .unwrap();
+        let directories: Vec<&String> = matches.get_many::<String>("directory").unwrap().collect();
+        assert_eq!(directories, vec!["work", "src"]);
+        assert!(matches.subcommand().is_none());
+    }
+
+    #[test]
+    fn test_stats_subcommand_parsing() {
+        let matches = build_cli()
+            .try_get_matches_from(["venv_cleaner", "stats", "--by-python", "-r"])
+            .unwrap();
+        let (name, stats_matches) = matches.subcommand().unwrap();
+        assert_eq!(name, "stats");
+        assert!(stats_matches.get_flag("by-python"));
+        assert!(stats_matches.get_flag("recursive"));
+    }
+
+    #[test]
+    fn test_slim_subcommand_parsing() {
+        let matches = build_cli()
+            .try_get_matches_from(["venv_cleaner", "slim", "--dry-run", "-r"])
+            .unwrap();
+        let (name, slim_matches) = matches.subcommand().unwrap();
+        assert_eq!(name, "slim");
+        assert!(slim_matches.get_flag("dry-run"));
+    }
+
+    #[test]
+    fn test_unarchive_subcommand_parsing() {
+        let matches = build_cli()
+            .try_get_matches_from(["venv_cleaner", "unarchive", "a/.venv.archived", "b", "-r"])
+            .unwrap();
+        let (name, unarchive_matches) = matches.subcommand().unwrap();
+        assert_eq!(name, "unarchive");
+        assert_eq!(unarchive_matches.get_many::<String>("paths").unwrap().count(), 2);
+        assert!(build_cli().try_get_matches_from(["venv_cleaner", "--archive", "--trash"]).is_err());
+    }
+
+    #[test]
+    fn test_diff_subcommand_parsing() {
+        let matches = build_cli()
+            .try_get_matches_from(["venv_cleaner", "diff", "--before", "old.json", "--after", "new.json"])
+            .unwrap();
+        let (name, diff_matches) = matches.subcommand().unwrap();
+        assert_eq!(name, "diff");
+        assert_eq!(diff_matches.get_one::<String>("after").unwrap(), "new.json");
+        assert!(build_cli().try_get_matches_from(["venv_cleaner", "diff", "--before", "old.json"]).is_err());
+    }
+
+    #[test]
+    fn test_audit_subcommand_parsing() {
+        let matches = build_cli()
+            .try_get_matches_from(["venv_cleaner", "audit", "verify", "--log", "audit.log"])
+            .unwrap();
+        let (_, audit_matches) = matches.subcommand().unwrap();
+        let (action, verify_matches) = audit_matches.subcommand().unwrap();
+        assert_eq!(action, "verify");
+        assert_eq!(verify_matches.get_one::<String>("log").unwrap(), "audit.log");
+        assert!(build_cli().try_get_matches_from(["venv_cleaner", "audit"]).is_err());
+    }
+
+    #[test]
+    fn test_trash_subcommand_parsing() {
+        let matches = build_cli()
+            .try_get_matches_from(["venv_cleaner", "trash", "purge", "--older-than", "30d", "--dry-run"])
+            .unwrap();
+        let (name, trash_matches) = matches.subcommand().unwrap();
+        assert_eq!(name, "trash");
+        let (action, purge_matches) = trash_matches.subcommand().unwrap();
+        assert_eq!(action, "purge");
+        assert_eq!(purge_matches.get_one::<String>("older-than").unwrap(), "30d");
+    }
+
+    #[test]
+    fn test_info_subcommand_parsing() {
+        let matches = build_cli().try_get_matches_from(["venv_cleaner", "info"]).unwrap();
+        assert_eq!(matches.subcommand_name(), Some("info"));
+    }
+
     #[test]
     fn test_mode_determination() {
-        let matches = build_cli().try_get_matches_from(&["venv_cleaner"]).unwrap();
+        let matches = build_cli().try_get_matches_from(["venv_cleaner"]).unwrap();
         assert_eq!(determine_mode(&matches), AppMode::Cli);
     }
 
     #[test]
     fn test_tui_mode_determination() {
-        let matches = build_cli().try_get_matches_from(&["venv_cleaner", "--tui"]).unwrap();
+        let matches = build_cli().try_get_matches_from(["venv_cleaner", "--tui"]).unwrap();
         assert_eq!(determine_mode(&matches), AppMode::Tui);
     }
 
+    #[test]
+    fn test_read_only_needs_tui() {
+        assert!(build_cli().try_get_matches_from(["venv_cleaner", "--read-only"]).is_err());
+        let matches = build_cli().try_get_matches_from(["venv_cleaner", "--tui", "--read-only"]).unwrap();
+        assert!(matches.get_flag("read-only"));
+    }
+
     #[test]
     fn test_gui_mode_determination() {
-        let matches = build_cli().try_get_matches_from(&["venv_cleaner", "--gui"]).unwrap();
+        let matches = build_cli().try_get_matches_from(["venv_cleaner", "--gui"]).unwrap();
+        assert_eq!(determine_mode(&matches), AppMode::Gui);
+
+        let matches = build_cli().try_get_matches_from(["venv_cleaner", "--gui", "--tray"]).unwrap();
         assert_eq!(determine_mode(&matches), AppMode::Gui);
+        assert!(build_cli().try_get_matches_from(["venv_cleaner", "--tray"]).is_err());
+    }
+
+    #[test]
+    fn test_only_runs_that_print_are_paged() {
+        let prints = |args: &[&str]| only_prints(&build_cli().try_get_matches_from(args).unwrap());
+        assert!(prints(&["venv_cleaner", "-q", "--pager"]));
+        assert!(prints(&["venv_cleaner", "stats", "--pager"]));
+        assert!(prints(&["venv_cleaner", "trash", "list"]));
+        assert!(prints(&["venv_cleaner", "slim", "--dry-run"]));
+        assert!(!prints(&["venv_cleaner", "slim"]));
+        assert!(!prints(&["venv_cleaner", "--pager"]));
+        assert!(!prints(&["venv_cleaner", "-f"]));
+        assert!(!prints(&["venv_cleaner", "--tui"]));
     }
 }
@@ -4,12 +4,20 @@
 //! .venv directory management, selection state, sorting, and user interactions.
 
 use std::collections::HashSet;
-use std::path::PathBuf;
-use std::time::Instant;
-
-use crate::core::{VenvInfo, Result};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use chrono::Utc;
+
+use crate::core::{deletion_eta, export, peek, roots, shell, sorting, AgeBucket, ArchivedVenv, BatchResult, FileUtils, LogBuffer, Recommendation, ScanMetadata, SizeDeltas, SortOrder, VenvInfo, VenvPeek, Result};
+use super::glyphs::Glyphs;
+use super::theme::Theme;
+use super::toast::{Severity, Toast, Toasts};
+use super::tree::{DirTree, TreeNode};
 use super::{AppState, SortBy};
 
+/// Status shown when a browse-only session is asked to change something
+pub const READ_ONLY_NOTICE: &str = "🔒 Read-only session: nothing can be deleted, slimmed or restored (started with --read-only)";
+
 /// Main application state for the TUI mode
 pub struct TuiApp {
     /// Current application state
@@ -24,8 +32,8 @@ pub struct TuiApp {
     sort_by: SortBy,
     /// Reverse sort order
     reverse_sort: bool,
-    /// Current status message
-    status: String,
+    /// Status bar notifications
+    toasts: Toasts,
     /// Error message (if any)
     error_message: String,
     /// Last tick time for animations
@@ -44,10 +52,73 @@ pub struct TuiApp {
     scroll_offset: usize,
     /// Number of items visible in the list
     visible_items: usize,
+    /// Archived .venv directories (stub files) found by the last scan
+    archived: Vec<ArchivedVenv>,
+    /// Highlighted index in the archived list
+    archived_index: usize,
+    /// Whether the list is split into age bucket sections
+    group_by_age: bool,
+    /// Age bucket sections whose items are hidden
+    collapsed_buckets: HashSet<AgeBucket>,
+    /// The scanned hierarchy shown in the tree pane
+    tree: DirTree,
+    /// Highlighted directory in the tree pane
+    tree_index: usize,
+    /// Which panes are shown and which one has the keyboard
+    pane_focus: PaneFocus,
+    /// Contents of the selected .venv being peeked at before deletion
+    peek: Option<VenvPeek>,
+    /// Position of the peeked .venv among the selected ones
+    peek_index: usize,
+    /// Whether deleting the selected .venv directories' user data was confirmed
+    user_data_confirmed: bool,
+    /// Whether the deletion being confirmed moves the .venv directories to the trash
+    delete_to_trash: bool,
+    /// Files in the .venv directories of the deletion being confirmed
+    deletion_file_count: u64,
+    /// Change in size of each .venv since the previous scan
+    size_deltas: SizeDeltas,
+    /// Whether the list shows the change in size since the previous scan
+    show_size_delta: bool,
+    /// Whether this is a browse-only session (`--read-only`)
+    read_only: bool,
+    /// Whether the policy requires deletions to go to the trash, so it cannot be switched off
+    trash_locked: bool,
+    /// Whether deletions go to the trash unless switched in the dialog
+    trash_by_default: bool,
+    /// Where deleted folders go, as shown in the header
+    delete_mode: String,
+    /// Directories scanned, when there are several; the list is split into one section per root
+    roots: Vec<PathBuf>,
+    /// Recent log lines for the log pane
+    logs: LogBuffer,
+    /// Whether the log pane is shown
+    show_log: bool,
+    /// Recommendations worked out when the recommendations screen was last opened
+    recommendations: Vec<Recommendation>,
+    /// File name typed into the export prompt
+    export_path: String,
+    /// The last scan, described at the end of exports
+    scan_metadata: Option<ScanMetadata>,
+    /// Colors used for drawing
+    theme: Theme,
+    /// Markers used for drawing
+    glyphs: Glyphs,
+}
+
+/// Keyboard focus between the directory tree and the .venv list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaneFocus {
+    /// Only the .venv list is shown
+    List,
+    /// The tree pane is shown and navigated with the arrow keys
+    Tree,
+    /// The tree pane is shown, filtering the list, which has the keyboard
+    FilteredList,
 }
 
 /// Progress information for ongoing deletion operations
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct DeletionProgress {
     /// Total number of items to delete
     pub total: usize,
@@ -59,18 +130,8 @@ pub struct DeletionProgress {
     pub failed: usize,
     /// Results of deletion operations (path, success)
     pub results: Vec<(String, bool)>,
-}
-
-impl Default for DeletionProgress {
-    fn default() -> Self {
-        Self {
-            total: 0,
-            completed: 0,
-            successful: 0,
-            failed: 0,
-            results: Vec::new(),
-        }
-    }
+    /// Estimated time until the batch finishes, once measured
+    pub eta: Option<Duration>,
 }
 
 impl TuiApp {
@@ -83,7 +144,7 @@ impl TuiApp {
             selected_venvs: HashSet::new(),
             sort_by: SortBy::Path,
             reverse_sort: false,
-            status: "Initializing...".to_string(),
+            toasts: Toasts::default(),
             error_message: String::new(),
             last_tick: Instant::now(),
             loading_dots: 0,
@@ -93,6 +154,32 @@ impl TuiApp {
             show_details: false,
             scroll_offset: 0,
             visible_items: 20, // Default, will be updated based on terminal size
+            archived: Vec::new(),
+            archived_index: 0,
+            group_by_age: false,
+            collapsed_buckets: HashSet::new(),
+            tree: DirTree::default(),
+            tree_index: 0,
+            pane_focus: PaneFocus::List,
+            peek: None,
+            peek_index: 0,
+            user_data_confirmed: false,
+            delete_to_trash: false,
+            deletion_file_count: 0,
+            size_deltas: SizeDeltas::default(),
+            show_size_delta: false,
+            read_only: false,
+            trash_locked: false,
+            trash_by_default: false,
+            delete_mode: String::new(),
+            roots: Vec::new(),
+            logs: LogBuffer::default(),
+            show_log: false,
+            recommendations: Vec::new(),
+            export_path: String::new(),
+            scan_metadata: None,
+            theme: Theme::default(),
+            glyphs: Glyphs::default(),
         }
     }
 
@@ -106,6 +193,26 @@ impl TuiApp {
         self.state = state;
     }
 
+    /// Get the color theme
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Set the color theme
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    /// Get the list's markers
+    pub fn glyphs(&self) -> &Glyphs {
+        &self.glyphs
+    }
+
+    /// Set the list's markers
+    pub fn set_glyphs(&mut self, glyphs: Glyphs) {
+        self.glyphs = glyphs;
+    }
+
     /// Get the list of .venv directories
     pub fn venvs(&self) -> &[VenvInfo] {
         &self.venvs
@@ -114,10 +221,17 @@ impl TuiApp {
     /// Set the list of .venv directories
     pub fn set_venvs(&mut self, mut venvs: Vec<VenvInfo>) {
         self.sort_venvs(&mut venvs);
+
+        // Keep the highlighted directory across refreshes when it still has venvs
+        let highlighted = self.tree.nodes().get(self.tree_index).map(|node| node.path.clone());
+        self.tree = DirTree::build(&self.current_directory, &venvs);
+        self.tree_index = highlighted.and_then(|path| self.tree.position(&path)).unwrap_or(0);
+
         self.venvs = venvs;
         self.selected_index = 0;
         self.selected_venvs.clear();
         self.scroll_offset = 0;
+        self.snap_to_visible();
 
         // Update status with current count
         if self.venvs.is_empty() {
@@ -127,6 +241,278 @@ impl TuiApp {
         }
     }
 
+    /// Get the archived .venv directories
+    pub fn archived(&self) -> &[ArchivedVenv] {
+        &self.archived
+    }
+
+    /// Set the archived .venv directories, keeping the highlight in range
+    pub fn set_archived(&mut self, archived: Vec<ArchivedVenv>) {
+        self.archived = archived;
+        if self.archived_index >= self.archived.len() {
+            self.archived_index = 0;
+        }
+    }
+
+    /// Get the highlighted index in the archived list
+    pub fn archived_index(&self) -> usize {
+        self.archived_index
+    }
+
+    /// Get the highlighted archived .venv
+    pub fn selected_archived(&self) -> Option<&ArchivedVenv> {
+        self.archived.get(self.archived_index)
+    }
+
+    /// Highlight the next archived .venv, wrapping around
+    pub fn select_next_archived(&mut self) {
+        if !self.archived.is_empty() {
+            self.archived_index = (self.archived_index + 1) % self.archived.len();
+        }
+    }
+
+    /// Set where the log pane's lines come from
+    pub fn set_logs(&mut self, logs: LogBuffer) {
+        self.logs = logs;
+    }
+
+    /// Get the recent log lines
+    pub fn logs(&self) -> &LogBuffer {
+        &self.logs
+    }
+
+    /// Show or hide the log pane
+    pub fn toggle_log_pane(&mut self) {
+        self.show_log = !self.show_log;
+    }
+
+    /// Check whether the log pane is shown
+    pub fn is_log_shown(&self) -> bool {
+        self.show_log
+    }
+
+    /// Set the directories scanned; with more than one the list gets a section per root
+    pub fn set_roots(&mut self, roots: Vec<PathBuf>) {
+        self.roots = if roots.len() > 1 { roots } else { Vec::new() };
+    }
+
+    /// Set the scan the listed items came from, described at the end of exports
+    pub fn set_scan_metadata(&mut self, metadata: ScanMetadata) {
+        self.scan_metadata = Some(metadata);
+    }
+
+    /// Set the recommendations shown on the recommendations screen
+    pub fn set_recommendations(&mut self, recommendations: Vec<Recommendation>) {
+        self.recommendations = recommendations;
+    }
+
+    /// Get the recommendations shown on the recommendations screen
+    pub fn recommendations(&self) -> &[Recommendation] {
+        &self.recommendations
+    }
+
+    /// Check whether several directories were scanned
+    pub fn is_multi_root(&self) -> bool {
+        !self.roots.is_empty()
+    }
+
+    /// Get the scanned directories of a multi-root scan
+    pub fn root_paths(&self) -> Vec<&Path> {
+        self.roots.iter().map(PathBuf::as_path).collect()
+    }
+
+    /// Set the change in size of each .venv since the previous scan
+    pub fn set_size_deltas(&mut self, size_deltas: SizeDeltas) {
+        self.size_deltas = size_deltas;
+    }
+
+    /// Get the change in size since the previous scan, if the list shows it
+    pub fn shown_size_deltas(&self) -> Option<&SizeDeltas> {
+        self.show_size_delta.then_some(&self.size_deltas)
+    }
+
+    /// Show or hide the change in size since the previous scan
+    pub fn toggle_size_delta(&mut self) {
+        self.show_size_delta = !self.show_size_delta;
+    }
+
+    /// Check whether the list is grouped into age buckets
+    pub fn is_grouped_by_age(&self) -> bool {
+        self.group_by_age
+    }
+
+    /// Switch age bucket grouping on or off (all sections start expanded)
+    pub fn toggle_group_by_age(&mut self) {
+        self.group_by_age = !self.group_by_age;
+        self.collapsed_buckets.clear();
+        self.sort_current_venvs();
+    }
+
+    /// Check whether an age bucket section is collapsed
+    pub fn is_bucket_collapsed(&self, bucket: AgeBucket) -> bool {
+        self.collapsed_buckets.contains(&bucket)
+    }
+
+    /// Collapse or expand an age bucket section
+    ///
+    /// Moves the highlight out of a section that is being collapsed.
+    pub fn toggle_bucket(&mut self, bucket: AgeBucket) {
+        if !self.collapsed_buckets.remove(&bucket) {
+            self.collapsed_buckets.insert(bucket);
+        }
+        self.snap_to_visible();
+    }
+
+    /// Get the directory tree of the last scan
+    pub fn tree(&self) -> &DirTree {
+        &self.tree
+    }
+
+    /// Get the highlighted index in the tree pane
+    pub fn tree_index(&self) -> usize {
+        self.tree_index
+    }
+
+    /// Get the pane layout and keyboard focus
+    pub fn pane_focus(&self) -> PaneFocus {
+        self.pane_focus
+    }
+
+    /// Check whether the tree pane is shown
+    pub fn is_tree_shown(&self) -> bool {
+        self.pane_focus != PaneFocus::List
+    }
+
+    /// Switch between list only, tree focused and filtered list focused
+    ///
+    /// Hiding the tree pane shows all .venv directories again.
+    pub fn cycle_pane_focus(&mut self) {
+        self.pane_focus = match self.pane_focus {
+            PaneFocus::List => PaneFocus::Tree,
+            PaneFocus::Tree => PaneFocus::FilteredList,
+            PaneFocus::FilteredList => PaneFocus::List,
+        };
+        self.snap_to_visible();
+    }
+
+    /// Get the directory the list is restricted to, if the tree pane is shown
+    pub fn tree_filter(&self) -> Option<&TreeNode> {
+        if self.is_tree_shown() {
+            self.tree.nodes().get(self.tree_index)
+        } else {
+            None
+        }
+    }
+
+    /// Highlight a directory in the tree pane and restrict the list to it
+    ///
+    /// # Arguments
+    /// * `index` - Index of the directory, clamped to the tree
+    pub fn select_tree_node(&mut self, index: usize) {
+        if self.tree.is_empty() {
+            return;
+        }
+        self.tree_index = index.min(self.tree.len() - 1);
+        self.selected_index = 0;
+        self.scroll_offset = 0;
+        self.snap_to_visible();
+    }
+
+    /// Highlight the next directory in the tree pane
+    pub fn select_next_tree_node(&mut self) {
+        self.select_tree_node(self.tree_index + 1);
+    }
+
+    /// Highlight the previous directory in the tree pane
+    pub fn select_previous_tree_node(&mut self) {
+        self.select_tree_node(self.tree_index.saturating_sub(1));
+    }
+
+    /// Highlight the parent of the highlighted directory in the tree pane
+    pub fn select_parent_tree_node(&mut self) {
+        if let Some(parent) = self.tree.parent_of(self.tree_index) {
+            self.select_tree_node(parent);
+        }
+    }
+
+    /// Check whether an item is hidden inside a collapsed section or outside the tree filter
+    fn is_hidden(&self, index: usize) -> bool {
+        let Some(venv) = self.venvs.get(index) else {
+            return false;
+        };
+        let collapsed = self.group_by_age && self.collapsed_buckets.contains(&venv.age_bucket());
+        collapsed || !self.matches_tree_filter(venv)
+    }
+
+    /// Check whether a .venv is inside the directory highlighted in the tree pane
+    ///
+    /// Always true while the tree pane is hidden.
+    pub fn matches_tree_filter(&self, venv: &VenvInfo) -> bool {
+        self.tree_filter().map(|node| venv.path().starts_with(&node.path)).unwrap_or(true)
+    }
+
+    /// Get the indices of the items not filtered out by the tree pane
+    pub fn filtered_indices(&self) -> Vec<usize> {
+        (0..self.venvs.len()).filter(|&i| self.matches_tree_filter(&self.venvs[i])).collect()
+    }
+
+    /// Get the items shown in the list, in display order
+    ///
+    /// Items in collapsed sections or outside the tree filter are left out.
+    pub fn shown_venvs(&self) -> Vec<&VenvInfo> {
+        (0..self.venvs.len()).filter(|&i| !self.is_hidden(i)).map(|i| &self.venvs[i]).collect()
+    }
+
+    /// Open the export prompt, suggesting a CSV file in the current directory
+    pub fn start_export(&mut self) {
+        self.export_path = self.current_directory.join("venvs.csv").display().to_string();
+        self.state = AppState::Exporting;
+    }
+
+    /// Get the file name typed into the export prompt
+    pub fn export_path(&self) -> &str {
+        &self.export_path
+    }
+
+    /// Type a character into the export prompt
+    pub fn push_export_char(&mut self, c: char) {
+        self.export_path.push(c);
+    }
+
+    /// Remove the last character of the export prompt
+    pub fn pop_export_char(&mut self) {
+        self.export_path.pop();
+    }
+
+    /// Write the shown items to the file named in the export prompt
+    ///
+    /// A relative name is taken relative to the current directory.
+    ///
+    /// # Returns
+    /// The number of rows written, or an error for an unknown extension or a failed write
+    pub fn export_shown(&self) -> Result<usize> {
+        let shown = self.shown_venvs();
+        let metadata = self
+            .scan_metadata
+            .clone()
+            .unwrap_or_else(|| ScanMetadata::new(&[self.current_directory.as_path()], false, Utc::now()));
+        export::write_file(&self.current_directory.join(self.export_path.trim()), &shown, &metadata)?;
+        Ok(shown.len())
+    }
+
+    /// Move the highlight to the nearest visible item, preferring later ones
+    fn snap_to_visible(&mut self) {
+        if !self.is_hidden(self.selected_index) {
+            return;
+        }
+        let next = (self.selected_index..self.venvs.len()).find(|&i| !self.is_hidden(i));
+        let previous = (0..self.selected_index).rev().find(|&i| !self.is_hidden(i));
+        if let Some(index) = next.or(previous) {
+            self.selected_index = index;
+            self.adjust_scroll();
+        }
+    }
+
     /// Get the currently selected index
     pub fn selected_index(&self) -> usize {
         self.selected_index
@@ -137,6 +523,59 @@ impl TuiApp {
         self.venvs.get(self.selected_index)
     }
 
+    /// Replace a .venv's information in place, e.g. after sizing it again
+    ///
+    /// # Returns
+    /// Whether a .venv with the same path was in the list
+    pub fn replace_venv(&mut self, venv: VenvInfo) -> bool {
+        match self.venvs.iter_mut().find(|v| v.path() == venv.path()) {
+            Some(slot) => {
+                *slot = venv;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Drop a .venv that was removed outside VenvCleaner since the scan
+    ///
+    /// Later selections shift down with the list, and the tree and the
+    /// summary totals follow from what is left.
+    ///
+    /// # Arguments
+    /// * `path` - Path of the vanished .venv
+    ///
+    /// # Returns
+    /// Whether a .venv with that path was in the list
+    pub fn remove_vanished(&mut self, path: &Path) -> bool {
+        let Some(position) = self.venvs.iter().position(|v| v.path() == path) else {
+            return false;
+        };
+
+        self.venvs.remove(position);
+        self.selected_venvs = self
+            .selected_venvs
+            .iter()
+            .filter(|&&i| i != position)
+            .map(|&i| if i > position { i - 1 } else { i })
+            .collect();
+        if self.selected_index > position {
+            self.selected_index -= 1;
+        }
+        self.selected_index = self.selected_index.min(self.venvs.len().saturating_sub(1));
+
+        self.tree = DirTree::build(&self.current_directory, &self.venvs);
+        self.tree_index = self.tree_index.min(self.tree.nodes().len().saturating_sub(1));
+        self.snap_to_visible();
+        self.adjust_scroll();
+
+        self.notify(Severity::Warning, format!(
+            "⚠️ {} no longer exists (removed outside VenvCleaner) and was dropped from the list",
+            path.display()
+        ));
+        true
+    }
+
     /// Get the set of selected .venv indices
     pub fn selected_venvs(&self) -> &HashSet<usize> {
         &self.selected_venvs
@@ -151,6 +590,151 @@ impl TuiApp {
             .collect()
     }
 
+    /// Ask for confirmation before deleting the selected .venv directories
+    ///
+    /// # Arguments
+    /// * `use_trash` - Whether the deletion starts out moving them to the trash
+    pub fn request_deletion(&mut self, use_trash: bool) {
+        if self.refuse_if_read_only() {
+            return;
+        }
+        self.user_data_confirmed = false;
+        self.delete_to_trash = use_trash;
+        // Counted once here rather than on every redraw of the dialog
+        self.deletion_file_count = deletion_eta::file_count(&self.get_selected_venvs());
+        self.state = AppState::ConfirmingDeletion;
+    }
+
+    /// Get the number of files in the .venv directories of the deletion being confirmed
+    pub fn deletion_file_count(&self) -> u64 {
+        self.deletion_file_count
+    }
+
+    /// Make this a browse-only session, in which nothing can be deleted, slimmed or restored
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    /// Check whether this is a browse-only session
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Refuse a change to the file system in a browse-only session
+    ///
+    /// # Returns
+    /// True, after showing a notice, if the change must not happen
+    pub fn refuse_if_read_only(&mut self) -> bool {
+        if self.read_only {
+            self.notify(Severity::Warning, READ_ONLY_NOTICE.to_string());
+        }
+        self.read_only
+    }
+
+    /// Check whether the deletion being confirmed moves the .venv directories to the trash
+    pub fn deletes_to_trash(&self) -> bool {
+        self.delete_to_trash
+    }
+
+    /// Lock deletions to the trash, as the policy's `require_trash` does
+    pub fn set_trash_locked(&mut self, locked: bool) {
+        self.trash_locked = locked;
+    }
+
+    /// Check whether deletions are locked to the trash
+    pub fn is_trash_locked(&self) -> bool {
+        self.trash_locked
+    }
+
+    /// Set where deletions go unless switched in the dialog
+    ///
+    /// # Arguments
+    /// * `to_trash` - Whether they go to the trash
+    /// * `description` - The mode as shown in the header (see `VenvCleaner::delete_mode_description`)
+    pub fn set_delete_mode(&mut self, to_trash: bool, description: String) {
+        self.trash_by_default = to_trash;
+        self.delete_mode = description;
+    }
+
+    /// Check whether deletions go to the trash unless switched in the dialog
+    pub fn trashes_by_default(&self) -> bool {
+        self.trash_by_default
+    }
+
+    /// Get where deleted folders go, as shown in the header
+    pub fn delete_mode(&self) -> &str {
+        &self.delete_mode
+    }
+
+    /// Switch the deletion being confirmed between the trash and permanent deletion
+    pub fn toggle_delete_to_trash(&mut self) {
+        self.delete_to_trash = !self.delete_to_trash;
+    }
+
+    /// Get the selected .venv directories that contain user data
+    pub fn selected_with_user_data(&self) -> Vec<VenvInfo> {
+        self.get_selected_venvs().into_iter().filter(VenvInfo::has_user_data).collect()
+    }
+
+    /// Check whether deleting the selection still needs the user data confirmed
+    pub fn needs_user_data_confirmation(&self) -> bool {
+        !self.user_data_confirmed && self.get_selected_venvs().iter().any(VenvInfo::has_user_data)
+    }
+
+    /// Confirm that the user data in the selection may be deleted
+    pub fn confirm_user_data(&mut self) {
+        self.user_data_confirmed = true;
+    }
+
+    /// Get the paths of the selected .venv directories, sorted
+    fn selected_paths(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = self.get_selected_venvs().iter().map(|v| v.path().to_path_buf()).collect();
+        paths.sort_by(|a, b| sorting::compare_paths(a, b));
+        paths
+    }
+
+    /// Look inside the first selected .venv
+    pub fn open_peek(&mut self) -> Result<()> {
+        self.peek_index = 0;
+        self.load_peek()
+    }
+
+    /// Look inside the next (or previous) selected .venv, wrapping around
+    ///
+    /// # Arguments
+    /// * `forward` - Move to the next .venv rather than the previous one
+    pub fn cycle_peek(&mut self, forward: bool) -> Result<()> {
+        let count = self.selected_venvs.len();
+        if count == 0 {
+            return Ok(());
+        }
+        self.peek_index = if forward {
+            (self.peek_index + 1) % count
+        } else {
+            (self.peek_index + count - 1) % count
+        };
+        self.load_peek()
+    }
+
+    /// Read the contents of the .venv at the current peek position
+    fn load_peek(&mut self) -> Result<()> {
+        self.peek = None;
+        if let Some(path) = self.selected_paths().get(self.peek_index) {
+            self.peek = Some(VenvPeek::of(path, peek::DEFAULT_PACKAGE_LIMIT)?);
+        }
+        Ok(())
+    }
+
+    /// Get the contents of the peeked .venv
+    pub fn peek(&self) -> Option<&VenvPeek> {
+        self.peek.as_ref()
+    }
+
+    /// Get the 1-based position of the peeked .venv and the number selected
+    pub fn peek_position(&self) -> (usize, usize) {
+        (self.peek_index + 1, self.selected_venvs.len())
+    }
+
     /// Check if there are any selected items
     pub fn has_selected_items(&self) -> bool {
         !self.selected_venvs.is_empty()
@@ -161,14 +745,29 @@ impl TuiApp {
         self.sort_by
     }
 
-    /// Get the current status message
+    /// Get the message the status bar shows
     pub fn status(&self) -> &str {
-        &self.status
+        self.toasts.current().map(Toast::message).unwrap_or_default()
+    }
+
+    /// Get the severity of the message the status bar shows
+    pub fn status_severity(&self) -> Severity {
+        self.toasts.current().map_or(Severity::Info, Toast::severity)
     }
 
-    /// Set the status message
+    /// Show an informational message in the status bar
     pub fn set_status(&mut self, status: String) {
-        self.status = status;
+        self.notify(Severity::Info, status);
+    }
+
+    /// Show a message in the status bar; warnings and errors stay up over newer info
+    pub fn notify(&mut self, severity: Severity, message: String) {
+        self.toasts.push(severity, message);
+    }
+
+    /// Get the status bar notifications of this session
+    pub fn toasts(&self) -> &Toasts {
+        &self.toasts
     }
 
     /// Get the error message
@@ -207,6 +806,16 @@ impl TuiApp {
         &self.deletion_progress
     }
 
+    /// Update the progress of a running batch deletion
+    ///
+    /// # Arguments
+    /// * `completed` - Number of .venv directories finished
+    /// * `total` - Number of .venv directories in the batch
+    /// * `eta` - Estimated time until the batch finishes, if measured
+    pub fn set_deletion_progress(&mut self, completed: usize, total: usize, eta: Option<Duration>) {
+        self.deletion_progress = DeletionProgress { total, completed, eta, ..DeletionProgress::default() };
+    }
+
     /// Check if details should be shown
     pub fn show_details(&self) -> bool {
         self.show_details
@@ -234,16 +843,17 @@ impl TuiApp {
 
     /// Move selection to the next item
     pub fn select_next(&mut self) {
-        if !self.venvs.is_empty() {
-            self.selected_index = (self.selected_index + 1).min(self.venvs.len() - 1);
+        // Items in collapsed sections are skipped
+        if let Some(index) = (self.selected_index + 1..self.venvs.len()).find(|&i| !self.is_hidden(i)) {
+            self.selected_index = index;
             self.adjust_scroll();
         }
     }
 
     /// Move selection to the previous item
     pub fn select_previous(&mut self) {
-        if self.selected_index > 0 {
-            self.selected_index -= 1;
+        if let Some(index) = (0..self.selected_index).rev().find(|&i| !self.is_hidden(i)) {
+            self.selected_index = index;
             self.adjust_scroll();
         }
     }
@@ -252,6 +862,7 @@ impl TuiApp {
     pub fn select_first(&mut self) {
         self.selected_index = 0;
         self.scroll_offset = 0;
+        self.snap_to_visible();
     }
 
     /// Move selection to the last item
@@ -259,6 +870,7 @@ impl TuiApp {
         if !self.venvs.is_empty() {
             self.selected_index = self.venvs.len() - 1;
             self.adjust_scroll();
+            self.snap_to_visible();
         }
     }
 
@@ -267,6 +879,7 @@ impl TuiApp {
         let page_size = self.visible_items.saturating_sub(1);
         self.selected_index = self.selected_index.saturating_sub(page_size);
         self.adjust_scroll();
+        self.snap_to_visible();
     }
 
     /// Move selection down by a page
@@ -275,6 +888,7 @@ impl TuiApp {
             let page_size = self.visible_items.saturating_sub(1);
             self.selected_index = (self.selected_index + page_size).min(self.venvs.len() - 1);
             self.adjust_scroll();
+            self.snap_to_visible();
         }
     }
 
@@ -287,9 +901,9 @@ impl TuiApp {
         }
     }
 
-    /// Select all items
+    /// Select all items (only those in the highlighted directory while the tree pane is shown)
     pub fn select_all(&mut self) {
-        self.selected_venvs = (0..self.venvs.len()).collect();
+        self.selected_venvs = self.filtered_indices().into_iter().collect();
     }
 
     /// Deselect all items
@@ -321,43 +935,14 @@ impl TuiApp {
 
     /// Sort a list of venvs according to current settings
     fn sort_venvs(&self, venvs: &mut [VenvInfo]) {
-        match self.sort_by {
-            SortBy::Path => {
-                venvs.sort_by(|a, b| {
-                    if self.reverse_sort {
-                        b.path().cmp(a.path())
-                    } else {
-                        a.path().cmp(b.path())
-                    }
-                });
-            }
-            SortBy::Size => {
-                venvs.sort_by(|a, b| {
-                    if self.reverse_sort {
-                        a.size_bytes().cmp(&b.size_bytes())
-                    } else {
-                        b.size_bytes().cmp(&a.size_bytes())
-                    }
-                });
-            }
-            SortBy::Created => {
-                venvs.sort_by(|a, b| {
-                    if self.reverse_sort {
-                        a.created().cmp(b.created())
-                    } else {
-                        b.created().cmp(a.created())
-                    }
-                });
-            }
-            SortBy::LastModified => {
-                venvs.sort_by(|a, b| {
-                    if self.reverse_sort {
-                        a.last_modified().cmp(b.last_modified())
-                    } else {
-                        b.last_modified().cmp(a.last_modified())
-                    }
-                });
-            }
+        SortOrder::new(self.sort_by.key()).with_reverse(self.reverse_sort).sort(venvs);
+
+        // Sections follow bucket or root order; the stable sort keeps the order within each
+        if self.group_by_age {
+            venvs.sort_by_key(|v| v.age_bucket());
+        } else if self.is_multi_root() {
+            let roots = self.root_paths();
+            venvs.sort_by_key(|v| roots::root_index(&roots, v.path()));
         }
     }
 
@@ -372,6 +957,7 @@ impl TuiApp {
 
     /// Handle periodic tick for animations and updates
     pub fn tick(&mut self) {
+        self.logs.drain();
         let now = Instant::now();
         if now.duration_since(self.last_tick).as_millis() > 500 {
             self.last_tick = now;
@@ -380,23 +966,13 @@ impl TuiApp {
     }
 
     /// Handle deletion results
-    pub fn handle_deletion_results(&mut self, results: Vec<(VenvInfo, Result<()>)>) {
-        let mut successful = 0;
-        let mut failed = 0;
-        let mut simplified_results = Vec::new();
-
-        for (venv, result) in results {
-            match result {
-                Ok(_) => {
-                    successful += 1;
-                    simplified_results.push((venv.path().display().to_string(), true));
-                }
-                Err(_) => {
-                    failed += 1;
-                    simplified_results.push((venv.path().display().to_string(), false));
-                }
-            }
-        }
+    pub fn handle_deletion_results(&mut self, batch: &BatchResult) {
+        let (successful, failed, vanished) = (batch.deleted(), batch.failed(), batch.vanished());
+        // A .venv already gone is what was asked for
+        let simplified_results: Vec<_> = batch
+            .attempted()
+            .map(|item| (item.venv.path().display().to_string(), !item.is_failed()))
+            .collect();
 
         self.deletion_progress = DeletionProgress {
             total: simplified_results.len(),
@@ -404,13 +980,19 @@ impl TuiApp {
             successful,
             failed,
             results: simplified_results,
+            eta: None,
         };
 
         // Update status message with more detail
+        let already_gone = if vanished > 0 {
+            format!(" {} had already been removed elsewhere.", vanished)
+        } else {
+            String::new()
+        };
         if failed == 0 {
-            self.set_status(format!("✅ Successfully deleted {} directories. List will refresh automatically.", successful));
+            self.set_status(format!("✅ Successfully deleted {} directories.{} List will refresh automatically.", successful, already_gone));
         } else {
-            self.set_status(format!("⚠️ Deleted {} directories, {} failed. Check permissions for failed items.", successful, failed));
+            self.notify(Severity::Error, format!("⚠️ Deleted {} directories, {} failed.{} Check permissions for failed items.", successful, failed, already_gone));
         }
 
         // Clear selected items after deletion
@@ -424,6 +1006,7 @@ impl TuiApp {
     /// Open the folder containing the selected .venv
     pub fn open_folder(&self) -> Result<()> {
         if let Some(venv) = self.selected_venv() {
+            FileUtils::ensure_exists(venv.path())?;
             if let Some(parent) = venv.parent_path() {
                 // Try to open the folder using the system's default file manager
                 #[cfg(target_os = "macos")]
@@ -445,6 +1028,14 @@ impl TuiApp {
         Ok(())
     }
 
+    /// Open a new terminal with the selected .venv activated
+    pub fn open_shell(&self) -> Result<()> {
+        match self.selected_venv() {
+            Some(venv) => shell::open_activated_shell(venv.path()),
+            None => Ok(()),
+        }
+    }
+
     /// Get the visible range of items for the current scroll position
     pub fn visible_range(&self) -> (usize, usize) {
         let start = self.scroll_offset;
@@ -459,13 +1050,14 @@ impl TuiApp {
 
     /// Get summary statistics for the current .venv list
     pub fn get_summary_stats(&self) -> SummaryStats {
-        let total_size = self.venvs.iter().map(|v| v.size_bytes()).sum();
+        let total_size = self.venvs.iter().map(|v| v.size().bytes()).sum();
         let selected_size = self.selected_venvs
             .iter()
             .filter_map(|&i| self.venvs.get(i))
-            .map(|v| v.size_bytes())
+            .map(|v| v.size().bytes())
             .sum();
 
+        let total_inodes = self.venvs.iter().filter_map(|v| v.inode_count()).sum();
         let old_count = self.venvs.iter().filter(|v| v.is_old()).count();
         let recent_count = self.venvs.iter().filter(|v| v.is_recently_used()).count();
 
@@ -474,6 +1066,7 @@ impl TuiApp {
             selected_count: self.selected_venvs.len(),
             total_size,
             selected_size,
+            total_inodes,
             old_count,
             recent_count,
         }
@@ -487,6 +1080,7 @@ pub struct SummaryStats {
     pub selected_count: usize,
     pub total_size: u64,
     pub selected_size: u64,
+    pub total_inodes: u64,
     pub old_count: usize,
     pub recent_count: usize,
 }
@@ -500,14 +1094,15 @@ impl Default for TuiApp {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::ByteSize;
     use std::path::PathBuf;
-    use chrono::Local;
+    use chrono::Utc;
 
     fn create_test_venv(path: &str, size: u64) -> VenvInfo {
-        let now = Local::now();
+        let now = Utc::now();
         VenvInfo::new(
             PathBuf::from(path),
-            size,
+            ByteSize::new(size),
             now,
             now,
         )
@@ -579,6 +1174,287 @@ mod tests {
         assert_eq!(app.selected_venvs().len(), 2);
     }
 
+    #[test]
+    fn test_replace_venv_keeps_selection() {
+        let mut app = TuiApp::new();
+        app.set_venvs(vec![
+            create_test_venv("/path1/.venv", 100).with_size_partial(true),
+            create_test_venv("/path2/.venv", 200),
+        ]);
+        app.toggle_selected();
+
+        assert!(app.replace_venv(create_test_venv("/path1/.venv", 150)));
+        assert!(app.is_item_selected(0));
+        let venv = app.selected_venv().unwrap();
+        assert_eq!(venv.size(), ByteSize::new(150));
+        assert!(!venv.is_size_partial());
+        assert!(!app.replace_venv(create_test_venv("/elsewhere/.venv", 1)));
+    }
+
+    #[test]
+    fn test_size_delta_column_toggles() {
+        let mut app = TuiApp::new();
+        app.set_size_deltas(SizeDeltas::new([(PathBuf::from("/path1/.venv"), 2048)].into_iter().collect()));
+        assert!(app.shown_size_deltas().is_none());
+
+        app.toggle_size_delta();
+        let deltas = app.shown_size_deltas().unwrap();
+        assert_eq!(deltas.describe(Path::new("/path1/.venv")), "+2.00 KB");
+        assert!(deltas.is_top_growth(Path::new("/path1/.venv")));
+    }
+
+    #[test]
+    fn test_group_by_age_and_collapse() {
+        let old = Utc::now() - chrono::Duration::days(200);
+        let mut app = TuiApp::new();
+        app.set_venvs(vec![
+            VenvInfo::new(PathBuf::from("/a/.venv"), ByteSize::new(1), old, old),
+            create_test_venv("/b/.venv", 2),
+            create_test_venv("/c/.venv", 3),
+        ]);
+
+        app.toggle_group_by_age();
+        assert!(app.is_grouped_by_age());
+        let buckets: Vec<AgeBucket> = app.venvs().iter().map(|v| v.age_bucket()).collect();
+        assert_eq!(buckets, vec![AgeBucket::Recent, AgeBucket::Recent, AgeBucket::Abandoned]);
+
+        // Collapsing the highlighted section moves the highlight out of it
+        app.toggle_bucket(AgeBucket::Recent);
+        assert_eq!(app.selected_index(), 2);
+        app.select_previous();
+        assert_eq!(app.selected_index(), 2);
+
+        app.toggle_bucket(AgeBucket::Recent);
+        app.select_previous();
+        assert_eq!(app.selected_index(), 1);
+    }
+
+    #[test]
+    fn test_export_writes_shown_items_in_order() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let old = Utc::now() - chrono::Duration::days(200);
+        let mut app = TuiApp::new();
+        app.set_current_directory(temp_dir.path().to_path_buf(), true);
+        app.set_venvs(vec![
+            VenvInfo::new(PathBuf::from("/a/.venv"), ByteSize::new(1), old, old),
+            create_test_venv("/b/.venv", 2),
+        ]);
+        app.toggle_group_by_age();
+        app.toggle_bucket(AgeBucket::Abandoned);
+
+        app.start_export();
+        assert_eq!(app.state(), &AppState::Exporting);
+        assert!(app.export_path().ends_with("venvs.csv"));
+        for _ in 0.."csv".len() {
+            app.pop_export_char();
+        }
+        "json".chars().for_each(|c| app.push_export_char(c));
+
+        // The collapsed section is left out
+        assert_eq!(app.export_shown().unwrap(), 1);
+        let written = std::fs::read_to_string(temp_dir.path().join("venvs.json")).unwrap();
+        assert!(written.contains("/b/.venv") && !written.contains("/a/.venv"));
+    }
+
+    #[test]
+    fn test_tree_pane_filters_list() {
+        let mut app = TuiApp::new();
+        app.set_current_directory(PathBuf::from("/work"), true);
+        app.set_venvs(vec![
+            create_test_venv("/work/clientA/api/.venv", 100),
+            create_test_venv("/work/clientA/web/.venv", 50),
+            create_test_venv("/work/tools/.venv", 25),
+        ]);
+
+        // Hidden tree pane: no filter
+        assert_eq!(app.filtered_indices().len(), 3);
+
+        app.cycle_pane_focus();
+        assert_eq!(app.pane_focus(), PaneFocus::Tree);
+        app.select_next_tree_node();
+        assert_eq!(app.tree_filter().unwrap().name(), "clientA");
+        assert_eq!(app.filtered_indices(), vec![0, 1]);
+
+        // Navigation and select-all stay inside the subtree
+        app.cycle_pane_focus();
+        app.select_last();
+        assert_eq!(app.selected_index(), 1);
+        app.select_all();
+        assert_eq!(app.selected_venvs().len(), 2);
+
+        // Hiding the pane shows everything again
+        app.cycle_pane_focus();
+        assert!(!app.is_tree_shown());
+        assert_eq!(app.filtered_indices().len(), 3);
+    }
+
+    #[test]
+    fn test_archived_navigation() {
+        use crate::core::{ArchivedVenv, VenvArchiver};
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut archived = Vec::new();
+        for project in ["alpha", "beta"] {
+            let venv = temp_dir.path().join(project).join(".venv");
+            std::fs::create_dir_all(&venv).unwrap();
+            VenvArchiver::new(temp_dir.path().join("archives"))
+                .archive(&VenvInfo::new(venv.clone(), ByteSize::new(0), Utc::now(), Utc::now()))
+                .unwrap();
+            archived.push(ArchivedVenv::load(&temp_dir.path().join(project).join(".venv.archived")).unwrap());
+        }
+
+        let mut app = TuiApp::new();
+        assert!(app.selected_archived().is_none());
+        app.set_archived(archived);
+        assert_eq!(app.selected_archived().unwrap().location(), temp_dir.path().join("alpha").display().to_string());
+
+        app.select_next_archived();
+        assert_eq!(app.archived_index(), 1);
+        app.select_next_archived();
+        assert_eq!(app.archived_index(), 0);
+    }
+
+    #[test]
+    fn test_peek_cycles_through_selection() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut venvs = Vec::new();
+        for project in ["beta", "alpha"] {
+            let venv = temp_dir.path().join(project).join(".venv");
+            std::fs::create_dir_all(venv.join("bin")).unwrap();
+            venvs.push(VenvInfo::new(venv, ByteSize::new(0), Utc::now(), Utc::now()));
+        }
+
+        let mut app = TuiApp::new();
+        app.set_venvs(venvs);
+        app.select_all();
+        app.open_peek().unwrap();
+        assert_eq!(app.peek_position(), (1, 2));
+        assert!(app.peek().unwrap().path.starts_with(temp_dir.path().join("alpha")));
+
+        app.cycle_peek(true).unwrap();
+        assert!(app.peek().unwrap().path.starts_with(temp_dir.path().join("beta")));
+        app.cycle_peek(true).unwrap();
+        assert_eq!(app.peek_position(), (1, 2));
+        app.cycle_peek(false).unwrap();
+        assert_eq!(app.peek_position(), (2, 2));
+    }
+
+    #[test]
+    fn test_user_data_needs_second_confirmation() {
+        use crate::core::UserDataFile;
+        let data = vec![UserDataFile { path: PathBuf::from("/b/.venv/data.csv"), size_bytes: 5 }];
+
+        let mut app = TuiApp::new();
+        app.set_venvs(vec![create_test_venv("/a/.venv", 1), create_test_venv("/b/.venv", 2).with_user_data(data)]);
+        app.toggle_selected();
+        app.request_deletion(false);
+        assert!(!app.needs_user_data_confirmation());
+
+        app.select_all();
+        app.request_deletion(false);
+        assert_eq!(app.state(), &AppState::ConfirmingDeletion);
+        assert_eq!(app.selected_with_user_data().len(), 1);
+        assert!(app.needs_user_data_confirmation());
+        app.confirm_user_data();
+        assert!(!app.needs_user_data_confirmation());
+
+        // A new request asks again
+        app.request_deletion(false);
+        assert!(app.needs_user_data_confirmation());
+    }
+
+    #[test]
+    fn test_trash_toggle_is_per_deletion() {
+        let mut app = TuiApp::new();
+        app.set_venvs(vec![create_test_venv("/a/.venv", 1)]);
+        app.toggle_selected();
+
+        app.request_deletion(true);
+        assert!(app.deletes_to_trash());
+        app.toggle_delete_to_trash();
+        assert!(!app.deletes_to_trash());
+
+        // The next deletion starts from the configured setting again
+        app.request_deletion(true);
+        assert!(app.deletes_to_trash());
+
+        app.set_delete_mode(false, "Permanent delete: this system has no trash".to_string());
+        assert!(!app.trashes_by_default());
+        assert_eq!(app.delete_mode(), "Permanent delete: this system has no trash");
+    }
+
+    #[test]
+    fn test_read_only_session_refuses_deletion() {
+        let mut app = TuiApp::new();
+        app.set_venvs(vec![create_test_venv("/a/.venv", 1)]);
+        app.set_read_only(true);
+        app.toggle_selected();
+
+        app.request_deletion(false);
+        assert_ne!(app.state(), &AppState::ConfirmingDeletion);
+        assert_eq!(app.status(), READ_ONLY_NOTICE);
+        // A later info message does not hide the warning
+        app.set_status("Sorted by size".to_string());
+        assert_eq!(app.status(), READ_ONLY_NOTICE);
+        assert_eq!(app.status_severity(), Severity::Warning);
+        assert_eq!(app.toasts().history().next().unwrap().message(), "Sorted by size");
+
+        app.set_read_only(false);
+        app.request_deletion(false);
+        assert_eq!(app.state(), &AppState::ConfirmingDeletion);
+    }
+
+    #[test]
+    fn test_deletion_counts_files_once() {
+        let mut app = TuiApp::new();
+        app.set_venvs(vec![create_test_venv("/a/.venv", 1).with_item_counts(Some((120, 4))), create_test_venv("/b/.venv", 2).with_item_counts(Some((30, 2)))]);
+        app.select_all();
+
+        app.request_deletion(false);
+        assert_eq!(app.deletion_file_count(), 150);
+    }
+
+    #[test]
+    fn test_multi_root_sections() {
+        let mut app = TuiApp::new();
+        app.set_roots(vec![PathBuf::from("/work")]);
+        assert!(!app.is_multi_root());
+
+        app.set_roots(vec![PathBuf::from("/work"), PathBuf::from("/src")]);
+        assert!(app.is_multi_root());
+        app.set_venvs(vec![
+            create_test_venv("/src/a/.venv", 1),
+            create_test_venv("/work/b/.venv", 2),
+            create_test_venv("/work/c/.venv", 3),
+        ]);
+
+        // Sorted by path within each root, roots in the order given
+        let paths: Vec<&str> = app.venvs().iter().map(|v| v.path().to_str().unwrap()).collect();
+        assert_eq!(paths, vec!["/work/b/.venv", "/work/c/.venv", "/src/a/.venv"]);
+    }
+
+    #[test]
+    fn test_remove_vanished() {
+        let mut app = TuiApp::new();
+        app.set_venvs(vec![
+            create_test_venv("/path1/.venv", 100),
+            create_test_venv("/path2/.venv", 200),
+            create_test_venv("/path3/.venv", 300),
+        ]);
+        app.toggle_selected();
+        app.select_last();
+        app.toggle_selected();
+
+        assert!(app.remove_vanished(Path::new("/path1/.venv")));
+        assert_eq!(app.venvs().len(), 2);
+        assert_eq!(app.selected_venv().unwrap().path(), Path::new("/path3/.venv"));
+        assert!(app.is_item_selected(1));
+        let stats = app.get_summary_stats();
+        assert_eq!((stats.total_size, stats.selected_size), (500, 300));
+        assert!(app.status().contains("no longer exists"));
+
+        assert!(!app.remove_vanished(Path::new("/path1/.venv")));
+    }
+
     #[test]
     fn test_sorting() {
         let mut app = TuiApp::new();
@@ -587,6 +1463,9 @@ mod tests {
         app.cycle_sort();
         assert_eq!(app.sort_by(), SortBy::Size);
 
+        app.cycle_sort();
+        assert_eq!(app.sort_by(), SortBy::Files);
+
         app.cycle_sort();
         assert_eq!(app.sort_by(), SortBy::Created);
 
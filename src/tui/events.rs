@@ -8,9 +8,9 @@ use std::sync::mpsc::{self, Receiver, Sender};
 use std::thread;
 use std::time::{Duration, Instant};
 use crossterm::event::{self, Event, KeyEvent};
-use log::{debug, warn};
+use tracing::{debug, warn};
 
-use crate::core::{VenvInfo, VenvCleanerError, Result};
+use crate::core::{BatchOptions, BatchResult, VenvInfo, VenvCleanerError, Result};
 
 /// Events that can occur in the TUI application
 #[derive(Debug)]
@@ -24,7 +24,7 @@ pub enum AppEvent {
     /// Error occurred while loading .venv directories
     LoadError(String),
     /// Deletion operation completed
-    DeletionComplete(Vec<(VenvInfo, Result<()>)>),
+    DeletionComplete(BatchResult),
 }
 
 /// Event handler for the TUI application
@@ -58,10 +58,8 @@ impl EventHandler {
                     Ok(true) => {
                         if let Ok(event) = event::read() {
                             match event {
-                                Event::Key(key) => {
-                                    if input_sender.send(AppEvent::Input(key)).is_err() {
-                                        break; // Receiver dropped, exit thread
-                                    }
+                                Event::Key(key) if input_sender.send(AppEvent::Input(key)).is_err() => {
+                                    break; // Receiver dropped, exit thread
                                 }
                                 Event::Resize(_, _) => {
                                     // Handle resize events if needed
@@ -97,7 +95,7 @@ impl EventHandler {
     ///
     /// # Returns
     /// The next AppEvent or an error
-    pub fn next(&mut self) -> Result<AppEvent> {
+    pub fn next_event(&mut self) -> Result<AppEvent> {
         // Check if it's time for a tick event
         let now = Instant::now();
         if now.duration_since(self.last_tick) >= self.tick_interval {
@@ -166,7 +164,7 @@ impl EventHandler {
                 }
                 Err(e) => {
                     warn!("Error loading .venv directories: {}", e);
-                    if sender.send(AppEvent::LoadError(e.to_string())).is_err() {
+                    if sender.send(AppEvent::LoadError(crate::core::platform::describe_error(&e))).is_err() {
                         warn!("Failed to send LoadError event");
                     }
                 }
@@ -189,15 +187,10 @@ impl EventHandler {
         thread::spawn(move || {
             debug!("Starting deletion task for {} directories", venvs.len());
 
-            let mut results = Vec::new();
-
-            for venv in venvs {
-                let result = cleaner.delete_venv_directory(&venv);
-                results.push((venv, result));
-            }
+            let batch = cleaner.delete_many(&venvs, &BatchOptions::new(), |_| {});
 
             debug!("Deletion task completed");
-            if sender.send(AppEvent::DeletionComplete(results)).is_err() {
+            if sender.send(AppEvent::DeletionComplete(batch)).is_err() {
                 warn!("Failed to send DeletionComplete event");
             }
         });
@@ -246,6 +239,38 @@ pub enum Shortcut {
     Sort,
     /// Open folder
     OpenFolder,
+    /// Open a terminal with the .venv activated
+    OpenShell,
+    /// Open the project in the configured editor
+    OpenEditor,
+    /// Export the shown list to a report file
+    Export,
+    /// Slim the selected .venv directories in place
+    Slim,
+    /// Show, focus or hide the directory tree pane
+    ToggleTree,
+    /// Size the highlighted .venv again without the sizing timeout
+    RetrySize,
+    /// Highlight the next archived .venv
+    NextArchived,
+    /// Restore the highlighted archived .venv
+    RestoreArchived,
+    /// Delete the highlighted archived .venv's archive
+    DeleteArchived,
+    /// Toggle grouping the list into age bucket sections
+    GroupByAge,
+    /// Collapse or expand an age bucket section
+    ToggleSection,
+    /// Show or hide the log pane
+    ToggleLog,
+    /// Show cleanup recommendations for the scan
+    Recommendations,
+    /// Look inside the directories about to be deleted
+    Peek,
+    /// Peek at the next or previous directory
+    SwitchPeek,
+    /// Switch the deletion between the trash and permanent deletion
+    ToggleTrash,
     /// Confirm action
     Confirm,
     /// Cancel action
@@ -302,6 +327,54 @@ impl KeyboardShortcuts for Shortcut {
             Shortcut::OpenFolder => {
                 matches!(key.code, KeyCode::Char('o'))
             }
+            Shortcut::OpenShell => {
+                matches!(key.code, KeyCode::Char('t'))
+            }
+            Shortcut::OpenEditor => {
+                matches!(key.code, KeyCode::Char('e'))
+            }
+            Shortcut::Export => {
+                matches!(key.code, KeyCode::Char('E'))
+            }
+            Shortcut::Slim => {
+                matches!(key.code, KeyCode::Char('m'))
+            }
+            Shortcut::ToggleTree => {
+                matches!(key.code, KeyCode::Tab)
+            }
+            Shortcut::RetrySize => {
+                matches!(key.code, KeyCode::Char('z'))
+            }
+            Shortcut::NextArchived => {
+                matches!(key.code, KeyCode::BackTab)
+            }
+            Shortcut::RestoreArchived => {
+                matches!(key.code, KeyCode::Char('u'))
+            }
+            Shortcut::DeleteArchived => {
+                matches!(key.code, KeyCode::Char('X'))
+            }
+            Shortcut::GroupByAge => {
+                matches!(key.code, KeyCode::Char('g'))
+            }
+            Shortcut::ToggleSection => {
+                matches!(key.code, KeyCode::Char('1'..='4'))
+            }
+            Shortcut::ToggleLog => {
+                matches!(key.code, KeyCode::Char('L'))
+            }
+            Shortcut::Recommendations => {
+                matches!(key.code, KeyCode::Char('i'))
+            }
+            Shortcut::Peek => {
+                matches!(key.code, KeyCode::Char('p'))
+            }
+            Shortcut::SwitchPeek => {
+                matches!(key.code, KeyCode::Left | KeyCode::Right)
+            }
+            Shortcut::ToggleTrash => {
+                matches!(key.code, KeyCode::Char('t'))
+            }
             Shortcut::Confirm => {
                 matches!(key.code, KeyCode::Char('y') | KeyCode::Enter)
             }
@@ -328,6 +401,22 @@ impl KeyboardShortcuts for Shortcut {
             Shortcut::Delete => "Del/x".to_string(),
             Shortcut::Sort => "s".to_string(),
             Shortcut::OpenFolder => "o".to_string(),
+            Shortcut::OpenShell => "t".to_string(),
+            Shortcut::OpenEditor => "e".to_string(),
+            Shortcut::Export => "E".to_string(),
+            Shortcut::Slim => "m".to_string(),
+            Shortcut::ToggleTree => "Tab".to_string(),
+            Shortcut::RetrySize => "z".to_string(),
+            Shortcut::NextArchived => "Shift+Tab".to_string(),
+            Shortcut::RestoreArchived => "u".to_string(),
+            Shortcut::DeleteArchived => "X".to_string(),
+            Shortcut::GroupByAge => "g".to_string(),
+            Shortcut::ToggleSection => "1-4".to_string(),
+            Shortcut::ToggleLog => "L".to_string(),
+            Shortcut::Recommendations => "i".to_string(),
+            Shortcut::Peek => "p".to_string(),
+            Shortcut::SwitchPeek => "←/→".to_string(),
+            Shortcut::ToggleTrash => "t".to_string(),
             Shortcut::Confirm => "y/Enter".to_string(),
             Shortcut::Cancel => "n/Esc".to_string(),
         }
@@ -358,10 +447,34 @@ pub fn get_shortcuts_for_state(state: &crate::tui::AppState) -> Vec<Shortcut> {
             Shortcut::Delete,
             Shortcut::Sort,
             Shortcut::OpenFolder,
+            Shortcut::OpenShell,
+            Shortcut::OpenEditor,
+            Shortcut::Export,
+            Shortcut::Slim,
+            Shortcut::ToggleTree,
+            Shortcut::RetrySize,
+            Shortcut::NextArchived,
+            Shortcut::RestoreArchived,
+            Shortcut::DeleteArchived,
+            Shortcut::GroupByAge,
+            Shortcut::ToggleSection,
+            Shortcut::ToggleLog,
+            Shortcut::Recommendations,
         ],
         AppState::ConfirmingDeletion => vec![
             Shortcut::Confirm,
             Shortcut::Cancel,
+            Shortcut::Peek,
+            Shortcut::ToggleTrash,
+        ],
+        AppState::PeekingDeletion => vec![
+            Shortcut::Confirm,
+            Shortcut::SwitchPeek,
+            Shortcut::Cancel, // Back to the confirmation
+        ],
+        AppState::ConfirmingArchiveDeletion => vec![
+            Shortcut::Confirm,
+            Shortcut::Cancel,
         ],
         AppState::Deleting => vec![
             Shortcut::Quit, // Force quit only
@@ -370,7 +483,11 @@ pub fn get_shortcuts_for_state(state: &crate::tui::AppState) -> Vec<Shortcut> {
             Shortcut::Quit,
             Shortcut::Cancel, // Return to browsing
         ],
-        AppState::Help => vec![
+        AppState::Exporting => vec![
+            Shortcut::Confirm,
+            Shortcut::Cancel,
+        ],
+        AppState::Help | AppState::Recommendations | AppState::Notifications => vec![
             // Any key returns to browsing
         ],
         AppState::Quit => vec![],
@@ -415,6 +532,13 @@ mod tests {
         assert!(browsing_shortcuts.contains(&Shortcut::Quit));
         assert!(browsing_shortcuts.contains(&Shortcut::Help));
         assert!(browsing_shortcuts.contains(&Shortcut::Delete));
+
+        let confirming_shortcuts = get_shortcuts_for_state(&crate::tui::AppState::ConfirmingDeletion);
+        assert!(confirming_shortcuts.contains(&Shortcut::Peek));
+        assert!(confirming_shortcuts.contains(&Shortcut::ToggleTrash));
+        let peeking_shortcuts = get_shortcuts_for_state(&crate::tui::AppState::PeekingDeletion);
+        assert!(peeking_shortcuts.contains(&Shortcut::Confirm));
+        assert!(peeking_shortcuts.contains(&Shortcut::SwitchPeek));
     }
 
     #[test]
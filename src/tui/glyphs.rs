@@ -0,0 +1,168 @@
+//! Markers drawn in the TUI's .venv list and details
+//!
+//! The list marks each .venv's age with 🟢🟡🔴 and selected rows with ✓.
+//! Terminals on the Linux console, old terminal types or a locale without
+//! UTF-8 (common over SSH from older systems) draw those as tofu boxes, so
+//! the markers fall back to plain ASCII there, coloured by the theme. The
+//! `glyphs` key of the `[tui]` table forces either set.
+
+use std::env;
+
+use crate::core::{Result, VenvCleanerError};
+
+/// Terminal types that cannot draw emoji
+const ASCII_TERMS: &[&str] = &["linux", "dumb", "vt100", "vt102", "vt220", "cons25", "ansi"];
+
+/// The markers the TUI draws
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Glyphs {
+    /// Used within the last 30 days
+    pub recent: &'static str,
+    /// Used 30 to 90 days ago
+    pub moderate: &'static str,
+    /// Not used for more than 90 days
+    pub old: &'static str,
+    /// Old, but the project has recent commits
+    pub active_project: &'static str,
+    /// Selected for deletion
+    pub marked: &'static str,
+    /// Holds user data
+    pub user_data: &'static str,
+    /// Holds an editable install of its project
+    pub editable: &'static str,
+    /// Whether the age markers need the theme's colors to tell them apart
+    pub colored: bool,
+}
+
+impl Glyphs {
+    /// Emoji markers, for terminals and fonts that draw them
+    pub const EMOJI: Glyphs = Glyphs {
+        recent: "🟢 ",
+        moderate: "🟡 ",
+        old: "🔴 ",
+        active_project: "🌱 ",
+        marked: "✓ ",
+        user_data: "◆ ",
+        editable: "✎ ",
+        colored: false,
+    };
+
+    /// Plain ASCII markers, coloured by the theme
+    pub const ASCII: Glyphs = Glyphs {
+        recent: "+ ",
+        moderate: "~ ",
+        old: "! ",
+        active_project: "^ ",
+        marked: "* ",
+        user_data: "# ",
+        editable: "e ",
+        colored: true,
+    };
+
+    /// Values accepted by `glyphs` in the config file
+    pub const NAMES: [&'static str; 3] = ["auto", "emoji", "ascii"];
+
+    /// Pick the markers set by the `[tui]` table, detecting the terminal for `auto`
+    ///
+    /// # Arguments
+    /// * `name` - The `glyphs` setting, None for `auto`
+    ///
+    /// # Returns
+    /// The markers, or an error naming an unknown setting
+    pub fn from_config(name: Option<&str>) -> Result<Self> {
+        match name.unwrap_or("auto") {
+            "auto" => Ok(Self::detect()),
+            "emoji" => Ok(Self::EMOJI),
+            "ascii" => Ok(Self::ASCII),
+            other => Err(VenvCleanerError::InvalidArgument(format!(
+                "Unknown TUI glyphs '{}' (expected one of: {})",
+                other,
+                Self::NAMES.join(", ")
+            ))),
+        }
+    }
+
+    /// Get this set's marker for an icon of the emoji set, as listed on the help screen
+    ///
+    /// # Arguments
+    /// * `emoji` - The icon without its trailing space, e.g. `🟢`
+    pub fn icon<'a>(&self, emoji: &'a str) -> &'a str {
+        let pairs = [
+            (Self::EMOJI.recent, self.recent),
+            (Self::EMOJI.moderate, self.moderate),
+            (Self::EMOJI.old, self.old),
+            (Self::EMOJI.active_project, self.active_project),
+            (Self::EMOJI.marked, self.marked),
+            (Self::EMOJI.user_data, self.user_data),
+            (Self::EMOJI.editable, self.editable),
+        ];
+        pairs
+            .iter()
+            .find(|(from, _)| from.trim_end() == emoji)
+            .map_or(emoji, |(_, to)| to.trim_end())
+    }
+
+    /// Pick the markers the current terminal can draw
+    pub fn detect() -> Self {
+        let term = env::var("TERM").ok();
+        // The first locale variable set decides, as in the C library
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty());
+        if supports_emoji(term.as_deref(), locale.as_deref()) {
+            Self::EMOJI
+        } else {
+            Self::ASCII
+        }
+    }
+}
+
+impl Default for Glyphs {
+    fn default() -> Self {
+        Self::EMOJI
+    }
+}
+
+/// Check whether a terminal can draw emoji, from its type and locale
+///
+/// Windows consoles set neither variable and get emoji.
+///
+/// # Arguments
+/// * `term` - `$TERM`
+/// * `locale` - The effective locale (`$LC_ALL`, `$LC_CTYPE` or `$LANG`)
+pub fn supports_emoji(term: Option<&str>, locale: Option<&str>) -> bool {
+    if term.is_some_and(|term| ASCII_TERMS.contains(&term)) {
+        return false;
+    }
+    match locale {
+        Some(locale) => {
+            let locale = locale.to_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        }
+        None => cfg!(windows),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supports_emoji() {
+        assert!(supports_emoji(Some("xterm-256color"), Some("en_GB.UTF-8")));
+        assert!(supports_emoji(Some("tmux-256color"), Some("C.utf8")));
+        assert!(!supports_emoji(Some("linux"), Some("en_US.UTF-8")));
+        assert!(!supports_emoji(Some("xterm"), Some("POSIX")));
+        assert!(!supports_emoji(Some("xterm"), Some("en_US.ISO-8859-1")));
+    }
+
+    #[test]
+    fn test_from_config() {
+        assert_eq!(Glyphs::from_config(Some("ascii")).unwrap(), Glyphs::ASCII);
+        assert_eq!(Glyphs::from_config(Some("emoji")).unwrap(), Glyphs::EMOJI);
+        assert!(Glyphs::from_config(Some("unicode")).is_err());
+        assert_eq!(Glyphs::ASCII.icon("🔴"), "!");
+        assert_eq!(Glyphs::EMOJI.icon("🔴"), "🔴");
+    }
+}
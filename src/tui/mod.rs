@@ -5,8 +5,15 @@
 //! sorting options, and interactive deletion capabilities.
 
 use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Once};
+use std::thread;
 use std::time::Duration;
+use chrono::Utc;
 use crossterm::{
+    cursor::Show,
     event::{DisableMouseCapture, EnableMouseCapture, KeyEvent, KeyCode, KeyModifiers},
     execute,
     terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
@@ -15,16 +22,51 @@ use ratatui::{
     backend::CrosstermBackend,
     Terminal,
 };
-use log::info;
+use tracing::{info, warn};
 
-use crate::core::{VenvCleaner, Result};
+use crate::core::{deletion_eta, editor, platform, recommendations, size_deltas, ActivityCache, AgeBucket, BatchOptions, ByteSize, CancellationToken, DeleteHooks, DeleteMode, DeletionEstimator, Detector, FsBackend, IncludeSet, LogBuffer, LogLine, OfflineIndex, OwnerFilter, PolicyConfig, ScanMetadata, SortKey, VenvCleaner, VenvCleanerError, VenvFilter, VenvInfo, Result};
 
 pub mod app;
 pub mod ui;
 pub mod events;
+pub mod glyphs;
+pub mod osc;
+pub mod theme;
+pub mod toast;
+pub mod tree;
 
-pub use app::TuiApp;
+pub use app::{PaneFocus, TuiApp};
 pub use events::{AppEvent, EventHandler};
+pub use glyphs::Glyphs;
+pub use osc::{TaskbarProgress, TerminalProgress};
+pub use theme::Theme;
+pub use toast::{Severity, Toast, Toasts};
+
+/// Whether the terminal is currently in raw mode on the alternate screen
+static TERMINAL_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Restore the terminal before a panic message is printed
+///
+/// A panic inside the draw closure unwinds through ratatui while the terminal
+/// is still in raw mode on the alternate screen, so the message would be
+/// garbled and then wiped when `Drop` leaves the screen. The hook restores the
+/// terminal first and then runs the previous hook, which prints the message
+/// on the normal screen. It is installed once and does nothing when the TUI
+/// is not active.
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if TERMINAL_ACTIVE.swap(false, Ordering::SeqCst) {
+                let _ = terminal::disable_raw_mode();
+                let _ = execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture, Show);
+                eprintln!("VenvCleaner TUI crashed; the terminal has been restored.");
+            }
+            previous(info);
+        }));
+    });
+}
 
 /// Main TUI mode handler for VenvCleaner
 pub struct TuiMode {
@@ -36,6 +78,14 @@ pub struct TuiMode {
     app: TuiApp,
     /// Event handler for user input
     event_handler: EventHandler,
+    /// Editor command template from the config file
+    editor: Option<String>,
+    /// Terminal title and taskbar progress reporting
+    progress: TerminalProgress,
+    /// Whether the next scan's results start selected (`--older-than`/`--min-size` at startup)
+    preselect: bool,
+    /// Cache of project last commits, looked up as .venv directories are highlighted
+    activity: ActivityCache,
 }
 
 /// Application states for the TUI
@@ -47,12 +97,22 @@ pub enum AppState {
     Browsing,
     /// Confirming deletion of selected directories
     ConfirmingDeletion,
+    /// Peeking inside the selected directories before confirming their deletion
+    PeekingDeletion,
+    /// Confirming deletion of the highlighted archived .venv's archive
+    ConfirmingArchiveDeletion,
     /// Deleting directories
     Deleting,
     /// Showing error message
     Error,
     /// Showing help screen
     Help,
+    /// Showing cleanup recommendations for the scan
+    Recommendations,
+    /// Typing the file to export the shown list to
+    Exporting,
+    /// Showing the status bar notifications of this session
+    Notifications,
     /// Application should quit
     Quit,
 }
@@ -64,6 +124,8 @@ pub enum SortBy {
     Path,
     /// Sort by size (largest first)
     Size,
+    /// Sort by file count (most files first)
+    Files,
     /// Sort by creation date (newest first)
     Created,
     /// Sort by last modified date (most recent first)
@@ -75,7 +137,8 @@ impl SortBy {
     pub fn next(self) -> Self {
         match self {
             SortBy::Path => SortBy::Size,
-            SortBy::Size => SortBy::Created,
+            SortBy::Size => SortBy::Files,
+            SortBy::Files => SortBy::Created,
             SortBy::Created => SortBy::LastModified,
             SortBy::LastModified => SortBy::Path,
         }
@@ -86,7 +149,8 @@ impl SortBy {
         match self {
             SortBy::Path => SortBy::LastModified,
             SortBy::Size => SortBy::Path,
-            SortBy::Created => SortBy::Size,
+            SortBy::Files => SortBy::Size,
+            SortBy::Created => SortBy::Files,
             SortBy::LastModified => SortBy::Created,
         }
     }
@@ -96,10 +160,22 @@ impl SortBy {
         match self {
             SortBy::Path => "Path",
             SortBy::Size => "Size",
+            SortBy::Files => "Files",
             SortBy::Created => "Created",
             SortBy::LastModified => "Last Used",
         }
     }
+
+    /// Get the shared sort key this option sorts by
+    pub fn key(self) -> SortKey {
+        match self {
+            SortBy::Path => SortKey::Path,
+            SortBy::Size => SortKey::Size,
+            SortBy::Files => SortKey::Files,
+            SortBy::Created => SortKey::Created,
+            SortBy::LastModified => SortKey::LastModified,
+        }
+    }
 }
 
 impl TuiMode {
@@ -110,7 +186,9 @@ impl TuiMode {
         verbosity: u8,
     ) -> Result<Self> {
         // Setup terminal
+        install_panic_hook();
         terminal::enable_raw_mode()?;
+        TERMINAL_ACTIVE.store(true, Ordering::SeqCst);
         let mut stdout = io::stdout();
         execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
         let backend = CrosstermBackend::new(stdout);
@@ -128,6 +206,7 @@ impl TuiMode {
         // Create application state
         let mut app = TuiApp::new();
         app.set_current_directory(base_directory, recursive);
+        app.set_delete_mode(cleaner.is_using_trash(), cleaner.delete_mode_description());
 
         // Create event handler
         let event_handler = EventHandler::new(Duration::from_millis(250))?;
@@ -137,14 +216,156 @@ impl TuiMode {
             terminal,
             app,
             event_handler,
+            editor: None,
+            progress: TerminalProgress::new(true),
+            preselect: false,
+            activity: ActivityCache::load_default(),
         })
     }
 
+    /// Browse without any way to delete, slim or restore, e.g. on machines where changes need approval
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.app.set_read_only(read_only);
+        self
+    }
+
+    /// Choose whether deleted directories go to the system trash (see `DeleteMode`)
+    pub fn with_delete_mode(mut self, mode: DeleteMode) -> Self {
+        self.cleaner = self.cleaner.clone().with_delete_mode(mode);
+        self.app.set_delete_mode(self.cleaner.is_using_trash(), self.cleaner.delete_mode_description());
+        self
+    }
+
+    /// Apply the deletion restrictions of the system policy and user config
+    pub fn with_policy(mut self, policy: PolicyConfig) -> Self {
+        self.app.set_trash_locked(policy.require_trash);
+        self.cleaner = self.cleaner.clone().with_policy(policy);
+        self.app.set_delete_mode(self.cleaner.is_using_trash(), self.cleaner.delete_mode_description());
+        self
+    }
+
+    /// Run these commands before and after each deletion
+    pub fn with_delete_hooks(mut self, delete_hooks: DeleteHooks) -> Self {
+        self.cleaner = self.cleaner.clone().with_delete_hooks(delete_hooks);
+        self
+    }
+
+    /// Archive deleted directories into the given directory instead of removing them
+    pub fn with_archive_dir(mut self, archive_dir: Option<std::path::PathBuf>) -> Self {
+        self.cleaner = self.cleaner.clone().with_archive_dir(archive_dir);
+        self
+    }
+
+    /// Stop sizing a single .venv after this long, showing a partial size
+    pub fn with_size_timeout(mut self, size_timeout: Option<Duration>) -> Self {
+        self.cleaner = self.cleaner.clone().with_size_timeout(size_timeout);
+        self
+    }
+
+    /// Choose whose .venv directories are listed (the current user's by default)
+    pub fn with_owner_filter(mut self, owner_filter: OwnerFilter) -> Self {
+        self.cleaner = self.cleaner.clone().with_owner_filter(owner_filter);
+        self
+    }
+
+    /// List only the .venv directories meeting age and size criteria, starting with them selected
+    pub fn with_venv_filter(mut self, venv_filter: VenvFilter) -> Self {
+        self.cleaner = self.cleaner.clone().with_venv_filter(venv_filter);
+        self.preselect = venv_filter.is_active();
+        self
+    }
+
+    /// Skip hidden directories other than `.venv` during recursive scans
+    pub fn with_skip_hidden(mut self, skip_hidden: bool) -> Self {
+        self.cleaner = self.cleaner.clone().with_skip_hidden(skip_hidden);
+        self
+    }
+
+    /// Choose whether folders named .venv must look like virtual environments to be listed
+    pub fn with_validation(mut self, validate: bool) -> Self {
+        self.cleaner = self.cleaner.clone().with_validation(validate);
+        self
+    }
+
+    /// Never walk into these directories during recursive scans
+    pub fn with_excludes(mut self, excludes: Vec<std::path::PathBuf>) -> Self {
+        self.cleaner = self.cleaner.clone().with_excludes(excludes);
+        self
+    }
+
+    /// Limit recursive scans to the subtrees matching these patterns
+    pub fn with_includes(mut self, includes: IncludeSet) -> Self {
+        self.cleaner = self.cleaner.clone().with_includes(includes);
+        self
+    }
+
+    /// Also find environments of these layouts, besides `.venv` directories
+    pub fn with_detectors(mut self, detectors: Vec<Detector>) -> Self {
+        self.cleaner = self.cleaner.clone().with_detectors(detectors);
+        self
+    }
+
+    /// Browse a saved scan instead of the disk, adding deletions to its plan
+    pub fn with_offline_index(mut self, offline: Option<OfflineIndex>) -> Self {
+        self.cleaner = self.cleaner.clone().with_offline_index(offline);
+        self
+    }
+
+    /// Scan and delete through another file system than the disk, e.g. a `MemoryFs` in tests
+    pub fn with_fs_backend(mut self, fs: Arc<dyn FsBackend>) -> Self {
+        self.cleaner = self.cleaner.clone().with_fs_backend(fs);
+        self
+    }
+
+    /// Override the scan and deletion parallelism (None keeps the defaults)
+    pub fn with_concurrency(mut self, scan_threads: Option<usize>, delete_jobs: Option<usize>) -> Self {
+        self.cleaner = self.cleaner.clone().with_scan_threads(scan_threads).with_delete_jobs(delete_jobs);
+        self
+    }
+
+    /// Also scan these directories, showing a section with subtotals per root
+    pub fn with_extra_roots(mut self, extra_roots: Vec<std::path::PathBuf>) -> Self {
+        self.cleaner = self.cleaner.clone().with_extra_roots(extra_roots);
+        self.app.set_roots(self.cleaner.roots().into_iter().map(|root| root.to_path_buf()).collect());
+        self
+    }
+
+    /// Show the log lines captured from this channel in the log pane (`L`)
+    pub fn with_log_receiver(mut self, receiver: Receiver<LogLine>) -> Self {
+        self.app.set_logs(LogBuffer::new(receiver));
+        self
+    }
+
+    /// Report progress in the terminal title and taskbar (OSC 9;4), or not at all
+    pub fn with_terminal_progress(mut self, enabled: bool) -> Self {
+        self.progress = TerminalProgress::new(enabled);
+        self
+    }
+
+    /// Draw with the given color theme
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.app.set_theme(theme);
+        self
+    }
+
+    /// Draw the list's markers with the given glyphs
+    pub fn with_glyphs(mut self, glyphs: Glyphs) -> Self {
+        self.app.set_glyphs(glyphs);
+        self
+    }
+
+    /// Open projects with this editor command template (`{dir}` is the project directory)
+    pub fn with_editor(mut self, editor: Option<String>) -> Self {
+        self.editor = editor;
+        self
+    }
+
     /// Run the TUI application
     pub fn run(&mut self) -> Result<()> {
         info!("Starting TUI mode");
 
         // Set initial loading state before drawing anything
+        self.progress.begin(self.terminal.backend_mut())?;
         self.app.set_state(AppState::Loading);
         self.app.set_status("Initializing VenvCleaner TUI...".to_string());
 
@@ -159,6 +380,8 @@ impl TuiMode {
 
         // Main event loop
         loop {
+            self.look_up_last_commit();
+
             // Draw the UI
             let app_ref = &self.app;
             self.terminal.draw(|f| {
@@ -174,6 +397,14 @@ impl TuiMode {
                         ui::draw_main_screen(f, size, app_ref);
                         ui::draw_confirmation_dialog(f, size, app_ref);
                     }
+                    AppState::PeekingDeletion => {
+                        ui::draw_main_screen(f, size, app_ref);
+                        ui::draw_peek_dialog(f, size, app_ref);
+                    }
+                    AppState::ConfirmingArchiveDeletion => {
+                        ui::draw_main_screen(f, size, app_ref);
+                        ui::draw_archive_deletion_dialog(f, size, app_ref);
+                    }
                     AppState::Deleting => {
                         ui::draw_main_screen(f, size, app_ref);
                         ui::draw_deletion_progress(f, size, app_ref);
@@ -182,7 +413,17 @@ impl TuiMode {
                         ui::draw_error_screen(f, size, app_ref);
                     }
                     AppState::Help => {
-                        ui::draw_help_screen(f, size);
+                        ui::draw_help_screen(f, size, app_ref.theme(), app_ref.glyphs());
+                    }
+                    AppState::Recommendations => {
+                        ui::draw_recommendations_screen(f, size, app_ref);
+                    }
+                    AppState::Notifications => {
+                        ui::draw_notifications_screen(f, size, app_ref);
+                    }
+                    AppState::Exporting => {
+                        ui::draw_main_screen(f, size, app_ref);
+                        ui::draw_export_prompt(f, size, app_ref);
                     }
                     AppState::Quit => {
                         // Should not reach here
@@ -191,17 +432,25 @@ impl TuiMode {
             })?;
 
             // Handle events
-            if let Ok(event) = self.event_handler.next() {
+            if let Ok(event) = self.event_handler.next_event() {
                 match event {
                     AppEvent::Input(key_event) => {
                         if self.handle_key_event(key_event)? {
                             break; // Exit requested
                         }
+                        // q during a scan quits once the scan has stopped
+                        if *self.app.state() == AppState::Quit {
+                            break;
+                        }
                     }
                     AppEvent::Tick => {
                         self.handle_tick()?;
                     }
                     AppEvent::VenvsLoaded(venvs) => {
+                        // Scans of this machine record their sizes for the next one to compare with
+                        if self.cleaner.offline_index().is_none() {
+                            self.app.set_size_deltas(size_deltas::record_scan(&venvs));
+                        }
                         self.app.set_venvs(venvs);
                         self.app.set_state(AppState::Browsing);
                     }
@@ -209,8 +458,8 @@ impl TuiMode {
                         self.app.set_error(error);
                         self.app.set_state(AppState::Error);
                     }
-                    AppEvent::DeletionComplete(results) => {
-                        self.app.handle_deletion_results(results);
+                    AppEvent::DeletionComplete(batch) => {
+                        self.app.handle_deletion_results(&batch);
                         // Refresh the list by reloading after a short delay to show completion
                         self.app.set_state(AppState::Loading);
                         self.start_loading_venvs()?;
@@ -219,6 +468,9 @@ impl TuiMode {
             }
         }
 
+        if let Err(e) = self.activity.save() {
+            warn!("Failed to save activity cache: {}", e);
+        }
         self.cleanup()?;
         Ok(())
     }
@@ -228,6 +480,8 @@ impl TuiMode {
         self.app.set_state(AppState::Loading);
         let search_mode = if self.cleaner.is_recursive() { "recursively" } else { "in current directory" };
         self.app.set_status(format!("🔍 Scanning for .venv directories {}...", search_mode));
+        let directory = self.app.current_directory().display().to_string();
+        self.report_progress(&format!("Scanning {}", directory), TaskbarProgress::Indeterminate);
 
         // Draw initial scanning message
         self.terminal.draw(|f| {
@@ -244,15 +498,38 @@ impl TuiMode {
             ui::draw_loading_screen(f, size, &self.app);
         })?;
 
-        // In a real implementation, this would spawn a background task
-        // For now, we'll do it synchronously but show the loading state
-
         // Add a brief delay to show the scanning message
         std::thread::sleep(std::time::Duration::from_millis(200));
 
-        let venvs = self.cleaner.find_venv_directories();
+        // Archived environments alone still make the list worth showing, as does a cancelled scan
+        let pending = self.cleaner.find_pending_deletions();
+        let started = Utc::now();
+        let (venvs, cancelled) = self.scan_cancellable()?;
+        let archived = self.cleaner.last_archived_venvs();
+        self.app.set_scan_metadata(
+            ScanMetadata::new(&self.cleaner.roots(), self.cleaner.is_recursive(), started).with_finished(Utc::now()),
+        );
+        if *self.app.state() == AppState::Quit {
+            return Ok(());
+        }
+        let venvs = match venvs {
+            Err(VenvCleanerError::NoVenvFound) if !archived.is_empty() || cancelled => Ok(Vec::new()),
+            result => result,
+        };
+        self.app.set_archived(archived);
 
         match venvs {
+            Ok(venvs) if cancelled => {
+                self.app.set_venvs(venvs);
+                self.app.set_state(AppState::Browsing);
+                self.preselect = false;
+                let count = self.app.venvs().len();
+                self.report_progress(&format!("Scan of {} cancelled", directory), TaskbarProgress::Hidden);
+                self.app.set_status(format!(
+                    "⏹ Scan cancelled: showing the {} .venv directories found so far. Press 'r' to scan everything.",
+                    count
+                ));
+            }
             Ok(venvs) => {
                 // Show completion message briefly
                 self.app.set_status(format!("✅ Scan complete! Processing {} directories...", venvs.len()));
@@ -267,14 +544,39 @@ impl TuiMode {
                 self.app.set_venvs(venvs);
                 self.app.set_state(AppState::Browsing);
                 let count = self.app.venvs().len();
+                let total = ByteSize::new(self.app.get_summary_stats().total_size).to_string();
+                self.report_progress(&format!("{} venvs ({}) in {}", count, total, directory), TaskbarProgress::Hidden);
                 if count == 0 {
                     self.app.set_status("No .venv directories found. Press 'r' to refresh or 'q' to quit.".to_string());
+                } else if std::mem::take(&mut self.preselect) {
+                    // Only the startup scan: later refreshes keep the user's own selection
+                    self.app.select_all();
+                    self.app.set_status(format!(
+                        "Selected the {} .venv directories matching --older-than/--min-size. Review them, then press x to delete.",
+                        count
+                    ));
+                } else if let Some((root, environment)) = self.cleaner.roots_inside_environments().first() {
+                    self.app.notify(Severity::Warning, format!(
+                        "Found {} .venv directories. ⚠️ {} is inside {}; they may be test data of its packages, check before deleting",
+                        count,
+                        root.display(),
+                        environment.display()
+                    ));
+                } else if let Some(warning) = self.cleaner.trash_warning() {
+                    self.app.notify(Severity::Warning, format!("Found {} .venv directories. ⚠️ {}", count, warning));
+                } else if !pending.is_empty() {
+                    self.app.notify(Severity::Warning, format!(
+                        "Found {} .venv directories. ⚠️ {} deletions were interrupted; run `venv_cleaner` on this directory to finish or restore them",
+                        count,
+                        pending.len()
+                    ));
                 } else {
                     self.app.set_status(format!("Found {} .venv directories. Use arrow keys to navigate, Space to select.", count));
                 }
             }
             Err(e) => {
-                self.app.set_error(e.to_string());
+                self.report_progress("Scan failed", TaskbarProgress::Error(100));
+                self.app.set_error(platform::describe_error(&e));
                 self.app.set_state(AppState::Error);
             }
         }
@@ -282,6 +584,63 @@ impl TuiMode {
         Ok(())
     }
 
+    /// Scan on a background thread, drawing how much was found so far until it finishes
+    ///
+    /// Esc stops the scan and keeps the .venv directories found so far; q
+    /// stops it and quits.
+    ///
+    /// # Returns
+    /// The scan's result and whether it was cancelled
+    fn scan_cancellable(&mut self) -> Result<(Result<Vec<VenvInfo>>, bool)> {
+        let cancellation = CancellationToken::new();
+        let cleaner = self.cleaner.clone().with_cancellation(cancellation.clone());
+        let (found_tx, found_rx) = mpsc::channel();
+        let scan = thread::spawn(move || {
+            cleaner.find_venv_directories_with(|venv_info| {
+                let _ = found_tx.send(venv_info.size().bytes());
+            })
+        });
+
+        let (mut found, mut found_bytes) = (0usize, 0u64);
+        while !scan.is_finished() {
+            for size_bytes in found_rx.try_iter() {
+                found += 1;
+                found_bytes += size_bytes;
+            }
+            if !cancellation.is_cancelled() {
+                self.app.set_status(format!(
+                    "📁 Found {} .venv directories ({}) so far...",
+                    found,
+                    ByteSize::new(found_bytes)
+                ));
+            }
+            self.terminal.draw(|f| {
+                let size = f.size();
+                ui::draw_loading_screen(f, size, &self.app);
+            })?;
+
+            while let Some(event) = self.event_handler.try_next() {
+                match event {
+                    AppEvent::Input(key) if matches!(key.code, KeyCode::Esc | KeyCode::Char('q')) => {
+                        if key.code == KeyCode::Char('q') {
+                            self.app.set_state(AppState::Quit);
+                        }
+                        cancellation.cancel();
+                        self.app.set_status("⏹ Cancelling scan...".to_string());
+                    }
+                    AppEvent::Tick => self.app.tick(),
+                    _ => {}
+                }
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+
+        let result = scan
+            .join()
+            .unwrap_or_else(|_| Err(VenvCleanerError::Io("The scan thread panicked".to_string())));
+        Ok((result, cancellation.is_cancelled()))
+    }
+
     /// Handle keyboard input events
     fn handle_key_event(&mut self, key: KeyEvent) -> Result<bool> {
         match self.app.state() {
@@ -297,9 +656,33 @@ impl TuiMode {
                     KeyCode::Char('h') | KeyCode::F(1) => {
                         self.app.set_state(AppState::Help);
                     }
+                    KeyCode::Char('!') => {
+                        self.app.set_state(AppState::Notifications);
+                    }
+                    KeyCode::Char('i') => {
+                        let found = recommendations::recommend(self.app.venvs(), &self.cleaner.roots());
+                        self.app.set_recommendations(found);
+                        self.app.set_state(AppState::Recommendations);
+                    }
                     KeyCode::Char('r') => {
                         self.start_loading_venvs()?;
                     }
+                    // While the tree pane has the keyboard the arrows move through directories
+                    KeyCode::Up if self.app.pane_focus() == PaneFocus::Tree => {
+                        self.app.select_previous_tree_node();
+                    }
+                    KeyCode::Down if self.app.pane_focus() == PaneFocus::Tree => {
+                        self.app.select_next_tree_node();
+                    }
+                    KeyCode::Left if self.app.pane_focus() == PaneFocus::Tree => {
+                        self.app.select_parent_tree_node();
+                    }
+                    KeyCode::Home if self.app.pane_focus() == PaneFocus::Tree => {
+                        self.app.select_tree_node(0);
+                    }
+                    KeyCode::End if self.app.pane_focus() == PaneFocus::Tree => {
+                        self.app.select_tree_node(usize::MAX);
+                    }
                     KeyCode::Up => {
                         self.app.select_previous();
                     }
@@ -321,19 +704,16 @@ impl TuiMode {
                     KeyCode::Enter | KeyCode::Char(' ') => {
                         self.app.toggle_selected();
                     }
-                    KeyCode::Char('a') => {
-                        if key.modifiers.contains(KeyModifiers::CONTROL) {
-                            self.app.select_all();
-                        }
+                    KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        self.app.select_all();
                     }
-                    KeyCode::Char('d') => {
-                        if key.modifiers.contains(KeyModifiers::CONTROL) {
-                            self.app.deselect_all();
-                        }
+                    KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        self.app.deselect_all();
                     }
-                    KeyCode::Delete | KeyCode::Char('x') => {
-                        if self.app.has_selected_items() {
-                            self.app.set_state(AppState::ConfirmingDeletion);
+                    KeyCode::Delete | KeyCode::Char('x') if self.app.has_selected_items() => {
+                        match self.cleaner.policy().check_batch_size(self.app.get_selected_venvs().len()) {
+                            Ok(()) => self.app.request_deletion(self.cleaner.is_using_trash()),
+                            Err(e) => self.app.notify(Severity::Warning, format!("🔒 {}", e)),
                         }
                     }
                     KeyCode::Char('s') => {
@@ -341,7 +721,75 @@ impl TuiMode {
                         self.app.set_status(format!("Sorted by {}", self.app.sort_by().display_name()));
                     }
                     KeyCode::Char('o') => {
-                        self.app.open_folder()?;
+                        if let Err(e) = self.app.open_folder() {
+                            self.report_failure("open folder", e);
+                        }
+                    }
+                    KeyCode::Char('m') if !self.app.refuse_if_read_only() => {
+                        self.start_slimming()?;
+                    }
+                    KeyCode::Char('z') => {
+                        self.retry_size();
+                    }
+                    KeyCode::Char('D') => {
+                        self.app.toggle_size_delta();
+                        let shown = if self.app.shown_size_deltas().is_some() { "Showing" } else { "Hiding" };
+                        self.app.set_status(format!("{} the change in size since the last scan", shown));
+                    }
+                    KeyCode::Tab => {
+                        self.app.cycle_pane_focus();
+                        let mode = match self.app.pane_focus() {
+                            PaneFocus::Tree => "Directory tree: ↑/↓ pick a directory, ← go up, Tab to work on its venvs",
+                            PaneFocus::FilteredList => "Showing the highlighted directory's venvs, Tab hides the tree",
+                            PaneFocus::List => "Tree hidden, showing all venvs",
+                        };
+                        self.app.set_status(mode.to_string());
+                    }
+                    KeyCode::BackTab => {
+                        self.app.select_next_archived();
+                    }
+                    KeyCode::Char('L') => {
+                        self.app.toggle_log_pane();
+                        let mode = if !self.app.is_log_shown() {
+                            "Log pane hidden"
+                        } else if self.app.logs().is_capturing() {
+                            "Log pane shown (-v or -vv capture more detail)"
+                        } else {
+                            "Log pane shown, but log capture is not active"
+                        };
+                        self.app.set_status(mode.to_string());
+                    }
+                    KeyCode::Char('g') => {
+                        self.app.toggle_group_by_age();
+                        let mode = if self.app.is_grouped_by_age() { "Grouped by age (1-4 collapse a section)" } else { "Grouping off" };
+                        self.app.set_status(mode.to_string());
+                    }
+                    KeyCode::Char(c @ '1'..='4') if self.app.is_grouped_by_age() => {
+                        let bucket = AgeBucket::ALL[c as usize - '1' as usize];
+                        self.app.toggle_bucket(bucket);
+                    }
+                    KeyCode::Char('u') if !self.app.refuse_if_read_only() => {
+                        self.restore_archived()?;
+                    }
+                    KeyCode::Char('X') if self.app.selected_archived().is_some() && !self.app.refuse_if_read_only() => {
+                        self.app.set_state(AppState::ConfirmingArchiveDeletion);
+                    }
+                    KeyCode::Char('e') => {
+                        match self.open_in_editor() {
+                            Ok(true) => self.app.set_status("Returned from the editor".to_string()),
+                            Ok(false) => {}
+                            Err(e) => self.report_failure("open editor", e),
+                        }
+                    }
+                    KeyCode::Char('E') => {
+                        self.app.start_export();
+                    }
+                    KeyCode::Char('t') => {
+                        // A missing terminal should not end the session, so only report it
+                        match self.app.open_shell() {
+                            Ok(()) => self.app.set_status("Opened a terminal with the .venv activated".to_string()),
+                            Err(e) => self.report_failure("open shell", e),
+                        }
                     }
                     _ => {}
                 }
@@ -349,7 +797,47 @@ impl TuiMode {
             AppState::ConfirmingDeletion => {
                 match key.code {
                     KeyCode::Char('y') | KeyCode::Enter => {
-                        self.start_deletion()?;
+                        self.confirm_deletion()?;
+                    }
+                    KeyCode::Char('n') | KeyCode::Esc => {
+                        self.app.set_state(AppState::Browsing);
+                    }
+                    KeyCode::Char('p') => {
+                        match self.app.open_peek() {
+                            Ok(()) => self.app.set_state(AppState::PeekingDeletion),
+                            Err(e) => self.report_failure("look inside the .venv", e),
+                        }
+                    }
+                    KeyCode::Char('t') => {
+                        if self.cleaner.policy().require_trash {
+                            self.app.notify(Severity::Warning, "The policy requires deleted .venv directories to go to the trash".to_string());
+                        } else {
+                            self.app.toggle_delete_to_trash();
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            AppState::PeekingDeletion => {
+                match key.code {
+                    KeyCode::Char('y') | KeyCode::Enter => {
+                        self.confirm_deletion()?;
+                    }
+                    KeyCode::Right | KeyCode::Left => {
+                        if let Err(e) = self.app.cycle_peek(key.code == KeyCode::Right) {
+                            self.report_failure("look inside the .venv", e);
+                        }
+                    }
+                    KeyCode::Char('p') | KeyCode::Char('n') | KeyCode::Esc => {
+                        self.app.set_state(AppState::ConfirmingDeletion);
+                    }
+                    _ => {}
+                }
+            }
+            AppState::ConfirmingArchiveDeletion => {
+                match key.code {
+                    KeyCode::Char('y') | KeyCode::Enter => {
+                        self.delete_archived()?;
                     }
                     KeyCode::Char('n') | KeyCode::Esc => {
                         self.app.set_state(AppState::Browsing);
@@ -357,6 +845,27 @@ impl TuiMode {
                     _ => {}
                 }
             }
+            AppState::Exporting => {
+                match key.code {
+                    KeyCode::Enter => {
+                        match self.app.export_shown() {
+                            Ok(rows) => self.app.set_status(format!("Exported {} .venv directories to {}", rows, self.app.export_path())),
+                            Err(e) => self.report_failure("export the list", e),
+                        }
+                        self.app.set_state(AppState::Browsing);
+                    }
+                    KeyCode::Esc => {
+                        self.app.set_state(AppState::Browsing);
+                    }
+                    KeyCode::Backspace => {
+                        self.app.pop_export_char();
+                    }
+                    KeyCode::Char(c) => {
+                        self.app.push_export_char(c);
+                    }
+                    _ => {}
+                }
+            }
             AppState::Deleting => {
                 // Only allow force quit during deletion
                 if key.code == KeyCode::Char('q') && key.modifiers.contains(KeyModifiers::CONTROL) {
@@ -372,8 +881,8 @@ impl TuiMode {
                     _ => {}
                 }
             }
-            AppState::Help => {
-                // Any key exits help
+            AppState::Help | AppState::Recommendations | AppState::Notifications => {
+                // Any key exits help, recommendations and notifications
                 self.app.set_state(AppState::Browsing);
             }
             AppState::Quit => {
@@ -391,32 +900,56 @@ impl TuiMode {
         Ok(())
     }
 
+    /// Handle a confirmation, which for selections holding user data must be given twice
+    fn confirm_deletion(&mut self) -> Result<()> {
+        if self.app.needs_user_data_confirmation() {
+            self.app.confirm_user_data();
+            self.app.set_state(AppState::ConfirmingDeletion);
+            return Ok(());
+        }
+        self.start_deletion()
+    }
+
     /// Start the deletion process for selected .venv directories
     fn start_deletion(&mut self) -> Result<()> {
         self.app.set_state(AppState::Deleting);
         self.app.set_status("Deleting selected directories...".to_string());
 
         let selected_venvs = self.app.get_selected_venvs();
-        let mut results = Vec::new();
+        let total = selected_venvs.len();
+        self.report_progress(&format!("Deleting 0/{}", total), TaskbarProgress::of(0, total));
 
-        for venv in &selected_venvs {
-            let result = self.cleaner.delete_venv_directory(venv);
-            results.push((venv.clone(), result));
-        }
+        let cleaner = self.cleaner.clone().with_trash(self.app.deletes_to_trash());
+        let mut estimator = DeletionEstimator::for_venvs(&selected_venvs);
+        self.app.set_deletion_progress(0, total, None);
+        self.terminal.draw(|f| ui::draw_deletion_progress(f, f.size(), &self.app))?;
 
-        // Calculate stats before moving results
-        let deleted_count = results.iter().filter(|(_, r)| r.is_ok()).count();
-        let failed_count = results.len() - deleted_count;
+        let mut done = 0;
+        let batch = cleaner.delete_many(&selected_venvs, &BatchOptions::new(), |item| {
+            done += 1;
+            estimator.record(item.venv.path());
+            let eta = estimator.remaining();
+            self.app.set_deletion_progress(done, total, eta);
+            let _ = self.terminal.draw(|f| ui::draw_deletion_progress(f, f.size(), &self.app));
+            self.report_progress(
+                &format!("Deleting {}/{}, {}", done, total, deletion_eta::format_eta(eta)),
+                TaskbarProgress::of(done, total),
+            );
+        });
+        let (deleted_count, failed_count, vanished_count) = (batch.deleted(), batch.failed(), batch.vanished());
 
-        self.app.handle_deletion_results(results);
+        self.app.handle_deletion_results(&batch);
 
         // Set a brief completion message
-
-        if failed_count == 0 {
-            self.app.set_status(format!("Successfully deleted {} directories. Refreshing list...", deleted_count));
+        let (severity, mut message) = if failed_count == 0 {
+            (Severity::Info, format!("Successfully deleted {} directories", deleted_count))
         } else {
-            self.app.set_status(format!("Deleted {} directories, {} failed. Refreshing list...", deleted_count, failed_count));
+            (Severity::Error, format!("Deleted {} directories, {} failed", deleted_count, failed_count))
+        };
+        if vanished_count > 0 {
+            message.push_str(&format!(" ({} had already been removed elsewhere)", vanished_count));
         }
+        self.app.notify(severity, format!("{}. Refreshing list...", message));
 
         // Trigger a refresh by going back to loading state
         self.app.set_state(AppState::Loading);
@@ -425,39 +958,173 @@ impl TuiMode {
         Ok(())
     }
 
-    /// Draw the user interface
-    fn draw_ui(&self, f: &mut ratatui::Frame) {
-        let size = f.size();
+    /// Slim the selected .venv directories (or the highlighted one) in place
+    fn start_slimming(&mut self) -> Result<()> {
+        let mut targets = self.app.get_selected_venvs();
+        if targets.is_empty() {
+            targets.extend(self.app.selected_venv().cloned());
+        }
+        if targets.is_empty() {
+            return Ok(());
+        }
 
-        match self.app.state() {
-            AppState::Loading => {
-                ui::draw_loading_screen(f, size, &self.app);
-            }
-            AppState::Browsing => {
-                ui::draw_main_screen(f, size, &self.app);
-            }
-            AppState::ConfirmingDeletion => {
-                ui::draw_main_screen(f, size, &self.app);
-                ui::draw_confirmation_dialog(f, size, &self.app);
-            }
-            AppState::Deleting => {
-                ui::draw_main_screen(f, size, &self.app);
-                ui::draw_deletion_progress(f, size, &self.app);
-            }
-            AppState::Error => {
-                ui::draw_error_screen(f, size, &self.app);
+        self.app.set_status(format!("Slimming {} directories...", targets.len()));
+        let mut saved = 0u64;
+        let mut failed = 0usize;
+        for (done, venv) in targets.iter().enumerate() {
+            self.report_progress(&format!("Slimming {}/{}", done + 1, targets.len()), TaskbarProgress::of(done, targets.len()));
+            match self.cleaner.slim_venv_directory(venv, false) {
+                Ok(report) => {
+                    saved += report.bytes_saved();
+                    failed += report.failed().len();
+                }
+                Err(_) => failed += 1,
             }
-            AppState::Help => {
-                ui::draw_help_screen(f, size);
+        }
+
+        // Sizes changed, so rescan before reporting
+        self.start_loading_venvs()?;
+        let summary = format!("✂️ Slimmed {} directories, reclaimed {}", targets.len(), ByteSize::new(saved));
+        if failed == 0 {
+            self.app.set_status(summary);
+        } else {
+            self.app.notify(Severity::Warning, format!("{} ({} items could not be removed)", summary, failed));
+        }
+
+        Ok(())
+    }
+
+    /// Fill in the last commit of the highlighted .venv's project for the details pane
+    ///
+    /// Asking git is slow, so only the highlighted .venv is looked up, and
+    /// through the activity cache.
+    fn look_up_last_commit(&mut self) {
+        if self.cleaner.offline_index().is_some() {
+            return;
+        }
+        let Some(venv) = self.app.selected_venv().filter(|venv| venv.project_last_commit().is_none()) else {
+            return;
+        };
+        if let Some(committed) = self.activity.last_commit(venv) {
+            let venv = venv.clone().with_project_last_commit(Some(committed));
+            self.app.replace_venv(venv);
+        }
+    }
+
+    /// Size the highlighted .venv again without the sizing timeout
+    fn retry_size(&mut self) {
+        let Some(venv) = self.app.selected_venv().cloned() else {
+            return;
+        };
+
+        self.app.set_status(format!("Sizing {}...", venv.location()));
+        let _ = self.terminal.draw(|f| ui::draw_main_screen(f, f.size(), &self.app));
+        match self.cleaner.recalculate_size(&venv) {
+            Ok(updated) => {
+                self.app.set_status(format!("{} is {}", venv.location(), updated.size_formatted()));
+                self.app.replace_venv(updated);
             }
-            AppState::Quit => {
-                // Should not reach here
+            Err(e) => self.report_failure(&format!("size {}", venv.location()), e),
+        }
+    }
+
+    /// Restore the highlighted archived .venv directory
+    fn restore_archived(&mut self) -> Result<()> {
+        let Some(archived) = self.app.selected_archived().cloned() else {
+            return Ok(());
+        };
+
+        self.app.set_status(format!("📦 Restoring {}...", archived.location()));
+        let result = self.cleaner.restore_archived_venv(&archived);
+
+        // The restored .venv joins the list, so rescan before reporting
+        self.start_loading_venvs()?;
+        match result {
+            Ok(venv_path) => self.app.set_status(format!("📦 Restored {}", venv_path.display())),
+            Err(e) => self.app.notify(Severity::Error, format!("Could not restore {}: {}", archived.location(), e)),
+        }
+
+        Ok(())
+    }
+
+    /// Delete the archive of the highlighted archived .venv directory
+    fn delete_archived(&mut self) -> Result<()> {
+        let Some(archived) = self.app.selected_archived().cloned() else {
+            self.app.set_state(AppState::Browsing);
+            return Ok(());
+        };
+
+        let result = self.cleaner.delete_archived_venv(&archived);
+        self.start_loading_venvs()?;
+        match result {
+            Ok(freed) => self.app.set_status(format!("Deleted archive of {}, freed {}", archived.location(), ByteSize::new(freed))),
+            Err(e) => self.app.notify(Severity::Error, format!("Could not delete archive of {}: {}", archived.location(), e)),
+        }
+
+        Ok(())
+    }
+
+    /// Open the highlighted .venv's project in the editor
+    ///
+    /// Terminal editors such as vim need the terminal, so the TUI steps
+    /// aside until the editor exits; GUI launchers like `code` return at once.
+    ///
+    /// # Returns
+    /// Whether an editor was run
+    fn open_in_editor(&mut self) -> Result<bool> {
+        let Some(venv) = self.app.selected_venv() else {
+            return Ok(false);
+        };
+        let mut command = editor::project_editor_command(venv.path(), self.editor.as_deref())?;
+
+        self.cleanup()?;
+        let status = command.status();
+        terminal::enable_raw_mode()?;
+        TERMINAL_ACTIVE.store(true, Ordering::SeqCst);
+        execute!(self.terminal.backend_mut(), EnterAlternateScreen, EnableMouseCapture)?;
+        self.progress.begin(self.terminal.backend_mut())?;
+        self.terminal.clear()?;
+
+        match status {
+            Ok(status) if status.success() => Ok(true),
+            Ok(status) => Err(VenvCleanerError::Io(format!("Editor exited with {}", status))),
+            Err(e) => Err(VenvCleanerError::Io(format!("Failed to run editor: {}", e))),
+        }
+    }
+
+    /// Report a failed action on a .venv
+    ///
+    /// A .venv removed outside VenvCleaner since the scan is dropped from
+    /// the list with a notice instead; an open deletion dialog is closed
+    /// when that leaves nothing selected.
+    fn report_failure(&mut self, action: &str, error: VenvCleanerError) {
+        if let VenvCleanerError::VenvVanished { path } = &error {
+            if self.app.remove_vanished(Path::new(path)) {
+                if !self.app.has_selected_items() {
+                    self.app.set_state(AppState::Browsing);
+                } else if *self.app.state() == AppState::PeekingDeletion {
+                    self.app.set_state(AppState::ConfirmingDeletion);
+                }
+                return;
             }
         }
+        self.app.notify(Severity::Error, format!("Could not {}: {}", action, error));
+    }
+
+    /// Show an activity in the terminal title and taskbar
+    ///
+    /// Both are cosmetic, so failing to write them never interrupts the TUI.
+    fn report_progress(&mut self, title: &str, progress: TaskbarProgress) {
+        let out = self.terminal.backend_mut();
+        let _ = self
+            .progress
+            .set_title(out, &format!("VenvCleaner - {}", title))
+            .and_then(|_| self.progress.set_progress(out, progress));
     }
 
     /// Clean up terminal state before exiting
     fn cleanup(&mut self) -> Result<()> {
+        TERMINAL_ACTIVE.store(false, Ordering::SeqCst);
         terminal::disable_raw_mode()?;
         execute!(
             self.terminal.backend_mut(),
@@ -465,13 +1132,15 @@ impl TuiMode {
             DisableMouseCapture
         )?;
         self.terminal.show_cursor()?;
+        self.progress.finish(self.terminal.backend_mut())?;
         Ok(())
     }
 }
 
 impl Drop for TuiMode {
     fn drop(&mut self) {
-        // Ensure cleanup happens even if there's a panic
+        // Ensure cleanup happens even if there's a panic (the panic hook has
+        // usually restored the terminal already; doing it twice is harmless)
         let _ = self.cleanup();
     }
 }
@@ -479,12 +1148,12 @@ impl Drop for TuiMode {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use tempfile::TempDir;
 
     #[test]
     fn test_sort_by_cycle() {
         assert_eq!(SortBy::Path.next(), SortBy::Size);
-        assert_eq!(SortBy::Size.next(), SortBy::Created);
+        assert_eq!(SortBy::Size.next(), SortBy::Files);
+        assert_eq!(SortBy::Files.next(), SortBy::Created);
         assert_eq!(SortBy::Created.next(), SortBy::LastModified);
         assert_eq!(SortBy::LastModified.next(), SortBy::Path);
     }
@@ -493,7 +1162,8 @@ mod tests {
     fn test_sort_by_previous() {
         assert_eq!(SortBy::Path.previous(), SortBy::LastModified);
         assert_eq!(SortBy::Size.previous(), SortBy::Path);
-        assert_eq!(SortBy::Created.previous(), SortBy::Size);
+        assert_eq!(SortBy::Files.previous(), SortBy::Size);
+        assert_eq!(SortBy::Created.previous(), SortBy::Files);
         assert_eq!(SortBy::LastModified.previous(), SortBy::Created);
     }
 
@@ -501,6 +1171,7 @@ mod tests {
     fn test_sort_by_display_names() {
         assert_eq!(SortBy::Path.display_name(), "Path");
         assert_eq!(SortBy::Size.display_name(), "Size");
+        assert_eq!(SortBy::Files.display_name(), "Files");
         assert_eq!(SortBy::Created.display_name(), "Created");
         assert_eq!(SortBy::LastModified.display_name(), "Last Used");
     }
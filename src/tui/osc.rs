@@ -0,0 +1,157 @@
+//! Terminal title and taskbar progress for the TUI mode
+//!
+//! Long scans and deletions are reported outside the TUI itself: the
+//! terminal title shows what is happening, and OSC 9;4 progress sequences
+//! (understood by Windows Terminal, iTerm2, ConEmu and others) put a progress
+//! indicator on the tab or taskbar entry, so it stays visible while the
+//! window is in the background. Terminals that do not know the sequences
+//! ignore them.
+
+use std::io::{self, Write};
+
+/// Taskbar progress states of the OSC 9;4 sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskbarProgress {
+    /// No progress indicator
+    Hidden,
+    /// Progress in percent
+    Normal(u8),
+    /// Progress in percent, shown as failed
+    Error(u8),
+    /// Busy with unknown duration
+    Indeterminate,
+}
+
+impl TaskbarProgress {
+    /// Progress of `done` out of `total` steps
+    pub fn of(done: usize, total: usize) -> Self {
+        match total {
+            0 => TaskbarProgress::Indeterminate,
+            total => TaskbarProgress::Normal((done.min(total) * 100 / total) as u8),
+        }
+    }
+
+    /// Build the OSC 9;4 escape sequence for this state
+    pub fn sequence(self) -> String {
+        let (state, percent) = match self {
+            TaskbarProgress::Hidden => (0, 0),
+            TaskbarProgress::Normal(percent) => (1, percent.min(100)),
+            TaskbarProgress::Error(percent) => (2, percent.min(100)),
+            TaskbarProgress::Indeterminate => (3, 0),
+        };
+        format!("\x1b]9;4;{};{}\x07", state, percent)
+    }
+}
+
+/// Build the escape sequence setting the terminal title
+///
+/// Control characters are dropped so a path cannot end the sequence early.
+pub fn title_sequence(title: &str) -> String {
+    let title: String = title.chars().filter(|c| !c.is_control()).collect();
+    format!("\x1b]0;{}\x07", title)
+}
+
+/// xterm sequence saving the current title on the terminal's title stack
+const PUSH_TITLE: &str = "\x1b[22;0t";
+
+/// xterm sequence restoring the title saved by `PUSH_TITLE`
+const POP_TITLE: &str = "\x1b[23;0t";
+
+/// Writes the terminal title and taskbar progress, restoring both when done
+#[derive(Debug, Clone, Default)]
+pub struct TerminalProgress {
+    /// Whether anything is written at all
+    enabled: bool,
+    /// Whether the user's title was saved and must be restored
+    title_saved: bool,
+}
+
+impl TerminalProgress {
+    /// Create a new TerminalProgress
+    ///
+    /// # Arguments
+    /// * `enabled` - Whether to write title and progress sequences
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled, title_saved: false }
+    }
+
+    /// Save the user's terminal title before changing it
+    pub fn begin(&mut self, out: &mut impl Write) -> io::Result<()> {
+        if self.enabled && !self.title_saved {
+            self.title_saved = true;
+            self.write(out, PUSH_TITLE)?;
+        }
+        Ok(())
+    }
+
+    /// Set the terminal title
+    pub fn set_title(&self, out: &mut impl Write, title: &str) -> io::Result<()> {
+        self.write(out, &title_sequence(title))
+    }
+
+    /// Set the taskbar progress
+    pub fn set_progress(&self, out: &mut impl Write, progress: TaskbarProgress) -> io::Result<()> {
+        self.write(out, &progress.sequence())
+    }
+
+    /// Remove the progress indicator and restore the user's title
+    pub fn finish(&mut self, out: &mut impl Write) -> io::Result<()> {
+        if self.title_saved {
+            self.title_saved = false;
+            self.write(out, &TaskbarProgress::Hidden.sequence())?;
+            self.write(out, POP_TITLE)?;
+        }
+        Ok(())
+    }
+
+    /// Write a sequence if enabled
+    fn write(&self, out: &mut impl Write, sequence: &str) -> io::Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+        out.write_all(sequence.as_bytes())?;
+        out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_sequences() {
+        assert_eq!(TaskbarProgress::Hidden.sequence(), "\x1b]9;4;0;0\x07");
+        assert_eq!(TaskbarProgress::Normal(42).sequence(), "\x1b]9;4;1;42\x07");
+        assert_eq!(TaskbarProgress::Error(250).sequence(), "\x1b]9;4;2;100\x07");
+        assert_eq!(TaskbarProgress::Indeterminate.sequence(), "\x1b]9;4;3;0\x07");
+        assert_eq!(TaskbarProgress::of(1, 4), TaskbarProgress::Normal(25));
+        assert_eq!(TaskbarProgress::of(0, 0), TaskbarProgress::Indeterminate);
+    }
+
+    #[test]
+    fn test_title_sequence_drops_control_characters() {
+        assert_eq!(title_sequence("VenvCleaner\x07 - ~/work\n"), "\x1b]0;VenvCleaner - ~/work\x07");
+    }
+
+    #[test]
+    fn test_title_saved_and_restored_once() {
+        let mut out = Vec::new();
+        let mut progress = TerminalProgress::new(true);
+        progress.begin(&mut out).unwrap();
+        progress.begin(&mut out).unwrap();
+        progress.set_title(&mut out, "Scanning").unwrap();
+        progress.finish(&mut out).unwrap();
+        progress.finish(&mut out).unwrap();
+
+        let written = String::from_utf8(out).unwrap();
+        assert_eq!(written, format!("{}\x1b]0;Scanning\x07\x1b]9;4;0;0\x07{}", PUSH_TITLE, POP_TITLE));
+
+        // Disabled: nothing is written
+        let mut out = Vec::new();
+        let mut progress = TerminalProgress::new(false);
+        progress.begin(&mut out).unwrap();
+        progress.set_progress(&mut out, TaskbarProgress::Normal(10)).unwrap();
+        progress.finish(&mut out).unwrap();
+        assert!(out.is_empty());
+    }
+}
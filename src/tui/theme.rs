@@ -0,0 +1,180 @@
+//! Color themes for the TUI mode
+//!
+//! The TUI draws with a small palette of semantic colors. The palette starts
+//! from a base theme — the default colors or a monochrome one for terminals
+//! without color support — and individual colors can be overridden from the
+//! `[tui]` table of the configuration file.
+
+use std::str::FromStr;
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::core::{ColorOverrides, Result, TuiConfig, VenvCleanerError};
+
+/// Semantic colors used throughout the TUI
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    /// Borders and headings
+    pub primary: Color,
+    /// Labels and secondary borders
+    pub secondary: Color,
+    /// Positive states
+    pub success: Color,
+    /// Warnings and large sizes
+    pub warning: Color,
+    /// Errors and old environments
+    pub error: Color,
+    /// De-emphasized text
+    pub muted: Color,
+    /// Background of the highlighted row
+    pub selected: Color,
+    /// Highlighted path and the focused pane's border
+    pub highlight: Color,
+    /// Whether highlighted rows are drawn in reverse video instead of a background color
+    pub reverse_selection: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            primary: Color::Cyan,
+            secondary: Color::Yellow,
+            success: Color::Green,
+            warning: Color::Yellow,
+            error: Color::Red,
+            muted: Color::Gray,
+            selected: Color::Blue,
+            highlight: Color::Magenta,
+            reverse_selection: false,
+        }
+    }
+}
+
+impl Theme {
+    /// Names of the base themes accepted by `theme` in the config file
+    pub const NAMES: [&'static str; 2] = ["default", "monochrome"];
+
+    /// A theme using only the terminal's own foreground and background
+    pub fn monochrome() -> Self {
+        Self {
+            primary: Color::Reset,
+            secondary: Color::Reset,
+            success: Color::Reset,
+            warning: Color::Reset,
+            error: Color::Reset,
+            muted: Color::Reset,
+            selected: Color::Reset,
+            highlight: Color::Reset,
+            reverse_selection: true,
+        }
+    }
+
+    /// Get a base theme by name
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::default()),
+            "monochrome" => Some(Self::monochrome()),
+            _ => None,
+        }
+    }
+
+    /// Build the theme described by the `[tui]` table of the config file
+    ///
+    /// # Arguments
+    /// * `config` - The TUI settings
+    ///
+    /// # Returns
+    /// The theme or an error naming the unknown theme or invalid color
+    pub fn from_config(config: &TuiConfig) -> Result<Self> {
+        let mut theme = match config.theme.as_deref() {
+            None => Self::default(),
+            Some(name) => Self::from_name(name).ok_or_else(|| {
+                VenvCleanerError::InvalidArgument(format!(
+                    "Unknown TUI theme '{}' (expected one of: {})",
+                    name,
+                    Self::NAMES.join(", ")
+                ))
+            })?,
+        };
+        theme.apply_overrides(&config.colors)?;
+        Ok(theme)
+    }
+
+    /// Replace colors with those set in the config file
+    fn apply_overrides(&mut self, colors: &ColorOverrides) -> Result<()> {
+        let slots = [
+            ("primary", &colors.primary, &mut self.primary),
+            ("secondary", &colors.secondary, &mut self.secondary),
+            ("success", &colors.success, &mut self.success),
+            ("warning", &colors.warning, &mut self.warning),
+            ("error", &colors.error, &mut self.error),
+            ("muted", &colors.muted, &mut self.muted),
+            ("selected", &colors.selected, &mut self.selected),
+            ("highlight", &colors.highlight, &mut self.highlight),
+        ];
+
+        for (key, value, slot) in slots {
+            if let Some(value) = value {
+                *slot = Color::from_str(value).map_err(|_| {
+                    VenvCleanerError::InvalidArgument(format!("Invalid TUI color {} = '{}'", key, value))
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Style of the highlighted row in a list
+    ///
+    /// # Arguments
+    /// * `background` - Background color to use unless the theme reverses rows
+    pub fn selection_style(&self, background: Color) -> Style {
+        let style = Style::default().add_modifier(Modifier::BOLD);
+        if self.reverse_selection {
+            style.add_modifier(Modifier::REVERSED)
+        } else {
+            style.bg(background)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_gives_default_theme() {
+        assert_eq!(Theme::from_config(&TuiConfig::default()).unwrap(), Theme::default());
+    }
+
+    #[test]
+    fn test_monochrome_with_overrides() {
+        let config = TuiConfig {
+            theme: Some("monochrome".to_string()),
+            colors: ColorOverrides {
+                error: Some("light-red".to_string()),
+                highlight: Some("#ff8800".to_string()),
+                selected: Some("208".to_string()),
+                ..ColorOverrides::default()
+            },
+            ..TuiConfig::default()
+        };
+
+        let theme = Theme::from_config(&config).unwrap();
+        assert_eq!(theme.primary, Color::Reset);
+        assert_eq!(theme.error, Color::LightRed);
+        assert_eq!(theme.highlight, Color::Rgb(0xff, 0x88, 0x00));
+        assert_eq!(theme.selected, Color::Indexed(208));
+        assert!(theme.selection_style(theme.selected).add_modifier.contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn test_invalid_theme_and_color() {
+        let unknown = TuiConfig { theme: Some("solarized".to_string()), ..TuiConfig::default() };
+        assert!(Theme::from_config(&unknown).is_err());
+
+        let bad_color = TuiConfig {
+            colors: ColorOverrides { primary: Some("not-a-color".to_string()), ..ColorOverrides::default() },
+            ..TuiConfig::default()
+        };
+        assert!(Theme::from_config(&bad_color).is_err());
+    }
+}
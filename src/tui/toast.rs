@@ -0,0 +1,167 @@
+//! Status bar notifications of the TUI
+//!
+//! Messages used to replace each other in a single status line, so a
+//! "Sorted by size" right after a failed deletion wiped the warning before
+//! anyone had read it. Every message is now a toast with a severity: the
+//! status bar shows the most severe toast that has not expired (the newest
+//! of equals), and once all have expired, the newest one. Info toasts
+//! expire after a few seconds, warnings and errors stay up longer, and the
+//! most recent toasts are kept for the history opened with `!`.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+
+/// Number of toasts kept for the history
+const HISTORY_LEN: usize = 100;
+
+/// How important a toast is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Progress and confirmations, such as "Sorted by size"
+    Info,
+    /// Something needs a look but nothing failed
+    Warning,
+    /// An action failed
+    Error,
+}
+
+impl Severity {
+    /// Get how long a toast of this severity outranks newer, less severe ones
+    pub fn lifetime(self) -> Duration {
+        match self {
+            Severity::Info => Duration::from_secs(4),
+            Severity::Warning => Duration::from_secs(15),
+            Severity::Error => Duration::from_secs(30),
+        }
+    }
+
+    /// Get the label shown in the history
+    pub fn label(self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+/// One notification
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toast {
+    /// The message
+    message: String,
+    /// How important it is
+    severity: Severity,
+    /// When it was raised, for expiry
+    raised: Instant,
+    /// When it was raised, for the history
+    raised_at: DateTime<Utc>,
+}
+
+impl Toast {
+    /// Get the message
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Get how important the toast is
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Get when the toast was raised
+    pub fn raised_at(&self) -> &DateTime<Utc> {
+        &self.raised_at
+    }
+
+    /// Check whether the toast has expired at a given time
+    fn is_expired(&self, now: Instant) -> bool {
+        now.duration_since(self.raised) >= self.severity.lifetime()
+    }
+}
+
+/// The toasts raised in this session, oldest first
+#[derive(Debug, Clone, Default)]
+pub struct Toasts {
+    /// The most recent toasts, oldest first
+    history: VecDeque<Toast>,
+}
+
+impl Toasts {
+    /// Raise a toast now
+    ///
+    /// # Arguments
+    /// * `severity` - How important the message is
+    /// * `message` - The message
+    pub fn push(&mut self, severity: Severity, message: String) {
+        self.push_at(severity, message, Instant::now());
+    }
+
+    /// Raise a toast at a given time
+    fn push_at(&mut self, severity: Severity, message: String, now: Instant) {
+        if self.history.len() == HISTORY_LEN {
+            self.history.pop_front();
+        }
+        self.history.push_back(Toast { message, severity, raised: now, raised_at: Utc::now() });
+    }
+
+    /// Get the toast the status bar shows now
+    pub fn current(&self) -> Option<&Toast> {
+        self.current_at(Instant::now())
+    }
+
+    /// Get the toast the status bar shows at a given time
+    ///
+    /// The most severe live toast wins, the newest among equals; with none
+    /// live, the newest toast stays up so the status bar is never blank.
+    fn current_at(&self, now: Instant) -> Option<&Toast> {
+        // max_by_key keeps the last of equals, so walk oldest to newest
+        self.history
+            .iter()
+            .filter(|toast| !toast.is_expired(now))
+            .max_by_key(|toast| toast.severity)
+            .or_else(|| self.history.back())
+    }
+
+    /// Get the kept toasts, newest first
+    pub fn history(&self) -> impl Iterator<Item = &Toast> {
+        self.history.iter().rev()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_info_does_not_hide_a_live_warning() {
+        let start = Instant::now();
+        let mut toasts = Toasts::default();
+        assert!(toasts.current_at(start).is_none());
+
+        toasts.push_at(Severity::Warning, "3 deletions failed".to_string(), start);
+        toasts.push_at(Severity::Info, "Copied path".to_string(), start + Duration::from_secs(1));
+        assert_eq!(toasts.current_at(start + Duration::from_secs(2)).unwrap().message(), "3 deletions failed");
+
+        // Once the warning expires the newest message shows again
+        let later = start + Severity::Warning.lifetime();
+        assert_eq!(toasts.current_at(later).unwrap().message(), "Copied path");
+        toasts.push_at(Severity::Error, "Export failed".to_string(), later);
+        toasts.push_at(Severity::Error, "Restore failed".to_string(), later);
+        assert_eq!(toasts.current_at(later).unwrap().message(), "Restore failed");
+    }
+
+    #[test]
+    fn test_history_is_capped_and_newest_first() {
+        let start = Instant::now();
+        let mut toasts = Toasts::default();
+        for index in 0..HISTORY_LEN + 5 {
+            toasts.push_at(Severity::Info, format!("message {}", index), start);
+        }
+        let history: Vec<&Toast> = toasts.history().collect();
+        assert_eq!(history.len(), HISTORY_LEN);
+        assert_eq!(history[0].message(), format!("message {}", HISTORY_LEN + 4));
+        assert_eq!(history[HISTORY_LEN - 1].message(), "message 5");
+    }
+}
@@ -0,0 +1,175 @@
+//! Directory tree pane for the TUI mode
+//!
+//! This module flattens the scanned hierarchy into the directories leading
+//! from the scan root to each .venv's project, with the number and total
+//! size of the environments below every directory. Highlighting a directory
+//! in the tree pane restricts the .venv list to that subtree.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::core::VenvInfo;
+
+/// A directory in the tree pane
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeNode {
+    /// Path of the directory
+    pub path: PathBuf,
+    /// Nesting level below the scan root (the root itself is 0)
+    pub depth: usize,
+    /// Number of .venv directories in this subtree
+    pub venv_count: usize,
+    /// Total size of the .venv directories in this subtree
+    pub total_size: u64,
+}
+
+impl TreeNode {
+    /// Get the name shown for this directory (the full path for the root)
+    pub fn name(&self) -> String {
+        if self.depth == 0 {
+            return self.path.display().to_string();
+        }
+        self.path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.path.display().to_string())
+    }
+}
+
+/// The scanned hierarchy, flattened in display (pre-)order
+#[derive(Debug, Clone, Default)]
+pub struct DirTree {
+    /// Directories, each directly followed by its subdirectories
+    nodes: Vec<TreeNode>,
+}
+
+impl DirTree {
+    /// Build the tree for a scan
+    ///
+    /// Every directory between the root and a .venv's project directory is
+    /// included; environments outside the root are attributed to the root.
+    ///
+    /// # Arguments
+    /// * `root` - The scanned base directory
+    /// * `venvs` - The .venv directories found by the scan
+    pub fn build(root: &Path, venvs: &[VenvInfo]) -> Self {
+        // Paths order component-wise, so each directory sorts right before its children
+        let mut totals: BTreeMap<PathBuf, (usize, u64)> = BTreeMap::new();
+        totals.insert(root.to_path_buf(), (0, 0));
+
+        for venv in venvs {
+            let project = venv.parent_path().unwrap_or(root);
+            let chain: Vec<&Path> = if project.starts_with(root) {
+                project.ancestors().take_while(|dir| dir.starts_with(root)).collect()
+            } else {
+                vec![root]
+            };
+
+            for dir in chain {
+                let entry = totals.entry(dir.to_path_buf()).or_insert((0, 0));
+                entry.0 += 1;
+                entry.1 += venv.size().bytes();
+            }
+        }
+
+        let root_depth = root.components().count();
+        let nodes = totals
+            .into_iter()
+            .map(|(path, (venv_count, total_size))| TreeNode {
+                depth: path.components().count() - root_depth,
+                path,
+                venv_count,
+                total_size,
+            })
+            .collect();
+
+        Self { nodes }
+    }
+
+    /// Get the directories in display order
+    pub fn nodes(&self) -> &[TreeNode] {
+        &self.nodes
+    }
+
+    /// Get the number of directories
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Check whether the tree has no directories
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Find the index of a directory
+    pub fn position(&self, path: &Path) -> Option<usize> {
+        self.nodes.iter().position(|node| node.path == path)
+    }
+
+    /// Find the index of a directory's parent
+    ///
+    /// # Arguments
+    /// * `index` - Index of the directory
+    ///
+    /// # Returns
+    /// The parent's index, or None for the root
+    pub fn parent_of(&self, index: usize) -> Option<usize> {
+        let depth = self.nodes.get(index)?.depth;
+        (0..index).rev().find(|&i| self.nodes[i].depth < depth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ByteSize;
+    use chrono::Utc;
+
+    fn venv(path: &str, size: u64) -> VenvInfo {
+        VenvInfo::new(PathBuf::from(path), ByteSize::new(size), Utc::now(), Utc::now())
+    }
+
+    #[test]
+    fn test_build_totals_per_subtree() {
+        let tree = DirTree::build(Path::new("/work"), &[
+            venv("/work/clientA/api/.venv", 100),
+            venv("/work/clientA/web/.venv", 50),
+            venv("/work/tools/.venv", 25),
+        ]);
+
+        let summary: Vec<(String, usize, usize, u64)> = tree
+            .nodes()
+            .iter()
+            .map(|n| (n.name(), n.depth, n.venv_count, n.total_size))
+            .collect();
+        assert_eq!(summary, vec![
+            ("/work".to_string(), 0, 3, 175),
+            ("clientA".to_string(), 1, 2, 150),
+            ("api".to_string(), 2, 1, 100),
+            ("web".to_string(), 2, 1, 50),
+            ("tools".to_string(), 1, 1, 25),
+        ]);
+    }
+
+    #[test]
+    fn test_parent_of() {
+        let tree = DirTree::build(Path::new("/work"), &[
+            venv("/work/clientA/api/.venv", 1),
+            venv("/work/tools/.venv", 1),
+        ]);
+
+        let api = tree.position(Path::new("/work/clientA/api")).unwrap();
+        let tools = tree.position(Path::new("/work/tools")).unwrap();
+        assert_eq!(tree.parent_of(api), tree.position(Path::new("/work/clientA")));
+        assert_eq!(tree.parent_of(tools), Some(0));
+        assert_eq!(tree.parent_of(0), None);
+    }
+
+    #[test]
+    fn test_venv_outside_root_counts_at_root() {
+        let tree = DirTree::build(Path::new("/work"), &[venv("/elsewhere/.venv", 10)]);
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree.nodes()[0].venv_count, 1);
+        assert_eq!(tree.nodes()[0].total_size, 10);
+    }
+}
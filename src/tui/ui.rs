@@ -13,62 +13,112 @@ use ratatui::{
     },
 };
 
-use super::{TuiApp, SortBy, AppState};
-
-/// Colors used throughout the TUI
-pub struct Colors;
-
-impl Colors {
-    pub const PRIMARY: Color = Color::Cyan;
-    pub const SECONDARY: Color = Color::Yellow;
-    pub const SUCCESS: Color = Color::Green;
-    pub const WARNING: Color = Color::Yellow;
-    pub const ERROR: Color = Color::Red;
-    pub const MUTED: Color = Color::Gray;
-    pub const SELECTED: Color = Color::Blue;
-    pub const HIGHLIGHT: Color = Color::Magenta;
-}
+use crate::core::{deletion_eta, help, ownership, roots, timestamps, AgeBucket, ByteSize, FileUtils, RootSummary, SizeDeltas, VenvInfo};
+use super::glyphs::Glyphs;
+use super::theme::Theme;
+use super::toast::Severity;
+use super::{PaneFocus, TuiApp, SortBy, AppState};
+
+/// Height of the log pane, including its border
+const LOG_PANE_HEIGHT: u16 = 8;
 
 /// Draw the main browsing screen
 pub fn draw_main_screen(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
+    let log_height = if app.is_log_shown() { LOG_PANE_HEIGHT } else { 0 };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3),  // Header
-            Constraint::Min(0),     // Main content
-            Constraint::Length(3),  // Footer
+            Constraint::Length(3),          // Header
+            Constraint::Min(0),             // Main content
+            Constraint::Length(log_height), // Log pane
+            Constraint::Length(3),          // Footer
         ])
         .split(area);
 
     // Header
     draw_header(f, chunks[0], app);
 
-    // Main content area
-    let main_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
+    // Main content area, with the directory tree on the left when shown
+    let constraints = if app.is_tree_shown() {
+        vec![
+            Constraint::Percentage(25), // Directory tree
+            Constraint::Percentage(50), // File list
+            Constraint::Percentage(25), // Details panel
+        ]
+    } else {
+        vec![
             Constraint::Percentage(70), // File list
             Constraint::Percentage(30), // Details panel
-        ])
+        ]
+    };
+    let main_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(constraints)
         .split(chunks[1]);
+    let (list_area, details_area) = if app.is_tree_shown() {
+        draw_tree_pane(f, main_chunks[0], app);
+        (main_chunks[1], main_chunks[2])
+    } else {
+        (main_chunks[0], main_chunks[1])
+    };
 
     // File list
-    draw_venv_list(f, main_chunks[0], app);
+    draw_venv_list(f, list_area, app);
 
     // Details panel
-    draw_details_panel(f, main_chunks[1], app);
+    draw_details_panel(f, details_area, app);
+
+    if app.is_log_shown() {
+        draw_log_pane(f, chunks[2], app);
+    }
 
     // Footer
-    draw_footer(f, chunks[2], app);
+    draw_footer(f, chunks[3], app);
+}
+
+/// Draw the most recent log lines
+fn draw_log_pane(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
+    let theme = app.theme();
+    let logs = app.logs();
+    let rows = area.height.saturating_sub(2) as usize;
+
+    let lines: Vec<Line> = if logs.lines().len() == 0 {
+        vec![Line::from(Span::styled("No log lines yet", Style::default().fg(theme.muted)))]
+    } else {
+        let skip = logs.lines().len().saturating_sub(rows);
+        logs.lines()
+            .skip(skip)
+            .map(|line| {
+                let color = match line.level {
+                    log::Level::Error => theme.error,
+                    log::Level::Warn => theme.warning,
+                    log::Level::Info => theme.primary,
+                    _ => theme.muted,
+                };
+                Line::from(Span::styled(line.formatted(), Style::default().fg(color)))
+            })
+            .collect()
+    };
+
+    let title = format!("Log ({} warnings) - L to hide", logs.warning_count());
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.muted))
+            .title(title),
+    );
+    f.render_widget(paragraph, area);
 }
 
 /// Draw the header section
 fn draw_header(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
+    let theme = app.theme();
     let search_mode = if app.is_recursive() { " (Recursive)" } else { " (Current Dir)" };
-    let title = format!("VenvCleaner - {}{}", app.current_directory().display(), search_mode);
+    let read_only = if app.is_read_only() { " 🔒 READ-ONLY" } else { "" };
+    let title = format!("VenvCleaner - {}{}{}", app.current_directory().display(), search_mode, read_only);
     let sort_info = format!("Sort: {} {}",
         app.sort_by().display_name(),
-        if app.sort_by() == SortBy::Size { "↓" } else { "↑" }
+        if matches!(app.sort_by(), SortBy::Size | SortBy::Files) { "↓" } else { "↑" }
     );
 
     let header_chunks = Layout::default()
@@ -81,15 +131,23 @@ fn draw_header(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
 
     let title_block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Colors::PRIMARY))
+        .border_style(Style::default().fg(theme.primary))
         .title(title);
 
-    let title_paragraph = Paragraph::new("")
+    // Where deletions go stays in view, so a permanent delete never comes as a surprise
+    let delete_mode = if app.is_read_only() {
+        Line::from("")
+    } else if app.trashes_by_default() {
+        Line::from(Span::styled(format!("♻️  {}", app.delete_mode()), Style::default().fg(theme.success)))
+    } else {
+        Line::from(Span::styled(format!("🔥 {}", app.delete_mode()), Style::default().fg(theme.error).add_modifier(Modifier::BOLD)))
+    };
+    let title_paragraph = Paragraph::new(delete_mode)
         .block(title_block);
 
     let sort_block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Colors::SECONDARY))
+        .border_style(Style::default().fg(theme.secondary))
         .title("Sort");
 
     let sort_paragraph = Paragraph::new(sort_info)
@@ -100,112 +158,102 @@ fn draw_header(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
     f.render_widget(sort_paragraph, header_chunks[1]);
 }
 
+/// Draw the directory tree pane with per-subtree .venv totals
+fn draw_tree_pane(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
+    let theme = app.theme();
+    let items: Vec<ListItem> = app
+        .tree()
+        .nodes()
+        .iter()
+        .map(|node| {
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{}{} ", "  ".repeat(node.depth), node.name())),
+                Span::styled(
+                    format!("{} · {}", node.venv_count, ByteSize::new(node.total_size)),
+                    Style::default().fg(theme.muted),
+                ),
+            ]))
+        })
+        .collect();
+
+    // The border shows which pane the arrow keys move
+    let focused = app.pane_focus() == PaneFocus::Tree;
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(if focused { theme.highlight } else { theme.muted }))
+                .title("📁 Directories (Tab)")
+        )
+        .highlight_style(theme.selection_style(if focused { theme.selected } else { Color::DarkGray }));
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(app.tree_index()));
+    f.render_stateful_widget(list, area, &mut list_state);
+}
+
 /// Draw the .venv directory list
 fn draw_venv_list(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
+    let theme = app.theme();
+    let glyphs = app.glyphs();
     let venvs = app.venvs();
     let selected_index = app.selected_index();
     let selected_venvs = app.selected_venvs();
 
-    // Get visible range without mutating app
-    let list_height = area.height.saturating_sub(2); // Account for borders
-    let visible_items = list_height as usize;
-
-    let scroll_offset = app.scroll_offset();
-    let start = scroll_offset;
-    let end = (start + visible_items).min(venvs.len());
-    let visible_venvs = &venvs[start..end];
-
-    let items: Vec<ListItem> = visible_venvs
-        .iter()
-        .enumerate()
-        .map(|(i, venv)| {
-            let actual_index = start + i;
-            let is_selected = actual_index == selected_index;
-            let is_marked = selected_venvs.contains(&actual_index);
-
-            let mut spans = vec![];
-
-            // Selection indicator
-            if is_marked {
-                spans.push(Span::styled("✓ ", Style::default().fg(Colors::SUCCESS)));
-            } else {
-                spans.push(Span::raw("  "));
-            }
-
-            // Age indicator
-            if venv.is_recently_used() {
-                spans.push(Span::styled("🟢 ", Style::default()));
-            } else if venv.is_old() {
-                spans.push(Span::styled("🔴 ", Style::default()));
-            } else {
-                spans.push(Span::styled("🟡 ", Style::default()));
-            }
-
-            // Path
-            let path_text = format_path_for_display(&venv.location(), 40);
-            spans.push(Span::styled(
-                format!("{:<40}", path_text),
-                if is_selected {
-                    Style::default().fg(Colors::HIGHLIGHT).add_modifier(Modifier::BOLD)
-                } else {
-                    Style::default()
-                }
-            ));
-
-            // Size
-            let size_text = venv.size_formatted();
-            let size_color = if venv.size_bytes() > 1024 * 1024 * 1024 {
-                Colors::ERROR
-            } else if venv.size_bytes() > 100 * 1024 * 1024 {
-                Colors::WARNING
-            } else {
-                Colors::MUTED
-            };
-
-            spans.push(Span::styled(
-                format!("{:>12}", size_text),
-                Style::default().fg(size_color)
-            ));
-
-            // Last used
-            let age_text = format!("{}d", venv.age_in_days());
-            spans.push(Span::styled(
-                format!("{:>6}", age_text),
-                Style::default().fg(Colors::MUTED)
-            ));
-
-            ListItem::new(Line::from(spans))
-        })
-        .collect();
+    let (items, highlight_index) = if app.is_grouped_by_age() {
+        grouped_list_items(app)
+    } else if app.is_multi_root() {
+        root_list_items(app)
+    } else if app.tree_filter().is_some() {
+        filtered_list_items(app)
+    } else {
+        // Get visible range without mutating app
+        let list_height = area.height.saturating_sub(2); // Account for borders
+        let visible_items = list_height as usize;
+
+        let start = app.scroll_offset();
+        let end = (start + visible_items).min(venvs.len());
+        let show_files = app.sort_by() == SortBy::Files;
+        let items: Vec<ListItem> = venvs[start..end]
+            .iter()
+            .enumerate()
+            .map(|(i, venv)| {
+                let actual_index = start + i;
+                venv_list_item(theme, glyphs, venv, actual_index == selected_index, selected_venvs.contains(&actual_index), show_files, app.shown_size_deltas())
+            })
+            .collect();
+
+        // Calculate the highlight index relative to the visible range
+        let highlight_index = if selected_index >= start && selected_index < end {
+            Some(selected_index - start)
+        } else {
+            None
+        };
+        (items, highlight_index)
+    };
 
-    let list_title = format!(".venv Directories ({}/{})",
-        venvs.len(),
+    let shown = match app.tree_filter() {
+        Some(node) => format!("{} in {}", node.venv_count, node.name()),
+        None => venvs.len().to_string(),
+    };
+    let list_title = format!(".venv Directories ({}/{}){}",
+        shown,
         if selected_venvs.is_empty() {
             "none selected".to_string()
         } else {
             format!("{} selected", selected_venvs.len())
-        }
+        },
+        if app.shown_size_deltas().is_some() { " · Δ since last scan" } else { "" }
     );
 
     let list = List::new(items)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Colors::PRIMARY))
+                .border_style(Style::default().fg(theme.primary))
                 .title(list_title)
         )
-        .highlight_style(
-            Style::default()
-                .bg(Colors::SELECTED)
-                .add_modifier(Modifier::BOLD)
-        );
-
-    // Calculate the highlight index relative to the visible range
-    let highlight_index = if selected_index >= start && selected_index < end {
-        Some(selected_index - start)
-    } else {
-        None
-    };
+        .highlight_style(theme.selection_style(theme.selected));
 
     let mut list_state = ListState::default();
     if let Some(index) = highlight_index {
@@ -215,14 +263,227 @@ fn draw_venv_list(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
     f.render_stateful_widget(list, area, &mut list_state);
 }
 
+/// Build the list rows for the .venv directories under the tree pane's directory
+///
+/// # Returns
+/// The rows and the row index of the highlighted .venv, if visible
+fn filtered_list_items(app: &TuiApp) -> (Vec<ListItem<'static>>, Option<usize>) {
+    let indices = app.filtered_indices();
+    let highlight_index = indices.iter().position(|&i| i == app.selected_index());
+    let items = indices
+        .into_iter()
+        .map(|i| venv_list_item(app.theme(), app.glyphs(), &app.venvs()[i], i == app.selected_index(), app.is_item_selected(i), app.sort_by() == SortBy::Files, app.shown_size_deltas()))
+        .collect();
+    (items, highlight_index)
+}
+
+/// Build the list rows with one collapsible heading per age bucket
+///
+/// All rows are returned; the list widget scrolls to keep the highlighted
+/// row in view, since headings make item indices and rows diverge.
+///
+/// # Returns
+/// The rows and the row index of the highlighted .venv, if visible
+fn grouped_list_items(app: &TuiApp) -> (Vec<ListItem<'static>>, Option<usize>) {
+    let theme = app.theme();
+    let glyphs = app.glyphs();
+    let mut items = Vec::new();
+    let mut highlight_index = None;
+
+    for (number, bucket) in AgeBucket::ALL.iter().enumerate() {
+        let members: Vec<(usize, &VenvInfo)> = app
+            .venvs()
+            .iter()
+            .enumerate()
+            .filter(|(_, venv)| venv.age_bucket() == *bucket && app.matches_tree_filter(venv))
+            .collect();
+        if members.is_empty() {
+            continue;
+        }
+
+        let collapsed = app.is_bucket_collapsed(*bucket);
+        let total: ByteSize = members.iter().map(|(_, venv)| venv.size()).sum();
+        items.push(ListItem::new(Line::from(vec![Span::styled(
+            format!(
+                "[{}] {} {} — {} dirs, {}",
+                number + 1,
+                if collapsed { "▶" } else { "▼" },
+                bucket.label(),
+                members.len(),
+                total
+            ),
+            Style::default().fg(theme.primary).add_modifier(Modifier::BOLD),
+        )])));
+
+        if collapsed {
+            continue;
+        }
+        for (index, venv) in members {
+            if index == app.selected_index() {
+                highlight_index = Some(items.len());
+            }
+            items.push(venv_list_item(theme, glyphs, venv, index == app.selected_index(), app.is_item_selected(index), app.sort_by() == SortBy::Files, app.shown_size_deltas()));
+        }
+    }
+
+    (items, highlight_index)
+}
+
+/// Build the list rows with a heading and subtotals for each scanned root
+///
+/// As with age buckets, all rows are returned and the list widget scrolls.
+///
+/// # Returns
+/// The rows and the row index of the highlighted .venv, if visible
+fn root_list_items(app: &TuiApp) -> (Vec<ListItem<'static>>, Option<usize>) {
+    let theme = app.theme();
+    let glyphs = app.glyphs();
+    let roots = app.root_paths();
+    let visible: Vec<VenvInfo> = app.venvs().iter().filter(|venv| app.matches_tree_filter(venv)).cloned().collect();
+    let mut items = Vec::new();
+    let mut highlight_index = None;
+
+    for (position, summary) in RootSummary::for_roots(&roots, &visible).into_iter().enumerate() {
+        items.push(ListItem::new(Line::from(vec![Span::styled(
+            format!(
+                "📂 {} — {} dirs, {}, {} reclaimable",
+                format_path_for_display(&summary.root.display().to_string(), 40),
+                summary.count,
+                ByteSize::new(summary.total_bytes),
+                ByteSize::new(summary.reclaimable_bytes)
+            ),
+            Style::default().fg(theme.primary).add_modifier(Modifier::BOLD),
+        )])));
+
+        for (index, venv) in app.venvs().iter().enumerate() {
+            if roots::root_index(&roots, venv.path()) != Some(position) || !app.matches_tree_filter(venv) {
+                continue;
+            }
+            if index == app.selected_index() {
+                highlight_index = Some(items.len());
+            }
+            items.push(venv_list_item(theme, glyphs, venv, index == app.selected_index(), app.is_item_selected(index), app.sort_by() == SortBy::Files, app.shown_size_deltas()));
+        }
+    }
+
+    (items, highlight_index)
+}
+
+/// Build the list row for a single .venv directory
+fn venv_list_item(
+    theme: &Theme,
+    glyphs: &Glyphs,
+    venv: &VenvInfo,
+    is_selected: bool,
+    is_marked: bool,
+    show_files: bool,
+    size_deltas: Option<&SizeDeltas>,
+) -> ListItem<'static> {
+    let mut spans = vec![];
+
+    // Selection indicator
+    if is_marked {
+        spans.push(Span::styled(glyphs.marked, Style::default().fg(theme.success)));
+    } else {
+        spans.push(Span::raw("  "));
+    }
+
+    // Age indicator
+    if venv.is_recently_used() {
+        spans.push(age_marker(glyphs, glyphs.recent, theme.success));
+    } else if venv.is_old() {
+        spans.push(age_marker(glyphs, glyphs.old, theme.error));
+    } else {
+        spans.push(age_marker(glyphs, glyphs.moderate, theme.warning));
+    }
+
+    // Path, with room for the user data, editable and project kind badges when needed
+    let badges = usize::from(venv.has_user_data()) + usize::from(venv.has_editable_install());
+    let kind_width = venv.project_kind().map_or(0, |kind| kind.badge().chars().count() + 3);
+    let path_width = 40 - 2 * badges - kind_width;
+    let path_text = format_path_for_display(&venv.location(), path_width);
+    if venv.has_user_data() {
+        spans.push(Span::styled(glyphs.user_data, Style::default().fg(theme.error)));
+    }
+    if venv.has_editable_install() {
+        spans.push(Span::styled(glyphs.editable, Style::default().fg(theme.warning)));
+    }
+    if let Some(kind) = venv.project_kind() {
+        spans.push(Span::styled(format!("[{}] ", kind.badge()), Style::default().fg(theme.secondary)));
+    }
+    spans.push(Span::styled(
+        format!("{:<width$}", path_text, width = path_width),
+        if is_selected {
+            Style::default().fg(theme.highlight).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        }
+    ));
+
+    // Size
+    let size_text = venv.size_formatted();
+    let size_color = if venv.size().bytes() > 1024 * 1024 * 1024 {
+        theme.error
+    } else if venv.size().bytes() > 100 * 1024 * 1024 {
+        theme.warning
+    } else {
+        theme.muted
+    };
+
+    spans.push(Span::styled(
+        format!("{:>12}", size_text),
+        Style::default().fg(size_color)
+    ));
+
+    // Change since the last scan, the largest growth highlighted (`D`)
+    if let Some(size_deltas) = size_deltas {
+        let style = match size_deltas.get(venv.path()) {
+            _ if size_deltas.is_top_growth(venv.path()) => Style::default().fg(theme.error).add_modifier(Modifier::BOLD),
+            Some(delta) if delta > 0 => Style::default().fg(theme.warning),
+            Some(delta) if delta < 0 => Style::default().fg(theme.success),
+            _ => Style::default().fg(theme.muted),
+        };
+        spans.push(Span::styled(format!("{:>12}", size_deltas.describe(venv.path())), style));
+    }
+
+    // File count, while sorting by it
+    if show_files {
+        spans.push(Span::styled(
+            format!("{:>10}", venv.file_count_formatted()),
+            Style::default().fg(theme.muted)
+        ));
+    }
+
+    // Last used
+    let age_text = format!("{}d", venv.age_in_days());
+    spans.push(Span::styled(
+        format!("{:>6}", age_text),
+        Style::default().fg(theme.muted)
+    ));
+
+    ListItem::new(Line::from(spans))
+}
+
+/// Build an age marker: emoji carry their own colors, ASCII markers take the theme's
+fn age_marker(glyphs: &Glyphs, glyph: &'static str, color: Color) -> Span<'static> {
+    if glyphs.colored {
+        Span::styled(glyph, Style::default().fg(color))
+    } else {
+        Span::raw(glyph)
+    }
+}
+
 /// Draw the details panel
 fn draw_details_panel(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
+    // Archived environments get their own section only when there are any
+    let constraints = if app.archived().is_empty() {
+        vec![Constraint::Percentage(60), Constraint::Percentage(40)]
+    } else {
+        vec![Constraint::Percentage(45), Constraint::Percentage(30), Constraint::Percentage(25)]
+    };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage(60), // Selected item details
-            Constraint::Percentage(40), // Summary statistics
-        ])
+        .constraints(constraints)
         .split(area);
 
     // Selected item details
@@ -230,62 +491,157 @@ fn draw_details_panel(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
 
     // Summary statistics
     draw_summary_stats(f, chunks[1], app);
+
+    if chunks.len() > 2 {
+        draw_archived_list(f, chunks[2], app);
+    }
+}
+
+/// Draw the archived .venv directories with their compressed size
+fn draw_archived_list(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
+    let theme = app.theme();
+    let items: Vec<ListItem> = app
+        .archived()
+        .iter()
+        .enumerate()
+        .map(|(index, archived)| {
+            let name = archived
+                .stub_path()
+                .parent()
+                .and_then(|p| p.file_name())
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| archived.location());
+            let size = match archived.compressed_size() {
+                Some(bytes) => Span::styled(ByteSize::new(bytes).to_string(), Style::default().fg(theme.secondary)),
+                None => Span::styled("missing", Style::default().fg(theme.error)),
+            };
+            let marker = if index == app.archived_index() { "▶ " } else { "  " };
+            ListItem::new(Line::from(vec![Span::raw(marker), Span::raw(format!("{} ", name)), size]))
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.secondary))
+            .title("📦 Archived (Shift+Tab u:Restore X:Delete)"),
+    );
+
+    f.render_widget(list, area);
 }
 
 /// Draw details for the selected .venv directory
 fn draw_selected_details(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
+    let theme = app.theme();
     let mut text = vec![];
 
     if let Some(venv) = app.selected_venv() {
         text.push(Line::from(vec![
-            Span::styled("Path: ", Style::default().fg(Colors::SECONDARY)),
+            Span::styled("Path: ", Style::default().fg(theme.secondary)),
             Span::raw(venv.path().display().to_string()),
         ]));
 
         text.push(Line::from(vec![
-            Span::styled("Size: ", Style::default().fg(Colors::SECONDARY)),
+            Span::styled("Size: ", Style::default().fg(theme.secondary)),
             Span::raw(venv.size_formatted()),
+            Span::styled(
+                if venv.is_size_partial() { " (partial, press z to size fully)" } else { "" },
+                Style::default().fg(theme.warning),
+            ),
         ]));
 
+        if let (Some(files), Some(dirs)) = (venv.file_count(), venv.dir_count()) {
+            text.push(Line::from(vec![
+                Span::styled("Files: ", Style::default().fg(theme.secondary)),
+                Span::raw(format!("{} ({} directories)", venv.file_count_formatted(), dirs)),
+                Span::styled(
+                    if files >= 100_000 { " – slow to delete" } else { "" },
+                    Style::default().fg(theme.warning),
+                ),
+            ]));
+        }
+
+        if let Some(filesystem) = venv.filesystem() {
+            text.push(Line::from(vec![
+                Span::styled("Filesystem: ", Style::default().fg(theme.secondary)),
+                Span::raw(filesystem.to_string()),
+            ]));
+        }
+
+        if let Some(volume) = venv.removable_volume() {
+            text.push(Line::from(vec![
+                Span::styled("Drive: ", Style::default().fg(theme.secondary)),
+                Span::raw(format!("{} (removable)", volume.display())),
+            ]));
+        }
+
+        // Only worth a line when other users' environments can be listed
+        if ownership::is_root() || venv.owner_uid() != ownership::current_uid() {
+            text.push(Line::from(vec![
+                Span::styled("Owner: ", Style::default().fg(theme.secondary)),
+                Span::raw(ownership::describe_owner(venv.owner_uid())),
+            ]));
+        }
+
+        if venv.has_user_data() {
+            text.push(Line::from(vec![
+                Span::styled("User data: ", Style::default().fg(theme.error).add_modifier(Modifier::BOLD)),
+                Span::raw(format!("{} file(s), {}", venv.user_data().len(), ByteSize::new(venv.user_data_bytes()))),
+            ]));
+        }
+
         text.push(Line::from(vec![
-            Span::styled("Created: ", Style::default().fg(Colors::SECONDARY)),
+            Span::styled("Created: ", Style::default().fg(theme.secondary)),
             Span::raw(venv.created_formatted()),
         ]));
 
         text.push(Line::from(vec![
-            Span::styled("Last Used: ", Style::default().fg(Colors::SECONDARY)),
+            Span::styled("Last Used: ", Style::default().fg(theme.secondary)),
             Span::raw(venv.last_modified_formatted()),
         ]));
 
+        if venv.project_last_commit().is_some() {
+            text.push(Line::from(vec![
+                Span::styled("Last Commit: ", Style::default().fg(theme.secondary)),
+                Span::raw(venv.project_last_commit_formatted()),
+            ]));
+        }
+
         text.push(Line::from(vec![
-            Span::styled("Age: ", Style::default().fg(Colors::SECONDARY)),
+            Span::styled("Age: ", Style::default().fg(theme.secondary)),
             Span::raw(format!("{} days", venv.age_in_days())),
         ]));
 
         text.push(Line::from(""));
 
         // Status indicators
+        let glyphs = app.glyphs();
         if venv.is_recently_used() {
             text.push(Line::from(vec![
-                Span::styled("🟢 ", Style::default()),
-                Span::styled("Recently used", Style::default().fg(Colors::SUCCESS)),
+                age_marker(glyphs, glyphs.recent, theme.success),
+                Span::styled("Recently used", Style::default().fg(theme.success)),
+            ]));
+        } else if venv.is_project_active() {
+            text.push(Line::from(vec![
+                age_marker(glyphs, glyphs.active_project, theme.warning),
+                Span::styled("Unchanged >90 days, but the project has recent commits", Style::default().fg(theme.warning)),
             ]));
         } else if venv.is_old() {
             text.push(Line::from(vec![
-                Span::styled("🔴 ", Style::default()),
-                Span::styled("Old (>90 days)", Style::default().fg(Colors::ERROR)),
+                age_marker(glyphs, glyphs.old, theme.error),
+                Span::styled("Old (>90 days)", Style::default().fg(theme.error)),
             ]));
         } else {
             text.push(Line::from(vec![
-                Span::styled("🟡 ", Style::default()),
-                Span::styled("Moderately used", Style::default().fg(Colors::WARNING)),
+                age_marker(glyphs, glyphs.moderate, theme.warning),
+                Span::styled("Moderately used", Style::default().fg(theme.warning)),
             ]));
         }
 
         if app.is_item_selected(app.selected_index()) {
             text.push(Line::from(vec![
-                Span::styled("✓ ", Style::default().fg(Colors::SUCCESS)),
-                Span::styled("Selected for deletion", Style::default().fg(Colors::SUCCESS)),
+                Span::styled(app.glyphs().marked, Style::default().fg(theme.success)),
+                Span::styled("Selected for deletion", Style::default().fg(theme.success)),
             ]));
         }
     } else {
@@ -296,7 +652,7 @@ fn draw_selected_details(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Colors::SECONDARY))
+                .border_style(Style::default().fg(theme.secondary))
                 .title("Details")
         )
         .wrap(Wrap { trim: true });
@@ -306,32 +662,37 @@ fn draw_selected_details(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
 
 /// Draw summary statistics
 fn draw_summary_stats(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
+    let theme = app.theme();
     let stats = app.get_summary_stats();
 
     let text = vec![
         Line::from(vec![
-            Span::styled("Total: ", Style::default().fg(Colors::SECONDARY)),
+            Span::styled("Total: ", Style::default().fg(theme.secondary)),
             Span::raw(format!("{} directories", stats.total_count)),
         ]),
         Line::from(vec![
-            Span::styled("Selected: ", Style::default().fg(Colors::SECONDARY)),
+            Span::styled("Selected: ", Style::default().fg(theme.secondary)),
             Span::raw(format!("{} directories", stats.selected_count)),
         ]),
         Line::from(vec![
-            Span::styled("Total Size: ", Style::default().fg(Colors::SECONDARY)),
-            Span::raw(format_size(stats.total_size)),
+            Span::styled("Total Size: ", Style::default().fg(theme.secondary)),
+            Span::raw(ByteSize::new(stats.total_size).to_string()),
         ]),
         Line::from(vec![
-            Span::styled("Selected Size: ", Style::default().fg(Colors::SECONDARY)),
-            Span::raw(format_size(stats.selected_size)),
+            Span::styled("Selected Size: ", Style::default().fg(theme.secondary)),
+            Span::raw(ByteSize::new(stats.selected_size).to_string()),
+        ]),
+        Line::from(vec![
+            Span::styled("Inodes: ", Style::default().fg(theme.secondary)),
+            Span::raw(FileUtils::format_count(stats.total_inodes)),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("🟢 Recent: ", Style::default().fg(Colors::SUCCESS)),
+            Span::styled(format!("{}Recent: ", app.glyphs().recent), Style::default().fg(theme.success)),
             Span::raw(format!("{}", stats.recent_count)),
         ]),
         Line::from(vec![
-            Span::styled("🔴 Old: ", Style::default().fg(Colors::ERROR)),
+            Span::styled(format!("{}Old: ", app.glyphs().old), Style::default().fg(theme.error)),
             Span::raw(format!("{}", stats.old_count)),
         ]),
     ];
@@ -340,7 +701,7 @@ fn draw_summary_stats(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Colors::SECONDARY))
+                .border_style(Style::default().fg(theme.secondary))
                 .title("Summary")
         );
 
@@ -349,6 +710,7 @@ fn draw_summary_stats(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
 
 /// Draw the footer with status and shortcuts
 fn draw_footer(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
+    let theme = app.theme();
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
@@ -357,32 +719,29 @@ fn draw_footer(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
         ])
         .split(area);
 
-    // Status
-    let status_text = app.status();
-    let status_paragraph = Paragraph::new(status_text)
+    // Status, coloured by the severity of the notification shown
+    let status_style = severity_style(theme, app.status_severity());
+    let status_paragraph = Paragraph::new(Span::styled(app.status(), status_style))
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Colors::MUTED))
-                .title("Status")
+                .border_style(Style::default().fg(theme.muted))
+                .title("Status (! history)")
         );
 
     // Shortcuts - show context-sensitive help
-    let shortcuts_text = match app.state() {
-        AppState::Browsing => {
-            if app.has_selected_items() {
-                "h:Help r:Refresh Space:Toggle x:Delete s:Sort o:Open Ctrl+A:All Ctrl+D:None q:Quit"
-            } else {
-                "h:Help r:Refresh Space:Select s:Sort o:Open Ctrl+A:Select All q:Quit"
-            }
-        }
-        _ => "h:Help r:Refresh Space:Select x:Delete s:Sort o:Open q:Quit"
+    let hints = match app.state() {
+        _ if app.is_read_only() => help::TUI_HINTS_READ_ONLY,
+        AppState::Browsing if app.has_selected_items() => help::TUI_HINTS_WITH_SELECTION,
+        AppState::Browsing => help::TUI_HINTS_BROWSING,
+        _ => help::TUI_HINTS_DEFAULT,
     };
+    let shortcuts_text = help::hint_line(hints);
     let shortcuts_paragraph = Paragraph::new(shortcuts_text)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Colors::MUTED))
+                .border_style(Style::default().fg(theme.muted))
                 .title("Shortcuts")
         );
 
@@ -392,6 +751,7 @@ fn draw_footer(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
 
 /// Draw the loading screen
 pub fn draw_loading_screen(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
+    let theme = app.theme();
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -406,11 +766,11 @@ pub fn draw_loading_screen(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
     // Title
     let title_text = vec![
         Line::from(vec![
-            Span::styled("VenvCleaner TUI", Style::default().fg(Colors::PRIMARY).add_modifier(Modifier::BOLD)),
+            Span::styled("VenvCleaner TUI", Style::default().fg(theme.primary).add_modifier(Modifier::BOLD)),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("🔍 Scanning for .venv directories...", Style::default().fg(Colors::SECONDARY)),
+            Span::styled("🔍 Scanning for .venv directories...", Style::default().fg(theme.secondary)),
         ]),
     ];
 
@@ -419,24 +779,24 @@ pub fn draw_loading_screen(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Colors::PRIMARY))
+                .border_style(Style::default().fg(theme.primary))
                 .title("Initializing")
         );
 
     // Loading animation
     let loading_text = format!("Loading{}", ".".repeat(app.loading_dots()));
     let loading_paragraph = Paragraph::new(loading_text)
-        .style(Style::default().fg(Colors::WARNING).add_modifier(Modifier::BOLD))
+        .style(Style::default().fg(theme.warning).add_modifier(Modifier::BOLD))
         .alignment(Alignment::Center);
 
     // Status and directory info
     let status_lines = vec![
         Line::from(vec![
-            Span::styled("Directory: ", Style::default().fg(Colors::SECONDARY)),
+            Span::styled("Directory: ", Style::default().fg(theme.secondary)),
             Span::raw(app.current_directory().display().to_string()),
         ]),
         Line::from(vec![
-            Span::styled("Mode: ", Style::default().fg(Colors::SECONDARY)),
+            Span::styled("Mode: ", Style::default().fg(theme.secondary)),
             Span::raw(if app.is_recursive() { "Recursive search" } else { "Current directory only" }),
         ]),
         Line::from(""),
@@ -448,22 +808,22 @@ pub fn draw_loading_screen(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Colors::MUTED))
+                .border_style(Style::default().fg(theme.muted))
                 .title("Status")
         );
 
     // Help text during loading
     let help_lines = vec![
         Line::from(vec![
-            Span::styled("Please wait while scanning directories...", Style::default().fg(Colors::MUTED)),
+            Span::styled("Please wait while scanning directories...", Style::default().fg(theme.muted)),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Press ", Style::default().fg(Colors::MUTED)),
-            Span::styled("q", Style::default().fg(Colors::WARNING).add_modifier(Modifier::BOLD)),
-            Span::styled(" or ", Style::default().fg(Colors::MUTED)),
-            Span::styled("Esc", Style::default().fg(Colors::WARNING).add_modifier(Modifier::BOLD)),
-            Span::styled(" to quit", Style::default().fg(Colors::MUTED)),
+            Span::styled("Press ", Style::default().fg(theme.muted)),
+            Span::styled("Esc", Style::default().fg(theme.warning).add_modifier(Modifier::BOLD)),
+            Span::styled(" to stop and list what was found so far, ", Style::default().fg(theme.muted)),
+            Span::styled("q", Style::default().fg(theme.warning).add_modifier(Modifier::BOLD)),
+            Span::styled(" to quit", Style::default().fg(theme.muted)),
         ]),
     ];
 
@@ -478,13 +838,15 @@ pub fn draw_loading_screen(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
 
 /// Draw the confirmation dialog
 pub fn draw_confirmation_dialog(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
+    let theme = app.theme();
     let selected_count = app.selected_venvs().len();
     let selected_venvs = app.get_selected_venvs();
-    let total_size: u64 = selected_venvs.iter().map(|v| v.size_bytes()).sum();
+    let total_size: ByteSize = selected_venvs.iter().map(|v| v.size()).sum();
+    let with_user_data = app.selected_with_user_data();
 
     // Calculate dialog size
     let dialog_width = 60;
-    let dialog_height = 12;
+    let dialog_height = if with_user_data.is_empty() { 14 } else { 16 };
     let x = (area.width.saturating_sub(dialog_width)) / 2;
     let y = (area.height.saturating_sub(dialog_height)) / 2;
     let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
@@ -492,33 +854,268 @@ pub fn draw_confirmation_dialog(f: &mut ratatui::Frame, area: Rect, app: &TuiApp
     // Clear the area behind the dialog
     f.render_widget(Clear, dialog_area);
 
+    let mut text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("⚠️  Confirm Deletion", Style::default().fg(theme.warning).add_modifier(Modifier::BOLD)),
+        ]),
+        Line::from(""),
+        if app.deletes_to_trash() {
+            Line::from(vec![
+                Span::raw("You are about to move "),
+                Span::styled(format!("{}", selected_count), Style::default().fg(theme.warning).add_modifier(Modifier::BOLD)),
+                Span::raw(" .venv directories to the Trash."),
+            ])
+        } else {
+            Line::from(vec![
+                Span::raw("You are about to permanently delete "),
+                Span::styled(format!("{}", selected_count), Style::default().fg(theme.error).add_modifier(Modifier::BOLD)),
+                Span::raw(" .venv directories."),
+            ])
+        },
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("Total size to be freed: "),
+            Span::styled(total_size.to_string(), Style::default().fg(theme.warning).add_modifier(Modifier::BOLD)),
+            Span::styled(
+                format!(" ({})", deletion_eta::describe_file_count(app.deletion_file_count())),
+                Style::default().fg(theme.muted),
+            ),
+        ]),
+        Line::from(""),
+        if app.deletes_to_trash() {
+            Line::from(vec![
+                Span::styled("♻️  Recoverable: they can be restored from the Trash", Style::default().fg(theme.success).add_modifier(Modifier::BOLD)),
+            ])
+        } else {
+            Line::from(vec![
+                Span::styled("⚠️  This action cannot be undone!", Style::default().fg(theme.error).add_modifier(Modifier::BOLD)),
+            ])
+        },
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("The list will automatically refresh after deletion."),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Press 'y' to confirm deletion or 'n'/Esc to cancel", Style::default().fg(theme.muted)),
+        ]),
+        Line::from(vec![
+            Span::styled("Press 'p' to peek inside before deciding", Style::default().fg(theme.muted)),
+        ]),
+        Line::from(vec![
+            if app.is_trash_locked() {
+                // Greyed out: the policy decides, so there is nothing to switch
+                Span::styled(
+                    "🔒 The policy requires the Trash",
+                    Style::default().fg(theme.muted).add_modifier(Modifier::DIM),
+                )
+            } else {
+                Span::styled(
+                    if app.deletes_to_trash() { "Press 't' to delete permanently instead" } else { "Press 't' to move to the Trash instead" },
+                    Style::default().fg(theme.muted),
+                )
+            },
+        ]),
+    ];
+
+    if !with_user_data.is_empty() {
+        let user_data_bytes: u64 = with_user_data.iter().map(|v| v.user_data_bytes()).sum();
+        let prompt = if app.needs_user_data_confirmation() {
+            "Press 'y' once to accept losing it, then again to delete"
+        } else {
+            "User data loss accepted: press 'y' again to delete"
+        };
+        text.insert(8, Line::from(vec![
+            Span::styled(
+                format!("{}{} hold user data ({}) that cannot be reinstalled", app.glyphs().user_data, with_user_data.len(), ByteSize::new(user_data_bytes)),
+                Style::default().fg(theme.error).add_modifier(Modifier::BOLD),
+            ),
+        ]));
+        text.insert(9, Line::from(vec![
+            Span::styled(prompt, Style::default().fg(theme.warning)),
+        ]));
+    }
+
+    let paragraph = Paragraph::new(text)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.error))
+                .border_type(BorderType::Double)
+                .title("Confirm Deletion")
+        )
+        .alignment(Alignment::Center);
+
+    f.render_widget(paragraph, dialog_area);
+}
+
+/// Number of top-level entries listed in the peek dialog
+const PEEK_ENTRY_ROWS: usize = 8;
+
+/// Draw the contents of a selected .venv before its deletion is confirmed
+pub fn draw_peek_dialog(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
+    let theme = app.theme();
+    let Some(peek) = app.peek() else {
+        return;
+    };
+    let (position, count) = app.peek_position();
+
+    let dialog_width = area.width.saturating_sub(4).min(76);
+    let dialog_height = area.height.saturating_sub(2).min(30);
+    let x = (area.width.saturating_sub(dialog_width)) / 2;
+    let y = (area.height.saturating_sub(dialog_height)) / 2;
+    let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+    f.render_widget(Clear, dialog_area);
+
+    let name_width = (dialog_width as usize).saturating_sub(20);
+    let entry_line = |name: String, size: u64, style: Style| {
+        Line::from(vec![
+            Span::styled(format!("  {:<width$}", FileUtils::truncate_start(&name, name_width), width = name_width), style),
+            Span::styled(format!("{:>10}", ByteSize::new(size).to_string()), Style::default().fg(theme.secondary)),
+        ])
+    };
+
+    let mut text = vec![
+        Line::from(vec![
+            Span::styled(
+                FileUtils::format_path_for_display(&peek.path, name_width + 8),
+                Style::default().fg(theme.primary).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!("  ({} of {})", position, count), Style::default().fg(theme.muted)),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Contents:", Style::default().fg(theme.secondary).add_modifier(Modifier::BOLD)),
+        ]),
+    ];
+
+    for entry in peek.entries.iter().take(PEEK_ENTRY_ROWS) {
+        // Loose files at the top of a .venv are the usual sign of stored data
+        let style = if entry.is_dir || entry.name == "pyvenv.cfg" {
+            Style::default()
+        } else {
+            Style::default().fg(theme.warning)
+        };
+        text.push(entry_line(entry.display_name(), entry.size_bytes, style));
+    }
+    if peek.entries.len() > PEEK_ENTRY_ROWS {
+        text.push(Line::from(Span::styled(
+            format!("  … and {} more", peek.entries.len() - PEEK_ENTRY_ROWS),
+            Style::default().fg(theme.muted),
+        )));
+    }
+
+    text.push(Line::from(""));
+    text.push(Line::from(vec![
+        Span::styled("Largest packages:", Style::default().fg(theme.secondary).add_modifier(Modifier::BOLD)),
+    ]));
+    if peek.packages.is_empty() {
+        text.push(Line::from(Span::styled("  No site-packages found", Style::default().fg(theme.muted))));
+    }
+    for package in &peek.packages {
+        text.push(entry_line(package.display_name(), package.size_bytes, Style::default()));
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .margin(1)
+        .split(dialog_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.warning))
+        .border_type(BorderType::Double)
+        .title("Peek Before Deleting");
+    f.render_widget(block, dialog_area);
+    f.render_widget(Paragraph::new(text), chunks[0]);
+    f.render_widget(
+        Paragraph::new("y:Delete ←/→:Other selected .venv p/Esc:Back")
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center),
+        chunks[1],
+    );
+}
+
+/// Draw the confirmation dialog for deleting an archive
+pub fn draw_archive_deletion_dialog(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
+    let theme = app.theme();
+    let Some(archived) = app.selected_archived() else {
+        return;
+    };
+
+    let dialog_width = 60;
+    let dialog_height = 10;
+    let x = (area.width.saturating_sub(dialog_width)) / 2;
+    let y = (area.height.saturating_sub(dialog_height)) / 2;
+    let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+    f.render_widget(Clear, dialog_area);
+
     let text = vec![
         Line::from(""),
         Line::from(vec![
-            Span::styled("⚠️  Confirm Deletion", Style::default().fg(Colors::WARNING).add_modifier(Modifier::BOLD)),
+            Span::styled("⚠️  Delete Archive", Style::default().fg(theme.warning).add_modifier(Modifier::BOLD)),
         ]),
         Line::from(""),
+        Line::from(format_path_for_display(&archived.location(), 54)),
         Line::from(vec![
-            Span::raw("You are about to permanently delete "),
-            Span::styled(format!("{}", selected_count), Style::default().fg(Colors::ERROR).add_modifier(Modifier::BOLD)),
-            Span::raw(" .venv directories."),
+            Span::raw("Archive size: "),
+            Span::styled(
+                ByteSize::new(archived.compressed_size().unwrap_or(0)).to_string(),
+                Style::default().fg(theme.warning).add_modifier(Modifier::BOLD),
+            ),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::raw("Total size to be freed: "),
-            Span::styled(format_size(total_size), Style::default().fg(Colors::WARNING).add_modifier(Modifier::BOLD)),
+            Span::styled("The .venv can no longer be restored afterwards!", Style::default().fg(theme.error)),
         ]),
+        Line::from(vec![
+            Span::styled("Press 'y' to confirm or 'n'/Esc to cancel", Style::default().fg(theme.muted)),
+        ]),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.error))
+                .border_type(BorderType::Double)
+                .title("Confirm Archive Deletion")
+        )
+        .alignment(Alignment::Center);
+
+    f.render_widget(paragraph, dialog_area);
+}
+
+/// Draw the prompt for the file to export the shown list to
+pub fn draw_export_prompt(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
+    let theme = app.theme();
+
+    let dialog_width = 70.min(area.width);
+    let dialog_height = 8;
+    let x = (area.width.saturating_sub(dialog_width)) / 2;
+    let y = (area.height.saturating_sub(dialog_height)) / 2;
+    let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+    f.render_widget(Clear, dialog_area);
+
+    let text = vec![
         Line::from(""),
         Line::from(vec![
-            Span::styled("⚠️  This action cannot be undone!", Style::default().fg(Colors::ERROR).add_modifier(Modifier::BOLD)),
+            Span::raw("Export "),
+            Span::styled(format!("{}", app.shown_venvs().len()), Style::default().fg(theme.primary).add_modifier(Modifier::BOLD)),
+            Span::raw(" shown .venv directories to:"),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::raw("The list will automatically refresh after deletion."),
+            Span::styled(format!("{}▏", app.export_path()), Style::default().fg(theme.highlight).add_modifier(Modifier::BOLD)),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Press 'y' to confirm deletion or 'n'/Esc to cancel", Style::default().fg(Colors::MUTED)),
+            Span::styled("End the name in .csv, .json, .md, .html or .txt; Enter to export, Esc to cancel", Style::default().fg(theme.muted)),
         ]),
     ];
 
@@ -526,9 +1123,9 @@ pub fn draw_confirmation_dialog(f: &mut ratatui::Frame, area: Rect, app: &TuiApp
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Colors::ERROR))
+                .border_style(Style::default().fg(theme.primary))
                 .border_type(BorderType::Double)
-                .title("Confirm Deletion")
+                .title("Export List")
         )
         .alignment(Alignment::Center);
 
@@ -537,6 +1134,7 @@ pub fn draw_confirmation_dialog(f: &mut ratatui::Frame, area: Rect, app: &TuiApp
 
 /// Draw the deletion progress dialog
 pub fn draw_deletion_progress(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
+    let theme = app.theme();
     let progress = app.deletion_progress();
 
     // Calculate dialog size
@@ -559,45 +1157,48 @@ pub fn draw_deletion_progress(f: &mut ratatui::Frame, area: Rect, app: &TuiApp)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Colors::PRIMARY))
+                .border_style(Style::default().fg(theme.primary))
                 .title("Deleting...")
         )
-        .gauge_style(Style::default().fg(Colors::SUCCESS))
+        .gauge_style(Style::default().fg(theme.success))
         .ratio(progress_ratio)
-        .label(format!("{}/{}", progress.completed, progress.total));
+        .label(format!("{}/{} · {}", progress.completed, progress.total, deletion_eta::format_eta(progress.eta)));
 
     f.render_widget(gauge, dialog_area);
 }
 
 /// Draw the error screen
 pub fn draw_error_screen(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
+    let theme = app.theme();
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Percentage(30),
-            Constraint::Length(10),
+            Constraint::Percentage(25),
+            Constraint::Length(14),
             Constraint::Percentage(60),
         ])
         .split(area);
 
-    let error_text = vec![
+    let mut error_text = vec![
         Line::from(""),
         Line::from(vec![
-            Span::styled("❌ Error", Style::default().fg(Colors::ERROR).add_modifier(Modifier::BOLD)),
+            Span::styled("❌ Error", Style::default().fg(theme.error).add_modifier(Modifier::BOLD)),
         ]),
         Line::from(""),
-        Line::from(app.error_message()),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Press Enter to continue or 'q' to quit", Style::default().fg(Colors::MUTED)),
-        ]),
     ];
+    // Errors may carry guidance on following lines (e.g. Full Disk Access steps)
+    error_text.extend(app.error_message().lines().map(|line| Line::from(line.to_string())));
+    error_text.push(Line::from(""));
+    error_text.push(Line::from(vec![
+        Span::styled("Press Enter to continue or 'q' to quit", Style::default().fg(theme.muted)),
+    ]));
 
     let paragraph = Paragraph::new(error_text)
+        .wrap(Wrap { trim: true })
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Colors::ERROR))
+                .border_style(Style::default().fg(theme.error))
                 .title("Error")
         )
         .alignment(Alignment::Center);
@@ -606,59 +1207,129 @@ pub fn draw_error_screen(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
 }
 
 /// Draw the help screen
-pub fn draw_help_screen(f: &mut ratatui::Frame, area: Rect) {
-    let help_text = vec![
-        Line::from(vec![
-            Span::styled("VenvCleaner TUI Help", Style::default().fg(Colors::PRIMARY).add_modifier(Modifier::BOLD)),
-        ]),
-        Line::from(""),
+pub fn draw_help_screen(f: &mut ratatui::Frame, area: Rect, theme: &Theme, glyphs: &Glyphs) {
+    let heading = |title: &str| Line::from(vec![
+        Span::styled(format!("{}:", title), Style::default().fg(theme.secondary).add_modifier(Modifier::BOLD)),
+    ]);
+
+    let mut help_text = vec![
         Line::from(vec![
-            Span::styled("Navigation:", Style::default().fg(Colors::SECONDARY).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("{} TUI Help", help::ABOUT.name), Style::default().fg(theme.primary).add_modifier(Modifier::BOLD)),
         ]),
-        Line::from("  ↑/↓       - Move selection up/down"),
-        Line::from("  Home/End  - Go to first/last item"),
-        Line::from("  PgUp/PgDn - Page up/down"),
         Line::from(""),
-        Line::from(vec![
-            Span::styled("Selection:", Style::default().fg(Colors::SECONDARY).add_modifier(Modifier::BOLD)),
-        ]),
-        Line::from("  Space/Enter - Toggle selection"),
-        Line::from("  Ctrl+A      - Select all"),
-        Line::from("  Ctrl+D      - Deselect all"),
+    ];
+    for section in help::TUI_SECTIONS {
+        help_text.push(heading(section.title));
+        let width = section.entries.iter().map(|entry| entry.keys.chars().count()).max().unwrap_or(0);
+        for entry in section.entries {
+            help_text.push(Line::from(format!("  {:<width$} - {}", entry.keys, entry.description, width = width)));
+        }
+        help_text.push(Line::from(""));
+    }
+
+    help_text.push(heading("Status Icons"));
+    let icons = std::iter::once(&help::TUI_SELECTED_ICON).chain(help::STATUS_ICONS);
+    help_text.extend(icons.map(|entry| Line::from(format!("  {} - {}", glyphs.icon(entry.keys), entry.description))));
+    help_text.extend([
         Line::from(""),
         Line::from(vec![
-            Span::styled("Actions:", Style::default().fg(Colors::SECONDARY).add_modifier(Modifier::BOLD)),
+            Span::styled("Press any key to return...", Style::default().fg(theme.muted)),
         ]),
-        Line::from("  x/Del    - Delete selected items"),
-        Line::from("  s        - Cycle sort order"),
-        Line::from("  o        - Open folder in file manager"),
-        Line::from("  r        - Refresh list"),
-        Line::from(""),
+    ]);
+
+    let paragraph = Paragraph::new(help_text)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.primary))
+                .title("Help")
+        )
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(paragraph, area);
+}
+
+/// Draw the recommendations screen
+pub fn draw_recommendations_screen(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
+    let theme = app.theme();
+    let mut text = vec![
         Line::from(vec![
-            Span::styled("Other:", Style::default().fg(Colors::SECONDARY).add_modifier(Modifier::BOLD)),
+            Span::styled("Recommendations", Style::default().fg(theme.primary).add_modifier(Modifier::BOLD)),
         ]),
-        Line::from("  h/F1     - Show this help"),
-        Line::from("  q/Esc    - Quit application"),
         Line::from(""),
+    ];
+    if app.recommendations().is_empty() {
+        text.push(Line::from("✅ Nothing to recommend: no broken, old, end-of-life or duplicate .venv directories"));
+        text.push(Line::from(""));
+    }
+    for recommendation in app.recommendations() {
+        text.push(Line::from(vec![
+            Span::styled(
+                format!("{} {}", recommendation.kind().icon(), recommendation.title()),
+                Style::default().fg(theme.secondary).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!(" ({})", ByteSize::new(recommendation.bytes())), Style::default().fg(theme.warning)),
+        ]));
+        text.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled(recommendation.command().to_string(), Style::default().fg(theme.success)),
+        ]));
+        if let Some(link) = recommendation.kind().doc_link() {
+            text.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(link, Style::default().fg(theme.muted).add_modifier(Modifier::UNDERLINED)),
+            ]));
+        }
+        text.push(Line::from(""));
+    }
+    text.push(Line::from(vec![
+        Span::styled("Press any key to return...", Style::default().fg(theme.muted)),
+    ]));
+
+    let paragraph = Paragraph::new(text)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.primary))
+                .title("Recommendations")
+        )
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(paragraph, area);
+}
+
+/// Draw the notifications raised in this session, newest first
+pub fn draw_notifications_screen(f: &mut ratatui::Frame, area: Rect, app: &TuiApp) {
+    let theme = app.theme();
+    let mut text = vec![
         Line::from(vec![
-            Span::styled("Status Icons:", Style::default().fg(Colors::SECONDARY).add_modifier(Modifier::BOLD)),
+            Span::styled("Notifications", Style::default().fg(theme.primary).add_modifier(Modifier::BOLD)),
         ]),
-        Line::from("  🟢 - Recently used (<30 days)"),
-        Line::from("  🟡 - Moderately used (30-90 days)"),
-        Line::from("  🔴 - Old (>90 days)"),
-        Line::from("  ✓  - Selected for deletion"),
         Line::from(""),
-        Line::from(vec![
-            Span::styled("Press any key to return...", Style::default().fg(Colors::MUTED)),
-        ]),
     ];
+    for toast in app.toasts().history() {
+        let style = severity_style(theme, toast.severity());
+        text.push(Line::from(vec![
+            Span::styled(timestamps::format_time(toast.raised_at()), Style::default().fg(theme.muted)),
+            Span::raw(" "),
+            Span::styled(format!("{:<7}", toast.severity().label()), style.add_modifier(Modifier::BOLD)),
+            Span::raw(" "),
+            Span::styled(toast.message().to_string(), style),
+        ]));
+    }
+    text.push(Line::from(""));
+    text.push(Line::from(vec![
+        Span::styled("Press any key to return...", Style::default().fg(theme.muted)),
+    ]));
 
-    let paragraph = Paragraph::new(help_text)
+    let paragraph = Paragraph::new(text)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Colors::PRIMARY))
-                .title("Help")
+                .border_style(Style::default().fg(theme.primary))
+                .title("Notifications")
         )
         .alignment(Alignment::Left)
         .wrap(Wrap { trim: true });
@@ -666,30 +1337,18 @@ pub fn draw_help_screen(f: &mut ratatui::Frame, area: Rect) {
     f.render_widget(paragraph, area);
 }
 
-/// Format a file path for display, truncating if necessary
-fn format_path_for_display(path: &str, max_length: usize) -> String {
-    if path.len() <= max_length {
-        path.to_string()
-    } else {
-        format!("...{}", &path[path.len().saturating_sub(max_length - 3)..])
+/// Get the text style of a notification of a severity
+fn severity_style(theme: &Theme, severity: Severity) -> Style {
+    match severity {
+        Severity::Info => Style::default(),
+        Severity::Warning => Style::default().fg(theme.warning),
+        Severity::Error => Style::default().fg(theme.error),
     }
 }
 
-/// Format a size in bytes to a human-readable string
-fn format_size(bytes: u64) -> String {
-    const KB: u64 = 1024;
-    const MB: u64 = KB * 1024;
-    const GB: u64 = MB * 1024;
-
-    if bytes >= GB {
-        format!("{:.2} GB", bytes as f64 / GB as f64)
-    } else if bytes >= MB {
-        format!("{:.2} MB", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.2} KB", bytes as f64 / KB as f64)
-    } else {
-        format!("{} bytes", bytes)
-    }
+/// Format a file path for display, truncating if necessary
+fn format_path_for_display(path: &str, max_length: usize) -> String {
+    FileUtils::truncate_start(path, max_length)
 }
 
 #[cfg(test)]
@@ -700,13 +1359,6 @@ mod tests {
     fn test_format_path_for_display() {
         assert_eq!(format_path_for_display("short", 10), "short");
         assert_eq!(format_path_for_display("very/long/path/here", 10), "...th/here");
-    }
-
-    #[test]
-    fn test_format_size() {
-        assert_eq!(format_size(512), "512 bytes");
-        assert_eq!(format_size(1536), "1.50 KB");
-        assert_eq!(format_size(2 * 1024 * 1024), "2.00 MB");
-        assert_eq!(format_size(1024 * 1024 * 1024), "1.00 GB");
+        assert_eq!(format_path_for_display("/home/日本語のプロジェクト", 9), "...プロジェクト");
     }
 }
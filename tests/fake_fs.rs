@@ -0,0 +1,192 @@
+//! Integration tests of scanning and deletion error paths against an in-memory file system
+//!
+//! Denied directories and removals failing halfway cannot be reproduced
+//! reliably with temp directories (root ignores permissions), so these run
+//! the core and each frontend on a `MemoryFs` with injected failures.
+
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use venv_cleaner::core::{BatchOptions, FsBackend, FsOp, MemoryFs, VenvCleaner, VenvCleanerError};
+
+/// Root of the in-memory trees; nothing exists there on disk
+const ROOT: &str = "/memory/work";
+
+/// Create an in-memory tree with a .venv in each of the given projects
+fn tree(projects: &[(&str, u64)]) -> Arc<MemoryFs> {
+    let fs = Arc::new(MemoryFs::new());
+    fs.add_dir(ROOT);
+    for (project, bytes) in projects {
+        fs.add_venv(venv(project), *bytes);
+    }
+    fs
+}
+
+/// Path of a project's .venv in the in-memory tree
+fn venv(project: &str) -> PathBuf {
+    Path::new(ROOT).join(project).join(".venv")
+}
+
+/// Create a cleaner scanning the in-memory tree recursively
+fn cleaner(fs: &Arc<MemoryFs>) -> VenvCleaner {
+    VenvCleaner::new(PathBuf::from(ROOT), true, true, false, 0).with_fs_backend(fs.clone())
+}
+
+mod core_api {
+    use super::*;
+
+    #[test]
+    fn test_scan_sizes_memory_venvs() {
+        let fs = tree(&[("api", 4096), ("web", 1024)]);
+        fs.add_dir(Path::new(ROOT).join("notes").join(".venv"));
+
+        let cleaner = cleaner(&fs);
+        let mut venvs = cleaner.find_venv_directories().unwrap();
+        venvs.sort_by(|a, b| a.path().cmp(b.path()));
+        let found: Vec<(PathBuf, u64)> = venvs.iter().map(|v| (v.path().to_path_buf(), v.size().bytes())).collect();
+        assert_eq!(found, vec![(venv("api"), 4096), (venv("web"), 1024)]);
+        assert_eq!((venvs[0].file_count(), venvs[0].dir_count()), (Some(2), Some(2)));
+        assert_eq!(cleaner.last_invalid_venvs(), vec![Path::new(ROOT).join("notes").join(".venv")]);
+    }
+
+    #[test]
+    fn test_scan_lists_denied_directories() {
+        let fs = tree(&[("api", 100), ("secret", 100)]);
+        fs.fail(Path::new(ROOT).join("secret"), FsOp::ReadDir, ErrorKind::PermissionDenied);
+
+        let cleaner = cleaner(&fs);
+        let venvs = cleaner.find_venv_directories().unwrap();
+        assert_eq!(venvs.len(), 1);
+        assert_eq!(venvs[0].path(), venv("api"));
+        assert_eq!(cleaner.last_unreadable_directories(), vec![Path::new(ROOT).join("secret")]);
+    }
+
+    #[test]
+    fn test_scan_of_denied_root_fails_with_partial_roots() {
+        let fs = tree(&[("api", 100)]);
+        fs.fail(ROOT, FsOp::ReadDir, ErrorKind::PermissionDenied);
+
+        let cleaner = cleaner(&fs).with_partial_roots(true);
+        assert!(matches!(cleaner.find_venv_directories(), Err(VenvCleanerError::PathError { .. })));
+        assert_eq!(cleaner.last_failed_roots().len(), 1);
+    }
+
+    #[test]
+    fn test_delete_removes_venv() {
+        let fs = tree(&[("api", 100)]);
+        let cleaner = cleaner(&fs);
+        let venvs = cleaner.find_venv_directories().unwrap();
+
+        cleaner.delete_venv_directory(&venvs[0]).unwrap();
+        assert!(!fs.contains(venv("api")));
+        assert!(fs.contains(Path::new(ROOT).join("api")));
+    }
+
+    #[test]
+    fn test_delete_denied_venv() {
+        let fs = tree(&[("api", 100), ("locked", 100)]);
+        let cleaner = cleaner(&fs);
+        let venvs = cleaner.find_venv_directories().unwrap();
+        let locked = venvs.iter().find(|v| v.path() == venv("locked")).unwrap();
+
+        fs.fail(venv("locked"), FsOp::ReadDir, ErrorKind::PermissionDenied);
+        let error = cleaner.delete_venv_directory(locked).unwrap_err();
+        assert!(error.is_permission_denied());
+        assert!(fs.contains(venv("locked").join("pyvenv.cfg")));
+
+        // The rename marking it for deletion can be refused as well
+        let fs = tree(&[("locked", 100)]);
+        let cleaner = self::cleaner(&fs);
+        let venvs = cleaner.find_venv_directories().unwrap();
+        fs.fail(venv("locked"), FsOp::Rename, ErrorKind::PermissionDenied);
+        assert!(cleaner.delete_venv_directory(&venvs[0]).unwrap_err().is_permission_denied());
+        assert!(fs.contains(venv("locked")));
+    }
+
+    #[test]
+    fn test_delete_failing_halfway_leaves_marked_directory() {
+        let fs = tree(&[("api", 100)]);
+        let cleaner = cleaner(&fs);
+        let venvs = cleaner.find_venv_directories().unwrap();
+
+        fs.add_file(venv("api").join("lib").join("busy.so"), 10);
+        fs.fail(venv("api").join("lib").join("busy.so"), FsOp::Remove, ErrorKind::PermissionDenied);
+        let error = cleaner.delete_venv_directory(&venvs[0]).unwrap_err();
+        assert!(error.is_permission_denied());
+
+        // The .venv is gone, and what is left is clearly marked as a deletion in progress
+        assert!(!fs.contains(venv("api")));
+        let marked = fs
+            .read_dir(&Path::new(ROOT).join("api"))
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.path)
+            .find(|path| path.file_name().is_some_and(|name| name.to_string_lossy().starts_with(".venv.deleting-")))
+            .unwrap();
+        assert!(fs.contains(marked.join("lib").join("busy.so")));
+        assert!(!fs.contains(marked.join("pyvenv.cfg")));
+        assert_eq!(error.marked_path(), Some(marked.as_path()));
+    }
+
+    #[test]
+    fn test_background_removal_reports_failures_when_finished() {
+        let fs = tree(&[("api", 100), ("locked", 100)]);
+        let cleaner = cleaner(&fs).with_background_removal(true);
+        let venvs = cleaner.find_venv_directories().unwrap();
+
+        fs.add_file(venv("locked").join("lib").join("busy.so"), 10);
+        fs.fail(venv("locked").join("lib").join("busy.so"), FsOp::Remove, ErrorKind::PermissionDenied);
+        for venv_info in &venvs {
+            cleaner.delete_venv_directory(venv_info).unwrap();
+        }
+
+        let failed = cleaner.finish_background_removals();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].0, venv("locked"));
+        assert!(failed[0].1.is_permission_denied());
+        assert!(fs.contains(failed[0].1.marked_path().unwrap().join("lib").join("busy.so")));
+        assert!(fs.read_dir(&Path::new(ROOT).join("api")).unwrap().is_empty());
+        assert!(cleaner.finish_background_removals().is_empty());
+    }
+
+    #[test]
+    fn test_delete_many_counts_each_outcome() {
+        let fs = tree(&[("api", 100), ("gone", 200), ("locked", 300)]);
+        let cleaner = cleaner(&fs);
+        let venvs = cleaner.find_venv_directories().unwrap();
+
+        fs.remove_dir_all(&venv("gone")).unwrap();
+        fs.fail(venv("locked"), FsOp::Rename, ErrorKind::PermissionDenied);
+        let mut done = 0;
+        let batch = cleaner.delete_many(&venvs, &BatchOptions::new(), |_| done += 1);
+        assert_eq!(done, 3);
+        assert_eq!((batch.deleted(), batch.vanished(), batch.failed()), (1, 1, 1));
+        assert!(fs.contains(venv("locked")));
+        assert!(!fs.contains(venv("api")));
+    }
+}
+
+#[cfg(feature = "tui")]
+mod tui {
+    use super::*;
+    use venv_cleaner::tui::{Severity, TuiApp};
+
+    #[test]
+    fn test_tui_reports_failed_deletions() {
+        let fs = tree(&[("api", 100), ("locked", 300)]);
+        let cleaner = cleaner(&fs);
+        let mut app = TuiApp::new();
+        app.set_venvs(cleaner.find_venv_directories().unwrap());
+        app.select_all();
+
+        fs.fail(venv("locked"), FsOp::Rename, ErrorKind::PermissionDenied);
+        let batch = cleaner.delete_many(&app.get_selected_venvs(), &BatchOptions::new(), |_| {});
+        app.handle_deletion_results(&batch);
+
+        let progress = app.deletion_progress();
+        assert_eq!((progress.successful, progress.failed), (1, 1));
+        assert_eq!(app.status_severity(), Severity::Error);
+        assert!(app.selected_venvs().is_empty());
+        assert!(fs.contains(venv("locked")));
+    }
+}
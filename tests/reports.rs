@@ -0,0 +1,93 @@
+//! Golden-file tests for the report formats
+//!
+//! Each format renders the same rows and scan metadata and is compared
+//! byte for byte with `tests/golden/venvs.<extension>`. A failure means
+//! scripts parsing the reports may break; if the change is intended, rerun
+//! with `UPDATE_GOLDEN=1` and review the diff of the golden files.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, TimeZone, Utc};
+use venv_cleaner::core::export::{self, ExportFormat};
+use venv_cleaner::core::{timestamps, ByteSize, PythonVersion, ScanMetadata, VenvInfo};
+
+/// The scan every golden file was rendered from
+fn metadata() -> ScanMetadata {
+    let started: DateTime<Utc> = Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap();
+    ScanMetadata {
+        version: "1.2.3".to_string(),
+        host: "build-01".to_string(),
+        roots: vec![PathBuf::from("/home/dev"), PathBuf::from("/srv/shared")],
+        recursive: true,
+        started,
+        finished: started + chrono::Duration::seconds(83),
+    }
+}
+
+/// Rows covering optional fields and characters each format has to escape
+fn venvs() -> Vec<VenvInfo> {
+    let day = |month, day| Utc.with_ymd_and_hms(2025, month, day, 9, 30, 0).unwrap();
+    vec![
+        VenvInfo::new(PathBuf::from("/home/dev/ml-pipeline/.venv"), ByteSize::new(1_610_612_736), day(1, 2), day(1, 20))
+            .with_python_version(PythonVersion::parse("3.11.9"))
+            .with_item_counts(Some((48_211, 3_904))),
+        VenvInfo::new(PathBuf::from("/home/dev/web app/.venv"), ByteSize::new(268_435_456), day(3, 14), day(5, 30))
+            .with_python_version(PythonVersion::parse("3.12.4")),
+        VenvInfo::new(PathBuf::from("/home/dev/a,\"b\"|<c>&d/.venv"), ByteSize::new(2048), day(5, 1), day(5, 1)),
+    ]
+}
+
+fn check(format: ExportFormat) {
+    timestamps::set_show_utc(true);
+    let venvs = venvs();
+    let rows: Vec<&VenvInfo> = venvs.iter().collect();
+    let rendered = export::render(&rows, format, &metadata());
+
+    let golden = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden").join(format!("venvs.{}", format.extension()));
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&golden, &rendered).unwrap();
+    }
+    let expected = fs::read_to_string(&golden).unwrap_or_else(|e| panic!("{}: {}", golden.display(), e));
+    assert_eq!(rendered, expected, "{} report differs from {}", format.name(), golden.display());
+}
+
+#[test]
+fn test_table_report_matches_golden_file() {
+    check(ExportFormat::Table);
+}
+
+#[test]
+fn test_json_report_matches_golden_file() {
+    check(ExportFormat::Json);
+}
+
+#[test]
+fn test_csv_report_matches_golden_file() {
+    check(ExportFormat::Csv);
+}
+
+#[test]
+fn test_markdown_report_matches_golden_file() {
+    check(ExportFormat::Markdown);
+}
+
+#[test]
+fn test_html_report_matches_golden_file() {
+    check(ExportFormat::Html);
+}
+
+#[test]
+fn test_streamed_report_matches_rendered_report() {
+    timestamps::set_show_utc(true);
+    let venvs = venvs();
+    let rows: Vec<&VenvInfo> = venvs.iter().collect();
+    for format in [ExportFormat::Json, ExportFormat::Csv, ExportFormat::Markdown, ExportFormat::Html] {
+        let reporter = format.reporter();
+        let mut streamed = reporter.header();
+        for (index, venv) in venvs.iter().enumerate() {
+            streamed.push_str(&reporter.row(index, venv, metadata().started));
+        }
+        streamed.push_str(&reporter.footer(venvs.len(), &metadata()));
+        assert_eq!(streamed, export::render(&rows, format, &metadata()), "{}", format.name());
+    }
+}
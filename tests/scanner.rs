@@ -0,0 +1,58 @@
+//! Integration tests for the .venv scanner using synthetic fixture trees
+
+use venv_cleaner::core::{PythonVersion, VenvCleaner, VenvCleanerError, VenvStats};
+use venv_cleaner::fixtures::{FixtureGenerator, FixtureSpec};
+use tempfile::TempDir;
+
+#[test]
+fn test_recursive_scan_finds_all_fixture_venvs() {
+    let temp_dir = TempDir::new().unwrap();
+    let spec = FixtureSpec::new(12).with_venv_size(8 * 1024);
+    FixtureGenerator::generate(temp_dir.path(), &spec).unwrap();
+
+    let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, false, false, 0);
+    let venvs = cleaner.find_venv_directories().unwrap();
+
+    assert_eq!(venvs.len(), 12);
+    assert!(venvs.iter().all(|v| v.size().bytes() == 8 * 1024));
+    assert!(venvs.iter().all(|v| v.python_version().is_some()));
+    assert_eq!(VenvStats::from_venvs(&venvs).total_bytes(), 12 * 8 * 1024);
+}
+
+#[test]
+fn test_non_recursive_scan_ignores_nested_fixtures() {
+    let temp_dir = TempDir::new().unwrap();
+    FixtureGenerator::generate(temp_dir.path(), &FixtureSpec::new(3)).unwrap();
+
+    let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), false, false, false, 0);
+    assert!(matches!(cleaner.find_venv_directories(), Err(VenvCleanerError::NoVenvFound)));
+}
+
+#[test]
+fn test_fixture_ages_are_reported() {
+    let temp_dir = TempDir::new().unwrap();
+    let spec = FixtureSpec::new(4).with_max_age_days(120);
+    FixtureGenerator::generate(temp_dir.path(), &spec).unwrap();
+
+    let cleaner = VenvCleaner::new(temp_dir.path().to_path_buf(), true, false, false, 0);
+    let venvs = cleaner.find_venv_directories().unwrap();
+
+    let old = venvs.iter().filter(|v| v.is_old()).count();
+    let recent = venvs.iter().filter(|v| v.is_recently_used()).count();
+    assert_eq!(old, 1); // only the 120 day old project
+    assert_eq!(recent, 1); // only the brand new project
+}
+
+#[test]
+fn test_delete_fixture_venv() {
+    let temp_dir = TempDir::new().unwrap();
+    let project = temp_dir.path().join("proj");
+    let venv = FixtureGenerator::create_venv(&project, "3.12.4", 4096).unwrap();
+
+    let cleaner = VenvCleaner::new(project, false, true, false, 0);
+    let venvs = cleaner.find_venv_directories().unwrap();
+    assert_eq!(venvs[0].python_version(), PythonVersion::parse("3.12.4"));
+
+    cleaner.delete_venv_directory(&venvs[0]).unwrap();
+    assert!(!venv.exists());
+}